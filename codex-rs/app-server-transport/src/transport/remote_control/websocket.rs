@@ -2046,6 +2046,7 @@ mod tests {
             agent_identity: None,
             personal_access_token: None,
             bedrock_api_key: None,
+            provider_tokens: None,
         }
     }
 
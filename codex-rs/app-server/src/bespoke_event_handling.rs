@@ -1517,7 +1517,11 @@ async fn handle_token_count_event(
     token_count_event: TokenCountEvent,
     outgoing: &ThreadScopedOutgoingMessageSender,
 ) {
-    let TokenCountEvent { info, rate_limits } = token_count_event;
+    let TokenCountEvent {
+        info,
+        rate_limits,
+        cost: _,
+    } = token_count_event;
     if let Some(token_usage) = info.map(ThreadTokenUsage::from) {
         let notification = ThreadTokenUsageUpdatedNotification {
             thread_id: conversation_id.to_string(),
@@ -3717,6 +3721,7 @@ mod tests {
             TokenCountEvent {
                 info: Some(info),
                 rate_limits: Some(rate_limits),
+                cost: None,
             },
             &outgoing,
         )
@@ -3774,6 +3779,7 @@ mod tests {
             TokenCountEvent {
                 info: None,
                 rate_limits: None,
+                cost: None,
             },
             &outgoing,
         )
@@ -676,6 +676,13 @@ mod thread_processor_behavior_tests {
             experimental_bearer_token: None,
             auth: None,
             aws: None,
+            oauth: None,
+            key_command: None,
+            key_file: None,
+            https_proxy: None,
+            no_proxy: None,
+            ca_bundle_path: None,
+            insecure_skip_verify: false,
             wire_api: WireApi::Responses,
             query_params: None,
             http_headers: None,
@@ -686,6 +693,8 @@ mod thread_processor_behavior_tests {
             websocket_connect_timeout_ms: None,
             requires_openai_auth: false,
             supports_websockets: true,
+            prompt_cache: None,
+            embedding_model: None,
         };
         let config_manager = ConfigManager::new(
             temp_dir.path().to_path_buf(),
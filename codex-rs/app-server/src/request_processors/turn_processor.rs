@@ -758,6 +758,7 @@ impl TurnRequestProcessor {
             active_permission_profile,
             windows_sandbox_level: None,
             model,
+            model_provider: None,
             effort,
             summary,
             service_tier,
@@ -131,6 +131,7 @@ async fn list_apps_returns_empty_with_api_key_auth() -> Result<()> {
             agent_identity: None,
             personal_access_token: None,
             bedrock_api_key: None,
+            provider_tokens: None,
         },
         AuthCredentialsStoreMode::File,
         AuthKeyringBackendKind::default(),
@@ -2260,6 +2260,7 @@ async fn thread_resume_token_usage_replay_can_belong_to_interrupted_turn() -> Re
                     model_context_window: Some(200_000),
                 }),
                 rate_limits: None,
+                cost: None,
             }))?,
         })
         .to_string(),
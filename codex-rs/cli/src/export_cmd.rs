@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use clap::Parser;
+use codex_core::config::Config;
+use codex_core::export::ExportFormat;
+use codex_core::export::load_conversation_export;
+use codex_utils_cli::CliConfigOverrides;
+
+/// Export a recorded conversation for sharing.
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex export")]
+pub struct ExportCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Thread id of the conversation to export.
+    id: String,
+
+    /// Output format: markdown, json, or html.
+    #[arg(long, default_value = "markdown")]
+    format: String,
+
+    /// Write the rendered export to this file instead of stdout.
+    #[arg(long = "output", short = 'o', value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+impl ExportCli {
+    pub async fn run(self) -> Result<()> {
+        let format = self
+            .format
+            .parse::<ExportFormat>()
+            .map_err(anyhow::Error::msg)?;
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+
+        let Some(export) = load_conversation_export(&config.codex_home, &self.id).await? else {
+            bail!("no conversation with id {}", self.id);
+        };
+        let rendered = export.render(format);
+        match self.output {
+            Some(path) => std::fs::write(&path, rendered)
+                .with_context(|| format!("failed to write {}", path.display()))?,
+            None => println!("{rendered}"),
+        }
+        Ok(())
+    }
+}
@@ -20,6 +20,7 @@ pub use login::run_login_with_api_key;
 pub use login::run_login_with_chatgpt;
 pub use login::run_login_with_device_code;
 pub use login::run_login_with_device_code_fallback_to_browser;
+pub use login::run_login_with_provider;
 pub use login::run_logout;
 
 #[derive(Debug, Default, Args)]
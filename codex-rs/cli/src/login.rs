@@ -19,6 +19,7 @@ use codex_login::login_with_api_key;
 use codex_login::logout_with_revoke;
 use codex_login::run_device_code_login;
 use codex_login::run_login_server;
+use codex_login::run_provider_device_login;
 use codex_protocol::auth::AuthMode;
 use codex_protocol::config_types::ForcedLoginMethod;
 use codex_utils_cli::CliConfigOverrides;
@@ -347,6 +348,46 @@ pub async fn run_login_with_device_code(
     }
 }
 
+/// Login to a third-party provider via its configured OAuth device flow.
+pub async fn run_login_with_provider(
+    cli_config_overrides: CliConfigOverrides,
+    provider_id: String,
+) -> ! {
+    let config = load_config_or_exit(cli_config_overrides).await;
+    let _login_log_guard = init_login_file_logging(&config);
+    tracing::info!("starting provider OAuth device login flow");
+
+    let Some(provider) = config.model_providers.get(&provider_id) else {
+        eprintln!("Unknown model provider `{provider_id}`.");
+        std::process::exit(1);
+    };
+    let Some(oauth) = provider.oauth.as_ref() else {
+        eprintln!(
+            "Provider `{provider_id}` has no [model_providers.{provider_id}.oauth] configuration, so OAuth login is not available for it."
+        );
+        std::process::exit(1);
+    };
+
+    match run_provider_device_login(
+        &config.codex_home,
+        &provider_id,
+        oauth,
+        config.cli_auth_credentials_store_mode,
+        config.auth_keyring_backend_kind(),
+    )
+    .await
+    {
+        Ok(()) => {
+            eprintln!("{LOGIN_SUCCESS_MESSAGE}");
+            std::process::exit(0);
+        }
+        Err(e) => {
+            eprintln!("Error logging in to provider `{provider_id}`: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
 /// Prefers device-code login (with `open_browser = false`) when headless environment is detected, but keeps
 /// `codex login` working in environments where device-code may be disabled/feature-gated.
 /// If `run_device_code_login` returns `ErrorKind::NotFound` ("device-code unsupported"), this
@@ -17,12 +17,14 @@ use codex_cli::run_login_with_access_token;
 use codex_cli::run_login_with_api_key;
 use codex_cli::run_login_with_chatgpt;
 use codex_cli::run_login_with_device_code;
+use codex_cli::run_login_with_provider;
 use codex_cli::run_logout;
 use codex_cloud_tasks::Cli as CloudTasksCli;
 use codex_exec::Cli as ExecCli;
 use codex_exec::Command as ExecCommand;
 use codex_exec::ReviewArgs;
 use codex_execpolicy::ExecPolicyCheckCommand;
+use codex_infinity::Cli as InfinityCli;
 use codex_responses_api_proxy::Args as ResponsesApiProxyArgs;
 use codex_rollout_trace::REDUCED_STATE_FILE_NAME;
 use codex_rollout_trace::replay_bundle;
@@ -50,13 +52,20 @@ mod app_cmd;
 mod desktop_app;
 mod doctor;
 mod exec_server_telemetry;
+mod export_cmd;
 mod marketplace_cmd;
 mod mcp_cmd;
+mod models_cmd;
+mod perf_cmd;
 mod plugin_cmd;
+mod providers_cmd;
+mod remote_cmd;
 mod remote_control_cmd;
 #[cfg(target_os = "windows")]
 mod sandbox_setup;
+mod search_cmd;
 mod state_db_recovery;
+mod usage_cmd;
 #[cfg(not(windows))]
 mod wsl_paths;
 
@@ -65,7 +74,14 @@ use crate::plugin_cmd::PluginCli;
 use crate::plugin_cmd::PluginSubcommand;
 use crate::remote_control_cmd::RemoteControlCommand;
 use doctor::DoctorCommand;
+use export_cmd::ExportCli;
+use models_cmd::ModelsCli;
+use perf_cmd::PerfCli;
+use providers_cmd::ProvidersCli;
+use remote_cmd::RemoteCli;
+use search_cmd::SearchCli;
 use state_db_recovery as local_state_db;
+use usage_cmd::UsageCli;
 
 use codex_config::LoaderOverrides;
 use codex_core::build_models_manager;
@@ -164,6 +180,27 @@ enum Subcommand {
     /// Diagnose local Codex installation, config, auth, and runtime health.
     Doctor(DoctorCommand),
 
+    /// List model ids advertised by configured providers' `/models` endpoints.
+    Models(ModelsCli),
+
+    /// Check configured model providers for reachability, auth, and latency.
+    Providers(ProvidersCli),
+
+    /// Report recorded token usage and estimated spend per provider/model.
+    Usage(UsageCli),
+
+    /// Report recorded streaming performance (TTFT, tokens/sec, stalls) per provider/model.
+    Perf(PerfCli),
+
+    /// Export a recorded conversation as Markdown, JSON, or HTML.
+    Export(ExportCli),
+
+    /// Search recorded conversations by content.
+    Search(SearchCli),
+
+    /// Drive a remote codex HTTP server (new, send, events, interrupt).
+    Remote(RemoteCli),
+
     /// Run commands within a Codex-provided sandbox.
     Sandbox(HostSandboxArgs),
 
@@ -197,6 +234,9 @@ enum Subcommand {
     #[clap(name = "cloud", alias = "cloud-tasks")]
     Cloud(CloudTasksCli),
 
+    /// Manage Codex Infinity projects and addons.
+    Infinity(InfinityCli),
+
     /// Internal: run the responses API proxy.
     #[clap(hide = true)]
     ResponsesApiProxy(ResponsesApiProxyArgs),
@@ -487,6 +527,11 @@ struct LoginCommand {
     #[arg(long = "device-auth")]
     use_device_code: bool,
 
+    /// Log in to a third-party model provider via its configured OAuth
+    /// device flow (`[model_providers.<id>.oauth]` in config.toml).
+    #[arg(long = "provider", value_name = "PROVIDER_ID")]
+    provider: Option<String>,
+
     /// EXPERIMENTAL: Use custom OAuth issuer base URL (advanced)
     /// Override the OAuth issuer base URL (advanced)
     #[arg(long = "experimental_issuer", value_name = "URL", hide = true)]
@@ -1363,6 +1408,8 @@ async fn cli_main(
                             "Choose one login credential source: --with-api-key or --with-access-token."
                         );
                         std::process::exit(1);
+                    } else if let Some(provider_id) = login_cli.provider {
+                        run_login_with_provider(login_cli.config_overrides, provider_id).await;
                     } else if login_cli.use_device_code {
                         run_login_with_device_code(
                             login_cli.config_overrides,
@@ -1429,6 +1476,86 @@ async fn cli_main(
             )
             .await?;
         }
+        Some(Subcommand::Models(mut models_cli)) => {
+            reject_remote_mode_for_subcommand(
+                root_remote.as_deref(),
+                root_remote_auth_token_env.as_deref(),
+                "models",
+            )?;
+            prepend_config_flags(
+                &mut models_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            models_cli.run().await?;
+        }
+        Some(Subcommand::Providers(mut providers_cli)) => {
+            reject_remote_mode_for_subcommand(
+                root_remote.as_deref(),
+                root_remote_auth_token_env.as_deref(),
+                "providers",
+            )?;
+            prepend_config_flags(
+                &mut providers_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            providers_cli.run().await?;
+        }
+        Some(Subcommand::Usage(mut usage_cli)) => {
+            reject_remote_mode_for_subcommand(
+                root_remote.as_deref(),
+                root_remote_auth_token_env.as_deref(),
+                "usage",
+            )?;
+            prepend_config_flags(
+                &mut usage_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            usage_cli.run().await?;
+        }
+        Some(Subcommand::Perf(mut perf_cli)) => {
+            reject_remote_mode_for_subcommand(
+                root_remote.as_deref(),
+                root_remote_auth_token_env.as_deref(),
+                "perf",
+            )?;
+            prepend_config_flags(
+                &mut perf_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            perf_cli.run().await?;
+        }
+        Some(Subcommand::Export(mut export_cli)) => {
+            reject_remote_mode_for_subcommand(
+                root_remote.as_deref(),
+                root_remote_auth_token_env.as_deref(),
+                "export",
+            )?;
+            prepend_config_flags(
+                &mut export_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            export_cli.run().await?;
+        }
+        Some(Subcommand::Search(mut search_cli)) => {
+            reject_remote_mode_for_subcommand(
+                root_remote.as_deref(),
+                root_remote_auth_token_env.as_deref(),
+                "search",
+            )?;
+            prepend_config_flags(
+                &mut search_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            search_cli.run().await?;
+        }
+        Some(Subcommand::Remote(remote_cli)) => {
+            reject_remote_mode_for_subcommand(
+                root_remote.as_deref(),
+                root_remote_auth_token_env.as_deref(),
+                "remote",
+            )?;
+            remote_cli.run().await?;
+        }
         Some(Subcommand::Cloud(mut cloud_cli)) => {
             reject_remote_mode_for_subcommand(
                 root_remote.as_deref(),
@@ -1442,6 +1569,18 @@ async fn cli_main(
             codex_cloud_tasks::run_main(cloud_cli, arg0_paths.codex_linux_sandbox_exe.clone())
                 .await?;
         }
+        Some(Subcommand::Infinity(mut infinity_cli)) => {
+            reject_remote_mode_for_subcommand(
+                root_remote.as_deref(),
+                root_remote_auth_token_env.as_deref(),
+                "infinity",
+            )?;
+            prepend_config_flags(
+                &mut infinity_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            codex_infinity::run_main(infinity_cli).await?;
+        }
         Some(Subcommand::Sandbox(mut sandbox_cli)) => {
             #[cfg(target_os = "windows")]
             if let Some(setup_cli) = sandbox_setup::parse_setup_command(&sandbox_cli.command)? {
@@ -2141,6 +2280,13 @@ fn unsupported_subcommand_name_for_strict_config(
         Some(Subcommand::Completion(_)) => Some("completion"),
         Some(Subcommand::Update) => Some("update"),
         Some(Subcommand::Cloud(_)) => Some("cloud"),
+        Some(Subcommand::Infinity(_)) => Some("infinity"),
+        Some(Subcommand::Models(_)) => Some("models"),
+        Some(Subcommand::Providers(_)) => Some("providers"),
+        Some(Subcommand::Usage(_)) => Some("usage"),
+        Some(Subcommand::Perf(_)) => Some("perf"),
+        Some(Subcommand::Export(_)) => Some("export"),
+        Some(Subcommand::Search(_)) => Some("search"),
         Some(Subcommand::Sandbox(_)) => Some("sandbox"),
         Some(Subcommand::Debug(_)) => Some("debug"),
         Some(Subcommand::Execpolicy(_)) => Some("execpolicy"),
@@ -0,0 +1,225 @@
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use clap::Parser;
+use codex_core::config::Config;
+use codex_model_provider_info::ModelProviderInfo;
+use codex_utils_cli::CliConfigOverrides;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+const MODELS_CACHE_FILE: &str = "provider_models_cache.json";
+const MODELS_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex models")]
+pub struct ModelsCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    subcommand: ModelsSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum ModelsSubcommand {
+    /// Query each configured provider's `/models` endpoint and list the
+    /// model ids it advertises.
+    List(ListArgs),
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex models list")]
+struct ListArgs {
+    /// List models for a single provider by id instead of all configured providers.
+    #[arg(long = "provider", value_name = "PROVIDER_ID")]
+    provider_id: Option<String>,
+
+    /// Output results as JSON.
+    #[arg(long = "json")]
+    json: bool,
+
+    /// Re-query providers even when a fresh cached listing exists.
+    #[arg(long = "refresh")]
+    refresh: bool,
+}
+
+/// On-disk cache of per-provider model listings, keyed by provider id.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ModelsCacheFile {
+    providers: BTreeMap<String, CachedProviderModels>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedProviderModels {
+    fetched_at_epoch_secs: u64,
+    model_ids: Vec<String>,
+}
+
+impl CachedProviderModels {
+    fn is_fresh(&self, now: SystemTime) -> bool {
+        now.duration_since(UNIX_EPOCH)
+            .ok()
+            .and_then(|now| now.checked_sub(Duration::from_secs(self.fetched_at_epoch_secs)))
+            .is_some_and(|age| age < MODELS_CACHE_TTL)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ListRow {
+    id: String,
+    name: String,
+    model_ids: Vec<String>,
+    cached: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl ModelsCli {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+
+        match self.subcommand {
+            ModelsSubcommand::List(args) => run_list(&config, args).await,
+        }
+    }
+}
+
+async fn run_list(config: &Config, args: ListArgs) -> Result<()> {
+    let mut providers: Vec<(&String, &ModelProviderInfo)> = match &args.provider_id {
+        Some(provider_id) => match config.model_providers.get_key_value(provider_id) {
+            Some(entry) => vec![entry],
+            None => {
+                let mut known: Vec<&str> =
+                    config.model_providers.keys().map(String::as_str).collect();
+                known.sort_unstable();
+                bail!(
+                    "unknown provider `{provider_id}`; configured providers: {}",
+                    known.join(", ")
+                );
+            }
+        },
+        None => config.model_providers.iter().collect(),
+    };
+    providers.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let cache_path = config.codex_home.join(MODELS_CACHE_FILE);
+    let mut cache = load_cache(&cache_path);
+    let now = SystemTime::now();
+
+    let client = reqwest::Client::new();
+    let mut rows = Vec::with_capacity(providers.len());
+    let mut cache_updated = false;
+    for (id, provider) in providers {
+        if !args.refresh
+            && let Some(cached) = cache.providers.get(id.as_str())
+            && cached.is_fresh(now)
+        {
+            rows.push(ListRow {
+                id: id.clone(),
+                name: provider.name.clone(),
+                model_ids: cached.model_ids.clone(),
+                cached: true,
+                error: None,
+            });
+            continue;
+        }
+
+        let listing = if provider.has_transport_overrides() {
+            match provider.build_http_client() {
+                Ok(provider_client) => provider.list_model_ids(&provider_client).await,
+                Err(error) => Err(error),
+            }
+        } else {
+            provider.list_model_ids(&client).await
+        };
+        match listing {
+            Ok(model_ids) => {
+                cache.providers.insert(
+                    id.clone(),
+                    CachedProviderModels {
+                        fetched_at_epoch_secs: now
+                            .duration_since(UNIX_EPOCH)
+                            .map(|now| now.as_secs())
+                            .unwrap_or_default(),
+                        model_ids: model_ids.clone(),
+                    },
+                );
+                cache_updated = true;
+                rows.push(ListRow {
+                    id: id.clone(),
+                    name: provider.name.clone(),
+                    model_ids,
+                    cached: false,
+                    error: None,
+                });
+            }
+            Err(error) => rows.push(ListRow {
+                id: id.clone(),
+                name: provider.name.clone(),
+                model_ids: Vec::new(),
+                cached: false,
+                error: Some(error),
+            }),
+        }
+    }
+
+    if cache_updated && let Err(err) = save_cache(&cache_path, &cache) {
+        eprintln!("warning: failed to write models cache: {err}");
+    }
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+        return Ok(());
+    }
+
+    let mut any_failed = false;
+    for row in &rows {
+        match &row.error {
+            Some(error) => {
+                any_failed = true;
+                println!("{id}: FAILED ({error})", id = row.id);
+            }
+            None => {
+                let source = if row.cached { "cached" } else { "live" };
+                println!(
+                    "{id}: {count} models ({source})",
+                    id = row.id,
+                    count = row.model_ids.len(),
+                );
+                for model_id in &row.model_ids {
+                    println!("  {model_id}");
+                }
+            }
+        }
+    }
+
+    if any_failed {
+        bail!("one or more providers failed to list models");
+    }
+    Ok(())
+}
+
+fn load_cache(cache_path: &Path) -> ModelsCacheFile {
+    let Ok(contents) = std::fs::read_to_string(cache_path) else {
+        return ModelsCacheFile::default();
+    };
+    serde_json::from_str(&contents).unwrap_or_default()
+}
+
+fn save_cache(cache_path: &Path, cache: &ModelsCacheFile) -> Result<()> {
+    let contents = serde_json::to_string_pretty(cache)?;
+    std::fs::write(cache_path, contents)?;
+    Ok(())
+}
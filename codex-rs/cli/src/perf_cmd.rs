@@ -0,0 +1,192 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::Context;
+use anyhow::Result;
+use clap::Parser;
+use codex_core::config::Config;
+use codex_core::stream_perf::PERF_LOG_FILE_NAME;
+use codex_core::stream_perf::StreamPerfRecord;
+use codex_utils_cli::CliConfigOverrides;
+use serde::Serialize;
+
+/// Summarize recorded streaming performance per provider/model.
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex perf")]
+pub struct PerfCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Output the report as JSON.
+    #[arg(long = "json")]
+    json: bool,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct PerfRow {
+    provider: String,
+    model: String,
+    streams: u64,
+    /// Milliseconds; `None` when no stream in this bucket produced a token.
+    avg_ttft_ms: Option<u64>,
+    p95_ttft_ms: Option<u64>,
+    avg_tokens_per_sec: Option<f64>,
+    stalls: u64,
+}
+
+impl PerfCli {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+
+        let log_path = config.codex_home.join(PERF_LOG_FILE_NAME);
+        let mut records = Vec::new();
+        // The rotated generation holds the older samples; read it first so
+        // records stay in chronological order.
+        read_records(&log_path.with_extension("jsonl.1"), &mut records);
+        read_records(&log_path, &mut records);
+
+        let rows = aggregate(records);
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&rows)?);
+            return Ok(());
+        }
+
+        if rows.is_empty() {
+            println!("no recorded streams under {}", log_path.display());
+            return Ok(());
+        }
+
+        println!(
+            "{:<20} {:<28} {:>8} {:>12} {:>12} {:>12} {:>8}",
+            "provider", "model", "streams", "avg ttft", "p95 ttft", "tokens/sec", "stalls"
+        );
+        for row in &rows {
+            println!(
+                "{:<20} {:<28} {:>8} {:>12} {:>12} {:>12} {:>8}",
+                row.provider,
+                row.model,
+                row.streams,
+                format_ms(row.avg_ttft_ms),
+                format_ms(row.p95_ttft_ms),
+                row.avg_tokens_per_sec
+                    .map(|tps| format!("{tps:.1}"))
+                    .unwrap_or_else(|| "-".to_string()),
+                row.stalls,
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Appends the parseable records from one perf log generation. Missing files
+/// and malformed lines are skipped: the report covers whatever is present.
+fn read_records(path: &Path, out: &mut Vec<StreamPerfRecord>) {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return;
+    };
+    out.extend(
+        contents
+            .lines()
+            .filter_map(|line| serde_json::from_str::<StreamPerfRecord>(line).ok()),
+    );
+}
+
+fn aggregate(records: Vec<StreamPerfRecord>) -> Vec<PerfRow> {
+    let mut buckets: BTreeMap<(String, String), Vec<StreamPerfRecord>> = BTreeMap::new();
+    for record in records {
+        buckets
+            .entry((record.provider.clone(), record.model.clone()))
+            .or_default()
+            .push(record);
+    }
+    buckets
+        .into_iter()
+        .map(|((provider, model), samples)| {
+            let mut ttfts: Vec<u64> = samples.iter().filter_map(|s| s.ttft_ms).collect();
+            ttfts.sort_unstable();
+            let throughputs: Vec<f64> = samples.iter().filter_map(|s| s.tokens_per_sec).collect();
+            PerfRow {
+                provider,
+                model,
+                streams: samples.len() as u64,
+                avg_ttft_ms: (!ttfts.is_empty())
+                    .then(|| ttfts.iter().sum::<u64>() / ttfts.len() as u64),
+                p95_ttft_ms: percentile(&ttfts, 95),
+                avg_tokens_per_sec: (!throughputs.is_empty())
+                    .then(|| throughputs.iter().sum::<f64>() / throughputs.len() as f64),
+                stalls: samples.iter().map(|s| u64::from(s.stall_count)).sum(),
+            }
+        })
+        .collect()
+}
+
+/// Nearest-rank percentile over an ascending-sorted slice.
+fn percentile(sorted: &[u64], pct: usize) -> Option<u64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (sorted.len() * pct).div_ceil(100);
+    Some(sorted[rank.saturating_sub(1).min(sorted.len() - 1)])
+}
+
+fn format_ms(value: Option<u64>) -> String {
+    match value {
+        Some(ms) => format!("{ms}ms"),
+        None => "-".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn record(
+        provider: &str,
+        ttft_ms: Option<u64>,
+        tokens_per_sec: Option<f64>,
+    ) -> StreamPerfRecord {
+        StreamPerfRecord {
+            timestamp_ms: 0,
+            provider: provider.to_string(),
+            model: "gpt-5.3-codex".to_string(),
+            ttft_ms,
+            output_tokens: Some(100),
+            tokens_per_sec,
+            stall_count: 1,
+        }
+    }
+
+    #[test]
+    fn aggregates_per_provider_and_model() {
+        let rows = aggregate(vec![
+            record("openai", Some(100), Some(50.0)),
+            record("openai", Some(300), Some(70.0)),
+            record("anthropic", Some(200), None),
+        ]);
+        assert_eq!(rows.len(), 2);
+        let openai = rows
+            .iter()
+            .find(|row| row.provider == "openai")
+            .expect("openai row");
+        assert_eq!(openai.streams, 2);
+        assert_eq!(openai.avg_ttft_ms, Some(200));
+        assert_eq!(openai.avg_tokens_per_sec, Some(60.0));
+        assert_eq!(openai.stalls, 2);
+    }
+
+    #[test]
+    fn percentile_uses_nearest_rank() {
+        let sorted: Vec<u64> = (1..=100).collect();
+        assert_eq!(percentile(&sorted, 95), Some(95));
+        assert_eq!(percentile(&[42], 95), Some(42));
+        assert_eq!(percentile(&[], 95), None);
+    }
+}
@@ -0,0 +1,135 @@
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use clap::Parser;
+use codex_core::config::Config;
+use codex_model_provider_info::ModelProviderInfo;
+use codex_model_provider_info::ProviderHealthCheck;
+use codex_utils_cli::CliConfigOverrides;
+use serde::Serialize;
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex providers")]
+pub struct ProvidersCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    subcommand: ProvidersSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum ProvidersSubcommand {
+    /// Probe each configured provider and report reachability, auth validity,
+    /// and measured latency.
+    Check(CheckArgs),
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex providers check")]
+struct CheckArgs {
+    /// Check a single provider by id instead of all configured providers.
+    #[arg(value_name = "PROVIDER_ID")]
+    provider_id: Option<String>,
+
+    /// Output results as JSON.
+    #[arg(long = "json")]
+    json: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct CheckRow {
+    id: String,
+    name: String,
+    #[serde(flatten)]
+    health: ProviderHealthCheck,
+}
+
+impl ProvidersCli {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+
+        match self.subcommand {
+            ProvidersSubcommand::Check(args) => run_check(&config, args).await,
+        }
+    }
+}
+
+async fn run_check(config: &Config, args: CheckArgs) -> Result<()> {
+    let mut providers: Vec<(&String, &ModelProviderInfo)> = match &args.provider_id {
+        Some(provider_id) => match config.model_providers.get_key_value(provider_id) {
+            Some(entry) => vec![entry],
+            None => {
+                let mut known: Vec<&str> =
+                    config.model_providers.keys().map(String::as_str).collect();
+                known.sort_unstable();
+                bail!(
+                    "unknown provider `{provider_id}`; configured providers: {}",
+                    known.join(", ")
+                );
+            }
+        },
+        None => config.model_providers.iter().collect(),
+    };
+    providers.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let client = reqwest::Client::new();
+    let mut rows = Vec::with_capacity(providers.len());
+    for (id, provider) in providers {
+        let health = if provider.has_transport_overrides() {
+            match provider.build_http_client() {
+                Ok(provider_client) => provider.health_check(&provider_client).await,
+                Err(error) => ProviderHealthCheck {
+                    reachable: false,
+                    auth_ok: None,
+                    latency_ms: 0,
+                    detail: error,
+                },
+            }
+        } else {
+            provider.health_check(&client).await
+        };
+        rows.push(CheckRow {
+            id: id.clone(),
+            name: provider.name.clone(),
+            health,
+        });
+    }
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+        return Ok(());
+    }
+
+    let mut any_failed = false;
+    for row in &rows {
+        let status = if row.health.reachable && row.health.auth_ok.unwrap_or(true) {
+            "ok"
+        } else {
+            any_failed = true;
+            "FAILED"
+        };
+        let auth = match row.health.auth_ok {
+            Some(true) => "auth ok",
+            Some(false) => "auth failed",
+            None => "auth unknown",
+        };
+        println!(
+            "{status:<6} {id:<20} {auth:<12} {latency:>6}ms  {detail}",
+            id = row.id,
+            latency = row.health.latency_ms,
+            detail = row.health.detail,
+        );
+    }
+
+    if any_failed {
+        bail!("one or more providers failed the preflight check");
+    }
+    Ok(())
+}
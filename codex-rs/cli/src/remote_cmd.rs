@@ -0,0 +1,187 @@
+//! `codex remote`: drive a remote `codex-http-server` instance.
+//!
+//! Gives centrally hosted agents a supported client beyond curl: new
+//! conversations are queued through the server's job queue, prompts are
+//! sent into existing conversations with the assistant's text streamed to
+//! stdout, and the server's event stream can be followed live.
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use clap::Parser;
+use codex_http_server_client::CompleteRequest;
+use codex_http_server_client::HttpServerClient;
+use codex_http_server_client::JobSpec;
+use futures::StreamExt;
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex remote")]
+pub struct RemoteCli {
+    /// Base URL of the codex HTTP server, e.g. `http://host:8811`.
+    #[arg(long, value_name = "URL")]
+    server: String,
+
+    #[command(subcommand)]
+    subcommand: RemoteSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum RemoteSubcommand {
+    /// Start a new conversation on the server by queueing a job.
+    New(NewArgs),
+
+    /// Send a prompt into a recorded conversation and stream the answer.
+    Send(SendArgs),
+
+    /// Show recent server activity, or follow the live event stream.
+    Events(EventsArgs),
+
+    /// Stop a conversation's running turn.
+    Interrupt(InterruptArgs),
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex remote new")]
+struct NewArgs {
+    /// Prompt the conversation starts with.
+    prompt: String,
+
+    /// Repository (working directory) the conversation runs in, as a path
+    /// on the server.
+    #[arg(long, value_name = "DIR")]
+    cwd: Option<PathBuf>,
+
+    /// `-c key=value` config overrides passed through to the conversation.
+    #[arg(short = 'c', long = "config", value_name = "KEY=VALUE")]
+    config_overrides: Vec<String>,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex remote send")]
+struct SendArgs {
+    /// Conversation to run the turn in.
+    conversation_id: String,
+
+    /// Prompt for this turn.
+    prompt: String,
+
+    /// Wait behind a running turn instead of failing when busy.
+    #[arg(long)]
+    queue: bool,
+
+    /// Interrupt a running turn and take the conversation over.
+    #[arg(long, conflicts_with = "queue")]
+    force: bool,
+
+    /// `-c key=value` config overrides for this turn.
+    #[arg(short = 'c', long = "config", value_name = "KEY=VALUE")]
+    config_overrides: Vec<String>,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex remote events")]
+struct EventsArgs {
+    /// Stay attached to `/events` and print events as they happen, instead
+    /// of listing recent activity and exiting.
+    #[arg(long)]
+    follow: bool,
+
+    /// Number of recent activity entries to list (without `--follow`).
+    #[arg(long, default_value_t = 50)]
+    limit: usize,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex remote interrupt")]
+struct InterruptArgs {
+    /// Conversation whose running turn to stop.
+    conversation_id: String,
+}
+
+impl RemoteCli {
+    pub async fn run(self) -> Result<()> {
+        let client = HttpServerClient::new(self.server);
+        match self.subcommand {
+            RemoteSubcommand::New(args) => run_new(&client, args).await,
+            RemoteSubcommand::Send(args) => run_send(&client, args).await,
+            RemoteSubcommand::Events(args) => run_events(&client, args).await,
+            RemoteSubcommand::Interrupt(args) => run_interrupt(&client, args).await,
+        }
+    }
+}
+
+async fn run_new(client: &HttpServerClient, args: NewArgs) -> Result<()> {
+    let job = client
+        .create_job(&JobSpec {
+            prompt: args.prompt,
+            cwd: args.cwd,
+            config_overrides: args.config_overrides,
+            ..JobSpec::default()
+        })
+        .await
+        .context("failed to queue the conversation")?;
+    println!("queued job {}", job.id);
+    println!("follow it with `codex remote events --follow` or `codex remote send` once it runs");
+    Ok(())
+}
+
+async fn run_send(client: &HttpServerClient, args: SendArgs) -> Result<()> {
+    let mut text = Box::pin(
+        client
+            .complete(
+                &args.conversation_id,
+                &CompleteRequest {
+                    prompt: args.prompt,
+                    config_overrides: args.config_overrides,
+                    queue: args.queue,
+                    force: args.force,
+                    ..CompleteRequest::default()
+                },
+            )
+            .await
+            .context("failed to start the turn")?,
+    );
+    let mut stdout = std::io::stdout();
+    while let Some(chunk) = text.next().await {
+        // Flush per chunk so the text shows up as the turn produces it.
+        stdout.write_all(chunk?.as_bytes())?;
+        stdout.flush()?;
+    }
+    stdout.write_all(b"\n")?;
+    Ok(())
+}
+
+async fn run_events(client: &HttpServerClient, args: EventsArgs) -> Result<()> {
+    if !args.follow {
+        let entries = client
+            .recent_audit(args.limit)
+            .await
+            .context("failed to list recent activity")?;
+        for entry in entries {
+            println!("{}  {}  {}", entry.at, entry.action, entry.detail);
+        }
+        return Ok(());
+    }
+    let mut events = Box::pin(
+        client
+            .events()
+            .await
+            .context("failed to open the event stream")?,
+    );
+    while let Some(event) = events.next().await {
+        let event = event?;
+        println!("{}  {}", event.kind, event.payload);
+    }
+    Ok(())
+}
+
+async fn run_interrupt(client: &HttpServerClient, args: InterruptArgs) -> Result<()> {
+    client
+        .interrupt_conversation(&args.conversation_id)
+        .await
+        .context("failed to interrupt the turn")?;
+    println!("interrupted conversation {}", args.conversation_id);
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use anyhow::Context;
+use anyhow::Result;
+use clap::Parser;
+use codex_core::config::Config;
+use codex_core::search::search_conversations;
+use codex_utils_cli::CliConfigOverrides;
+
+/// Search recorded conversations by content.
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex search")]
+pub struct SearchCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Text to search for in conversation transcripts.
+    query: String,
+
+    /// Search archived conversations instead of active ones.
+    #[arg(long)]
+    archived: bool,
+
+    /// Maximum number of conversations to show.
+    #[arg(long, default_value_t = 20)]
+    limit: usize,
+}
+
+impl SearchCli {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+
+        let results = search_conversations(
+            config.codex_home.as_path(),
+            &self.query,
+            self.archived,
+            self.limit,
+        )
+        .await
+        .context("failed to search conversations")?;
+        if results.is_empty() {
+            println!("no conversations match {:?}", self.query);
+            return Ok(());
+        }
+        for result in results {
+            println!("{}  {}", result.id, result.timestamp);
+            println!("    {}", result.snippet);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use clap::Parser;
+use codex_core::SESSIONS_SUBDIR;
+use codex_core::config::Config;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::RolloutLine;
+use codex_protocol::protocol::TokenCountEvent;
+use codex_utils_cli::CliConfigOverrides;
+use serde::Serialize;
+
+/// Summarize recorded token usage and estimated spend per provider/model.
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex usage")]
+pub struct UsageCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Output the report as JSON.
+    #[arg(long = "json")]
+    json: bool,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct UsageRow {
+    provider_id: String,
+    model: String,
+    sessions: u64,
+    input_tokens: i64,
+    cached_input_tokens: i64,
+    output_tokens: i64,
+    total_tokens: i64,
+    /// Sum of per-session cost estimates; `None` when no session in this
+    /// bucket had a priced model.
+    estimated_cost_usd: Option<f64>,
+}
+
+impl UsageCli {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+
+        let sessions_dir = config.codex_home.join(SESSIONS_SUBDIR);
+        let mut session_files = Vec::new();
+        collect_session_files(&sessions_dir, &mut session_files);
+        session_files.sort_unstable();
+
+        let mut rows: BTreeMap<(String, String), UsageRow> = BTreeMap::new();
+        for path in &session_files {
+            let Some(event) = last_token_count_event(path) else {
+                continue;
+            };
+            let Some(info) = event.info else {
+                continue;
+            };
+            let (provider_id, model, cost) = match event.cost {
+                Some(cost) => (cost.provider_id, cost.model, cost.estimated_cost_usd),
+                // Rollouts recorded before cost accounting carry usage but no
+                // attribution; keep their tokens visible under one bucket.
+                None => ("(unknown)".to_string(), "(unknown)".to_string(), None),
+            };
+            let usage = &info.total_token_usage;
+            let row = rows
+                .entry((provider_id.clone(), model.clone()))
+                .or_insert_with(|| UsageRow {
+                    provider_id,
+                    model,
+                    ..Default::default()
+                });
+            row.sessions += 1;
+            row.input_tokens += usage.input_tokens;
+            row.cached_input_tokens += usage.cached_input_tokens;
+            row.output_tokens += usage.output_tokens;
+            row.total_tokens += usage.total_tokens;
+            if let Some(cost) = cost {
+                *row.estimated_cost_usd.get_or_insert(0.0) += cost;
+            }
+        }
+
+        let rows: Vec<UsageRow> = rows.into_values().collect();
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&rows)?);
+            return Ok(());
+        }
+
+        if rows.is_empty() {
+            println!("no recorded sessions under {}", sessions_dir.display());
+            return Ok(());
+        }
+
+        println!(
+            "{:<20} {:<28} {:>8} {:>14} {:>14} {:>12}",
+            "provider", "model", "sessions", "input tokens", "output tokens", "est. cost"
+        );
+        let mut total_cost = 0.0;
+        let mut any_unpriced = false;
+        for row in &rows {
+            let cost = match row.estimated_cost_usd {
+                Some(cost) => {
+                    total_cost += cost;
+                    format!("${cost:.2}")
+                }
+                None => {
+                    any_unpriced = true;
+                    "-".to_string()
+                }
+            };
+            println!(
+                "{:<20} {:<28} {:>8} {:>14} {:>14} {:>12}",
+                row.provider_id, row.model, row.sessions, row.input_tokens, row.output_tokens, cost,
+            );
+        }
+        println!("total estimated cost: ${total_cost:.2}");
+        if any_unpriced {
+            println!(
+                "note: `-` rows have no rate; add a `[model_rates]` entry in config.toml to price them"
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Recursively collects rollout `.jsonl` files under `dir`. Missing or
+/// unreadable directories are skipped: the report covers whatever is present.
+fn collect_session_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_session_files(&path, out);
+        } else if path.extension().is_some_and(|ext| ext == "jsonl") {
+            out.push(path);
+        }
+    }
+}
+
+/// Returns the last `token_count` event recorded in a rollout file, which
+/// carries the cumulative usage for that session. Malformed lines are skipped
+/// so partially written files still contribute.
+fn last_token_count_event(path: &Path) -> Option<TokenCountEvent> {
+    let contents = fs::read_to_string(path).ok()?;
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<RolloutLine>(line).ok())
+        .filter_map(|line| match line.item {
+            RolloutItem::EventMsg(EventMsg::TokenCount(event)) => Some(event),
+            _ => None,
+        })
+        .next_back()
+}
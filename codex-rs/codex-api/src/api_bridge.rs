@@ -3,6 +3,7 @@ use crate::error::ApiError;
 use crate::rate_limits::parse_promo_message;
 use crate::rate_limits::parse_rate_limit_for_limit;
 use crate::rate_limits::parse_rate_limit_reached_type;
+use crate::rate_limits::retry_after_from_headers;
 use base64::Engine;
 use chrono::DateTime;
 use chrono::Utc;
@@ -108,6 +109,18 @@ pub fn map_api_error(err: ApiError) -> CodexErr {
                         }
                     }
 
+                    if let Some(delay) = headers.as_ref().and_then(retry_after_from_headers)
+                        && delay <= MAX_HEADER_RETRY_AFTER
+                    {
+                        return CodexErr::Stream(
+                            format!(
+                                "rate limited; server asked to retry in {}s",
+                                delay.as_secs().max(1)
+                            ),
+                            Some(delay),
+                        );
+                    }
+
                     CodexErr::RetryLimit(RetryLimitReachedError {
                         status,
                         request_id: extract_request_tracking_id(headers.as_ref()),
@@ -133,14 +146,24 @@ pub fn map_api_error(err: ApiError) -> CodexErr {
                 request_id: None,
             }),
             TransportError::Timeout => CodexErr::RequestTimeout,
-            TransportError::Network(msg) | TransportError::Build(msg) => {
-                CodexErr::Stream(msg, None)
-            }
+            // Connection-category failures carry a self-descriptive,
+            // user-facing message ("could not resolve <host> — ...").
+            connection @ (TransportError::ConnectTimeout { .. }
+            | TransportError::Dns { .. }
+            | TransportError::Tls { .. }
+            | TransportError::Proxy(_)) => CodexErr::Stream(connection.to_string(), None),
+            TransportError::Network(msg)
+            | TransportError::Build(msg)
+            | TransportError::BudgetExhausted(msg) => CodexErr::Stream(msg, None),
         },
         ApiError::RateLimit(msg) => CodexErr::Stream(msg, None),
     }
 }
 
+/// Server-advertised retry delays above this are treated as a hard rate limit
+/// rather than something worth silently waiting out.
+const MAX_HEADER_RETRY_AFTER: std::time::Duration = std::time::Duration::from_secs(15 * 60);
+
 const ACTIVE_LIMIT_HEADER: &str = "x-codex-active-limit";
 const REQUEST_ID_HEADER: &str = "x-request-id";
 const OAI_REQUEST_ID_HEADER: &str = "x-oai-request-id";
@@ -8,6 +8,36 @@ fn map_api_error_maps_server_overloaded() {
     assert!(matches!(err, CodexErr::ServerOverloaded));
 }
 
+#[test]
+fn map_api_error_surfaces_retry_after_delay_for_429() {
+    let mut headers = HeaderMap::new();
+    headers.insert("retry-after", http::HeaderValue::from_static("38"));
+    let err = map_api_error(ApiError::Transport(TransportError::Http {
+        status: http::StatusCode::TOO_MANY_REQUESTS,
+        url: Some("http://example.com/v1/responses".to_string()),
+        headers: Some(headers),
+        body: Some("rate limited".to_string()),
+    }));
+
+    let CodexErr::Stream(message, delay) = err else {
+        panic!("expected CodexErr::Stream, got {err:?}");
+    };
+    assert_eq!(delay, Some(std::time::Duration::from_secs(38)));
+    assert_eq!(message, "rate limited; server asked to retry in 38s");
+}
+
+#[test]
+fn map_api_error_keeps_retry_limit_for_429_without_retry_headers() {
+    let err = map_api_error(ApiError::Transport(TransportError::Http {
+        status: http::StatusCode::TOO_MANY_REQUESTS,
+        url: Some("http://example.com/v1/responses".to_string()),
+        headers: Some(HeaderMap::new()),
+        body: Some("rate limited".to_string()),
+    }));
+
+    assert!(matches!(err, CodexErr::RetryLimit(_)));
+}
+
 #[test]
 fn map_api_error_maps_server_overloaded_from_503_body() {
     let body = serde_json::json!({
@@ -41,6 +41,32 @@ pub struct CompactionInput<'a> {
     pub text: Option<TextControls>,
 }
 
+/// Request body for an OpenAI-compatible `POST /embeddings` call.
+#[derive(Debug, Clone, Serialize)]
+pub struct EmbeddingsRequest {
+    pub model: String,
+    pub input: Vec<String>,
+    /// Requested output dimensionality, for models that support shortening.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<u32>,
+}
+
+/// One embedding vector from an embeddings response, tagged with the index of
+/// the input it corresponds to.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Embedding {
+    pub index: usize,
+    pub embedding: Vec<f32>,
+}
+
+/// Response body for an OpenAI-compatible `POST /embeddings` call.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct EmbeddingsResponse {
+    pub data: Vec<Embedding>,
+    #[serde(default)]
+    pub model: String,
+}
+
 /// Canonical input payload for the memory summarize endpoint.
 #[derive(Debug, Clone, Serialize)]
 pub struct MemorySummarizeInput {
@@ -63,7 +89,7 @@ pub struct RawMemoryMetadata {
     pub source_path: String,
 }
 
-#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct MemorySummarizeOutput {
     #[serde(rename = "trace_summary", alias = "raw_memory")]
     pub raw_memory: String,
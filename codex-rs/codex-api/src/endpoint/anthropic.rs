@@ -0,0 +1,106 @@
+use crate::auth::SharedAuthProvider;
+use crate::common::ResponseStream;
+use crate::common::ResponsesApiRequest;
+use crate::endpoint::session::EndpointSession;
+use crate::error::ApiError;
+use crate::provider::Provider;
+use crate::requests::anthropic::AnthropicCacheOptions;
+use crate::requests::anthropic::build_anthropic_messages_body;
+use crate::sse::spawn_anthropic_response_stream;
+use crate::telemetry::SseTelemetry;
+use codex_client::EncodedJsonBody;
+use codex_client::HttpTransport;
+use codex_client::RequestTelemetry;
+use http::HeaderMap;
+use http::HeaderValue;
+use http::Method;
+use std::sync::Arc;
+use tracing::instrument;
+
+/// Messages API revision this client speaks.
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Client for Anthropic's native Messages API (`POST /v1/messages`).
+///
+/// Requests are translated from the Responses shape on the way out and the
+/// Messages event stream is translated back into `ResponseEvent`s, so callers
+/// use the same types regardless of wire protocol. Credentials are attached
+/// by the configured auth provider; Anthropic accepts bearer tokens for OAuth
+/// credentials, while static API keys should be supplied via an `x-api-key`
+/// header on the provider config.
+pub struct AnthropicMessagesClient<T: HttpTransport> {
+    session: EndpointSession<T>,
+    sse_telemetry: Option<Arc<dyn SseTelemetry>>,
+    cache_options: AnthropicCacheOptions,
+}
+
+impl<T: HttpTransport> AnthropicMessagesClient<T> {
+    pub fn new(transport: T, provider: Provider, auth: SharedAuthProvider) -> Self {
+        Self {
+            session: EndpointSession::new(transport, provider, auth),
+            sse_telemetry: None,
+            cache_options: AnthropicCacheOptions::default(),
+        }
+    }
+
+    pub fn with_telemetry(
+        self,
+        request: Option<Arc<dyn RequestTelemetry>>,
+        sse: Option<Arc<dyn SseTelemetry>>,
+    ) -> Self {
+        Self {
+            session: self.session.with_request_telemetry(request),
+            sse_telemetry: sse,
+            cache_options: self.cache_options,
+        }
+    }
+
+    pub fn with_cache_options(self, cache_options: AnthropicCacheOptions) -> Self {
+        Self {
+            cache_options,
+            ..self
+        }
+    }
+
+    #[instrument(
+        name = "anthropic_messages.stream_request",
+        level = "info",
+        skip_all,
+        fields(
+            transport = "anthropic_messages_http",
+            http.method = "POST",
+            api.path = "messages"
+        )
+    )]
+    pub async fn stream_request(
+        &self,
+        request: &ResponsesApiRequest,
+        extra_headers: HeaderMap,
+    ) -> Result<ResponseStream, ApiError> {
+        let body = build_anthropic_messages_body(request, self.cache_options);
+        let body = EncodedJsonBody::encode(&body)
+            .map_err(|e| ApiError::Stream(format!("failed to encode messages request: {e}")))?;
+
+        let mut headers = extra_headers;
+        headers.insert(
+            "anthropic-version",
+            HeaderValue::from_static(ANTHROPIC_VERSION),
+        );
+
+        let stream_response = self
+            .session
+            .stream_encoded_json_with(Method::POST, "messages", headers, Some(body), |req| {
+                req.headers.insert(
+                    http::header::ACCEPT,
+                    HeaderValue::from_static("text/event-stream"),
+                );
+            })
+            .await?;
+
+        Ok(spawn_anthropic_response_stream(
+            stream_response,
+            self.session.provider().stream_idle_timeout,
+            self.sse_telemetry.clone(),
+        ))
+    }
+}
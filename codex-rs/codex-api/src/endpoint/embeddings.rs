@@ -0,0 +1,181 @@
+use crate::auth::SharedAuthProvider;
+use crate::common::EmbeddingsRequest;
+use crate::common::EmbeddingsResponse;
+use crate::endpoint::session::EndpointSession;
+use crate::error::ApiError;
+use crate::provider::Provider;
+use codex_client::HttpTransport;
+use codex_client::RequestTelemetry;
+use http::HeaderMap;
+use http::Method;
+use serde_json::to_value;
+use std::sync::Arc;
+
+/// Client for OpenAI-compatible `POST /embeddings`.
+pub struct EmbeddingsClient<T: HttpTransport> {
+    session: EndpointSession<T>,
+}
+
+impl<T: HttpTransport> EmbeddingsClient<T> {
+    pub fn new(transport: T, provider: Provider, auth: SharedAuthProvider) -> Self {
+        Self {
+            session: EndpointSession::new(transport, provider, auth),
+        }
+    }
+
+    pub fn with_telemetry(self, request: Option<Arc<dyn RequestTelemetry>>) -> Self {
+        Self {
+            session: self.session.with_request_telemetry(request),
+        }
+    }
+
+    fn path() -> &'static str {
+        "embeddings"
+    }
+
+    pub async fn create(
+        &self,
+        request: &EmbeddingsRequest,
+        extra_headers: HeaderMap,
+    ) -> Result<EmbeddingsResponse, ApiError> {
+        let body = to_value(request)
+            .map_err(|e| ApiError::Stream(format!("failed to encode embeddings request: {e}")))?;
+        let resp = self
+            .session
+            .execute(Method::POST, Self::path(), extra_headers, Some(body))
+            .await?;
+        let mut parsed: EmbeddingsResponse =
+            serde_json::from_slice(&resp.body).map_err(|e| ApiError::Stream(e.to_string()))?;
+        // Providers may return entries out of order; callers index by input
+        // position, so normalize here.
+        parsed.data.sort_by_key(|embedding| embedding.index);
+        Ok(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::AuthProvider;
+    use crate::provider::RetryConfig;
+    use codex_client::Request;
+    use codex_client::RequestBody;
+    use codex_client::Response;
+    use codex_client::StreamResponse;
+    use codex_client::TransportError;
+    use http::HeaderMap;
+    use http::StatusCode;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[derive(Clone, Default)]
+    struct DummyAuth;
+
+    impl AuthProvider for DummyAuth {
+        fn add_auth_headers(&self, _headers: &mut HeaderMap) {}
+    }
+
+    #[derive(Clone)]
+    struct CapturingTransport {
+        last_request: Arc<Mutex<Option<Request>>>,
+        response_body: Arc<Vec<u8>>,
+    }
+
+    impl CapturingTransport {
+        fn new(response_body: Vec<u8>) -> Self {
+            Self {
+                last_request: Arc::new(Mutex::new(None)),
+                response_body: Arc::new(response_body),
+            }
+        }
+    }
+
+    impl HttpTransport for CapturingTransport {
+        async fn execute(&self, req: Request) -> Result<Response, TransportError> {
+            *self.last_request.lock().expect("lock request store") = Some(req);
+            Ok(Response {
+                status: StatusCode::OK,
+                headers: HeaderMap::new(),
+                body: self.response_body.as_ref().clone().into(),
+            })
+        }
+
+        async fn stream(&self, _req: Request) -> Result<StreamResponse, TransportError> {
+            Err(TransportError::Build("stream should not run".to_string()))
+        }
+    }
+
+    fn provider(base_url: &str) -> Provider {
+        Provider {
+            name: "test".to_string(),
+            base_url: base_url.to_string(),
+            query_params: None,
+            headers: HeaderMap::new(),
+            retry: RetryConfig {
+                max_attempts: 1,
+                base_delay: Duration::from_millis(1),
+                retry_429: false,
+                retry_5xx: true,
+                retry_transport: true,
+                budget: None,
+                observer: None,
+            },
+            stream_idle_timeout: Duration::from_secs(1),
+        }
+    }
+
+    #[tokio::test]
+    async fn create_posts_expected_payload_and_sorts_embeddings_by_index() {
+        let transport = CapturingTransport::new(
+            serde_json::to_vec(&json!({
+                "model": "text-embedding-3-small",
+                "data": [
+                    {"index": 1, "embedding": [0.5, 0.6]},
+                    {"index": 0, "embedding": [0.1, 0.2]},
+                ]
+            }))
+            .expect("serialize response"),
+        );
+        let client = EmbeddingsClient::new(
+            transport.clone(),
+            provider("https://example.com/v1"),
+            Arc::new(DummyAuth),
+        );
+
+        let request = EmbeddingsRequest {
+            model: "text-embedding-3-small".to_string(),
+            input: vec!["alpha".to_string(), "beta".to_string()],
+            dimensions: None,
+        };
+        let response = client
+            .create(&request, HeaderMap::new())
+            .await
+            .expect("request should succeed");
+
+        assert_eq!(response.data.len(), 2);
+        assert_eq!(response.data[0].index, 0);
+        assert_eq!(response.data[0].embedding, vec![0.1, 0.2]);
+        assert_eq!(response.data[1].index, 1);
+
+        let captured = transport
+            .last_request
+            .lock()
+            .expect("lock request store")
+            .clone()
+            .expect("request captured");
+        assert_eq!(captured.url, "https://example.com/v1/embeddings");
+        let RequestBody::Json(body) = captured.body.expect("request body") else {
+            panic!("expected JSON body");
+        };
+        assert_eq!(
+            body,
+            json!({
+                "model": "text-embedding-3-small",
+                "input": ["alpha", "beta"],
+            })
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use crate::auth::SharedAuthProvider;
+use crate::common::ResponseStream;
+use crate::common::ResponsesApiRequest;
+use crate::endpoint::session::EndpointSession;
+use crate::error::ApiError;
+use crate::provider::Provider;
+use crate::requests::gemini::build_gemini_generate_content_body;
+use crate::sse::spawn_gemini_response_stream;
+use crate::telemetry::SseTelemetry;
+use codex_client::EncodedJsonBody;
+use codex_client::HttpTransport;
+use codex_client::RequestTelemetry;
+use http::HeaderMap;
+use http::HeaderValue;
+use http::Method;
+use std::sync::Arc;
+use tracing::instrument;
+
+/// Client for Google's native `generateContent` API
+/// (`POST /models/{model}:streamGenerateContent`).
+///
+/// Requests are translated from the Responses shape on the way out and the
+/// chunked generation stream is translated back into `ResponseEvent`s, so
+/// callers use the same types regardless of wire protocol. API keys go via an
+/// `x-goog-api-key` header on the provider config; OAuth credentials are
+/// attached as bearer tokens by the configured auth provider.
+pub struct GeminiGenerateContentClient<T: HttpTransport> {
+    session: EndpointSession<T>,
+    sse_telemetry: Option<Arc<dyn SseTelemetry>>,
+}
+
+impl<T: HttpTransport> GeminiGenerateContentClient<T> {
+    pub fn new(transport: T, provider: Provider, auth: SharedAuthProvider) -> Self {
+        Self {
+            session: EndpointSession::new(transport, provider, auth),
+            sse_telemetry: None,
+        }
+    }
+
+    pub fn with_telemetry(
+        self,
+        request: Option<Arc<dyn RequestTelemetry>>,
+        sse: Option<Arc<dyn SseTelemetry>>,
+    ) -> Self {
+        Self {
+            session: self.session.with_request_telemetry(request),
+            sse_telemetry: sse,
+        }
+    }
+
+    #[instrument(
+        name = "gemini_generate_content.stream_request",
+        level = "info",
+        skip_all,
+        fields(
+            transport = "gemini_http",
+            http.method = "POST",
+            api.path = "streamGenerateContent"
+        )
+    )]
+    pub async fn stream_request(
+        &self,
+        request: &ResponsesApiRequest,
+        extra_headers: HeaderMap,
+    ) -> Result<ResponseStream, ApiError> {
+        let body = build_gemini_generate_content_body(request);
+        let body = EncodedJsonBody::encode(&body).map_err(|e| {
+            ApiError::Stream(format!("failed to encode generateContent request: {e}"))
+        })?;
+
+        // The model is part of the path; `alt=sse` selects SSE framing over
+        // the default JSON-array streaming.
+        let path = format!(
+            "models/{model}:streamGenerateContent?alt=sse",
+            model = request.model
+        );
+        let stream_response = self
+            .session
+            .stream_encoded_json_with(Method::POST, &path, extra_headers, Some(body), |req| {
+                req.headers.insert(
+                    http::header::ACCEPT,
+                    HeaderValue::from_static("text/event-stream"),
+                );
+            })
+            .await?;
+
+        Ok(spawn_gemini_response_stream(
+            stream_response,
+            self.session.provider().stream_idle_timeout,
+            self.sse_telemetry.clone(),
+        ))
+    }
+}
@@ -140,6 +140,8 @@ mod tests {
                 retry_429: false,
                 retry_5xx: true,
                 retry_transport: true,
+                budget: None,
+                observer: None,
             },
             stream_idle_timeout: Duration::from_secs(1),
         }
@@ -1,4 +1,7 @@
+pub(crate) mod anthropic;
 pub(crate) mod compact;
+pub(crate) mod embeddings;
+pub(crate) mod gemini;
 pub(crate) mod images;
 pub(crate) mod memories;
 pub(crate) mod models;
@@ -9,7 +12,10 @@ pub(crate) mod responses_websocket;
 pub(crate) mod search;
 mod session;
 
+pub use anthropic::AnthropicMessagesClient;
 pub use compact::CompactClient;
+pub use embeddings::EmbeddingsClient;
+pub use gemini::GeminiGenerateContentClient;
 pub use images::ImagesClient;
 pub use memories::MemoriesClient;
 pub use models::ModelsClient;
@@ -1791,6 +1791,8 @@ mod tests {
                 retry_429: false,
                 retry_5xx: false,
                 retry_transport: false,
+                budget: None,
+                observer: None,
             },
             stream_idle_timeout: Duration::from_secs(5),
         };
@@ -2115,6 +2117,8 @@ mod tests {
                 retry_429: false,
                 retry_5xx: false,
                 retry_transport: false,
+                budget: None,
+                observer: None,
             },
             stream_idle_timeout: Duration::from_secs(5),
         };
@@ -2240,6 +2244,8 @@ mod tests {
                 retry_429: false,
                 retry_5xx: false,
                 retry_transport: false,
+                budget: None,
+                observer: None,
             },
             stream_idle_timeout: Duration::from_secs(5),
         };
@@ -2344,6 +2350,8 @@ mod tests {
                 retry_429: false,
                 retry_5xx: false,
                 retry_transport: false,
+                budget: None,
+                observer: None,
             },
             stream_idle_timeout: Duration::from_secs(5),
         };
@@ -2434,6 +2442,8 @@ mod tests {
                 retry_429: false,
                 retry_5xx: false,
                 retry_transport: false,
+                budget: None,
+                observer: None,
             },
             stream_idle_timeout: Duration::from_secs(5),
         };
@@ -8,6 +8,8 @@ use crate::requests::Compression;
 use crate::requests::headers::build_session_headers;
 use crate::requests::headers::insert_header;
 use crate::requests::headers::subagent_header;
+use crate::sse::StreamResumeFuture;
+use crate::sse::StreamResumer;
 use crate::sse::spawn_response_stream;
 use crate::telemetry::SseTelemetry;
 use codex_client::EncodedJsonBody;
@@ -24,7 +26,7 @@ use std::sync::OnceLock;
 use tracing::instrument;
 
 pub struct ResponsesClient<T: HttpTransport> {
-    session: EndpointSession<T>,
+    session: Arc<EndpointSession<T>>,
     sse_telemetry: Option<Arc<dyn SseTelemetry>>,
 }
 
@@ -38,10 +40,10 @@ pub struct ResponsesOptions {
     pub turn_state: Option<Arc<OnceLock<String>>>,
 }
 
-impl<T: HttpTransport> ResponsesClient<T> {
+impl<T: HttpTransport + 'static> ResponsesClient<T> {
     pub fn new(transport: T, provider: Provider, auth: SharedAuthProvider) -> Self {
         Self {
-            session: EndpointSession::new(transport, provider, auth),
+            session: Arc::new(EndpointSession::new(transport, provider, auth)),
             sse_telemetry: None,
         }
     }
@@ -51,8 +53,14 @@ impl<T: HttpTransport> ResponsesClient<T> {
         request: Option<Arc<dyn RequestTelemetry>>,
         sse: Option<Arc<dyn SseTelemetry>>,
     ) -> Self {
+        // Part of the builder chain, so the session has not been shared with
+        // an in-flight stream yet and the unwrap-and-rebuild always succeeds.
+        let session = match Arc::try_unwrap(self.session) {
+            Ok(session) => Arc::new(session.with_request_telemetry(request)),
+            Err(session) => session,
+        };
         Self {
-            session: self.session.with_request_telemetry(request),
+            session,
             sse_telemetry: sse,
         }
     }
@@ -154,11 +162,46 @@ impl<T: HttpTransport> ResponsesClient<T> {
             )
             .await?;
 
+        let resumer: Arc<dyn StreamResumer> = Arc::new(ResponsesStreamResumer {
+            session: Arc::clone(&self.session),
+        });
         Ok(spawn_response_stream(
             stream_response,
             self.session.provider().stream_idle_timeout,
             self.sse_telemetry.clone(),
             turn_state,
+            Some(resumer),
         ))
     }
 }
+
+/// Resumes an interrupted Responses SSE stream with
+/// `GET responses/{id}?stream=true&starting_after={n}`.
+///
+/// Providers that do not support resuming reject the request, which makes the
+/// stream surface the original error and fall back to a full restart.
+struct ResponsesStreamResumer<T: HttpTransport> {
+    session: Arc<EndpointSession<T>>,
+}
+
+impl<T: HttpTransport + 'static> StreamResumer for ResponsesStreamResumer<T> {
+    fn resume(&self, response_id: &str, starting_after: u64) -> StreamResumeFuture<'_> {
+        let session = Arc::clone(&self.session);
+        let path = format!("{}/{response_id}", ResponsesClient::<T>::path());
+        Box::pin(async move {
+            session
+                .stream_encoded_json_with(Method::GET, &path, HeaderMap::new(), None, |req| {
+                    req.headers.insert(
+                        http::header::ACCEPT,
+                        HeaderValue::from_static("text/event-stream"),
+                    );
+                    // The provider may already have attached query params.
+                    let separator = if req.url.contains('?') { '&' } else { '?' };
+                    req.url.push(separator);
+                    req.url
+                        .push_str(&format!("stream=true&starting_after={starting_after}"));
+                })
+                .await
+        })
+    }
+}
@@ -31,6 +31,7 @@ use tokio::sync::Mutex;
 use tokio::sync::mpsc;
 use tokio::sync::oneshot;
 use tokio::time::Instant;
+use tokio_tungstenite::tungstenite::Bytes;
 use tokio_tungstenite::tungstenite::Error as WsError;
 use tokio_tungstenite::tungstenite::Message;
 use tokio_tungstenite::tungstenite::client::IntoClientRequest;
@@ -157,6 +158,8 @@ const X_REASONING_INCLUDED_HEADER: &str = "x-reasoning-included";
 const OPENAI_MODEL_HEADER: &str = "openai-model";
 const WEBSOCKET_CONNECTION_LIMIT_REACHED_CODE: &str = "websocket_connection_limit_reached";
 const WEBSOCKET_CONNECTION_LIMIT_REACHED_MESSAGE: &str = "Responses websocket connection limit reached (60 minutes). Create a new websocket connection to continue.";
+/// How often to ping a pooled connection that is idle between turns.
+const WEBSOCKET_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(30);
 const RESPONSES_WEBSOCKET_TIMING_KIND: &str = "responsesapi.websocket_timing";
 const RESPONSES_WEBSOCKET_TIMING_EVENT_TARGET: &str = "codex_api::responses_websocket_timing";
 const SESSION_ID_CLIENT_METADATA_KEY: &str = "session_id";
@@ -184,6 +187,7 @@ pub struct ResponsesWebsocketConnection {
     models_etag: Option<String>,
     server_model: Option<String>,
     telemetry: Option<Arc<dyn WebsocketTelemetry>>,
+    keepalive_task: tokio::task::JoinHandle<()>,
 }
 
 impl std::fmt::Debug for ResponsesWebsocketConnection {
@@ -208,13 +212,16 @@ impl ResponsesWebsocketConnection {
         server_model: Option<String>,
         telemetry: Option<Arc<dyn WebsocketTelemetry>>,
     ) -> Self {
+        let stream = Arc::new(Mutex::new(Some(stream)));
+        let keepalive_task = tokio::spawn(Self::run_keepalive(Arc::downgrade(&stream)));
         Self {
-            stream: Arc::new(Mutex::new(Some(stream))),
+            stream,
             idle_timeout,
             server_reasoning_included,
             models_etag,
             server_model,
             telemetry,
+            keepalive_task,
         }
     }
 
@@ -222,6 +229,36 @@ impl ResponsesWebsocketConnection {
         self.stream.lock().await.is_none()
     }
 
+    /// Sends a Ping on the pooled connection while it sits idle between turns
+    /// so NAT and proxy idle timeouts do not silently kill it. Ticks are
+    /// skipped while a turn holds the stream (live traffic already keeps the
+    /// connection active); the task exits once the connection is dropped,
+    /// closed, or the ping fails.
+    async fn run_keepalive(stream: std::sync::Weak<Mutex<Option<WsStream>>>) {
+        let mut interval = tokio::time::interval(WEBSOCKET_KEEPALIVE_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        // The first tick completes immediately; the connection is fresh then.
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            let Some(stream) = stream.upgrade() else {
+                return;
+            };
+            let Ok(mut guard) = stream.try_lock() else {
+                continue;
+            };
+            let Some(ws_stream) = guard.as_ref() else {
+                return;
+            };
+            if ws_stream.send(Message::Ping(Bytes::new())).await.is_err() {
+                // The pump task has shut down; drop the stream so the next
+                // turn reconnects instead of reusing a dead connection.
+                *guard = None;
+                return;
+            }
+        }
+    }
+
     #[instrument(
         name = "responses_websocket.stream_request",
         level = "info",
@@ -329,6 +366,12 @@ impl ResponsesWebsocketConnection {
     }
 }
 
+impl Drop for ResponsesWebsocketConnection {
+    fn drop(&mut self) {
+        self.keepalive_task.abort();
+    }
+}
+
 /// Client for connecting to the Responses WebSocket endpoint for one provider.
 pub struct ResponsesWebsocketClient {
     provider: Provider,
@@ -571,6 +614,10 @@ fn map_ws_error(err: WsError, url: &Url) -> ApiError {
         WsError::ConnectionClosed | WsError::AlreadyClosed => {
             ApiError::Stream("websocket closed".to_string())
         }
+        WsError::Tls(err) => ApiError::Transport(TransportError::Tls {
+            host: url.host_str().unwrap_or_default().to_string(),
+            message: err.to_string(),
+        }),
         WsError::Io(err) => ApiError::Transport(TransportError::Network(err.to_string())),
         other => ApiError::Transport(TransportError::Network(other.to_string())),
     }
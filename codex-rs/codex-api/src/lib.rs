@@ -16,7 +16,10 @@ pub(crate) mod telemetry;
 pub use crate::requests::headers::build_session_headers;
 pub use codex_client::RequestTelemetry;
 pub use codex_client::ReqwestTransport;
+pub use codex_client::RetryBudget;
+pub use codex_client::RetryObserver;
 pub use codex_client::TransportError;
+pub use codex_client::VcrTransport;
 
 pub use crate::api_bridge::map_api_error;
 pub use crate::auth::AgentIdentityTelemetry;
@@ -27,6 +30,9 @@ pub use crate::auth::AuthProviderFuture;
 pub use crate::auth::SharedAuthProvider;
 pub use crate::auth::auth_header_telemetry;
 pub use crate::common::CompactionInput;
+pub use crate::common::Embedding;
+pub use crate::common::EmbeddingsRequest;
+pub use crate::common::EmbeddingsResponse;
 pub use crate::common::MemorySummarizeInput;
 pub use crate::common::MemorySummarizeOutput;
 pub use crate::common::OpenAiVerbosity;
@@ -46,7 +52,10 @@ pub use crate::common::WS_REQUEST_HEADER_TRACEPARENT_CLIENT_METADATA_KEY;
 pub use crate::common::WS_REQUEST_HEADER_TRACESTATE_CLIENT_METADATA_KEY;
 pub use crate::common::create_text_param_for_request;
 pub use crate::common::response_create_client_metadata;
+pub use crate::endpoint::AnthropicMessagesClient;
 pub use crate::endpoint::CompactClient;
+pub use crate::endpoint::EmbeddingsClient;
+pub use crate::endpoint::GeminiGenerateContentClient;
 pub use crate::endpoint::ImagesClient;
 pub use crate::endpoint::MemoriesClient;
 pub use crate::endpoint::ModelsClient;
@@ -81,6 +90,7 @@ pub use crate::images::ImageUrl;
 pub use crate::provider::Provider;
 pub use crate::provider::RetryConfig;
 pub use crate::provider::is_azure_responses_provider;
+pub use crate::requests::AnthropicCacheOptions;
 pub use crate::requests::Compression;
 pub use crate::search::AllowedCaller;
 pub use crate::search::ApproximateLocation;
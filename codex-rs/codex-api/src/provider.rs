@@ -1,10 +1,13 @@
 use codex_client::Request;
 use codex_client::RequestCompression;
+use codex_client::RetryBudget;
+use codex_client::RetryObserver;
 use codex_client::RetryOn;
 use codex_client::RetryPolicy;
 use http::Method;
 use http::header::HeaderMap;
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 use url::Url;
 
@@ -12,13 +15,33 @@ use url::Url;
 ///
 /// This is converted into a `RetryPolicy` used by `codex-client` to drive
 /// transport-level retries for both unary and streaming calls.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct RetryConfig {
     pub max_attempts: u64,
     pub base_delay: Duration,
     pub retry_429: bool,
     pub retry_5xx: bool,
     pub retry_transport: bool,
+    /// Shared retry budget for the logical operation issuing these requests;
+    /// `None` leaves per-request `max_attempts` as the only limit.
+    pub budget: Option<RetryBudget>,
+    /// Observer notified of per-request retry progress; `None` keeps retries
+    /// silent.
+    pub observer: Option<Arc<dyn RetryObserver>>,
+}
+
+impl std::fmt::Debug for RetryConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryConfig")
+            .field("max_attempts", &self.max_attempts)
+            .field("base_delay", &self.base_delay)
+            .field("retry_429", &self.retry_429)
+            .field("retry_5xx", &self.retry_5xx)
+            .field("retry_transport", &self.retry_transport)
+            .field("budget", &self.budget)
+            .field("observer", &self.observer.is_some())
+            .finish()
+    }
 }
 
 impl RetryConfig {
@@ -31,6 +54,8 @@ impl RetryConfig {
                 retry_5xx: self.retry_5xx,
                 retry_transport: self.retry_transport,
             },
+            budget: self.budget.clone(),
+            observer: self.observer.clone(),
         }
     }
 }
@@ -1,3 +1,5 @@
+use chrono::DateTime;
+use chrono::Utc;
 use codex_protocol::account::PlanType;
 use codex_protocol::protocol::CreditsSnapshot;
 use codex_protocol::protocol::RateLimitReachedType;
@@ -7,6 +9,7 @@ use http::HeaderMap;
 use serde::Deserialize;
 use std::collections::BTreeSet;
 use std::fmt::Display;
+use std::time::Duration;
 
 #[derive(Debug)]
 pub struct RateLimitError {
@@ -252,6 +255,89 @@ fn parse_header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
     headers.get(name)?.to_str().ok()
 }
 
+/// Computes how long the server asked us to wait before retrying.
+///
+/// `Retry-After` (numeric seconds or an HTTP-date) takes precedence;
+/// otherwise the smallest positive wait advertised by the OpenAI
+/// `x-ratelimit-reset-*` headers (compact durations such as `6m12s`) or the
+/// Anthropic `anthropic-ratelimit-*-reset` headers (RFC 3339 timestamps) is
+/// used.
+pub fn retry_after_from_headers(headers: &HeaderMap) -> Option<Duration> {
+    if let Some(delay) = parse_retry_after_header(headers) {
+        return Some(delay);
+    }
+
+    headers
+        .iter()
+        .filter_map(|(name, value)| {
+            let name = name.as_str().to_ascii_lowercase();
+            let value = value.to_str().ok()?;
+            if name.starts_with("x-ratelimit-reset-") {
+                parse_compact_duration(value)
+            } else if name.starts_with("anthropic-ratelimit-") && name.ends_with("-reset") {
+                delay_until_rfc3339(value)
+            } else {
+                None
+            }
+        })
+        .min()
+}
+
+fn parse_retry_after_header(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers
+        .get(http::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let at = DateTime::parse_from_rfc2822(value).ok()?;
+    at.signed_duration_since(Utc::now()).to_std().ok()
+}
+
+/// Parses compact durations in the style OpenAI rate-limit headers use, e.g.
+/// `38s`, `6m12s`, `250ms`, or a bare number of seconds.
+fn parse_compact_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    if let Ok(seconds) = value.parse::<f64>() {
+        return Duration::try_from_secs_f64(seconds).ok();
+    }
+
+    let mut total = Duration::ZERO;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let digits_end = rest.find(|ch: char| !ch.is_ascii_digit() && ch != '.')?;
+        if digits_end == 0 {
+            return None;
+        }
+        let (number, tail) = rest.split_at(digits_end);
+        let number = number.parse::<f64>().ok()?;
+        let unit_end = tail
+            .find(|ch: char| !ch.is_ascii_alphabetic())
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_end);
+        let seconds = match unit {
+            "h" => number * 3600.0,
+            "m" => number * 60.0,
+            "s" => number,
+            "ms" => number / 1000.0,
+            _ => return None,
+        };
+        total = total.checked_add(Duration::try_from_secs_f64(seconds).ok()?)?;
+        rest = tail;
+    }
+    Some(total)
+}
+
+fn delay_until_rfc3339(value: &str) -> Option<Duration> {
+    let at = DateTime::parse_from_rfc3339(value.trim()).ok()?;
+    at.signed_duration_since(Utc::now()).to_std().ok()
+}
+
 fn has_rate_limit_data(snapshot: &RateLimitSnapshot) -> bool {
     snapshot.primary.is_some() || snapshot.secondary.is_some() || snapshot.credits.is_some()
 }
@@ -375,4 +461,82 @@ mod tests {
         assert_eq!(updates[0].secondary, None);
         assert_eq!(updates[0].credits, None);
     }
+
+    #[test]
+    fn retry_after_from_headers_parses_numeric_seconds() {
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", HeaderValue::from_static("38"));
+
+        assert_eq!(
+            retry_after_from_headers(&headers),
+            Some(Duration::from_secs(38))
+        );
+    }
+
+    #[test]
+    fn retry_after_from_headers_parses_http_date() {
+        let at = Utc::now() + chrono::Duration::seconds(90);
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "retry-after",
+            HeaderValue::from_str(&at.to_rfc2822()).expect("header value"),
+        );
+
+        let delay = retry_after_from_headers(&headers).expect("delay");
+        assert!(delay <= Duration::from_secs(90));
+        assert!(delay >= Duration::from_secs(85));
+    }
+
+    #[test]
+    fn retry_after_from_headers_parses_openai_reset_durations() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-ratelimit-reset-requests",
+            HeaderValue::from_static("6m12s"),
+        );
+        headers.insert("x-ratelimit-reset-tokens", HeaderValue::from_static("38s"));
+
+        assert_eq!(
+            retry_after_from_headers(&headers),
+            Some(Duration::from_secs(38))
+        );
+    }
+
+    #[test]
+    fn retry_after_from_headers_parses_anthropic_reset_timestamp() {
+        let at = Utc::now() + chrono::Duration::seconds(45);
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "anthropic-ratelimit-requests-reset",
+            HeaderValue::from_str(&at.to_rfc3339()).expect("header value"),
+        );
+
+        let delay = retry_after_from_headers(&headers).expect("delay");
+        assert!(delay <= Duration::from_secs(45));
+        assert!(delay >= Duration::from_secs(40));
+    }
+
+    #[test]
+    fn retry_after_from_headers_prefers_retry_after_over_reset_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", HeaderValue::from_static("10"));
+        headers.insert("x-ratelimit-reset-tokens", HeaderValue::from_static("2s"));
+
+        assert_eq!(
+            retry_after_from_headers(&headers),
+            Some(Duration::from_secs(10))
+        );
+    }
+
+    #[test]
+    fn retry_after_from_headers_ignores_unparseable_values() {
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", HeaderValue::from_static("soon"));
+        headers.insert(
+            "x-ratelimit-reset-requests",
+            HeaderValue::from_static("later"),
+        );
+
+        assert_eq!(retry_after_from_headers(&headers), None);
+    }
 }
@@ -0,0 +1,203 @@
+//! Translates a Responses API request into the Anthropic Messages API shape.
+//!
+//! The OpenAI-compat shim Anthropic exposes drops provider-native features;
+//! translating ourselves keeps prompt caching (`cache_control` on the system
+//! prompt) and native `tool_use`/`tool_result` blocks.
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::ResponseItem;
+use serde_json::Value;
+use serde_json::json;
+
+use crate::common::ResponsesApiRequest;
+
+/// Messages API requires an explicit output cap; we have no per-request value
+/// in the Responses shape, so use a generous fixed ceiling.
+const DEFAULT_MAX_TOKENS: u64 = 32_000;
+
+/// Where to place `cache_control` prompt-cache breakpoints in the translated
+/// Messages request.
+#[derive(Clone, Copy, Debug)]
+pub struct AnthropicCacheOptions {
+    /// Mark the system prompt block as a breakpoint; this also covers the
+    /// tool definitions that precede it in the cached prefix.
+    pub cache_system_prompt: bool,
+    /// Mark the last tool definition as an additional breakpoint so the tool
+    /// schemas stay cached even when the system prompt changes.
+    pub cache_tool_definitions: bool,
+}
+
+impl Default for AnthropicCacheOptions {
+    fn default() -> Self {
+        Self {
+            cache_system_prompt: true,
+            cache_tool_definitions: false,
+        }
+    }
+}
+
+/// Builds the JSON body for `POST /v1/messages` from a Responses request.
+pub fn build_anthropic_messages_body(
+    request: &ResponsesApiRequest,
+    cache: AnthropicCacheOptions,
+) -> Value {
+    let mut body = json!({
+        "model": request.model,
+        "max_tokens": DEFAULT_MAX_TOKENS,
+        "stream": request.stream,
+        "messages": build_messages(&request.input),
+    });
+    if !request.instructions.is_empty() {
+        let mut system = json!({
+            "type": "text",
+            "text": request.instructions,
+        });
+        if cache.cache_system_prompt {
+            // `cache_control` marks the instruction block as a prompt-cache
+            // breakpoint so repeated turns reuse the cached prefix.
+            system["cache_control"] = json!({"type": "ephemeral"});
+        }
+        body["system"] = Value::Array(vec![system]);
+    }
+    if let Some(tools) = request.tools.as_ref() {
+        let mut tools: Vec<Value> = tools.iter().filter_map(translate_tool).collect();
+        if !tools.is_empty() {
+            if cache.cache_tool_definitions
+                && let Some(last) = tools.last_mut()
+            {
+                last["cache_control"] = json!({"type": "ephemeral"});
+            }
+            body["tools"] = Value::Array(tools);
+            body["tool_choice"] = match request.tool_choice.as_str() {
+                "none" => json!({"type": "none"}),
+                "required" => json!({"type": "any"}),
+                _ => json!({"type": "auto"}),
+            };
+        }
+    }
+    body
+}
+
+/// Maps a Responses function tool definition onto an Anthropic tool. Tool
+/// types without an Anthropic equivalent (hosted web search, etc.) are
+/// dropped rather than sent as malformed definitions.
+fn translate_tool(tool: &Value) -> Option<Value> {
+    if tool.get("type").and_then(Value::as_str) != Some("function") {
+        return None;
+    }
+    let name = tool.get("name")?.as_str()?;
+    let mut translated = json!({
+        "name": name,
+        "input_schema": tool.get("parameters").cloned().unwrap_or(json!({"type": "object"})),
+    });
+    if let Some(description) = tool.get("description").and_then(Value::as_str) {
+        translated["description"] = json!(description);
+    }
+    Some(translated)
+}
+
+fn build_messages(input: &[ResponseItem]) -> Vec<Value> {
+    let mut messages: Vec<(String, Vec<Value>)> = Vec::new();
+    for item in input {
+        let (role, blocks) = match item {
+            ResponseItem::Message { role, content, .. } => {
+                let role = if role == "assistant" {
+                    "assistant"
+                } else {
+                    // System/developer text travels in `system`; anything
+                    // else in the transcript is presented as user input.
+                    "user"
+                };
+                let blocks: Vec<Value> = content.iter().filter_map(content_block).collect();
+                (role.to_string(), blocks)
+            }
+            ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            } => {
+                let input: Value = serde_json::from_str(arguments).unwrap_or_else(|_| json!({}));
+                (
+                    "assistant".to_string(),
+                    vec![json!({
+                        "type": "tool_use",
+                        "id": call_id,
+                        "name": name,
+                        "input": input,
+                    })],
+                )
+            }
+            ResponseItem::FunctionCallOutput {
+                call_id, output, ..
+            } => {
+                let mut block = json!({
+                    "type": "tool_result",
+                    "tool_use_id": call_id,
+                    "content": function_output_text(&output.body),
+                });
+                if output.success == Some(false) {
+                    block["is_error"] = json!(true);
+                }
+                ("user".to_string(), vec![block])
+            }
+            // Reasoning, web-search results, and other Responses-only items
+            // have no Messages equivalent and are reconstructed server-side.
+            _ => continue,
+        };
+        if blocks.is_empty() {
+            continue;
+        }
+        match messages.last_mut() {
+            // The Messages API requires alternating roles; fold consecutive
+            // same-role items into one message.
+            Some((last_role, last_blocks)) if *last_role == role => last_blocks.extend(blocks),
+            _ => messages.push((role, blocks)),
+        }
+    }
+    messages
+        .into_iter()
+        .map(|(role, content)| json!({"role": role, "content": content}))
+        .collect()
+}
+
+fn content_block(item: &ContentItem) -> Option<Value> {
+    match item {
+        ContentItem::InputText { text }
+        | ContentItem::OutputText { text }
+        | ContentItem::Refusal { refusal: text } => Some(json!({"type": "text", "text": text})),
+        ContentItem::InputImage { image_url, .. } => Some(image_block(image_url)),
+    }
+}
+
+/// Anthropic takes images as either base64 data or a plain URL source.
+fn image_block(image_url: &str) -> Value {
+    if let Some(rest) = image_url.strip_prefix("data:")
+        && let Some((media_type, data)) = rest.split_once(";base64,")
+    {
+        return json!({
+            "type": "image",
+            "source": {"type": "base64", "media_type": media_type, "data": data},
+        });
+    }
+    json!({
+        "type": "image",
+        "source": {"type": "url", "url": image_url},
+    })
+}
+
+fn function_output_text(body: &FunctionCallOutputBody) -> String {
+    match body {
+        FunctionCallOutputBody::Text(text) => text.clone(),
+        FunctionCallOutputBody::ContentItems(items) => items
+            .iter()
+            .filter_map(|item| match item {
+                FunctionCallOutputContentItem::InputText { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
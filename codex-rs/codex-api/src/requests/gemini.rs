@@ -0,0 +1,193 @@
+//! Translates a Responses API request into Google's native
+//! `generateContent` shape.
+//!
+//! The OpenAI-compat endpoint Google exposes lags the native API and rewrites
+//! function schemas into its own subset badly; translating ourselves keeps
+//! `system_instruction`, native function calling, and inline image parts.
+
+use std::collections::HashMap;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::ResponseItem;
+use serde_json::Value;
+use serde_json::json;
+
+use crate::common::ResponsesApiRequest;
+
+/// JSON Schema keywords Gemini's `Schema` type does not accept. Sending them
+/// fails the whole request, so they are stripped recursively.
+const UNSUPPORTED_SCHEMA_KEYS: &[&str] = &[
+    "$schema",
+    "additionalProperties",
+    "default",
+    "exclusiveMaximum",
+    "exclusiveMinimum",
+    "oneOf",
+    "allOf",
+];
+
+/// Builds the JSON body for `POST /models/{model}:streamGenerateContent`.
+pub fn build_gemini_generate_content_body(request: &ResponsesApiRequest) -> Value {
+    let mut body = json!({
+        "contents": build_contents(&request.input),
+    });
+    if !request.instructions.is_empty() {
+        body["systemInstruction"] = json!({
+            "parts": [{"text": request.instructions}],
+        });
+    }
+    if let Some(tools) = request.tools.as_ref() {
+        let declarations: Vec<Value> = tools.iter().filter_map(translate_tool).collect();
+        if !declarations.is_empty() {
+            body["tools"] = json!([{"functionDeclarations": declarations}]);
+            let mode = match request.tool_choice.as_str() {
+                "none" => "NONE",
+                "required" => "ANY",
+                _ => "AUTO",
+            };
+            body["toolConfig"] = json!({"functionCallingConfig": {"mode": mode}});
+        }
+    }
+    body
+}
+
+/// Maps a Responses function tool onto a Gemini function declaration. Tool
+/// types without a native equivalent are dropped rather than sent mangled.
+fn translate_tool(tool: &Value) -> Option<Value> {
+    if tool.get("type").and_then(Value::as_str) != Some("function") {
+        return None;
+    }
+    let name = tool.get("name")?.as_str()?;
+    let mut parameters = tool
+        .get("parameters")
+        .cloned()
+        .unwrap_or_else(|| json!({"type": "object"}));
+    sanitize_schema(&mut parameters);
+    let mut declaration = json!({
+        "name": name,
+        "parameters": parameters,
+    });
+    if let Some(description) = tool.get("description").and_then(Value::as_str) {
+        declaration["description"] = json!(description);
+    }
+    Some(declaration)
+}
+
+/// Removes schema keywords Gemini rejects, recursing into nested schemas.
+fn sanitize_schema(schema: &mut Value) {
+    let Value::Object(map) = schema else {
+        return;
+    };
+    for key in UNSUPPORTED_SCHEMA_KEYS {
+        map.remove(*key);
+    }
+    if let Some(properties) = map.get_mut("properties").and_then(Value::as_object_mut) {
+        for property in properties.values_mut() {
+            sanitize_schema(property);
+        }
+    }
+    if let Some(items) = map.get_mut("items") {
+        sanitize_schema(items);
+    }
+}
+
+fn build_contents(input: &[ResponseItem]) -> Vec<Value> {
+    // Gemini's `functionResponse` parts are matched by function name, not
+    // call id, so map ids back to names from the preceding calls.
+    let mut call_names: HashMap<&str, &str> = HashMap::new();
+    for item in input {
+        if let ResponseItem::FunctionCall { name, call_id, .. } = item {
+            call_names.insert(call_id.as_str(), name.as_str());
+        }
+    }
+
+    let mut contents: Vec<(String, Vec<Value>)> = Vec::new();
+    for item in input {
+        let (role, parts) = match item {
+            ResponseItem::Message { role, content, .. } => {
+                let role = if role == "assistant" { "model" } else { "user" };
+                let parts: Vec<Value> = content.iter().filter_map(content_part).collect();
+                (role.to_string(), parts)
+            }
+            ResponseItem::FunctionCall {
+                name, arguments, ..
+            } => {
+                let args: Value = serde_json::from_str(arguments).unwrap_or_else(|_| json!({}));
+                (
+                    "model".to_string(),
+                    vec![json!({
+                        "functionCall": {"name": name, "args": args},
+                    })],
+                )
+            }
+            ResponseItem::FunctionCallOutput {
+                call_id, output, ..
+            } => {
+                let name = call_names
+                    .get(call_id.as_str())
+                    .copied()
+                    .unwrap_or(call_id.as_str());
+                (
+                    "user".to_string(),
+                    vec![json!({
+                        "functionResponse": {
+                            "name": name,
+                            "response": {"output": function_output_text(&output.body)},
+                        },
+                    })],
+                )
+            }
+            // Reasoning and other Responses-only items have no native
+            // equivalent and are reconstructed server-side.
+            _ => continue,
+        };
+        if parts.is_empty() {
+            continue;
+        }
+        match contents.last_mut() {
+            // The API requires alternating roles; fold consecutive same-role
+            // items into one content entry.
+            Some((last_role, last_parts)) if *last_role == role => last_parts.extend(parts),
+            _ => contents.push((role, parts)),
+        }
+    }
+    contents
+        .into_iter()
+        .map(|(role, parts)| json!({"role": role, "parts": parts}))
+        .collect()
+}
+
+fn content_part(item: &ContentItem) -> Option<Value> {
+    match item {
+        ContentItem::InputText { text }
+        | ContentItem::OutputText { text }
+        | ContentItem::Refusal { refusal: text } => Some(json!({"text": text})),
+        ContentItem::InputImage { image_url, .. } => image_part(image_url),
+    }
+}
+
+/// Gemini takes inline images as base64 `inlineData`; plain URLs have no
+/// supported source type and are dropped.
+fn image_part(image_url: &str) -> Option<Value> {
+    let rest = image_url.strip_prefix("data:")?;
+    let (media_type, data) = rest.split_once(";base64,")?;
+    Some(json!({
+        "inlineData": {"mimeType": media_type, "data": data},
+    }))
+}
+
+fn function_output_text(body: &FunctionCallOutputBody) -> String {
+    match body {
+        FunctionCallOutputBody::Text(text) => text.clone(),
+        FunctionCallOutputBody::ContentItems(items) => items
+            .iter()
+            .filter_map(|item| match item {
+                FunctionCallOutputContentItem::InputText { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
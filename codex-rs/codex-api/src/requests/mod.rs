@@ -1,4 +1,9 @@
+pub(crate) mod anthropic;
+pub(crate) mod gemini;
 pub(crate) mod headers;
 pub(crate) mod responses;
 
+pub use anthropic::AnthropicCacheOptions;
+pub use anthropic::build_anthropic_messages_body;
+pub use gemini::build_gemini_generate_content_body;
 pub use responses::Compression;
@@ -0,0 +1,451 @@
+//! Translates Anthropic Messages streaming events into `ResponseEvent`s.
+//!
+//! The Messages stream is block-oriented: `content_block_start` opens a text
+//! or `tool_use` block at an index, deltas arrive per block, and
+//! `message_delta`/`message_stop` close the message with usage and a stop
+//! reason. We accumulate per-block state and emit the same event shapes the
+//! Responses SSE path produces so downstream consumers are wire-agnostic.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use codex_client::ByteStream;
+use codex_client::StreamResponse;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use eventsource_stream::Eventsource;
+use futures::StreamExt;
+use serde_json::Value;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::timeout;
+use tracing::debug;
+use tracing::trace;
+
+use crate::common::ResponseEvent;
+use crate::common::ResponseStream;
+use crate::error::ApiError;
+use crate::rate_limits::parse_all_rate_limits;
+use crate::telemetry::SseTelemetry;
+
+const REQUEST_ID_HEADER: &str = "request-id";
+
+/// Spawns a task translating an Anthropic Messages SSE response into the
+/// shared `ResponseStream` shape.
+pub fn spawn_anthropic_response_stream(
+    stream_response: StreamResponse,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+) -> ResponseStream {
+    let rate_limit_snapshots = parse_all_rate_limits(&stream_response.headers);
+    let upstream_request_id = stream_response
+        .headers
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(async move {
+        for snapshot in rate_limit_snapshots {
+            let _ = tx_event.send(Ok(ResponseEvent::RateLimits(snapshot))).await;
+        }
+        process_anthropic_sse(stream_response.bytes, tx_event, idle_timeout, telemetry).await;
+    });
+
+    ResponseStream {
+        rx_event,
+        upstream_request_id,
+    }
+}
+
+async fn process_anthropic_sse(
+    stream: ByteStream,
+    tx_event: mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+) {
+    let mut stream = stream.eventsource();
+    let mut state = MessageState::default();
+
+    loop {
+        let start = Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        if let Some(t) = telemetry.as_ref() {
+            t.on_sse_poll(&response, start.elapsed());
+        }
+        let sse = match response {
+            Ok(Some(Ok(sse))) => sse,
+            Ok(Some(Err(e))) => {
+                debug!("SSE Error: {e:#}");
+                let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
+                return;
+            }
+            Ok(None) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream(
+                        "stream closed before message_stop".into(),
+                    )))
+                    .await;
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .await;
+                return;
+            }
+        };
+
+        trace!("Anthropic SSE event: {}", &sse.data);
+        let data: Value = match serde_json::from_str(&sse.data) {
+            Ok(data) => data,
+            Err(e) => {
+                debug!("Failed to parse SSE event: {e}, data: {}", &sse.data);
+                continue;
+            }
+        };
+
+        let completed = matches!(event_type(&data), Some("message_stop"));
+        for event in state.process_event(&data) {
+            let is_error = event.is_err();
+            if tx_event.send(event).await.is_err() || is_error {
+                return;
+            }
+        }
+        if completed {
+            return;
+        }
+    }
+}
+
+/// Accumulated content for one streamed block index.
+enum BlockState {
+    Text(String),
+    ToolUse {
+        id: String,
+        name: String,
+        input_json: String,
+    },
+}
+
+/// Mutable translation state for a single streamed message.
+#[derive(Default)]
+struct MessageState {
+    message_id: String,
+    input_tokens: i64,
+    cached_input_tokens: i64,
+    output_tokens: i64,
+    stop_reason: Option<String>,
+    blocks: BTreeMap<i64, BlockState>,
+}
+
+impl MessageState {
+    /// Feeds one decoded stream event and returns the `ResponseEvent`s it
+    /// translates to, in emission order.
+    fn process_event(&mut self, data: &Value) -> Vec<Result<ResponseEvent, ApiError>> {
+        match event_type(data) {
+            Some("message_start") => {
+                if let Some(message) = data.get("message") {
+                    self.message_id = str_field(message, "id").unwrap_or_default();
+                    if let Some(usage) = message.get("usage") {
+                        self.record_usage(usage);
+                    }
+                }
+                vec![Ok(ResponseEvent::Created)]
+            }
+            Some("content_block_start") => {
+                let Some(index) = data.get("index").and_then(Value::as_i64) else {
+                    return Vec::new();
+                };
+                let Some(block) = data.get("content_block") else {
+                    return Vec::new();
+                };
+                match block.get("type").and_then(Value::as_str) {
+                    Some("tool_use") => {
+                        self.blocks.insert(
+                            index,
+                            BlockState::ToolUse {
+                                id: str_field(block, "id").unwrap_or_default(),
+                                name: str_field(block, "name").unwrap_or_default(),
+                                input_json: String::new(),
+                            },
+                        );
+                    }
+                    _ => {
+                        self.blocks.insert(
+                            index,
+                            BlockState::Text(str_field(block, "text").unwrap_or_default()),
+                        );
+                    }
+                }
+                Vec::new()
+            }
+            Some("content_block_delta") => {
+                let Some(index) = data.get("index").and_then(Value::as_i64) else {
+                    return Vec::new();
+                };
+                let Some(delta) = data.get("delta") else {
+                    return Vec::new();
+                };
+                match (
+                    self.blocks.get_mut(&index),
+                    delta.get("type").and_then(Value::as_str),
+                ) {
+                    (Some(BlockState::Text(text)), Some("text_delta")) => {
+                        let Some(chunk) = str_field(delta, "text") else {
+                            return Vec::new();
+                        };
+                        text.push_str(&chunk);
+                        vec![Ok(ResponseEvent::OutputTextDelta(chunk))]
+                    }
+                    (
+                        Some(BlockState::ToolUse { id, input_json, .. }),
+                        Some("input_json_delta"),
+                    ) => {
+                        let Some(chunk) = str_field(delta, "partial_json") else {
+                            return Vec::new();
+                        };
+                        input_json.push_str(&chunk);
+                        vec![Ok(ResponseEvent::ToolCallInputDelta {
+                            item_id: id.clone(),
+                            call_id: Some(id.clone()),
+                            delta: chunk,
+                        })]
+                    }
+                    _ => Vec::new(),
+                }
+            }
+            Some("content_block_stop") => {
+                let Some(index) = data.get("index").and_then(Value::as_i64) else {
+                    return Vec::new();
+                };
+                match self.blocks.remove(&index) {
+                    Some(BlockState::Text(text)) => {
+                        vec![Ok(ResponseEvent::OutputItemDone(ResponseItem::Message {
+                            id: None,
+                            role: "assistant".to_string(),
+                            content: vec![ContentItem::OutputText { text }],
+                            phase: None,
+                            internal_chat_message_metadata_passthrough: None,
+                        }))]
+                    }
+                    Some(BlockState::ToolUse {
+                        id,
+                        name,
+                        input_json,
+                    }) => {
+                        let arguments = if input_json.is_empty() {
+                            "{}".to_string()
+                        } else {
+                            input_json
+                        };
+                        vec![Ok(ResponseEvent::OutputItemDone(
+                            ResponseItem::FunctionCall {
+                                id: None,
+                                name,
+                                namespace: None,
+                                arguments,
+                                call_id: id,
+                                internal_chat_message_metadata_passthrough: None,
+                            },
+                        ))]
+                    }
+                    None => Vec::new(),
+                }
+            }
+            Some("message_delta") => {
+                if let Some(stop_reason) = data
+                    .get("delta")
+                    .and_then(|delta| str_field(delta, "stop_reason"))
+                {
+                    self.stop_reason = Some(stop_reason);
+                }
+                if let Some(usage) = data.get("usage") {
+                    self.record_usage(usage);
+                }
+                Vec::new()
+            }
+            Some("message_stop") => {
+                vec![Ok(ResponseEvent::Completed {
+                    response_id: std::mem::take(&mut self.message_id),
+                    token_usage: Some(self.token_usage()),
+                    // `tool_use` and `max_tokens` stops leave the turn open.
+                    end_turn: self
+                        .stop_reason
+                        .as_deref()
+                        .map(|reason| reason == "end_turn"),
+                })]
+            }
+            Some("error") => {
+                let message = data
+                    .get("error")
+                    .and_then(|error| str_field(error, "message"))
+                    .unwrap_or_else(|| "unknown Anthropic stream error".to_string());
+                vec![Err(ApiError::Stream(message))]
+            }
+            // `ping` and future event types are ignorable by contract.
+            _ => Vec::new(),
+        }
+    }
+
+    fn record_usage(&mut self, usage: &Value) {
+        for (field, slot) in [
+            ("input_tokens", &mut self.input_tokens),
+            ("output_tokens", &mut self.output_tokens),
+            ("cache_read_input_tokens", &mut self.cached_input_tokens),
+        ] {
+            if let Some(value) = usage.get(field).and_then(Value::as_i64) {
+                *slot = value;
+            }
+        }
+    }
+
+    fn token_usage(&self) -> TokenUsage {
+        // Anthropic reports `input_tokens` excluding cache reads; fold the
+        // cached portion back in so totals line up with other providers.
+        let input_tokens = self.input_tokens + self.cached_input_tokens;
+        TokenUsage {
+            input_tokens,
+            cached_input_tokens: self.cached_input_tokens,
+            output_tokens: self.output_tokens,
+            reasoning_output_tokens: 0,
+            total_tokens: input_tokens + self.output_tokens,
+        }
+    }
+}
+
+fn event_type(data: &Value) -> Option<&str> {
+    data.get("type").and_then(Value::as_str)
+}
+
+fn str_field(data: &Value, field: &str) -> Option<String> {
+    data.get(field).and_then(Value::as_str).map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn events(state: &mut MessageState, data: Value) -> Vec<ResponseEvent> {
+        state
+            .process_event(&data)
+            .into_iter()
+            .map(|event| event.expect("expected Ok event"))
+            .collect()
+    }
+
+    #[test]
+    fn translates_text_stream_into_deltas_and_message() {
+        let mut state = MessageState::default();
+        events(
+            &mut state,
+            json!({"type": "message_start", "message": {"id": "msg_1", "usage": {"input_tokens": 10}}}),
+        );
+        events(
+            &mut state,
+            json!({"type": "content_block_start", "index": 0, "content_block": {"type": "text", "text": ""}}),
+        );
+        let deltas = events(
+            &mut state,
+            json!({"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": "Hello"}}),
+        );
+        assert_matches!(
+            deltas.as_slice(),
+            [ResponseEvent::OutputTextDelta(text)] if text == "Hello"
+        );
+        let done = events(
+            &mut state,
+            json!({"type": "content_block_stop", "index": 0}),
+        );
+        assert_matches!(
+            done.as_slice(),
+            [ResponseEvent::OutputItemDone(ResponseItem::Message { role, content, .. })]
+                if role == "assistant"
+                    && *content == vec![ContentItem::OutputText { text: "Hello".to_string() }]
+        );
+    }
+
+    #[test]
+    fn accumulates_tool_use_input_and_emits_function_call() {
+        let mut state = MessageState::default();
+        events(
+            &mut state,
+            json!({"type": "content_block_start", "index": 0, "content_block": {"type": "tool_use", "id": "toolu_1", "name": "shell"}}),
+        );
+        events(
+            &mut state,
+            json!({"type": "content_block_delta", "index": 0, "delta": {"type": "input_json_delta", "partial_json": "{\"command\":"}}),
+        );
+        events(
+            &mut state,
+            json!({"type": "content_block_delta", "index": 0, "delta": {"type": "input_json_delta", "partial_json": "[\"ls\"]}"}}),
+        );
+        let done = events(
+            &mut state,
+            json!({"type": "content_block_stop", "index": 0}),
+        );
+        assert_matches!(
+            done.as_slice(),
+            [ResponseEvent::OutputItemDone(ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            })] if name == "shell"
+                && arguments == "{\"command\":[\"ls\"]}"
+                && call_id == "toolu_1"
+        );
+    }
+
+    #[test]
+    fn message_stop_carries_usage_and_end_turn() {
+        let mut state = MessageState::default();
+        events(
+            &mut state,
+            json!({"type": "message_start", "message": {"id": "msg_1", "usage": {"input_tokens": 100, "cache_read_input_tokens": 40}}}),
+        );
+        events(
+            &mut state,
+            json!({"type": "message_delta", "delta": {"stop_reason": "end_turn"}, "usage": {"output_tokens": 25}}),
+        );
+        let completed = events(&mut state, json!({"type": "message_stop"}));
+        let [
+            ResponseEvent::Completed {
+                response_id,
+                token_usage,
+                end_turn,
+            },
+        ] = completed.as_slice()
+        else {
+            panic!("expected Completed, got {completed:?}");
+        };
+        assert_eq!(response_id, "msg_1");
+        assert_eq!(*end_turn, Some(true));
+        assert_eq!(
+            *token_usage,
+            Some(TokenUsage {
+                input_tokens: 140,
+                cached_input_tokens: 40,
+                output_tokens: 25,
+                reasoning_output_tokens: 0,
+                total_tokens: 165,
+            })
+        );
+    }
+
+    #[test]
+    fn error_events_surface_as_stream_errors() {
+        let mut state = MessageState::default();
+        let result = state.process_event(
+            &json!({"type": "error", "error": {"type": "overloaded_error", "message": "Overloaded"}}),
+        );
+        assert_matches!(
+            result.as_slice(),
+            [Err(ApiError::Stream(message))] if message == "Overloaded"
+        );
+    }
+}
@@ -0,0 +1,382 @@
+//! Translates Gemini `streamGenerateContent` chunks into `ResponseEvent`s.
+//!
+//! The stream is a sequence of `GenerateContentResponse` JSON chunks: text
+//! parts are incremental deltas, function calls arrive whole within one
+//! chunk, and the final chunk carries `finishReason` plus `usageMetadata`.
+//! There is no terminal event; the stream simply ends. We accumulate text
+//! across chunks and emit the same event shapes the Responses SSE path
+//! produces so downstream consumers are wire-agnostic.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use codex_client::ByteStream;
+use codex_client::StreamResponse;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use eventsource_stream::Eventsource;
+use futures::StreamExt;
+use serde_json::Value;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::timeout;
+use tracing::debug;
+use tracing::trace;
+
+use crate::common::ResponseEvent;
+use crate::common::ResponseStream;
+use crate::error::ApiError;
+use crate::rate_limits::parse_all_rate_limits;
+use crate::telemetry::SseTelemetry;
+
+/// Spawns a task translating a Gemini streaming response into the shared
+/// `ResponseStream` shape.
+pub fn spawn_gemini_response_stream(
+    stream_response: StreamResponse,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+) -> ResponseStream {
+    let rate_limit_snapshots = parse_all_rate_limits(&stream_response.headers);
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(async move {
+        for snapshot in rate_limit_snapshots {
+            let _ = tx_event.send(Ok(ResponseEvent::RateLimits(snapshot))).await;
+        }
+        process_gemini_sse(stream_response.bytes, tx_event, idle_timeout, telemetry).await;
+    });
+
+    ResponseStream {
+        rx_event,
+        upstream_request_id: None,
+    }
+}
+
+async fn process_gemini_sse(
+    stream: ByteStream,
+    tx_event: mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+) {
+    let mut stream = stream.eventsource();
+    let mut state = GenerationState::default();
+
+    loop {
+        let start = Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        if let Some(t) = telemetry.as_ref() {
+            t.on_sse_poll(&response, start.elapsed());
+        }
+        let chunk = match response {
+            Ok(Some(Ok(sse))) => sse,
+            Ok(Some(Err(e))) => {
+                debug!("SSE Error: {e:#}");
+                let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
+                return;
+            }
+            // End of stream is the normal completion signal.
+            Ok(None) => {
+                for event in state.finish() {
+                    if tx_event.send(event).await.is_err() {
+                        return;
+                    }
+                }
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .await;
+                return;
+            }
+        };
+
+        trace!("Gemini SSE chunk: {}", &chunk.data);
+        let data: Value = match serde_json::from_str(&chunk.data) {
+            Ok(data) => data,
+            Err(e) => {
+                debug!("Failed to parse SSE chunk: {e}, data: {}", &chunk.data);
+                continue;
+            }
+        };
+
+        for event in state.process_chunk(&data) {
+            let is_error = event.is_err();
+            if tx_event.send(event).await.is_err() || is_error {
+                return;
+            }
+        }
+    }
+}
+
+/// Mutable translation state for one streamed generation.
+#[derive(Default)]
+struct GenerationState {
+    started: bool,
+    response_id: String,
+    text: String,
+    function_calls: usize,
+    finish_reason: Option<String>,
+    input_tokens: i64,
+    cached_input_tokens: i64,
+    output_tokens: i64,
+    reasoning_output_tokens: i64,
+}
+
+impl GenerationState {
+    /// Feeds one decoded chunk and returns the `ResponseEvent`s it translates
+    /// to, in emission order.
+    fn process_chunk(&mut self, data: &Value) -> Vec<Result<ResponseEvent, ApiError>> {
+        if let Some(error) = data.get("error") {
+            let message = error
+                .get("message")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown Gemini stream error")
+                .to_string();
+            return vec![Err(ApiError::Stream(message))];
+        }
+
+        let mut events = Vec::new();
+        if !self.started {
+            self.started = true;
+            events.push(Ok(ResponseEvent::Created));
+        }
+        if let Some(id) = data.get("responseId").and_then(Value::as_str) {
+            self.response_id = id.to_string();
+        }
+        if let Some(usage) = data.get("usageMetadata") {
+            self.record_usage(usage);
+        }
+
+        let Some(candidate) = data
+            .get("candidates")
+            .and_then(Value::as_array)
+            .and_then(|candidates| candidates.first())
+        else {
+            return events;
+        };
+        if let Some(reason) = candidate.get("finishReason").and_then(Value::as_str) {
+            self.finish_reason = Some(reason.to_string());
+        }
+        let parts = candidate
+            .get("content")
+            .and_then(|content| content.get("parts"))
+            .and_then(Value::as_array);
+        for part in parts.into_iter().flatten() {
+            if let Some(text) = part.get("text").and_then(Value::as_str) {
+                self.text.push_str(text);
+                events.push(Ok(ResponseEvent::OutputTextDelta(text.to_string())));
+            } else if let Some(call) = part.get("functionCall") {
+                events.push(Ok(self.function_call_item(call)));
+            }
+        }
+        events
+    }
+
+    /// Function calls arrive whole; emit them as completed items directly.
+    /// Gemini has no call ids, so synthesize one stable within the turn.
+    fn function_call_item(&mut self, call: &Value) -> ResponseEvent {
+        let name = call
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        let arguments = call
+            .get("args")
+            .map(Value::to_string)
+            .unwrap_or_else(|| "{}".to_string());
+        self.function_calls += 1;
+        ResponseEvent::OutputItemDone(ResponseItem::FunctionCall {
+            id: None,
+            name: name.clone(),
+            namespace: None,
+            arguments,
+            call_id: format!("{name}-{n}", n = self.function_calls),
+            internal_chat_message_metadata_passthrough: None,
+        })
+    }
+
+    /// Emits the trailing message and `Completed` once the stream ends.
+    fn finish(&mut self) -> Vec<Result<ResponseEvent, ApiError>> {
+        let mut events = Vec::new();
+        if !self.text.is_empty() {
+            events.push(Ok(ResponseEvent::OutputItemDone(ResponseItem::Message {
+                id: None,
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText {
+                    text: std::mem::take(&mut self.text),
+                }],
+                phase: None,
+                internal_chat_message_metadata_passthrough: None,
+            })));
+        }
+        // A `STOP` finish still leaves the turn open when the model asked
+        // for tools; the caller owes it the function results.
+        let end_turn = self
+            .finish_reason
+            .as_deref()
+            .map(|reason| reason == "STOP" && self.function_calls == 0);
+        events.push(Ok(ResponseEvent::Completed {
+            response_id: std::mem::take(&mut self.response_id),
+            token_usage: Some(self.token_usage()),
+            end_turn,
+        }));
+        events
+    }
+
+    fn record_usage(&mut self, usage: &Value) {
+        for (field, slot) in [
+            ("promptTokenCount", &mut self.input_tokens),
+            ("candidatesTokenCount", &mut self.output_tokens),
+            ("cachedContentTokenCount", &mut self.cached_input_tokens),
+            ("thoughtsTokenCount", &mut self.reasoning_output_tokens),
+        ] {
+            if let Some(value) = usage.get(field).and_then(Value::as_i64) {
+                *slot = value;
+            }
+        }
+    }
+
+    fn token_usage(&self) -> TokenUsage {
+        let output_tokens = self.output_tokens + self.reasoning_output_tokens;
+        TokenUsage {
+            input_tokens: self.input_tokens,
+            cached_input_tokens: self.cached_input_tokens,
+            output_tokens,
+            reasoning_output_tokens: self.reasoning_output_tokens,
+            total_tokens: self.input_tokens + output_tokens,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn events(state: &mut GenerationState, data: Value) -> Vec<ResponseEvent> {
+        state
+            .process_chunk(&data)
+            .into_iter()
+            .map(|event| event.expect("expected Ok event"))
+            .collect()
+    }
+
+    #[test]
+    fn translates_text_chunks_into_deltas_and_trailing_message() {
+        let mut state = GenerationState::default();
+        let first = events(
+            &mut state,
+            json!({
+                "responseId": "resp_1",
+                "candidates": [{"content": {"parts": [{"text": "Hel"}]}}],
+            }),
+        );
+        assert_matches!(
+            first.as_slice(),
+            [
+                ResponseEvent::Created,
+                ResponseEvent::OutputTextDelta(text),
+            ] if text == "Hel"
+        );
+        events(
+            &mut state,
+            json!({
+                "candidates": [{
+                    "content": {"parts": [{"text": "lo"}]},
+                    "finishReason": "STOP",
+                }],
+                "usageMetadata": {"promptTokenCount": 12, "candidatesTokenCount": 3},
+            }),
+        );
+        let done: Vec<ResponseEvent> = state
+            .finish()
+            .into_iter()
+            .map(|event| event.expect("expected Ok event"))
+            .collect();
+        let [
+            ResponseEvent::OutputItemDone(ResponseItem::Message { role, content, .. }),
+            ResponseEvent::Completed {
+                response_id,
+                token_usage,
+                end_turn,
+            },
+        ] = done.as_slice()
+        else {
+            panic!("expected message and Completed, got {done:?}");
+        };
+        assert_eq!(role, "assistant");
+        assert_eq!(
+            *content,
+            vec![ContentItem::OutputText {
+                text: "Hello".to_string()
+            }]
+        );
+        assert_eq!(response_id, "resp_1");
+        assert_eq!(*end_turn, Some(true));
+        assert_eq!(
+            *token_usage,
+            Some(TokenUsage {
+                input_tokens: 12,
+                cached_input_tokens: 0,
+                output_tokens: 3,
+                reasoning_output_tokens: 0,
+                total_tokens: 15,
+            })
+        );
+    }
+
+    #[test]
+    fn function_calls_emit_items_and_keep_the_turn_open() {
+        let mut state = GenerationState::default();
+        let emitted = events(
+            &mut state,
+            json!({
+                "candidates": [{
+                    "content": {"parts": [{"functionCall": {"name": "shell", "args": {"command": ["ls"]}}}]},
+                    "finishReason": "STOP",
+                }],
+            }),
+        );
+        assert_matches!(
+            emitted.as_slice(),
+            [
+                ResponseEvent::Created,
+                ResponseEvent::OutputItemDone(ResponseItem::FunctionCall {
+                    name,
+                    arguments,
+                    call_id,
+                    ..
+                }),
+            ] if name == "shell"
+                && arguments == "{\"command\":[\"ls\"]}"
+                && call_id == "shell-1"
+        );
+        let done: Vec<ResponseEvent> = state
+            .finish()
+            .into_iter()
+            .map(|event| event.expect("expected Ok event"))
+            .collect();
+        assert_matches!(
+            done.as_slice(),
+            [ResponseEvent::Completed {
+                end_turn: Some(false),
+                ..
+            }]
+        );
+    }
+
+    #[test]
+    fn error_chunks_surface_as_stream_errors() {
+        let mut state = GenerationState::default();
+        let result = state.process_chunk(
+            &json!({"error": {"code": 429, "message": "Resource has been exhausted"}}),
+        );
+        assert_matches!(
+            result.as_slice(),
+            [Err(ApiError::Stream(message))] if message == "Resource has been exhausted"
+        );
+    }
+}
@@ -1,5 +1,11 @@
+pub(crate) mod anthropic;
+pub(crate) mod gemini;
 pub(crate) mod responses;
 
+pub use anthropic::spawn_anthropic_response_stream;
+pub use gemini::spawn_gemini_response_stream;
 pub(crate) use responses::ResponsesStreamEvent;
+pub use responses::StreamResumeFuture;
+pub use responses::StreamResumer;
 pub(crate) use responses::process_responses_event;
 pub use responses::spawn_response_stream;
@@ -16,6 +16,8 @@ use eventsource_stream::Eventsource;
 use futures::StreamExt;
 use serde::Deserialize;
 use serde_json::Value;
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
 use std::sync::OnceLock;
 use std::time::Duration;
@@ -31,11 +33,38 @@ const OPENAI_MODEL_HEADER: &str = "openai-model";
 const REQUEST_ID_HEADER: &str = "x-request-id";
 const TRUSTED_ACCESS_FOR_CYBER_VERIFICATION: &str = "trusted_access_for_cyber";
 
+/// Maximum number of in-place resume attempts per response stream before the
+/// error is surfaced to the caller and the turn is restarted from scratch.
+const MAX_STREAM_RESUME_ATTEMPTS: u32 = 3;
+
+/// Reopens an interrupted Responses SSE stream from a known cursor.
+///
+/// The per-request retry in `run_with_retry` replays the original request; a
+/// resumer instead continues the same response from the last sequence number
+/// the client observed, so already-streamed output is not regenerated.
+pub trait StreamResumer: Send + Sync {
+    /// Reopens the stream for `response_id`, skipping events up to and
+    /// including `starting_after`.
+    fn resume(&self, response_id: &str, starting_after: u64) -> StreamResumeFuture<'_>;
+}
+
+pub type StreamResumeFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<StreamResponse, ApiError>> + Send + 'a>>;
+
+/// Last position observed on a response stream, used to resume after a
+/// mid-stream disconnect.
+#[derive(Default)]
+struct StreamCursor {
+    response_id: Option<String>,
+    last_sequence_number: Option<u64>,
+}
+
 pub fn spawn_response_stream(
     stream_response: StreamResponse,
     idle_timeout: Duration,
     telemetry: Option<Arc<dyn SseTelemetry>>,
     turn_state: Option<Arc<OnceLock<String>>>,
+    resumer: Option<Arc<dyn StreamResumer>>,
 ) -> ResponseStream {
     let rate_limit_snapshots = parse_all_rate_limits(&stream_response.headers);
     let models_etag = stream_response
@@ -89,6 +118,7 @@ pub fn spawn_response_stream(
             idle_timeout,
             telemetry,
             safety_buffering_treatment,
+            resumer,
         )
         .await;
     });
@@ -161,6 +191,7 @@ pub struct ResponsesStreamEvent {
     #[serde(rename = "type")]
     pub(crate) kind: String,
     pub(crate) headers: Option<Value>,
+    sequence_number: Option<u64>,
     metadata: Option<Value>,
     response: Option<Value>,
     item: Option<Value>,
@@ -178,6 +209,14 @@ impl ResponsesStreamEvent {
         &self.kind
     }
 
+    /// Returns the response id carried by this event's `response` payload.
+    fn response_id(&self) -> Option<&str> {
+        self.response
+            .as_ref()
+            .and_then(|response| response.get("id"))
+            .and_then(Value::as_str)
+    }
+
     /// Returns the effective model reported by the server, if present.
     ///
     /// Precedence:
@@ -484,6 +523,7 @@ pub async fn process_sse(
         idle_timeout,
         telemetry,
         SafetyBufferingTreatment::default(),
+        /*resumer*/ None,
     )
     .await;
 }
@@ -494,10 +534,13 @@ async fn process_sse_with_treatment(
     idle_timeout: Duration,
     telemetry: Option<Arc<dyn SseTelemetry>>,
     safety_buffering_treatment: SafetyBufferingTreatment,
+    resumer: Option<Arc<dyn StreamResumer>>,
 ) {
     let mut stream = stream.eventsource();
     let mut response_error: Option<ApiError> = None;
     let mut last_server_model: Option<String> = None;
+    let mut cursor = StreamCursor::default();
+    let mut resume_attempts = 0u32;
 
     loop {
         let start = Instant::now();
@@ -509,10 +552,24 @@ async fn process_sse_with_treatment(
             Ok(Some(Ok(sse))) => sse,
             Ok(Some(Err(e))) => {
                 debug!("SSE Error: {e:#}");
+                if response_error.is_none()
+                    && let Some(resumed) =
+                        try_resume(resumer.as_deref(), &cursor, &mut resume_attempts).await
+                {
+                    stream = resumed.bytes.eventsource();
+                    continue;
+                }
                 let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
                 return;
             }
             Ok(None) => {
+                if response_error.is_none()
+                    && let Some(resumed) =
+                        try_resume(resumer.as_deref(), &cursor, &mut resume_attempts).await
+                {
+                    stream = resumed.bytes.eventsource();
+                    continue;
+                }
                 let error = response_error.unwrap_or(ApiError::Stream(
                     "stream closed before response.completed".into(),
                 ));
@@ -520,6 +577,13 @@ async fn process_sse_with_treatment(
                 return;
             }
             Err(_) => {
+                if response_error.is_none()
+                    && let Some(resumed) =
+                        try_resume(resumer.as_deref(), &cursor, &mut resume_attempts).await
+                {
+                    stream = resumed.bytes.eventsource();
+                    continue;
+                }
                 let _ = tx_event
                     .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
                     .await;
@@ -536,6 +600,21 @@ async fn process_sse_with_treatment(
                 continue;
             }
         };
+        if let Some(sequence_number) = event.sequence_number {
+            if cursor
+                .last_sequence_number
+                .is_some_and(|last| sequence_number <= last)
+            {
+                // Replayed by the server after a resume; already processed.
+                continue;
+            }
+            cursor.last_sequence_number = Some(sequence_number);
+        }
+        if cursor.response_id.is_none()
+            && let Some(response_id) = event.response_id()
+        {
+            cursor.response_id = Some(response_id.to_string());
+        }
         let model_verifications = event.model_verifications();
         let turn_moderation_metadata = event.turn_moderation_metadata();
         let safety_buffering = event.safety_buffering(&safety_buffering_treatment);
@@ -595,6 +674,36 @@ async fn process_sse_with_treatment(
     }
 }
 
+/// Attempts to reopen the stream from the last observed cursor.
+///
+/// Returns `None` — leaving the caller to surface the original error and
+/// trigger a full restart — when no resumer is configured, the stream never
+/// reported sequence numbers (the provider does not support resume), the
+/// attempt limit is reached, or the resume request itself fails.
+async fn try_resume(
+    resumer: Option<&dyn StreamResumer>,
+    cursor: &StreamCursor,
+    attempts: &mut u32,
+) -> Option<StreamResponse> {
+    let resumer = resumer?;
+    let response_id = cursor.response_id.as_deref()?;
+    let starting_after = cursor.last_sequence_number?;
+    if *attempts >= MAX_STREAM_RESUME_ATTEMPTS {
+        return None;
+    }
+    *attempts += 1;
+    match resumer.resume(response_id, starting_after).await {
+        Ok(resumed) => {
+            debug!("resumed response stream {response_id} after sequence {starting_after}");
+            Some(resumed)
+        }
+        Err(error) => {
+            debug!("failed to resume response stream {response_id}: {error:#}");
+            None
+        }
+    }
+}
+
 fn try_parse_retry_after(err: &Error) -> Option<Duration> {
     if err.code.as_deref() != Some("rate_limit_exceeded") {
         return None;
@@ -676,6 +785,8 @@ mod tests {
     use http::StatusCode;
     use pretty_assertions::assert_eq;
     use serde_json::json;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
     use tokio::sync::mpsc;
     use tokio_test::io::Builder as IoBuilder;
     use tokio_util::io::ReaderStream;
@@ -859,6 +970,149 @@ mod tests {
         }
     }
 
+    struct ScriptedResumer {
+        calls: Mutex<Vec<(String, u64)>>,
+        bodies: Mutex<VecDeque<Result<String, ApiError>>>,
+    }
+
+    impl ScriptedResumer {
+        fn new(bodies: Vec<Result<String, ApiError>>) -> Self {
+            Self {
+                calls: Mutex::new(Vec::new()),
+                bodies: Mutex::new(bodies.into_iter().collect()),
+            }
+        }
+
+        fn calls(&self) -> Vec<(String, u64)> {
+            self.calls.lock().expect("poisoned lock").clone()
+        }
+    }
+
+    impl StreamResumer for ScriptedResumer {
+        fn resume(&self, response_id: &str, starting_after: u64) -> StreamResumeFuture<'_> {
+            self.calls
+                .lock()
+                .expect("poisoned lock")
+                .push((response_id.to_string(), starting_after));
+            let next = self.bodies.lock().expect("poisoned lock").pop_front();
+            Box::pin(async move {
+                match next {
+                    Some(Ok(body)) => Ok(StreamResponse {
+                        status: StatusCode::OK,
+                        headers: HeaderMap::new(),
+                        bytes: Box::pin(stream::iter(vec![Ok(Bytes::from(body))])),
+                    }),
+                    Some(Err(error)) => Err(error),
+                    None => Err(ApiError::Stream("unscripted resume call".into())),
+                }
+            })
+        }
+    }
+
+    async fn collect_events_with_resumer(
+        chunks: Vec<Result<Bytes, TransportError>>,
+        resumer: Arc<dyn StreamResumer>,
+    ) -> Vec<Result<ResponseEvent, ApiError>> {
+        let (tx, mut rx) = mpsc::channel::<Result<ResponseEvent, ApiError>>(16);
+        tokio::spawn(process_sse_with_treatment(
+            Box::pin(stream::iter(chunks)),
+            tx,
+            idle_timeout(),
+            /*telemetry*/ None,
+            SafetyBufferingTreatment::default(),
+            Some(resumer),
+        ));
+
+        let mut events = Vec::new();
+        while let Some(ev) = rx.recv().await {
+            events.push(ev);
+        }
+        events
+    }
+
+    #[tokio::test]
+    async fn resumes_stream_from_last_sequence_number_after_disconnect() {
+        let first = concat!(
+            "event: response.created\n",
+            "data: {\"type\":\"response.created\",\"sequence_number\":1,\"response\":{\"id\":\"resp_resume\"}}\n\n",
+            "event: response.output_text.delta\n",
+            "data: {\"type\":\"response.output_text.delta\",\"sequence_number\":2,\"delta\":\"Hel\"}\n\n",
+        );
+        // The server replays the last acknowledged event before new ones; the
+        // replayed sequence number must be dropped instead of re-delivered.
+        let resumed = concat!(
+            "event: response.output_text.delta\n",
+            "data: {\"type\":\"response.output_text.delta\",\"sequence_number\":2,\"delta\":\"Hel\"}\n\n",
+            "event: response.output_text.delta\n",
+            "data: {\"type\":\"response.output_text.delta\",\"sequence_number\":3,\"delta\":\"lo\"}\n\n",
+            "event: response.completed\n",
+            "data: {\"type\":\"response.completed\",\"sequence_number\":4,\"response\":{\"id\":\"resp_resume\"}}\n\n",
+        );
+        let resumer = Arc::new(ScriptedResumer::new(vec![Ok(resumed.to_string())]));
+
+        let events = collect_events_with_resumer(
+            vec![
+                Ok(Bytes::from_static(first.as_bytes())),
+                Err(TransportError::Network("connection reset".into())),
+            ],
+            resumer.clone(),
+        )
+        .await;
+
+        assert_eq!(resumer.calls(), vec![("resp_resume".to_string(), 2)]);
+        assert_eq!(events.len(), 4);
+        assert_matches!(&events[0], Ok(ResponseEvent::Created {}));
+        assert_matches!(&events[1], Ok(ResponseEvent::OutputTextDelta(delta)) if delta == "Hel");
+        assert_matches!(&events[2], Ok(ResponseEvent::OutputTextDelta(delta)) if delta == "lo");
+        assert_matches!(&events[3], Ok(ResponseEvent::Completed { .. }));
+    }
+
+    #[tokio::test]
+    async fn does_not_resume_without_sequence_numbers() {
+        let first = concat!(
+            "event: response.created\n",
+            "data: {\"type\":\"response.created\",\"response\":{\"id\":\"resp_no_seq\"}}\n\n",
+        );
+        let resumer = Arc::new(ScriptedResumer::new(Vec::new()));
+
+        let events = collect_events_with_resumer(
+            vec![
+                Ok(Bytes::from_static(first.as_bytes())),
+                Err(TransportError::Network("connection reset".into())),
+            ],
+            resumer.clone(),
+        )
+        .await;
+
+        assert_eq!(resumer.calls(), Vec::<(String, u64)>::new());
+        assert_matches!(events.last(), Some(Err(ApiError::Stream(_))));
+    }
+
+    #[tokio::test]
+    async fn surfaces_original_error_when_resume_request_fails() {
+        let first = concat!(
+            "event: response.created\n",
+            "data: {\"type\":\"response.created\",\"sequence_number\":1,\"response\":{\"id\":\"resp_fail\"}}\n\n",
+        );
+        let resumer = Arc::new(ScriptedResumer::new(vec![Err(ApiError::Stream(
+            "resume not supported".into(),
+        ))]));
+
+        let events = collect_events_with_resumer(
+            vec![Ok(Bytes::from_static(first.as_bytes()))],
+            resumer.clone(),
+        )
+        .await;
+
+        assert_eq!(resumer.calls(), vec![("resp_fail".to_string(), 1)]);
+        match events.last() {
+            Some(Err(ApiError::Stream(msg))) => {
+                assert_eq!(msg, "stream closed before response.completed")
+            }
+            other => panic!("unexpected final event: {other:?}"),
+        }
+    }
+
     #[tokio::test]
     async fn parses_tool_search_call_items() {
         let events = run_sse(vec![
@@ -40,6 +40,8 @@ fn provider(base_url: &str) -> Provider {
             retry_429: false,
             retry_5xx: true,
             retry_transport: true,
+            budget: None,
+            observer: None,
         },
         stream_idle_timeout: std::time::Duration::from_secs(1),
     }
@@ -69,6 +69,8 @@ fn test_provider(base_url: String) -> Provider {
             retry_429: false,
             retry_5xx: false,
             retry_transport: false,
+            budget: None,
+            observer: None,
         },
         stream_idle_timeout: Duration::from_secs(5),
     }
@@ -68,6 +68,8 @@ fn provider(name: &str) -> Provider {
             retry_429: false,
             retry_5xx: false,
             retry_transport: true,
+            budget: None,
+            observer: None,
         },
         stream_idle_timeout: Duration::from_millis(50),
     }
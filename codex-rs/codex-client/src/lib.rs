@@ -1,13 +1,21 @@
 mod retry;
 mod sse;
 mod telemetry;
+mod vcr;
 
+pub use crate::retry::MAX_CONCURRENT_REQUESTS_ENV_VAR;
+pub use crate::retry::RetryBudget;
+pub use crate::retry::RetryObserver;
 pub use crate::retry::RetryOn;
 pub use crate::retry::RetryPolicy;
 pub use crate::retry::backoff;
 pub use crate::retry::run_with_retry;
 pub use crate::sse::sse_stream;
 pub use crate::telemetry::RequestTelemetry;
+pub use crate::vcr::VCR_CASSETTE_ENV_VAR;
+pub use crate::vcr::VCR_MODE_ENV_VAR;
+pub use crate::vcr::VcrMode;
+pub use crate::vcr::VcrTransport;
 pub use codex_http_client::HttpClient as CodexHttpClient;
 pub use codex_http_client::RequestBuilder as CodexRequestBuilder;
 pub use codex_http_client::*;
@@ -2,14 +2,72 @@ use codex_http_client::Request;
 use codex_http_client::TransportError;
 use rand::Rng;
 use std::future::Future;
+use std::sync::Arc;
+use std::sync::LazyLock;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::time::Duration;
+use std::time::Instant;
+use tokio::sync::Semaphore;
 use tokio::time::sleep;
 
-#[derive(Debug, Clone)]
+/// Environment variable overriding the process-wide concurrent-request cap.
+pub const MAX_CONCURRENT_REQUESTS_ENV_VAR: &str = "CODEX_MAX_CONCURRENT_REQUESTS";
+const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 32;
+
+/// Process-wide cap on in-flight provider requests, so a burst of retrying
+/// callers queues instead of multiplying load.
+static GLOBAL_REQUEST_LIMITER: LazyLock<Semaphore> = LazyLock::new(|| {
+    let permits = std::env::var(MAX_CONCURRENT_REQUESTS_ENV_VAR)
+        .ok()
+        .and_then(|value| value.parse::<usize>().ok())
+        .filter(|permits| *permits > 0)
+        .unwrap_or(DEFAULT_MAX_CONCURRENT_REQUESTS);
+    Semaphore::new(permits)
+});
+
+#[derive(Clone)]
 pub struct RetryPolicy {
     pub max_attempts: u64,
     pub base_delay: Duration,
     pub retry_on: RetryOn,
+    /// Shared budget capping cumulative retries across requests; `None`
+    /// leaves per-request `max_attempts` as the only limit.
+    pub budget: Option<RetryBudget>,
+    /// Observer notified of retry progress; `None` keeps retries silent.
+    pub observer: Option<Arc<dyn RetryObserver>>,
+}
+
+impl std::fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("base_delay", &self.base_delay)
+            .field("retry_on", &self.retry_on)
+            .field("budget", &self.budget)
+            .field("observer", &self.observer.is_some())
+            .finish()
+    }
+}
+
+/// Observes retry progress inside [`run_with_retry`] so embedding
+/// applications can surface reconnect progress to users and export metrics
+/// instead of the backoff sleeps being invisible.
+///
+/// All methods default to no-ops so implementations only override the events
+/// they care about.
+pub trait RetryObserver: Send + Sync {
+    /// Called immediately before each attempt is sent; the first attempt is
+    /// number `0`.
+    fn on_attempt(&self, _attempt: u64) {}
+
+    /// Called when `attempt` failed with a retryable `error` and the runner
+    /// is about to sleep for `delay` before the next attempt.
+    fn on_backoff(&self, _attempt: u64, _delay: Duration, _error: &TransportError) {}
+
+    /// Called when the runner stops retrying: the error was not retryable,
+    /// the attempt limit was reached, or the retry budget was spent.
+    fn on_give_up(&self, _attempt: u64, _error: &TransportError) {}
 }
 
 #[derive(Debug, Clone)]
@@ -19,6 +77,53 @@ pub struct RetryOn {
     pub retry_transport: bool,
 }
 
+/// Shared cap on retry work across all the requests of one logical operation
+/// (e.g. a turn). Clones share the same budget, so a burst of failing calls
+/// stops retrying once the cumulative count or wall-clock allowance is spent.
+#[derive(Debug, Clone)]
+pub struct RetryBudget {
+    inner: Arc<RetryBudgetInner>,
+}
+
+#[derive(Debug)]
+struct RetryBudgetInner {
+    remaining_retries: AtomicU64,
+    deadline: Option<Instant>,
+}
+
+impl RetryBudget {
+    /// Creates a budget allowing `max_total_retries` cumulative retries,
+    /// optionally bounded by `max_elapsed` of wall-clock time from now.
+    pub fn new(max_total_retries: u64, max_elapsed: Option<Duration>) -> Self {
+        Self {
+            inner: Arc::new(RetryBudgetInner {
+                remaining_retries: AtomicU64::new(max_total_retries),
+                deadline: max_elapsed.and_then(|max| Instant::now().checked_add(max)),
+            }),
+        }
+    }
+
+    /// Consumes one retry, erroring once the count or deadline is exhausted.
+    fn try_consume(&self) -> Result<(), TransportError> {
+        if let Some(deadline) = self.inner.deadline
+            && Instant::now() >= deadline
+        {
+            return Err(TransportError::BudgetExhausted(
+                "retry wall-clock budget exhausted".to_string(),
+            ));
+        }
+        self.inner
+            .remaining_retries
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |remaining| {
+                remaining.checked_sub(1)
+            })
+            .map(|_| ())
+            .map_err(|_| {
+                TransportError::BudgetExhausted("cumulative retry budget exhausted".to_string())
+            })
+    }
+}
+
 impl RetryOn {
     pub fn should_retry(&self, err: &TransportError, attempt: u64, max_attempts: u64) -> bool {
         if attempt >= max_attempts {
@@ -29,7 +134,13 @@ impl RetryOn {
                 (self.retry_429 && status.as_u16() == 429)
                     || (self.retry_5xx && status.is_server_error())
             }
-            TransportError::Timeout | TransportError::Network(_) => self.retry_transport,
+            TransportError::Timeout
+            | TransportError::ConnectTimeout { .. }
+            | TransportError::Dns { .. }
+            | TransportError::Proxy(_)
+            | TransportError::Network(_) => self.retry_transport,
+            // TLS failures are deterministic (bad certificate or trust
+            // configuration), so retrying only delays the diagnostic.
             _ => false,
         }
     }
@@ -57,17 +168,47 @@ where
 {
     for attempt in 0..=policy.max_attempts {
         let req = make_req();
-        match op(req, attempt).await {
+        if let Some(observer) = &policy.observer {
+            observer.on_attempt(attempt);
+        }
+        let permit = GLOBAL_REQUEST_LIMITER
+            .acquire()
+            .await
+            .map_err(|_| TransportError::Build("global request limiter closed".to_string()))?;
+        let result = op(req, attempt).await;
+        drop(permit);
+        match result {
             Ok(resp) => return Ok(resp),
             Err(err)
                 if policy
                     .retry_on
                     .should_retry(&err, attempt, policy.max_attempts) =>
             {
-                sleep(backoff(policy.base_delay, attempt + 1)).await;
+                if let Some(budget) = &policy.budget
+                    && let Err(budget_err) = budget.try_consume()
+                {
+                    if let Some(observer) = &policy.observer {
+                        observer.on_give_up(attempt, &budget_err);
+                    }
+                    return Err(budget_err);
+                }
+                let delay = backoff(policy.base_delay, attempt + 1);
+                if let Some(observer) = &policy.observer {
+                    observer.on_backoff(attempt, delay, &err);
+                }
+                sleep(delay).await;
+            }
+            Err(err) => {
+                if let Some(observer) = &policy.observer {
+                    observer.on_give_up(attempt, &err);
+                }
+                return Err(err);
             }
-            Err(err) => return Err(err),
         }
     }
-    Err(TransportError::RetryLimit)
+    let err = TransportError::RetryLimit;
+    if let Some(observer) = &policy.observer {
+        observer.on_give_up(policy.max_attempts, &err);
+    }
+    Err(err)
 }
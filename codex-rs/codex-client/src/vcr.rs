@@ -0,0 +1,470 @@
+//! Request/response recording and replay ("VCR") for HTTP transports.
+//!
+//! In record mode, [`VcrTransport`] passes requests through to the inner
+//! transport and appends each interaction — including streamed response
+//! chunks — to a cassette file as JSON Lines. In replay mode, recorded
+//! interactions are served back deterministically without touching the
+//! network, keyed by method, URL, and request body; repeated identical
+//! requests replay in recording order.
+//!
+//! Bodies and chunks are stored as UTF-8 text so cassettes stay readable and
+//! hand-editable; the transports this wraps speak JSON/SSE, which is UTF-8.
+
+use bytes::Bytes;
+use codex_http_client::ByteStream;
+use codex_http_client::HttpTransport;
+use codex_http_client::Request;
+use codex_http_client::RequestBody;
+use codex_http_client::Response;
+use codex_http_client::StreamResponse;
+use codex_http_client::TransportError;
+use futures::StreamExt;
+use http::HeaderMap;
+use http::HeaderName;
+use http::HeaderValue;
+use http::StatusCode;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+
+/// Environment variable selecting `record` or `replay`; unset disables VCR.
+pub const VCR_MODE_ENV_VAR: &str = "CODEX_VCR_MODE";
+/// Environment variable holding the cassette file path.
+pub const VCR_CASSETTE_ENV_VAR: &str = "CODEX_VCR_CASSETTE";
+
+/// Whether a cassette is being written or served back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VcrMode {
+    Record,
+    Replay,
+}
+
+/// One request/response pair in a cassette, stored as a single JSON line.
+///
+/// `body` is set for unary responses and `chunks` for streamed ones; error
+/// responses record their status and body so replay reproduces the failure.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedInteraction {
+    method: String,
+    url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    request_body: Option<String>,
+    status: u16,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    headers: Vec<(String, String)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    chunks: Option<Vec<String>>,
+}
+
+impl RecordedInteraction {
+    fn key(&self) -> String {
+        interaction_key(&self.method, &self.url, self.request_body.as_deref())
+    }
+}
+
+/// Cassettes are shared process-wide per path so every transport built during
+/// a session appends to — or consumes from — the same recording.
+static CASSETTES: LazyLock<Mutex<HashMap<PathBuf, Arc<Cassette>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug)]
+struct Cassette {
+    mode: VcrMode,
+    path: PathBuf,
+    /// Replay: remaining interactions per request key, in recording order.
+    remaining: Mutex<HashMap<String, VecDeque<RecordedInteraction>>>,
+    /// Record: append handle for the cassette file.
+    writer: Mutex<Option<File>>,
+}
+
+impl Cassette {
+    fn open(mode: VcrMode, path: &Path) -> Result<Arc<Self>, String> {
+        let Ok(mut cassettes) = CASSETTES.lock() else {
+            return Err("vcr cassette registry lock poisoned".to_string());
+        };
+        if let Some(cassette) = cassettes.get(path) {
+            if cassette.mode != mode {
+                return Err(format!(
+                    "vcr cassette `{}` is already open in a different mode",
+                    path.display()
+                ));
+            }
+            return Ok(cassette.clone());
+        }
+
+        let cassette = match mode {
+            VcrMode::Record => {
+                let file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .map_err(|err| {
+                        format!("failed to open vcr cassette `{}`: {err}", path.display())
+                    })?;
+                Cassette {
+                    mode,
+                    path: path.to_path_buf(),
+                    remaining: Mutex::new(HashMap::new()),
+                    writer: Mutex::new(Some(file)),
+                }
+            }
+            VcrMode::Replay => {
+                let contents = std::fs::read_to_string(path).map_err(|err| {
+                    format!("failed to read vcr cassette `{}`: {err}", path.display())
+                })?;
+                let mut remaining: HashMap<String, VecDeque<RecordedInteraction>> = HashMap::new();
+                for (index, line) in contents.lines().enumerate() {
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    let interaction: RecordedInteraction =
+                        serde_json::from_str(line).map_err(|err| {
+                            format!(
+                                "invalid vcr cassette `{}` at line {}: {err}",
+                                path.display(),
+                                index + 1
+                            )
+                        })?;
+                    remaining
+                        .entry(interaction.key())
+                        .or_default()
+                        .push_back(interaction);
+                }
+                Cassette {
+                    mode,
+                    path: path.to_path_buf(),
+                    remaining: Mutex::new(remaining),
+                    writer: Mutex::new(None),
+                }
+            }
+        };
+
+        let cassette = Arc::new(cassette);
+        cassettes.insert(path.to_path_buf(), cassette.clone());
+        Ok(cassette)
+    }
+
+    fn append(&self, interaction: &RecordedInteraction) {
+        let Ok(serialized) = serde_json::to_string(interaction) else {
+            tracing::warn!("failed to serialize vcr interaction");
+            return;
+        };
+        let Ok(mut writer) = self.writer.lock() else {
+            return;
+        };
+        if let Some(file) = writer.as_mut()
+            && let Err(err) = writeln!(file, "{serialized}")
+        {
+            tracing::warn!(
+                "failed to append to vcr cassette `{}`: {err}",
+                self.path.display()
+            );
+        }
+    }
+
+    fn take(&self, key: &str) -> Option<RecordedInteraction> {
+        let mut remaining = self.remaining.lock().ok()?;
+        let queue = remaining.get_mut(key)?;
+        queue.pop_front()
+    }
+
+    fn missing_interaction(&self, method: &str, url: &str) -> TransportError {
+        TransportError::Build(format!(
+            "vcr cassette `{}` has no remaining interaction for {method} {url}",
+            self.path.display()
+        ))
+    }
+}
+
+/// Transport wrapper that records interactions to a cassette or replays them.
+///
+/// With no VCR configuration the wrapper is a transparent passthrough, so
+/// callers can wrap unconditionally via [`VcrTransport::from_env`].
+#[derive(Debug, Clone)]
+pub struct VcrTransport<T> {
+    inner: T,
+    cassette: Option<Arc<Cassette>>,
+}
+
+impl<T> VcrTransport<T> {
+    /// Wraps `inner` according to `CODEX_VCR_MODE` and `CODEX_VCR_CASSETTE`;
+    /// passthrough when the mode variable is unset.
+    pub fn from_env(inner: T) -> Result<Self, String> {
+        let Some(mode) = std::env::var(VCR_MODE_ENV_VAR)
+            .ok()
+            .filter(|mode| !mode.trim().is_empty())
+        else {
+            return Ok(Self {
+                inner,
+                cassette: None,
+            });
+        };
+        let mode = match mode.trim() {
+            "record" => VcrMode::Record,
+            "replay" => VcrMode::Replay,
+            other => {
+                return Err(format!(
+                    "{VCR_MODE_ENV_VAR} must be `record` or `replay`, got `{other}`"
+                ));
+            }
+        };
+        let cassette_path = std::env::var(VCR_CASSETTE_ENV_VAR)
+            .ok()
+            .map(PathBuf::from)
+            .filter(|path| !path.as_os_str().is_empty())
+            .ok_or_else(|| {
+                format!("{VCR_CASSETTE_ENV_VAR} must be set when {VCR_MODE_ENV_VAR} is set")
+            })?;
+        Self::with_cassette(inner, mode, &cassette_path)
+    }
+
+    /// Wraps `inner` with an explicit mode and cassette path.
+    pub fn with_cassette(inner: T, mode: VcrMode, cassette_path: &Path) -> Result<Self, String> {
+        let cassette = Cassette::open(mode, cassette_path)?;
+        Ok(Self {
+            inner,
+            cassette: Some(cassette),
+        })
+    }
+}
+
+impl<T: HttpTransport> HttpTransport for VcrTransport<T> {
+    async fn execute(&self, req: Request) -> Result<Response, TransportError> {
+        let Some(cassette) = &self.cassette else {
+            return self.inner.execute(req).await;
+        };
+
+        let method = req.method.to_string();
+        let url = req.url.clone();
+        let request_body = request_body_text(&req);
+
+        match cassette.mode {
+            VcrMode::Replay => {
+                let key = interaction_key(&method, &url, request_body.as_deref());
+                let interaction = cassette
+                    .take(&key)
+                    .ok_or_else(|| cassette.missing_interaction(&method, &url))?;
+                let status = recorded_status(&interaction)?;
+                let headers = headers_from_recorded(&interaction.headers);
+                if !status.is_success() {
+                    return Err(TransportError::Http {
+                        status,
+                        url: Some(url),
+                        headers: Some(headers),
+                        body: interaction.body,
+                    });
+                }
+                Ok(Response {
+                    status,
+                    headers,
+                    body: Bytes::from(interaction.body.unwrap_or_default()),
+                })
+            }
+            VcrMode::Record => {
+                let result = self.inner.execute(req).await;
+                match &result {
+                    Ok(response) => cassette.append(&RecordedInteraction {
+                        method,
+                        url,
+                        request_body,
+                        status: response.status.as_u16(),
+                        headers: headers_to_recorded(&response.headers),
+                        body: Some(String::from_utf8_lossy(&response.body).into_owned()),
+                        chunks: None,
+                    }),
+                    Err(TransportError::Http {
+                        status,
+                        headers,
+                        body,
+                        ..
+                    }) => cassette.append(&RecordedInteraction {
+                        method,
+                        url,
+                        request_body,
+                        status: status.as_u16(),
+                        headers: headers
+                            .as_ref()
+                            .map(headers_to_recorded)
+                            .unwrap_or_default(),
+                        body: body.clone(),
+                        chunks: None,
+                    }),
+                    Err(_) => {}
+                }
+                result
+            }
+        }
+    }
+
+    async fn stream(&self, req: Request) -> Result<StreamResponse, TransportError> {
+        let Some(cassette) = &self.cassette else {
+            return self.inner.stream(req).await;
+        };
+
+        let method = req.method.to_string();
+        let url = req.url.clone();
+        let request_body = request_body_text(&req);
+
+        match cassette.mode {
+            VcrMode::Replay => {
+                let key = interaction_key(&method, &url, request_body.as_deref());
+                let interaction = cassette
+                    .take(&key)
+                    .ok_or_else(|| cassette.missing_interaction(&method, &url))?;
+                let status = recorded_status(&interaction)?;
+                let headers = headers_from_recorded(&interaction.headers);
+                if !status.is_success() {
+                    return Err(TransportError::Http {
+                        status,
+                        url: Some(url),
+                        headers: Some(headers),
+                        body: interaction.body,
+                    });
+                }
+                let chunks = interaction.chunks.unwrap_or_default();
+                let bytes: ByteStream =
+                    futures::stream::iter(chunks.into_iter().map(|chunk| Ok(Bytes::from(chunk))))
+                        .boxed();
+                Ok(StreamResponse {
+                    status,
+                    headers,
+                    bytes,
+                })
+            }
+            VcrMode::Record => {
+                let result = self.inner.stream(req).await;
+                match result {
+                    Ok(response) => {
+                        let recorder = StreamRecorder {
+                            cassette: cassette.clone(),
+                            interaction: Some(RecordedInteraction {
+                                method,
+                                url,
+                                request_body,
+                                status: response.status.as_u16(),
+                                headers: headers_to_recorded(&response.headers),
+                                body: None,
+                                chunks: Some(Vec::new()),
+                            }),
+                        };
+                        Ok(StreamResponse {
+                            status: response.status,
+                            headers: response.headers,
+                            bytes: record_stream(response.bytes, recorder),
+                        })
+                    }
+                    Err(err) => {
+                        if let TransportError::Http {
+                            status,
+                            headers,
+                            body,
+                            ..
+                        } = &err
+                        {
+                            cassette.append(&RecordedInteraction {
+                                method,
+                                url,
+                                request_body,
+                                status: status.as_u16(),
+                                headers: headers
+                                    .as_ref()
+                                    .map(headers_to_recorded)
+                                    .unwrap_or_default(),
+                                body: body.clone(),
+                                chunks: None,
+                            });
+                        }
+                        Err(err)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Appends the collected interaction when the wrapped stream is dropped, so
+/// partially-consumed streams are still captured.
+struct StreamRecorder {
+    cassette: Arc<Cassette>,
+    interaction: Option<RecordedInteraction>,
+}
+
+impl Drop for StreamRecorder {
+    fn drop(&mut self) {
+        if let Some(interaction) = self.interaction.take() {
+            self.cassette.append(&interaction);
+        }
+    }
+}
+
+fn record_stream(bytes: ByteStream, recorder: StreamRecorder) -> ByteStream {
+    futures::stream::unfold((bytes, recorder), |(mut bytes, mut recorder)| async move {
+        let item = bytes.next().await?;
+        if let (Some(interaction), Ok(chunk)) = (recorder.interaction.as_mut(), &item)
+            && let Some(chunks) = interaction.chunks.as_mut()
+        {
+            chunks.push(String::from_utf8_lossy(chunk).into_owned());
+        }
+        Some((item, (bytes, recorder)))
+    })
+    .boxed()
+}
+
+fn interaction_key(method: &str, url: &str, request_body: Option<&str>) -> String {
+    format!("{method} {url}\n{}", request_body.unwrap_or_default())
+}
+
+fn request_body_text(req: &Request) -> Option<String> {
+    match req.body.as_ref()? {
+        RequestBody::Json(value) => Some(value.to_string()),
+        RequestBody::EncodedJson(body) => {
+            Some(String::from_utf8_lossy(body.as_bytes()).into_owned())
+        }
+        RequestBody::Raw(bytes) => Some(String::from_utf8_lossy(bytes).into_owned()),
+    }
+}
+
+fn recorded_status(interaction: &RecordedInteraction) -> Result<StatusCode, TransportError> {
+    StatusCode::from_u16(interaction.status).map_err(|err| {
+        TransportError::Build(format!(
+            "vcr interaction for {} {} has invalid status {}: {err}",
+            interaction.method, interaction.url, interaction.status
+        ))
+    })
+}
+
+fn headers_to_recorded(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| {
+            value
+                .to_str()
+                .ok()
+                .map(|value| (name.as_str().to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+fn headers_from_recorded(recorded: &[(String, String)]) -> HeaderMap {
+    let mut headers = HeaderMap::with_capacity(recorded.len());
+    for (name, value) in recorded {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::try_from(name.as_str()),
+            HeaderValue::try_from(value.as_str()),
+        ) {
+            headers.append(name, value);
+        }
+    }
+    headers
+}
@@ -11,15 +11,23 @@ use crate::types::AnalyticsConfigToml;
 use crate::types::ApprovalsReviewer;
 use crate::types::AppsConfigToml;
 use crate::types::AuthCredentialsStoreMode;
+use crate::types::ContextOverflowFallbackToml;
 use crate::types::FeedbackConfigToml;
 use crate::types::History;
+use crate::types::HttpServerToml;
 use crate::types::MarketplaceConfig;
 use crate::types::McpServerConfig;
 use crate::types::MemoriesToml;
+use crate::types::ModelRateToml;
+use crate::types::ModelRulesToml;
 use crate::types::Notice;
 use crate::types::OAuthCredentialsStoreMode;
 use crate::types::OtelConfigToml;
 use crate::types::PluginConfig;
+use crate::types::ProviderPoolToml;
+use crate::types::ProviderRaceToml;
+use crate::types::RefusalFallbackToml;
+use crate::types::ResponseCacheToml;
 use crate::types::SandboxWorkspaceWrite;
 use crate::types::ShellEnvironmentPolicyToml;
 use crate::types::SkillsConfig;
@@ -160,6 +168,11 @@ pub struct ConfigToml {
     /// Provider to use from the model_providers map.
     pub model_provider: Option<String>,
 
+    /// Restrict provider resolution to local endpoints (ollama, lmstudio, or
+    /// a custom localhost `base_url`) and fail fast instead of retrying
+    /// unreachable cloud hosts.
+    pub offline: Option<bool>,
+
     /// Size of the context window for the model, in tokens.
     pub model_context_window: Option<i64>,
 
@@ -290,6 +303,25 @@ pub struct ConfigToml {
     #[serde(default, deserialize_with = "deserialize_model_providers")]
     pub model_providers: HashMap<String, ModelProviderInfo>,
 
+    /// Maps model name patterns (`*` wildcard, e.g. `claude-*`) to provider
+    /// IDs. Consulted when `--model` is supplied without an explicit
+    /// provider, so the model is sent to a provider that actually serves it
+    /// instead of the active provider. The most specific matching pattern
+    /// wins.
+    #[serde(default)]
+    pub model_routes: HashMap<String, String>,
+
+    /// Maps model name patterns to per-1M-token prices used for cost
+    /// accounting, overriding the bundled rate table.
+    #[serde(default)]
+    pub model_rates: HashMap<String, ModelRateToml>,
+
+    /// Named pools of interchangeable providers. Selecting a pool id as the
+    /// model provider balances requests across the members by observed
+    /// latency and error rate.
+    #[serde(default)]
+    pub provider_pools: HashMap<String, ProviderPoolToml>,
+
     /// Maximum number of bytes to include from an AGENTS.md project doc file.
     #[serde(default = "default_project_doc_max_bytes")]
     pub project_doc_max_bytes: Option<usize>,
@@ -506,6 +538,25 @@ pub struct ConfigToml {
     /// OTEL configuration.
     pub otel: Option<OtelConfigToml>,
 
+    /// Refusal-fallback configuration.
+    pub refusal_fallback: Option<RefusalFallbackToml>,
+
+    /// Retry context-length-exceeded requests on a long-context model.
+    pub context_overflow_fallback: Option<ContextOverflowFallbackToml>,
+
+    /// Route turns to a model picked by task-signal rules.
+    pub model_rules: Option<ModelRulesToml>,
+
+    /// Settings for the standalone HTTP server (`codex-http-server`).
+    pub http_server: Option<HttpServerToml>,
+
+    /// Race sampling requests against a secondary provider; first usable
+    /// stream wins.
+    pub provider_race: Option<ProviderRaceToml>,
+
+    /// On-disk cache for non-streaming model responses.
+    pub response_cache: Option<ResponseCacheToml>,
+
     /// Windows-specific configuration.
     #[serde(default)]
     pub windows: Option<WindowsToml>,
@@ -646,6 +697,29 @@ pub struct ToolsToml {
     )]
     pub web_search: Option<WebSearchToolConfig>,
     pub experimental_request_user_input: Option<ExperimentalRequestUserInput>,
+    pub ra1: Option<Ra1ToolToml>,
+}
+
+/// Configuration for the built-in RA1 generation tools exposed by the MCP
+/// server, so self-hosted or proxied deployments of the art API work without
+/// recompiling.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct Ra1ToolToml {
+    /// Whether the RA1 tools are offered at all. Defaults to `true` (they are
+    /// still hidden when the API key env var is unset).
+    pub enabled: Option<bool>,
+    /// Environment variable holding the API key. Defaults to
+    /// `NETWRCK_API_KEY`.
+    pub api_key_env: Option<String>,
+    /// Base URL of the art API. Defaults to `https://netwrck.com/api`.
+    pub base_url: Option<String>,
+    /// Default image size when a call does not specify one. Defaults to
+    /// `1024x1024`.
+    pub default_size: Option<String>,
+    /// Maximum cumulative generation cost per session, in USD. Unset means
+    /// unlimited.
+    pub session_cost_cap_usd: Option<f64>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
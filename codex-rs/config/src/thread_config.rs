@@ -306,6 +306,13 @@ mod tests {
             experimental_bearer_token: None,
             auth: None,
             aws: None,
+            oauth: None,
+            key_command: None,
+            key_file: None,
+            https_proxy: None,
+            no_proxy: None,
+            ca_bundle_path: None,
+            insecure_skip_verify: false,
             wire_api: WireApi::Responses,
             query_params: None,
             http_headers: None,
@@ -316,6 +323,8 @@ mod tests {
             websocket_connect_timeout_ms: None,
             requires_openai_auth: false,
             supports_websockets: true,
+            prompt_cache: None,
+            embedding_model: None,
         }
     }
 }
@@ -100,6 +100,8 @@ pub struct ModelProviderAuthInfo {
 pub enum WireApi {
     Unspecified = 0,
     Responses = 1,
+    AnthropicMessages = 2,
+    GeminiGenerateContent = 3,
 }
 impl WireApi {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -110,6 +112,8 @@ impl WireApi {
         match self {
             Self::Unspecified => "WIRE_API_UNSPECIFIED",
             Self::Responses => "WIRE_API_RESPONSES",
+            Self::AnthropicMessages => "WIRE_API_ANTHROPIC_MESSAGES",
+            Self::GeminiGenerateContent => "WIRE_API_GEMINI_GENERATE_CONTENT",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -117,6 +121,8 @@ impl WireApi {
         match value {
             "WIRE_API_UNSPECIFIED" => Some(Self::Unspecified),
             "WIRE_API_RESPONSES" => Some(Self::Responses),
+            "WIRE_API_ANTHROPIC_MESSAGES" => Some(Self::AnthropicMessages),
+            "WIRE_API_GEMINI_GENERATE_CONTENT" => Some(Self::GeminiGenerateContent),
             _ => None,
         }
     }
@@ -159,6 +159,8 @@ fn model_provider_from_proto(
     let id = provider.id;
     let wire_api = match proto::WireApi::try_from(provider.wire_api) {
         Ok(proto::WireApi::Responses) => WireApi::Responses,
+        Ok(proto::WireApi::AnthropicMessages) => WireApi::AnthropicMessages,
+        Ok(proto::WireApi::GeminiGenerateContent) => WireApi::GeminiGenerateContent,
         Ok(proto::WireApi::Unspecified) => {
             return Err(parse_error("remote thread config omitted wire_api"));
         }
@@ -180,6 +182,13 @@ fn model_provider_from_proto(
             .map(model_provider_auth_from_proto)
             .transpose()?,
         aws: None,
+        oauth: None,
+        key_command: None,
+        key_file: None,
+        https_proxy: None,
+        no_proxy: None,
+        ca_bundle_path: None,
+        insecure_skip_verify: false,
         wire_api,
         query_params: provider.query_params.map(|map| map.values),
         http_headers: provider.http_headers.map(|map| map.values),
@@ -190,6 +199,8 @@ fn model_provider_from_proto(
         websocket_connect_timeout_ms: provider.websocket_connect_timeout_ms,
         requires_openai_auth: provider.requires_openai_auth,
         supports_websockets: provider.supports_websockets,
+        prompt_cache: None,
+        embedding_model: None,
     };
     Ok((id, info))
 }
@@ -207,6 +218,13 @@ fn model_provider_to_proto(
         experimental_bearer_token,
         auth,
         aws: _,
+        oauth: _,
+        key_command: _,
+        key_file: _,
+        https_proxy: _,
+        no_proxy: _,
+        ca_bundle_path: _,
+        insecure_skip_verify: _,
         wire_api,
         query_params,
         http_headers,
@@ -217,6 +235,8 @@ fn model_provider_to_proto(
         websocket_connect_timeout_ms,
         requires_openai_auth,
         supports_websockets,
+        prompt_cache: _,
+        embedding_model: _,
     } = provider;
 
     proto::ModelProvider {
@@ -289,6 +309,8 @@ fn proto_string_map(values: HashMap<String, String>) -> proto::StringMap {
 fn proto_wire_api(wire_api: WireApi) -> proto::WireApi {
     match wire_api {
         WireApi::Responses => proto::WireApi::Responses,
+        WireApi::AnthropicMessages => proto::WireApi::AnthropicMessages,
+        WireApi::GeminiGenerateContent => proto::WireApi::GeminiGenerateContent,
     }
 }
 
@@ -536,7 +558,16 @@ mod tests {
             websocket_connect_timeout_ms: Some(10_000),
             requires_openai_auth: false,
             supports_websockets: true,
+            prompt_cache: None,
+            embedding_model: None,
             aws: None,
+            oauth: None,
+            key_command: None,
+            key_file: None,
+            https_proxy: None,
+            no_proxy: None,
+            ca_bundle_path: None,
+            insecure_skip_verify: false,
         }
     }
 
@@ -25,6 +25,7 @@ use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::fmt;
+use std::path::PathBuf;
 
 use schemars::JsonSchema;
 use serde::Deserialize;
@@ -564,6 +565,540 @@ pub struct OtelConfigToml {
     pub tracestate: Option<BTreeMap<String, BTreeMap<String, String>>>,
 }
 
+/// Refusal-fallback settings loaded from config.toml. Fields are optional so
+/// we can apply defaults.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct RefusalFallbackToml {
+    /// Whether to reroute refused responses to the fallback model. Defaults
+    /// to true when `OPENROUTER_API_KEY` is set.
+    pub enabled: Option<bool>,
+
+    /// Model to reroute refused turns to.
+    pub model: Option<String>,
+
+    /// Provider id (from `model_providers`) serving the fallback model.
+    pub provider: Option<String>,
+
+    /// Only responses at or below this word count are considered refusal
+    /// candidates; longer responses are assumed to contain real work.
+    pub max_word_count: Option<usize>,
+
+    /// Extra indicator phrases that mark a response as a refusal, merged with
+    /// the built-in list. Matched case-insensitively.
+    pub indicator_phrases: Option<Vec<String>>,
+}
+
+/// Effective refusal-fallback settings after defaults are applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RefusalFallbackConfig {
+    pub enabled: bool,
+    pub model: String,
+    pub provider: String,
+    pub max_word_count: usize,
+    pub indicator_phrases: Vec<String>,
+}
+
+impl Default for RefusalFallbackConfig {
+    fn default() -> Self {
+        RefusalFallbackConfig {
+            enabled: false,
+            model: DEFAULT_REFUSAL_FALLBACK_MODEL.to_owned(),
+            provider: DEFAULT_REFUSAL_FALLBACK_PROVIDER.to_owned(),
+            max_word_count: DEFAULT_REFUSAL_FALLBACK_MAX_WORD_COUNT,
+            indicator_phrases: Vec::new(),
+        }
+    }
+}
+
+pub const DEFAULT_REFUSAL_FALLBACK_MODEL: &str = "x-ai/grok-code-fast-1";
+pub const DEFAULT_REFUSAL_FALLBACK_PROVIDER: &str = "openrouter";
+pub const DEFAULT_REFUSAL_FALLBACK_MAX_WORD_COUNT: usize = 120;
+
+/// `[context_overflow_fallback]` table in config.toml. Fields are optional so
+/// we can apply defaults.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ContextOverflowFallbackToml {
+    /// Whether to retry context-length-exceeded requests on the fallback
+    /// model. Defaults to true when `model` is configured.
+    pub enabled: Option<bool>,
+
+    /// Long-context model to retry oversized requests on before failing the
+    /// turn.
+    pub model: Option<String>,
+}
+
+/// Effective context-overflow-fallback settings after defaults are applied.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ContextOverflowFallbackConfig {
+    pub enabled: bool,
+    pub model: Option<String>,
+}
+
+/// `[model_rules]` table in config.toml. All fields optional so we can
+/// apply defaults.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ModelRulesToml {
+    /// Whether to route turns through the rules below. Defaults to true when
+    /// any rule is configured.
+    pub enabled: Option<bool>,
+
+    /// Ordered rules; the first matching rule picks the turn's model.
+    #[serde(default)]
+    pub rules: Vec<ModelRuleToml>,
+}
+
+/// One `[[model_rules.rules]]` entry.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ModelRuleToml {
+    /// Model to run the turn on when this rule matches.
+    pub model: String,
+
+    /// Case-insensitive substrings; the rule matches when any of them appears
+    /// in the prompt. Empty matches any prompt.
+    #[serde(default)]
+    pub keywords: Vec<String>,
+
+    /// File extensions (e.g. "rs", "md"); the rule matches when the prompt
+    /// mentions a file with one of them. Empty matches any prompt.
+    #[serde(default)]
+    pub languages: Vec<String>,
+
+    /// Rule applies only when the prompt is at most this many characters.
+    pub max_prompt_chars: Option<usize>,
+
+    /// When set, the rule applies only when the built-in reasoning-heavy
+    /// heuristic agrees (`true`) or disagrees (`false`).
+    pub reasoning: Option<bool>,
+}
+
+/// Effective model-rules settings after defaults are applied.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ModelRulesConfig {
+    pub enabled: bool,
+    pub rules: Vec<ModelRuleToml>,
+}
+
+/// `[provider_race]` table in config.toml. All fields optional so we can
+/// apply defaults.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ProviderRaceToml {
+    /// Whether to race each sampling request against the secondary provider.
+    /// Defaults to true when `provider` is configured.
+    pub enabled: Option<bool>,
+
+    /// Id of the provider (a key in `model_providers`) to race the session's
+    /// primary provider against.
+    pub provider: Option<String>,
+}
+
+/// Effective provider-race settings after defaults are applied.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ProviderRaceConfig {
+    pub enabled: bool,
+    pub provider: Option<String>,
+}
+
+/// `[http_server]` table in config.toml. All fields optional so we can
+/// apply defaults.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HttpServerToml {
+    /// Scheduled conversations started by the HTTP server
+    /// (`[[http_server.schedules]]`).
+    #[serde(default)]
+    pub schedules: Vec<HttpScheduleToml>,
+
+    /// Number of workers draining the job queue.
+    pub job_workers: Option<usize>,
+
+    /// GitHub token used to push branches and open pull requests from
+    /// conversations (`POST /conversations/{id}/pr`).
+    pub github_token: Option<String>,
+
+    /// Named prompt templates served from `/templates`
+    /// (`[[http_server.templates]]`).
+    #[serde(default)]
+    pub templates: Vec<HttpTemplateToml>,
+
+    /// Event-bus URL (`redis://host:port`) used to fan job events out
+    /// across replicas; unset keeps events in-process.
+    pub event_bus: Option<String>,
+
+    /// Maximum sandbox permissions grantable per conversation over
+    /// `PATCH /conversations/{id}/sandbox` (`[http_server.sandbox_limits]`).
+    #[serde(default)]
+    pub sandbox_limits: Option<HttpSandboxLimitsToml>,
+
+    /// Default cap, in seconds, on one queued conversation turn; a run that
+    /// exceeds it is interrupted and marked failed. Unset means no cap.
+    pub max_turn_seconds: Option<u64>,
+
+    /// Cap on concurrent model turns across the whole server process,
+    /// covering jobs, schedules, replays, and hand-backs. Interactive turns
+    /// queue ahead of batch turns when the cap is hit. Unset means no cap.
+    pub max_concurrent_turns: Option<usize>,
+
+    /// Object-storage archival of idle conversations
+    /// (`[http_server.archive]`). Unset disables the archiver.
+    #[serde(default)]
+    pub archive: Option<HttpArchiveToml>,
+
+    /// Webhook notifications for job and sandbox lifecycle events
+    /// (`[http_server.notify]`). Unset disables notifications.
+    #[serde(default)]
+    pub notify: Option<HttpNotifyToml>,
+
+    /// Payload size limits (`[http_server.limits]`). Unset fields keep
+    /// the built-in defaults.
+    #[serde(default)]
+    pub limits: Option<HttpLimitsToml>,
+
+    /// Preprocessing applied to image attachments
+    /// (`[http_server.images]`). Unset fields keep the built-in defaults.
+    #[serde(default)]
+    pub images: Option<HttpImagesToml>,
+
+    /// Slack chat-ops bridge for approving sandbox grants
+    /// (`[http_server.chatops]`). Unset disables the bridge.
+    #[serde(default)]
+    pub chatops: Option<HttpChatopsToml>,
+
+    /// Disk bounds on conversation workspaces (`[http_server.quota]`).
+    /// Unset disables quota enforcement.
+    #[serde(default)]
+    pub quota: Option<HttpQuotaToml>,
+
+    /// Redaction applied to transcripts and events the server hands out
+    /// (`[http_server.redaction]`). Unset keeps the built-in secret
+    /// formats.
+    #[serde(default)]
+    pub redaction: Option<HttpRedactionToml>,
+
+    /// Policy for per-conversation instruction overrides sent by API
+    /// clients (`[http_server.instructions]`). Unset rejects all
+    /// overrides.
+    #[serde(default)]
+    pub instructions: Option<HttpInstructionsToml>,
+}
+
+/// One `[[http_server.schedules]]` entry.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HttpScheduleToml {
+    /// Display name for the schedule (defaults to the cron expression).
+    pub name: Option<String>,
+
+    /// Five-field cron expression (minute hour day-of-month month day-of-week).
+    pub cron: String,
+
+    /// Prompt for the scheduled conversation. `{date}`, `{time}`, and `{cwd}`
+    /// are substituted when the run starts.
+    pub prompt: String,
+
+    /// Working directory for the scheduled conversation.
+    pub cwd: Option<PathBuf>,
+}
+
+/// One `[[http_server.templates]]` entry: a named prompt with `{variable}`
+/// placeholders filled in when a client references the template.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HttpTemplateToml {
+    pub name: String,
+    pub prompt: String,
+}
+
+/// `[http_server.archive]` table: where and how the server archives idle
+/// conversations to S3-compatible object storage.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HttpArchiveToml {
+    /// Bucket archives are uploaded to.
+    pub bucket: String,
+
+    /// Key prefix inside the bucket (defaults to `codex`).
+    pub prefix: Option<String>,
+
+    /// Endpoint for S3-compatible stores such as MinIO or R2, e.g.
+    /// `http://minio.internal:9000`. Objects are addressed path-style.
+    /// Defaults to AWS S3 in `region`.
+    pub endpoint: Option<String>,
+
+    /// SigV4 signing region (defaults to `us-east-1`).
+    pub region: Option<String>,
+
+    /// Static credentials; unset falls back to the `AWS_ACCESS_KEY_ID` and
+    /// `AWS_SECRET_ACCESS_KEY` environment variables.
+    pub access_key_id: Option<String>,
+    pub secret_access_key: Option<String>,
+
+    /// Seconds between archiver scans (defaults to 300).
+    pub scan_interval_seconds: Option<u64>,
+
+    /// Seconds a conversation's rollout must go unmodified before it is
+    /// considered complete and archived (defaults to 3600).
+    pub idle_seconds: Option<u64>,
+}
+
+/// `[http_server.images]` table: how image attachments are preprocessed
+/// before a turn sees them.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HttpImagesToml {
+    /// Largest width or height an attachment keeps; larger images are
+    /// downscaled (defaults to 2048).
+    pub max_dimension: Option<u32>,
+
+    /// Re-encode every attachment into this format: `png`, `jpeg`, or
+    /// `webp`. Unset keeps the encoding the pipeline picks per image.
+    pub format: Option<String>,
+}
+
+/// `[http_server.limits]` table: payload size bounds the HTTP server
+/// enforces. Unset fields keep the built-in defaults.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HttpLimitsToml {
+    /// Largest accepted request body, in bytes (defaults to 2 MiB).
+    pub max_request_bytes: Option<usize>,
+
+    /// Largest `/events` payload sent to an SSE client, in bytes; larger
+    /// payloads are replaced with a truncation stub (defaults to 256 KiB).
+    pub max_event_bytes: Option<usize>,
+
+    /// Transcript size, in bytes, beyond which exports are summarized by
+    /// eliding middle entries (defaults to 4 MiB).
+    pub max_transcript_bytes: Option<usize>,
+}
+
+/// `[http_server.notify]` table: webhooks the server posts to when a
+/// queued conversation completes, fails, or needs a sandbox grant approved.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HttpNotifyToml {
+    /// Slack incoming-webhook URL messages are posted to.
+    pub slack_webhook_url: Option<String>,
+
+    /// Discord webhook URL messages are posted to.
+    pub discord_webhook_url: Option<String>,
+
+    /// Externally reachable base URL of this server, e.g.
+    /// `https://codex.example.com`; when set, notifications link back to
+    /// the job or conversation they describe.
+    pub base_url: Option<String>,
+}
+
+/// `[http_server.quota]` table: disk bounds on conversation workspaces.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HttpQuotaToml {
+    /// Largest on-disk size, in bytes, a conversation's workspace may reach
+    /// before further turns in it are refused. Unset means no quota.
+    pub max_workspace_bytes: Option<u64>,
+}
+
+/// `[http_server.redaction]` table: patterns scrubbed from transcripts
+/// and events before the server persists, returns, or streams them.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HttpRedactionToml {
+    /// Extra regexes whose matches are replaced with `[redacted]`, on top
+    /// of the built-in secret formats.
+    #[serde(default)]
+    pub patterns: Vec<String>,
+
+    /// Whether the built-in secret formats (API keys, bearer tokens,
+    /// credential assignments) are scrubbed too. Defaults to true.
+    pub builtin: Option<bool>,
+}
+
+/// `[http_server.instructions]` table: which instruction overrides API
+/// clients may attach when starting a conversation, and patterns that
+/// reject an override outright.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HttpInstructionsToml {
+    /// Override fields clients may send: `base_instructions`,
+    /// `user_instructions`, or both. Fields not listed are rejected, so
+    /// an empty (or absent) list denies every override.
+    #[serde(default)]
+    pub allow: Vec<String>,
+
+    /// Regexes that reject an allowed override when they match anywhere
+    /// in its text.
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
+/// `[http_server.chatops]` table: the Slack app used to approve or deny
+/// pending sandbox grants with interactive buttons instead of an operator
+/// at a terminal.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HttpChatopsToml {
+    /// Bot token (`xoxb-…`) approval messages are posted with.
+    pub slack_bot_token: Option<String>,
+
+    /// Channel id or name the approval messages go to.
+    pub channel: Option<String>,
+
+    /// Signing secret used to validate Slack's callback requests; the
+    /// app's interactivity request URL must point at this server's
+    /// `/chatops/slack/callback`.
+    pub signing_secret: Option<String>,
+}
+
+/// `[http_server.sandbox_limits]` table: the widest sandbox the server may
+/// grant to a single conversation. Defaults to granting nothing.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HttpSandboxLimitsToml {
+    /// Directories whose subtrees may be added as writable roots.
+    #[serde(default)]
+    pub writable_roots: Vec<PathBuf>,
+
+    /// Whether network access may be granted.
+    #[serde(default)]
+    pub network_access: bool,
+}
+
+/// Effective HTTP-server settings after defaults are applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpServerConfig {
+    pub schedules: Vec<HttpScheduleToml>,
+    pub job_workers: usize,
+    pub github_token: Option<String>,
+    pub templates: Vec<HttpTemplateToml>,
+    pub event_bus: Option<String>,
+    pub sandbox_limits: HttpSandboxLimitsToml,
+    pub max_turn_seconds: Option<u64>,
+    pub max_concurrent_turns: Option<usize>,
+    pub archive: Option<HttpArchiveToml>,
+    pub notify: Option<HttpNotifyToml>,
+    pub limits: Option<HttpLimitsToml>,
+    pub images: Option<HttpImagesToml>,
+    pub chatops: Option<HttpChatopsToml>,
+    pub quota: Option<HttpQuotaToml>,
+    pub redaction: Option<HttpRedactionToml>,
+    pub instructions: Option<HttpInstructionsToml>,
+}
+
+impl Default for HttpServerConfig {
+    fn default() -> Self {
+        HttpServerConfig {
+            schedules: Vec::new(),
+            job_workers: DEFAULT_HTTP_JOB_WORKERS,
+            github_token: None,
+            templates: Vec::new(),
+            event_bus: None,
+            sandbox_limits: HttpSandboxLimitsToml::default(),
+            max_turn_seconds: None,
+            max_concurrent_turns: None,
+            archive: None,
+            notify: None,
+            limits: None,
+            images: None,
+            chatops: None,
+            quota: None,
+            redaction: None,
+            instructions: None,
+        }
+    }
+}
+
+impl HttpServerConfig {
+    pub fn from_toml(toml: Option<HttpServerToml>) -> Self {
+        let toml = toml.unwrap_or_default();
+        Self {
+            schedules: toml.schedules,
+            job_workers: toml.job_workers.unwrap_or(DEFAULT_HTTP_JOB_WORKERS),
+            github_token: toml.github_token,
+            templates: toml.templates,
+            event_bus: toml.event_bus,
+            sandbox_limits: toml.sandbox_limits.unwrap_or_default(),
+            max_turn_seconds: toml.max_turn_seconds,
+            max_concurrent_turns: toml.max_concurrent_turns,
+            archive: toml.archive,
+            notify: toml.notify,
+            limits: toml.limits,
+            images: toml.images,
+            chatops: toml.chatops,
+            quota: toml.quota,
+            redaction: toml.redaction,
+            instructions: toml.instructions,
+        }
+    }
+}
+
+/// Conservative default: scheduled and queued conversations run real agent
+/// turns, so parallelism is kept low unless the operator raises it.
+pub const DEFAULT_HTTP_JOB_WORKERS: usize = 2;
+
+/// `[response_cache]` table in config.toml. All fields optional so we can
+/// apply defaults.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ResponseCacheToml {
+    /// Whether to cache non-streaming model responses on disk. Off by
+    /// default; mainly useful for CI reruns hitting identical prompts.
+    pub enabled: Option<bool>,
+
+    /// How long a cached response stays valid, in seconds.
+    pub ttl_seconds: Option<u64>,
+}
+
+/// Effective response-cache settings after defaults are applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponseCacheConfig {
+    pub enabled: bool,
+    pub ttl_seconds: u64,
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        ResponseCacheConfig {
+            enabled: false,
+            ttl_seconds: DEFAULT_RESPONSE_CACHE_TTL_SECONDS,
+        }
+    }
+}
+
+/// One day: long enough to cover CI retries, short enough that stale output
+/// ages out without manual cleanup.
+pub const DEFAULT_RESPONSE_CACHE_TTL_SECONDS: u64 = 24 * 60 * 60;
+
+/// Per-1M-token prices for a model pattern under `[model_rates]`. Overrides
+/// (or extends) the rate table bundled with Codex; patterns support a `*`
+/// wildcard like `[model_routes]`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ModelRateToml {
+    /// Dollars per 1M uncached input tokens.
+    pub input_per_mtok: Option<f64>,
+
+    /// Dollars per 1M cached input tokens. Defaults to the uncached rate.
+    pub cached_input_per_mtok: Option<f64>,
+
+    /// Dollars per 1M output tokens (reasoning tokens bill as output).
+    pub output_per_mtok: Option<f64>,
+}
+
+/// One `[provider_pools]` entry: a set of interchangeable providers (e.g.
+/// OpenRouter and direct Anthropic serving the same model) that requests are
+/// balanced across by observed latency and error rate.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ProviderPoolToml {
+    /// Member provider ids, in preference order. Order breaks ties until
+    /// latency data has been observed for a member.
+    pub providers: Vec<String>,
+}
+
 /// Effective OTEL settings after defaults are applied.
 #[derive(Debug, Clone, PartialEq)]
 pub struct OtelConfig {
@@ -31,11 +31,16 @@ use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 
 use codex_api::AgentIdentityTelemetry;
+use codex_api::AnthropicCacheOptions;
+use codex_api::AnthropicMessagesClient as ApiAnthropicMessagesClient;
 use codex_api::ApiError;
 use codex_api::AuthProvider;
 use codex_api::CompactClient as ApiCompactClient;
 use codex_api::CompactionInput as ApiCompactionInput;
 use codex_api::Compression;
+use codex_api::EmbeddingsClient as ApiEmbeddingsClient;
+use codex_api::EmbeddingsRequest as ApiEmbeddingsRequest;
+use codex_api::GeminiGenerateContentClient as ApiGeminiGenerateContentClient;
 use codex_api::MemoriesClient as ApiMemoriesClient;
 use codex_api::MemorySummarizeInput as ApiMemorySummarizeInput;
 use codex_api::MemorySummarizeOutput as ApiMemorySummarizeOutput;
@@ -54,10 +59,12 @@ use codex_api::ResponsesOptions as ApiResponsesOptions;
 use codex_api::ResponsesWebsocketClient as ApiWebSocketResponsesClient;
 use codex_api::ResponsesWebsocketConnection as ApiWebSocketConnection;
 use codex_api::ResponsesWsRequest;
+use codex_api::RetryBudget;
 use codex_api::SharedAuthProvider;
 use codex_api::SseTelemetry;
 use codex_api::StreamOptions;
 use codex_api::TransportError;
+use codex_api::VcrTransport;
 use codex_api::WebsocketTelemetry;
 use codex_api::auth_header_telemetry;
 use codex_api::build_session_headers;
@@ -114,6 +121,7 @@ use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
 use crate::client_common::ResponseStream;
 use crate::feedback_tags;
+use crate::response_cache::ResponseCache;
 use crate::responses_metadata::CodexResponsesMetadata;
 use crate::responses_metadata::subagent_header_value;
 use crate::util::emit_feedback_auth_recovery_tags;
@@ -156,11 +164,19 @@ const X_OPENAI_INTERNAL_CODEX_RESPONSES_LITE_HEADER: &str =
     "x-openai-internal-codex-responses-lite";
 const REALTIME_CALLS_ENDPOINT: &str = "/realtime/calls";
 const RESPONSES_ENDPOINT: &str = "/responses";
+const ANTHROPIC_MESSAGES_ENDPOINT: &str = "/messages";
+const GEMINI_GENERATE_CONTENT_ENDPOINT: &str = "/streamGenerateContent";
 const RESPONSES_COMPACT_ENDPOINT: &str = "/responses/compact";
 // `/responses/compact` is unary, so the timeout covers the full response rather than one idle
 // period between stream events.
 const COMPACT_REQUEST_TIMEOUT_IDLE_MULTIPLIER: u32 = 4;
 const MEMORIES_SUMMARIZE_ENDPOINT: &str = "/memories/trace_summarize";
+const EMBEDDINGS_ENDPOINT: &str = "/embeddings";
+/// Cumulative retries allowed across all requests within one turn, on top of
+/// the per-request retry limits.
+const TURN_RETRY_BUDGET_MAX_RETRIES: u64 = 64;
+/// Wall-clock ceiling on retry-driven waiting within one turn.
+const TURN_RETRY_BUDGET_MAX_ELAPSED: Duration = Duration::from_secs(10 * 60);
 #[cfg(test)]
 pub(crate) const WEBSOCKET_CONNECT_TIMEOUT: Duration =
     Duration::from_millis(DEFAULT_WEBSOCKET_CONNECT_TIMEOUT_MS);
@@ -253,6 +269,7 @@ pub struct ModelClient {
     state: Arc<ModelClientState>,
     agent_identity_policy: AgentIdentityAuthPolicy,
     prompt_cache_key_override: Option<String>,
+    response_cache: Option<ResponseCache>,
     http_client_factory: HttpClientFactory,
 }
 
@@ -454,10 +471,59 @@ impl ModelClient {
             }),
             agent_identity_policy,
             prompt_cache_key_override: None,
+            response_cache: None,
             http_client_factory,
         }
     }
 
+    /// Derives a client that targets a different provider while sharing every
+    /// other session-scoped setting (auth, thread id, feature toggles).
+    ///
+    /// Used by the provider-race mode, which needs a second client bound to
+    /// the secondary provider. Transport fallback and websocket caching state
+    /// start fresh since they are provider-specific.
+    pub(crate) fn with_provider(&self, provider_info: ModelProviderInfo) -> Self {
+        let model_provider = create_model_provider(provider_info, self.auth_manager());
+        let codex_api_key_env_enabled = model_provider
+            .auth_manager()
+            .as_ref()
+            .is_some_and(|manager| manager.codex_api_key_env_enabled());
+        let auth_env_telemetry =
+            collect_auth_env_telemetry(model_provider.info(), codex_api_key_env_enabled);
+        let include_attestation = model_provider.supports_attestation();
+        Self {
+            state: Arc::new(ModelClientState {
+                thread_id: self.state.thread_id,
+                provider: model_provider,
+                auth_env_telemetry,
+                session_source: self.state.session_source.clone(),
+                originator: self.state.originator.clone(),
+                model_verbosity: self.state.model_verbosity,
+                enable_request_compression: self.state.enable_request_compression,
+                include_timing_metrics: self.state.include_timing_metrics,
+                beta_features_header: self.state.beta_features_header.clone(),
+                item_ids_enabled: self.state.item_ids_enabled,
+                concurrent_reasoning_summaries_enabled: self
+                    .state
+                    .concurrent_reasoning_summaries_enabled,
+                include_attestation,
+                attestation_provider: self.state.attestation_provider.clone(),
+                disable_websockets: AtomicBool::new(false),
+                agent_identity_session_fallback: AgentIdentitySessionFallback::default(),
+                cached_websocket_session: StdMutex::new(WebsocketSession::default()),
+            }),
+            agent_identity_policy: self.agent_identity_policy,
+            prompt_cache_key_override: self.prompt_cache_key_override.clone(),
+            response_cache: self.response_cache.clone(),
+            http_client_factory: self.http_client_factory.clone(),
+        }
+    }
+
+    /// Display name of the provider this client is bound to.
+    pub(crate) fn provider_name(&self) -> String {
+        self.state.provider.info().name.clone()
+    }
+
     pub(crate) fn with_prompt_cache_key_override(
         mut self,
         prompt_cache_key_override: Option<String>,
@@ -466,12 +532,47 @@ impl ModelClient {
         self
     }
 
+    /// Attaches the opt-in disk cache consulted by the unary utility calls
+    /// ([`ModelClient::summarize_memories`],
+    /// [`ModelClient::create_embeddings`]); `None` leaves them uncached.
+    pub(crate) fn with_response_cache(mut self, response_cache: Option<ResponseCache>) -> Self {
+        self.response_cache = response_cache;
+        self
+    }
+
     fn prompt_cache_key(&self) -> String {
+        // Precedence: per-turn override, then a fixed provider-level key, then
+        // the per-thread default.
         self.prompt_cache_key_override
             .clone()
+            .or_else(|| {
+                self.state
+                    .provider
+                    .info()
+                    .prompt_cache
+                    .as_ref()
+                    .and_then(|cache| cache.prompt_cache_key.clone())
+            })
             .unwrap_or_else(|| self.state.thread_id.to_string())
     }
 
+    /// Cache breakpoint placement for Anthropic Messages requests, honoring
+    /// any `prompt_cache` overrides on the provider config.
+    fn anthropic_cache_options(&self) -> AnthropicCacheOptions {
+        let defaults = AnthropicCacheOptions::default();
+        let Some(cache) = self.state.provider.info().prompt_cache.as_ref() else {
+            return defaults;
+        };
+        AnthropicCacheOptions {
+            cache_system_prompt: cache
+                .cache_system_prompt
+                .unwrap_or(defaults.cache_system_prompt),
+            cache_tool_definitions: cache
+                .cache_tool_definitions
+                .unwrap_or(defaults.cache_tool_definitions),
+        }
+    }
+
     /// Creates a fresh turn-scoped streaming session.
     ///
     /// This constructor does not perform network I/O itself; the session opens a websocket lazily
@@ -681,6 +782,25 @@ impl ModelClient {
             return Ok(Vec::new());
         }
 
+        let payload = ApiMemorySummarizeInput {
+            model: model_info.slug.clone(),
+            raw_memories,
+            reasoning: effort
+                .map(reasoning_effort_for_request)
+                .map(|effort| Reasoning {
+                    effort: Some(effort),
+                    summary: None,
+                    context: None,
+                }),
+        };
+        // Cache lookup happens before auth and transport setup so a hit
+        // skips the wire entirely.
+        if let Some(cache) = &self.response_cache
+            && let Some(cached) = cache.lookup(&payload.model, &payload)
+        {
+            return Ok(cached);
+        }
+
         let client_setup = self.current_client_setup().await?;
         let transport =
             self.build_api_transport(&client_setup.api_provider, MEMORIES_SUMMARIZE_ENDPOINT)?;
@@ -699,22 +819,78 @@ impl ModelClient {
             ApiMemoriesClient::new(transport, client_setup.api_provider, client_setup.api_auth)
                 .with_telemetry(Some(request_telemetry));
 
-        let payload = ApiMemorySummarizeInput {
-            model: model_info.slug.clone(),
-            raw_memories,
-            reasoning: effort
-                .map(reasoning_effort_for_request)
-                .map(|effort| Reasoning {
-                    effort: Some(effort),
-                    summary: None,
-                    context: None,
-                }),
+        let output = client
+            .summarize_input(&payload, self.build_subagent_headers())
+            .await
+            .map_err(|error| self.state.provider.map_api_error(error))?;
+        if let Some(cache) = &self.response_cache {
+            cache.store(&payload.model, &payload, &output);
+        }
+        Ok(output)
+    }
+
+    /// Creates embeddings for the given inputs using the provider's
+    /// configured `embedding_model`.
+    ///
+    /// This is a unary call (no streaming) to the OpenAI-compatible
+    /// `/embeddings` endpoint. Vectors are returned in input order.
+    pub async fn create_embeddings(
+        &self,
+        input: Vec<String>,
+        session_telemetry: &SessionTelemetry,
+    ) -> Result<Vec<Vec<f32>>> {
+        if input.is_empty() {
+            return Ok(Vec::new());
+        }
+        let Some(embedding_model) = self.state.provider.info().embedding_model.clone() else {
+            return Err(CodexErr::UnsupportedOperation(format!(
+                "provider `{}` has no `embedding_model` configured",
+                self.state.provider.info().name
+            )));
         };
 
-        client
-            .summarize_input(&payload, self.build_subagent_headers())
+        let request = ApiEmbeddingsRequest {
+            model: embedding_model,
+            input,
+            dimensions: None,
+        };
+        if let Some(cache) = &self.response_cache
+            && let Some(cached) = cache.lookup(&request.model, &request)
+        {
+            return Ok(cached);
+        }
+
+        let client_setup = self.current_client_setup().await?;
+        let transport =
+            self.build_api_transport(&client_setup.api_provider, EMBEDDINGS_ENDPOINT)?;
+        let request_telemetry = Self::build_request_telemetry(
+            session_telemetry,
+            AuthRequestTelemetryContext::new(
+                client_setup.auth.as_ref().map(CodexAuth::auth_mode),
+                client_setup.api_auth.as_ref(),
+                client_setup.agent_identity_telemetry.clone(),
+                PendingUnauthorizedRetry::default(),
+            ),
+            RequestRouteTelemetry::for_endpoint(EMBEDDINGS_ENDPOINT),
+            self.state.auth_env_telemetry.clone(),
+        );
+        let client =
+            ApiEmbeddingsClient::new(transport, client_setup.api_provider, client_setup.api_auth)
+                .with_telemetry(Some(request_telemetry));
+
+        let response = client
+            .create(&request, self.build_subagent_headers())
             .await
-            .map_err(|error| self.state.provider.map_api_error(error))
+            .map_err(|error| self.state.provider.map_api_error(error))?;
+        let vectors: Vec<Vec<f32>> = response
+            .data
+            .into_iter()
+            .map(|embedding| embedding.embedding)
+            .collect();
+        if let Some(cache) = &self.response_cache {
+            cache.store(&request.model, &request, &vectors);
+        }
+        Ok(vectors)
     }
 
     fn build_subagent_headers(&self) -> ApiHeaderMap {
@@ -964,15 +1140,27 @@ impl ModelClient {
         &self,
         api_provider: &ApiProvider,
         endpoint: &str,
-    ) -> Result<ReqwestTransport> {
+    ) -> Result<VcrTransport<ReqwestTransport>> {
         let request_url = api_provider.url_for_path(endpoint);
-        let client = build_default_reqwest_client_for_route(
-            &self.http_client_factory,
-            &request_url,
-            ClientRouteClass::Api,
-        )
-        .map_err(std::io::Error::from)?;
-        Ok(ReqwestTransport::new(client))
+        let provider_info = self.state.provider.info();
+        let client = if provider_info.has_transport_overrides() {
+            provider_info
+                .apply_transport_overrides(
+                    reqwest::Client::builder()
+                        .default_headers(codex_login::default_client::default_headers()),
+                )
+                .and_then(|builder| builder.build().map_err(|err| err.to_string()))
+                .map_err(std::io::Error::other)?
+        } else {
+            build_default_reqwest_client_for_route(
+                &self.http_client_factory,
+                &request_url,
+                ClientRouteClass::Api,
+            )
+            .map_err(std::io::Error::from)?
+        };
+        VcrTransport::from_env(ReqwestTransport::new(client))
+            .map_err(|err| std::io::Error::other(err).into())
     }
 
     pub(crate) async fn prewarm_auth(&self) -> Result<()> {
@@ -1116,6 +1304,11 @@ impl ModelClientSession {
         Arc::clone(&self.turn_state)
     }
 
+    /// Display name of the provider this session's client is bound to.
+    pub(crate) fn provider_name(&self) -> String {
+        self.client.provider_name()
+    }
+
     fn reset_websocket_session(&mut self) {
         self.websocket_session.connection = None;
         self.websocket_session.last_request = None;
@@ -1408,8 +1601,13 @@ impl ModelClientSession {
             .as_ref()
             .map(AuthManager::unauthorized_recovery);
         let mut pending_retry = PendingUnauthorizedRetry::default();
+        let retry_budget = RetryBudget::new(
+            TURN_RETRY_BUDGET_MAX_RETRIES,
+            Some(TURN_RETRY_BUDGET_MAX_ELAPSED),
+        );
         loop {
-            let client_setup = self.client.current_client_setup().await?;
+            let mut client_setup = self.client.current_client_setup().await?;
+            client_setup.api_provider.retry.budget = Some(retry_budget.clone());
             let transport = self
                 .client
                 .build_api_transport(&client_setup.api_provider, RESPONSES_ENDPOINT)?;
@@ -1539,8 +1737,13 @@ impl ModelClientSession {
             .as_ref()
             .map(AuthManager::unauthorized_recovery);
         let mut pending_retry = PendingUnauthorizedRetry::default();
+        let retry_budget = RetryBudget::new(
+            TURN_RETRY_BUDGET_MAX_RETRIES,
+            Some(TURN_RETRY_BUDGET_MAX_ELAPSED),
+        );
         loop {
-            let client_setup = self.client.current_client_setup().await?;
+            let mut client_setup = self.client.current_client_setup().await?;
+            client_setup.api_provider.retry.budget = Some(retry_budget.clone());
             let request_auth_context = AuthRequestTelemetryContext::new(
                 client_setup.auth.as_ref().map(CodexAuth::auth_mode),
                 client_setup.api_auth.as_ref(),
@@ -1821,6 +2024,292 @@ impl ModelClientSession {
                 )
                 .await
             }
+            WireApi::AnthropicMessages => {
+                self.stream_anthropic_messages(
+                    prompt,
+                    model_info,
+                    session_telemetry,
+                    effort,
+                    summary,
+                    service_tier,
+                    responses_metadata,
+                    inference_trace,
+                )
+                .await
+            }
+            WireApi::GeminiGenerateContent => {
+                self.stream_gemini_generate_content(
+                    prompt,
+                    model_info,
+                    session_telemetry,
+                    effort,
+                    summary,
+                    service_tier,
+                    responses_metadata,
+                    inference_trace,
+                )
+                .await
+            }
+        }
+    }
+
+    /// Streams a turn via Anthropic's native Messages API.
+    ///
+    /// The request is still built in the Responses shape; `codex-api` owns the
+    /// translation to Messages blocks and back, so retries and telemetry here
+    /// mirror [`Self::stream_responses_api`].
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(
+        name = "model_client.stream_anthropic_messages",
+        level = "info",
+        skip_all,
+        fields(
+            model = %model_info.slug,
+            wire_api = %self.client.state.provider.info().wire_api,
+            transport = "anthropic_messages_http",
+            http.method = "POST",
+            api.path = "messages",
+        )
+    )]
+    async fn stream_anthropic_messages(
+        &self,
+        prompt: &Prompt,
+        model_info: &ModelInfo,
+        session_telemetry: &SessionTelemetry,
+        effort: Option<ReasoningEffortConfig>,
+        summary: ReasoningSummaryConfig,
+        service_tier: Option<String>,
+        responses_metadata: &CodexResponsesMetadata,
+        inference_trace: &InferenceTraceContext,
+    ) -> Result<ResponseStream> {
+        let auth_manager = self.client.state.provider.auth_manager();
+        let mut auth_recovery = auth_manager
+            .as_ref()
+            .map(AuthManager::unauthorized_recovery);
+        let mut pending_retry = PendingUnauthorizedRetry::default();
+        let retry_budget = RetryBudget::new(
+            TURN_RETRY_BUDGET_MAX_RETRIES,
+            Some(TURN_RETRY_BUDGET_MAX_ELAPSED),
+        );
+        loop {
+            let mut client_setup = self.client.current_client_setup().await?;
+            client_setup.api_provider.retry.budget = Some(retry_budget.clone());
+            let transport = self
+                .client
+                .build_api_transport(&client_setup.api_provider, ANTHROPIC_MESSAGES_ENDPOINT)?;
+            let request_auth_context = AuthRequestTelemetryContext::new(
+                client_setup.auth.as_ref().map(CodexAuth::auth_mode),
+                client_setup.api_auth.as_ref(),
+                client_setup.agent_identity_telemetry.clone(),
+                pending_retry,
+            );
+            let (request_telemetry, sse_telemetry) = Self::build_streaming_telemetry(
+                session_telemetry,
+                request_auth_context,
+                RequestRouteTelemetry::for_endpoint(ANTHROPIC_MESSAGES_ENDPOINT),
+                self.client.state.auth_env_telemetry.clone(),
+            );
+
+            let mut request = self.client.build_responses_request(
+                &client_setup.api_provider,
+                prompt,
+                model_info,
+                effort.clone(),
+                summary,
+                service_tier.clone(),
+                responses_metadata,
+            )?;
+            let store = request.store;
+            self.client
+                .prepare_response_items_for_request(&mut request.input, store);
+            let request_session_telemetry =
+                session_telemetry_for_request(session_telemetry, &request);
+            let mut extra_headers = ApiHeaderMap::new();
+            let inference_trace_attempt = inference_trace.start_attempt();
+            inference_trace_attempt.add_request_headers(&mut extra_headers);
+            inference_trace_attempt.record_started(&request);
+            let client = ApiAnthropicMessagesClient::new(
+                transport,
+                client_setup.api_provider,
+                client_setup.api_auth,
+            )
+            .with_telemetry(Some(request_telemetry), Some(sse_telemetry))
+            .with_cache_options(self.client.anthropic_cache_options());
+            let stream_result = client.stream_request(&request, extra_headers).await;
+
+            match stream_result {
+                Ok(stream) => {
+                    let (stream, _) = map_response_stream(
+                        stream,
+                        request_session_telemetry,
+                        inference_trace_attempt,
+                        Arc::clone(&self.client.state.provider),
+                    );
+                    return Ok(stream);
+                }
+                Err(ApiError::Transport(
+                    unauthorized_transport @ TransportError::Http { status, .. },
+                )) if status == StatusCode::UNAUTHORIZED => {
+                    let response_debug_context =
+                        extract_response_debug_context(&unauthorized_transport);
+                    inference_trace_attempt.record_failed(
+                        &unauthorized_transport,
+                        response_debug_context.request_id.as_deref(),
+                        /*output_items*/ &[],
+                    );
+                    pending_retry = PendingUnauthorizedRetry::from_recovery(
+                        handle_unauthorized(
+                            unauthorized_transport,
+                            &mut auth_recovery,
+                            session_telemetry,
+                            &self.client.state.provider,
+                        )
+                        .await?,
+                    );
+                    continue;
+                }
+                Err(err) => {
+                    let response_debug_context =
+                        extract_response_debug_context_from_api_error(&err);
+                    let err = self.client.state.provider.map_api_error(err);
+                    inference_trace_attempt.record_failed(
+                        &err,
+                        response_debug_context.request_id.as_deref(),
+                        /*output_items*/ &[],
+                    );
+                    return Err(err);
+                }
+            }
+        }
+    }
+
+    /// Streams a turn via Google's native `generateContent` API.
+    ///
+    /// The request is still built in the Responses shape; `codex-api` owns the
+    /// translation to Gemini contents and back, so retries and telemetry here
+    /// mirror [`Self::stream_responses_api`].
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(
+        name = "model_client.stream_gemini_generate_content",
+        level = "info",
+        skip_all,
+        fields(
+            model = %model_info.slug,
+            wire_api = %self.client.state.provider.info().wire_api,
+            transport = "gemini_http",
+            http.method = "POST",
+            api.path = "streamGenerateContent",
+        )
+    )]
+    async fn stream_gemini_generate_content(
+        &self,
+        prompt: &Prompt,
+        model_info: &ModelInfo,
+        session_telemetry: &SessionTelemetry,
+        effort: Option<ReasoningEffortConfig>,
+        summary: ReasoningSummaryConfig,
+        service_tier: Option<String>,
+        responses_metadata: &CodexResponsesMetadata,
+        inference_trace: &InferenceTraceContext,
+    ) -> Result<ResponseStream> {
+        let auth_manager = self.client.state.provider.auth_manager();
+        let mut auth_recovery = auth_manager
+            .as_ref()
+            .map(AuthManager::unauthorized_recovery);
+        let mut pending_retry = PendingUnauthorizedRetry::default();
+        let retry_budget = RetryBudget::new(
+            TURN_RETRY_BUDGET_MAX_RETRIES,
+            Some(TURN_RETRY_BUDGET_MAX_ELAPSED),
+        );
+        loop {
+            let mut client_setup = self.client.current_client_setup().await?;
+            client_setup.api_provider.retry.budget = Some(retry_budget.clone());
+            let transport = self.client.build_api_transport(
+                &client_setup.api_provider,
+                GEMINI_GENERATE_CONTENT_ENDPOINT,
+            )?;
+            let request_auth_context = AuthRequestTelemetryContext::new(
+                client_setup.auth.as_ref().map(CodexAuth::auth_mode),
+                client_setup.api_auth.as_ref(),
+                client_setup.agent_identity_telemetry.clone(),
+                pending_retry,
+            );
+            let (request_telemetry, sse_telemetry) = Self::build_streaming_telemetry(
+                session_telemetry,
+                request_auth_context,
+                RequestRouteTelemetry::for_endpoint(GEMINI_GENERATE_CONTENT_ENDPOINT),
+                self.client.state.auth_env_telemetry.clone(),
+            );
+
+            let mut request = self.client.build_responses_request(
+                &client_setup.api_provider,
+                prompt,
+                model_info,
+                effort.clone(),
+                summary,
+                service_tier.clone(),
+                responses_metadata,
+            )?;
+            let store = request.store;
+            self.client
+                .prepare_response_items_for_request(&mut request.input, store);
+            let request_session_telemetry =
+                session_telemetry_for_request(session_telemetry, &request);
+            let mut extra_headers = ApiHeaderMap::new();
+            let inference_trace_attempt = inference_trace.start_attempt();
+            inference_trace_attempt.add_request_headers(&mut extra_headers);
+            inference_trace_attempt.record_started(&request);
+            let client = ApiGeminiGenerateContentClient::new(
+                transport,
+                client_setup.api_provider,
+                client_setup.api_auth,
+            )
+            .with_telemetry(Some(request_telemetry), Some(sse_telemetry));
+            let stream_result = client.stream_request(&request, extra_headers).await;
+
+            match stream_result {
+                Ok(stream) => {
+                    let (stream, _) = map_response_stream(
+                        stream,
+                        request_session_telemetry,
+                        inference_trace_attempt,
+                        Arc::clone(&self.client.state.provider),
+                    );
+                    return Ok(stream);
+                }
+                Err(ApiError::Transport(
+                    unauthorized_transport @ TransportError::Http { status, .. },
+                )) if status == StatusCode::UNAUTHORIZED => {
+                    let response_debug_context =
+                        extract_response_debug_context(&unauthorized_transport);
+                    inference_trace_attempt.record_failed(
+                        &unauthorized_transport,
+                        response_debug_context.request_id.as_deref(),
+                        /*output_items*/ &[],
+                    );
+                    pending_retry = PendingUnauthorizedRetry::from_recovery(
+                        handle_unauthorized(
+                            unauthorized_transport,
+                            &mut auth_recovery,
+                            session_telemetry,
+                            &self.client.state.provider,
+                        )
+                        .await?,
+                    );
+                    continue;
+                }
+                Err(err) => {
+                    let response_debug_context =
+                        extract_response_debug_context_from_api_error(&err);
+                    let err = self.client.state.provider.map_api_error(err);
+                    inference_trace_attempt.record_failed(
+                        &err,
+                        response_debug_context.request_id.as_deref(),
+                        /*output_items*/ &[],
+                    );
+                    return Err(err);
+                }
+            }
         }
     }
 
@@ -2001,6 +2490,16 @@ where
                     end_turn,
                 }) => {
                     feedback_tags!(last_model_response_id = &response_id);
+                    crate::provider_pool::record_request_outcome(
+                        &provider.info().name,
+                        Some(
+                            ttft_ms
+                                .and_then(|ms| u64::try_from(ms).ok())
+                                .map(Duration::from_millis)
+                                .unwrap_or_else(|| request_start.elapsed()),
+                        ),
+                        /* success */ true,
+                    );
                     if let Some(usage) = &token_usage {
                         session_telemetry.sse_event_completed(
                             usage.input_tokens,
@@ -2066,6 +2565,11 @@ where
                     );
                     if !logged_error {
                         session_telemetry.see_event_completed_failed(&mapped);
+                        crate::provider_pool::record_request_outcome(
+                            &provider.info().name,
+                            /* latency */ None,
+                            /* success */ false,
+                        );
                         logged_error = true;
                     }
                     if tx_event.send(Err(mapped)).await.is_err() {
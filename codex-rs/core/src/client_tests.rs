@@ -572,6 +572,46 @@ async fn summarize_memories_returns_empty_for_empty_input() {
     assert_eq!(output.len(), 0);
 }
 
+#[tokio::test]
+async fn summarize_memories_returns_cached_output_without_dispatch() {
+    let dir = TempDir::new().expect("create tempdir");
+    let cache = crate::response_cache::ResponseCache::new(
+        dir.path().to_path_buf(),
+        Duration::from_secs(60),
+    );
+    let raw_memory = codex_api::RawMemory {
+        id: "memory-0".to_string(),
+        metadata: codex_api::RawMemoryMetadata {
+            source_path: "AGENTS.md".to_string(),
+        },
+        items: Vec::new(),
+    };
+    let payload = codex_api::MemorySummarizeInput {
+        model: "gpt-test".to_string(),
+        raw_memories: vec![raw_memory.clone()],
+        reasoning: None,
+    };
+    let cached = vec![codex_api::MemorySummarizeOutput {
+        raw_memory: "prefers rebase over merge".to_string(),
+        memory_summary: "Prefers rebase.".to_string(),
+    }];
+    cache.store(&payload.model, &payload, &cached);
+
+    // The test provider points at an unreachable host, so anything but a
+    // cache hit would fail the call.
+    let client = test_model_client(SessionSource::Cli).with_response_cache(Some(cache));
+    let output = client
+        .summarize_memories(
+            vec![raw_memory],
+            &test_model_info(),
+            /*effort*/ None,
+            &test_session_telemetry(),
+        )
+        .await
+        .expect("cached summarize request should succeed");
+    assert_eq!(output, cached);
+}
+
 #[tokio::test]
 async fn dropped_response_stream_traces_cancelled_partial_output() -> anyhow::Result<()> {
     let temp = TempDir::new()?;
@@ -500,9 +500,9 @@ fn message_text_token_count(item: &ResponseItem) -> usize {
     content
         .iter()
         .map(|item| match item {
-            ContentItem::InputText { text } | ContentItem::OutputText { text } => {
-                approx_token_count(text)
-            }
+            ContentItem::InputText { text }
+            | ContentItem::OutputText { text }
+            | ContentItem::Refusal { refusal: text } => approx_token_count(text),
             ContentItem::InputImage { .. } => 0,
         })
         .sum()
@@ -527,7 +527,9 @@ fn truncate_message_text_to_token_budget(
     let mut truncated_content = Vec::with_capacity(content.len());
     for mut content_item in content {
         match &mut content_item {
-            ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+            ContentItem::InputText { text }
+            | ContentItem::OutputText { text }
+            | ContentItem::Refusal { refusal: text } => {
                 if remaining == 0 {
                     continue;
                 }
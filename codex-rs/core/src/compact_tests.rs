@@ -267,6 +267,13 @@ fn should_use_remote_compact_task_for_azure_provider() {
         experimental_bearer_token: None,
         auth: None,
         aws: None,
+        oauth: None,
+        key_command: None,
+        key_file: None,
+        https_proxy: None,
+        no_proxy: None,
+        ca_bundle_path: None,
+        insecure_skip_verify: false,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
@@ -277,6 +284,8 @@ fn should_use_remote_compact_task_for_azure_provider() {
         websocket_connect_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        prompt_cache: None,
+        embedding_model: None,
     };
 
     assert!(should_use_remote_compact_task(&provider));
@@ -217,6 +217,48 @@ async fn load_config_normalizes_relative_cwd_override() -> std::io::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn load_config_offline_rejects_cloud_providers() -> std::io::Result<()> {
+    let codex_home = tempdir()?;
+    let err = Config::load_from_base_config_with_overrides(
+        ConfigToml {
+            offline: Some(true),
+            ..Default::default()
+        },
+        ConfigOverrides::default(),
+        codex_home.abs(),
+    )
+    .await
+    .expect_err("offline mode should reject the default cloud provider");
+
+    assert!(
+        err.to_string()
+            .contains("offline mode requires a local model provider"),
+        "unexpected error: {err}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn load_config_offline_clamps_retries_for_local_providers() -> std::io::Result<()> {
+    let codex_home = tempdir()?;
+    let config = Config::load_from_base_config_with_overrides(
+        ConfigToml {
+            offline: Some(true),
+            model_provider: Some("ollama".to_string()),
+            ..Default::default()
+        },
+        ConfigOverrides::default(),
+        codex_home.abs(),
+    )
+    .await?;
+
+    assert!(config.offline);
+    assert!(config.model_provider.request_max_retries() <= 1);
+    assert!(config.model_provider.stream_max_retries() <= 1);
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_toml_parsing() {
     let history_with_persistence = r#"
@@ -359,6 +401,7 @@ web_search = true
         Some(ToolsToml {
             web_search: None,
             experimental_request_user_input: None,
+            ra1: None,
         })
     );
 }
@@ -378,6 +421,7 @@ web_search = false
         Some(ToolsToml {
             web_search: None,
             experimental_request_user_input: None,
+            ra1: None,
         })
     );
 }
@@ -396,6 +440,7 @@ fn tools_experimental_request_user_input_defaults_to_enabled() {
         Some(ToolsToml {
             web_search: None,
             experimental_request_user_input: Some(ExperimentalRequestUserInput { enabled: true }),
+            ra1: None,
         })
     );
 }
@@ -415,6 +460,7 @@ enabled = false
         Some(ToolsToml {
             web_search: None,
             experimental_request_user_input: Some(ExperimentalRequestUserInput { enabled: false }),
+            ra1: None,
         })
     );
 }
@@ -429,6 +475,7 @@ async fn load_config_resolves_experimental_request_user_input_enabled() -> std::
                 experimental_request_user_input: Some(ExperimentalRequestUserInput {
                     enabled: false,
                 }),
+                ra1: None,
             }),
             ..ConfigToml::default()
         },
@@ -441,6 +488,35 @@ async fn load_config_resolves_experimental_request_user_input_enabled() -> std::
     Ok(())
 }
 
+#[tokio::test]
+async fn load_config_resolves_ra1_tool_config() -> std::io::Result<()> {
+    let codex_home = tempdir()?;
+    let config_toml: ConfigToml = toml::from_str(
+        r#"
+[tools.ra1]
+api_key_env = "MY_ART_KEY"
+base_url = "https://art.example.com/api/"
+default_size = "768x768"
+session_cost_cap_usd = 2.5
+"#,
+    )
+    .expect("TOML deserialization should succeed");
+    let config = Config::load_from_base_config_with_overrides(
+        config_toml,
+        ConfigOverrides::default(),
+        codex_home.abs(),
+    )
+    .await?;
+
+    assert!(config.ra1_tool.enabled);
+    assert_eq!(config.ra1_tool.api_key_env, "MY_ART_KEY");
+    // Trailing slashes are normalised away so endpoint paths join cleanly.
+    assert_eq!(config.ra1_tool.base_url, "https://art.example.com/api");
+    assert_eq!(config.ra1_tool.default_size, "768x768");
+    assert_eq!(config.ra1_tool.session_cost_cap_usd, Some(2.5));
+    Ok(())
+}
+
 #[tokio::test]
 async fn load_config_resolves_code_mode_config() -> std::io::Result<()> {
     let codex_home = tempdir()?;
@@ -11447,3 +11523,44 @@ fn test_tui_notification_condition_rejects_unknown_value() {
         "unexpected error: {err}"
     );
 }
+
+#[test]
+fn model_routes_pick_most_specific_pattern() {
+    let routes: HashMap<String, String> = [
+        ("claude-*".to_string(), "anthropic".to_string()),
+        ("claude-3-*".to_string(), "openrouter".to_string()),
+        ("llama*".to_string(), "ollama".to_string()),
+    ]
+    .into_iter()
+    .collect();
+
+    assert_eq!(
+        super::route_model_to_provider("claude-3-opus", &routes),
+        Some("openrouter".to_string())
+    );
+    assert_eq!(
+        super::route_model_to_provider("claude-sonnet", &routes),
+        Some("anthropic".to_string())
+    );
+    assert_eq!(
+        super::route_model_to_provider("llama3.3", &routes),
+        Some("ollama".to_string())
+    );
+    assert_eq!(super::route_model_to_provider("gpt-5.5", &routes), None);
+}
+
+#[test]
+fn model_route_patterns_match_case_insensitively_and_exactly_without_wildcard() {
+    let routes: HashMap<String, String> = [("grok-code-fast-1".to_string(), "xai".to_string())]
+        .into_iter()
+        .collect();
+
+    assert_eq!(
+        super::route_model_to_provider("Grok-Code-Fast-1", &routes),
+        Some("xai".to_string())
+    );
+    assert_eq!(
+        super::route_model_to_provider("grok-code-fast-10", &routes),
+        None
+    );
+}
@@ -41,8 +41,10 @@ use codex_config::types::McpServerConfig;
 use codex_config::types::McpServerDisabledReason;
 use codex_config::types::MemoriesConfig;
 use codex_config::types::ModelAvailabilityNuxConfig;
+use codex_config::types::ModelRateToml;
 use codex_config::types::Notice;
 use codex_config::types::OAuthCredentialsStoreMode;
+use codex_config::types::ProviderPoolToml;
 use codex_config::types::SessionPickerViewMode;
 use codex_config::types::ToolSuggestConfig;
 use codex_config::types::ToolSuggestDisabledTool;
@@ -86,6 +88,7 @@ use codex_model_provider_info::OLLAMA_CHAT_PROVIDER_REMOVED_ERROR;
 use codex_model_provider_info::built_in_model_providers;
 use codex_model_provider_info::merge_configured_model_providers;
 use codex_models_manager::ModelsManagerConfig;
+use codex_models_manager::capabilities::validate_model_supported;
 use codex_protocol::config_types::AltScreenMode;
 use codex_protocol::config_types::AutoCompactTokenLimitScope;
 use codex_protocol::config_types::ForcedLoginMethod;
@@ -649,6 +652,10 @@ pub struct Config {
     /// Info needed to make an API request to the model.
     pub model_provider: ModelProviderInfo,
 
+    /// Restrict provider resolution to local endpoints and avoid any network
+    /// access (model catalog refreshes stay on the cached copy).
+    pub offline: bool,
+
     /// Optionally specify the personality of the model
     pub personality: Option<Personality>,
 
@@ -852,6 +859,12 @@ pub struct Config {
     /// Combined provider map (defaults plus user-defined providers).
     pub model_providers: HashMap<String, ModelProviderInfo>,
 
+    /// User-supplied `[model_rates]` price overrides for cost accounting.
+    pub model_rates: HashMap<String, ModelRateToml>,
+
+    /// Named pools of interchangeable providers balanced by observed latency.
+    pub provider_pools: HashMap<String, ProviderPoolToml>,
+
     /// Maximum number of bytes to include from an AGENTS.md project doc file.
     pub project_doc_max_bytes: usize,
 
@@ -1021,6 +1034,9 @@ pub struct Config {
     /// Whether to register the experimental request_user_input tool.
     pub experimental_request_user_input_enabled: bool,
 
+    /// Configuration for the built-in RA1 generation tools.
+    pub ra1_tool: Ra1ToolConfig,
+
     /// Configuration for the experimental code-mode tool surface.
     pub code_mode: CodeModeConfig,
 
@@ -1081,6 +1097,48 @@ pub struct Config {
 
     /// OTEL configuration (exporter type, endpoint, headers, etc.).
     pub otel: codex_config::types::OtelConfig,
+
+    /// Refusal-fallback configuration (reroute refused turns to a secondary model).
+    pub refusal_fallback: codex_config::types::RefusalFallbackConfig,
+
+    /// Context-overflow fallback (retry oversized requests on a long-context model).
+    pub context_overflow_fallback: codex_config::types::ContextOverflowFallbackConfig,
+
+    /// Rules that route turns to a model picked from task signals.
+    pub model_rules: codex_config::types::ModelRulesConfig,
+
+    /// Settings for the standalone HTTP server.
+    pub http_server: codex_config::types::HttpServerConfig,
+
+    /// Provider race (send each sampling request to a secondary provider too;
+    /// the first usable stream wins).
+    pub provider_race: codex_config::types::ProviderRaceConfig,
+
+    /// On-disk cache for non-streaming model responses.
+    pub response_cache: codex_config::types::ResponseCacheConfig,
+}
+
+/// Resolved `[tools.ra1]` configuration with defaults applied, consumed by
+/// the MCP server's built-in RA1 generation tools.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Ra1ToolConfig {
+    pub enabled: bool,
+    pub api_key_env: String,
+    pub base_url: String,
+    pub default_size: String,
+    pub session_cost_cap_usd: Option<f64>,
+}
+
+impl Default for Ra1ToolConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            api_key_env: "NETWRCK_API_KEY".to_string(),
+            base_url: "https://netwrck.com/api".to_string(),
+            default_size: "1024x1024".to_string(),
+            session_cost_cap_usd: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
@@ -2472,6 +2530,30 @@ fn resolve_web_search_config(config_toml: &ConfigToml) -> Option<WebSearchConfig
         .map(Into::into)
 }
 
+fn resolve_ra1_tool_config(config_toml: &ConfigToml) -> Ra1ToolConfig {
+    let base = config_toml
+        .tools
+        .as_ref()
+        .and_then(|tools| tools.ra1.as_ref());
+    let default = Ra1ToolConfig::default();
+    Ra1ToolConfig {
+        enabled: base
+            .and_then(|config| config.enabled)
+            .unwrap_or(default.enabled),
+        api_key_env: base
+            .and_then(|config| config.api_key_env.clone())
+            .unwrap_or(default.api_key_env),
+        base_url: base
+            .and_then(|config| config.base_url.clone())
+            .map(|url| url.trim_end_matches('/').to_string())
+            .unwrap_or(default.base_url),
+        default_size: base
+            .and_then(|config| config.default_size.clone())
+            .unwrap_or(default.default_size),
+        session_cost_cap_usd: base.and_then(|config| config.session_cost_cap_usd),
+    }
+}
+
 fn resolve_experimental_request_user_input_enabled(config_toml: &ConfigToml) -> bool {
     config_toml
         .tools
@@ -3415,6 +3497,7 @@ impl Config {
         let web_search_config = resolve_web_search_config(&cfg);
         let experimental_request_user_input_enabled =
             resolve_experimental_request_user_input_enabled(&cfg);
+        let ra1_tool = resolve_ra1_tool_config(&cfg);
         let code_mode = resolve_code_mode_config(&cfg);
         let multi_agent_v2 = resolve_multi_agent_v2_config(&cfg);
         let token_budget = resolve_token_budget_config(&cfg, &features)?;
@@ -3435,9 +3518,42 @@ impl Config {
             merge_configured_model_providers(built_in_model_providers(openai_base_url), cfg.model_providers)
                 .map_err(|message| std::io::Error::new(std::io::ErrorKind::InvalidData, message))?;
 
+        // When `--model` is supplied without an explicit provider, consult the
+        // `[model_routes]` table so the model is sent somewhere that serves it
+        // rather than the active provider.
+        let routed_provider_id = model
+            .as_deref()
+            .and_then(|model| route_model_to_provider(model, &cfg.model_routes));
         let model_provider_id = model_provider
+            .or(routed_provider_id)
             .or(cfg.model_provider)
             .unwrap_or_else(|| "openai".to_string());
+        // A `[provider_pools]` id resolves to its healthiest member; request
+        // outcomes recorded while streaming feed subsequent selections.
+        let model_provider_id = match cfg.provider_pools.get(&model_provider_id) {
+            Some(pool) => {
+                let members: Vec<(&str, &str)> = pool
+                    .providers
+                    .iter()
+                    .filter_map(|id| {
+                        model_providers
+                            .get(id)
+                            .map(|info| (id.as_str(), info.name.as_str()))
+                    })
+                    .collect();
+                crate::provider_pool::select_from_pool(members)
+                    .map(str::to_string)
+                    .ok_or_else(|| {
+                        std::io::Error::new(
+                            std::io::ErrorKind::InvalidData,
+                            format!(
+                                "provider pool `{model_provider_id}` has no members that resolve to a configured provider"
+                            ),
+                        )
+                    })?
+            }
+            None => model_provider_id,
+        };
         let model_provider = model_providers
             .get(&model_provider_id)
             .ok_or_else(|| {
@@ -3450,6 +3566,28 @@ impl Config {
             })?
             .clone();
 
+        let offline = cfg.offline.unwrap_or(false);
+        let model_provider = if offline {
+            if !model_provider.is_local() {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!(
+                        "offline mode requires a local model provider, but `{model_provider_id}` \
+is not served from localhost; use --oss/--local-provider or configure a provider with a \
+localhost base_url"
+                    ),
+                ));
+            }
+            // A dead local endpoint refuses connections immediately, so the
+            // cloud-tuned retry schedule would only delay the diagnostic.
+            let mut model_provider = model_provider;
+            model_provider.request_max_retries = Some(model_provider.request_max_retries().min(1));
+            model_provider.stream_max_retries = Some(model_provider.stream_max_retries().min(1));
+            model_provider
+        } else {
+            model_provider
+        };
+
         let shell_environment_policy = shell_environment_policy_override
             .unwrap_or_else(|| cfg.shell_environment_policy.into());
         let allow_login_shell = cfg.allow_login_shell.unwrap_or(true);
@@ -3583,6 +3721,11 @@ impl Config {
         let forced_login_method = cfg.forced_login_method;
 
         let model = model.or(cfg.model);
+        if let Some(model) = &model {
+            validate_model_supported(model).map_err(|message| {
+                std::io::Error::new(std::io::ErrorKind::InvalidInput, message)
+            })?;
+        }
         let notices = cfg.notice.unwrap_or_default();
         let service_tier = match service_tier_override {
             Some(Some(service_tier)) => Some(service_tier),
@@ -3789,6 +3932,13 @@ impl Config {
         )
         .map_err(std::io::Error::from)?;
         let otel = otel::resolve_config(cfg.otel.unwrap_or_default(), &mut startup_warnings);
+        let refusal_fallback = crate::refusal_fallback::resolve_config(cfg.refusal_fallback);
+        let context_overflow_fallback =
+            crate::context_overflow_fallback::resolve_config(cfg.context_overflow_fallback);
+        let model_rules = crate::model_router::resolve_config(cfg.model_rules);
+        let http_server = codex_config::types::HttpServerConfig::from_toml(cfg.http_server);
+        let provider_race = crate::provider_race::resolve_config(cfg.provider_race);
+        let response_cache = crate::response_cache::resolve_config(cfg.response_cache);
         let config = Self {
             model,
             service_tier,
@@ -3800,6 +3950,7 @@ impl Config {
                 .unwrap_or_default(),
             model_provider_id,
             model_provider,
+            offline,
             cwd: resolved_cwd,
             workspace_roots: workspace_roots.clone(),
             workspace_roots_explicit,
@@ -3847,6 +3998,8 @@ impl Config {
             mcp_oauth_callback_port: cfg.mcp_oauth_callback_port,
             mcp_oauth_callback_url: cfg.mcp_oauth_callback_url.clone(),
             model_providers,
+            model_rates: cfg.model_rates,
+            provider_pools: cfg.provider_pools,
             project_doc_max_bytes: cfg.project_doc_max_bytes.unwrap_or(AGENTS_MD_MAX_BYTES),
             project_doc_fallback_filenames: cfg
                 .project_doc_fallback_filenames
@@ -3947,6 +4100,7 @@ impl Config {
             web_search_mode: constrained_web_search_mode.value,
             web_search_config,
             experimental_request_user_input_enabled,
+            ra1_tool,
             code_mode,
             use_experimental_unified_exec_tool,
             background_terminal_max_timeout,
@@ -4023,6 +4177,12 @@ impl Config {
                 .map(|t| t.keymap.clone())
                 .unwrap_or_default(),
             otel,
+            refusal_fallback,
+            context_overflow_fallback,
+            model_rules,
+            http_server,
+            provider_race,
+            response_cache,
         };
         Ok(config)
         })
@@ -4359,6 +4519,40 @@ pub fn log_dir(cfg: &Config) -> std::io::Result<PathBuf> {
     Ok(cfg.log_dir.clone())
 }
 
+/// Picks a provider for `model` from the `[model_routes]` table. Patterns
+/// support a `*` wildcard and match case-insensitively; the most specific
+/// match (most literal characters) wins so `claude-3-*` beats `claude-*`.
+fn route_model_to_provider(model: &str, routes: &HashMap<String, String>) -> Option<String> {
+    routes
+        .iter()
+        .filter(|(pattern, _)| model_pattern_matches(pattern, model))
+        .max_by_key(|(pattern, _)| pattern.chars().filter(|c| *c != '*').count())
+        .map(|(_, provider_id)| provider_id.clone())
+}
+
+pub(crate) fn model_pattern_matches(pattern: &str, model: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let model = model.to_lowercase();
+    let mut segments = pattern.split('*');
+    let first = segments.next().unwrap_or_default();
+    if !model.starts_with(first) {
+        return false;
+    }
+    let mut remainder = &model[first.len()..];
+    let rest: Vec<&str> = segments.collect();
+    let Some((last, middle)) = rest.split_last() else {
+        // No `*` in the pattern: require an exact match.
+        return remainder.is_empty();
+    };
+    for segment in middle {
+        match remainder.find(segment) {
+            Some(idx) => remainder = &remainder[idx + segment.len()..],
+            None => return false,
+        }
+    }
+    remainder.ends_with(last)
+}
+
 #[cfg(test)]
 #[path = "config_tests.rs"]
 mod tests;
@@ -237,6 +237,32 @@ impl ContextManager {
         }
     }
 
+    /// Content of the most recent assistant message, which refusal
+    /// classification inspects for explicit `refusal` output items as well
+    /// as the output text. `None` when no assistant message exists yet.
+    pub(crate) fn last_assistant_message_content(&self) -> Option<Vec<ContentItem>> {
+        self.items.iter().rev().find_map(|item| match item {
+            ResponseItem::Message { role, content, .. } if role == "assistant" => {
+                Some(content.clone())
+            }
+            _ => None,
+        })
+    }
+
+    /// Removes the most recent assistant message, so a turn rerouted by the
+    /// refusal fallback can re-answer without the refused response in its
+    /// context. Returns true when a message was removed.
+    pub(crate) fn remove_last_assistant_message(&mut self) -> bool {
+        let Some(index) = self.items.iter().rposition(
+            |item| matches!(item, ResponseItem::Message { role, .. } if role == "assistant"),
+        ) else {
+            return false;
+        };
+        self.items.remove(index);
+        self.history_version = self.history_version.saturating_add(1);
+        true
+    }
+
     /// Drop the last `num_turns` instruction turns from this history.
     ///
     /// Instruction turns are history messages that should behave like a new prompt boundary:
@@ -836,6 +836,34 @@ fn replace_last_turn_images_replaces_tool_output_images() {
     );
 }
 
+#[test]
+fn last_assistant_message_is_exposed_and_removable() {
+    let mut history = create_history_with_items(vec![
+        user_input_text_msg("please help"),
+        assistant_msg("I'm sorry, but I can't help with that."),
+    ]);
+
+    let content = history
+        .last_assistant_message_content()
+        .expect("assistant message");
+    assert_eq!(
+        content,
+        vec![ContentItem::OutputText {
+            text: "I'm sorry, but I can't help with that.".to_string(),
+        }]
+    );
+
+    let version = history.history_version();
+    assert!(history.remove_last_assistant_message());
+    assert_eq!(history.last_assistant_message_content(), None);
+    assert_eq!(
+        history.raw_items(),
+        vec![user_input_text_msg("please help")]
+    );
+    assert!(history.history_version() > version);
+    assert!(!history.remove_last_assistant_message());
+}
+
 #[test]
 fn replace_last_turn_images_does_not_touch_user_images() {
     let items = vec![ResponseItem::Message {
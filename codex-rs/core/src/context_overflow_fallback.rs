@@ -0,0 +1,82 @@
+//! Fallback routing when a request exceeds the model's context window.
+//!
+//! Long threads can outgrow the active model even after compaction. When the
+//! provider rejects a request for exceeding context, we can retry the turn on
+//! a configured long-context model (e.g. a 1M-context variant) instead of
+//! failing outright. This module resolves the `[context_overflow_fallback]`
+//! config block and decides which model, if any, to retry on.
+
+use codex_config::types::ContextOverflowFallbackConfig;
+use codex_config::types::ContextOverflowFallbackToml;
+
+pub(crate) fn resolve_config(
+    toml: Option<ContextOverflowFallbackToml>,
+) -> ContextOverflowFallbackConfig {
+    let toml = toml.unwrap_or_default();
+    // Configuring a fallback model opts in unless `enabled` says otherwise.
+    let enabled = toml.enabled.unwrap_or(toml.model.is_some());
+    ContextOverflowFallbackConfig {
+        enabled,
+        model: toml.model,
+    }
+}
+
+/// Returns the model to retry a context-length-exceeded request on, or `None`
+/// when the fallback is disabled, unconfigured, or the turn is already
+/// running on the fallback model.
+pub(crate) fn fallback_model(
+    config: &ContextOverflowFallbackConfig,
+    current_model: &str,
+) -> Option<String> {
+    if !config.enabled {
+        return None;
+    }
+    config
+        .model
+        .as_ref()
+        .filter(|model| model.as_str() != current_model)
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn configuring_a_model_enables_the_fallback() {
+        let config = resolve_config(Some(ContextOverflowFallbackToml {
+            enabled: None,
+            model: Some("gpt-5.3-codex-1m".to_string()),
+        }));
+        assert!(config.enabled);
+        assert_eq!(
+            fallback_model(&config, "gpt-5.3-codex"),
+            Some("gpt-5.3-codex-1m".to_string())
+        );
+    }
+
+    #[test]
+    fn explicit_disable_wins_over_configured_model() {
+        let config = resolve_config(Some(ContextOverflowFallbackToml {
+            enabled: Some(false),
+            model: Some("gpt-5.3-codex-1m".to_string()),
+        }));
+        assert_eq!(fallback_model(&config, "gpt-5.3-codex"), None);
+    }
+
+    #[test]
+    fn unconfigured_block_stays_disabled() {
+        let config = resolve_config(None);
+        assert!(!config.enabled);
+        assert_eq!(fallback_model(&config, "gpt-5.3-codex"), None);
+    }
+
+    #[test]
+    fn does_not_retry_on_the_model_that_overflowed() {
+        let config = resolve_config(Some(ContextOverflowFallbackToml {
+            enabled: Some(true),
+            model: Some("gpt-5.3-codex-1m".to_string()),
+        }));
+        assert_eq!(fallback_model(&config, "gpt-5.3-codex-1m"), None);
+    }
+}
@@ -0,0 +1,182 @@
+//! Token cost accounting per provider/model.
+//!
+//! Prices come from a bundled rate table keyed by model name patterns, which
+//! `[model_rates]` in config.toml can override or extend. Estimates feed the
+//! `TokenCount` event (and therefore the rollout) so spend can be attributed
+//! across providers after the fact, e.g. by `codex usage`.
+
+use std::collections::HashMap;
+
+use codex_config::types::ModelRateToml;
+use codex_protocol::protocol::TokenUsage;
+
+use crate::config::model_pattern_matches;
+
+/// Effective per-1M-token prices for a model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ModelRate {
+    pub input_per_mtok: f64,
+    pub cached_input_per_mtok: f64,
+    pub output_per_mtok: f64,
+}
+
+/// Bundled list-price table, dollars per 1M tokens. Approximate and
+/// intentionally coarse: `[model_rates]` is the escape hatch when a team
+/// needs exact contract pricing. Most specific matching pattern wins.
+const BUILTIN_RATES: &[(&str, ModelRate)] = &[
+    (
+        "gpt-5*",
+        ModelRate {
+            input_per_mtok: 1.25,
+            cached_input_per_mtok: 0.125,
+            output_per_mtok: 10.0,
+        },
+    ),
+    (
+        "gpt-4o*",
+        ModelRate {
+            input_per_mtok: 2.5,
+            cached_input_per_mtok: 1.25,
+            output_per_mtok: 10.0,
+        },
+    ),
+    (
+        "o3*",
+        ModelRate {
+            input_per_mtok: 2.0,
+            cached_input_per_mtok: 0.5,
+            output_per_mtok: 8.0,
+        },
+    ),
+    (
+        "claude-*opus*",
+        ModelRate {
+            input_per_mtok: 15.0,
+            cached_input_per_mtok: 1.5,
+            output_per_mtok: 75.0,
+        },
+    ),
+    (
+        "claude-*",
+        ModelRate {
+            input_per_mtok: 3.0,
+            cached_input_per_mtok: 0.3,
+            output_per_mtok: 15.0,
+        },
+    ),
+    (
+        "grok-*",
+        ModelRate {
+            input_per_mtok: 3.0,
+            cached_input_per_mtok: 0.75,
+            output_per_mtok: 15.0,
+        },
+    ),
+    (
+        "deepseek-*",
+        ModelRate {
+            input_per_mtok: 0.27,
+            cached_input_per_mtok: 0.07,
+            output_per_mtok: 1.1,
+        },
+    ),
+];
+
+/// Returns the effective rate for `model`, preferring the user's
+/// `[model_rates]` entries over the bundled table. `None` when neither has a
+/// matching pattern (e.g. local oss models, which are free anyway).
+pub(crate) fn rate_for_model(
+    model: &str,
+    user_rates: &HashMap<String, ModelRateToml>,
+) -> Option<ModelRate> {
+    if let Some((_, rate_toml)) = user_rates
+        .iter()
+        .filter(|(pattern, _)| model_pattern_matches(pattern, model))
+        .max_by_key(|(pattern, _)| pattern.chars().filter(|c| *c != '*').count())
+    {
+        let input = rate_toml.input_per_mtok.unwrap_or(0.0);
+        return Some(ModelRate {
+            input_per_mtok: input,
+            cached_input_per_mtok: rate_toml.cached_input_per_mtok.unwrap_or(input),
+            output_per_mtok: rate_toml.output_per_mtok.unwrap_or(0.0),
+        });
+    }
+    BUILTIN_RATES
+        .iter()
+        .filter(|(pattern, _)| model_pattern_matches(pattern, model))
+        .max_by_key(|(pattern, _)| pattern.chars().filter(|c| *c != '*').count())
+        .map(|(_, rate)| *rate)
+}
+
+/// Estimated dollars for `usage` at `model`'s rate; `None` when the model is
+/// not priced. Reasoning tokens are already included in `output_tokens`.
+pub(crate) fn estimate_cost_usd(
+    model: &str,
+    usage: &TokenUsage,
+    user_rates: &HashMap<String, ModelRateToml>,
+) -> Option<f64> {
+    let rate = rate_for_model(model, user_rates)?;
+    let uncached_input = (usage.input_tokens - usage.cached_input_tokens).max(0) as f64;
+    let cached_input = usage.cached_input_tokens.max(0) as f64;
+    let output = usage.output_tokens.max(0) as f64;
+    Some(
+        (uncached_input * rate.input_per_mtok
+            + cached_input * rate.cached_input_per_mtok
+            + output * rate.output_per_mtok)
+            / 1_000_000.0,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(input: i64, cached: i64, output: i64) -> TokenUsage {
+        TokenUsage {
+            input_tokens: input,
+            cached_input_tokens: cached,
+            output_tokens: output,
+            reasoning_output_tokens: 0,
+            total_tokens: input + output,
+        }
+    }
+
+    #[test]
+    fn builtin_rates_price_known_models() {
+        let cost = estimate_cost_usd("gpt-5.5-codex", &usage(1_000_000, 0, 0), &HashMap::new());
+        assert_eq!(cost, Some(1.25));
+    }
+
+    #[test]
+    fn most_specific_builtin_pattern_wins() {
+        let opus = estimate_cost_usd("claude-3-opus", &usage(1_000_000, 0, 0), &HashMap::new());
+        let sonnet = estimate_cost_usd("claude-3-sonnet", &usage(1_000_000, 0, 0), &HashMap::new());
+        assert_eq!(opus, Some(15.0));
+        assert_eq!(sonnet, Some(3.0));
+    }
+
+    #[test]
+    fn user_rates_override_builtin_table() {
+        let user_rates: HashMap<String, ModelRateToml> = [(
+            "gpt-5*".to_string(),
+            ModelRateToml {
+                input_per_mtok: Some(1.0),
+                cached_input_per_mtok: None,
+                output_per_mtok: Some(4.0),
+            },
+        )]
+        .into_iter()
+        .collect();
+        let cost = estimate_cost_usd("gpt-5.5", &usage(1_000_000, 500_000, 250_000), &user_rates);
+        // 0.5M uncached at $1 + 0.5M cached at the uncached rate + 0.25M out at $4.
+        assert_eq!(cost, Some(0.5 + 0.5 + 1.0));
+    }
+
+    #[test]
+    fn unknown_models_are_unpriced() {
+        assert_eq!(
+            estimate_cost_usd("llama3.3", &usage(1_000_000, 0, 0), &HashMap::new()),
+            None
+        );
+    }
+}
@@ -114,6 +114,9 @@ fn parse_user_message(message: &[ContentItem]) -> Option<UserMessageItem> {
             ContentItem::OutputText { text } => {
                 warn!("Output text in user message: {}", text);
             }
+            ContentItem::Refusal { refusal } => {
+                warn!("Refusal in user message: {}", refusal);
+            }
         }
     }
 
@@ -128,7 +131,9 @@ fn parse_agent_message(
     let mut content: Vec<AgentMessageContent> = Vec::new();
     for content_item in message.iter() {
         match content_item {
-            ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+            ContentItem::InputText { text }
+            | ContentItem::OutputText { text }
+            | ContentItem::Refusal { refusal: text } => {
                 content.push(AgentMessageContent::Text { text: text.clone() });
             }
             _ => {
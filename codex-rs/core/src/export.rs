@@ -0,0 +1,626 @@
+//! Renders a recorded conversation into shareable formats.
+//!
+//! The exporter loads a thread's rollout file and flattens it into a
+//! transcript of messages, executed commands, diffs, and tool output, then
+//! renders that transcript as Markdown, JSON, or a self-contained HTML page.
+//! Both `codex export` and the HTTP server's export endpoint go through this
+//! module so every surface shares one rendering.
+
+use std::path::Path;
+use std::str::FromStr;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::models::WebSearchAction;
+use codex_protocol::protocol::RolloutItem;
+use codex_rollout::RolloutRecorder;
+use codex_rollout::find_thread_path_by_id_str;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Tool output beyond this many characters is truncated in the transcript;
+/// full output stays in the rollout file.
+const MAX_OUTPUT_CHARS: usize = 10_000;
+
+/// Output format for a conversation export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Markdown,
+    Json,
+    Html,
+}
+
+impl ExportFormat {
+    /// MIME type for serving a rendered export over HTTP.
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Markdown => "text/markdown; charset=utf-8",
+            Self::Json => "application/json",
+            Self::Html => "text/html; charset=utf-8",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "markdown" | "md" => Ok(Self::Markdown),
+            "json" => Ok(Self::Json),
+            "html" => Ok(Self::Html),
+            other => Err(format!(
+                "unknown export format `{other}` (expected markdown, json, or html)"
+            )),
+        }
+    }
+}
+
+/// One transcript entry; the kind picks the rendering (prose, fenced command,
+/// diff, and so on).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptEntryKind {
+    Message,
+    Command,
+    CommandOutput,
+    Diff,
+    Note,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptEntry {
+    pub kind: TranscriptEntryKind,
+    pub heading: String,
+    pub body: String,
+}
+
+/// A conversation flattened into renderable transcript entries.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConversationExport {
+    pub id: String,
+    pub timestamp: Option<String>,
+    pub cwd: Option<String>,
+    pub entries: Vec<TranscriptEntry>,
+}
+
+/// Loads the rollout for `id` under `codex_home` and flattens it into an
+/// export. Returns `Ok(None)` when no thread with that id exists.
+pub async fn load_conversation_export(
+    codex_home: &Path,
+    id: &str,
+) -> std::io::Result<Option<ConversationExport>> {
+    let Some(path) = find_thread_path_by_id_str(codex_home, id, None).await? else {
+        return Ok(None);
+    };
+    let (items, _thread_id, _parse_errors) = RolloutRecorder::load_rollout_items(&path).await?;
+    Ok(Some(ConversationExport::from_items(id.to_string(), &items)))
+}
+
+/// One command call pulled out of a conversation by call id, for streaming
+/// its output separately from the rest of the transcript.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExecCall {
+    pub call_id: String,
+    /// The command line, when the call's arguments record one.
+    pub command: Option<String>,
+    /// Output recorded for the call. Rollouts store the aggregated output,
+    /// not a stdout/stderr split.
+    pub output: String,
+}
+
+/// Where an exec-call lookup landed; the caller usually maps the two misses
+/// to different error messages.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExecCallLookup {
+    NoConversation,
+    NoCall,
+    Found(ExecCall),
+}
+
+/// Loads the rollout for `id` under `codex_home` and pulls out the command
+/// call with `call_id`.
+pub async fn load_exec_call(
+    codex_home: &Path,
+    id: &str,
+    call_id: &str,
+) -> std::io::Result<ExecCallLookup> {
+    let Some(path) = find_thread_path_by_id_str(codex_home, id, None).await? else {
+        return Ok(ExecCallLookup::NoConversation);
+    };
+    let (items, _thread_id, _parse_errors) = RolloutRecorder::load_rollout_items(&path).await?;
+    Ok(match ExecCall::from_items(call_id, &items) {
+        Some(call) => ExecCallLookup::Found(call),
+        None => ExecCallLookup::NoCall,
+    })
+}
+
+impl ExecCall {
+    /// Finds the call with `call_id` in `items`; `None` when nothing in the
+    /// rollout references that id. A call whose output was never recorded
+    /// (e.g. the turn is still running) is found with an empty `output`.
+    pub fn from_items(call_id: &str, items: &[RolloutItem]) -> Option<Self> {
+        let mut command = None;
+        let mut output = None;
+        for item in items {
+            let RolloutItem::ResponseItem(item) = item else {
+                continue;
+            };
+            match item {
+                ResponseItem::FunctionCall {
+                    call_id: id,
+                    arguments,
+                    ..
+                } if id == call_id => {
+                    command = Some(shell_command(arguments).unwrap_or_else(|| arguments.clone()));
+                }
+                ResponseItem::LocalShellCall {
+                    call_id: id,
+                    action,
+                    ..
+                } if id.as_deref() == Some(call_id) => {
+                    let LocalShellAction::Exec(exec) = action;
+                    command = Some(exec.command.join(" "));
+                }
+                ResponseItem::FunctionCallOutput {
+                    call_id: id,
+                    output: payload,
+                    ..
+                }
+                | ResponseItem::CustomToolCallOutput {
+                    call_id: id,
+                    output: payload,
+                    ..
+                } if id == call_id => {
+                    output = Some(payload.body.to_text().unwrap_or_default());
+                }
+                _ => {}
+            }
+        }
+        if command.is_none() && output.is_none() {
+            return None;
+        }
+        Some(Self {
+            call_id: call_id.to_string(),
+            command,
+            output: output.unwrap_or_default(),
+        })
+    }
+}
+
+impl ConversationExport {
+    pub fn from_items(id: String, items: &[RolloutItem]) -> Self {
+        let mut export = Self {
+            id,
+            timestamp: None,
+            cwd: None,
+            entries: Vec::new(),
+        };
+        for item in items {
+            match item {
+                RolloutItem::SessionMeta(session_meta_line) => {
+                    if export.timestamp.is_none() {
+                        export.timestamp = Some(session_meta_line.meta.timestamp.clone());
+                        export.cwd = Some(session_meta_line.meta.cwd.display().to_string());
+                    }
+                }
+                RolloutItem::ResponseItem(response_item) => {
+                    if let Some(entry) = transcript_entry(response_item) {
+                        export.entries.push(entry);
+                    }
+                }
+                RolloutItem::Compacted(_) => {
+                    export.entries.push(TranscriptEntry {
+                        kind: TranscriptEntryKind::Note,
+                        heading: "Note".to_string(),
+                        body: "Conversation history was compacted here.".to_string(),
+                    });
+                }
+                RolloutItem::InterAgentCommunication(_)
+                | RolloutItem::InterAgentCommunicationMetadata { .. }
+                | RolloutItem::TurnContext(_)
+                | RolloutItem::WorldState(_)
+                | RolloutItem::EventMsg(_) => {}
+            }
+        }
+        export
+    }
+
+    /// Title for a pull request opened from this conversation's changes: the
+    /// first line of the first user message, or a generic fallback.
+    pub fn pr_title(&self) -> String {
+        const MAX_TITLE_CHARS: usize = 72;
+        let first_user_line = self
+            .entries
+            .iter()
+            .find(|entry| entry.kind == TranscriptEntryKind::Message && entry.heading == "User")
+            .and_then(|entry| entry.body.lines().next())
+            .unwrap_or_default()
+            .trim();
+        if first_user_line.is_empty() {
+            format!("Codex conversation {}", self.id)
+        } else if first_user_line.chars().count() <= MAX_TITLE_CHARS {
+            first_user_line.to_string()
+        } else {
+            let mut title: String = first_user_line.chars().take(MAX_TITLE_CHARS - 1).collect();
+            title.push('…');
+            title
+        }
+    }
+
+    /// Pull request body summarizing the conversation: the final assistant
+    /// message, followed by the commands that were run.
+    pub fn pr_body(&self) -> String {
+        const MAX_COMMANDS: usize = 20;
+        let mut out = String::new();
+        if let Some(entry) = self.entries.iter().rfind(|entry| {
+            entry.kind == TranscriptEntryKind::Message && entry.heading == "Assistant"
+        }) {
+            out.push_str(&entry.body);
+            out.push('\n');
+        }
+        let commands: Vec<&TranscriptEntry> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.kind == TranscriptEntryKind::Command)
+            .collect();
+        if !commands.is_empty() {
+            out.push_str("\n## Commands run\n\n");
+            for entry in commands.iter().take(MAX_COMMANDS) {
+                let command = entry.body.lines().next().unwrap_or_default();
+                out.push_str(&format!("- `{command}`\n"));
+            }
+            if commands.len() > MAX_COMMANDS {
+                out.push_str(&format!("- … and {} more\n", commands.len() - MAX_COMMANDS));
+            }
+        }
+        out.push_str(&format!(
+            "\n---\nGenerated from Codex conversation `{}`.\n",
+            self.id
+        ));
+        out
+    }
+
+    pub fn render(&self, format: ExportFormat) -> String {
+        match format {
+            ExportFormat::Markdown => self.render_markdown(),
+            ExportFormat::Json => serde_json::to_string_pretty(self).unwrap_or_default(),
+            ExportFormat::Html => self.render_html(),
+        }
+    }
+
+    fn render_markdown(&self) -> String {
+        let mut out = format!("# Codex conversation {}\n", self.id);
+        if let Some(timestamp) = &self.timestamp {
+            out.push_str(&format!("\n- Started: {timestamp}"));
+        }
+        if let Some(cwd) = &self.cwd {
+            out.push_str(&format!("\n- Working directory: `{cwd}`"));
+        }
+        out.push('\n');
+        for entry in &self.entries {
+            out.push_str(&format!("\n## {}\n\n", entry.heading));
+            match entry.kind {
+                TranscriptEntryKind::Message | TranscriptEntryKind::Note => {
+                    out.push_str(&entry.body);
+                    out.push('\n');
+                }
+                TranscriptEntryKind::Command => {
+                    out.push_str(&fenced("shell", &entry.body));
+                }
+                TranscriptEntryKind::CommandOutput => {
+                    out.push_str(&fenced("", &entry.body));
+                }
+                TranscriptEntryKind::Diff => {
+                    out.push_str(&fenced("diff", &entry.body));
+                }
+            }
+        }
+        out
+    }
+
+    fn render_html(&self) -> String {
+        let title = html_escape(&format!("Codex conversation {}", self.id));
+        let mut out = format!(
+            "<!doctype html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n</head>\n<body>\n<h1>{title}</h1>\n"
+        );
+        if let Some(timestamp) = &self.timestamp {
+            out.push_str(&format!("<p>Started: {}</p>\n", html_escape(timestamp)));
+        }
+        if let Some(cwd) = &self.cwd {
+            out.push_str(&format!(
+                "<p>Working directory: <code>{}</code></p>\n",
+                html_escape(cwd)
+            ));
+        }
+        for entry in &self.entries {
+            out.push_str(&format!("<h2>{}</h2>\n", html_escape(&entry.heading)));
+            let body = html_escape(&entry.body);
+            match entry.kind {
+                TranscriptEntryKind::Message | TranscriptEntryKind::Note => {
+                    out.push_str(&format!("<p>{}</p>\n", body.replace('\n', "<br>\n")));
+                }
+                TranscriptEntryKind::Command
+                | TranscriptEntryKind::CommandOutput
+                | TranscriptEntryKind::Diff => {
+                    out.push_str(&format!("<pre><code>{body}</code></pre>\n"));
+                }
+            }
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+}
+
+fn transcript_entry(item: &ResponseItem) -> Option<TranscriptEntry> {
+    match item {
+        ResponseItem::Message { role, content, .. } => {
+            let body = message_text(content);
+            if body.is_empty() {
+                return None;
+            }
+            let heading = match role.as_str() {
+                "user" => "User".to_string(),
+                "assistant" => "Assistant".to_string(),
+                other => other.to_string(),
+            };
+            Some(TranscriptEntry {
+                kind: TranscriptEntryKind::Message,
+                heading,
+                body,
+            })
+        }
+        ResponseItem::LocalShellCall { action, .. } => {
+            let LocalShellAction::Exec(exec) = action;
+            Some(TranscriptEntry {
+                kind: TranscriptEntryKind::Command,
+                heading: "Command".to_string(),
+                body: exec.command.join(" "),
+            })
+        }
+        ResponseItem::FunctionCall {
+            name, arguments, ..
+        } => Some(function_call_entry(name, arguments)),
+        ResponseItem::CustomToolCall { name, input, .. } => Some(function_call_entry(name, input)),
+        ResponseItem::FunctionCallOutput { output, .. }
+        | ResponseItem::CustomToolCallOutput { output, .. } => {
+            let body = output.body.to_text().unwrap_or_default();
+            if body.is_empty() {
+                return None;
+            }
+            Some(TranscriptEntry {
+                kind: TranscriptEntryKind::CommandOutput,
+                heading: "Output".to_string(),
+                body: truncate_chars(&body),
+            })
+        }
+        ResponseItem::WebSearchCall { action, .. } => {
+            let query = action.as_ref().and_then(|action| match action {
+                WebSearchAction::Search { query, .. } => query.clone(),
+                _ => None,
+            })?;
+            Some(TranscriptEntry {
+                kind: TranscriptEntryKind::Note,
+                heading: "Web search".to_string(),
+                body: query,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Renders a tool invocation; `apply_patch` becomes a diff entry, `shell`-like
+/// tools a command entry, and everything else the raw call.
+fn function_call_entry(name: &str, arguments: &str) -> TranscriptEntry {
+    if name == "apply_patch" {
+        let patch = serde_json::from_str::<serde_json::Value>(arguments)
+            .ok()
+            .and_then(|args| {
+                args.get("input")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+            })
+            .unwrap_or_else(|| arguments.to_string());
+        return TranscriptEntry {
+            kind: TranscriptEntryKind::Diff,
+            heading: "Patch".to_string(),
+            body: patch,
+        };
+    }
+    match shell_command(arguments) {
+        Some(command) => TranscriptEntry {
+            kind: TranscriptEntryKind::Command,
+            heading: "Command".to_string(),
+            body: command,
+        },
+        None => TranscriptEntry {
+            kind: TranscriptEntryKind::Command,
+            heading: format!("Tool: {name}"),
+            body: arguments.to_string(),
+        },
+    }
+}
+
+/// The `command` array from shell-style call arguments, joined into one line.
+fn shell_command(arguments: &str) -> Option<String> {
+    let args = serde_json::from_str::<serde_json::Value>(arguments).ok()?;
+    let words: Vec<String> = args
+        .get("command")?
+        .as_array()?
+        .iter()
+        .filter_map(|word| word.as_str().map(str::to_string))
+        .collect();
+    (!words.is_empty()).then(|| words.join(" "))
+}
+
+fn message_text(content: &[ContentItem]) -> String {
+    content
+        .iter()
+        .filter_map(|item| match item {
+            ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                Some(text.clone())
+            }
+            ContentItem::InputImage { .. } => Some("[image]".to_string()),
+            ContentItem::Refusal { refusal } => Some(refusal.clone()),
+        })
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn truncate_chars(text: &str) -> String {
+    if text.chars().count() <= MAX_OUTPUT_CHARS {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(MAX_OUTPUT_CHARS).collect();
+    truncated.push_str("\n… [output truncated]");
+    truncated
+}
+
+/// Fenced code block whose fence is long enough not to collide with the body.
+fn fenced(language: &str, body: &str) -> String {
+    let longest_backtick_run = body
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    let fence = "`".repeat(longest_backtick_run.max(2) + 1);
+    format!("{fence}{language}\n{body}\n{fence}\n")
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    fn user_message(text: &str) -> RolloutItem {
+        RolloutItem::ResponseItem(ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+            phase: None,
+            internal_chat_message_metadata_passthrough: None,
+        })
+    }
+
+    fn shell_call(command: &str) -> RolloutItem {
+        RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+            id: None,
+            name: "shell".to_string(),
+            namespace: None,
+            arguments: serde_json::json!({ "command": command.split(' ').collect::<Vec<_>>() })
+                .to_string(),
+            call_id: "call-1".to_string(),
+            internal_chat_message_metadata_passthrough: None,
+        })
+    }
+
+    #[test]
+    fn exec_call_is_found_by_call_id() {
+        let items = vec![
+            user_message("fix the bug"),
+            shell_call("cargo test -p codex-core"),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCallOutput {
+                id: None,
+                call_id: "call-1".to_string(),
+                output: FunctionCallOutputPayload::from_text("ok".to_string()),
+                internal_chat_message_metadata_passthrough: None,
+            }),
+        ];
+        let call = ExecCall::from_items("call-1", &items).expect("find call");
+        assert_eq!(call.command, Some("cargo test -p codex-core".to_string()));
+        assert_eq!(call.output, "ok");
+        assert_eq!(ExecCall::from_items("call-9", &items), None);
+    }
+
+    #[test]
+    fn flattens_messages_commands_and_patches() {
+        let items = vec![
+            user_message("fix the bug"),
+            shell_call("cargo test -p codex-core"),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCallOutput {
+                id: None,
+                call_id: "call-1".to_string(),
+                output: FunctionCallOutputPayload::from_text("ok".to_string()),
+                internal_chat_message_metadata_passthrough: None,
+            }),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                id: None,
+                name: "apply_patch".to_string(),
+                namespace: None,
+                arguments: serde_json::json!({ "input": "*** Begin Patch\n*** End Patch" })
+                    .to_string(),
+                call_id: "call-2".to_string(),
+                internal_chat_message_metadata_passthrough: None,
+            }),
+        ];
+        let export = ConversationExport::from_items("t-1".to_string(), &items);
+        let kinds: Vec<TranscriptEntryKind> =
+            export.entries.iter().map(|entry| entry.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TranscriptEntryKind::Message,
+                TranscriptEntryKind::Command,
+                TranscriptEntryKind::CommandOutput,
+                TranscriptEntryKind::Diff,
+            ]
+        );
+        assert_eq!(export.entries[1].body, "cargo test -p codex-core");
+    }
+
+    #[test]
+    fn markdown_renders_commands_in_fences() {
+        let export = ConversationExport::from_items("t-1".to_string(), &[shell_call("git status")]);
+        let markdown = export.render(ExportFormat::Markdown);
+        assert!(markdown.contains("# Codex conversation t-1"));
+        assert!(markdown.contains("```shell\ngit status\n```"));
+    }
+
+    #[test]
+    fn html_escapes_message_content() {
+        let export = ConversationExport::from_items("t-1".to_string(), &[user_message("<script>")]);
+        let html = export.render(ExportFormat::Html);
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(!html.contains("<script>"));
+    }
+
+    #[test]
+    fn pr_title_and_body_come_from_the_transcript() {
+        let export = ConversationExport::from_items(
+            "t-1".to_string(),
+            &[
+                user_message("fix the login redirect\nwith more context"),
+                shell_call("cargo test -p codex-core"),
+            ],
+        );
+        assert_eq!(export.pr_title(), "fix the login redirect");
+        let body = export.pr_body();
+        assert!(body.contains("- `cargo test -p codex-core`"));
+        assert!(body.contains("Generated from Codex conversation `t-1`"));
+    }
+
+    #[test]
+    fn pr_title_falls_back_for_empty_transcripts() {
+        let export = ConversationExport::from_items("t-1".to_string(), &[]);
+        assert_eq!(export.pr_title(), "Codex conversation t-1");
+    }
+
+    #[test]
+    fn format_parses_known_names() {
+        assert_eq!("md".parse::<ExportFormat>(), Ok(ExportFormat::Markdown));
+        assert_eq!("JSON".parse::<ExportFormat>(), Ok(ExportFormat::Json));
+        assert!("pdf".parse::<ExportFormat>().is_err());
+    }
+}
@@ -811,7 +811,9 @@ mod tests {
                             .iter()
                             .map(|item| match item {
                                 ContentItem::InputText { text } => text.as_str(),
-                                ContentItem::InputImage { .. } | ContentItem::OutputText { .. } => {
+                                ContentItem::InputImage { .. }
+                                | ContentItem::OutputText { .. }
+                                | ContentItem::Refusal { .. } => {
                                     panic!("expected input text content, got {item:?}")
                                 }
                             })
@@ -21,15 +21,26 @@ pub use turn_metadata::detached_memory_responses_metadata;
 mod codex_thread;
 mod compact_model_fallback;
 mod compact_remote;
+pub mod refusal_fallback;
+pub use refusal_fallback::RefusalClassifier;
+pub use refusal_fallback::install_refusal_classifier;
 mod compact_remote_v2;
 mod compact_token_budget;
 mod config_lock;
+mod context_overflow_fallback;
+mod cost_accounting;
+mod model_router;
+mod provider_pool;
+mod provider_race;
+pub mod response_cache;
+pub mod stream_perf;
 pub use codex_thread::BackgroundTerminalInfo;
 pub use codex_thread::CodexThread;
 pub use codex_thread::CodexThreadSettingsOverrides;
 pub use codex_thread::ThreadConfigSnapshot;
 pub use codex_thread::TryStartTurnIfIdleError;
 pub use codex_thread::TryStartTurnIfIdleRejectionReason;
+pub use response_cache::ResponseCache;
 pub use session::turn_context::TurnContext;
 mod agent;
 mod agent_communication;
@@ -46,6 +57,7 @@ mod environment_selection;
 pub mod exec;
 pub mod exec_env;
 mod exec_policy;
+pub mod export;
 #[cfg(test)]
 mod git_info_tests;
 mod guardian;
@@ -88,6 +100,7 @@ pub(crate) mod mentions {
 }
 mod sandbox_tags;
 pub mod sandboxing;
+pub mod search;
 mod session_prefix;
 mod session_startup_prewarm;
 pub mod skills;
@@ -0,0 +1,258 @@
+//! Routes turns to a model chosen by user-configured rules.
+//!
+//! `[model_rules]` lets cheap models pick up trivial work automatically: each
+//! rule matches task signals drawn from the turn's prompt (length, mentioned
+//! file extensions, and a reasoning-heavy keyword heuristic) and names the
+//! model to run the turn on. The first matching rule wins; when nothing
+//! matches the turn stays on the session's default model.
+
+use codex_config::types::ModelRuleToml;
+use codex_config::types::ModelRulesConfig;
+use codex_config::types::ModelRulesToml;
+
+/// Prompt phrases that mark a task as reasoning-heavy. Deliberately short:
+/// rules that need finer control can match on their own keywords instead.
+const REASONING_KEYWORDS: &[&str] = &[
+    "architecture",
+    "concurrency",
+    "deadlock",
+    "debug",
+    "design",
+    "prove",
+    "race condition",
+    "refactor",
+    "security",
+];
+
+pub(crate) fn resolve_config(toml: Option<ModelRulesToml>) -> ModelRulesConfig {
+    let toml = toml.unwrap_or_default();
+    // Configuring rules opts in unless `enabled` says otherwise.
+    let enabled = toml.enabled.unwrap_or(!toml.rules.is_empty());
+    ModelRulesConfig {
+        enabled,
+        rules: toml.rules,
+    }
+}
+
+/// Task signals extracted from a turn's prompt, matched against rules.
+#[derive(Debug, PartialEq)]
+pub(crate) struct TaskSignals {
+    prompt_lowercase: String,
+    prompt_chars: usize,
+    /// Lowercased extensions of file names mentioned in the prompt.
+    languages: Vec<String>,
+    reasoning_heavy: bool,
+}
+
+impl TaskSignals {
+    pub(crate) fn from_prompt(prompt: &str) -> Self {
+        let prompt_lowercase = prompt.to_lowercase();
+        let reasoning_heavy = REASONING_KEYWORDS
+            .iter()
+            .any(|keyword| prompt_lowercase.contains(keyword));
+        let mut languages: Vec<String> = prompt_lowercase
+            .split_whitespace()
+            .filter_map(extension_of)
+            .map(str::to_string)
+            .collect();
+        languages.sort_unstable();
+        languages.dedup();
+        Self {
+            prompt_chars: prompt.chars().count(),
+            prompt_lowercase,
+            languages,
+            reasoning_heavy,
+        }
+    }
+}
+
+/// Extension of a file-like token (`src/main.rs` -> `rs`). Version numbers
+/// and abbreviations with long or non-alphabetic "extensions" are skipped.
+fn extension_of(token: &str) -> Option<&str> {
+    let token = token.trim_matches(|c: char| !c.is_alphanumeric());
+    let (stem, extension) = token.rsplit_once('.')?;
+    if stem.is_empty()
+        || extension.is_empty()
+        || extension.len() > 4
+        || !extension.chars().all(|c| c.is_ascii_alphabetic())
+    {
+        return None;
+    }
+    Some(extension)
+}
+
+/// Returns the model to run the turn on, or `None` when routing is disabled,
+/// no rule matches, or the match resolves to the model already in use.
+pub(crate) fn route(
+    config: &ModelRulesConfig,
+    current_model: &str,
+    signals: &TaskSignals,
+) -> Option<String> {
+    if !config.enabled {
+        return None;
+    }
+    config
+        .rules
+        .iter()
+        .find(|rule| rule_matches(rule, signals))
+        .map(|rule| rule.model.clone())
+        .filter(|model| model != current_model)
+}
+
+fn rule_matches(rule: &ModelRuleToml, signals: &TaskSignals) -> bool {
+    if rule
+        .max_prompt_chars
+        .is_some_and(|max| signals.prompt_chars > max)
+    {
+        return false;
+    }
+    if rule
+        .reasoning
+        .is_some_and(|reasoning| reasoning != signals.reasoning_heavy)
+    {
+        return false;
+    }
+    if !rule.languages.is_empty()
+        && !rule.languages.iter().any(|language| {
+            signals
+                .languages
+                .iter()
+                .any(|extension| extension.eq_ignore_ascii_case(language))
+        })
+    {
+        return false;
+    }
+    if !rule.keywords.is_empty()
+        && !rule
+            .keywords
+            .iter()
+            .any(|keyword| signals.prompt_lowercase.contains(&keyword.to_lowercase()))
+    {
+        return false;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(model: &str) -> ModelRuleToml {
+        ModelRuleToml {
+            model: model.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn configuring_rules_enables_the_router() {
+        let config = resolve_config(Some(ModelRulesToml {
+            enabled: None,
+            rules: vec![rule("gpt-5.3-codex-mini")],
+        }));
+        assert!(config.enabled);
+        let signals = TaskSignals::from_prompt("fix the typo");
+        assert_eq!(
+            route(&config, "gpt-5.3-codex", &signals),
+            Some("gpt-5.3-codex-mini".to_string())
+        );
+    }
+
+    #[test]
+    fn explicit_disable_wins_over_configured_rules() {
+        let config = resolve_config(Some(ModelRulesToml {
+            enabled: Some(false),
+            rules: vec![rule("gpt-5.3-codex-mini")],
+        }));
+        let signals = TaskSignals::from_prompt("fix the typo");
+        assert_eq!(route(&config, "gpt-5.3-codex", &signals), None);
+    }
+
+    #[test]
+    fn does_not_route_to_the_model_already_in_use() {
+        let config = resolve_config(Some(ModelRulesToml {
+            enabled: None,
+            rules: vec![rule("gpt-5.3-codex")],
+        }));
+        let signals = TaskSignals::from_prompt("fix the typo");
+        assert_eq!(route(&config, "gpt-5.3-codex", &signals), None);
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let config = resolve_config(Some(ModelRulesToml {
+            enabled: None,
+            rules: vec![
+                ModelRuleToml {
+                    keywords: vec!["typo".to_string()],
+                    ..rule("gpt-5.3-codex-mini")
+                },
+                rule("gpt-5.3-codex-spark"),
+            ],
+        }));
+        let typo = TaskSignals::from_prompt("fix a typo in README.md");
+        assert_eq!(
+            route(&config, "gpt-5.3-codex", &typo),
+            Some("gpt-5.3-codex-mini".to_string())
+        );
+        let other = TaskSignals::from_prompt("add a flag");
+        assert_eq!(
+            route(&config, "gpt-5.3-codex", &other),
+            Some("gpt-5.3-codex-spark".to_string())
+        );
+    }
+
+    #[test]
+    fn language_rules_match_mentioned_file_extensions() {
+        let config = resolve_config(Some(ModelRulesToml {
+            enabled: None,
+            rules: vec![ModelRuleToml {
+                languages: vec!["md".to_string()],
+                ..rule("gpt-5.3-codex-mini")
+            }],
+        }));
+        let markdown = TaskSignals::from_prompt("update docs/README.md");
+        assert_eq!(
+            route(&config, "gpt-5.3-codex", &markdown),
+            Some("gpt-5.3-codex-mini".to_string())
+        );
+        let rust = TaskSignals::from_prompt("update src/main.rs");
+        assert_eq!(route(&config, "gpt-5.3-codex", &rust), None);
+    }
+
+    #[test]
+    fn reasoning_heavy_prompts_skip_downrouting_rules() {
+        let config = resolve_config(Some(ModelRulesToml {
+            enabled: None,
+            rules: vec![ModelRuleToml {
+                reasoning: Some(false),
+                ..rule("gpt-5.3-codex-mini")
+            }],
+        }));
+        let trivial = TaskSignals::from_prompt("bump the version");
+        assert_eq!(
+            route(&config, "gpt-5.3-codex", &trivial),
+            Some("gpt-5.3-codex-mini".to_string())
+        );
+        let heavy = TaskSignals::from_prompt("debug the race condition in the scheduler");
+        assert_eq!(route(&config, "gpt-5.3-codex", &heavy), None);
+    }
+
+    #[test]
+    fn long_prompts_fail_max_prompt_chars() {
+        let config = resolve_config(Some(ModelRulesToml {
+            enabled: None,
+            rules: vec![ModelRuleToml {
+                max_prompt_chars: Some(10),
+                ..rule("gpt-5.3-codex-mini")
+            }],
+        }));
+        let short = TaskSignals::from_prompt("fix typo");
+        assert_eq!(
+            route(&config, "gpt-5.3-codex", &short),
+            Some("gpt-5.3-codex-mini".to_string())
+        );
+        let long = TaskSignals::from_prompt("please rewrite the whole module carefully");
+        assert_eq!(route(&config, "gpt-5.3-codex", &long), None);
+    }
+}
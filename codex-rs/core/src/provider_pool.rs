@@ -0,0 +1,126 @@
+//! Latency-aware balancing across interchangeable model providers.
+//!
+//! A `[provider_pools]` entry names a set of providers that serve the same
+//! models (e.g. OpenRouter and direct Anthropic). When the pool id is selected
+//! as the model provider, the member with the best observed latency/error
+//! profile is picked. Outcomes are recorded per request as responses stream
+//! back, so a member that degrades mid-session loses future selections.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Weight of the newest sample in the rolling latency/error averages.
+const EWMA_ALPHA: f64 = 0.3;
+
+/// Score penalty, in milliseconds, applied per unit of error rate. An
+/// endpoint failing every request scores as if it were this much slower.
+const ERROR_PENALTY_MS: f64 = 30_000.0;
+
+#[derive(Debug, Default, Clone, Copy)]
+struct EndpointStats {
+    latency_ms: f64,
+    error_rate: f64,
+    samples: u64,
+}
+
+impl EndpointStats {
+    fn record(&mut self, latency: Option<Duration>, success: bool) {
+        if let Some(latency) = latency {
+            let latency_ms = latency.as_millis() as f64;
+            self.latency_ms = if self.samples == 0 {
+                latency_ms
+            } else {
+                EWMA_ALPHA * latency_ms + (1.0 - EWMA_ALPHA) * self.latency_ms
+            };
+        }
+        let outcome = if success { 0.0 } else { 1.0 };
+        self.error_rate = if self.samples == 0 {
+            outcome
+        } else {
+            EWMA_ALPHA * outcome + (1.0 - EWMA_ALPHA) * self.error_rate
+        };
+        self.samples += 1;
+    }
+
+    fn score(&self) -> f64 {
+        self.latency_ms + self.error_rate * ERROR_PENALTY_MS
+    }
+}
+
+/// Process-wide stats keyed by provider name, shared by every session so a
+/// pool member probed once informs selections elsewhere.
+fn stats() -> &'static Mutex<HashMap<String, EndpointStats>> {
+    static STATS: OnceLock<Mutex<HashMap<String, EndpointStats>>> = OnceLock::new();
+    STATS.get_or_init(Mutex::default)
+}
+
+/// Records one request outcome for a provider. `latency` is time to first
+/// token when available; errors without a meaningful latency pass `None` and
+/// only move the error rate.
+pub(crate) fn record_request_outcome(
+    provider_name: &str,
+    latency: Option<Duration>,
+    success: bool,
+) {
+    if let Ok(mut stats) = stats().lock() {
+        stats
+            .entry(provider_name.to_string())
+            .or_default()
+            .record(latency, success);
+    }
+}
+
+/// Picks the healthiest member of a pool. `members` pairs each provider id
+/// with the name outcomes are recorded under. Members without samples are
+/// probed first in listed order; after that the lowest latency-plus-error
+/// score wins, with listed order breaking ties.
+pub(crate) fn select_from_pool<'a>(
+    members: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Option<&'a str> {
+    let stats = stats().lock().ok()?;
+    let mut best: Option<(&str, f64)> = None;
+    for (id, name) in members {
+        let Some(endpoint) = stats.get(name).filter(|endpoint| endpoint.samples > 0) else {
+            return Some(id);
+        };
+        let score = endpoint.score();
+        if best.is_none_or(|(_, best_score)| score < best_score) {
+            best = Some((id, score));
+        }
+    }
+    best.map(|(id, _)| id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn untried_members_are_probed_in_order() {
+        let selected = select_from_pool([
+            ("pool-test-a", "Pool Test A"),
+            ("pool-test-b", "Pool Test B"),
+        ]);
+        assert_eq!(selected, Some("pool-test-a"));
+    }
+
+    #[test]
+    fn lower_latency_member_wins_once_sampled() {
+        record_request_outcome("Slow Endpoint", Some(Duration::from_millis(2_000)), true);
+        record_request_outcome("Fast Endpoint", Some(Duration::from_millis(200)), true);
+        let selected = select_from_pool([("slow", "Slow Endpoint"), ("fast", "Fast Endpoint")]);
+        assert_eq!(selected, Some("fast"));
+    }
+
+    #[test]
+    fn errors_outweigh_a_latency_edge() {
+        record_request_outcome("Flaky Endpoint", Some(Duration::from_millis(100)), true);
+        record_request_outcome("Flaky Endpoint", None, false);
+        record_request_outcome("Steady Endpoint", Some(Duration::from_millis(900)), true);
+        let selected =
+            select_from_pool([("flaky", "Flaky Endpoint"), ("steady", "Steady Endpoint")]);
+        assert_eq!(selected, Some("steady"));
+    }
+}
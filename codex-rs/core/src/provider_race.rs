@@ -0,0 +1,244 @@
+//! Opt-in racing of sampling requests across two providers.
+//!
+//! For interactive sessions where p99 latency matters more than cost, the
+//! `[provider_race]` config block names a secondary provider. Each sampling
+//! request is sent to both providers and the first stream to produce a usable
+//! event wins; the loser is dropped, which cancels its in-flight request. The
+//! winner is recorded in the provider-pool stats so repeated races also
+//! sharpen pool selection.
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use codex_config::types::ProviderRaceConfig;
+use codex_config::types::ProviderRaceToml;
+use codex_model_provider_info::ModelProviderInfo;
+use futures::StreamExt;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use crate::client_common::ResponseStream;
+
+/// Bound on the relay channel between the winning stream and the consumer.
+/// Matches the capacity used by the per-provider response streams.
+const RACE_STREAM_CHANNEL_CAPACITY: usize = 1600;
+
+pub(crate) fn resolve_config(toml: Option<ProviderRaceToml>) -> ProviderRaceConfig {
+    let toml = toml.unwrap_or_default();
+    // Configuring a secondary provider opts in unless `enabled` says otherwise.
+    let enabled = toml.enabled.unwrap_or(toml.provider.is_some());
+    ProviderRaceConfig {
+        enabled,
+        provider: toml.provider,
+    }
+}
+
+/// Returns the provider to race the current one against, or `None` when the
+/// race is disabled, the configured id is unknown, or it resolves to the
+/// provider the session is already using.
+pub(crate) fn race_provider_info(
+    config: &ProviderRaceConfig,
+    current: &ModelProviderInfo,
+    providers: &HashMap<String, ModelProviderInfo>,
+) -> Option<ModelProviderInfo> {
+    if !config.enabled {
+        return None;
+    }
+    let provider_id = config.provider.as_deref()?;
+    let Some(info) = providers.get(provider_id) else {
+        tracing::warn!("provider_race.provider `{provider_id}` is not a configured provider");
+        return None;
+    };
+    if info.name == current.name {
+        return None;
+    }
+    Some(info.clone())
+}
+
+/// Races two response streams and returns the winner.
+///
+/// The first stream to yield an `Ok` event wins; that event and the rest of
+/// the winning stream are relayed to the returned stream, and the loser is
+/// dropped (cancelling its request). If the first stream to respond fails
+/// before producing a usable event, the other stream is returned untouched
+/// and surfaces its own outcome.
+pub(crate) async fn race_streams(
+    primary: ResponseStream,
+    primary_provider: String,
+    secondary: ResponseStream,
+    secondary_provider: String,
+) -> ResponseStream {
+    let mut primary = primary;
+    let mut secondary = secondary;
+    let started = Instant::now();
+    let (winner, winner_provider, first_event, loser, loser_provider) = tokio::select! {
+        event = primary.next() => (primary, primary_provider, event, secondary, secondary_provider),
+        event = secondary.next() => (secondary, secondary_provider, event, primary, primary_provider),
+    };
+    match first_event {
+        Some(Ok(event)) => {
+            let latency = started.elapsed();
+            tracing::info!(
+                "provider race: {winner_provider} won in {latency:?}; cancelling {loser_provider}"
+            );
+            crate::provider_pool::record_request_outcome(&winner_provider, Some(latency), true);
+            // Dropping the loser cancels its producer via `consumer_dropped`.
+            drop(loser);
+            relay_winner(winner, event)
+        }
+        Some(Err(_)) | None => {
+            // The fastest responder was not usable; fall back to the other
+            // stream and let the caller see its events (or its error).
+            tracing::warn!(
+                "provider race: {winner_provider} failed before streaming; \
+                 continuing with {loser_provider}"
+            );
+            drop(winner);
+            loser
+        }
+    }
+}
+
+/// Re-emits the already-consumed first event followed by the rest of the
+/// winning stream on a fresh `ResponseStream`.
+fn relay_winner(
+    mut winner: ResponseStream,
+    first_event: crate::client_common::ResponseEvent,
+) -> ResponseStream {
+    let (tx_event, rx_event) = mpsc::channel(RACE_STREAM_CHANNEL_CAPACITY);
+    let consumer_dropped = CancellationToken::new();
+    let relay_cancelled = consumer_dropped.clone();
+    tokio::spawn(async move {
+        if tx_event.send(Ok(first_event)).await.is_err() {
+            return;
+        }
+        loop {
+            tokio::select! {
+                _ = relay_cancelled.cancelled() => break,
+                event = winner.next() => match event {
+                    Some(event) => {
+                        if tx_event.send(event).await.is_err() {
+                            break;
+                        }
+                    }
+                    None => break,
+                },
+            }
+        }
+    });
+    ResponseStream {
+        rx_event,
+        consumer_dropped,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client_common::ResponseEvent;
+    use codex_protocol::error::Result;
+
+    fn test_stream() -> (
+        mpsc::Sender<Result<ResponseEvent>>,
+        ResponseStream,
+        CancellationToken,
+    ) {
+        let (tx_event, rx_event) = mpsc::channel(4);
+        let consumer_dropped = CancellationToken::new();
+        let token = consumer_dropped.clone();
+        (
+            tx_event,
+            ResponseStream {
+                rx_event,
+                consumer_dropped,
+            },
+            token,
+        )
+    }
+
+    #[test]
+    fn configuring_a_provider_enables_the_race() {
+        let config = resolve_config(Some(ProviderRaceToml {
+            enabled: None,
+            provider: Some("backup".to_string()),
+        }));
+        assert!(config.enabled);
+        assert_eq!(config.provider.as_deref(), Some("backup"));
+    }
+
+    #[test]
+    fn explicit_disable_wins_over_configured_provider() {
+        let config = resolve_config(Some(ProviderRaceToml {
+            enabled: Some(false),
+            provider: Some("backup".to_string()),
+        }));
+        assert!(!config.enabled);
+        let providers = HashMap::from([("backup".to_string(), ModelProviderInfo::default())]);
+        assert_eq!(
+            race_provider_info(&config, &ModelProviderInfo::default(), &providers),
+            None
+        );
+    }
+
+    #[test]
+    fn does_not_race_a_provider_against_itself() {
+        let current = ModelProviderInfo {
+            name: "openai".to_string(),
+            ..Default::default()
+        };
+        let providers = HashMap::from([("openai".to_string(), current.clone())]);
+        let config = resolve_config(Some(ProviderRaceToml {
+            enabled: Some(true),
+            provider: Some("openai".to_string()),
+        }));
+        assert_eq!(race_provider_info(&config, &current, &providers), None);
+    }
+
+    #[tokio::test]
+    async fn first_usable_stream_wins_and_the_loser_is_cancelled() {
+        let (fast_tx, fast, _fast_token) = test_stream();
+        let (_slow_tx, slow, slow_token) = test_stream();
+        fast_tx
+            .send(Ok(ResponseEvent::Created))
+            .await
+            .expect("send first event");
+
+        let mut stream = race_streams(fast, "fast".to_string(), slow, "slow".to_string()).await;
+        assert!(slow_token.is_cancelled(), "loser should be cancelled");
+        assert!(matches!(
+            stream.next().await,
+            Some(Ok(ResponseEvent::Created))
+        ));
+
+        // Later events on the winning stream are relayed through.
+        fast_tx
+            .send(Ok(ResponseEvent::Created))
+            .await
+            .expect("send second event");
+        drop(fast_tx);
+        assert!(matches!(
+            stream.next().await,
+            Some(Ok(ResponseEvent::Created))
+        ));
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn a_dead_first_responder_loses_the_race() {
+        let (dead_tx, dead, _dead_token) = test_stream();
+        let (live_tx, live, live_token) = test_stream();
+        // The faster provider closes its stream without producing an event.
+        drop(dead_tx);
+
+        let mut stream = race_streams(dead, "dead".to_string(), live, "live".to_string()).await;
+        assert!(!live_token.is_cancelled());
+        live_tx
+            .send(Ok(ResponseEvent::Created))
+            .await
+            .expect("send event");
+        assert!(matches!(
+            stream.next().await,
+            Some(Ok(ResponseEvent::Created))
+        ));
+    }
+}
@@ -0,0 +1,327 @@
+//! Fallback routing for refused responses.
+//!
+//! Some models decline ordinary coding work outright ("I'm sorry, but I
+//! can't help with that"). When that happens we can reroute the turn to a
+//! secondary model instead of surfacing the refusal. This module resolves
+//! the `[refusal_fallback]` config block and classifies final responses.
+//!
+//! Classification prefers the Responses API `refusal` output item when the
+//! provider emits one; otherwise it falls back to a phrase heuristic (or a
+//! host-installed [`RefusalClassifier`]).
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::OnceLock;
+
+use codex_config::types::RefusalFallbackConfig;
+use codex_config::types::RefusalFallbackToml;
+use codex_model_provider_info::ModelProviderInfo;
+use codex_protocol::models::ContentItem;
+
+const OPENROUTER_API_KEY_ENV: &str = "OPENROUTER_API_KEY";
+
+/// Built-in phrases that mark a short response as a refusal. Matched
+/// case-insensitively; user-configured phrases are merged on top.
+const BUILTIN_INDICATOR_PHRASES: &[&str] = &[
+    // English
+    "i'm sorry, but i can't",
+    "i'm sorry, but i cannot",
+    "i can't assist with",
+    "i cannot assist with",
+    "i can't help with",
+    "i cannot help with",
+    "i won't be able to help",
+    "i am unable to help",
+    "i must decline",
+    // Spanish
+    "lo siento, pero no puedo",
+    "no puedo ayudarte con",
+    // French
+    "désolé, je ne peux pas",
+    "je ne peux pas vous aider",
+    // German
+    "es tut mir leid, aber ich kann nicht",
+    "ich kann dabei nicht helfen",
+    // Portuguese
+    "desculpe, mas não posso",
+    // Chinese
+    "我不能协助",
+    "我无法帮助",
+    // Japanese
+    "お手伝いできません",
+    // Russian
+    "я не могу помочь",
+];
+
+/// Generous upper bound on characters per whitespace-delimited "word". Keeps
+/// the short-response guard meaningful for scripts that do not use spaces
+/// (a long CJK answer is one "word" but many characters).
+const MAX_CHARS_PER_WORD: usize = 8;
+
+/// Host integration boundary for refusal classification.
+///
+/// The built-in classifier is a phrase heuristic; hosts that want something
+/// stronger (e.g. a small local model) can install their own implementation
+/// via [`install_refusal_classifier`].
+pub trait RefusalClassifier: Send + Sync {
+    /// Returns a human-readable reason when `text` is a refusal, `None`
+    /// otherwise. `text` is the concatenated output text of the final
+    /// assistant message.
+    fn classify(&self, config: &RefusalFallbackConfig, text: &str) -> Option<String>;
+}
+
+static CUSTOM_CLASSIFIER: OnceLock<Arc<dyn RefusalClassifier>> = OnceLock::new();
+
+/// Install a process-wide custom classifier, replacing the built-in phrase
+/// heuristic. Returns `false` if a classifier was already installed.
+pub fn install_refusal_classifier(classifier: Arc<dyn RefusalClassifier>) -> bool {
+    CUSTOM_CLASSIFIER.set(classifier).is_ok()
+}
+
+pub(crate) fn resolve_config(toml: Option<RefusalFallbackToml>) -> RefusalFallbackConfig {
+    let toml = toml.unwrap_or_default();
+    let defaults = RefusalFallbackConfig::default();
+    // Preserve the historical behaviour of enabling the fallback whenever an
+    // OpenRouter key is available, unless config says otherwise.
+    let enabled = toml
+        .enabled
+        .unwrap_or_else(|| std::env::var(OPENROUTER_API_KEY_ENV).is_ok_and(|key| !key.is_empty()));
+    let mut indicator_phrases: Vec<String> = BUILTIN_INDICATOR_PHRASES
+        .iter()
+        .map(|phrase| (*phrase).to_string())
+        .collect();
+    if let Some(extra) = toml.indicator_phrases {
+        indicator_phrases.extend(extra.into_iter().map(|phrase| phrase.to_lowercase()));
+    }
+    RefusalFallbackConfig {
+        enabled,
+        model: toml.model.unwrap_or(defaults.model),
+        provider: toml.provider.unwrap_or(defaults.provider),
+        max_word_count: toml.max_word_count.unwrap_or(defaults.max_word_count),
+        indicator_phrases,
+    }
+}
+
+/// Returns the model to re-answer a refused response on, or `None` when the
+/// fallback is disabled or the turn is already running on the fallback
+/// model.
+pub(crate) fn fallback_model(
+    config: &RefusalFallbackConfig,
+    current_model: &str,
+) -> Option<String> {
+    if !config.enabled || config.model == current_model {
+        return None;
+    }
+    Some(config.model.clone())
+}
+
+/// Resolves the configured fallback provider, or `None` when the configured
+/// id is unknown or it resolves to the provider the turn is already using —
+/// the reroute then keeps the session provider and only switches models.
+pub(crate) fn fallback_provider_info(
+    config: &RefusalFallbackConfig,
+    current_provider_name: &str,
+    providers: &HashMap<String, ModelProviderInfo>,
+) -> Option<ModelProviderInfo> {
+    let Some(info) = providers.get(&config.provider) else {
+        tracing::warn!(
+            "refusal_fallback.provider `{}` is not a configured provider",
+            config.provider
+        );
+        return None;
+    };
+    if info.name == current_provider_name {
+        return None;
+    }
+    Some(info.clone())
+}
+
+/// Classifies the final assistant message. Returns a human-readable reason
+/// when the message should be rerouted to the fallback model.
+///
+/// An explicit `refusal` output item from the provider always wins; plain
+/// text goes through the installed [`RefusalClassifier`] (the built-in
+/// phrase heuristic by default).
+pub fn classify_response(
+    config: &RefusalFallbackConfig,
+    content: &[ContentItem],
+) -> Option<String> {
+    if !config.enabled {
+        return None;
+    }
+    if let Some(refusal) = explicit_refusal(content) {
+        return Some(format!("model returned a refusal output item: {refusal}"));
+    }
+    let text = collect_output_text(content);
+    match CUSTOM_CLASSIFIER.get() {
+        Some(classifier) => classifier.classify(config, &text),
+        None => matched_phrase(config, &text).map(|phrase| format!("matched phrase \"{phrase}\"")),
+    }
+}
+
+/// Returns the text of the first explicit `refusal` output item, if any.
+fn explicit_refusal(content: &[ContentItem]) -> Option<&str> {
+    content.iter().find_map(|item| match item {
+        ContentItem::Refusal { refusal } => Some(refusal.as_str()),
+        _ => None,
+    })
+}
+
+fn collect_output_text(content: &[ContentItem]) -> String {
+    content
+        .iter()
+        .filter_map(|item| match item {
+            ContentItem::OutputText { text } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns true when `text` looks like an outright refusal rather than an
+/// answer that merely apologises along the way. Long responses are assumed
+/// to contain real work and are never flagged.
+pub fn is_refusal(config: &RefusalFallbackConfig, text: &str) -> bool {
+    config.enabled && matched_phrase(config, text).is_some()
+}
+
+/// The phrase heuristic behind [`is_refusal`]: returns the indicator phrase
+/// that matched a short response, counting both words and characters so the
+/// length guard also holds for languages without word spacing.
+fn matched_phrase<'a>(config: &'a RefusalFallbackConfig, text: &str) -> Option<&'a str> {
+    let trimmed = text.trim();
+    if trimmed.is_empty()
+        || trimmed.split_whitespace().count() > config.max_word_count
+        || trimmed.chars().count() > config.max_word_count * MAX_CHARS_PER_WORD
+    {
+        return None;
+    }
+    let lowered = trimmed.to_lowercase();
+    config
+        .indicator_phrases
+        .iter()
+        .find(|phrase| lowered.contains(phrase.as_str()))
+        .map(String::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled_config() -> RefusalFallbackConfig {
+        let mut config = resolve_config(None);
+        config.enabled = true;
+        config
+    }
+
+    #[test]
+    fn flags_short_refusals() {
+        let config = enabled_config();
+        assert!(is_refusal(
+            &config,
+            "I'm sorry, but I can't help with that request."
+        ));
+    }
+
+    #[test]
+    fn ignores_long_responses_with_apologies() {
+        let config = enabled_config();
+        let text = format!(
+            "I can't help with the second part, but here is the rest. {}",
+            "word ".repeat(200)
+        );
+        assert!(!is_refusal(&config, &text));
+    }
+
+    #[test]
+    fn custom_phrases_are_merged_and_lowercased() {
+        let toml = RefusalFallbackToml {
+            enabled: Some(true),
+            indicator_phrases: Some(vec!["Desole, je ne peux pas".to_string()]),
+            ..Default::default()
+        };
+        let config = resolve_config(Some(toml));
+        assert!(is_refusal(&config, "Desole, je ne peux pas vous aider."));
+    }
+
+    #[test]
+    fn disabled_config_never_flags() {
+        let mut config = enabled_config();
+        config.enabled = false;
+        assert!(!is_refusal(
+            &config,
+            "I'm sorry, but I can't help with that."
+        ));
+    }
+
+    #[test]
+    fn flags_non_english_refusals() {
+        let config = enabled_config();
+        assert!(is_refusal(&config, "Lo siento, pero no puedo ayudar."));
+        assert!(is_refusal(&config, "对不起，我无法帮助完成这个请求。"));
+    }
+
+    #[test]
+    fn ignores_long_unspaced_responses() {
+        let config = enabled_config();
+        let text = format!(
+            "我无法帮助完成第二部分，但这是其余的内容。{}",
+            "码".repeat(2000)
+        );
+        assert!(!is_refusal(&config, &text));
+    }
+
+    #[test]
+    fn fallback_model_skips_disabled_configs_and_the_fallback_itself() {
+        let config = enabled_config();
+        assert_eq!(
+            fallback_model(&config, "gpt-5.3-codex"),
+            Some(config.model.clone())
+        );
+        assert_eq!(fallback_model(&config, config.model.as_str()), None);
+
+        let mut disabled = enabled_config();
+        disabled.enabled = false;
+        assert_eq!(fallback_model(&disabled, "gpt-5.3-codex"), None);
+    }
+
+    #[test]
+    fn fallback_provider_resolves_only_configured_other_providers() {
+        let mut config = enabled_config();
+        config.provider = "openrouter".to_string();
+        let openrouter = ModelProviderInfo {
+            name: "OpenRouter".to_string(),
+            ..Default::default()
+        };
+        let providers = HashMap::from([("openrouter".to_string(), openrouter)]);
+        assert_eq!(
+            fallback_provider_info(&config, "OpenAI", &providers).map(|info| info.name),
+            Some("OpenRouter".to_string())
+        );
+        assert!(fallback_provider_info(&config, "OpenRouter", &providers).is_none());
+        assert!(fallback_provider_info(&config, "OpenAI", &HashMap::new()).is_none());
+    }
+
+    #[test]
+    fn explicit_refusal_item_wins_regardless_of_length() {
+        let config = enabled_config();
+        let content = vec![
+            ContentItem::OutputText {
+                text: "word ".repeat(500),
+            },
+            ContentItem::Refusal {
+                refusal: "I can't help with that.".to_string(),
+            },
+        ];
+        let reason = classify_response(&config, &content);
+        assert!(reason.is_some_and(|reason| reason.contains("refusal output item")));
+    }
+
+    #[test]
+    fn plain_answer_is_not_classified() {
+        let config = enabled_config();
+        let content = vec![ContentItem::OutputText {
+            text: "Here is the patch you asked for.".to_string(),
+        }];
+        assert_eq!(classify_response(&config, &content), None);
+    }
+}
@@ -0,0 +1,230 @@
+//! Opt-in disk cache for non-streaming model calls.
+//!
+//! The deterministic unary calls on [`ModelClient`] — memory summarization
+//! and embeddings — consult this cache before going to the wire, which cuts
+//! cost and latency when CI reruns hit identical prompts. Conversation
+//! compaction stays uncached: its payload carries session-scoped metadata
+//! (prompt cache key, instructions), so identical-looking work never hashes
+//! the same across sessions. Entries live under `codex_home/cache` keyed
+//! by model plus a hash of the canonicalized request JSON, and expire after
+//! the configured TTL. Everything here is best-effort: cache failures never
+//! fail the underlying call.
+//!
+//! [`ModelClient`]: crate::client::ModelClient
+
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use codex_config::types::ResponseCacheConfig;
+use codex_config::types::ResponseCacheToml;
+use serde::Deserialize;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use sha2::Digest;
+use sha2::Sha256;
+use tracing::debug;
+
+use crate::config::Config;
+
+/// Subdirectory of `codex_home` holding cached responses.
+const CACHE_SUBDIR: &str = "cache/responses";
+
+pub(crate) fn resolve_config(toml: Option<ResponseCacheToml>) -> ResponseCacheConfig {
+    let toml = toml.unwrap_or_default();
+    let defaults = ResponseCacheConfig::default();
+    ResponseCacheConfig {
+        enabled: toml.enabled.unwrap_or(defaults.enabled),
+        ttl_seconds: toml.ttl_seconds.unwrap_or(defaults.ttl_seconds),
+    }
+}
+
+/// On-disk envelope stored per entry so expiry survives process restarts.
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    cached_at_unix_secs: u64,
+    response: Value,
+}
+
+/// Handle to the response cache directory for one `codex_home`.
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    dir: PathBuf,
+    ttl: Duration,
+}
+
+impl ResponseCache {
+    pub fn new(dir: PathBuf, ttl: Duration) -> Self {
+        Self { dir, ttl }
+    }
+
+    /// Returns a cache handle when `[response_cache]` is enabled, `None`
+    /// otherwise so call sites can skip hashing entirely.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        if !config.response_cache.enabled {
+            return None;
+        }
+        Some(Self::new(
+            config.codex_home.join(CACHE_SUBDIR),
+            Duration::from_secs(config.response_cache.ttl_seconds),
+        ))
+    }
+
+    /// Returns the cached response for `model` + `request` when present and
+    /// unexpired. Expired entries are deleted on the way out.
+    pub fn lookup<T: DeserializeOwned>(&self, model: &str, request: &impl Serialize) -> Option<T> {
+        let path = self.entry_path(model, request)?;
+        let contents = std::fs::read_to_string(&path).ok()?;
+        let entry: CacheEntry = serde_json::from_str(&contents).ok()?;
+        let cached_at = Duration::from_secs(entry.cached_at_unix_secs);
+        if now_unix()
+            .checked_sub(cached_at)
+            .is_none_or(|age| age >= self.ttl)
+        {
+            let _ = std::fs::remove_file(&path);
+            return None;
+        }
+        serde_json::from_value(entry.response).ok()
+    }
+
+    /// Records `response` for `model` + `request`. Failures are logged and
+    /// swallowed; the caller already has the response in hand.
+    pub fn store(&self, model: &str, request: &impl Serialize, response: &impl Serialize) {
+        let Some(path) = self.entry_path(model, request) else {
+            return;
+        };
+        let Ok(response) = serde_json::to_value(response) else {
+            return;
+        };
+        let entry = CacheEntry {
+            cached_at_unix_secs: now_unix().as_secs(),
+            response,
+        };
+        if let Err(error) = self.write_entry(&path, &entry) {
+            debug!("failed to write response cache entry: {error}");
+        }
+    }
+
+    fn write_entry(&self, path: &std::path::Path, entry: &CacheEntry) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        // Write-then-rename so concurrent readers never see a partial entry.
+        let tmp = tempfile::NamedTempFile::new_in(&self.dir)?;
+        serde_json::to_writer(tmp.as_file(), entry)?;
+        tmp.persist(path).map_err(|error| error.error)?;
+        Ok(())
+    }
+
+    fn entry_path(&self, model: &str, request: &impl Serialize) -> Option<PathBuf> {
+        let request = serde_json::to_value(request).ok()?;
+        let mut hasher = Sha256::new();
+        hasher.update(model.as_bytes());
+        hasher.update([0]);
+        hasher.update(canonical_json(&request).as_bytes());
+        let digest = hasher.finalize();
+        Some(self.dir.join(format!("{digest:x}.json")))
+    }
+}
+
+fn now_unix() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+/// Serializes JSON with object keys sorted so logically identical requests
+/// hash the same regardless of field order.
+fn canonical_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort_unstable();
+            out.push('{');
+            for (i, key) in keys.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String((*key).clone()).to_string());
+                out.push(':');
+                write_canonical(&map[*key], out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        other => out.push_str(&other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn stores_and_returns_responses() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ResponseCache::new(dir.path().to_path_buf(), Duration::from_secs(60));
+        let request = json!({"input": "summarize this", "temperature": 0});
+        cache.store("gpt-5.5", &request, &json!({"summary": "done"}));
+        let hit: Option<Value> = cache.lookup("gpt-5.5", &request);
+        assert_eq!(hit, Some(json!({"summary": "done"})));
+    }
+
+    #[test]
+    fn key_covers_model_and_request() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ResponseCache::new(dir.path().to_path_buf(), Duration::from_secs(60));
+        let request = json!({"input": "summarize this"});
+        cache.store("gpt-5.5", &request, &json!("cached"));
+        assert_eq!(
+            cache.lookup::<Value>("o3", &request),
+            None,
+            "different model must miss"
+        );
+        assert_eq!(
+            cache.lookup::<Value>("gpt-5.5", &json!({"input": "other"})),
+            None,
+            "different request must miss"
+        );
+    }
+
+    #[test]
+    fn field_order_does_not_change_the_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ResponseCache::new(dir.path().to_path_buf(), Duration::from_secs(60));
+        cache.store(
+            "gpt-5.5",
+            &json!({"a": 1, "b": [{"x": 1, "y": 2}]}),
+            &json!("cached"),
+        );
+        let hit: Option<Value> = cache.lookup("gpt-5.5", &json!({"b": [{"y": 2, "x": 1}], "a": 1}));
+        assert_eq!(hit, Some(json!("cached")));
+    }
+
+    #[test]
+    fn expired_entries_are_evicted() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ResponseCache::new(dir.path().to_path_buf(), Duration::ZERO);
+        let request = json!({"input": "summarize this"});
+        cache.store("gpt-5.5", &request, &json!("cached"));
+        assert_eq!(cache.lookup::<Value>("gpt-5.5", &request), None);
+        let remaining = std::fs::read_dir(dir.path()).unwrap().count();
+        assert_eq!(remaining, 0, "expired entry should be removed on lookup");
+    }
+}
@@ -58,12 +58,11 @@ pub(crate) async fn handle_retryable_response_stream_error(
     if *retries < max_retries {
         *retries += 1;
         let retry_count = *retries;
-        let delay = match &err {
-            CodexErr::Stream(_, requested_delay) => {
-                requested_delay.unwrap_or_else(|| backoff(retry_count))
-            }
-            _ => backoff(retry_count),
+        let requested_delay = match &err {
+            CodexErr::Stream(_, requested_delay) => *requested_delay,
+            _ => None,
         };
+        let delay = requested_delay.unwrap_or_else(|| backoff(retry_count));
         log_retry(request, turn_context, &err, retry_count, max_retries, delay);
 
         // In release builds, hide the first websocket retry notification to reduce noisy
@@ -73,13 +72,16 @@ pub(crate) async fn handle_retryable_response_stream_error(
             || !sess.services.model_client.responses_websocket_enabled();
         if report_error {
             // Surface retry information to any UI/front-end so the user understands what is
-            // happening instead of staring at a seemingly frozen screen.
-            sess.notify_stream_error(
-                turn_context,
-                format!("Reconnecting... {retry_count}/{max_retries}"),
-                err,
-            )
-            .await;
+            // happening instead of staring at a seemingly frozen screen. When the server
+            // asked for a specific wait, include it so the UI can show "retrying in 38s".
+            let message = match requested_delay {
+                Some(requested_delay) => format!(
+                    "Reconnecting... {retry_count}/{max_retries} (retrying in {}s)",
+                    requested_delay.as_secs().max(1)
+                ),
+                None => format!("Reconnecting... {retry_count}/{max_retries}"),
+            };
+            sess.notify_stream_error(turn_context, message, err).await;
         }
         tokio::time::sleep(delay).await;
         return Ok(());
@@ -0,0 +1,62 @@
+//! Searches recorded conversations by content.
+//!
+//! Matching rollout files under `CODEX_HOME` are found with ripgrep (with a
+//! scan fallback), then resolved to their session metadata plus a snippet
+//! around the first hit. Both `codex search` and the HTTP server's `/search`
+//! endpoint share this module.
+
+use std::io;
+use std::path::Path;
+
+use codex_install_context::InstallContext;
+use codex_rollout::first_rollout_content_match_snippet;
+use codex_rollout::read_session_meta_line;
+use codex_rollout::search_rollout_matches;
+use serde::Serialize;
+
+/// One conversation matching a search, newest first.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConversationSearchResult {
+    pub id: String,
+    pub timestamp: String,
+    pub cwd: String,
+    /// Transcript excerpt around the first match.
+    pub snippet: String,
+}
+
+/// Searches rollout transcripts under `codex_home` for `search_term`,
+/// returning up to `limit` conversations ordered newest first.
+pub async fn search_conversations(
+    codex_home: &Path,
+    search_term: &str,
+    archived: bool,
+    limit: usize,
+) -> io::Result<Vec<ConversationSearchResult>> {
+    let rg_command = InstallContext::current().rg_command();
+    let matches =
+        search_rollout_matches(rg_command.as_path(), codex_home, archived, search_term).await?;
+    let mut results = Vec::new();
+    for (path, snippet) in matches {
+        let Ok(meta_line) = read_session_meta_line(&path).await else {
+            continue;
+        };
+        let snippet = match snippet {
+            Some(snippet) => snippet,
+            None => match first_rollout_content_match_snippet(&path, search_term).await {
+                Ok(Some(snippet)) => snippet,
+                // The file-level hit was in metadata rather than
+                // conversation content; skip it.
+                Ok(None) | Err(_) => continue,
+            },
+        };
+        results.push(ConversationSearchResult {
+            id: meta_line.meta.id.to_string(),
+            timestamp: meta_line.meta.timestamp.clone(),
+            cwd: meta_line.meta.cwd.display().to_string(),
+            snippet,
+        });
+    }
+    results.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    results.truncate(limit);
+    Ok(results)
+}
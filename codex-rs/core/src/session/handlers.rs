@@ -131,6 +131,7 @@ async fn thread_settings_update(
         active_permission_profile,
         windows_sandbox_level,
         model,
+        model_provider,
         effort,
         summary,
         service_tier,
@@ -159,6 +160,7 @@ async fn thread_settings_update(
         permission_profile,
         active_permission_profile,
         windows_sandbox_level,
+        model_provider,
         collaboration_mode: Some(collaboration_mode),
         reasoning_summary: summary,
         service_tier,
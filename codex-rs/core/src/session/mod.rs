@@ -355,6 +355,7 @@ use codex_protocol::protocol::ErrorEvent;
 use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::ExecApprovalRequestEvent;
+use codex_protocol::protocol::FallbackTriggeredEvent;
 use codex_protocol::protocol::InitialHistory;
 use codex_protocol::protocol::McpServerRefreshConfig;
 use codex_protocol::protocol::ModelRerouteEvent;
@@ -373,6 +374,7 @@ use codex_protocol::protocol::SessionNetworkProxyRuntime;
 use codex_protocol::protocol::StreamErrorEvent;
 use codex_protocol::protocol::Submission;
 use codex_protocol::protocol::ThreadMemoryMode;
+use codex_protocol::protocol::TokenCostInfo;
 use codex_protocol::protocol::TokenCountEvent;
 use codex_protocol::protocol::TokenUsage;
 use codex_protocol::protocol::TokenUsageInfo;
@@ -562,7 +564,7 @@ impl Codex {
         };
 
         let config = Arc::new(config);
-        let refresh_strategy = if session_source.is_non_root_agent() {
+        let refresh_strategy = if session_source.is_non_root_agent() || config.offline {
             codex_models_manager::manager::RefreshStrategy::Offline
         } else {
             codex_models_manager::manager::RefreshStrategy::OnlineIfUncached
@@ -2986,6 +2988,37 @@ impl Session {
         true
     }
 
+    /// Announce that a refusal classification rerouted the turn to a fallback
+    /// model. Emits a structured `FallbackTriggered` event (persisted in the
+    /// rollout) plus a `Warning` so every front-end surfaces the switch.
+    pub(crate) async fn notify_fallback_triggered(
+        self: &Arc<Self>,
+        turn_context: &Arc<TurnContext>,
+        from_model: String,
+        to_model: String,
+        reason: String,
+    ) {
+        self.send_event(
+            turn_context,
+            EventMsg::FallbackTriggered(FallbackTriggeredEvent {
+                from_model: from_model.clone(),
+                to_model: to_model.clone(),
+                reason: reason.clone(),
+            }),
+        )
+        .await;
+
+        self.send_event(
+            turn_context,
+            EventMsg::Warning(WarningEvent {
+                message: format!(
+                    "Response from {from_model} was classified as a refusal ({reason}); retried with {to_model}."
+                ),
+            }),
+        )
+        .await;
+    }
+
     pub(crate) async fn emit_model_verification(
         self: &Arc<Self>,
         turn_context: &Arc<TurnContext>,
@@ -3788,7 +3821,23 @@ impl Session {
             let state = self.state.lock().await;
             state.token_info_and_rate_limits()
         };
-        let event = EventMsg::TokenCount(TokenCountEvent { info, rate_limits });
+        let cost = info.as_ref().map(|info| {
+            let model = turn_context.model_info.slug.clone();
+            TokenCostInfo {
+                estimated_cost_usd: crate::cost_accounting::estimate_cost_usd(
+                    &model,
+                    &info.total_token_usage,
+                    &turn_context.config.model_rates,
+                ),
+                provider_id: turn_context.config.model_provider_id.clone(),
+                model,
+            }
+        });
+        let event = EventMsg::TokenCount(TokenCountEvent {
+            info,
+            rate_limits,
+            cost,
+        });
         self.send_event(turn_context, event).await;
     }
 
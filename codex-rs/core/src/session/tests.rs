@@ -2245,24 +2245,28 @@ async fn record_initial_history_seeds_token_info_from_rollout() {
         TokenCountEvent {
             info: Some(info1),
             rate_limits: None,
+            cost: None,
         },
     )));
     rollout_items.push(RolloutItem::EventMsg(EventMsg::TokenCount(
         TokenCountEvent {
             info: None,
             rate_limits: None,
+            cost: None,
         },
     )));
     rollout_items.push(RolloutItem::EventMsg(EventMsg::TokenCount(
         TokenCountEvent {
             info: Some(info2.clone()),
             rate_limits: None,
+            cost: None,
         },
     )));
     rollout_items.push(RolloutItem::EventMsg(EventMsg::TokenCount(
         TokenCountEvent {
             info: None,
             rate_limits: None,
+            cost: None,
         },
     )));
 
@@ -4386,6 +4390,38 @@ async fn session_settings_legacy_fast_service_tier_update_uses_priority_request_
     );
 }
 
+#[tokio::test]
+async fn session_settings_model_provider_update_switches_provider() {
+    let session_configuration = make_session_configuration_for_tests().await;
+
+    let updated = session_configuration
+        .apply(&SessionSettingsUpdate {
+            model_provider: Some("groq".to_string()),
+            ..Default::default()
+        })
+        .expect("built-in provider update should apply");
+
+    assert_eq!(updated.provider.name, "Groq");
+    assert_eq!(
+        updated.thread_config_snapshot().model_provider_id,
+        "groq".to_string()
+    );
+}
+
+#[tokio::test]
+async fn session_settings_unknown_model_provider_update_is_rejected() {
+    let session_configuration = make_session_configuration_for_tests().await;
+
+    let err = session_configuration
+        .apply(&SessionSettingsUpdate {
+            model_provider: Some("no-such-provider".to_string()),
+            ..Default::default()
+        })
+        .expect_err("unknown provider id should be rejected");
+
+    assert!(err.to_string().contains("no-such-provider"));
+}
+
 pub(crate) async fn make_session_configuration_for_tests() -> SessionConfiguration {
     let codex_home = tempfile::tempdir().expect("create temp dir");
     let config = build_test_config(codex_home.path()).await;
@@ -5553,6 +5589,7 @@ pub(crate) async fn make_session_and_context() -> (Session, TurnContext) {
             /*attestation_provider*/ None,
             config.http_client_factory(),
         ),
+        race_model_client: None,
         code_mode_service: crate::tools::code_mode::CodeModeService::new(Arc::new(
             codex_code_mode::InProcessCodeModeSessionProvider,
         )),
@@ -7684,6 +7721,7 @@ where
             /*attestation_provider*/ None,
             config.http_client_factory(),
         ),
+        race_model_client: None,
         code_mode_service: crate::tools::code_mode::CodeModeService::new(Arc::new(
             codex_code_mode::InProcessCodeModeSessionProvider,
         )),
@@ -6,6 +6,7 @@ use std::sync::atomic::Ordering;
 
 use crate::SkillInjections;
 use crate::build_skill_injections;
+use crate::client::ModelClient;
 use crate::client::ModelClientSession;
 use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
@@ -143,7 +144,7 @@ use tracing::warn;
 ///
 pub(crate) async fn run_turn(
     sess: Arc<Session>,
-    turn_context: Arc<TurnContext>,
+    mut turn_context: Arc<TurnContext>,
     turn_extension_data: Arc<codex_extension_api::ExtensionData>,
     input: Vec<TurnInput>,
     prewarmed_client_session: Option<ModelClientSession>,
@@ -151,6 +152,25 @@ pub(crate) async fn run_turn(
 ) -> CodexResult<Option<String>> {
     let mut client_session =
         prewarmed_client_session.unwrap_or_else(|| sess.services.model_client.new_session());
+    if turn_context.config.model_rules.enabled {
+        let signals = crate::model_router::TaskSignals::from_prompt(&turn_input_text(&input));
+        if let Some(model) = crate::model_router::route(
+            &turn_context.config.model_rules,
+            &turn_context.model_info.slug,
+            &signals,
+        ) {
+            info!(
+                model = %turn_context.model_info.slug,
+                routed_model = %model,
+                "model rule matched; routing turn"
+            );
+            turn_context = Arc::new(
+                turn_context
+                    .with_model(model, &sess.services.models_manager)
+                    .await,
+            );
+        }
+    }
     // TODO(ccunningham): Pre-turn compaction runs before context updates and the
     // new user message are recorded. Estimate pending incoming items (context
     // diffs/full reinjection + user input) and trigger compaction preemptively
@@ -223,6 +243,20 @@ pub(crate) async fn run_turn(
     // 1. At the start of a turn, so the fresh turn input in `input` gets sampled first.
     // 2. After auto-compact, when model/tool continuation needs to resume before any steer.
 
+    // One retry per turn: when the provider rejects a request for exceeding
+    // context, finish the turn on the configured long-context model.
+    let mut context_overflow_fallback = crate::context_overflow_fallback::fallback_model(
+        &turn_context.config.context_overflow_fallback,
+        &turn_context.model_info.slug,
+    );
+
+    // One retry per turn: when the final response classifies as a refusal,
+    // re-answer on the configured fallback model.
+    let mut refusal_fallback = crate::refusal_fallback::fallback_model(
+        &turn_context.config.refusal_fallback,
+        &turn_context.model_info.slug,
+    );
+
     let mut next_step_context = Some(first_step_context);
     loop {
         // Note that pending_input would be something like a message the user
@@ -372,6 +406,67 @@ pub(crate) async fn run_turn(
                 }
 
                 if !needs_follow_up {
+                    // Classify the completed response's content items rather
+                    // than its flattened text, so an explicit `refusal`
+                    // output item (and any host-installed classifier) is
+                    // consulted.
+                    let refusal_reason = if refusal_fallback.is_some() {
+                        let content = {
+                            let state = sess.state.lock().await;
+                            state.history.last_assistant_message_content()
+                        };
+                        content.and_then(|content| {
+                            crate::refusal_fallback::classify_response(
+                                &turn_context.config.refusal_fallback,
+                                &content,
+                            )
+                        })
+                    } else {
+                        None
+                    };
+                    if let Some(reason) = refusal_reason {
+                        let Some(fallback_model) = refusal_fallback.take() else {
+                            break;
+                        };
+                        info!(
+                            model = %turn_context.model_info.slug,
+                            fallback_model = %fallback_model,
+                            reason = %reason,
+                            "final response classified as a refusal; retrying turn on fallback model"
+                        );
+                        sess.notify_fallback_triggered(
+                            &turn_context,
+                            turn_context.model_info.slug.clone(),
+                            fallback_model.clone(),
+                            reason,
+                        )
+                        .await;
+                        {
+                            // The refused message must not ride along as
+                            // context for the re-answer.
+                            let mut state = sess.state.lock().await;
+                            state.history.remove_last_assistant_message();
+                        }
+                        if let Some(provider_info) = crate::refusal_fallback::fallback_provider_info(
+                            &turn_context.config.refusal_fallback,
+                            &sess.services.model_client.provider_name(),
+                            &turn_context.config.model_providers,
+                        ) {
+                            client_session = sess
+                                .services
+                                .model_client
+                                .with_provider(provider_info)
+                                .new_session();
+                        }
+                        turn_context = Arc::new(
+                            turn_context
+                                .with_model(fallback_model, &sess.services.models_manager)
+                                .await,
+                        );
+                        next_step_context =
+                            Some(sess.capture_step_context(Arc::clone(&turn_context)).await);
+                        continue;
+                    }
                     last_agent_message = sampling_request_last_agent_message;
                     let stop_outcome = run_turn_stop_hooks(
                         &sess,
@@ -444,6 +539,31 @@ pub(crate) async fn run_turn(
                 sess.send_event(&turn_context, event).await;
                 break;
             }
+            Err(CodexErr::ContextWindowExceeded) if context_overflow_fallback.is_some() => {
+                let Some(fallback_model) = context_overflow_fallback.take() else {
+                    break;
+                };
+                info!(
+                    model = %turn_context.model_info.slug,
+                    fallback_model = %fallback_model,
+                    "context window exceeded; retrying turn on fallback model"
+                );
+                sess.send_event(
+                    &turn_context,
+                    EventMsg::Warning(WarningEvent {
+                        message: format!("Context window exceeded; retrying on {fallback_model}."),
+                    }),
+                )
+                .await;
+                turn_context = Arc::new(
+                    turn_context
+                        .with_model(fallback_model, &sess.services.models_manager)
+                        .await,
+                );
+                next_step_context =
+                    Some(sess.capture_step_context(Arc::clone(&turn_context)).await);
+                continue;
+            }
             Err(e) => {
                 info!("Turn error: {e:#}");
                 let error = e.to_codex_protocol_error();
@@ -461,6 +581,24 @@ pub(crate) async fn run_turn(
     Ok(last_agent_message)
 }
 
+/// Concatenates the user-authored text in the turn input, which is what the
+/// `[model_rules]` router matches against.
+fn turn_input_text(input: &[TurnInput]) -> String {
+    input
+        .iter()
+        .filter_map(|item| match item {
+            TurnInput::UserInput { content, .. } => Some(content),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|item| match item {
+            UserInput::Text { text, .. } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 #[instrument(level = "trace", skip_all)]
 async fn turn_diff_display_roots(turn_context: &TurnContext) -> Vec<(String, PathBuf)> {
     let mut display_roots = Vec::new();
@@ -1550,6 +1688,7 @@ pub(super) fn realtime_text_for_event(msg: &EventMsg) -> Option<(String, Option<
         | EventMsg::RealtimeConversationRealtime(_)
         | EventMsg::RealtimeConversationClosed(_)
         | EventMsg::ModelReroute(_)
+        | EventMsg::FallbackTriggered(_)
         | EventMsg::ModelVerification(_)
         | EventMsg::TurnModerationMetadata(_)
         | EventMsg::SafetyBuffering(_)
@@ -1975,20 +2114,89 @@ async fn try_run_sampling_request(
         .features
         .enabled(Feature::ConcurrentReasoningSummaries)
         && turn_context.provider.info().is_openai();
-    let mut stream = client_session
-        .stream(
-            prompt,
-            &turn_context.model_info,
-            &turn_context.session_telemetry,
-            turn_context.reasoning_effort.clone(),
-            turn_context.reasoning_summary,
-            turn_context.config.service_tier.clone(),
-            responses_metadata,
-            &inference_trace,
-        )
-        .instrument(trace_span!("stream_request"))
-        .or_cancel(&cancellation_token)
-        .await??;
+    // Provider race: when configured, open the same request against the
+    // secondary provider too and keep whichever stream responds first.
+    let race_session = turn_context
+        .config
+        .provider_race
+        .enabled
+        .then(|| sess.services.race_model_client.as_ref())
+        .flatten()
+        .map(ModelClient::new_session);
+    let mut stream = match race_session {
+        Some(mut race_session) => {
+            let primary_provider = turn_context.provider.info().name.clone();
+            let race_provider = race_session.provider_name();
+            async {
+                let (primary, secondary) = tokio::join!(
+                    client_session.stream(
+                        prompt,
+                        &turn_context.model_info,
+                        &turn_context.session_telemetry,
+                        turn_context.reasoning_effort.clone(),
+                        turn_context.reasoning_summary,
+                        turn_context.config.service_tier.clone(),
+                        responses_metadata,
+                        &inference_trace,
+                    ),
+                    race_session.stream(
+                        prompt,
+                        &turn_context.model_info,
+                        &turn_context.session_telemetry,
+                        turn_context.reasoning_effort.clone(),
+                        turn_context.reasoning_summary,
+                        turn_context.config.service_tier.clone(),
+                        responses_metadata,
+                        &inference_trace,
+                    ),
+                );
+                match (primary, secondary) {
+                    (Ok(primary), Ok(secondary)) => Ok(crate::provider_race::race_streams(
+                        primary,
+                        primary_provider,
+                        secondary,
+                        race_provider,
+                    )
+                    .await),
+                    (Ok(primary), Err(err)) => {
+                        warn!(
+                            "provider race: {race_provider} request failed: {err}; using primary"
+                        );
+                        Ok(primary)
+                    }
+                    (Err(err), Ok(secondary)) => {
+                        warn!(
+                            "provider race: primary request failed: {err}; using {race_provider}"
+                        );
+                        Ok(secondary)
+                    }
+                    (Err(err), Err(race_err)) => {
+                        warn!("provider race: {race_provider} request also failed: {race_err}");
+                        Err(err)
+                    }
+                }
+            }
+            .instrument(trace_span!("stream_request"))
+            .or_cancel(&cancellation_token)
+            .await??
+        }
+        None => {
+            client_session
+                .stream(
+                    prompt,
+                    &turn_context.model_info,
+                    &turn_context.session_telemetry,
+                    turn_context.reasoning_effort.clone(),
+                    turn_context.reasoning_summary,
+                    turn_context.config.service_tier.clone(),
+                    responses_metadata,
+                    &inference_trace,
+                )
+                .instrument(trace_span!("stream_request"))
+                .or_cancel(&cancellation_token)
+                .await??
+        }
+    };
     let mut in_flight: FuturesOrdered<BoxFuture<'static, CodexResult<ResponseInputItem>>> =
         FuturesOrdered::new();
     let mut needs_follow_up = false;
@@ -2007,6 +2215,7 @@ async fn try_run_sampling_request(
     let defer_streamed_turn_items_for_contributors =
         !sess.services.extensions.turn_item_contributors().is_empty();
     let mut active_item_is_streaming_to_client = false;
+    let mut stream_perf = crate::stream_perf::StreamPerfTracker::new();
     let receiving_span = trace_span!("receiving_stream");
     let outcome: CodexResult<SamplingRequestResult> = loop {
         let handle_responses = trace_span!(
@@ -2048,6 +2257,7 @@ async fn try_run_sampling_request(
             .session_telemetry
             .record_responses(&handle_responses, &event);
         record_turn_ttft_metric(&turn_context, &event).await;
+        stream_perf.observe(&event);
 
         match event {
             ResponseEvent::Created => {}
@@ -2461,6 +2671,16 @@ async fn try_run_sampling_request(
         }
     };
     drop(sampling_timing_guard);
+    if let Some(record) = stream_perf.into_record(
+        turn_context.provider.info().name.clone(),
+        turn_context.model_info.slug.clone(),
+    ) {
+        crate::stream_perf::record_sample(
+            &turn_context.session_telemetry,
+            &turn_context.config.codex_home,
+            record,
+        );
+    }
 
     flush_assistant_text_segments_all(
         &sess,
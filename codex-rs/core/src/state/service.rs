@@ -98,6 +98,9 @@ pub(crate) struct SessionServices {
     pub(crate) time_provider: Arc<dyn TimeProvider>,
     /// Session-scoped model client shared across turns.
     pub(crate) model_client: ModelClient,
+    /// Secondary client for the opt-in provider-race mode, bound to the
+    /// `[provider_race]` provider. `None` when racing is not configured.
+    pub(crate) race_model_client: Option<ModelClient>,
     pub(crate) code_mode_service: CodeModeService,
     pub(crate) tool_search_handler_cache: ToolSearchHandlerCache,
     pub(crate) turn_environments: Arc<ThreadEnvironments>,
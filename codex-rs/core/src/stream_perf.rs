@@ -0,0 +1,244 @@
+//! Streaming performance metrics per provider.
+//!
+//! Every completed model stream (one sampling request) is measured for
+//! time-to-first-token, output tokens per second, and stall count (gaps in
+//! the event stream after the first token). Samples are emitted as OTEL
+//! metrics tagged with the provider and appended to `perf.jsonl` under the
+//! Codex home, where `codex perf` aggregates them into a report so users can
+//! compare providers and wire APIs on observed numbers.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_otel::MODEL_STREAM_STALL_COUNT_METRIC;
+use codex_otel::MODEL_STREAM_TOKENS_PER_SEC_METRIC;
+use codex_otel::MODEL_STREAM_TTFT_DURATION_METRIC;
+use codex_otel::SessionTelemetry;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::io::AsyncWriteExt;
+
+use crate::ResponseEvent;
+use crate::turn_timing::now_unix_timestamp_ms;
+use crate::turn_timing::response_event_records_turn_ttft;
+
+/// File under the Codex home that `codex perf` reads.
+pub const PERF_LOG_FILE_NAME: &str = "perf.jsonl";
+/// Rotate the log once it exceeds this size; one previous generation is kept.
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+/// A gap of at least this long between stream events, after the first token,
+/// counts as a stall.
+const STALL_GAP: Duration = Duration::from_secs(2);
+
+/// One measured model stream, as persisted to the perf log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamPerfRecord {
+    pub timestamp_ms: i64,
+    pub provider: String,
+    pub model: String,
+    /// Milliseconds from the request until the first token-bearing event.
+    pub ttft_ms: Option<u64>,
+    pub output_tokens: Option<i64>,
+    /// Output tokens divided by the first-token-to-completion duration.
+    pub tokens_per_sec: Option<f64>,
+    pub stall_count: u32,
+}
+
+/// Observes the events of a single model stream and produces a
+/// [`StreamPerfRecord`] once the stream completes.
+pub(crate) struct StreamPerfTracker {
+    started_at: Instant,
+    first_token_at: Option<Instant>,
+    last_event_at: Instant,
+    completed_at: Option<Instant>,
+    output_tokens: Option<i64>,
+    stall_count: u32,
+}
+
+impl StreamPerfTracker {
+    pub(crate) fn new() -> Self {
+        let now = Instant::now();
+        Self {
+            started_at: now,
+            first_token_at: None,
+            last_event_at: now,
+            completed_at: None,
+            output_tokens: None,
+            stall_count: 0,
+        }
+    }
+
+    pub(crate) fn observe(&mut self, event: &ResponseEvent) {
+        let now = Instant::now();
+        if self.first_token_at.is_some()
+            && now.saturating_duration_since(self.last_event_at) >= STALL_GAP
+        {
+            self.stall_count = self.stall_count.saturating_add(1);
+        }
+        self.last_event_at = now;
+        if self.first_token_at.is_none() && response_event_records_turn_ttft(event) {
+            self.first_token_at = Some(now);
+        }
+        if let ResponseEvent::Completed { token_usage, .. } = event {
+            self.completed_at = Some(now);
+            self.output_tokens = token_usage.as_ref().map(|usage| usage.output_tokens);
+        }
+    }
+
+    /// Returns the measured sample, or `None` when the stream never completed
+    /// (aborted or errored streams are not recorded).
+    pub(crate) fn into_record(self, provider: String, model: String) -> Option<StreamPerfRecord> {
+        let completed_at = self.completed_at?;
+        let ttft = self
+            .first_token_at
+            .map(|at| at.saturating_duration_since(self.started_at));
+        let tokens_per_sec = match (self.output_tokens, self.first_token_at) {
+            (Some(tokens), Some(first_token_at)) if tokens > 0 => {
+                let streaming = completed_at.saturating_duration_since(first_token_at);
+                (!streaming.is_zero()).then(|| tokens as f64 / streaming.as_secs_f64())
+            }
+            _ => None,
+        };
+        Some(StreamPerfRecord {
+            timestamp_ms: now_unix_timestamp_ms(),
+            provider,
+            model,
+            ttft_ms: ttft.map(|ttft| u64::try_from(ttft.as_millis()).unwrap_or(u64::MAX)),
+            output_tokens: self.output_tokens,
+            tokens_per_sec,
+            stall_count: self.stall_count,
+        })
+    }
+}
+
+/// Emits the sample as OTEL metrics and appends it to the perf log.
+pub(crate) fn record_sample(
+    telemetry: &SessionTelemetry,
+    codex_home: &Path,
+    record: StreamPerfRecord,
+) {
+    let tags = [("provider", record.provider.as_str())];
+    if let Some(ttft_ms) = record.ttft_ms {
+        telemetry.record_duration(
+            MODEL_STREAM_TTFT_DURATION_METRIC,
+            Duration::from_millis(ttft_ms),
+            &tags,
+        );
+    }
+    if let Some(tokens_per_sec) = record.tokens_per_sec {
+        telemetry.histogram(
+            MODEL_STREAM_TOKENS_PER_SEC_METRIC,
+            tokens_per_sec as i64,
+            &tags,
+        );
+    }
+    if record.stall_count > 0 {
+        telemetry.counter(
+            MODEL_STREAM_STALL_COUNT_METRIC,
+            i64::from(record.stall_count),
+            &tags,
+        );
+    }
+    spawn_append(codex_home.join(PERF_LOG_FILE_NAME), record);
+}
+
+/// Appends the record to the perf log off the turn's critical path. Failures
+/// are logged and otherwise ignored; perf reporting is best effort.
+fn spawn_append(path: PathBuf, record: StreamPerfRecord) {
+    tokio::spawn(async move {
+        let mut line = match serde_json::to_string(&record) {
+            Ok(line) => line,
+            Err(err) => {
+                tracing::warn!("failed to serialize stream perf record: {err}");
+                return;
+            }
+        };
+        line.push('\n');
+        rotate_if_needed(&path);
+        let result = async {
+            let mut file = tokio::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .await?;
+            file.write_all(line.as_bytes()).await
+        }
+        .await;
+        if let Err(err) = result {
+            tracing::warn!("failed to append to {}: {err}", path.display());
+        }
+    });
+}
+
+/// Renames an oversized log to `perf.jsonl.1`, keeping one previous
+/// generation so reports retain recent history across rotations.
+fn rotate_if_needed(path: &Path) {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return;
+    };
+    if metadata.len() < MAX_LOG_BYTES {
+        return;
+    }
+    let rotated = path.with_extension("jsonl.1");
+    if let Err(err) = std::fs::rename(path, &rotated) {
+        tracing::warn!("failed to rotate {}: {err}", path.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::TokenUsage;
+
+    fn completed(output_tokens: i64) -> ResponseEvent {
+        ResponseEvent::Completed {
+            response_id: "resp".to_string(),
+            token_usage: Some(TokenUsage {
+                output_tokens,
+                ..Default::default()
+            }),
+            end_turn: None,
+        }
+    }
+
+    #[test]
+    fn incomplete_streams_are_not_recorded() {
+        let mut tracker = StreamPerfTracker::new();
+        tracker.observe(&ResponseEvent::Created);
+        tracker.observe(&ResponseEvent::OutputTextDelta("hi".to_string()));
+        assert!(
+            tracker
+                .into_record("openai".to_string(), "gpt-5.3-codex".to_string())
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn completed_streams_record_ttft_and_throughput() {
+        let mut tracker = StreamPerfTracker::new();
+        tracker.observe(&ResponseEvent::Created);
+        tracker.observe(&ResponseEvent::OutputTextDelta("hi".to_string()));
+        tracker.observe(&completed(42));
+        let record = tracker
+            .into_record("openai".to_string(), "gpt-5.3-codex".to_string())
+            .expect("completed stream should produce a record");
+        assert_eq!(record.provider, "openai");
+        assert_eq!(record.output_tokens, Some(42));
+        assert!(record.ttft_ms.is_some());
+        assert_eq!(record.stall_count, 0);
+    }
+
+    #[test]
+    fn ttft_requires_a_token_bearing_event() {
+        let mut tracker = StreamPerfTracker::new();
+        tracker.observe(&ResponseEvent::Created);
+        tracker.observe(&completed(0));
+        let record = tracker
+            .into_record("openai".to_string(), "gpt-5.3-codex".to_string())
+            .expect("completed stream should produce a record");
+        assert_eq!(record.ttft_ms, None);
+        assert_eq!(record.tokens_per_sec, None);
+    }
+}
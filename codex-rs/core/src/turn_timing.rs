@@ -327,7 +327,7 @@ impl TurnTimingStateInner {
     }
 }
 
-fn response_event_records_turn_ttft(event: &ResponseEvent) -> bool {
+pub(crate) fn response_event_records_turn_ttft(event: &ResponseEvent) -> bool {
     match event {
         ResponseEvent::OutputItemDone(item) | ResponseEvent::OutputItemAdded(item) => {
             response_item_records_turn_ttft(item)
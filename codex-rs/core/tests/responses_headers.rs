@@ -78,6 +78,13 @@ async fn responses_stream_includes_subagent_header_on_review() {
         experimental_bearer_token: None,
         auth: None,
         aws: None,
+        oauth: None,
+        key_command: None,
+        key_file: None,
+        https_proxy: None,
+        no_proxy: None,
+        ca_bundle_path: None,
+        insecure_skip_verify: false,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
@@ -88,6 +95,8 @@ async fn responses_stream_includes_subagent_header_on_review() {
         websocket_connect_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        prompt_cache: None,
+        embedding_model: None,
     };
 
     let codex_home = TempDir::new().expect("failed to create TempDir");
@@ -214,6 +223,13 @@ async fn responses_stream_includes_subagent_header_on_other() {
         experimental_bearer_token: None,
         auth: None,
         aws: None,
+        oauth: None,
+        key_command: None,
+        key_file: None,
+        https_proxy: None,
+        no_proxy: None,
+        ca_bundle_path: None,
+        insecure_skip_verify: false,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
@@ -224,6 +240,8 @@ async fn responses_stream_includes_subagent_header_on_other() {
         websocket_connect_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        prompt_cache: None,
+        embedding_model: None,
     };
 
     let codex_home = TempDir::new().expect("failed to create TempDir");
@@ -331,6 +349,13 @@ async fn responses_respects_model_info_overrides_from_config() {
         experimental_bearer_token: None,
         auth: None,
         aws: None,
+        oauth: None,
+        key_command: None,
+        key_file: None,
+        https_proxy: None,
+        no_proxy: None,
+        ca_bundle_path: None,
+        insecure_skip_verify: false,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
@@ -341,6 +366,8 @@ async fn responses_respects_model_info_overrides_from_config() {
         websocket_connect_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        prompt_cache: None,
+        embedding_model: None,
     };
 
     let codex_home = TempDir::new().expect("failed to create TempDir");
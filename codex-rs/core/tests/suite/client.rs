@@ -1296,6 +1296,13 @@ async fn send_provider_auth_request(server: &MockServer, auth: ModelProviderAuth
         experimental_bearer_token: None,
         auth: Some(auth),
         aws: None,
+        oauth: None,
+        key_command: None,
+        key_file: None,
+        https_proxy: None,
+        no_proxy: None,
+        ca_bundle_path: None,
+        insecure_skip_verify: false,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
@@ -1306,6 +1313,8 @@ async fn send_provider_auth_request(server: &MockServer, auth: ModelProviderAuth
         websocket_connect_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        prompt_cache: None,
+        embedding_model: None,
     };
 
     let codex_home = TempDir::new().unwrap();
@@ -3023,6 +3032,13 @@ async fn azure_responses_request_includes_store_and_prefixed_item_ids() {
         experimental_bearer_token: None,
         auth: None,
         aws: None,
+        oauth: None,
+        key_command: None,
+        key_file: None,
+        https_proxy: None,
+        no_proxy: None,
+        ca_bundle_path: None,
+        insecure_skip_verify: false,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
@@ -3033,6 +3049,8 @@ async fn azure_responses_request_includes_store_and_prefixed_item_ids() {
         websocket_connect_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        prompt_cache: None,
+        embedding_model: None,
     };
 
     let codex_home = TempDir::new().unwrap();
@@ -3731,6 +3749,13 @@ async fn azure_overrides_assign_properties_used_for_responses_url() {
         experimental_bearer_token: None,
         auth: None,
         aws: None,
+        oauth: None,
+        key_command: None,
+        key_file: None,
+        https_proxy: None,
+        no_proxy: None,
+        ca_bundle_path: None,
+        insecure_skip_verify: false,
         query_params: Some(std::collections::HashMap::from([(
             "api-version".to_string(),
             "2025-04-01-preview".to_string(),
@@ -3748,6 +3773,8 @@ async fn azure_overrides_assign_properties_used_for_responses_url() {
         websocket_connect_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        prompt_cache: None,
+        embedding_model: None,
     };
 
     // Init session
@@ -3825,6 +3852,13 @@ async fn env_var_overrides_loaded_auth() {
         experimental_bearer_token: None,
         auth: None,
         aws: None,
+        oauth: None,
+        key_command: None,
+        key_file: None,
+        https_proxy: None,
+        no_proxy: None,
+        ca_bundle_path: None,
+        insecure_skip_verify: false,
         wire_api: WireApi::Responses,
         http_headers: Some(std::collections::HashMap::from([(
             "Custom-Header".to_string(),
@@ -3837,6 +3871,8 @@ async fn env_var_overrides_loaded_auth() {
         websocket_connect_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        prompt_cache: None,
+        embedding_model: None,
     };
 
     // Init session
@@ -2266,6 +2266,13 @@ fn websocket_provider_with_connect_timeout(
         experimental_bearer_token: None,
         auth: None,
         aws: None,
+        oauth: None,
+        key_command: None,
+        key_file: None,
+        https_proxy: None,
+        no_proxy: None,
+        ca_bundle_path: None,
+        insecure_skip_verify: false,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
@@ -2276,6 +2283,8 @@ fn websocket_provider_with_connect_timeout(
         websocket_connect_timeout_ms,
         requires_openai_auth: false,
         supports_websockets: true,
+        prompt_cache: None,
+        embedding_model: None,
     }
 }
 
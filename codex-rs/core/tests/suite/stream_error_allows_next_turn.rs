@@ -71,6 +71,13 @@ async fn continue_after_stream_error() {
         experimental_bearer_token: None,
         auth: None,
         aws: None,
+        oauth: None,
+        key_command: None,
+        key_file: None,
+        https_proxy: None,
+        no_proxy: None,
+        ca_bundle_path: None,
+        insecure_skip_verify: false,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
@@ -81,6 +88,8 @@ async fn continue_after_stream_error() {
         websocket_connect_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        prompt_cache: None,
+        embedding_model: None,
     };
 
     let TestCodex { codex, .. } = test_codex()
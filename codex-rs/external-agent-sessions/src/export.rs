@@ -185,6 +185,7 @@ fn token_count_item(last_model_visible_tokens: i64) -> RolloutItem {
             model_context_window: None,
         }),
         rate_limits: None,
+        cost: None,
     }))
 }
 
@@ -15,8 +15,24 @@ pub enum TransportError {
     },
     #[error("retry limit reached")]
     RetryLimit,
+    #[error("retry budget exhausted: {0}")]
+    BudgetExhausted(String),
+    /// The request was sent but the response (or response body) timed out.
     #[error("timeout")]
     Timeout,
+    /// The TCP connection could not be established in time.
+    #[error("timed out connecting to {host}")]
+    ConnectTimeout { host: String },
+    /// Hostname resolution failed.
+    #[error("could not resolve {host} — check your network/proxy settings")]
+    Dns { host: String },
+    /// The TLS handshake or certificate validation failed.
+    #[error("TLS failure connecting to {host}: {message}")]
+    Tls { host: String, message: String },
+    /// The configured proxy refused or dropped the connection.
+    #[error("proxy error: {0}")]
+    Proxy(String),
+    /// Connection-level failure that fits none of the categories above.
     #[error("network error: {0}")]
     Network(String),
     #[error("request build error: {0}")]
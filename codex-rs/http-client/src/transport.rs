@@ -74,14 +74,61 @@ impl ReqwestTransport {
     }
 
     fn map_error(err: reqwest::Error) -> TransportError {
+        let host = err.url().and_then(|url| url.host_str()).map(str::to_string);
         if err.is_timeout() {
-            TransportError::Timeout
-        } else {
-            TransportError::Network(err.to_string())
+            return match (err.is_connect(), host) {
+                (true, Some(host)) => TransportError::ConnectTimeout { host },
+                _ => TransportError::Timeout,
+            };
         }
+        classify_network_error(host, error_chain_text(&err))
     }
 }
 
+/// Joins an error with its source chain, since `reqwest::Error`'s `Display`
+/// omits the underlying cause that identifies the failure category.
+fn error_chain_text(err: &dyn std::error::Error) -> String {
+    let mut text = err.to_string();
+    let mut source = err.source();
+    while let Some(cause) = source {
+        text.push_str(": ");
+        text.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    text
+}
+
+/// Buckets a connection-level failure into a diagnosable [`TransportError`]
+/// variant based on the error text, falling back to `Network`.
+fn classify_network_error(host: Option<String>, detail: String) -> TransportError {
+    let lower = detail.to_ascii_lowercase();
+    if lower.contains("dns error")
+        || lower.contains("failed to lookup address")
+        || lower.contains("name or service not known")
+        || lower.contains("no such host")
+    {
+        if let Some(host) = host {
+            return TransportError::Dns { host };
+        }
+    }
+    if lower.contains("tls")
+        || lower.contains("ssl")
+        || lower.contains("certificate")
+        || lower.contains("handshake")
+    {
+        if let Some(host) = host {
+            return TransportError::Tls {
+                host,
+                message: detail,
+            };
+        }
+    }
+    if lower.contains("proxy") {
+        return TransportError::Proxy(detail);
+    }
+    TransportError::Network(detail)
+}
+
 fn request_body_for_trace(req: &Request) -> String {
     match req.body.as_ref() {
         Some(RequestBody::Json(body)) => body.to_string(),
@@ -160,3 +207,51 @@ impl HttpTransport for ReqwestTransport {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn classifies_dns_failures_with_host() {
+        let err = classify_network_error(
+            Some("api.x.ai".to_string()),
+            "error sending request: dns error: failed to lookup address information".to_string(),
+        );
+        assert!(matches!(&err, TransportError::Dns { host } if host == "api.x.ai"));
+        assert_eq!(
+            err.to_string(),
+            "could not resolve api.x.ai — check your network/proxy settings"
+        );
+    }
+
+    #[test]
+    fn classifies_tls_failures_with_host() {
+        let err = classify_network_error(
+            Some("api.x.ai".to_string()),
+            "error sending request: invalid peer certificate: UnknownIssuer".to_string(),
+        );
+        assert!(matches!(&err, TransportError::Tls { host, .. } if host == "api.x.ai"));
+    }
+
+    #[test]
+    fn classifies_proxy_failures() {
+        let err = classify_network_error(
+            None,
+            "error sending request: proxy server unreachable".to_string(),
+        );
+        assert!(matches!(err, TransportError::Proxy(_)));
+    }
+
+    #[test]
+    fn falls_back_to_network_without_category_or_host() {
+        let detail = "error sending request: connection reset by peer".to_string();
+        let err = classify_network_error(None, detail.clone());
+        assert!(matches!(&err, TransportError::Network(msg) if *msg == detail));
+
+        // Category keywords without a host to report still fall back.
+        let err = classify_network_error(None, "dns error: lookup failed".to_string());
+        assert!(matches!(err, TransportError::Network(_)));
+    }
+}
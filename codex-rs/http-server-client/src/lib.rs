@@ -0,0 +1,396 @@
+//! Typed Rust client for the codex HTTP server.
+//!
+//! Wraps the REST and SSE endpoints of `codex-http-server` with typed
+//! methods so integrators stop hand-rolling reqwest calls. The request and
+//! response types are the server's own structs, re-exported here, so the
+//! two crates cannot drift apart. Conversations are started through the job
+//! queue: [`HttpServerClient::create_job`] is the "run this prompt"
+//! primitive, and [`HttpServerClient::events`] streams the job lifecycle.
+
+use std::fmt;
+
+use anyhow::Result;
+use anyhow::anyhow;
+use futures::Stream;
+use futures::StreamExt;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+pub use codex_http_server::AuditEntry;
+pub use codex_http_server::CompleteRequest;
+pub use codex_http_server::ConversationCreated;
+pub use codex_http_server::CreateScheduleRequest;
+pub use codex_http_server::ErrorBody;
+pub use codex_http_server::ErrorCode;
+pub use codex_http_server::InstructionOverrides;
+pub use codex_http_server::Job;
+pub use codex_http_server::JobSpec;
+pub use codex_http_server::JobStatus;
+pub use codex_http_server::NewConversationRequest;
+pub use codex_http_server::PromptTemplate;
+pub use codex_http_server::RunStatus;
+pub use codex_http_server::Schedule;
+pub use codex_http_server::ScheduleRun;
+pub use codex_http_server::ServerEvent;
+
+/// Client for one codex HTTP server instance.
+#[derive(Clone)]
+pub struct HttpServerClient {
+    base_url: String,
+    http: reqwest::Client,
+}
+
+impl HttpServerClient {
+    /// `base_url` is the server root, e.g. `http://127.0.0.1:4420`.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// `POST /jobs`: queues a conversation and returns it in `queued` state.
+    pub async fn create_job(&self, spec: &JobSpec) -> Result<Job> {
+        self.post_json("/jobs", spec).await
+    }
+
+    /// `GET /jobs`
+    pub async fn list_jobs(&self) -> Result<Vec<Job>> {
+        self.get_json("/jobs").await
+    }
+
+    /// `GET /jobs/{id}`
+    pub async fn get_job(&self, id: u64) -> Result<Job> {
+        self.get_json(&format!("/jobs/{id}")).await
+    }
+
+    /// `POST /schedules`
+    pub async fn create_schedule(&self, request: &CreateScheduleRequest) -> Result<Schedule> {
+        self.post_json("/schedules", request).await
+    }
+
+    /// `GET /schedules`
+    pub async fn list_schedules(&self) -> Result<Vec<Schedule>> {
+        self.get_json("/schedules").await
+    }
+
+    /// `DELETE /schedules/{id}`
+    pub async fn delete_schedule(&self, id: u64) -> Result<()> {
+        let response = self
+            .http
+            .delete(self.url(&format!("/schedules/{id}")))
+            .send()
+            .await?;
+        check(response).await?;
+        Ok(())
+    }
+
+    /// `GET /schedules/{id}/runs`
+    pub async fn schedule_runs(&self, id: u64) -> Result<Vec<ScheduleRun>> {
+        self.get_json(&format!("/schedules/{id}/runs")).await
+    }
+
+    /// `GET /templates`
+    pub async fn list_templates(&self) -> Result<Vec<PromptTemplate>> {
+        self.get_json("/templates").await
+    }
+
+    /// `GET /conversations/{id}/export`: the rendered transcript
+    /// (`markdown`, `json`, or `html`).
+    pub async fn export_conversation(&self, id: &str, format: &str) -> Result<String> {
+        let response = self
+            .http
+            .get(self.url(&format!("/conversations/{id}/export")))
+            .query(&[("format", format)])
+            .send()
+            .await?;
+        Ok(check(response).await?.text().await?)
+    }
+
+    /// `POST /conversations/{id}/complete`: runs one turn in the
+    /// conversation and streams the assistant's text as it arrives.
+    pub async fn complete(
+        &self,
+        id: &str,
+        request: &CompleteRequest,
+    ) -> Result<impl Stream<Item = Result<String>>> {
+        let response = self
+            .http
+            .post(self.url(&format!("/conversations/{id}/complete")))
+            .json(request)
+            .send()
+            .await?;
+        let body = check(response).await?.bytes_stream();
+        Ok(body.map(|chunk| {
+            chunk
+                .map(|chunk| String::from_utf8_lossy(&chunk).into_owned())
+                .map_err(anyhow::Error::from)
+        }))
+    }
+
+    /// `POST /conversations`: starts a brand-new conversation, optionally
+    /// overriding its instructions when the server's
+    /// `[http_server.instructions]` policy allows it.
+    pub async fn new_conversation(
+        &self,
+        request: &NewConversationRequest,
+    ) -> Result<ConversationCreated> {
+        self.post_json("/conversations", request).await
+    }
+
+    /// `POST /conversations/{id}/interrupt`: stops the conversation's
+    /// running turn.
+    pub async fn interrupt_conversation(&self, id: &str) -> Result<()> {
+        let response = self
+            .http
+            .post(self.url(&format!("/conversations/{id}/interrupt")))
+            .send()
+            .await?;
+        check(response).await?;
+        Ok(())
+    }
+
+    /// `GET /audit?limit=`: recent mutating API actions, newest first.
+    pub async fn recent_audit(&self, limit: usize) -> Result<Vec<AuditEntry>> {
+        let response = self
+            .http
+            .get(self.url("/audit"))
+            .query(&[("limit", limit)])
+            .send()
+            .await?;
+        Ok(check(response).await?.json().await?)
+    }
+
+    /// `GET /events`: the server's event stream (`job.queued`, `job.done`,
+    /// …) as a stream of [`ServerEvent`]s. The stream stays open until
+    /// dropped or the server goes away.
+    pub async fn events(&self) -> Result<impl Stream<Item = Result<ServerEvent>>> {
+        let response = self.http.get(self.url("/events")).send().await?;
+        Ok(sse_events(check(response).await?))
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.base_url)
+    }
+
+    async fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let response = self.http.get(self.url(path)).send().await?;
+        Ok(check(response).await?.json().await?)
+    }
+
+    async fn post_json<T: DeserializeOwned>(&self, path: &str, body: &impl Serialize) -> Result<T> {
+        let response = self.http.post(self.url(path)).json(body).send().await?;
+        Ok(check(response).await?.json().await?)
+    }
+}
+
+/// A non-2xx response. `body` is the server's structured error, so callers
+/// can branch on [`ErrorBody::code`] instead of matching on the message;
+/// it is `None` only when the response body was not an [`ErrorBody`] (e.g.
+/// a rejection produced by the HTTP stack before a handler ran).
+#[derive(Debug)]
+pub struct ApiFailure {
+    pub status: u16,
+    pub body: Option<ErrorBody>,
+    pub message: String,
+}
+
+impl fmt::Display for ApiFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.body {
+            Some(body) => write!(
+                f,
+                "server returned {} ({}): {}",
+                self.status,
+                body.code.as_str(),
+                self.message
+            ),
+            None => write!(f, "server returned {}: {}", self.status, self.message),
+        }
+    }
+}
+
+impl std::error::Error for ApiFailure {}
+
+/// Turns a non-success response into an [`ApiFailure`].
+async fn check(response: reqwest::Response) -> Result<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let raw = response.text().await.unwrap_or_default();
+    let body = serde_json::from_str::<ErrorBody>(&raw).ok();
+    let message = body
+        .as_ref()
+        .map(|body| body.message.clone())
+        .unwrap_or(raw);
+    Err(anyhow::Error::new(ApiFailure {
+        status: status.as_u16(),
+        body,
+        message,
+    }))
+}
+
+/// Reassembles the response body's SSE frames into [`ServerEvent`]s.
+fn sse_events(response: reqwest::Response) -> impl Stream<Item = Result<ServerEvent>> {
+    let body = Box::pin(response.bytes_stream());
+    futures::stream::unfold((body, Vec::new()), |(mut body, mut buffer)| async move {
+        loop {
+            // Split on the byte level: a chunk boundary may fall inside a
+            // UTF-8 character, but never inside the `\n\n` frame separator.
+            if let Some(end) = buffer.windows(2).position(|window| window == b"\n\n") {
+                let block = String::from_utf8_lossy(&buffer[..end]).into_owned();
+                buffer.drain(..end + 2);
+                if let Some(event) = parse_sse_block(&block) {
+                    return Some((event, (body, buffer)));
+                }
+                continue;
+            }
+            match body.next().await? {
+                Ok(chunk) => buffer.extend_from_slice(&chunk),
+                Err(err) => return Some((Err(err.into()), (body, buffer))),
+            }
+        }
+    })
+}
+
+/// One SSE frame as a [`ServerEvent`]; `None` for keep-alive comments and
+/// frames without an event name or data.
+fn parse_sse_block(block: &str) -> Option<Result<ServerEvent>> {
+    let mut kind = None;
+    let mut data = String::new();
+    for line in block.lines() {
+        if let Some(rest) = line.strip_prefix("event:") {
+            kind = Some(rest.trim_start().to_string());
+        } else if let Some(rest) = line.strip_prefix("data:") {
+            if !data.is_empty() {
+                data.push('\n');
+            }
+            data.push_str(rest.strip_prefix(' ').unwrap_or(rest));
+        }
+    }
+    let kind = kind?;
+    if data.is_empty() {
+        return None;
+    }
+    Some(
+        serde_json::from_str(&data)
+            .map(|payload| ServerEvent { kind, payload })
+            .map_err(|err| anyhow!("malformed payload for event {kind}: {err}")),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_config::types::HttpSandboxLimitsToml;
+    use codex_http_server::ServerConfig;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    /// Serves a real server over an ephemeral port with no job workers, so
+    /// queued jobs stay queued.
+    async fn start_server() -> (tempfile::TempDir, HttpServerClient) {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind");
+        let addr = listener.local_addr().expect("local addr");
+        let config = ServerConfig {
+            codex_home: codex_home.path().to_path_buf(),
+            schedules: Vec::new(),
+            job_workers: 0,
+            codex_bin: PathBuf::from("codex"),
+            github_token: None,
+            templates: Vec::new(),
+            event_bus: None,
+            sandbox_limits: HttpSandboxLimitsToml::default(),
+            model_providers: HashMap::new(),
+            max_turn_seconds: None,
+            max_concurrent_turns: None,
+            archive: None,
+            notify: None,
+            limits: None,
+            images: None,
+            chatops: None,
+            quota: None,
+            redaction: None,
+            instructions: None,
+        };
+        tokio::spawn(async move {
+            let _ = codex_http_server::serve(listener, config).await;
+        });
+        (codex_home, HttpServerClient::new(format!("http://{addr}")))
+    }
+
+    #[tokio::test]
+    async fn jobs_roundtrip_through_the_api() {
+        let (_codex_home, client) = start_server().await;
+        let created = client
+            .create_job(&JobSpec {
+                prompt: "triage the failing build".to_string(),
+                ..JobSpec::default()
+            })
+            .await
+            .expect("create job");
+        assert_eq!(created.status, JobStatus::Queued);
+
+        let jobs = client.list_jobs().await.expect("list jobs");
+        assert_eq!(jobs.len(), 1);
+        let fetched = client.get_job(created.id).await.expect("get job");
+        assert_eq!(fetched.spec.prompt, "triage the failing build");
+
+        let err = client.get_job(99).await.expect_err("unknown job");
+        assert!(err.to_string().contains("404"), "unexpected error: {err}");
+    }
+
+    #[tokio::test]
+    async fn error_bodies_carry_stable_codes() {
+        let (_codex_home, client) = start_server().await;
+        let err = client.get_job(99).await.expect_err("unknown job");
+        let failure = err.downcast_ref::<ApiFailure>().expect("api failure");
+        assert_eq!(failure.status, 404);
+        let body = failure.body.as_ref().expect("structured body");
+        assert_eq!(body.code, ErrorCode::NotFound);
+        assert!(!body.retryable);
+    }
+
+    #[tokio::test]
+    async fn events_stream_reports_queued_jobs() {
+        let (_codex_home, client) = start_server().await;
+        let mut events = Box::pin(client.events().await.expect("open event stream"));
+        let job = client
+            .create_job(&JobSpec {
+                prompt: "fix flaky tests".to_string(),
+                ..JobSpec::default()
+            })
+            .await
+            .expect("create job");
+
+        let event = tokio::time::timeout(Duration::from_secs(5), events.next())
+            .await
+            .expect("event within timeout")
+            .expect("stream open")
+            .expect("well-formed event");
+        assert_eq!(event.kind, "job.queued");
+        assert_eq!(event.payload["id"], serde_json::json!(job.id));
+    }
+
+    #[test]
+    fn sse_blocks_parse_into_events() {
+        let event = parse_sse_block("event: job.done\ndata: {\"id\":1}")
+            .expect("an event")
+            .expect("well-formed");
+        assert_eq!(event.kind, "job.done");
+        assert_eq!(event.payload, serde_json::json!({"id": 1}));
+
+        assert!(parse_sse_block(": keep-alive").is_none());
+        assert!(
+            parse_sse_block("event: job.done\ndata: not json")
+                .expect("an event")
+                .is_err()
+        );
+    }
+}
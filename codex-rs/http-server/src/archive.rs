@@ -0,0 +1,548 @@
+//! Background archival of idle conversations to S3-compatible storage and
+//! the `/conversations/{id}/archive` route.
+//!
+//! Rollout files grow without bound under `CODEX_HOME`; the archiver
+//! periodically uploads conversations whose rollouts have gone unmodified
+//! for a configurable idle window — the rollout itself plus the artifacts
+//! its patches produced — to the bucket configured under
+//! `[http_server.archive]`, so long-term history lives in object storage
+//! instead of the server disk. `GET /conversations/{id}/archive` returns
+//! time-limited signed URLs for the uploaded objects. Requests are signed
+//! with SigV4 directly, the same way the event bus speaks RESP directly,
+//! so MinIO, R2, and AWS S3 all work without an SDK dependency.
+
+use std::collections::HashSet;
+use std::path::Path as FsPath;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use axum::Json;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use chrono::DateTime;
+use chrono::TimeDelta;
+use chrono::Utc;
+use codex_config::types::HttpArchiveToml;
+use codex_core::export::load_conversation_export;
+use codex_rollout::SESSIONS_SUBDIR;
+use hmac::Mac;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+use tracing::info;
+use tracing::warn;
+
+use crate::AppState;
+use crate::artifacts;
+use crate::error::ApiError;
+use crate::events::ServerEvent;
+use crate::storage::audit;
+
+const DEFAULT_PREFIX: &str = "codex";
+const DEFAULT_REGION: &str = "us-east-1";
+const DEFAULT_SCAN_INTERVAL: Duration = Duration::from_secs(300);
+const DEFAULT_IDLE: Duration = Duration::from_secs(3600);
+
+/// How long a signed URL from `GET /conversations/{id}/archive` stays valid.
+const SIGNED_URL_TTL: Duration = Duration::from_secs(900);
+
+/// One archived conversation: the object keys its rollout and artifacts
+/// were uploaded under.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ConversationArchive {
+    pub conversation_id: String,
+    /// Uploaded object keys, the rollout first.
+    pub keys: Vec<String>,
+    pub archived_at: DateTime<Utc>,
+}
+
+/// Uploads conversations to one bucket and signs URLs for what it uploaded.
+pub(crate) struct Archiver {
+    bucket: String,
+    prefix: String,
+    /// Endpoint origin without a trailing slash, e.g. `http://minio:9000`.
+    endpoint: String,
+    /// Authority part of `endpoint`; SigV4 signs it as the `host` header.
+    host: String,
+    region: String,
+    access_key_id: String,
+    secret_access_key: String,
+    pub(crate) scan_interval: Duration,
+    idle: Duration,
+    http: reqwest::Client,
+}
+
+impl Archiver {
+    pub(crate) fn from_toml(toml: &HttpArchiveToml) -> Result<Self, String> {
+        if toml.bucket.is_empty() {
+            return Err("http_server.archive.bucket must not be empty".to_string());
+        }
+        let access_key_id = toml
+            .access_key_id
+            .clone()
+            .or_else(|| std::env::var("AWS_ACCESS_KEY_ID").ok())
+            .ok_or_else(|| {
+                "no archive access key; set http_server.archive.access_key_id or AWS_ACCESS_KEY_ID"
+                    .to_string()
+            })?;
+        let secret_access_key = toml
+            .secret_access_key
+            .clone()
+            .or_else(|| std::env::var("AWS_SECRET_ACCESS_KEY").ok())
+            .ok_or_else(|| {
+                "no archive secret key; set http_server.archive.secret_access_key or \
+                 AWS_SECRET_ACCESS_KEY"
+                    .to_string()
+            })?;
+        let region = toml
+            .region
+            .clone()
+            .unwrap_or_else(|| DEFAULT_REGION.to_string());
+        let endpoint = toml
+            .endpoint
+            .clone()
+            .unwrap_or_else(|| format!("https://s3.{region}.amazonaws.com"))
+            .trim_end_matches('/')
+            .to_string();
+        let host = endpoint_host(&endpoint).ok_or_else(|| {
+            format!("unsupported archive endpoint {endpoint}; expected http(s)://host[:port]")
+        })?;
+        Ok(Self {
+            bucket: toml.bucket.clone(),
+            prefix: toml
+                .prefix
+                .clone()
+                .unwrap_or_else(|| DEFAULT_PREFIX.to_string()),
+            endpoint,
+            host,
+            region,
+            access_key_id,
+            secret_access_key,
+            scan_interval: toml
+                .scan_interval_seconds
+                .map_or(DEFAULT_SCAN_INTERVAL, Duration::from_secs),
+            idle: toml.idle_seconds.map_or(DEFAULT_IDLE, Duration::from_secs),
+            http: reqwest::Client::new(),
+        })
+    }
+
+    /// Archives every idle, not-yet-archived conversation, logging per-
+    /// conversation failures so one bad upload doesn't stall the rest.
+    pub(crate) async fn archive_idle(&self, state: &AppState) {
+        let archived: HashSet<String> = match state.storage.load_archives().await {
+            Ok(archives) => archives
+                .into_iter()
+                .map(|archive| archive.conversation_id)
+                .collect(),
+            Err(err) => {
+                warn!("failed to load archived conversations: {err}");
+                return;
+            }
+        };
+        for (id, rollout) in idle_rollouts(&state.codex_home.join(SESSIONS_SUBDIR), self.idle) {
+            if archived.contains(&id) {
+                continue;
+            }
+            match self.archive_conversation(state, &id, &rollout).await {
+                Ok(archive) => {
+                    info!(
+                        "archived conversation {id} ({} objects)",
+                        archive.keys.len()
+                    );
+                    audit(
+                        &*state.storage,
+                        "archive.create",
+                        &format!("conversation {id}"),
+                    )
+                    .await;
+                    state
+                        .events
+                        .publish(ServerEvent {
+                            kind: "conversation.archived".to_string(),
+                            payload: serde_json::json!({
+                                "conversation_id": id,
+                                "keys": archive.keys,
+                            }),
+                        })
+                        .await;
+                }
+                Err(err) => warn!("failed to archive conversation {id}: {err}"),
+            }
+        }
+    }
+
+    /// Uploads the rollout and the conversation's surviving artifacts, then
+    /// records the archive so the conversation is not uploaded again.
+    async fn archive_conversation(
+        &self,
+        state: &AppState,
+        id: &str,
+        rollout: &FsPath,
+    ) -> anyhow::Result<ConversationArchive> {
+        let bytes = tokio::fs::read(rollout).await?;
+        let rollout_key = format!("{}/{id}/rollout.jsonl", self.prefix);
+        self.upload(&rollout_key, bytes, "application/json").await?;
+        let mut keys = vec![rollout_key];
+        if let Ok(Some(export)) = load_conversation_export(&state.codex_home, id).await
+            && let Some(cwd) = export.cwd.clone().map(PathBuf::from)
+        {
+            for path in artifacts::artifact_relative_paths(&export) {
+                let full_path = cwd.join(&path);
+                let Ok(metadata) = std::fs::metadata(&full_path) else {
+                    continue;
+                };
+                if !metadata.is_file() || metadata.len() > artifacts::MAX_ARTIFACT_BYTES {
+                    continue;
+                }
+                let Ok(bytes) = std::fs::read(&full_path) else {
+                    continue;
+                };
+                let key = format!("{}/{id}/artifacts/{path}", self.prefix);
+                self.upload(&key, bytes, artifacts::content_type_for(&path))
+                    .await?;
+                keys.push(key);
+            }
+        }
+        let archive = ConversationArchive {
+            conversation_id: id.to_string(),
+            keys,
+            archived_at: Utc::now(),
+        };
+        state.storage.save_archive(&archive).await?;
+        Ok(archive)
+    }
+
+    /// `PUT`s one object with SigV4 header signing.
+    async fn upload(
+        &self,
+        key: &str,
+        body: Vec<u8>,
+        content_type: &'static str,
+    ) -> anyhow::Result<()> {
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let scope = format!("{date}/{}/s3/aws4_request", self.region);
+        let canonical_uri = self.canonical_uri(key);
+        let payload_hash = sha256_hex(&body);
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+        let canonical_request = format!(
+            "PUT\n{canonical_uri}\n\nhost:{}\nx-amz-content-sha256:{payload_hash}\n\
+             x-amz-date:{amz_date}\n\n{signed_headers}\n{payload_hash}",
+            self.host
+        );
+        let signature = self.sign(&date, &scope, &amz_date, &canonical_request);
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={signed_headers}, \
+             Signature={signature}",
+            self.access_key_id
+        );
+        let response = self
+            .http
+            .put(format!("{}{canonical_uri}", self.endpoint))
+            .header("authorization", authorization)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("x-amz-date", amz_date)
+            .header("content-type", content_type)
+            .body(body)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!("upload of {key} failed with status {}", response.status());
+        }
+        Ok(())
+    }
+
+    /// A time-limited signed `GET` URL for one uploaded object (SigV4
+    /// query-string signing with an unsigned payload).
+    pub(crate) fn presign_get(&self, key: &str, now: DateTime<Utc>) -> String {
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let scope = format!("{date}/{}/s3/aws4_request", self.region);
+        let canonical_uri = self.canonical_uri(key);
+        let credential = uri_encode(&format!("{}/{scope}", self.access_key_id), false);
+        let canonical_query = format!(
+            "X-Amz-Algorithm=AWS4-HMAC-SHA256&X-Amz-Credential={credential}\
+             &X-Amz-Date={amz_date}&X-Amz-Expires={}&X-Amz-SignedHeaders=host",
+            SIGNED_URL_TTL.as_secs()
+        );
+        let canonical_request = format!(
+            "GET\n{canonical_uri}\n{canonical_query}\nhost:{}\n\nhost\nUNSIGNED-PAYLOAD",
+            self.host
+        );
+        let signature = self.sign(&date, &scope, &amz_date, &canonical_request);
+        format!(
+            "{}{canonical_uri}?{canonical_query}&X-Amz-Signature={signature}",
+            self.endpoint
+        )
+    }
+
+    /// Path-style canonical URI for `key`: `/bucket/prefixed-key`.
+    fn canonical_uri(&self, key: &str) -> String {
+        format!(
+            "/{}/{}",
+            uri_encode(&self.bucket, false),
+            uri_encode(key, true)
+        )
+    }
+
+    /// SigV4 signature over `canonical_request`.
+    fn sign(&self, date: &str, scope: &str, amz_date: &str, canonical_request: &str) -> String {
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+            sha256_hex(canonical_request.as_bytes())
+        );
+        let mut key = hmac_sha256(
+            format!("AWS4{}", self.secret_access_key).as_bytes(),
+            date.as_bytes(),
+        );
+        for part in [self.region.as_str(), "s3", "aws4_request"] {
+            key = hmac_sha256(&key, part.as_bytes());
+        }
+        hex(&hmac_sha256(&key, string_to_sign.as_bytes()))
+    }
+}
+
+/// Periodically archives idle conversations; returns immediately when no
+/// archiver is configured.
+pub(crate) async fn run_loop(state: AppState) {
+    let Some(archiver) = state.archiver.clone() else {
+        return;
+    };
+    loop {
+        archiver.archive_idle(&state).await;
+        tokio::time::sleep(archiver.scan_interval).await;
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct SignedObject {
+    pub key: String,
+    pub url: String,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ArchiveUrls {
+    pub conversation_id: String,
+    pub archived_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub urls: Vec<SignedObject>,
+}
+
+/// `GET /conversations/{id}/archive`
+///
+/// Returns time-limited signed URLs for the conversation's archived
+/// objects.
+pub(crate) async fn get_archive(State(state): State<AppState>, Path(id): Path<String>) -> Response {
+    let Some(archiver) = state.archiver.clone() else {
+        return ApiError::invalid_state("archiving is not configured under [http_server.archive]")
+            .into_response();
+    };
+    let archives = match state.storage.load_archives().await {
+        Ok(archives) => archives,
+        Err(err) => {
+            return ApiError::internal(format!("failed to load archives: {err}")).into_response();
+        }
+    };
+    let Some(archive) = archives
+        .into_iter()
+        .find(|archive| archive.conversation_id == id)
+    else {
+        return ApiError::not_found(format!("conversation {id} has not been archived"))
+            .into_response();
+    };
+    let now = Utc::now();
+    let urls = archive
+        .keys
+        .iter()
+        .map(|key| SignedObject {
+            key: key.clone(),
+            url: archiver.presign_get(key, now),
+        })
+        .collect();
+    Json(ArchiveUrls {
+        conversation_id: archive.conversation_id,
+        archived_at: archive.archived_at,
+        expires_at: now + TimeDelta::seconds(SIGNED_URL_TTL.as_secs() as i64),
+        urls,
+    })
+    .into_response()
+}
+
+/// Rollout files under `sessions` whose last modification is at least
+/// `idle` ago, as `(conversation_id, path)` pairs.
+fn idle_rollouts(sessions: &FsPath, idle: Duration) -> Vec<(String, PathBuf)> {
+    let mut found = Vec::new();
+    let mut dirs = vec![sessions.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                dirs.push(path);
+                continue;
+            }
+            let Some(id) = conversation_id_from_filename(&path) else {
+                continue;
+            };
+            let idle_enough = entry
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .is_some_and(|elapsed| elapsed >= idle);
+            if idle_enough {
+                found.push((id, path));
+            }
+        }
+    }
+    found
+}
+
+/// `rollout-{timestamp}-{uuid}.jsonl` → the uuid; anything else is not a
+/// rollout file.
+fn conversation_id_from_filename(path: &FsPath) -> Option<String> {
+    let name = path.file_name()?.to_str()?;
+    let stem = name.strip_prefix("rollout-")?.strip_suffix(".jsonl")?;
+    let candidate = stem.get(stem.len().checked_sub(36)?..)?;
+    uuid::Uuid::parse_str(candidate).ok()?;
+    Some(candidate.to_string())
+}
+
+/// Authority part of an `http(s)://` endpoint.
+fn endpoint_host(endpoint: &str) -> Option<String> {
+    let rest = endpoint
+        .strip_prefix("https://")
+        .or_else(|| endpoint.strip_prefix("http://"))?;
+    let authority = rest.split('/').next()?;
+    if authority.is_empty() {
+        None
+    } else {
+        Some(authority.to_string())
+    }
+}
+
+/// SigV4 URI encoding: unreserved characters pass through, everything else
+/// is percent-encoded; `/` is kept when encoding object keys.
+fn uri_encode(input: &str, keep_slash: bool) -> String {
+    let mut out = String::new();
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                out.push(byte as char);
+            }
+            b'/' if keep_slash => out.push('/'),
+            _ => out.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    out
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    let mut hasher = sha2::Sha256::new();
+    hasher.update(data);
+    hex(&hasher.finalize())
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let Ok(mut mac) = hmac::Hmac::<sha2::Sha256>::new_from_slice(key) else {
+        // HMAC accepts keys of any length, so this cannot fail.
+        return Vec::new();
+    };
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_state;
+    use axum::http::StatusCode;
+    use pretty_assertions::assert_eq;
+
+    fn archiver() -> Archiver {
+        Archiver::from_toml(&HttpArchiveToml {
+            bucket: "codex-archive".to_string(),
+            endpoint: Some("http://minio.internal:9000".to_string()),
+            access_key_id: Some("AKIDEXAMPLE".to_string()),
+            secret_access_key: Some("secret".to_string()),
+            ..HttpArchiveToml::default()
+        })
+        .expect("build archiver")
+    }
+
+    #[test]
+    fn presigned_urls_carry_a_deterministic_sigv4_signature() {
+        let archiver = archiver();
+        let now = DateTime::parse_from_rfc3339("2026-08-27T10:00:00Z")
+            .expect("parse time")
+            .with_timezone(&Utc);
+        let url = archiver.presign_get("codex/t-1/rollout.jsonl", now);
+        assert!(url.starts_with(
+            "http://minio.internal:9000/codex-archive/codex/t-1/rollout.jsonl\
+             ?X-Amz-Algorithm=AWS4-HMAC-SHA256"
+        ));
+        assert!(
+            url.contains("X-Amz-Credential=AKIDEXAMPLE%2F20260827%2Fus-east-1%2Fs3%2Faws4_request")
+        );
+        assert!(url.contains("X-Amz-Date=20260827T100000Z"));
+
+        let signature = url
+            .split("X-Amz-Signature=")
+            .nth(1)
+            .expect("signature parameter");
+        assert_eq!(signature.len(), 64);
+        assert!(signature.chars().all(|c| c.is_ascii_hexdigit()));
+        // Same inputs sign identically; a different key does not.
+        assert_eq!(url, archiver.presign_get("codex/t-1/rollout.jsonl", now));
+        assert_ne!(url, archiver.presign_get("codex/t-2/rollout.jsonl", now));
+    }
+
+    #[test]
+    fn uri_encoding_matches_the_sigv4_charset() {
+        assert_eq!(
+            uri_encode("codex/t-1/artifacts/report 1.md", true),
+            "codex/t-1/artifacts/report%201.md"
+        );
+        assert_eq!(uri_encode("a/b", false), "a%2Fb");
+        assert_eq!(uri_encode("safe-chars_.~", false), "safe-chars_.~");
+    }
+
+    #[test]
+    fn conversation_ids_come_from_rollout_filenames() {
+        let id = "0199a213-81ba-7142-ba53-6b2ebc1b3a5a";
+        assert_eq!(
+            conversation_id_from_filename(FsPath::new(&format!(
+                "sessions/2026/08/27/rollout-2026-08-27T10-00-00-{id}.jsonl"
+            ))),
+            Some(id.to_string())
+        );
+        assert_eq!(
+            conversation_id_from_filename(FsPath::new("sessions/2026/08/27/notes.jsonl")),
+            None
+        );
+        assert_eq!(
+            conversation_id_from_filename(FsPath::new("rollout.txt")),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn archive_urls_without_configured_archiver_are_invalid_state() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let response = get_archive(
+            State(test_state(codex_home.path()).await),
+            Path("0199a213-81ba-7142-ba53-6b2ebc1b3a5a".to_string()),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+}
@@ -0,0 +1,247 @@
+//! Handlers for the `/conversations/{id}/artifacts` routes.
+//!
+//! Artifacts are the files a conversation created or modified, derived from
+//! the `apply_patch` calls in its transcript and resolved against the
+//! conversation's recorded working directory. Downloads are limited to
+//! tracked paths so transcripts can't be used to read arbitrary files.
+
+use std::collections::BTreeSet;
+use std::path::Component;
+use std::path::Path as FsPath;
+use std::path::PathBuf;
+
+use axum::Json;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::http::header::CONTENT_TYPE;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use codex_core::export::ConversationExport;
+use codex_core::export::TranscriptEntryKind;
+use codex_core::export::load_conversation_export;
+use serde::Serialize;
+
+use crate::AppState;
+use crate::error::ApiError;
+
+/// Artifacts larger than this are refused rather than streamed.
+pub(crate) const MAX_ARTIFACT_BYTES: u64 = 10 * 1024 * 1024;
+
+#[derive(Debug, Serialize)]
+pub(crate) struct Artifact {
+    pub path: String,
+    pub size_bytes: u64,
+    pub content_type: &'static str,
+}
+
+/// `GET /conversations/{id}/artifacts`
+///
+/// Lists the files the conversation created or modified that still exist in
+/// its working directory.
+pub(crate) async fn list_artifacts(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Response {
+    let (export, cwd) = match conversation_cwd(&state, &id).await {
+        Ok(loaded) => loaded,
+        Err(response) => return response,
+    };
+    let artifacts: Vec<Artifact> = artifact_relative_paths(&export)
+        .into_iter()
+        .filter_map(|path| {
+            let metadata = std::fs::metadata(cwd.join(&path)).ok()?;
+            if !metadata.is_file() {
+                return None;
+            }
+            Some(Artifact {
+                content_type: content_type_for(&path),
+                size_bytes: metadata.len(),
+                path,
+            })
+        })
+        .collect();
+    Json(artifacts).into_response()
+}
+
+/// `GET /conversations/{id}/artifacts/{*path}`
+///
+/// Downloads one tracked artifact with content-type detection.
+pub(crate) async fn download_artifact(
+    State(state): State<AppState>,
+    Path((id, path)): Path<(String, String)>,
+) -> Response {
+    let (export, cwd) = match conversation_cwd(&state, &id).await {
+        Ok(loaded) => loaded,
+        Err(response) => return response,
+    };
+    if !artifact_relative_paths(&export).contains(&path) {
+        return ApiError::not_found(format!("conversation {id} has no artifact at {path}"))
+            .into_response();
+    }
+    let full_path = cwd.join(&path);
+    let metadata = match std::fs::metadata(&full_path) {
+        Ok(metadata) if metadata.is_file() => metadata,
+        _ => {
+            return ApiError::not_found(format!("artifact {path} no longer exists"))
+                .into_response();
+        }
+    };
+    if metadata.len() > MAX_ARTIFACT_BYTES {
+        return ApiError::payload_too_large(format!(
+            "artifact {path} exceeds the {MAX_ARTIFACT_BYTES} byte limit"
+        ))
+        .into_response();
+    }
+    match std::fs::read(&full_path) {
+        Ok(bytes) => ([(CONTENT_TYPE, content_type_for(&path))], bytes).into_response(),
+        Err(err) => ApiError::internal(format!("failed to read artifact: {err}")).into_response(),
+    }
+}
+
+/// Loads the conversation and its recorded working directory, mapping the
+/// failure modes to HTTP responses.
+async fn conversation_cwd(
+    state: &AppState,
+    id: &str,
+) -> Result<(ConversationExport, PathBuf), Response> {
+    let export = match load_conversation_export(&state.codex_home, id).await {
+        Ok(Some(export)) => export,
+        Ok(None) => {
+            return Err(
+                ApiError::not_found(format!("no conversation with id {id}")).into_response()
+            );
+        }
+        Err(err) => {
+            return Err(
+                ApiError::internal(format!("failed to load conversation: {err}")).into_response(),
+            );
+        }
+    };
+    let Some(cwd) = export.cwd.clone() else {
+        return Err(ApiError::invalid_state(format!(
+            "conversation {id} has no recorded working directory"
+        ))
+        .into_response());
+    };
+    Ok((export, PathBuf::from(cwd)))
+}
+
+/// Relative paths the conversation's patches added or updated, deduplicated
+/// and with unsafe (absolute or parent-escaping) paths dropped.
+pub(crate) fn artifact_relative_paths(export: &ConversationExport) -> BTreeSet<String> {
+    let mut paths = BTreeSet::new();
+    for entry in &export.entries {
+        if entry.kind != TranscriptEntryKind::Diff {
+            continue;
+        }
+        for line in entry.body.lines() {
+            let path = line
+                .strip_prefix("*** Add File: ")
+                .or_else(|| line.strip_prefix("*** Update File: "));
+            if let Some(path) = path.map(str::trim)
+                && !path.is_empty()
+                && is_safe_relative_path(path)
+            {
+                paths.insert(path.to_string());
+            }
+        }
+    }
+    paths
+}
+
+/// Rejects absolute paths and any path that escapes the working directory.
+fn is_safe_relative_path(path: &str) -> bool {
+    let path = FsPath::new(path);
+    !path.is_absolute()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+/// Content type by file extension; unknown extensions download as bytes.
+pub(crate) fn content_type_for(path: &str) -> &'static str {
+    let extension = FsPath::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match extension.as_str() {
+        "css" => "text/css; charset=utf-8",
+        "csv" => "text/csv; charset=utf-8",
+        "gif" => "image/gif",
+        "htm" | "html" => "text/html; charset=utf-8",
+        "jpeg" | "jpg" => "image/jpeg",
+        "js" => "text/javascript; charset=utf-8",
+        "json" => "application/json",
+        "md" => "text/markdown; charset=utf-8",
+        "pdf" => "application/pdf",
+        "png" => "image/png",
+        "svg" => "image/svg+xml",
+        "txt" => "text/plain; charset=utf-8",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_state;
+    use axum::http::StatusCode;
+    use codex_core::export::TranscriptEntry;
+    use pretty_assertions::assert_eq;
+
+    fn export_with_patch(patch: &str) -> ConversationExport {
+        ConversationExport {
+            id: "t-1".to_string(),
+            timestamp: None,
+            cwd: None,
+            entries: vec![TranscriptEntry {
+                kind: TranscriptEntryKind::Diff,
+                heading: "Patch".to_string(),
+                body: patch.to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn paths_come_from_add_and_update_markers() {
+        let export = export_with_patch(
+            "*** Begin Patch\n*** Add File: report.md\n+hi\n*** Update File: src/lib.rs\n*** Delete File: old.rs\n*** End Patch",
+        );
+        let paths: Vec<String> = artifact_relative_paths(&export).into_iter().collect();
+        assert_eq!(
+            paths,
+            vec!["report.md".to_string(), "src/lib.rs".to_string()]
+        );
+    }
+
+    #[test]
+    fn escaping_paths_are_dropped() {
+        let export = export_with_patch(
+            "*** Add File: ../outside.txt\n*** Add File: /etc/passwd\n*** Add File: ok.txt",
+        );
+        let paths: Vec<String> = artifact_relative_paths(&export).into_iter().collect();
+        assert_eq!(paths, vec!["ok.txt".to_string()]);
+    }
+
+    #[test]
+    fn content_types_are_detected_by_extension() {
+        assert_eq!(content_type_for("chart.png"), "image/png");
+        assert_eq!(
+            content_type_for("report.MD"),
+            "text/markdown; charset=utf-8"
+        );
+        assert_eq!(content_type_for("blob.bin"), "application/octet-stream");
+    }
+
+    #[tokio::test]
+    async fn unknown_conversation_returns_not_found() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let response = list_artifacts(
+            State(test_state(codex_home.path()).await),
+            Path("0199a213-81ba-7142-ba53-6b2ebc1b3a5a".to_string()),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,56 @@
+//! Handler for the `/audit` route.
+
+use axum::Json;
+use axum::extract::Query;
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use serde::Deserialize;
+
+use crate::AppState;
+use crate::error::ApiError;
+
+const MAX_ENTRIES: usize = 500;
+const DEFAULT_ENTRIES: usize = 50;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct AuditQuery {
+    limit: Option<usize>,
+}
+
+/// `GET /audit?limit=`
+///
+/// Recent mutating API actions, newest first.
+pub(crate) async fn recent_audit(
+    State(state): State<AppState>,
+    Query(query): Query<AuditQuery>,
+) -> Response {
+    let limit = query.limit.unwrap_or(DEFAULT_ENTRIES).clamp(1, MAX_ENTRIES);
+    match state.storage.recent_audit(limit).await {
+        Ok(entries) => Json(entries).into_response(),
+        Err(err) => ApiError::internal(format!("failed to load audit log: {err}")).into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_state;
+    use axum::http::StatusCode;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn mutating_actions_show_up_newest_first() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let state = test_state(codex_home.path()).await;
+        crate::storage::audit(&*state.storage, "job.create", "job 1").await;
+        crate::storage::audit(&*state.storage, "job.create", "job 2").await;
+
+        let response = recent_audit(State(state.clone()), Query(AuditQuery { limit: None })).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let entries = state.storage.recent_audit(10).await.expect("recent audit");
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].detail, "job 2");
+        assert_eq!(entries[1].detail, "job 1");
+    }
+}
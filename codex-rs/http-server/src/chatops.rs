@@ -0,0 +1,462 @@
+//! Slack chat-ops bridge for sandbox grant approvals.
+//!
+//! A remote headless conversation that requests a sandbox grant beyond
+//! `[http_server.sandbox_limits]` stalls until an operator approves it.
+//! When `[http_server.chatops]` configures a Slack app, a background task
+//! watches the event bus for `sandbox.denied` and posts a message with
+//! Approve/Deny buttons; the button click comes back to
+//! `POST /chatops/slack/callback`, is validated against the app's signing
+//! secret, and approving persists the requested grant as the conversation's
+//! sandbox override — the same thing `PATCH /conversations/{id}/sandbox`
+//! would do, minus the terminal.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use axum::extract::State;
+use axum::http::HeaderMap;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use chrono::Utc;
+use codex_config::types::HttpChatopsToml;
+use hmac::Mac;
+use serde::Deserialize;
+use serde_json::json;
+use tracing::warn;
+
+use crate::AppState;
+use crate::error::ApiError;
+use crate::events::ServerEvent;
+use crate::sandbox::SandboxOverride;
+use crate::storage::audit;
+
+/// Seconds of clock skew beyond which a signed callback is rejected; the
+/// window Slack itself recommends against replayed requests.
+const MAX_TIMESTAMP_SKEW_SECONDS: i64 = 300;
+
+const APPROVE_ACTION: &str = "codex_approve";
+const DENY_ACTION: &str = "codex_deny";
+
+/// A grant waiting for an operator's button click.
+#[derive(Debug, Clone)]
+struct PendingApproval {
+    conversation_id: String,
+    requested: SandboxOverride,
+}
+
+/// Posts approval messages and resolves the button clicks they come back
+/// with.
+pub(crate) struct Chatops {
+    bot_token: String,
+    channel: String,
+    signing_secret: String,
+    /// Grants posted to Slack and not yet approved or denied, by the id
+    /// carried in the buttons' `value`.
+    pending: Mutex<HashMap<String, PendingApproval>>,
+    http: reqwest::Client,
+}
+
+impl Chatops {
+    pub(crate) fn from_toml(toml: &HttpChatopsToml) -> Result<Self, String> {
+        let (Some(bot_token), Some(channel), Some(signing_secret)) = (
+            toml.slack_bot_token.clone(),
+            toml.channel.clone(),
+            toml.signing_secret.clone(),
+        ) else {
+            return Err(
+                "http_server.chatops needs slack_bot_token, channel, and signing_secret"
+                    .to_string(),
+            );
+        };
+        Ok(Self {
+            bot_token,
+            channel,
+            signing_secret,
+            pending: Mutex::new(HashMap::new()),
+            http: reqwest::Client::new(),
+        })
+    }
+
+    fn pending(&self) -> std::sync::MutexGuard<'_, HashMap<String, PendingApproval>> {
+        match self.pending.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Registers the denied grant and posts the approval message for it.
+    async fn post_approval(&self, event: &ServerEvent) {
+        let Some(conversation_id) = event
+            .payload
+            .get("conversation_id")
+            .and_then(|id| id.as_str())
+        else {
+            return;
+        };
+        let requested = match event.payload.get("requested") {
+            Some(requested) => match serde_json::from_value(requested.clone()) {
+                Ok(requested) => requested,
+                Err(err) => {
+                    warn!("ignoring sandbox.denied with malformed grant: {err}");
+                    return;
+                }
+            },
+            None => return,
+        };
+        let approval_id = uuid::Uuid::new_v4().to_string();
+        self.pending().insert(
+            approval_id.clone(),
+            PendingApproval {
+                conversation_id: conversation_id.to_string(),
+                requested,
+            },
+        );
+        let text = format!(
+            "Conversation {conversation_id} requests a sandbox grant beyond the server limits"
+        );
+        let body = json!({
+            "channel": self.channel,
+            "text": text,
+            "blocks": [
+                { "type": "section", "text": { "type": "mrkdwn", "text": text } },
+                { "type": "actions", "elements": [
+                    {
+                        "type": "button",
+                        "text": { "type": "plain_text", "text": "Approve" },
+                        "style": "primary",
+                        "action_id": APPROVE_ACTION,
+                        "value": approval_id,
+                    },
+                    {
+                        "type": "button",
+                        "text": { "type": "plain_text", "text": "Deny" },
+                        "style": "danger",
+                        "action_id": DENY_ACTION,
+                        "value": approval_id,
+                    },
+                ] },
+            ],
+        });
+        let result = self
+            .http
+            .post("https://slack.com/api/chat.postMessage")
+            .bearer_auth(&self.bot_token)
+            .json(&body)
+            .send()
+            .await;
+        match result {
+            Ok(response) if !response.status().is_success() => {
+                warn!("slack chat.postMessage returned {}", response.status());
+            }
+            Ok(_) => {}
+            Err(err) => warn!("failed to post slack approval message: {err}"),
+        }
+    }
+
+    /// Validates Slack's `v0=` signature over the raw request body.
+    fn verify_signature(&self, headers: &HeaderMap, body: &str) -> Result<(), ApiError> {
+        let timestamp = header(headers, "x-slack-request-timestamp")?;
+        let skew = timestamp
+            .parse::<i64>()
+            .ok()
+            .map(|timestamp| (Utc::now().timestamp() - timestamp).abs());
+        match skew {
+            Some(skew) if skew <= MAX_TIMESTAMP_SKEW_SECONDS => {}
+            _ => {
+                return Err(ApiError::invalid_request(
+                    "x-slack-request-timestamp is missing, malformed, or stale",
+                ));
+            }
+        }
+        let signature = header(headers, "x-slack-signature")?;
+        let Some(signature) = signature.strip_prefix("v0=").and_then(hex_decode) else {
+            return Err(ApiError::invalid_request(
+                "x-slack-signature is not a v0 hex signature",
+            ));
+        };
+        let key = self.signing_secret.as_bytes();
+        let Ok(mut mac) = hmac::Hmac::<sha2::Sha256>::new_from_slice(key) else {
+            // HMAC accepts keys of any length, so this cannot fail.
+            return Err(ApiError::internal("failed to initialize the signature key"));
+        };
+        mac.update(format!("v0:{timestamp}:{body}").as_bytes());
+        mac.verify_slice(&signature)
+            .map_err(|_| ApiError::sandbox_denied("slack signature does not match"))
+    }
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> Result<&'a str, ApiError> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| ApiError::invalid_request(format!("missing {name} header")))
+}
+
+fn hex_decode(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&hex[index..index + 2], 16).ok())
+        .collect()
+}
+
+/// The fields of Slack's interactive-action payload the callback uses.
+#[derive(Debug, Deserialize)]
+struct ActionPayload {
+    actions: Vec<Action>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Action {
+    action_id: String,
+    value: String,
+}
+
+/// The `payload` field of Slack's form-encoded callback body, decoded.
+fn form_payload(body: &str) -> Option<String> {
+    body.split('&')
+        .find_map(|pair| pair.strip_prefix("payload="))
+        .map(percent_decode)
+}
+
+/// Decodes `application/x-www-form-urlencoded` escapes: `+` is a space and
+/// `%XX` is a byte.
+fn percent_decode(encoded: &str) -> String {
+    let mut bytes = Vec::with_capacity(encoded.len());
+    let mut rest = encoded.bytes();
+    while let Some(byte) = rest.next() {
+        match byte {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let pair = [rest.next(), rest.next()];
+                match pair {
+                    [Some(high), Some(low)] => {
+                        let hex = [high, low];
+                        match u8::from_str_radix(&String::from_utf8_lossy(&hex), 16) {
+                            Ok(decoded) => bytes.push(decoded),
+                            // Malformed escape; keep it verbatim.
+                            Err(_) => bytes.extend_from_slice(&[byte, high, low]),
+                        }
+                    }
+                    _ => bytes.push(byte),
+                }
+            }
+            byte => bytes.push(byte),
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// `POST /chatops/slack/callback`
+///
+/// Slack's interactivity request URL: resolves the Approve/Deny click for a
+/// pending grant. The response text replaces the Slack message.
+pub(crate) async fn slack_callback(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: String,
+) -> Response {
+    let Some(chatops) = state.chatops.clone() else {
+        return ApiError::not_found("chat-ops is not configured on this server").into_response();
+    };
+    if let Err(err) = chatops.verify_signature(&headers, &body) {
+        return err.into_response();
+    }
+    let Some(payload) = form_payload(&body) else {
+        return ApiError::invalid_request("request body has no payload field").into_response();
+    };
+    let payload: ActionPayload = match serde_json::from_str(&payload) {
+        Ok(payload) => payload,
+        Err(err) => {
+            return ApiError::invalid_request(format!("malformed action payload: {err}"))
+                .into_response();
+        }
+    };
+    let Some(action) = payload.actions.first() else {
+        return ApiError::invalid_request("action payload has no actions").into_response();
+    };
+    let Some(pending) = chatops.pending().remove(&action.value) else {
+        return ApiError::not_found("this approval was already resolved or is unknown")
+            .into_response();
+    };
+    let id = pending.conversation_id;
+    match action.action_id.as_str() {
+        APPROVE_ACTION => {
+            let saved = state
+                .storage
+                .save_sandbox_override(&id, &pending.requested)
+                .await;
+            if let Err(err) = saved {
+                return ApiError::internal(format!("failed to save sandbox override: {err}"))
+                    .into_response();
+            }
+            audit(
+                &*state.storage,
+                "chatops.approve",
+                &format!("conversation {id}"),
+            )
+            .await;
+            format!("Approved the sandbox grant for conversation {id}").into_response()
+        }
+        DENY_ACTION => {
+            audit(
+                &*state.storage,
+                "chatops.deny",
+                &format!("conversation {id}"),
+            )
+            .await;
+            format!("Denied the sandbox grant for conversation {id}").into_response()
+        }
+        other => ApiError::invalid_request(format!("unknown action {other}")).into_response(),
+    }
+}
+
+/// Watches the event bus and posts approval messages until the server
+/// shuts down. Spawned from [`crate::serve`]; returns immediately when
+/// chat-ops is not configured.
+pub(crate) async fn run_loop(state: AppState) {
+    let Some(chatops) = state.chatops.clone() else {
+        return;
+    };
+    let mut rx = state.events.subscribe();
+    while let Some(event) = rx.next().await {
+        if event.kind == "sandbox.denied" {
+            chatops.post_approval(&event).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_state;
+    use axum::http::StatusCode;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    fn chatops() -> Chatops {
+        Chatops::from_toml(&HttpChatopsToml {
+            slack_bot_token: Some("xoxb-test".to_string()),
+            channel: Some("#codex".to_string()),
+            signing_secret: Some("secret".to_string()),
+        })
+        .expect("build chatops")
+    }
+
+    /// Slack's signature for `body` at `timestamp`, as the header value.
+    fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+        let mut mac = hmac::Hmac::<sha2::Sha256>::new_from_slice(secret.as_bytes())
+            .expect("hmac accepts any key");
+        mac.update(format!("v0:{timestamp}:{body}").as_bytes());
+        let hex: String = mac
+            .finalize()
+            .into_bytes()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        format!("v0={hex}")
+    }
+
+    fn signed_headers(secret: &str, body: &str) -> HeaderMap {
+        let timestamp = Utc::now().timestamp();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-slack-request-timestamp",
+            timestamp.to_string().parse().expect("header value"),
+        );
+        headers.insert(
+            "x-slack-signature",
+            sign(secret, timestamp, body).parse().expect("header value"),
+        );
+        headers
+    }
+
+    #[test]
+    fn valid_signatures_are_accepted_and_forgeries_are_not() {
+        let chatops = chatops();
+        let body = "payload=%7B%7D";
+        assert!(
+            chatops
+                .verify_signature(&signed_headers("secret", body), body)
+                .is_ok()
+        );
+        assert!(
+            chatops
+                .verify_signature(&signed_headers("wrong-secret", body), body)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn stale_timestamps_are_rejected() {
+        let chatops = chatops();
+        let body = "payload=%7B%7D";
+        let stale = Utc::now().timestamp() - MAX_TIMESTAMP_SKEW_SECONDS - 1;
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-slack-request-timestamp",
+            stale.to_string().parse().expect("header value"),
+        );
+        headers.insert(
+            "x-slack-signature",
+            sign("secret", stale, body).parse().expect("header value"),
+        );
+        assert!(chatops.verify_signature(&headers, body).is_err());
+    }
+
+    #[test]
+    fn form_payload_is_percent_decoded() {
+        let payload = form_payload("token=abc&payload=%7B%22a%22%3A+1%7D").expect("payload field");
+        assert_eq!(payload, "{\"a\": 1}");
+        assert_eq!(form_payload("token=abc"), None);
+    }
+
+    #[tokio::test]
+    async fn approving_persists_the_grant() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let mut state = test_state(codex_home.path()).await;
+        let chatops = Arc::new(chatops());
+        chatops.pending().insert(
+            "approval-1".to_string(),
+            PendingApproval {
+                conversation_id: "abc".to_string(),
+                requested: SandboxOverride {
+                    writable_roots: vec![PathBuf::from("/srv/repos/api")],
+                    network_access: true,
+                },
+            },
+        );
+        state.chatops = Some(chatops);
+
+        let payload = json!({
+            "actions": [{ "action_id": APPROVE_ACTION, "value": "approval-1" }],
+        })
+        .to_string();
+        let body = format!(
+            "payload={}",
+            payload.replace('{', "%7B").replace('}', "%7D")
+        );
+        let headers = signed_headers("secret", &body);
+        let response = slack_callback(State(state.clone()), headers, body).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let saved = state
+            .storage
+            .load_sandbox_override("abc")
+            .await
+            .expect("load override")
+            .expect("override saved");
+        assert!(saved.network_access);
+    }
+
+    #[tokio::test]
+    async fn callback_requires_chatops_to_be_configured() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let state = test_state(codex_home.path()).await;
+        let response = slack_callback(State(state), HeaderMap::new(), String::new()).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,248 @@
+//! Plaintext completion streaming: the `/conversations/{id}/complete` route.
+//!
+//! `/events` and the per-command stream speak SSE; this route is for clients
+//! that just want the answer. The prompt runs one turn in the conversation's
+//! recorded working directory — or its scratch workspace under
+//! `CODEX_HOME/workspaces` when none was recorded (see [`crate::quota`]) —
+//! and the response body is the assistant's text,
+//! streamed as chunked `text/plain` with no SSE or JSON framing, so the
+//! output pipes straight into curl, shell scripts, and legacy systems. Text
+//! arrives message by message as the conversation produces it; `codex exec`
+//! reports no finer-grained deltas. At most one turn runs per conversation;
+//! a second request gets `409` unless it queues or forces an interrupt
+//! (see [`crate::turns`]).
+
+use std::convert::Infallible;
+use std::path::PathBuf;
+
+use axum::Json;
+use axum::body::Body;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::http::HeaderMap;
+use axum::http::header::CONTENT_TYPE;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use chrono::Utc;
+use codex_core::export::load_conversation_export;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::Instrument;
+use tracing::warn;
+
+use crate::AppState;
+use crate::error::ApiError;
+use crate::storage::audit;
+
+/// Chunks buffered before the runner waits on a slow reader.
+const CHANNEL_CAPACITY: usize = 16;
+
+/// Body of `POST /conversations/{id}/complete`; shared with
+/// `codex-http-server-client`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CompleteRequest {
+    pub prompt: String,
+    /// `-c key=value` config overrides for this turn.
+    #[serde(default)]
+    pub config_overrides: Vec<String>,
+    /// Base64 data-URL attachments, preprocessed server-side before the
+    /// turn sees them (see [`crate::images`]).
+    #[serde(default)]
+    pub images: Vec<String>,
+    /// Wait behind a running turn instead of getting `409`.
+    #[serde(default)]
+    pub queue: bool,
+    /// Interrupt a running turn and take the conversation over.
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Everything one turn ran with, recorded so `/retry-last` can replay it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct TurnInput {
+    pub prompt: String,
+    pub config_overrides: Vec<String>,
+    /// Original data-URL attachments; a retry stages them again.
+    pub images: Vec<String>,
+}
+
+/// `POST /conversations/{id}/complete`
+pub(crate) async fn complete_conversation(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    Json(request): Json<CompleteRequest>,
+) -> Response {
+    if request.prompt.trim().is_empty() {
+        return ApiError::invalid_request("prompt must not be empty").into_response();
+    }
+    let export = match load_conversation_export(&state.codex_home, &id).await {
+        Ok(Some(export)) => export,
+        Ok(None) => {
+            return ApiError::not_found(format!("no conversation with id {id}")).into_response();
+        }
+        Err(err) => {
+            return ApiError::internal(format!("failed to load conversation: {err}"))
+                .into_response();
+        }
+    };
+    let cwd = match crate::quota::turn_cwd(&state, &id, export.cwd.map(PathBuf::from)).await {
+        Ok(cwd) => cwd,
+        Err(err) => return err.into_response(),
+    };
+    if request.force && state.active_turns.interrupt(&id).is_some() {
+        audit(
+            &*state.storage,
+            "complete.interrupt",
+            &format!("conversation {id}"),
+        )
+        .await;
+    }
+    if request.queue {
+        state.active_turns.begin_queued(&id, &request.prompt).await;
+    } else if let Err(active) = state.active_turns.try_begin(&id, &request.prompt) {
+        return ApiError::invalid_state(format!("conversation {id} already has a turn running"))
+            .with_details(serde_json::to_value(&active).unwrap_or_default())
+            .into_response();
+    }
+    audit(
+        &*state.storage,
+        "complete.run",
+        &format!("conversation {id}"),
+    )
+    .await;
+    let input = TurnInput {
+        prompt: request.prompt,
+        config_overrides: request.config_overrides,
+        images: request.images,
+    };
+    let span = crate::trace::turn_span(&headers, &id);
+    stream_turn(state, id, input, Some(cwd), span).await
+}
+
+/// Runs one turn and streams its text; the caller has already claimed the
+/// conversation in [`crate::turns::ActiveTurns`]. Stages the attachments,
+/// records the attempt for `/retry-last`, and releases the conversation
+/// when the turn ends.
+pub(crate) async fn stream_turn(
+    state: AppState,
+    id: String,
+    input: TurnInput,
+    cwd: Option<PathBuf>,
+    span: tracing::Span,
+) -> Response {
+    let staged = if input.images.is_empty() {
+        None
+    } else {
+        match state.images.stage(&state.codex_home, &input.images).await {
+            Ok(staged) => Some(staged),
+            Err(err) => {
+                state.active_turns.finish(&id);
+                return err.into_response();
+            }
+        }
+    };
+    let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+    let active_turns = state.active_turns.clone();
+    let redactor = state.redactor.clone();
+    let turn_id = id.clone();
+    // The turn span parents the whole chain: the runner copies its context
+    // into the spawned `codex exec`, whose own spans continue the trace.
+    let turn = tokio::spawn(
+        async move {
+            // The status line is already on the wire; a failure can only be
+            // reported through the body text the runner sends.
+            let image_paths = staged
+                .as_ref()
+                .map(|staged| staged.paths.clone())
+                .unwrap_or_default();
+            let outcome = state
+                .runner
+                .run_streaming(
+                    &input.prompt,
+                    cwd.as_deref(),
+                    &input.config_overrides,
+                    &image_paths,
+                    tx,
+                )
+                .await;
+            if !outcome.success {
+                warn!(
+                    "completion turn for conversation {id} failed: {}",
+                    outcome.detail
+                );
+            }
+            let attempt = crate::retry::TurnAttempt {
+                conversation_id: id.clone(),
+                input,
+                success: outcome.success,
+                at: Utc::now(),
+            };
+            if let Err(err) = state.storage.save_turn_attempt(&attempt).await {
+                warn!("failed to record turn attempt for conversation {id}: {err}");
+            }
+            if let Some(staged) = staged {
+                staged.cleanup().await;
+            }
+            state.active_turns.finish(&id);
+        }
+        .instrument(span),
+    );
+    // A later force interrupt aborts the turn, killing the spawned process.
+    active_turns.set_abort(&turn_id, turn.abort_handle());
+    // Scrubbed on the way out so secrets the turn echoed never reach the
+    // client, wherever the runner got them from.
+    let body = Body::from_stream(
+        ReceiverStream::new(rx).map(move |chunk| Ok::<_, Infallible>(redactor.redact_text(&chunk))),
+    );
+    ([(CONTENT_TYPE, "text/plain; charset=utf-8")], body).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_state;
+    use axum::http::StatusCode;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn completing_unknown_conversation_is_not_found() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let response = complete_conversation(
+            State(test_state(codex_home.path()).await),
+            Path("0199a213-81ba-7142-ba53-6b2ebc1b3a5a".to_string()),
+            HeaderMap::new(),
+            Json(CompleteRequest {
+                prompt: "summarize the last run".to_string(),
+                config_overrides: Vec::new(),
+                images: Vec::new(),
+                queue: false,
+                force: false,
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn empty_prompt_is_rejected() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let response = complete_conversation(
+            State(test_state(codex_home.path()).await),
+            Path("0199a213-81ba-7142-ba53-6b2ebc1b3a5a".to_string()),
+            HeaderMap::new(),
+            Json(CompleteRequest {
+                prompt: "  ".to_string(),
+                config_overrides: Vec::new(),
+                images: Vec::new(),
+                queue: false,
+                force: false,
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,299 @@
+//! Handlers for the `/conversations` routes.
+
+use std::path::PathBuf;
+
+use axum::Json;
+use axum::extract::Path;
+use axum::extract::Query;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::http::header::CONTENT_TYPE;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use codex_core::export::ExportFormat;
+use codex_core::export::load_conversation_export;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::AppState;
+use crate::error::ApiError;
+use crate::github;
+use crate::instructions::InstructionOverrides;
+use crate::storage::audit;
+
+/// Body of `POST /conversations`; shared with `codex-http-server-client`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct NewConversationRequest {
+    /// First prompt of the new conversation.
+    pub prompt: String,
+    /// Working directory the conversation runs in.
+    pub cwd: Option<PathBuf>,
+    /// `-c key=value` config overrides passed through to the conversation.
+    #[serde(default)]
+    pub config_overrides: Vec<String>,
+    /// `base_instructions` and `user_instructions` overrides, gated by the
+    /// server's `[http_server.instructions]` policy.
+    #[serde(flatten)]
+    pub instructions: InstructionOverrides,
+}
+
+/// Response of `POST /conversations`; shared with
+/// `codex-http-server-client`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConversationCreated {
+    /// Id the conversation was recorded under, for follow-up turns through
+    /// `/conversations/{id}/complete`; `null` when the runner did not
+    /// report one.
+    pub id: Option<String>,
+    /// Assistant text of the first turn.
+    pub reply: String,
+}
+
+/// `POST /conversations`
+///
+/// Starts a brand-new conversation with a first prompt. Different API
+/// consumers shape the agent through the instruction overrides — when the
+/// operator allows them — instead of through separate server deployments.
+pub(crate) async fn new_conversation(
+    State(state): State<AppState>,
+    Json(request): Json<NewConversationRequest>,
+) -> Response {
+    if request.prompt.trim().is_empty() {
+        return ApiError::invalid_request("prompt must not be empty").into_response();
+    }
+    let mut config_overrides = request.config_overrides;
+    match state.instructions.render(&request.instructions) {
+        Ok(rendered) => config_overrides.extend(rendered),
+        Err(err) => return err.into_response(),
+    }
+    let detail = if request.instructions.is_empty() {
+        "new conversation".to_string()
+    } else {
+        "new conversation with instruction overrides".to_string()
+    };
+    audit(&*state.storage, "conversation.create", &detail).await;
+    let created = state
+        .runner
+        .run_new_conversation(&request.prompt, request.cwd.as_deref(), &config_overrides)
+        .await;
+    if !created.outcome.success {
+        return ApiError::internal(format!("conversation failed: {}", created.outcome.detail))
+            .into_response();
+    }
+    (
+        StatusCode::CREATED,
+        Json(ConversationCreated {
+            id: created.conversation_id,
+            reply: state.redactor.redact_text(&created.outcome.detail),
+        }),
+    )
+        .into_response()
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ExportQuery {
+    format: Option<String>,
+}
+
+/// `GET /conversations/{id}/export?format=markdown|json|html`
+///
+/// Renders the conversation's transcript in the requested format; the format
+/// defaults to Markdown.
+pub(crate) async fn export_conversation(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<ExportQuery>,
+) -> Response {
+    let format = match query
+        .format
+        .as_deref()
+        .unwrap_or("markdown")
+        .parse::<ExportFormat>()
+    {
+        Ok(format) => format,
+        Err(message) => return ApiError::invalid_request(message).into_response(),
+    };
+    match load_conversation_export(&state.codex_home, &id).await {
+        Ok(Some(mut export)) => {
+            crate::limits::summarize_transcript(&mut export, state.limits.max_transcript_bytes);
+            state.redactor.redact_transcript(&mut export);
+            (
+                StatusCode::OK,
+                [(CONTENT_TYPE, format.content_type())],
+                export.render(format),
+            )
+                .into_response()
+        }
+        Ok(None) => ApiError::not_found(format!("no conversation with id {id}")).into_response(),
+        Err(err) => {
+            ApiError::internal(format!("failed to load conversation: {err}")).into_response()
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct PullRequestCreated {
+    url: String,
+}
+
+/// `POST /conversations/{id}/pr`
+///
+/// Commits the conversation's changes on a fresh branch in its recorded
+/// working directory, pushes with the configured GitHub token, and opens a
+/// pull request whose title and summary come from the transcript.
+pub(crate) async fn open_conversation_pr(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Response {
+    let Some(token) = state.settings().github_token.clone() else {
+        return ApiError::missing_credentials("no github_token configured under [http_server]")
+            .into_response();
+    };
+    let export = match load_conversation_export(&state.codex_home, &id).await {
+        Ok(Some(export)) => export,
+        Ok(None) => {
+            return ApiError::not_found(format!("no conversation with id {id}")).into_response();
+        }
+        Err(err) => {
+            return ApiError::internal(format!("failed to load conversation: {err}"))
+                .into_response();
+        }
+    };
+    let Some(cwd) = export.cwd.clone() else {
+        return ApiError::invalid_state(format!(
+            "conversation {id} has no recorded working directory"
+        ))
+        .into_response();
+    };
+    let branch = format!("codex/conversation-{id}");
+    match github::open_pull_request_from_repo(
+        std::path::Path::new(&cwd),
+        &token,
+        &branch,
+        &export.pr_title(),
+        &export.pr_body(),
+    )
+    .await
+    {
+        Ok(url) => {
+            audit(&*state.storage, "pr.open", &format!("conversation {id}")).await;
+            (StatusCode::CREATED, Json(PullRequestCreated { url })).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_state as state;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn new_conversation_with_empty_prompt_is_rejected() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let response = new_conversation(
+            State(state(codex_home.path()).await),
+            Json(NewConversationRequest {
+                prompt: "  ".to_string(),
+                ..NewConversationRequest::default()
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn instruction_overrides_are_denied_by_default() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let response = new_conversation(
+            State(state(codex_home.path()).await),
+            Json(NewConversationRequest {
+                prompt: "triage the queue".to_string(),
+                instructions: InstructionOverrides {
+                    base_instructions: Some("You are a support bot.".to_string()),
+                    user_instructions: None,
+                },
+                ..NewConversationRequest::default()
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn allowed_overrides_start_the_conversation() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let mut state = state(codex_home.path()).await;
+        state.instructions = crate::instructions::InstructionsPolicy::from_toml(Some(
+            &codex_config::types::HttpInstructionsToml {
+                allow: vec!["user_instructions".to_string()],
+                deny: Vec::new(),
+            },
+        ))
+        .expect("build policy");
+        let response = new_conversation(
+            State(state),
+            Json(NewConversationRequest {
+                prompt: "triage the queue".to_string(),
+                instructions: InstructionOverrides {
+                    base_instructions: None,
+                    user_instructions: Some("Answer in French.".to_string()),
+                },
+                ..NewConversationRequest::default()
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn unknown_conversation_returns_not_found() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let response = export_conversation(
+            State(state(codex_home.path()).await),
+            Path("0199a213-81ba-7142-ba53-6b2ebc1b3a5a".to_string()),
+            Query(ExportQuery { format: None }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn unknown_format_returns_bad_request() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let response = export_conversation(
+            State(state(codex_home.path()).await),
+            Path("0199a213-81ba-7142-ba53-6b2ebc1b3a5a".to_string()),
+            Query(ExportQuery {
+                format: Some("pdf".to_string()),
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn pr_without_configured_token_is_rejected() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let response = open_conversation_pr(
+            State(state(codex_home.path()).await),
+            Path("0199a213-81ba-7142-ba53-6b2ebc1b3a5a".to_string()),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn pr_for_unknown_conversation_is_not_found() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let state = state(codex_home.path()).await;
+        state.settings.write().expect("write settings").github_token = Some("token".to_string());
+        let response = open_conversation_pr(
+            State(state),
+            Path("0199a213-81ba-7142-ba53-6b2ebc1b3a5a".to_string()),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,220 @@
+//! Minimal five-field cron expressions for schedule triggers.
+//!
+//! Supports the common subset — `*`, numbers, ranges, step values, and comma
+//! lists — over minute, hour, day-of-month, month, and day-of-week (0-6,
+//! with 7 accepted as Sunday). As in standard cron, when both day fields are
+//! restricted (neither starts with `*`) a day matches when either field
+//! does: `0 0 13 * 5` fires on the 13th and on every Friday. Times are
+//! evaluated in UTC.
+
+use chrono::DateTime;
+use chrono::Datelike;
+use chrono::Duration;
+use chrono::Timelike;
+use chrono::Utc;
+
+/// How far ahead `next_after` searches before giving up; covers every
+/// satisfiable expression (e.g. Feb 29) within one leap cycle.
+const MAX_SEARCH_DAYS: i64 = 366 * 4;
+
+/// A parsed cron expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CronExpr {
+    minutes: Vec<u32>,
+    hours: Vec<u32>,
+    days_of_month: Vec<u32>,
+    months: Vec<u32>,
+    days_of_week: Vec<u32>,
+    /// Whether the day-of-month field was written as something other than
+    /// `*`; drives the either-field day rule below.
+    day_of_month_restricted: bool,
+    /// Same for the day-of-week field.
+    day_of_week_restricted: bool,
+}
+
+impl CronExpr {
+    pub(crate) fn parse(expr: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields.as_slice() else {
+            return Err(format!(
+                "expected 5 cron fields (minute hour day-of-month month day-of-week), got {}",
+                fields.len()
+            ));
+        };
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59)?,
+            hours: parse_field(hour, 0, 23)?,
+            days_of_month: parse_field(day_of_month, 1, 31)?,
+            months: parse_field(month, 1, 12)?,
+            days_of_week: {
+                let mut days: Vec<u32> = parse_field(day_of_week, 0, 7)?
+                    .into_iter()
+                    .map(|day| day % 7)
+                    .collect();
+                days.sort_unstable();
+                days.dedup();
+                days
+            },
+            // Like Vixie cron, a field starting with `*` (including `*/n`)
+            // counts as unrestricted.
+            day_of_month_restricted: !day_of_month.starts_with('*'),
+            day_of_week_restricted: !day_of_week.starts_with('*'),
+        })
+    }
+
+    /// First instant strictly after `after` that matches the expression.
+    pub(crate) fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut candidate = (after + Duration::minutes(1))
+            .with_second(0)?
+            .with_nanosecond(0)?;
+        let limit = after + Duration::days(MAX_SEARCH_DAYS);
+        while candidate <= limit {
+            if !self.months.contains(&candidate.month())
+                || !self.matches_day(candidate)
+                || !self.hours.contains(&candidate.hour())
+            {
+                candidate += Duration::minutes(1);
+                continue;
+            }
+            if self.minutes.contains(&candidate.minute()) {
+                return Some(candidate);
+            }
+            candidate += Duration::minutes(1);
+        }
+        None
+    }
+
+    fn matches_day(&self, candidate: DateTime<Utc>) -> bool {
+        let day_of_month = self.days_of_month.contains(&candidate.day());
+        let day_of_week = self
+            .days_of_week
+            .contains(&candidate.weekday().num_days_from_sunday());
+        // Standard cron ORs the two day fields when both are restricted; an
+        // unrestricted field's full-range set makes the AND arm collapse to
+        // the restricted one.
+        if self.day_of_month_restricted && self.day_of_week_restricted {
+            day_of_month || day_of_week
+        } else {
+            day_of_month && day_of_week
+        }
+    }
+}
+
+/// Parses one cron field into the sorted set of matching values.
+fn parse_field(field: &str, min: u32, max: u32) -> Result<Vec<u32>, String> {
+    let mut values = Vec::new();
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .map_err(|_| format!("invalid cron step `{step}` in `{field}`"))?;
+                if step == 0 {
+                    return Err(format!("cron step must be positive in `{field}`"));
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (parse_value(start, field)?, parse_value(end, field)?)
+        } else {
+            let value = parse_value(range, field)?;
+            (value, value)
+        };
+        if start < min || end > max || start > end {
+            return Err(format!(
+                "cron value out of range in `{field}` (expected {min}-{max})"
+            ));
+        }
+        values.extend((start..=end).step_by(step as usize));
+    }
+    values.sort_unstable();
+    values.dedup();
+    Ok(values)
+}
+
+fn parse_value(value: &str, field: &str) -> Result<u32, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid cron value `{value}` in `{field}`"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use pretty_assertions::assert_eq;
+
+    fn utc(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0)
+            .single()
+            .expect("valid timestamp")
+    }
+
+    #[test]
+    fn nightly_run_fires_at_the_configured_hour() {
+        let expr = CronExpr::parse("0 3 * * *").expect("parse");
+        assert_eq!(
+            expr.next_after(utc(2026, 8, 27, 12, 0)),
+            Some(utc(2026, 8, 28, 3, 0))
+        );
+        assert_eq!(
+            expr.next_after(utc(2026, 8, 28, 2, 59)),
+            Some(utc(2026, 8, 28, 3, 0))
+        );
+    }
+
+    #[test]
+    fn step_and_list_fields() {
+        let expr = CronExpr::parse("*/15 9-17 * * 1,3,5").expect("parse");
+        // 2026-08-27 is a Thursday; the next matching weekday is Friday.
+        assert_eq!(
+            expr.next_after(utc(2026, 8, 27, 18, 0)),
+            Some(utc(2026, 8, 28, 9, 0))
+        );
+        assert_eq!(
+            expr.next_after(utc(2026, 8, 28, 9, 1)),
+            Some(utc(2026, 8, 28, 9, 15))
+        );
+    }
+
+    #[test]
+    fn restricted_day_fields_fire_on_either() {
+        let expr = CronExpr::parse("0 0 13 * 5").expect("parse");
+        // 2026-08-27 is a Thursday: the next Friday comes before the next
+        // 13th.
+        assert_eq!(
+            expr.next_after(utc(2026, 8, 27, 12, 0)),
+            Some(utc(2026, 8, 28, 0, 0))
+        );
+        // 2026-09-13 is a Sunday: the 13th comes before the next Friday.
+        assert_eq!(
+            expr.next_after(utc(2026, 9, 12, 23, 59)),
+            Some(utc(2026, 9, 13, 0, 0))
+        );
+        // With day-of-week unrestricted the day-of-month alone decides.
+        let expr = CronExpr::parse("0 0 13 * *").expect("parse");
+        assert_eq!(
+            expr.next_after(utc(2026, 8, 27, 12, 0)),
+            Some(utc(2026, 9, 13, 0, 0))
+        );
+    }
+
+    #[test]
+    fn seven_is_accepted_as_sunday() {
+        let sunday_as_seven = CronExpr::parse("0 0 * * 7").expect("parse");
+        let sunday_as_zero = CronExpr::parse("0 0 * * 0").expect("parse");
+        assert_eq!(sunday_as_seven, sunday_as_zero);
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        assert!(CronExpr::parse("0 3 * *").is_err());
+        assert!(CronExpr::parse("61 * * * *").is_err());
+        assert!(CronExpr::parse("* * * * 8").is_err());
+        assert!(CronExpr::parse("*/0 * * * *").is_err());
+    }
+}
@@ -0,0 +1,277 @@
+//! Structured error bodies for non-2xx responses.
+//!
+//! Every handler error is an [`ApiError`]: an HTTP status plus a JSON body
+//! carrying a stable machine-readable `code`, a human-readable `message`,
+//! whether retrying the same request can succeed, and optional structured
+//! `details`. Clients branch on the code instead of regexing messages.
+//!
+//! The codes and their statuses are part of the API contract:
+//!
+//! | code                    | status | retryable | meaning                                      |
+//! |-------------------------|--------|-----------|----------------------------------------------|
+//! | `invalid_request`       | 400    | no        | malformed or unusable input                  |
+//! | `missing_credentials`   | 400    | no        | the server lacks a required credential       |
+//! | `sandbox_denied`        | 403    | no        | grant exceeds `[http_server.sandbox_limits]` |
+//! | `not_found`             | 404    | no        | no resource with that id                     |
+//! | `invalid_state`         | 409    | no        | resource exists but cannot serve the request |
+//! | `payload_too_large`     | 413    | no        | the resource exceeds a server-side size cap  |
+//! | `internal`              | 500    | no        | unexpected failure inside the server         |
+//! | `upstream_error`        | 502    | yes       | an upstream API rejected or failed the call  |
+//! | `provider_rate_limited` | 503    | yes       | an upstream API rate-limited the server      |
+//! | `not_ready`             | 503    | yes       | the server is still starting up              |
+
+use axum::Json;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Stable machine-readable error codes; see the module docs for the table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    InvalidRequest,
+    MissingCredentials,
+    SandboxDenied,
+    NotFound,
+    InvalidState,
+    PayloadTooLarge,
+    Internal,
+    UpstreamError,
+    ProviderRateLimited,
+    NotReady,
+}
+
+impl ErrorCode {
+    /// The wire name, as serialized into [`ErrorBody::code`].
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::InvalidRequest => "invalid_request",
+            ErrorCode::MissingCredentials => "missing_credentials",
+            ErrorCode::SandboxDenied => "sandbox_denied",
+            ErrorCode::NotFound => "not_found",
+            ErrorCode::InvalidState => "invalid_state",
+            ErrorCode::PayloadTooLarge => "payload_too_large",
+            ErrorCode::Internal => "internal",
+            ErrorCode::UpstreamError => "upstream_error",
+            ErrorCode::ProviderRateLimited => "provider_rate_limited",
+            ErrorCode::NotReady => "not_ready",
+        }
+    }
+}
+
+/// JSON body of every non-2xx response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorBody {
+    pub code: ErrorCode,
+    pub message: String,
+    /// Whether retrying the identical request can succeed.
+    pub retryable: bool,
+    /// Code-specific structured context, e.g. the upstream status.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+}
+
+/// A handler error: a status plus the [`ErrorBody`] sent to the client.
+#[derive(Debug)]
+pub(crate) struct ApiError {
+    status: StatusCode,
+    body: ErrorBody,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, code: ErrorCode, retryable: bool, message: String) -> Self {
+        Self {
+            status,
+            body: ErrorBody {
+                code,
+                message,
+                retryable,
+                details: None,
+            },
+        }
+    }
+
+    pub(crate) fn invalid_request(message: impl Into<String>) -> Self {
+        Self::new(
+            StatusCode::BAD_REQUEST,
+            ErrorCode::InvalidRequest,
+            false,
+            message.into(),
+        )
+    }
+
+    pub(crate) fn missing_credentials(message: impl Into<String>) -> Self {
+        Self::new(
+            StatusCode::BAD_REQUEST,
+            ErrorCode::MissingCredentials,
+            false,
+            message.into(),
+        )
+    }
+
+    pub(crate) fn sandbox_denied(message: impl Into<String>) -> Self {
+        Self::new(
+            StatusCode::FORBIDDEN,
+            ErrorCode::SandboxDenied,
+            false,
+            message.into(),
+        )
+    }
+
+    pub(crate) fn not_found(message: impl Into<String>) -> Self {
+        Self::new(
+            StatusCode::NOT_FOUND,
+            ErrorCode::NotFound,
+            false,
+            message.into(),
+        )
+    }
+
+    pub(crate) fn invalid_state(message: impl Into<String>) -> Self {
+        Self::new(
+            StatusCode::CONFLICT,
+            ErrorCode::InvalidState,
+            false,
+            message.into(),
+        )
+    }
+
+    pub(crate) fn payload_too_large(message: impl Into<String>) -> Self {
+        Self::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            ErrorCode::PayloadTooLarge,
+            false,
+            message.into(),
+        )
+    }
+
+    pub(crate) fn internal(message: impl Into<String>) -> Self {
+        Self::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            ErrorCode::Internal,
+            false,
+            message.into(),
+        )
+    }
+
+    pub(crate) fn upstream(message: impl Into<String>) -> Self {
+        Self::new(
+            StatusCode::BAD_GATEWAY,
+            ErrorCode::UpstreamError,
+            true,
+            message.into(),
+        )
+    }
+
+    pub(crate) fn provider_rate_limited(message: impl Into<String>) -> Self {
+        Self::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            ErrorCode::ProviderRateLimited,
+            true,
+            message.into(),
+        )
+    }
+
+    pub(crate) fn not_ready(message: impl Into<String>) -> Self {
+        Self::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            ErrorCode::NotReady,
+            true,
+            message.into(),
+        )
+    }
+
+    pub(crate) fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.body.details = Some(details);
+        self
+    }
+
+    #[cfg(test)]
+    pub(crate) fn code(&self) -> ErrorCode {
+        self.body.code
+    }
+
+    #[cfg(test)]
+    pub(crate) fn message(&self) -> &str {
+        &self.body.message
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(self.body)).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn body_serializes_with_stable_code_names() {
+        let error = ApiError::provider_rate_limited("GitHub API returned 429")
+            .with_details(serde_json::json!({ "upstream_status": 429 }));
+        let body = serde_json::to_value(&error.body).expect("serialize body");
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "code": "provider_rate_limited",
+                "message": "GitHub API returned 429",
+                "retryable": true,
+                "details": { "upstream_status": 429 },
+            })
+        );
+    }
+
+    #[test]
+    fn wire_names_match_serialization() {
+        for code in [
+            ErrorCode::InvalidRequest,
+            ErrorCode::MissingCredentials,
+            ErrorCode::SandboxDenied,
+            ErrorCode::NotFound,
+            ErrorCode::InvalidState,
+            ErrorCode::PayloadTooLarge,
+            ErrorCode::Internal,
+            ErrorCode::UpstreamError,
+            ErrorCode::ProviderRateLimited,
+            ErrorCode::NotReady,
+        ] {
+            assert_eq!(
+                serde_json::to_value(code).expect("serialize code"),
+                serde_json::Value::String(code.as_str().to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn statuses_follow_the_documented_table() {
+        assert_eq!(
+            ApiError::invalid_request("").status,
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            ApiError::missing_credentials("").status,
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(ApiError::sandbox_denied("").status, StatusCode::FORBIDDEN);
+        assert_eq!(ApiError::not_found("").status, StatusCode::NOT_FOUND);
+        assert_eq!(ApiError::invalid_state("").status, StatusCode::CONFLICT);
+        assert_eq!(
+            ApiError::payload_too_large("").status,
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+        assert_eq!(
+            ApiError::internal("").status,
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+        assert_eq!(ApiError::upstream("").status, StatusCode::BAD_GATEWAY);
+        assert_eq!(
+            ApiError::not_ready("").status,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+    }
+}
@@ -0,0 +1,735 @@
+//! Cross-replica event fanout and the `/events` SSE route.
+//!
+//! Job lifecycle events are published on an [`EventBus`] and streamed to
+//! clients as server-sent events. A single replica uses the in-process bus;
+//! deployments running several replicas behind a load balancer set
+//! `http_server.event_bus = "redis://host:port"` so a client's SSE
+//! connection sees every event no matter which replica handled the job.
+//!
+//! The event schema is versioned so pinned client deployments survive
+//! server upgrades: `/health/ready` reports the server's version and a
+//! client sends `Accept-Version` to ask for an older one, in which case
+//! kinds and payload fields added since are dropped rather than breaking
+//! its parser.
+//!
+//! Fanout runs in two lanes. Bulk kinds — output deltas, queue position
+//! updates — go through a bounded broadcast channel and may be dropped
+//! when a subscriber lags. Critical kinds — errors, approval requests,
+//! and completions — are mirrored onto a per-subscriber lossless lane, so
+//! a subscription replays them in publish order even after it lagged.
+
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use axum::extract::State;
+use axum::http::HeaderMap;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use axum::response::Sse;
+use axum::response::sse::Event;
+use axum::response::sse::KeepAlive;
+use futures::Stream;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::error::TryRecvError;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use crate::AppState;
+use crate::error::ApiError;
+
+/// Version of the `/events` schema this server emits. Version 1 predates
+/// the turn gate and offload subsystems; version 2 added their event kinds
+/// and the job payloads' per-run model knobs; version 3 added the
+/// workspace quota kinds.
+pub(crate) const EVENT_SCHEMA_VERSION: u32 = 3;
+
+/// Header a client sets to pin the schema version it parses.
+const ACCEPT_VERSION: &str = "accept-version";
+
+/// Event kinds introduced in version 2; version-1 clients never see them.
+const V2_KINDS: &[&str] = &[
+    "turn.queued",
+    "turn.started",
+    "offload.started",
+    "offload.handed_back",
+    "offload.failed",
+    "conversation.archived",
+    "sandbox.denied",
+];
+
+/// Job payload fields introduced in version 2.
+const V2_JOB_FIELDS: &[&str] = &["reasoning_effort", "verbosity"];
+
+/// Event kinds introduced in version 3; older clients never see them.
+const V3_KINDS: &[&str] = &["quota.exceeded", "workspace.cleaned"];
+
+/// Redis pub/sub channel shared by all replicas.
+const CHANNEL: &str = "codex-http-events";
+
+/// How many bulk events a slow SSE client may lag before it starts
+/// dropping them; critical events are replayed from the lossless lane
+/// instead.
+const BUS_CAPACITY: usize = 256;
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// One event on the bus, e.g. `job.done` with the job as payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerEvent {
+    pub kind: String,
+    pub payload: serde_json::Value,
+}
+
+/// Whether `kind` rides the critical lane. Errors, approval requests, and
+/// completions must reach even a lagging subscriber; everything else may
+/// be dropped under backpressure.
+fn is_critical(kind: &str) -> bool {
+    matches!(
+        kind,
+        "job.done" | "job.timeout" | "sandbox.denied" | "quota.exceeded" | "offload.handed_back"
+    ) || kind.ends_with(".failed")
+}
+
+/// An event stamped with its position in the publish order; the sequence
+/// number lets a subscription slot replayed critical events back where
+/// the bulk lane dropped them.
+#[derive(Debug, Clone)]
+struct SequencedEvent {
+    seq: u64,
+    event: ServerEvent,
+}
+
+/// Fanout used by the job queue and the `/events` route.
+#[async_trait]
+pub(crate) trait EventBus: Send + Sync {
+    async fn publish(&self, event: ServerEvent);
+    fn subscribe(&self) -> EventSubscription;
+}
+
+/// In-process bus: events reach subscribers on this replica only.
+pub(crate) struct LocalEventBus {
+    bulk: broadcast::Sender<SequencedEvent>,
+    /// The critical lanes and the publish-order counter; one lock keeps
+    /// both lanes delivering in the same order.
+    critical: std::sync::Mutex<CriticalLanes>,
+}
+
+#[derive(Default)]
+struct CriticalLanes {
+    seq: u64,
+    subscribers: Vec<mpsc::UnboundedSender<SequencedEvent>>,
+}
+
+impl LocalEventBus {
+    pub(crate) fn new() -> Self {
+        let (bulk, _) = broadcast::channel(BUS_CAPACITY);
+        Self {
+            bulk,
+            critical: std::sync::Mutex::new(CriticalLanes::default()),
+        }
+    }
+}
+
+#[async_trait]
+impl EventBus for LocalEventBus {
+    async fn publish(&self, event: ServerEvent) {
+        let mut lanes = self.critical.lock().expect("lock critical lanes");
+        lanes.seq += 1;
+        let event = SequencedEvent {
+            seq: lanes.seq,
+            event,
+        };
+        // The critical copy is sent first so a subscriber that sees the
+        // bulk copy always finds it at the head of its lossless lane.
+        if is_critical(&event.event.kind) {
+            lanes
+                .subscribers
+                .retain(|lane| lane.send(event.clone()).is_ok());
+        }
+        // No subscribers is fine; events are fire-and-forget.
+        let _ = self.bulk.send(event);
+    }
+
+    fn subscribe(&self) -> EventSubscription {
+        let mut lanes = self.critical.lock().expect("lock critical lanes");
+        let (tx, critical) = mpsc::unbounded_channel();
+        lanes.subscribers.push(tx);
+        EventSubscription {
+            bulk: self.bulk.subscribe(),
+            critical,
+            stashed: None,
+            replayed: VecDeque::new(),
+        }
+    }
+}
+
+/// One subscriber's view of the bus: the bounded bulk lane merged with its
+/// lossless critical lane. Events come out in publish order; when the bulk
+/// lane drops events under backpressure, the critical ones among them are
+/// replayed in their original positions.
+pub(crate) struct EventSubscription {
+    bulk: broadcast::Receiver<SequencedEvent>,
+    critical: mpsc::UnboundedReceiver<SequencedEvent>,
+    /// Critical event read ahead of the bulk lane, held until its turn.
+    stashed: Option<SequencedEvent>,
+    /// Replayed critical events waiting to be handed out.
+    replayed: VecDeque<ServerEvent>,
+}
+
+impl EventSubscription {
+    /// The next event, or `None` once the bus is gone and every pending
+    /// critical event has been delivered.
+    pub(crate) async fn next(&mut self) -> Option<ServerEvent> {
+        loop {
+            if let Some(event) = self.replayed.pop_front() {
+                return Some(event);
+            }
+            match self.bulk.recv().await {
+                Ok(event) => self.absorb(event),
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("event subscriber lagged behind the bus; dropped {skipped} bulk events");
+                }
+                Err(RecvError::Closed) => {
+                    return self.take_critical().map(|event| event.event);
+                }
+            }
+        }
+    }
+
+    /// Like [`Self::next`] but never waits; `None` means no event is ready
+    /// right now, not that the stream ended.
+    pub(crate) fn try_next(&mut self) -> Option<ServerEvent> {
+        loop {
+            if let Some(event) = self.replayed.pop_front() {
+                return Some(event);
+            }
+            match self.bulk.try_recv() {
+                Ok(event) => self.absorb(event),
+                Err(TryRecvError::Lagged(skipped)) => {
+                    warn!("event subscriber lagged behind the bus; dropped {skipped} bulk events");
+                }
+                Err(TryRecvError::Empty) => return None,
+                Err(TryRecvError::Closed) => {
+                    return self.take_critical().map(|event| event.event);
+                }
+            }
+        }
+    }
+
+    /// Adapts the subscription for SSE routes.
+    pub(crate) fn into_stream(self) -> impl Stream<Item = ServerEvent> {
+        futures::stream::unfold(self, |mut subscription| async move {
+            subscription.next().await.map(|event| (event, subscription))
+        })
+    }
+
+    /// Queues `event` for delivery, replaying any critical events the bulk
+    /// lane dropped ahead of it. A critical event's own lossless copy is
+    /// consumed so it is not delivered twice.
+    fn absorb(&mut self, event: SequencedEvent) {
+        while let Some(critical) = self.take_critical() {
+            if critical.seq > event.seq {
+                self.stashed = Some(critical);
+                break;
+            }
+            if critical.seq == event.seq {
+                break;
+            }
+            self.replayed.push_back(critical.event);
+        }
+        self.replayed.push_back(event.event);
+    }
+
+    /// The next critical event, consulting the read-ahead stash first.
+    fn take_critical(&mut self) -> Option<SequencedEvent> {
+        self.stashed
+            .take()
+            .or_else(|| self.critical.try_recv().ok())
+    }
+}
+
+/// Wire format on the Redis channel; the replica id lets a replica skip its
+/// own messages, which it already delivered locally.
+#[derive(Debug, Serialize, Deserialize)]
+struct Envelope {
+    replica: String,
+    event: ServerEvent,
+}
+
+/// Bus backed by Redis pub/sub: publishes go to the shared channel and a
+/// background task forwards other replicas' messages into the local bus.
+pub(crate) struct RedisEventBus {
+    local: LocalEventBus,
+    publisher: Mutex<BufReader<TcpStream>>,
+    addr: String,
+    replica: String,
+}
+
+impl RedisEventBus {
+    pub(crate) async fn connect(url: &str) -> anyhow::Result<Arc<Self>> {
+        let addr = redis_addr(url).map_err(anyhow::Error::msg)?;
+        let publisher = Mutex::new(BufReader::new(TcpStream::connect(&addr).await?));
+        let bus = Arc::new(Self {
+            local: LocalEventBus::new(),
+            publisher,
+            addr,
+            replica: uuid::Uuid::new_v4().to_string(),
+        });
+        tokio::spawn(bus.clone().subscriber_loop());
+        Ok(bus)
+    }
+
+    /// Subscribes to [`CHANNEL`] and forwards other replicas' events into
+    /// the local bus, reconnecting when the connection drops.
+    async fn subscriber_loop(self: Arc<Self>) {
+        loop {
+            match self.forward_messages().await {
+                Ok(()) => {}
+                Err(err) => warn!("event-bus subscriber disconnected: {err}"),
+            }
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    }
+
+    async fn forward_messages(&self) -> std::io::Result<()> {
+        let mut conn = BufReader::new(TcpStream::connect(&self.addr).await?);
+        conn.write_all(&encode_command(&["SUBSCRIBE", CHANNEL]))
+            .await?;
+        conn.flush().await?;
+        loop {
+            let frame = read_frame(&mut conn).await?;
+            if frame.first().map(String::as_str) != Some("message") {
+                continue;
+            }
+            let Some(payload) = frame.get(2) else {
+                continue;
+            };
+            match serde_json::from_str::<Envelope>(payload) {
+                Ok(envelope) if envelope.replica != self.replica => {
+                    self.local.publish(envelope.event).await;
+                }
+                Ok(_) => {}
+                Err(err) => warn!("ignoring malformed event-bus message: {err}"),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl EventBus for RedisEventBus {
+    async fn publish(&self, event: ServerEvent) {
+        self.local.publish(event.clone()).await;
+        let envelope = Envelope {
+            replica: self.replica.clone(),
+            event,
+        };
+        let payload = match serde_json::to_string(&envelope) {
+            Ok(payload) => payload,
+            Err(err) => {
+                warn!("failed to encode event-bus message: {err}");
+                return;
+            }
+        };
+        let mut conn = self.publisher.lock().await;
+        let result = async {
+            conn.write_all(&encode_command(&["PUBLISH", CHANNEL, &payload]))
+                .await?;
+            conn.flush().await?;
+            read_frame(&mut conn).await
+        }
+        .await;
+        if let Err(err) = result {
+            warn!("failed to publish event to the bus: {err}");
+        }
+    }
+
+    fn subscribe(&self) -> EventSubscription {
+        self.local.subscribe()
+    }
+}
+
+/// `host:port` from a `redis://` URL, defaulting the port to 6379.
+fn redis_addr(url: &str) -> Result<String, String> {
+    let Some(rest) = url.strip_prefix("redis://") else {
+        return Err(format!(
+            "unsupported event-bus URL {url}; expected redis://host:port"
+        ));
+    };
+    let host = rest.trim_end_matches('/');
+    if host.is_empty() {
+        return Err(format!("event-bus URL {url} has no host"));
+    }
+    if host.contains(':') {
+        Ok(host.to_string())
+    } else {
+        Ok(format!("{host}:6379"))
+    }
+}
+
+/// RESP encoding of one command, e.g. `PUBLISH channel payload`.
+fn encode_command(args: &[&str]) -> Vec<u8> {
+    let mut out = format!("*{}\r\n", args.len()).into_bytes();
+    for arg in args {
+        out.extend_from_slice(format!("${}\r\n", arg.len()).as_bytes());
+        out.extend_from_slice(arg.as_bytes());
+        out.extend_from_slice(b"\r\n");
+    }
+    out
+}
+
+/// Reads one RESP reply, flattening arrays of scalars into their elements.
+/// Pub/sub replies never nest deeper than that.
+async fn read_frame(conn: &mut BufReader<TcpStream>) -> std::io::Result<Vec<String>> {
+    let line = read_line(conn).await?;
+    let (kind, rest) = split_type(&line)?;
+    if kind != '*' {
+        return Ok(read_scalar_rest(conn, kind, rest)
+            .await?
+            .into_iter()
+            .collect());
+    }
+    let len: usize = rest
+        .parse()
+        .map_err(|_| std::io::Error::other(format!("bad RESP array length {rest}")))?;
+    let mut elements = Vec::with_capacity(len);
+    for _ in 0..len {
+        let line = read_line(conn).await?;
+        let (kind, rest) = split_type(&line)?;
+        if let Some(value) = read_scalar_rest(conn, kind, rest).await? {
+            elements.push(value);
+        }
+    }
+    Ok(elements)
+}
+
+/// Completes a scalar whose type byte and header have been read.
+async fn read_scalar_rest(
+    conn: &mut BufReader<TcpStream>,
+    kind: char,
+    rest: &str,
+) -> std::io::Result<Option<String>> {
+    match kind {
+        '+' | ':' => Ok(Some(rest.to_string())),
+        '-' => Err(std::io::Error::other(format!("redis error: {rest}"))),
+        '$' => {
+            let len: i64 = rest
+                .parse()
+                .map_err(|_| std::io::Error::other(format!("bad RESP bulk length {rest}")))?;
+            if len < 0 {
+                return Ok(None);
+            }
+            let mut buf = vec![0u8; len as usize + 2];
+            conn.read_exact(&mut buf).await?;
+            buf.truncate(len as usize);
+            Ok(Some(String::from_utf8_lossy(&buf).into_owned()))
+        }
+        other => Err(std::io::Error::other(format!(
+            "unexpected RESP type {other}"
+        ))),
+    }
+}
+
+fn split_type(line: &str) -> std::io::Result<(char, &str)> {
+    let mut chars = line.chars();
+    let kind = chars
+        .next()
+        .ok_or_else(|| std::io::Error::other("empty RESP line"))?;
+    Ok((kind, &line[kind.len_utf8()..]))
+}
+
+async fn read_line(conn: &mut BufReader<TcpStream>) -> std::io::Result<String> {
+    let mut line = String::new();
+    if conn.read_line(&mut line).await? == 0 {
+        return Err(std::io::Error::other("event-bus connection closed"));
+    }
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// `GET /events`
+///
+/// Streams bus events as SSE; the event name is the bus event's kind.
+/// An `Accept-Version` header pins the stream to an older schema version.
+pub(crate) async fn stream_events(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    let version = match requested_version(&headers) {
+        Ok(version) => version,
+        Err(err) => return err.into_response(),
+    };
+    let max_event_bytes = state.limits.max_event_bytes;
+    let stream = state
+        .events
+        .subscribe()
+        .into_stream()
+        .filter_map(move |event| async move {
+            let event = downconvert(event, version)?;
+            let event = crate::limits::clamp_event(event, max_event_bytes);
+            let event = Event::default()
+                .event(event.kind)
+                .json_data(&event.payload)
+                .ok()?;
+            Some(Ok::<_, Infallible>(event))
+        });
+    Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// The schema version the request asks for; no header means the current
+/// one.
+fn requested_version(headers: &HeaderMap) -> Result<u32, ApiError> {
+    let Some(value) = headers.get(ACCEPT_VERSION) else {
+        return Ok(EVENT_SCHEMA_VERSION);
+    };
+    match value
+        .to_str()
+        .ok()
+        .and_then(|value| value.trim().parse::<u32>().ok())
+    {
+        Some(version) if (1..=EVENT_SCHEMA_VERSION).contains(&version) => Ok(version),
+        _ => Err(ApiError::invalid_request(format!(
+            "unsupported Accept-Version; this server speaks versions 1 through \
+             {EVENT_SCHEMA_VERSION}"
+        ))),
+    }
+}
+
+/// Converts `event` to the schema `version` the client asked for: kinds the
+/// old schema does not know are dropped (`None`) and payload fields added
+/// since are omitted.
+fn downconvert(event: ServerEvent, version: u32) -> Option<ServerEvent> {
+    if version >= EVENT_SCHEMA_VERSION {
+        return Some(event);
+    }
+    if V3_KINDS.contains(&event.kind.as_str()) {
+        return None;
+    }
+    if version < 2 && V2_KINDS.contains(&event.kind.as_str()) {
+        return None;
+    }
+    let mut event = event;
+    if version < 2
+        && event.kind.starts_with("job.")
+        && let Some(payload) = event.payload.as_object_mut()
+    {
+        for field in V2_JOB_FIELDS {
+            payload.remove(*field);
+        }
+    }
+    Some(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn redis_addr_parses_urls() {
+        assert_eq!(
+            redis_addr("redis://cache.internal:6380"),
+            Ok("cache.internal:6380".to_string())
+        );
+        assert_eq!(
+            redis_addr("redis://localhost"),
+            Ok("localhost:6379".to_string())
+        );
+        assert!(redis_addr("nats://localhost").is_err());
+    }
+
+    #[test]
+    fn accept_version_header_is_validated() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(
+            requested_version(&headers).expect("no header"),
+            EVENT_SCHEMA_VERSION
+        );
+
+        headers.insert(ACCEPT_VERSION, "1".parse().expect("header value"));
+        assert_eq!(requested_version(&headers).expect("version 1"), 1);
+
+        for unsupported in ["0", "99", "latest"] {
+            headers.insert(ACCEPT_VERSION, unsupported.parse().expect("header value"));
+            assert!(requested_version(&headers).is_err());
+        }
+    }
+
+    #[test]
+    fn events_downconvert_for_version_1_clients() {
+        let queued = ServerEvent {
+            kind: "turn.queued".to_string(),
+            payload: serde_json::json!({"priority": "batch", "position": 1}),
+        };
+        assert!(downconvert(queued.clone(), 1).is_none());
+        assert!(downconvert(queued, EVENT_SCHEMA_VERSION).is_some());
+
+        let job = ServerEvent {
+            kind: "job.queued".to_string(),
+            payload: serde_json::json!({
+                "id": 1,
+                "prompt": "fix the bug",
+                "reasoning_effort": "xhigh",
+                "verbosity": "low",
+            }),
+        };
+        let downconverted = downconvert(job, 1).expect("job event survives");
+        assert_eq!(
+            downconverted.payload,
+            serde_json::json!({"id": 1, "prompt": "fix the bug"})
+        );
+    }
+
+    #[test]
+    fn version_3_kinds_are_hidden_from_older_clients() {
+        let exceeded = ServerEvent {
+            kind: "quota.exceeded".to_string(),
+            payload: serde_json::json!({"conversation_id": "abc", "used_bytes": 9}),
+        };
+        assert!(downconvert(exceeded.clone(), 1).is_none());
+        assert!(downconvert(exceeded.clone(), 2).is_none());
+        assert!(downconvert(exceeded, EVENT_SCHEMA_VERSION).is_some());
+
+        // Version-2 clients keep the kinds and fields their schema has.
+        let queued = ServerEvent {
+            kind: "turn.queued".to_string(),
+            payload: serde_json::json!({"priority": "batch"}),
+        };
+        assert!(downconvert(queued, 2).is_some());
+    }
+
+    #[tokio::test]
+    async fn local_bus_delivers_to_subscribers() {
+        let bus = LocalEventBus::new();
+        let mut rx = bus.subscribe();
+        bus.publish(ServerEvent {
+            kind: "job.done".to_string(),
+            payload: serde_json::json!({"id": 1}),
+        })
+        .await;
+        let event = rx.next().await.expect("receive event");
+        assert_eq!(event.kind, "job.done");
+    }
+
+    #[tokio::test]
+    async fn critical_events_are_delivered_once_in_publish_order() {
+        let bus = LocalEventBus::new();
+        let mut rx = bus.subscribe();
+        for kind in ["job.queued", "job.running", "job.failed", "job.done"] {
+            bus.publish(ServerEvent {
+                kind: kind.to_string(),
+                payload: serde_json::json!({"id": 1}),
+            })
+            .await;
+        }
+        let mut kinds = Vec::new();
+        while let Some(event) = rx.try_next() {
+            kinds.push(event.kind);
+        }
+        assert_eq!(
+            kinds,
+            vec!["job.queued", "job.running", "job.failed", "job.done"]
+        );
+    }
+
+    #[tokio::test]
+    async fn critical_events_survive_a_lagging_subscriber() {
+        let bus = LocalEventBus::new();
+        let mut rx = bus.subscribe();
+        bus.publish(ServerEvent {
+            kind: "job.failed".to_string(),
+            payload: serde_json::json!({"id": 1}),
+        })
+        .await;
+        // Overflow the bulk lane so the failure's bulk copy is dropped.
+        for i in 0..2 * BUS_CAPACITY {
+            bus.publish(ServerEvent {
+                kind: "exec.output".to_string(),
+                payload: serde_json::json!({"i": i}),
+            })
+            .await;
+        }
+        let mut kinds = Vec::new();
+        while let Some(event) = rx.try_next() {
+            kinds.push(event.kind);
+        }
+        // The failure is replayed ahead of the surviving bulk events.
+        assert_eq!(kinds.first().map(String::as_str), Some("job.failed"));
+        assert_eq!(kinds.iter().filter(|kind| *kind == "job.failed").count(), 1);
+        assert_eq!(kinds.len(), BUS_CAPACITY + 1);
+    }
+
+    /// Accepts the publisher and subscriber connections, answers one
+    /// `PUBLISH`, and relays a message from a second replica.
+    async fn fake_redis(listener: TcpListener) {
+        let (publisher, _) = listener.accept().await.expect("accept publisher");
+        let mut publisher = BufReader::new(publisher);
+        let (subscriber, _) = listener.accept().await.expect("accept subscriber");
+        let mut subscriber = BufReader::new(subscriber);
+
+        let subscribe = read_frame(&mut subscriber).await.expect("read subscribe");
+        assert_eq!(
+            subscribe,
+            vec!["SUBSCRIBE".to_string(), CHANNEL.to_string()]
+        );
+
+        let publish = read_frame(&mut publisher).await.expect("read publish");
+        assert_eq!(publish[0], "PUBLISH");
+        publisher.write_all(b":1\r\n").await.expect("ack publish");
+        publisher.flush().await.expect("flush ack");
+
+        let envelope = serde_json::to_string(&Envelope {
+            replica: "other-replica".to_string(),
+            event: ServerEvent {
+                kind: "job.done".to_string(),
+                payload: serde_json::json!({"id": 2}),
+            },
+        })
+        .expect("encode envelope");
+        let message = format!(
+            "*3\r\n$7\r\nmessage\r\n${}\r\n{CHANNEL}\r\n${}\r\n{envelope}\r\n",
+            CHANNEL.len(),
+            envelope.len()
+        );
+        subscriber
+            .write_all(message.as_bytes())
+            .await
+            .expect("send message");
+        subscriber.flush().await.expect("flush message");
+    }
+
+    #[tokio::test]
+    async fn redis_bus_publishes_and_receives_remote_events() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("local addr");
+        let server = tokio::spawn(fake_redis(listener));
+
+        let bus = RedisEventBus::connect(&format!("redis://{addr}"))
+            .await
+            .expect("connect bus");
+        let mut rx = bus.subscribe();
+
+        bus.publish(ServerEvent {
+            kind: "job.queued".to_string(),
+            payload: serde_json::json!({"id": 1}),
+        })
+        .await;
+        // Delivered locally without a round trip through the server.
+        assert_eq!(rx.next().await.expect("receive local").kind, "job.queued");
+
+        // Relayed from the fake second replica.
+        assert_eq!(rx.next().await.expect("receive remote").kind, "job.done");
+        server.await.expect("fake redis");
+    }
+}
@@ -0,0 +1,188 @@
+//! Per-command output streaming and the
+//! `/conversations/{id}/exec/{call_id}/stream` route.
+//!
+//! `/events` is the whole firehose; this route narrows to one command so a
+//! web UI can render a terminal pane per command without client-side
+//! filtering. The output already recorded for the call is replayed first as
+//! `stdout` events (rollouts store the combined output, not a stdout/stderr
+//! split), then [`EXEC_OUTPUT_KIND`] bus events for the same call — published
+//! by whichever replica is driving the conversation — are forwarded under
+//! their own channel name until the client disconnects.
+
+use std::convert::Infallible;
+
+use axum::extract::Path;
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use axum::response::Sse;
+use axum::response::sse::Event;
+use axum::response::sse::KeepAlive;
+use codex_core::export::ExecCall;
+use codex_core::export::ExecCallLookup;
+use codex_core::export::load_exec_call;
+use futures::StreamExt;
+
+use crate::AppState;
+use crate::error::ApiError;
+use crate::events::ServerEvent;
+
+/// Bus event carrying one output chunk of a running command. The payload
+/// names the conversation, the call, the channel (`stdout`/`stderr`), and
+/// the chunk text.
+pub(crate) const EXEC_OUTPUT_KIND: &str = "exec.output";
+
+/// Recorded output is replayed in chunks about this many characters long,
+/// so clients see the same cadence as a live tail.
+const CHUNK_CHARS: usize = 4096;
+
+/// `GET /conversations/{id}/exec/{call_id}/stream`
+pub(crate) async fn stream_exec_output(
+    State(state): State<AppState>,
+    Path((id, call_id)): Path<(String, String)>,
+) -> Response {
+    let mut call = match load_exec_call(&state.codex_home, &id, &call_id).await {
+        Ok(ExecCallLookup::Found(call)) => call,
+        Ok(ExecCallLookup::NoConversation) => {
+            return ApiError::not_found(format!("no conversation with id {id}")).into_response();
+        }
+        Ok(ExecCallLookup::NoCall) => {
+            return ApiError::not_found(format!("no command call {call_id} in conversation {id}"))
+                .into_response();
+        }
+        Err(err) => {
+            return ApiError::internal(format!("failed to load conversation: {err}"))
+                .into_response();
+        }
+    };
+    // Live chunks were scrubbed when they were published; the recorded
+    // output comes straight from the rollout and is scrubbed here.
+    call.output = state.redactor.redact_text(&call.output);
+    let recorded = futures::stream::iter(replay_events(&call).into_iter().map(Ok));
+    let live = state
+        .events
+        .subscribe()
+        .into_stream()
+        .filter_map(move |event| {
+            let id = id.clone();
+            let call_id = call_id.clone();
+            async move { live_event(event, &id, &call_id) }
+        });
+    Sse::new(recorded.chain(live))
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// The initial SSE events for a call: its command, then the recorded output
+/// in `stdout` chunks.
+fn replay_events(call: &ExecCall) -> Vec<Event> {
+    let mut events = Vec::new();
+    if let Some(command) = &call.command {
+        events.extend(sse_event("command", command));
+    }
+    for chunk in chunk_output(&call.output) {
+        events.extend(sse_event("stdout", &chunk));
+    }
+    events
+}
+
+/// Forwards an [`EXEC_OUTPUT_KIND`] bus event when it belongs to this call,
+/// named after the payload's channel.
+fn live_event(event: ServerEvent, id: &str, call_id: &str) -> Option<Result<Event, Infallible>> {
+    if event.kind != EXEC_OUTPUT_KIND {
+        return None;
+    }
+    let field = |name: &str| event.payload.get(name).and_then(serde_json::Value::as_str);
+    if field("conversation_id") != Some(id) || field("call_id") != Some(call_id) {
+        return None;
+    }
+    let channel = match field("stream") {
+        Some("stderr") => "stderr",
+        _ => "stdout",
+    };
+    Some(Ok(sse_event(channel, field("chunk")?)?))
+}
+
+/// The chunk is JSON-encoded so control characters survive SSE framing.
+fn sse_event(name: &'static str, data: &str) -> Option<Event> {
+    Event::default().event(name).json_data(data).ok()
+}
+
+/// Splits recorded output into [`CHUNK_CHARS`]-sized chunks on character
+/// boundaries.
+fn chunk_output(output: &str) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut chunk = String::new();
+    let mut len = 0;
+    for ch in output.chars() {
+        chunk.push(ch);
+        len += 1;
+        if len == CHUNK_CHARS {
+            chunks.push(std::mem::take(&mut chunk));
+            len = 0;
+        }
+    }
+    if !chunk.is_empty() {
+        chunks.push(chunk);
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_state;
+    use axum::http::StatusCode;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn unknown_conversation_is_not_found() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let response = stream_exec_output(
+            State(test_state(codex_home.path()).await),
+            Path((
+                "0199a213-81ba-7142-ba53-6b2ebc1b3a5a".to_string(),
+                "call-1".to_string(),
+            )),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn output_is_chunked_on_character_boundaries() {
+        let output = "é".repeat(CHUNK_CHARS + 2);
+        let chunks = chunk_output(&output);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].chars().count(), CHUNK_CHARS);
+        assert_eq!(chunks[1], "éé");
+        assert!(chunk_output("").is_empty());
+    }
+
+    #[test]
+    fn live_events_are_filtered_to_the_call() {
+        let event = |conversation: &str, call: &str| ServerEvent {
+            kind: EXEC_OUTPUT_KIND.to_string(),
+            payload: serde_json::json!({
+                "conversation_id": conversation,
+                "call_id": call,
+                "stream": "stderr",
+                "chunk": "warning: unused import",
+            }),
+        };
+        assert!(live_event(event("t-1", "call-1"), "t-1", "call-1").is_some());
+        assert!(live_event(event("t-1", "call-2"), "t-1", "call-1").is_none());
+        assert!(live_event(event("t-2", "call-1"), "t-1", "call-1").is_none());
+        assert!(
+            live_event(
+                ServerEvent {
+                    kind: "job.done".to_string(),
+                    payload: serde_json::json!({}),
+                },
+                "t-1",
+                "call-1",
+            )
+            .is_none()
+        );
+    }
+}
@@ -0,0 +1,297 @@
+//! Liveness and readiness probes for the `/health` routes.
+//!
+//! `/health/live` only proves the process is serving requests; orchestrators
+//! use it to decide when to restart an instance. `/health/ready` also checks
+//! the dependencies a request would hit — config parse, auth file, provider
+//! reachability, and free disk under `CODEX_HOME` — with per-check details,
+//! so traffic is not routed to a half-broken instance.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use codex_config::CONFIG_TOML_FILE;
+use codex_config::config_toml::ConfigToml;
+use serde::Serialize;
+use tokio::net::TcpStream;
+
+use crate::AppState;
+use crate::error::ApiError;
+
+/// Readiness fails when less free space than this is left under
+/// `CODEX_HOME`, since every turn appends to a rollout file there.
+const MIN_FREE_DISK_BYTES: u64 = 256 * 1024 * 1024;
+
+/// How long a provider probe result is reused before re-probing.
+const PROBE_TTL: Duration = Duration::from_secs(60);
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Endpoint probed when the configured provider has no explicit base URL.
+const DEFAULT_PROBE_URL: &str = "https://api.openai.com/v1";
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct Check {
+    name: &'static str,
+    ok: bool,
+    detail: String,
+}
+
+impl Check {
+    fn ok(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            ok: true,
+            detail: detail.into(),
+        }
+    }
+
+    fn failed(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            ok: false,
+            detail: detail.into(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ReadyReport {
+    status: &'static str,
+    /// Version of the `/events` schema; clients pin an older one with
+    /// `Accept-Version`.
+    event_schema_version: u32,
+    checks: Vec<Check>,
+}
+
+/// Caches the provider probe between readiness requests so frequent
+/// kubelet polls don't hammer the provider.
+#[derive(Clone, Default)]
+pub(crate) struct HealthCache {
+    probe: Arc<Mutex<Option<(Instant, Check)>>>,
+}
+
+/// `GET /health/live`
+pub(crate) async fn live() -> &'static str {
+    "ok"
+}
+
+/// `GET /health/ready`
+pub(crate) async fn ready(State(state): State<AppState>) -> Response {
+    let checks = vec![
+        config_check(&state).await,
+        auth_check(&state).await,
+        provider_check(&state).await,
+        disk_check(&state),
+    ];
+    let all_ok = checks.iter().all(|check| check.ok);
+    let report = ReadyReport {
+        status: if all_ok { "ok" } else { "failed" },
+        event_schema_version: crate::events::EVENT_SCHEMA_VERSION,
+        checks,
+    };
+    if all_ok {
+        (StatusCode::OK, Json(report)).into_response()
+    } else {
+        ApiError::not_ready("one or more readiness checks failed")
+            .with_details(serde_json::to_value(&report).unwrap_or(serde_json::Value::Null))
+            .into_response()
+    }
+}
+
+/// config.toml parses (a missing file falls back to defaults).
+async fn config_check(state: &AppState) -> Check {
+    let path = state.codex_home.join(CONFIG_TOML_FILE);
+    match tokio::fs::read_to_string(&path).await {
+        Ok(contents) => match toml::from_str::<ConfigToml>(&contents) {
+            Ok(_) => Check::ok("config", format!("{} parsed", path.display())),
+            Err(err) => Check::failed(
+                "config",
+                format!("{} does not parse: {err}", path.display()),
+            ),
+        },
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            Check::ok("config", "no config.toml; using defaults")
+        }
+        Err(err) => Check::failed(
+            "config",
+            format!("failed to read {}: {err}", path.display()),
+        ),
+    }
+}
+
+/// auth.json, when present, is valid JSON.
+async fn auth_check(state: &AppState) -> Check {
+    let path = state.codex_home.join("auth.json");
+    match tokio::fs::read_to_string(&path).await {
+        Ok(contents) => match serde_json::from_str::<serde_json::Value>(&contents) {
+            Ok(_) => Check::ok("auth", "auth.json parsed"),
+            Err(err) => Check::failed("auth", format!("auth.json does not parse: {err}")),
+        },
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            Check::ok("auth", "no auth.json; assuming environment-based auth")
+        }
+        Err(err) => Check::failed("auth", format!("failed to read auth.json: {err}")),
+    }
+}
+
+/// The model provider endpoint accepts TCP connections; cached for
+/// [`PROBE_TTL`].
+async fn provider_check(state: &AppState) -> Check {
+    {
+        let cached = match state.health.probe.lock() {
+            Ok(cached) => cached.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        };
+        if let Some((at, check)) = cached
+            && at.elapsed() < PROBE_TTL
+        {
+            return check;
+        }
+    }
+    let url = state
+        .providers
+        .list()
+        .get("openai")
+        .and_then(|provider| provider.base_url.clone())
+        .unwrap_or_else(|| DEFAULT_PROBE_URL.to_string());
+    let check = probe_url(&url).await;
+    let mut cached = match state.health.probe.lock() {
+        Ok(cached) => cached,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    *cached = Some((Instant::now(), check.clone()));
+    check
+}
+
+async fn probe_url(url: &str) -> Check {
+    let Some(addr) = host_port(url) else {
+        return Check::failed("provider", format!("cannot probe provider URL {url}"));
+    };
+    match tokio::time::timeout(PROBE_TIMEOUT, TcpStream::connect(&addr)).await {
+        Ok(Ok(_)) => Check::ok("provider", format!("{addr} reachable")),
+        Ok(Err(err)) => Check::failed("provider", format!("{addr} unreachable: {err}")),
+        Err(_) => Check::failed("provider", format!("{addr} unreachable: connect timed out")),
+    }
+}
+
+/// `host:port` to probe for a provider base URL.
+fn host_port(url: &str) -> Option<String> {
+    let (default_port, rest) = if let Some(rest) = url.strip_prefix("https://") {
+        (443, rest)
+    } else if let Some(rest) = url.strip_prefix("http://") {
+        (80, rest)
+    } else {
+        return None;
+    };
+    let authority = rest.split('/').next()?;
+    if authority.is_empty() {
+        return None;
+    }
+    if authority.contains(':') {
+        Some(authority.to_string())
+    } else {
+        Some(format!("{authority}:{default_port}"))
+    }
+}
+
+/// Enough free disk under `CODEX_HOME` to keep recording rollouts.
+#[cfg(unix)]
+fn disk_check(state: &AppState) -> Check {
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = std::ffi::CString::new(state.codex_home.as_os_str().as_bytes());
+    let Ok(path) = path else {
+        return Check::failed("disk", "codex_home is not a valid path");
+    };
+    let mut stats: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: `path` is a valid NUL-terminated string and `stats` is a
+    // properly sized statvfs buffer.
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stats) } != 0 {
+        return Check::failed(
+            "disk",
+            format!("statvfs failed: {}", std::io::Error::last_os_error()),
+        );
+    }
+    // The field widths differ between platforms.
+    #[allow(clippy::unnecessary_cast)]
+    let free = stats.f_bavail as u64 * stats.f_frsize as u64;
+    if free < MIN_FREE_DISK_BYTES {
+        Check::failed(
+            "disk",
+            format!("{free} bytes free under codex_home; need {MIN_FREE_DISK_BYTES}"),
+        )
+    } else {
+        Check::ok("disk", format!("{free} bytes free"))
+    }
+}
+
+#[cfg(not(unix))]
+fn disk_check(_state: &AppState) -> Check {
+    Check::ok("disk", "free-space check not supported on this platform")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_state;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn host_port_handles_schemes_and_ports() {
+        assert_eq!(
+            host_port("https://api.openai.com/v1"),
+            Some("api.openai.com:443".to_string())
+        );
+        assert_eq!(
+            host_port("http://localhost:11434/v1"),
+            Some("localhost:11434".to_string())
+        );
+        assert_eq!(host_port("ftp://example.com"), None);
+    }
+
+    #[tokio::test]
+    async fn malformed_config_fails_readiness_check() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let state = test_state(codex_home.path()).await;
+        std::fs::write(codex_home.path().join(CONFIG_TOML_FILE), "http_server = 3")
+            .expect("write config");
+        let check = config_check(&state).await;
+        assert!(!check.ok);
+    }
+
+    #[tokio::test]
+    async fn missing_config_and_auth_are_ok() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let state = test_state(codex_home.path()).await;
+        assert!(config_check(&state).await.ok);
+        assert!(auth_check(&state).await.ok);
+        assert!(disk_check(&state).ok);
+    }
+
+    #[tokio::test]
+    async fn provider_probe_is_cached() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let state = test_state(codex_home.path()).await;
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind");
+        let addr = listener.local_addr().expect("local addr");
+        let reachable = probe_url(&format!("http://{addr}")).await;
+        assert!(reachable.ok);
+
+        {
+            let mut cached = state.health.probe.lock().expect("lock probe");
+            *cached = Some((Instant::now(), Check::failed("provider", "cached failure")));
+        }
+        let check = provider_check(&state).await;
+        assert!(!check.ok);
+        assert_eq!(check.detail, "cached failure");
+    }
+}
@@ -0,0 +1,244 @@
+//! Server-side preprocessing of image attachments.
+//!
+//! Clients attach screenshots to `POST /conversations/{id}/complete` as
+//! base64 data URLs. Forwarded untouched, an oversized screenshot blows
+//! past provider limits, so every attachment is decoded and downscaled
+//! through the same pipeline the CLI uses (`codex-utils-image`), optionally
+//! re-encoded into one configured format, and staged as files handed to
+//! `codex exec --image`. Unsupported or malformed attachments are rejected
+//! with `400` before the turn starts. Configured under
+//! `[http_server.images]`.
+
+use std::io::Cursor;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_config::types::HttpImagesToml;
+use codex_utils_image::PromptImageMode;
+use codex_utils_image::PromptImageResizeLimits;
+use codex_utils_image::load_data_url_for_prompt;
+use image::ImageFormat;
+use tracing::warn;
+
+use crate::error::ApiError;
+
+const DEFAULT_MAX_DIMENSION: u32 = 2048;
+/// Patch budget matching the core image preparation's high-detail limits.
+const MAX_PATCHES: usize = 2_500;
+
+/// Staging area under `CODEX_HOME` for preprocessed attachments.
+const STAGING_SUBDIR: &str = "tmp";
+
+/// Downscales and re-encodes attachments per `[http_server.images]`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ImagePipeline {
+    max_dimension: u32,
+    /// Re-encode every attachment into this format when set; otherwise the
+    /// encoding the shared pipeline picks is kept.
+    format: Option<ImageFormat>,
+}
+
+impl Default for ImagePipeline {
+    fn default() -> Self {
+        Self {
+            max_dimension: DEFAULT_MAX_DIMENSION,
+            format: None,
+        }
+    }
+}
+
+impl ImagePipeline {
+    pub(crate) fn from_toml(toml: Option<&HttpImagesToml>) -> Result<Self, String> {
+        let Some(toml) = toml else {
+            return Ok(Self::default());
+        };
+        let format = match toml.format.as_deref() {
+            None => None,
+            Some("png") => Some(ImageFormat::Png),
+            Some("jpeg" | "jpg") => Some(ImageFormat::Jpeg),
+            Some("webp") => Some(ImageFormat::WebP),
+            Some(other) => {
+                return Err(format!(
+                    "unsupported http_server.images.format {other}; expected png, jpeg, or webp"
+                ));
+            }
+        };
+        Ok(Self {
+            max_dimension: toml.max_dimension.unwrap_or(DEFAULT_MAX_DIMENSION),
+            format,
+        })
+    }
+
+    /// Preprocesses every attachment and writes the results under a fresh
+    /// directory in `CODEX_HOME`, so nothing is written when any attachment
+    /// is rejected. The caller cleans the directory up after the turn.
+    pub(crate) async fn stage(
+        &self,
+        codex_home: &Path,
+        images: &[String],
+    ) -> Result<StagedImages, ApiError> {
+        let mut processed = Vec::with_capacity(images.len());
+        for image_url in images {
+            processed.push(self.process(image_url)?);
+        }
+        let dir = codex_home
+            .join(STAGING_SUBDIR)
+            .join(format!("images-{}", uuid::Uuid::new_v4()));
+        if let Err(err) = tokio::fs::create_dir_all(&dir).await {
+            return Err(ApiError::internal(format!(
+                "failed to stage attachments: {err}"
+            )));
+        }
+        let mut paths = Vec::with_capacity(processed.len());
+        for (index, (bytes, extension)) in processed.into_iter().enumerate() {
+            let path = dir.join(format!("image-{index}.{extension}"));
+            if let Err(err) = tokio::fs::write(&path, bytes).await {
+                return Err(ApiError::internal(format!(
+                    "failed to stage attachments: {err}"
+                )));
+            }
+            paths.push(path);
+        }
+        Ok(StagedImages { dir, paths })
+    }
+
+    /// One attachment's processed bytes plus the file extension to stage
+    /// them under.
+    fn process(&self, image_url: &str) -> Result<(Vec<u8>, &'static str), ApiError> {
+        let limits = PromptImageResizeLimits {
+            max_dimension: self.max_dimension,
+            max_patches: MAX_PATCHES,
+        };
+        let encoded =
+            load_data_url_for_prompt(image_url, PromptImageMode::ResizeWithLimits(limits))
+                .map_err(|err| ApiError::invalid_request(format!("unsupported image: {err}")))?;
+        match self.format {
+            Some(format) if !format_matches_mime(format, &encoded.mime) => {
+                Ok((transcode(&encoded.bytes, format)?, extension_for(format)))
+            }
+            _ => {
+                let extension =
+                    ImageFormat::from_mime_type(&encoded.mime).map_or("png", extension_for);
+                Ok((encoded.bytes.to_vec(), extension))
+            }
+        }
+    }
+}
+
+/// Preprocessed attachments on disk for the length of one turn.
+pub(crate) struct StagedImages {
+    dir: PathBuf,
+    pub(crate) paths: Vec<PathBuf>,
+}
+
+impl StagedImages {
+    /// Removes the staged files once the turn no longer needs them.
+    pub(crate) async fn cleanup(self) {
+        if let Err(err) = tokio::fs::remove_dir_all(&self.dir).await {
+            warn!("failed to remove staged attachments: {err}");
+        }
+    }
+}
+
+fn format_matches_mime(format: ImageFormat, mime: &str) -> bool {
+    ImageFormat::from_mime_type(mime) == Some(format)
+}
+
+fn extension_for(format: ImageFormat) -> &'static str {
+    match format {
+        ImageFormat::Jpeg => "jpg",
+        ImageFormat::WebP => "webp",
+        ImageFormat::Gif => "gif",
+        _ => "png",
+    }
+}
+
+/// Re-encodes already-downscaled image bytes into `format`.
+fn transcode(bytes: &[u8], format: ImageFormat) -> Result<Vec<u8>, ApiError> {
+    let decoded = image::load_from_memory(bytes)
+        .map_err(|err| ApiError::invalid_request(format!("unsupported image: {err}")))?;
+    // JPEG has no alpha channel; flatten instead of failing the encode.
+    let decoded = if format == ImageFormat::Jpeg {
+        image::DynamicImage::ImageRgb8(decoded.to_rgb8())
+    } else {
+        decoded
+    };
+    let mut out = Cursor::new(Vec::new());
+    decoded
+        .write_to(&mut out, format)
+        .map_err(|err| ApiError::internal(format!("failed to re-encode image: {err}")))?;
+    Ok(out.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+    use codex_utils_image::data_url_from_bytes;
+    use pretty_assertions::assert_eq;
+
+    fn png_data_url(width: u32, height: u32) -> String {
+        let mut bytes = Cursor::new(Vec::new());
+        image::DynamicImage::ImageRgb8(image::RgbImage::new(width, height))
+            .write_to(&mut bytes, ImageFormat::Png)
+            .expect("encode test image");
+        data_url_from_bytes("image/png", &bytes.into_inner())
+    }
+
+    #[tokio::test]
+    async fn attachments_are_downscaled_and_staged() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let pipeline = ImagePipeline {
+            max_dimension: 8,
+            format: None,
+        };
+        let staged = pipeline
+            .stage(codex_home.path(), &[png_data_url(64, 48)])
+            .await
+            .expect("stage attachment");
+        assert_eq!(staged.paths.len(), 1);
+        let bytes = std::fs::read(&staged.paths[0]).expect("read staged image");
+        let staged_image = image::load_from_memory(&bytes).expect("decode staged image");
+        assert!(staged_image.width() <= 8 && staged_image.height() <= 8);
+        staged.cleanup().await;
+    }
+
+    #[tokio::test]
+    async fn a_configured_format_transcodes_the_attachment() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let pipeline = ImagePipeline {
+            max_dimension: 64,
+            format: Some(ImageFormat::Jpeg),
+        };
+        let staged = pipeline
+            .stage(codex_home.path(), &[png_data_url(16, 16)])
+            .await
+            .expect("stage attachment");
+        assert!(staged.paths[0].to_string_lossy().ends_with(".jpg"));
+        let bytes = std::fs::read(&staged.paths[0]).expect("read staged image");
+        assert_eq!(image::guess_format(&bytes).ok(), Some(ImageFormat::Jpeg));
+        staged.cleanup().await;
+    }
+
+    #[tokio::test]
+    async fn non_image_attachments_are_rejected() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let err = ImagePipeline::default()
+            .stage(
+                codex_home.path(),
+                &["data:text/plain;base64,aGk=".to_string()],
+            )
+            .await
+            .expect_err("should be rejected");
+        assert_eq!(err.code(), ErrorCode::InvalidRequest);
+    }
+
+    #[test]
+    fn unknown_configured_formats_are_rejected() {
+        let toml = HttpImagesToml {
+            max_dimension: None,
+            format: Some("tiff".to_string()),
+        };
+        assert!(ImagePipeline::from_toml(Some(&toml)).is_err());
+    }
+}
@@ -0,0 +1,666 @@
+//! Queued non-interactive conversations executed by a bounded worker pool.
+//!
+//! `POST /jobs` enqueues a prompt plus working directory and config
+//! overrides; N workers drain the queue by running `codex exec` per job.
+//! Jobs move queued → running → done/failed and every transition is written
+//! through to [`Storage`], so a restart re-queues pending work instead of
+//! dropping it.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use std::time::Duration;
+
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::sync::mpsc;
+use tracing::info;
+use tracing::warn;
+
+use crate::events::EventBus;
+use crate::events::ServerEvent;
+use crate::runner::ConversationRunner;
+use crate::storage::Storage;
+use crate::worktree;
+use crate::worktree::WorktreeState;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Done,
+    Failed,
+}
+
+/// What to run: the payload of `POST /jobs`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JobSpec {
+    /// Prompt text; filled in from `template` when one is referenced.
+    #[serde(default)]
+    pub prompt: String,
+    /// Named prompt template expanded into `prompt` at creation time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Values for the template's `{variable}` placeholders.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub vars: HashMap<String, String>,
+    /// Repository (working directory) the conversation runs in.
+    pub cwd: Option<PathBuf>,
+    /// `-c key=value` config overrides passed through to the conversation.
+    #[serde(default)]
+    pub config_overrides: Vec<String>,
+    /// Run in a dedicated git worktree and branch created from `cwd`, so
+    /// concurrent jobs on one repo don't trample each other's working tree.
+    #[serde(default)]
+    pub use_worktree: bool,
+    /// Cap, in seconds, on this job's turn; overrides the server default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_turn_seconds: Option<u64>,
+    /// Reasoning effort for this run only (e.g. `low`, `medium`, `high`,
+    /// `xhigh`); the conversation's configured default is untouched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<String>,
+    /// Final-answer verbosity for this run only (`low`, `medium`, `high`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verbosity: Option<String>,
+}
+
+impl JobSpec {
+    /// The caller's raw `-c` overrides plus the per-run model knobs rendered
+    /// as overrides, so they apply to this run without changing any
+    /// persisted configuration.
+    pub(crate) fn effective_config_overrides(&self) -> Vec<String> {
+        let mut overrides = self.config_overrides.clone();
+        if let Some(effort) = &self.reasoning_effort {
+            overrides.push(format!("model_reasoning_effort={effort:?}"));
+        }
+        if let Some(verbosity) = &self.verbosity {
+            overrides.push(format!("model_verbosity={verbosity:?}"));
+        }
+        overrides
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: u64,
+    #[serde(flatten)]
+    pub spec: JobSpec,
+    pub status: JobStatus,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Tail of the conversation output, or the failure reason.
+    pub result: Option<String>,
+    /// Worktree the job ran in, when created with `use_worktree`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worktree: Option<WorktreeState>,
+}
+
+impl Job {
+    pub(crate) fn queued(id: u64, spec: JobSpec) -> Self {
+        Self {
+            id,
+            spec,
+            status: JobStatus::Queued,
+            created_at: Utc::now(),
+            started_at: None,
+            finished_at: None,
+            result: None,
+            worktree: None,
+        }
+    }
+}
+
+#[derive(Default)]
+struct JobQueueState {
+    next_job_id: u64,
+    jobs: HashMap<u64, Job>,
+}
+
+/// Shared job registry; workers pull ids off the channel in FIFO order.
+#[derive(Clone)]
+pub(crate) struct JobQueue {
+    state: Arc<Mutex<JobQueueState>>,
+    tx: mpsc::UnboundedSender<u64>,
+    rx: Arc<tokio::sync::Mutex<mpsc::UnboundedReceiver<u64>>>,
+    runner: Arc<dyn ConversationRunner>,
+    storage: Arc<dyn Storage>,
+    events: Arc<dyn EventBus>,
+    worktrees_dir: PathBuf,
+    /// Server-wide default for [`JobSpec::max_turn_seconds`].
+    max_turn_seconds: Option<u64>,
+}
+
+impl JobQueue {
+    /// Loads persisted jobs from `storage`, re-queueing jobs that were still
+    /// pending and failing jobs interrupted mid-run by the restart.
+    pub(crate) async fn load(
+        codex_home: &Path,
+        storage: Arc<dyn Storage>,
+        events: Arc<dyn EventBus>,
+        runner: Arc<dyn ConversationRunner>,
+        max_turn_seconds: Option<u64>,
+    ) -> Self {
+        let loaded = match storage.load_jobs().await {
+            Ok(jobs) => jobs,
+            Err(err) => {
+                warn!("failed to load persisted jobs: {err}");
+                Vec::new()
+            }
+        };
+        let (tx, rx) = mpsc::unbounded_channel();
+        let mut state = JobQueueState::default();
+        let mut interrupted: Vec<u64> = Vec::new();
+        for mut job in loaded {
+            state.next_job_id = state.next_job_id.max(job.id);
+            match job.status {
+                JobStatus::Queued => {
+                    let _ = tx.send(job.id);
+                }
+                JobStatus::Running => {
+                    job.status = JobStatus::Failed;
+                    job.finished_at = Some(Utc::now());
+                    job.result = Some("interrupted by server restart".to_string());
+                    interrupted.push(job.id);
+                }
+                JobStatus::Done | JobStatus::Failed => {}
+            }
+            state.jobs.insert(job.id, job);
+        }
+        let queue = Self {
+            state: Arc::new(Mutex::new(state)),
+            tx,
+            rx: Arc::new(tokio::sync::Mutex::new(rx)),
+            runner,
+            storage,
+            events,
+            worktrees_dir: codex_home.join("worktrees"),
+            max_turn_seconds,
+        };
+        for id in interrupted {
+            queue.save_job(id).await;
+        }
+        queue
+    }
+
+    /// Spawns `worker_count` workers draining the queue.
+    pub(crate) fn start_workers(&self, worker_count: usize) {
+        for _ in 0..worker_count.max(1) {
+            let queue = self.clone();
+            tokio::spawn(async move {
+                loop {
+                    let id = {
+                        let mut rx = queue.rx.lock().await;
+                        rx.recv().await
+                    };
+                    let Some(id) = id else {
+                        break;
+                    };
+                    queue.run_job(id).await;
+                }
+            });
+        }
+    }
+
+    pub(crate) async fn enqueue(&self, spec: JobSpec) -> Job {
+        let job = {
+            let mut state = self.lock_state();
+            state.next_job_id += 1;
+            let job = Job::queued(state.next_job_id, spec);
+            state.jobs.insert(job.id, job.clone());
+            job
+        };
+        self.save_job(job.id).await;
+        let _ = self.tx.send(job.id);
+        job
+    }
+
+    pub(crate) fn get(&self, id: u64) -> Option<Job> {
+        self.lock_state().jobs.get(&id).cloned()
+    }
+
+    pub(crate) fn list(&self) -> Vec<Job> {
+        let mut jobs: Vec<Job> = self.lock_state().jobs.values().cloned().collect();
+        jobs.sort_by_key(|job| job.id);
+        jobs
+    }
+
+    async fn run_job(&self, id: u64) {
+        let spec = {
+            let mut state = self.lock_state();
+            let Some(job) = state.jobs.get_mut(&id) else {
+                return;
+            };
+            // Re-queued duplicates or externally mutated entries: only a
+            // queued job may start running.
+            if job.status != JobStatus::Queued {
+                return;
+            }
+            job.status = JobStatus::Running;
+            job.started_at = Some(Utc::now());
+            info!(job = id, "starting queued conversation");
+            job.spec.clone()
+        };
+        self.save_job(id).await;
+        let run_cwd = if spec.use_worktree {
+            match self.create_worktree(id, &spec).await {
+                Ok(worktree) => Some(worktree.path),
+                Err(message) => {
+                    self.finish_job(id, false, message).await;
+                    return;
+                }
+            }
+        } else {
+            spec.cwd.clone()
+        };
+        let overrides = spec.effective_config_overrides();
+        let run = self
+            .runner
+            .run(&spec.prompt, run_cwd.as_deref(), &overrides);
+        match spec.max_turn_seconds.or(self.max_turn_seconds) {
+            Some(secs) => match tokio::time::timeout(Duration::from_secs(secs), run).await {
+                Ok(outcome) => self.finish_job(id, outcome.success, outcome.detail).await,
+                Err(_) => {
+                    self.events
+                        .publish(ServerEvent {
+                            kind: "job.timeout".to_string(),
+                            payload: serde_json::json!({ "id": id, "max_turn_seconds": secs }),
+                        })
+                        .await;
+                    self.finish_job(
+                        id,
+                        false,
+                        format!("turn timed out after {secs}s and was interrupted"),
+                    )
+                    .await;
+                }
+            },
+            None => {
+                let outcome = run.await;
+                self.finish_job(id, outcome.success, outcome.detail).await;
+            }
+        }
+    }
+
+    /// Creates the job's dedicated worktree and branch off `spec.cwd` and
+    /// records it on the job.
+    async fn create_worktree(&self, id: u64, spec: &JobSpec) -> Result<WorktreeState, String> {
+        let Some(base_repo) = spec.cwd.clone() else {
+            return Err("use_worktree requires cwd to point at a git repository".to_string());
+        };
+        let path = self.worktrees_dir.join(format!("job-{id}"));
+        let branch = format!("codex/job-{id}");
+        worktree::create(&base_repo, &path, &branch).await?;
+        let worktree = WorktreeState {
+            path,
+            branch,
+            base_repo,
+        };
+        {
+            let mut state = self.lock_state();
+            if let Some(job) = state.jobs.get_mut(&id) {
+                job.worktree = Some(worktree.clone());
+            }
+        }
+        self.save_job(id).await;
+        Ok(worktree)
+    }
+
+    /// Forgets a job's worktree after it has been discarded.
+    pub(crate) async fn clear_worktree(&self, id: u64) {
+        {
+            let mut state = self.lock_state();
+            if let Some(job) = state.jobs.get_mut(&id) {
+                job.worktree = None;
+            }
+        }
+        self.save_job(id).await;
+    }
+
+    async fn finish_job(&self, id: u64, success: bool, detail: String) {
+        {
+            let mut state = self.lock_state();
+            if let Some(job) = state.jobs.get_mut(&id) {
+                job.status = if success {
+                    JobStatus::Done
+                } else {
+                    JobStatus::Failed
+                };
+                job.finished_at = Some(Utc::now());
+                job.result = Some(detail);
+            }
+        }
+        self.save_job(id).await;
+    }
+
+    /// Writes one job through to storage and announces the new state on the
+    /// event bus; best-effort, the queue stays usable if either fails.
+    async fn save_job(&self, id: u64) {
+        let Some(job) = self.get(id) else {
+            return;
+        };
+        if let Err(err) = self.storage.save_job(&job).await {
+            warn!(job = id, "failed to persist job: {err}");
+        }
+        let kind = match job.status {
+            JobStatus::Queued => "job.queued",
+            JobStatus::Running => "job.running",
+            JobStatus::Done => "job.done",
+            JobStatus::Failed => "job.failed",
+        };
+        self.events
+            .publish(ServerEvent {
+                kind: kind.to_string(),
+                payload: serde_json::to_value(&job).unwrap_or_default(),
+            })
+            .await;
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, JobQueueState> {
+        match self.state.lock() {
+            Ok(state) => state,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::LocalEventBus;
+    use crate::runner::RunOutcome;
+    use crate::storage::SqliteStorage;
+    use async_trait::async_trait;
+    use pretty_assertions::assert_eq;
+
+    struct StaticRunner {
+        success: bool,
+    }
+
+    #[async_trait]
+    impl ConversationRunner for StaticRunner {
+        async fn run(
+            &self,
+            _prompt: &str,
+            _cwd: Option<&Path>,
+            _config_overrides: &[String],
+        ) -> RunOutcome {
+            RunOutcome {
+                success: self.success,
+                detail: "output".to_string(),
+            }
+        }
+    }
+
+    async fn queue(codex_home: &Path, success: bool) -> JobQueue {
+        let storage = Arc::new(SqliteStorage::open(codex_home).await.expect("open storage"));
+        JobQueue::load(
+            codex_home,
+            storage,
+            Arc::new(LocalEventBus::new()),
+            Arc::new(StaticRunner { success }),
+            None,
+        )
+        .await
+    }
+
+    #[test]
+    fn model_knobs_render_as_per_run_overrides() {
+        let spec = JobSpec {
+            prompt: "think hard".to_string(),
+            config_overrides: vec!["model=\"gpt-5.1\"".to_string()],
+            reasoning_effort: Some("xhigh".to_string()),
+            verbosity: Some("low".to_string()),
+            ..JobSpec::default()
+        };
+        assert_eq!(
+            spec.effective_config_overrides(),
+            vec![
+                "model=\"gpt-5.1\"".to_string(),
+                "model_reasoning_effort=\"xhigh\"".to_string(),
+                "model_verbosity=\"low\"".to_string(),
+            ]
+        );
+    }
+
+    fn spec(prompt: &str) -> JobSpec {
+        JobSpec {
+            prompt: prompt.to_string(),
+            ..JobSpec::default()
+        }
+    }
+
+    async fn wait_for_finish(queue: &JobQueue, id: u64) -> Job {
+        for _ in 0..300 {
+            if let Some(job) = queue.get(id)
+                && matches!(job.status, JobStatus::Done | JobStatus::Failed)
+            {
+                return job;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("job {id} did not finish");
+    }
+
+    #[tokio::test]
+    async fn job_transitions_to_done() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let queue = queue(codex_home.path(), true).await;
+        queue.start_workers(2);
+        let job = queue.enqueue(spec("fix the bug")).await;
+        assert_eq!(job.status, JobStatus::Queued);
+        let finished = wait_for_finish(&queue, job.id).await;
+        assert_eq!(finished.status, JobStatus::Done);
+        assert_eq!(finished.result.as_deref(), Some("output"));
+        assert!(finished.started_at.is_some());
+        assert!(finished.finished_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn failed_run_marks_job_failed() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let queue = queue(codex_home.path(), false).await;
+        queue.start_workers(1);
+        let job = queue.enqueue(spec("doomed")).await;
+        let finished = wait_for_finish(&queue, job.id).await;
+        assert_eq!(finished.status, JobStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn jobs_survive_restart() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let first = queue(codex_home.path(), true).await;
+        // No workers: the job stays queued when the "server" goes away.
+        let queued = first.enqueue(spec("pending work")).await;
+        drop(first);
+
+        let second = queue(codex_home.path(), true).await;
+        let reloaded = second.get(queued.id).expect("job persisted");
+        assert_eq!(reloaded.status, JobStatus::Queued);
+        second.start_workers(1);
+        let finished = wait_for_finish(&second, queued.id).await;
+        assert_eq!(finished.status, JobStatus::Done);
+    }
+
+    #[tokio::test]
+    async fn interrupted_running_job_is_failed_on_reload() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let first = queue(codex_home.path(), true).await;
+        let job = first.enqueue(spec("long running")).await;
+        {
+            let mut state = first.lock_state();
+            let running = state.jobs.get_mut(&job.id).expect("job exists");
+            running.status = JobStatus::Running;
+        }
+        first.save_job(job.id).await;
+        drop(first);
+
+        let second = queue(codex_home.path(), true).await;
+        let reloaded = second.get(job.id).expect("job persisted");
+        assert_eq!(reloaded.status, JobStatus::Failed);
+        assert_eq!(
+            reloaded.result.as_deref(),
+            Some("interrupted by server restart")
+        );
+    }
+
+    #[tokio::test]
+    async fn worktree_without_cwd_fails_the_job() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let queue = queue(codex_home.path(), true).await;
+        queue.start_workers(1);
+        let job = queue
+            .enqueue(JobSpec {
+                use_worktree: true,
+                ..spec("isolated work")
+            })
+            .await;
+        let finished = wait_for_finish(&queue, job.id).await;
+        assert_eq!(finished.status, JobStatus::Failed);
+        assert_eq!(
+            finished.result.as_deref(),
+            Some("use_worktree requires cwd to point at a git repository")
+        );
+    }
+
+    /// Runner that never finishes on its own.
+    struct StuckRunner;
+
+    #[async_trait]
+    impl ConversationRunner for StuckRunner {
+        async fn run(
+            &self,
+            _prompt: &str,
+            _cwd: Option<&Path>,
+            _config_overrides: &[String],
+        ) -> RunOutcome {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            RunOutcome {
+                success: true,
+                detail: String::new(),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn job_exceeding_max_turn_is_interrupted() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let storage = Arc::new(
+            SqliteStorage::open(codex_home.path())
+                .await
+                .expect("open storage"),
+        );
+        let events = Arc::new(LocalEventBus::new());
+        let mut rx = events.subscribe();
+        let queue = JobQueue::load(
+            codex_home.path(),
+            storage,
+            events,
+            Arc::new(StuckRunner),
+            None,
+        )
+        .await;
+        queue.start_workers(1);
+        let job = queue
+            .enqueue(JobSpec {
+                max_turn_seconds: Some(1),
+                ..spec("runaway loop")
+            })
+            .await;
+        let finished = wait_for_finish(&queue, job.id).await;
+        assert_eq!(finished.status, JobStatus::Failed);
+        assert_eq!(
+            finished.result.as_deref(),
+            Some("turn timed out after 1s and was interrupted")
+        );
+        let mut kinds = Vec::new();
+        while let Some(event) = rx.try_next() {
+            kinds.push(event.kind);
+        }
+        assert!(kinds.contains(&"job.timeout".to_string()));
+    }
+
+    /// Runner that records the working directory it was invoked with.
+    struct CwdRecordingRunner {
+        cwd: Arc<Mutex<Option<PathBuf>>>,
+    }
+
+    #[async_trait]
+    impl ConversationRunner for CwdRecordingRunner {
+        async fn run(
+            &self,
+            _prompt: &str,
+            cwd: Option<&Path>,
+            _config_overrides: &[String],
+        ) -> RunOutcome {
+            *self.cwd.lock().expect("lock cwd") = cwd.map(Path::to_path_buf);
+            RunOutcome {
+                success: true,
+                detail: String::new(),
+            }
+        }
+    }
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = std::process::Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .status()
+            .expect("run git");
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    #[tokio::test]
+    async fn worktree_job_runs_in_its_own_checkout() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let repo = tempfile::tempdir().expect("create tempdir");
+        git(repo.path(), &["init", "--initial-branch=main"]);
+        git(repo.path(), &["config", "user.email", "test@example.com"]);
+        git(repo.path(), &["config", "user.name", "Test"]);
+        std::fs::write(repo.path().join("README.md"), "hello\n").expect("write file");
+        git(repo.path(), &["add", "-A"]);
+        git(repo.path(), &["commit", "-m", "init"]);
+
+        let storage = Arc::new(
+            SqliteStorage::open(codex_home.path())
+                .await
+                .expect("open storage"),
+        );
+        let seen_cwd = Arc::new(Mutex::new(None));
+        let queue = JobQueue::load(
+            codex_home.path(),
+            storage,
+            Arc::new(LocalEventBus::new()),
+            Arc::new(CwdRecordingRunner {
+                cwd: seen_cwd.clone(),
+            }),
+            None,
+        )
+        .await;
+        queue.start_workers(1);
+        let job = queue
+            .enqueue(JobSpec {
+                cwd: Some(repo.path().to_path_buf()),
+                use_worktree: true,
+                ..spec("isolated work")
+            })
+            .await;
+        let finished = wait_for_finish(&queue, job.id).await;
+        assert_eq!(finished.status, JobStatus::Done);
+        let worktree = finished.worktree.expect("worktree recorded");
+        assert_eq!(worktree.branch, format!("codex/job-{}", job.id));
+        assert_eq!(
+            seen_cwd.lock().expect("lock cwd").clone(),
+            Some(worktree.path.clone())
+        );
+        assert!(worktree.path.join("README.md").exists());
+    }
+}
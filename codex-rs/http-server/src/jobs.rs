@@ -0,0 +1,310 @@
+//! Handlers for the `/jobs` routes.
+
+use axum::Json;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::http::header::CONTENT_TYPE;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use serde::Deserialize;
+
+use crate::AppState;
+use crate::error::ApiError;
+use crate::job_queue::Job;
+use crate::job_queue::JobSpec;
+use crate::storage::audit;
+use crate::templates::render_template;
+use crate::worktree;
+use crate::worktree::WorktreeState;
+
+/// Reasoning efforts accepted for [`JobSpec::reasoning_effort`].
+const REASONING_EFFORTS: &[&str] = &[
+    "none", "minimal", "low", "medium", "high", "xhigh", "max", "ultra",
+];
+
+/// Verbosity levels accepted for [`JobSpec::verbosity`].
+const VERBOSITY_LEVELS: &[&str] = &["low", "medium", "high"];
+
+/// `POST /jobs`
+pub(crate) async fn create_job(
+    State(state): State<AppState>,
+    Json(mut spec): Json<JobSpec>,
+) -> Response {
+    if let Some(template) = spec.template.clone() {
+        let Some(prompt) = state.templates.get(&template) else {
+            return ApiError::invalid_request(format!("unknown template {template}"))
+                .into_response();
+        };
+        match render_template(&prompt, &spec.vars) {
+            Ok(rendered) => spec.prompt = rendered,
+            Err(message) => return ApiError::invalid_request(message).into_response(),
+        }
+    }
+    if spec.prompt.trim().is_empty() {
+        return ApiError::invalid_request("prompt must not be empty").into_response();
+    }
+    if let Some(effort) = &spec.reasoning_effort
+        && !REASONING_EFFORTS.contains(&effort.as_str())
+    {
+        return ApiError::invalid_request(format!(
+            "unknown reasoning_effort {effort}; expected one of {}",
+            REASONING_EFFORTS.join(", ")
+        ))
+        .into_response();
+    }
+    if let Some(verbosity) = &spec.verbosity
+        && !VERBOSITY_LEVELS.contains(&verbosity.as_str())
+    {
+        return ApiError::invalid_request(format!(
+            "unknown verbosity {verbosity}; expected one of {}",
+            VERBOSITY_LEVELS.join(", ")
+        ))
+        .into_response();
+    }
+    let job = state.job_queue.enqueue(spec).await;
+    audit(&*state.storage, "job.create", &format!("job {}", job.id)).await;
+    (StatusCode::CREATED, Json(job)).into_response()
+}
+
+/// `GET /jobs`
+pub(crate) async fn list_jobs(State(state): State<AppState>) -> Json<Vec<Job>> {
+    Json(state.job_queue.list())
+}
+
+/// `GET /jobs/{id}`
+pub(crate) async fn get_job(State(state): State<AppState>, Path(id): Path<u64>) -> Response {
+    match state.job_queue.get(id) {
+        Some(job) => Json(job).into_response(),
+        None => ApiError::not_found(format!("no job with id {id}")).into_response(),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CommitRequest {
+    message: String,
+}
+
+/// `GET /jobs/{id}/diff`
+///
+/// Diff of everything the job changed in its worktree.
+pub(crate) async fn job_diff(State(state): State<AppState>, Path(id): Path<u64>) -> Response {
+    let worktree = match job_worktree(&state, id) {
+        Ok(worktree) => worktree,
+        Err(response) => return response,
+    };
+    match worktree::diff(&worktree.path).await {
+        Ok(diff) => ([(CONTENT_TYPE, "text/plain; charset=utf-8")], diff).into_response(),
+        Err(message) => ApiError::internal(message).into_response(),
+    }
+}
+
+/// `POST /jobs/{id}/commit`
+///
+/// Stages and commits all of the worktree's changes.
+pub(crate) async fn commit_job_worktree(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+    Json(request): Json<CommitRequest>,
+) -> Response {
+    if request.message.trim().is_empty() {
+        return ApiError::invalid_request("message must not be empty").into_response();
+    }
+    let worktree = match job_worktree(&state, id) {
+        Ok(worktree) => worktree,
+        Err(response) => return response,
+    };
+    match worktree::commit(&worktree.path, &request.message).await {
+        Ok(summary) => {
+            audit(&*state.storage, "worktree.commit", &format!("job {id}")).await;
+            summary.into_response()
+        }
+        Err(message) => ApiError::internal(message).into_response(),
+    }
+}
+
+/// `POST /jobs/{id}/push`
+///
+/// Pushes the worktree's branch to `origin`.
+pub(crate) async fn push_job_worktree(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+) -> Response {
+    let worktree = match job_worktree(&state, id) {
+        Ok(worktree) => worktree,
+        Err(response) => return response,
+    };
+    match worktree::push(&worktree.path, &worktree.branch).await {
+        Ok(summary) => {
+            audit(&*state.storage, "worktree.push", &format!("job {id}")).await;
+            summary.into_response()
+        }
+        Err(message) => ApiError::internal(message).into_response(),
+    }
+}
+
+/// `DELETE /jobs/{id}/worktree`
+///
+/// Removes the worktree and its branch, discarding any uncommitted work.
+pub(crate) async fn discard_job_worktree(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+) -> Response {
+    let worktree = match job_worktree(&state, id) {
+        Ok(worktree) => worktree,
+        Err(response) => return response,
+    };
+    match worktree::discard(&worktree.base_repo, &worktree.path, &worktree.branch).await {
+        Ok(()) => {
+            state.job_queue.clear_worktree(id).await;
+            audit(&*state.storage, "worktree.discard", &format!("job {id}")).await;
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Err(message) => ApiError::internal(message).into_response(),
+    }
+}
+
+fn job_worktree(state: &AppState, id: u64) -> Result<WorktreeState, Response> {
+    let Some(job) = state.job_queue.get(id) else {
+        return Err(ApiError::not_found(format!("no job with id {id}")).into_response());
+    };
+    job.worktree
+        .ok_or_else(|| ApiError::invalid_state(format!("job {id} has no worktree")).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_state;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn create_then_fetch_job() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let state = test_state(codex_home.path()).await;
+        let response = create_job(
+            State(state.clone()),
+            Json(JobSpec {
+                prompt: "triage the failing build".to_string(),
+                ..JobSpec::default()
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let jobs = list_jobs(State(state.clone())).await.0;
+        assert_eq!(jobs.len(), 1);
+        let fetched = get_job(State(state), Path(jobs[0].id)).await;
+        assert_eq!(fetched.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn empty_prompt_is_rejected() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let response = create_job(
+            State(test_state(codex_home.path()).await),
+            Json(JobSpec {
+                prompt: "  ".to_string(),
+                ..JobSpec::default()
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn template_fills_in_the_prompt() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let state = test_state(codex_home.path()).await;
+        state
+            .templates
+            .insert("triage-bug".to_string(), "triage {issue}".to_string())
+            .await;
+        let response = create_job(
+            State(state.clone()),
+            Json(JobSpec {
+                template: Some("triage-bug".to_string()),
+                vars: HashMap::from([("issue".to_string(), "#42".to_string())]),
+                ..JobSpec::default()
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(state.job_queue.list()[0].spec.prompt, "triage #42");
+    }
+
+    #[tokio::test]
+    async fn unknown_template_is_rejected() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let response = create_job(
+            State(test_state(codex_home.path()).await),
+            Json(JobSpec {
+                template: Some("missing".to_string()),
+                ..JobSpec::default()
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn unknown_reasoning_effort_is_rejected() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let response = create_job(
+            State(test_state(codex_home.path()).await),
+            Json(JobSpec {
+                prompt: "think hard".to_string(),
+                reasoning_effort: Some("extreme".to_string()),
+                ..JobSpec::default()
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn per_run_model_knobs_are_accepted() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let response = create_job(
+            State(test_state(codex_home.path()).await),
+            Json(JobSpec {
+                prompt: "think hard".to_string(),
+                reasoning_effort: Some("xhigh".to_string()),
+                verbosity: Some("low".to_string()),
+                ..JobSpec::default()
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn unknown_job_is_not_found() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let response = get_job(State(test_state(codex_home.path()).await), Path(99)).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn diff_for_unknown_job_is_not_found() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let response = job_diff(State(test_state(codex_home.path()).await), Path(99)).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn diff_for_job_without_worktree_is_a_conflict() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let state = test_state(codex_home.path()).await;
+        let job = state
+            .job_queue
+            .enqueue(JobSpec {
+                prompt: "no isolation".to_string(),
+                ..JobSpec::default()
+            })
+            .await;
+        let response = job_diff(State(state), Path(job.id)).await;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+}
@@ -0,0 +1,503 @@
+//! HTTP server exposing recorded Codex conversations over REST.
+//!
+//! The server serves transcripts straight from the rollout files under
+//! `CODEX_HOME` so conversations can be shared with tools that speak HTTP
+//! instead of the app-server protocol, runs the schedule subsystem that
+//! starts cron-triggered conversations, and drains a persistent job queue
+//! with a bounded worker pool.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use axum::Router;
+use axum::extract::DefaultBodyLimit;
+use axum::routing::delete;
+use axum::routing::get;
+use axum::routing::post;
+use axum::routing::put;
+use codex_config::types::HttpArchiveToml;
+use codex_config::types::HttpChatopsToml;
+use codex_config::types::HttpImagesToml;
+use codex_config::types::HttpInstructionsToml;
+use codex_config::types::HttpLimitsToml;
+use codex_config::types::HttpNotifyToml;
+use codex_config::types::HttpQuotaToml;
+use codex_config::types::HttpRedactionToml;
+use codex_config::types::HttpSandboxLimitsToml;
+use codex_config::types::HttpScheduleToml;
+use codex_config::types::HttpTemplateToml;
+use codex_model_provider_info::ModelProviderInfo;
+use tokio::net::TcpListener;
+use tracing::warn;
+
+mod archive;
+mod artifacts;
+mod audit;
+mod chatops;
+mod complete;
+mod conversations;
+mod cron;
+mod error;
+mod events;
+mod exec;
+mod github;
+mod health;
+mod images;
+mod instructions;
+mod job_queue;
+mod jobs;
+mod limits;
+mod mcp_servers;
+mod notify;
+mod offload;
+mod providers;
+mod quota;
+mod recordings;
+mod redact;
+mod reload;
+mod retry;
+mod runner;
+mod sandbox;
+mod scheduler;
+mod schedules;
+mod search;
+pub mod selftest;
+mod storage;
+mod templates;
+mod trace;
+mod turn_gate;
+mod turns;
+mod worktree;
+
+// Wire types shared with `codex-http-server-client`, so the client cannot
+// drift from what the handlers accept and return.
+pub use complete::CompleteRequest;
+pub use conversations::ConversationCreated;
+pub use conversations::NewConversationRequest;
+pub use error::ErrorBody;
+pub use error::ErrorCode;
+pub use events::ServerEvent;
+pub use instructions::InstructionOverrides;
+pub use job_queue::Job;
+pub use job_queue::JobSpec;
+pub use job_queue::JobStatus;
+pub use scheduler::RunStatus;
+pub use scheduler::Schedule;
+pub use scheduler::ScheduleRun;
+pub use schedules::CreateScheduleRequest;
+pub use storage::AuditEntry;
+pub use templates::PromptTemplate;
+pub use worktree::WorktreeState;
+
+use events::EventBus;
+use events::LocalEventBus;
+use events::RedisEventBus;
+use health::HealthCache;
+use job_queue::JobQueue;
+use providers::ProviderRegistry;
+use recordings::RecordingSessions;
+use reload::ReloadableSettings;
+use reload::SharedSettings;
+use runner::CodexExecRunner;
+use runner::ConversationRunner;
+use scheduler::Scheduler;
+use storage::SqliteStorage;
+use storage::Storage;
+use templates::TemplateStore;
+use turn_gate::TurnGate;
+use turn_gate::TurnPriority;
+
+/// Settings for [`serve`].
+pub struct ServerConfig {
+    /// Codex home directory holding recorded sessions.
+    pub codex_home: PathBuf,
+    /// Schedules from `[[http_server.schedules]]` in config.toml.
+    pub schedules: Vec<HttpScheduleToml>,
+    /// Number of workers draining the job queue.
+    pub job_workers: usize,
+    /// Binary spawned for scheduled and queued conversations (usually `codex`).
+    pub codex_bin: PathBuf,
+    /// GitHub token used to push branches and open pull requests.
+    pub github_token: Option<String>,
+    /// Prompt templates from `[[http_server.templates]]` in config.toml.
+    pub templates: Vec<HttpTemplateToml>,
+    /// Event-bus URL (`redis://host:port`) for cross-replica event fanout.
+    pub event_bus: Option<String>,
+    /// Maximum sandbox permissions grantable to a single conversation.
+    pub sandbox_limits: HttpSandboxLimitsToml,
+    /// Merged provider definitions (built-ins plus config.toml).
+    pub model_providers: HashMap<String, ModelProviderInfo>,
+    /// Default cap, in seconds, on one queued conversation turn.
+    pub max_turn_seconds: Option<u64>,
+    /// Cap on concurrent model turns across the whole process.
+    pub max_concurrent_turns: Option<usize>,
+    /// Object-storage archival of idle conversations
+    /// (`[http_server.archive]`); unset disables the archiver.
+    pub archive: Option<HttpArchiveToml>,
+    /// Webhooks notified of job and sandbox lifecycle events
+    /// (`[http_server.notify]`); unset disables notifications.
+    pub notify: Option<HttpNotifyToml>,
+    /// Payload size limits (`[http_server.limits]`); unset fields keep
+    /// the built-in defaults.
+    pub limits: Option<HttpLimitsToml>,
+    /// Attachment preprocessing (`[http_server.images]`); unset fields
+    /// keep the built-in defaults.
+    pub images: Option<HttpImagesToml>,
+    /// Slack chat-ops bridge for sandbox grant approvals
+    /// (`[http_server.chatops]`); unset disables the bridge.
+    pub chatops: Option<HttpChatopsToml>,
+    /// Disk bounds on conversation workspaces (`[http_server.quota]`);
+    /// unset disables enforcement.
+    pub quota: Option<HttpQuotaToml>,
+    /// Redaction applied to transcripts and events the server hands out
+    /// (`[http_server.redaction]`); unset keeps the built-in secret
+    /// formats.
+    pub redaction: Option<HttpRedactionToml>,
+    /// Policy for the instruction overrides `POST /conversations` accepts
+    /// (`[http_server.instructions]`); unset rejects all overrides.
+    pub instructions: Option<HttpInstructionsToml>,
+}
+
+/// State shared by all request handlers.
+#[derive(Clone)]
+pub(crate) struct AppState {
+    pub(crate) codex_home: PathBuf,
+    /// Binary shelled out to for MCP diagnostics; turns go through `runner`.
+    pub(crate) codex_bin: PathBuf,
+    pub(crate) scheduler: Scheduler,
+    pub(crate) job_queue: JobQueue,
+    pub(crate) templates: TemplateStore,
+    pub(crate) storage: Arc<dyn Storage>,
+    pub(crate) events: Arc<dyn EventBus>,
+    /// Settings that `POST /admin/reload` can swap without a restart.
+    pub(crate) settings: SharedSettings,
+    pub(crate) providers: ProviderRegistry,
+    pub(crate) health: HealthCache,
+    /// Runner replays re-execute recorded conversations through.
+    pub(crate) runner: Arc<dyn ConversationRunner>,
+    pub(crate) recording_sessions: RecordingSessions,
+    /// Uploads idle conversations to object storage; `None` when
+    /// `[http_server.archive]` is not configured.
+    pub(crate) archiver: Option<Arc<archive::Archiver>>,
+    /// Posts lifecycle webhooks; `None` when `[http_server.notify]` is
+    /// not configured.
+    pub(crate) notifier: Option<Arc<notify::Notifier>>,
+    /// Which conversations have a turn running, for busy `409`s and
+    /// force interrupts.
+    pub(crate) active_turns: turns::ActiveTurns,
+    /// Payload size limits enforced by the router and handlers.
+    pub(crate) limits: limits::Limits,
+    /// Preprocessing applied to image attachments before a turn sees them.
+    pub(crate) images: images::ImagePipeline,
+    /// Last restart probe per MCP server, shown in `GET /mcp-servers`.
+    pub(crate) mcp_probes: mcp_servers::McpProbes,
+    /// Posts Slack approval messages and resolves their button clicks;
+    /// `None` when `[http_server.chatops]` is not configured.
+    pub(crate) chatops: Option<Arc<chatops::Chatops>>,
+    /// Disk bounds enforced on conversation workspaces.
+    pub(crate) quota: quota::Quota,
+    /// Scrubs secrets from transcripts and streamed turn output; bus
+    /// events are scrubbed once at publish instead.
+    pub(crate) redactor: redact::Redactor,
+    /// Which instruction overrides `POST /conversations` accepts.
+    pub(crate) instructions: instructions::InstructionsPolicy,
+}
+
+impl AppState {
+    pub(crate) fn settings(&self) -> std::sync::RwLockReadGuard<'_, ReloadableSettings> {
+        match self.settings.read() {
+            Ok(settings) => settings,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+pub(crate) fn router(state: AppState) -> Router {
+    let max_request_bytes = state.limits.max_request_bytes;
+    Router::new()
+        .route("/health/live", get(health::live))
+        .route("/health/ready", get(health::ready))
+        .route("/conversations", post(conversations::new_conversation))
+        .route(
+            "/conversations/{id}/export",
+            get(conversations::export_conversation),
+        )
+        .route(
+            "/conversations/{id}/pr",
+            post(conversations::open_conversation_pr),
+        )
+        .route(
+            "/conversations/{id}/complete",
+            post(complete::complete_conversation),
+        )
+        .route(
+            "/conversations/{id}/artifacts",
+            get(artifacts::list_artifacts),
+        )
+        .route(
+            "/conversations/{id}/artifacts/{*path}",
+            get(artifacts::download_artifact),
+        )
+        .route(
+            "/conversations/{id}/exec/{call_id}/stream",
+            get(exec::stream_exec_output),
+        )
+        .route(
+            "/conversations/{id}/sandbox",
+            get(sandbox::get_sandbox).patch(sandbox::update_sandbox),
+        )
+        .route("/conversations/{id}/archive", get(archive::get_archive))
+        .route("/conversations/{id}/retry-last", post(retry::retry_last))
+        .route("/conversations/{id}/interrupt", post(turns::interrupt_turn))
+        .route(
+            "/conversations/{id}/cleanup",
+            post(quota::cleanup_workspace),
+        )
+        .route(
+            "/conversations/{id}/offload",
+            post(offload::offload_conversation),
+        )
+        .route("/offloads", get(offload::list_offloads))
+        .route(
+            "/conversations/{id}/recordings",
+            post(recordings::record_conversation),
+        )
+        .route("/recordings", get(recordings::list_recordings))
+        .route("/recordings/{id}", get(recordings::get_recording))
+        .route("/replays", post(recordings::create_replay))
+        .route(
+            "/schedules",
+            get(schedules::list_schedules).post(schedules::create_schedule),
+        )
+        .route("/schedules/{id}", delete(schedules::delete_schedule))
+        .route("/schedules/{id}/runs", get(schedules::schedule_runs))
+        .route("/jobs", get(jobs::list_jobs).post(jobs::create_job))
+        .route("/jobs/{id}", get(jobs::get_job))
+        .route("/jobs/{id}/diff", get(jobs::job_diff))
+        .route("/jobs/{id}/commit", post(jobs::commit_job_worktree))
+        .route("/jobs/{id}/push", post(jobs::push_job_worktree))
+        .route("/jobs/{id}/worktree", delete(jobs::discard_job_worktree))
+        .route("/mcp-servers", get(mcp_servers::list_mcp_servers))
+        .route(
+            "/mcp-servers/{name}/restart",
+            post(mcp_servers::restart_mcp_server),
+        )
+        .route("/chatops/slack/callback", post(chatops::slack_callback))
+        .route("/search", get(search::search))
+        .route("/audit", get(audit::recent_audit))
+        .route("/admin/reload", post(reload::reload_config))
+        .route("/admin/providers", get(providers::list_providers))
+        .route("/admin/providers/{id}", put(providers::put_provider))
+        .route("/events", get(events::stream_events))
+        .route(
+            "/templates",
+            get(templates::list_templates).post(templates::create_template),
+        )
+        .route(
+            "/templates/{name}",
+            get(templates::get_template)
+                .put(templates::update_template)
+                .delete(templates::delete_template),
+        )
+        .layer(DefaultBodyLimit::max(max_request_bytes))
+        .with_state(state)
+}
+
+/// Serves the router on `listener` until the task is cancelled, running the
+/// schedule loop and job workers in the background.
+pub async fn serve(listener: TcpListener, server_config: ServerConfig) -> anyhow::Result<()> {
+    let storage: Arc<dyn Storage> = Arc::new(SqliteStorage::open(&server_config.codex_home).await?);
+    let redactor = match redact::Redactor::from_toml(server_config.redaction.as_ref()) {
+        Ok(redactor) => redactor,
+        Err(err) => {
+            warn!("transcript redaction misconfigured, using built-ins: {err}");
+            redact::Redactor::default()
+        }
+    };
+    let instructions =
+        match instructions::InstructionsPolicy::from_toml(server_config.instructions.as_ref()) {
+            Ok(policy) => policy,
+            Err(err) => {
+                warn!("instruction overrides misconfigured, denying all: {err}");
+                instructions::InstructionsPolicy::default()
+            }
+        };
+    let events: Arc<dyn EventBus> = match &server_config.event_bus {
+        Some(url) => RedisEventBus::connect(url).await?,
+        None => Arc::new(LocalEventBus::new()),
+    };
+    // Scrubbing at publish covers every subscriber at once: SSE clients,
+    // webhooks, chat-ops, and the cross-replica channel.
+    let events: Arc<dyn EventBus> = Arc::new(redact::RedactingBus::new(events, redactor.clone()));
+    // Jobs and schedules take the batch lane through the turn gate; the
+    // runner handed to request handlers takes the interactive lane.
+    let gate = TurnGate::new(server_config.max_concurrent_turns, events.clone());
+    let exec_runner = Arc::new(CodexExecRunner {
+        codex_bin: server_config.codex_bin.clone(),
+    });
+    let batch_runner = gate.wrap(TurnPriority::Batch, exec_runner.clone());
+    let runner = gate.wrap(TurnPriority::Interactive, exec_runner);
+    let scheduler = Scheduler::new(batch_runner.clone(), storage.clone());
+    scheduler.load_persisted().await;
+    scheduler.seed_from_config(&server_config.schedules).await;
+    tokio::spawn(scheduler.clone().run_loop());
+    let job_queue = JobQueue::load(
+        &server_config.codex_home,
+        storage.clone(),
+        events.clone(),
+        batch_runner,
+        server_config.max_turn_seconds,
+    )
+    .await;
+    job_queue.start_workers(server_config.job_workers);
+    let templates = TemplateStore::load(storage.clone()).await;
+    templates.seed_from_config(&server_config.templates);
+    let archiver = match &server_config.archive {
+        Some(toml) => match archive::Archiver::from_toml(toml) {
+            Ok(archiver) => Some(Arc::new(archiver)),
+            Err(err) => {
+                warn!("conversation archiver disabled: {err}");
+                None
+            }
+        },
+        None => None,
+    };
+    let chatops = match &server_config.chatops {
+        Some(toml) => match chatops::Chatops::from_toml(toml) {
+            Ok(chatops) => Some(Arc::new(chatops)),
+            Err(err) => {
+                warn!("chat-ops bridge disabled: {err}");
+                None
+            }
+        },
+        None => None,
+    };
+    let notifier = match &server_config.notify {
+        Some(toml) => match notify::Notifier::from_toml(toml) {
+            Ok(notifier) => Some(Arc::new(notifier)),
+            Err(err) => {
+                warn!("webhook notifier disabled: {err}");
+                None
+            }
+        },
+        None => None,
+    };
+    let state = AppState {
+        codex_home: server_config.codex_home,
+        codex_bin: server_config.codex_bin,
+        scheduler,
+        job_queue,
+        templates,
+        storage,
+        events,
+        settings: Arc::new(std::sync::RwLock::new(ReloadableSettings {
+            github_token: server_config.github_token,
+            sandbox_limits: server_config.sandbox_limits,
+        })),
+        providers: ProviderRegistry::new(server_config.model_providers),
+        health: HealthCache::default(),
+        runner,
+        recording_sessions: RecordingSessions::default(),
+        archiver,
+        notifier,
+        active_turns: turns::ActiveTurns::default(),
+        limits: limits::Limits::from_toml(server_config.limits.as_ref()),
+        images: match images::ImagePipeline::from_toml(server_config.images.as_ref()) {
+            Ok(pipeline) => pipeline,
+            Err(err) => {
+                warn!("attachment preprocessing misconfigured, using defaults: {err}");
+                images::ImagePipeline::default()
+            }
+        },
+        mcp_probes: mcp_servers::McpProbes::default(),
+        chatops,
+        quota: quota::Quota::from_toml(server_config.quota.as_ref()),
+        redactor,
+        instructions,
+    };
+    tokio::spawn(reload::watch_loop(state.clone()));
+    tokio::spawn(archive::run_loop(state.clone()));
+    tokio::spawn(notify::run_loop(state.clone()));
+    tokio::spawn(chatops::run_loop(state.clone()));
+    let codex_home = state.codex_home.clone();
+    axum::serve(listener, router(state))
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+    // Scratch workspaces do not outlive the server that provisioned them.
+    quota::cleanup_all(&codex_home).await;
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::*;
+    use crate::runner::ConversationRunner;
+    use crate::runner::RunOutcome;
+    use async_trait::async_trait;
+    use std::path::Path;
+
+    struct NoopRunner;
+
+    #[async_trait]
+    impl ConversationRunner for NoopRunner {
+        async fn run(
+            &self,
+            _prompt: &str,
+            _cwd: Option<&Path>,
+            _config_overrides: &[String],
+        ) -> RunOutcome {
+            RunOutcome {
+                success: true,
+                detail: String::new(),
+            }
+        }
+    }
+
+    /// App state over `codex_home` whose runner succeeds without doing
+    /// anything; no workers or schedule loop are started.
+    pub(crate) async fn test_state(codex_home: &Path) -> AppState {
+        let runner = Arc::new(NoopRunner);
+        let storage: Arc<dyn Storage> = Arc::new(
+            SqliteStorage::open(codex_home)
+                .await
+                .expect("open test storage"),
+        );
+        let events: Arc<dyn EventBus> = Arc::new(LocalEventBus::new());
+        AppState {
+            codex_home: codex_home.to_path_buf(),
+            codex_bin: PathBuf::from("codex"),
+            scheduler: Scheduler::new(runner.clone(), storage.clone()),
+            job_queue: JobQueue::load(
+                codex_home,
+                storage.clone(),
+                events.clone(),
+                runner.clone(),
+                None,
+            )
+            .await,
+            templates: TemplateStore::load(storage.clone()).await,
+            storage,
+            events,
+            settings: Arc::new(std::sync::RwLock::new(ReloadableSettings::default())),
+            providers: ProviderRegistry::new(codex_model_provider_info::built_in_model_providers(
+                None,
+            )),
+            health: HealthCache::default(),
+            runner,
+            recording_sessions: RecordingSessions::default(),
+            archiver: None,
+            notifier: None,
+            active_turns: turns::ActiveTurns::default(),
+            limits: limits::Limits::default(),
+            images: images::ImagePipeline::default(),
+            mcp_probes: mcp_servers::McpProbes::default(),
+            chatops: None,
+            quota: quota::Quota::default(),
+            redactor: redact::Redactor::default(),
+            instructions: instructions::InstructionsPolicy::default(),
+        }
+    }
+}
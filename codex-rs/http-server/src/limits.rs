@@ -0,0 +1,174 @@
+//! Payload size limits from `[http_server.limits]`.
+//!
+//! A public-facing server needs bounds in three places: request bodies, so
+//! a client can't post a 50 MB base64 image at a JSON route; individual
+//! `/events` payloads, so one oversized event can't wedge every SSE
+//! client; and exported transcripts, which are summarized down to a
+//! configurable size by keeping the opening entry and the most recent
+//! entries and replacing the elided middle with a note saying what was
+//! dropped.
+
+use codex_config::types::HttpLimitsToml;
+use codex_core::export::ConversationExport;
+use codex_core::export::TranscriptEntry;
+use codex_core::export::TranscriptEntryKind;
+
+use crate::events::ServerEvent;
+
+const DEFAULT_MAX_REQUEST_BYTES: usize = 2 * 1024 * 1024;
+const DEFAULT_MAX_EVENT_BYTES: usize = 256 * 1024;
+const DEFAULT_MAX_TRANSCRIPT_BYTES: usize = 4 * 1024 * 1024;
+
+/// Effective limits after defaults are applied.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Limits {
+    /// Largest accepted request body, enforced on the whole router.
+    pub max_request_bytes: usize,
+    /// Largest `/events` payload sent to an SSE client.
+    pub max_event_bytes: usize,
+    /// Transcript size beyond which exports are summarized.
+    pub max_transcript_bytes: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_request_bytes: DEFAULT_MAX_REQUEST_BYTES,
+            max_event_bytes: DEFAULT_MAX_EVENT_BYTES,
+            max_transcript_bytes: DEFAULT_MAX_TRANSCRIPT_BYTES,
+        }
+    }
+}
+
+impl Limits {
+    pub(crate) fn from_toml(toml: Option<&HttpLimitsToml>) -> Self {
+        let defaults = Self::default();
+        let Some(toml) = toml else {
+            return defaults;
+        };
+        Self {
+            max_request_bytes: toml.max_request_bytes.unwrap_or(defaults.max_request_bytes),
+            max_event_bytes: toml.max_event_bytes.unwrap_or(defaults.max_event_bytes),
+            max_transcript_bytes: toml
+                .max_transcript_bytes
+                .unwrap_or(defaults.max_transcript_bytes),
+        }
+    }
+}
+
+/// Replaces an oversized event payload with a stub naming how large it
+/// was, so the stream stays parseable for every client.
+pub(crate) fn clamp_event(event: ServerEvent, max_bytes: usize) -> ServerEvent {
+    let payload_bytes = event.payload.to_string().len();
+    if payload_bytes <= max_bytes {
+        return event;
+    }
+    ServerEvent {
+        kind: event.kind,
+        payload: serde_json::json!({
+            "truncated": true,
+            "payload_bytes": payload_bytes,
+        }),
+    }
+}
+
+/// Summarizes `export` in place when its entries exceed `max_bytes`: the
+/// opening entry and the most recent entries that fit are kept, and the
+/// middle becomes a note recording how much was elided.
+pub(crate) fn summarize_transcript(export: &mut ConversationExport, max_bytes: usize) {
+    let total: usize = export.entries.iter().map(entry_bytes).sum();
+    if total <= max_bytes {
+        return;
+    }
+    // Budget the tail with the opening entry already accounted for; recent
+    // entries carry the state a reader usually needs.
+    let mut budget = max_bytes.saturating_sub(export.entries.first().map_or(0, entry_bytes));
+    let mut tail_start = export.entries.len();
+    while tail_start > 1 {
+        let bytes = entry_bytes(&export.entries[tail_start - 1]);
+        if bytes > budget {
+            break;
+        }
+        budget -= bytes;
+        tail_start -= 1;
+    }
+    let elided = &export.entries[1..tail_start];
+    let note = TranscriptEntry {
+        kind: TranscriptEntryKind::Note,
+        heading: "Transcript summarized".to_string(),
+        body: format!(
+            "{} entries ({} bytes) elided to stay under the server's {max_bytes}-byte \
+             transcript limit.",
+            elided.len(),
+            elided.iter().map(entry_bytes).sum::<usize>(),
+        ),
+    };
+    let mut entries = Vec::with_capacity(export.entries.len() - elided.len() + 1);
+    entries.extend(export.entries.first().cloned());
+    entries.push(note);
+    entries.extend(export.entries[tail_start..].iter().cloned());
+    export.entries = entries;
+}
+
+fn entry_bytes(entry: &TranscriptEntry) -> usize {
+    entry.heading.len() + entry.body.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn entry(heading: &str, body: &str) -> TranscriptEntry {
+        TranscriptEntry {
+            kind: TranscriptEntryKind::Message,
+            heading: heading.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    fn export(entries: Vec<TranscriptEntry>) -> ConversationExport {
+        ConversationExport {
+            id: "abc".to_string(),
+            timestamp: None,
+            cwd: None,
+            entries,
+        }
+    }
+
+    #[test]
+    fn small_transcripts_are_left_alone() {
+        let mut export = export(vec![entry("User", "hello"), entry("Assistant", "hi")]);
+        let before = export.clone();
+        summarize_transcript(&mut export, 1024);
+        assert_eq!(export, before);
+    }
+
+    #[test]
+    fn oversized_transcripts_keep_the_opening_and_the_tail() {
+        let mut export = export(vec![
+            entry("User", "the task"),
+            entry("Assistant", &"x".repeat(400)),
+            entry("Assistant", &"y".repeat(400)),
+            entry("Assistant", "the answer"),
+        ]);
+        summarize_transcript(&mut export, 200);
+        assert_eq!(export.entries.len(), 3);
+        assert_eq!(export.entries[0].body, "the task");
+        assert_eq!(export.entries[1].kind, TranscriptEntryKind::Note);
+        assert!(export.entries[1].body.contains("2 entries (818 bytes)"));
+        assert_eq!(export.entries[2].body, "the answer");
+    }
+
+    #[test]
+    fn oversized_event_payloads_are_stubbed() {
+        let event = ServerEvent {
+            kind: "job.done".to_string(),
+            payload: serde_json::json!({ "result": "z".repeat(100) }),
+        };
+        let clamped = clamp_event(event.clone(), 32);
+        assert_eq!(clamped.kind, "job.done");
+        assert_eq!(clamped.payload["truncated"], true);
+        assert_eq!(clamp_event(event.clone(), 4096).payload, event.payload);
+    }
+}
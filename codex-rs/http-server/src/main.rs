@@ -0,0 +1,106 @@
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Parser;
+use codex_core::config::Config;
+use codex_http_server::ServerConfig;
+use tokio::net::TcpListener;
+use tracing::info;
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::prelude::*;
+
+/// CLI arguments for the HTTP server.
+#[derive(Debug, Parser)]
+#[command(
+    name = "codex-http-server",
+    about = "Serve Codex conversations over HTTP"
+)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Port to listen on. If not set, an ephemeral port is used.
+    #[arg(long)]
+    port: Option<u16>,
+
+    /// Codex home directory holding recorded sessions (defaults to `CODEX_HOME`).
+    #[arg(long, value_name = "DIR")]
+    codex_home: Option<PathBuf>,
+
+    /// Binary spawned for scheduled conversations.
+    #[arg(long, value_name = "BIN", default_value = "codex")]
+    codex_bin: PathBuf,
+}
+
+/// Without a subcommand the binary just serves.
+#[derive(Debug, clap::Subcommand)]
+enum Command {
+    /// Boot on an ephemeral port with a stub model, run a scripted
+    /// conversation (create, send, stream, interrupt, shutdown), and exit
+    /// nonzero on failure.
+    Selftest,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    if let Some(Command::Selftest) = args.command {
+        tracing_subscriber::fmt::init();
+        return codex_http_server::selftest::run().await;
+    }
+    let config = Config::load_with_cli_overrides(vec![])
+        .await
+        .context("failed to load configuration")?;
+    // Spans go to the OTLP endpoint `[otel]` in config.toml points at, so
+    // a traced client sees API, model, and tool latency in one trace; the
+    // provider flushes when it drops at the end of `main`.
+    let otel = match codex_core::otel_init::build_provider(
+        &config,
+        env!("CARGO_PKG_VERSION"),
+        Some("codex-http-server"),
+        /* default_analytics_enabled */ false,
+    ) {
+        Ok(otel) => otel,
+        Err(err) => {
+            eprintln!("could not create otel exporter: {err}");
+            None
+        }
+    };
+    let fmt_layer = tracing_subscriber::fmt::layer()
+        .with_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")));
+    let _ = tracing_subscriber::registry()
+        .with(fmt_layer)
+        .with(otel.as_ref().and_then(|otel| otel.tracing_layer()))
+        .with(otel.as_ref().and_then(|otel| otel.logger_layer()))
+        .try_init();
+    let server_config = ServerConfig {
+        codex_home: args
+            .codex_home
+            .unwrap_or_else(|| config.codex_home.to_path_buf()),
+        schedules: config.http_server.schedules.clone(),
+        job_workers: config.http_server.job_workers,
+        codex_bin: args.codex_bin,
+        github_token: config.http_server.github_token.clone(),
+        templates: config.http_server.templates.clone(),
+        event_bus: config.http_server.event_bus.clone(),
+        sandbox_limits: config.http_server.sandbox_limits.clone(),
+        model_providers: config.model_providers.clone(),
+        max_turn_seconds: config.http_server.max_turn_seconds,
+        max_concurrent_turns: config.http_server.max_concurrent_turns,
+        archive: config.http_server.archive,
+        notify: config.http_server.notify,
+        limits: config.http_server.limits,
+        images: config.http_server.images,
+        chatops: config.http_server.chatops,
+        quota: config.http_server.quota,
+        redaction: config.http_server.redaction,
+        instructions: config.http_server.instructions,
+    };
+    let addr = SocketAddr::from(([127, 0, 0, 1], args.port.unwrap_or(0)));
+    let listener = TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("failed to bind {addr}"))?;
+    info!("listening on http://{}", listener.local_addr()?);
+    codex_http_server::serve(listener, server_config).await
+}
@@ -0,0 +1,416 @@
+//! Remote MCP diagnostics: the `/mcp-servers` routes.
+//!
+//! The server-side agent gets its MCP connections from each `codex exec`
+//! child, so this process holds no resident connection to show or bounce.
+//! `GET /mcp-servers` shells out to `codex mcp list --json` — the same view
+//! an operator gets on the box — and annotates each server with the result
+//! of its last probe. `POST /mcp-servers/{name}/restart` launches a fresh
+//! instance of a stdio server, handshakes it, and records the tools it
+//! offers, which is exactly what the next conversation turn will get; a
+//! server that fails the handshake is the diagnosis the operator came for.
+//! HTTP-transport servers run remotely and have no process here to restart.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::time::Duration;
+
+use axum::Json;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::process::Command;
+
+use crate::AppState;
+use crate::error::ApiError;
+use crate::storage::audit;
+
+/// How long a restarted server gets to come up when its config sets no
+/// `startup_timeout_sec`.
+const DEFAULT_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Outcome of launching a server and asking it for its tools.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct McpProbe {
+    pub success: bool,
+    /// Tool names the server offered when the handshake succeeded.
+    pub tools: Vec<String>,
+    /// What went wrong when it did not.
+    pub detail: Option<String>,
+    pub at: DateTime<Utc>,
+}
+
+/// Last probe per server, kept so the listing can show how a server behaved
+/// the last time an operator bounced it.
+#[derive(Clone, Default)]
+pub(crate) struct McpProbes {
+    inner: Arc<Mutex<HashMap<String, McpProbe>>>,
+}
+
+impl McpProbes {
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, McpProbe>> {
+        match self.inner.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    fn record(&self, name: &str, probe: McpProbe) {
+        self.lock().insert(name.to_string(), probe);
+    }
+
+    fn snapshot(&self) -> HashMap<String, McpProbe> {
+        self.lock().clone()
+    }
+}
+
+/// `GET /mcp-servers`
+pub(crate) async fn list_mcp_servers(State(state): State<AppState>) -> Response {
+    let servers = match configured_servers(&state.codex_bin).await {
+        Ok(servers) => servers,
+        Err(err) => return err.into_response(),
+    };
+    let servers = annotate(servers, &state.mcp_probes.snapshot());
+    Json(serde_json::json!({ "servers": servers })).into_response()
+}
+
+/// `POST /mcp-servers/{name}/restart`
+pub(crate) async fn restart_mcp_server(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Response {
+    let servers = match configured_servers(&state.codex_bin).await {
+        Ok(servers) => servers,
+        Err(err) => return err.into_response(),
+    };
+    let Some(entry) = servers
+        .into_iter()
+        .find(|server| server.get("name").and_then(Value::as_str) == Some(name.as_str()))
+    else {
+        return ApiError::not_found(format!("no MCP server named {name}")).into_response();
+    };
+    let launch = match stdio_launch(&entry, &name) {
+        Ok(launch) => launch,
+        Err(err) => return err.into_response(),
+    };
+    let probe = probe_stdio(&launch).await;
+    state.mcp_probes.record(&name, probe.clone());
+    audit(&*state.storage, "mcp.restart", &format!("server {name}")).await;
+    Json(serde_json::json!({ "name": name, "probe": probe })).into_response()
+}
+
+/// Runs `codex mcp list --json` and returns the configured servers.
+async fn configured_servers(codex_bin: &std::path::Path) -> Result<Vec<Value>, ApiError> {
+    let output = Command::new(codex_bin)
+        .arg("mcp")
+        .arg("list")
+        .arg("--json")
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .map_err(|err| {
+            ApiError::internal(format!("failed to launch {}: {err}", codex_bin.display()))
+        })?;
+    if !output.status.success() {
+        return Err(ApiError::internal(format!(
+            "listing MCP servers failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    serde_json::from_slice(&output.stdout)
+        .map_err(|err| ApiError::internal(format!("unexpected MCP listing: {err}")))
+}
+
+/// Attaches each server's last probe to its listing entry.
+fn annotate(servers: Vec<Value>, probes: &HashMap<String, McpProbe>) -> Vec<Value> {
+    servers
+        .into_iter()
+        .map(|mut server| {
+            if let Some(name) = server.get("name").and_then(Value::as_str)
+                && let Some(probe) = probes.get(name)
+                && let Ok(probe) = serde_json::to_value(probe)
+                && let Value::Object(fields) = &mut server
+            {
+                fields.insert("last_probe".to_string(), probe);
+            }
+            server
+        })
+        .collect()
+}
+
+/// What the listing declares for one server, as far as a restart needs it.
+#[derive(Debug, Deserialize)]
+struct ServerEntry {
+    enabled: bool,
+    transport: Transport,
+    startup_timeout_sec: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Transport {
+    Stdio {
+        command: String,
+        #[serde(default)]
+        args: Vec<String>,
+        #[serde(default)]
+        env: Option<HashMap<String, String>>,
+        #[serde(default)]
+        cwd: Option<PathBuf>,
+    },
+    StreamableHttp {
+        url: String,
+    },
+}
+
+/// How to launch one stdio server for a probe.
+struct StdioLaunch {
+    command: String,
+    args: Vec<String>,
+    env: HashMap<String, String>,
+    cwd: Option<PathBuf>,
+    timeout: Duration,
+}
+
+/// Extracts the launch parameters for a restart, rejecting servers that
+/// have no local process to restart.
+fn stdio_launch(entry: &Value, name: &str) -> Result<StdioLaunch, ApiError> {
+    let server: ServerEntry = serde_json::from_value(entry.clone())
+        .map_err(|err| ApiError::internal(format!("unexpected MCP listing: {err}")))?;
+    if !server.enabled {
+        return Err(ApiError::invalid_state(format!(
+            "MCP server {name} is disabled; enable it before restarting"
+        )));
+    }
+    match server.transport {
+        Transport::Stdio {
+            command,
+            args,
+            env,
+            cwd,
+        } => Ok(StdioLaunch {
+            command,
+            args,
+            env: env.unwrap_or_default(),
+            cwd,
+            timeout: server
+                .startup_timeout_sec
+                .map_or(DEFAULT_PROBE_TIMEOUT, Duration::from_secs_f64),
+        }),
+        Transport::StreamableHttp { url } => Err(ApiError::invalid_state(format!(
+            "MCP server {name} runs remotely at {url}; there is no server-side process to restart"
+        ))),
+    }
+}
+
+/// Launches the server and handshakes it, reporting the outcome either way.
+async fn probe_stdio(launch: &StdioLaunch) -> McpProbe {
+    let outcome = tokio::time::timeout(launch.timeout, handshake(launch)).await;
+    let (success, tools, detail) = match outcome {
+        Ok(Ok(tools)) => (true, tools, None),
+        Ok(Err(detail)) => (false, Vec::new(), Some(detail)),
+        Err(_) => (
+            false,
+            Vec::new(),
+            Some(format!(
+                "no handshake within {}s",
+                launch.timeout.as_secs_f64()
+            )),
+        ),
+    };
+    McpProbe {
+        success,
+        tools,
+        detail,
+        at: Utc::now(),
+    }
+}
+
+/// Speaks just enough MCP over stdio to initialize the server and list its
+/// tools; the child dies with the probe via `kill_on_drop`.
+async fn handshake(launch: &StdioLaunch) -> Result<Vec<String>, String> {
+    let mut command = Command::new(&launch.command);
+    command
+        .args(&launch.args)
+        .envs(&launch.env)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true);
+    if let Some(cwd) = &launch.cwd {
+        command.current_dir(cwd);
+    }
+    let mut child = command
+        .spawn()
+        .map_err(|err| format!("failed to launch {}: {err}", launch.command))?;
+    let mut stdin = child.stdin.take().ok_or("stdin unavailable")?;
+    let stdout = child.stdout.take().ok_or("stdout unavailable")?;
+    let mut lines = BufReader::new(stdout).lines();
+    send(
+        &mut stdin,
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-06-18",
+                "capabilities": {},
+                "clientInfo": {
+                    "name": "codex-http-server",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            },
+        }),
+    )
+    .await?;
+    response_for(&mut lines, 1).await?;
+    send(
+        &mut stdin,
+        serde_json::json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+    )
+    .await?;
+    send(
+        &mut stdin,
+        serde_json::json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list", "params": {} }),
+    )
+    .await?;
+    let result = response_for(&mut lines, 2).await?;
+    let mut tools: Vec<String> = result["tools"]
+        .as_array()
+        .map(|tools| {
+            tools
+                .iter()
+                .filter_map(|tool| tool.get("name").and_then(Value::as_str))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    tools.sort();
+    Ok(tools)
+}
+
+async fn send(stdin: &mut (impl AsyncWriteExt + Unpin), message: Value) -> Result<(), String> {
+    let mut line = message.to_string();
+    line.push('\n');
+    stdin
+        .write_all(line.as_bytes())
+        .await
+        .map_err(|err| format!("failed to write to the server: {err}"))
+}
+
+/// Reads stdout until the response to request `id` arrives, skipping
+/// server-initiated messages and non-JSON banner lines.
+async fn response_for(
+    lines: &mut tokio::io::Lines<BufReader<tokio::process::ChildStdout>>,
+    id: u64,
+) -> Result<Value, String> {
+    loop {
+        let line = match lines.next_line().await {
+            Ok(Some(line)) => line,
+            Ok(None) => return Err("the server exited during the handshake".to_string()),
+            Err(err) => return Err(format!("failed to read from the server: {err}")),
+        };
+        let Ok(message) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        if message.get("id").and_then(Value::as_u64) != Some(id) {
+            continue;
+        }
+        if let Some(error) = message.get("error") {
+            let detail = error
+                .get("message")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown error");
+            return Err(format!("the server rejected the handshake: {detail}"));
+        }
+        return Ok(message.get("result").cloned().unwrap_or_default());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+    use crate::test_support::test_state;
+    use axum::http::StatusCode;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn a_missing_codex_binary_is_an_internal_error() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let mut state = test_state(codex_home.path()).await;
+        state.codex_bin = PathBuf::from("/nonexistent/codex");
+        let response = list_mcp_servers(State(state)).await;
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[test]
+    fn probes_are_merged_into_the_listing() {
+        let servers = vec![
+            serde_json::json!({ "name": "docs", "enabled": true }),
+            serde_json::json!({ "name": "search", "enabled": true }),
+        ];
+        let probes = HashMap::from([(
+            "docs".to_string(),
+            McpProbe {
+                success: true,
+                tools: vec!["lookup".to_string()],
+                detail: None,
+                at: Utc::now(),
+            },
+        )]);
+        let annotated = annotate(servers, &probes);
+        assert_eq!(annotated[0]["last_probe"]["tools"][0], "lookup");
+        assert!(annotated[1].get("last_probe").is_none());
+    }
+
+    #[test]
+    fn servers_without_a_local_process_cannot_be_restarted() {
+        let disabled = serde_json::json!({
+            "name": "docs",
+            "enabled": false,
+            "transport": { "type": "stdio", "command": "docs-mcp" },
+        });
+        let err = stdio_launch(&disabled, "docs").expect_err("disabled should be rejected");
+        assert_eq!(err.code(), ErrorCode::InvalidState);
+        let remote = serde_json::json!({
+            "name": "search",
+            "enabled": true,
+            "transport": { "type": "streamable_http", "url": "https://mcp.example.com" },
+        });
+        let err = stdio_launch(&remote, "search").expect_err("remote should be rejected");
+        assert_eq!(err.code(), ErrorCode::InvalidState);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn a_stdio_server_is_probed_for_its_tools() {
+        let script = r#"read _init
+echo '{"jsonrpc":"2.0","id":1,"result":{}}'
+read _initialized
+read _list
+echo '{"jsonrpc":"2.0","id":2,"result":{"tools":[{"name":"search"},{"name":"echo"}]}}'"#;
+        let launch = StdioLaunch {
+            command: "sh".to_string(),
+            args: vec!["-c".to_string(), script.to_string()],
+            env: HashMap::new(),
+            cwd: None,
+            timeout: DEFAULT_PROBE_TIMEOUT,
+        };
+        let probe = probe_stdio(&launch).await;
+        assert!(probe.success, "{:?}", probe.detail);
+        assert_eq!(probe.tools, vec!["echo".to_string(), "search".to_string()]);
+    }
+}
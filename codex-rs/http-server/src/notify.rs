@@ -0,0 +1,191 @@
+//! Webhook notifications for task lifecycle events.
+//!
+//! Long-running remote tasks shouldn't require polling `/jobs` or holding
+//! an `/events` stream open: when `[http_server.notify]` configures a Slack
+//! or Discord webhook, a background task watches the event bus and posts a
+//! short message — with a link back to this server — when a queued
+//! conversation completes, fails, or requests a sandbox grant an operator
+//! has to approve. With a Redis event bus every replica sees every event,
+//! so configure the notifier on a single replica to avoid duplicate posts.
+
+use codex_config::types::HttpNotifyToml;
+use serde_json::json;
+use tracing::warn;
+
+use crate::AppState;
+use crate::events::ServerEvent;
+
+/// Longest prompt excerpt included in a notification.
+const SUMMARY_CHARS: usize = 120;
+
+/// Posts lifecycle messages to the configured webhooks.
+pub(crate) struct Notifier {
+    slack_webhook_url: Option<String>,
+    discord_webhook_url: Option<String>,
+    /// Externally reachable base URL without a trailing slash, if set.
+    base_url: Option<String>,
+    http: reqwest::Client,
+}
+
+impl Notifier {
+    pub(crate) fn from_toml(toml: &HttpNotifyToml) -> Result<Self, String> {
+        if toml.slack_webhook_url.is_none() && toml.discord_webhook_url.is_none() {
+            return Err(
+                "http_server.notify needs slack_webhook_url or discord_webhook_url".to_string(),
+            );
+        }
+        Ok(Self {
+            slack_webhook_url: toml.slack_webhook_url.clone(),
+            discord_webhook_url: toml.discord_webhook_url.clone(),
+            base_url: toml
+                .base_url
+                .as_ref()
+                .map(|url| url.trim_end_matches('/').to_string()),
+            http: reqwest::Client::new(),
+        })
+    }
+
+    /// Message for `event`, or `None` for kinds that aren't notified.
+    fn render(&self, event: &ServerEvent) -> Option<String> {
+        let (line, path) = match event.kind.as_str() {
+            "job.done" | "job.failed" => {
+                let id = event.payload.get("id")?.as_u64()?;
+                let prompt = summary(event.payload.get("prompt")?.as_str()?);
+                let line = if event.kind == "job.done" {
+                    format!("Job {id} completed: {prompt}")
+                } else {
+                    format!("Job {id} failed: {prompt}")
+                };
+                (line, format!("/jobs/{id}"))
+            }
+            "job.timeout" => {
+                let id = event.payload.get("id")?.as_u64()?;
+                let secs = event.payload.get("max_turn_seconds")?.as_u64()?;
+                (
+                    format!("Job {id} failed: interrupted after {secs}s"),
+                    format!("/jobs/{id}"),
+                )
+            }
+            "sandbox.denied" => {
+                let id = event.payload.get("conversation_id")?.as_str()?;
+                (
+                    format!(
+                        "Conversation {id} requested a sandbox grant beyond the server \
+                         limits and needs an operator to approve it"
+                    ),
+                    format!("/conversations/{id}/sandbox"),
+                )
+            }
+            _ => return None,
+        };
+        match &self.base_url {
+            Some(base) => Some(format!("{line}\n{base}{path}")),
+            None => Some(line),
+        }
+    }
+
+    /// Posts `message` to every configured webhook; failures are logged so
+    /// a dead webhook doesn't stall the watcher.
+    async fn post(&self, message: &str) {
+        if let Some(url) = &self.slack_webhook_url {
+            self.post_json(url, &json!({ "text": message }), "slack")
+                .await;
+        }
+        if let Some(url) = &self.discord_webhook_url {
+            self.post_json(url, &json!({ "content": message }), "discord")
+                .await;
+        }
+    }
+
+    async fn post_json(&self, url: &str, body: &serde_json::Value, which: &str) {
+        match self.http.post(url).json(body).send().await {
+            Ok(response) if !response.status().is_success() => {
+                warn!("{which} webhook returned {}", response.status());
+            }
+            Ok(_) => {}
+            Err(err) => warn!("failed to post {which} webhook: {err}"),
+        }
+    }
+}
+
+/// First line of `prompt`, truncated to [`SUMMARY_CHARS`].
+fn summary(prompt: &str) -> String {
+    let line = prompt.lines().next().unwrap_or_default().trim();
+    if line.chars().count() <= SUMMARY_CHARS {
+        line.to_string()
+    } else {
+        let truncated: String = line.chars().take(SUMMARY_CHARS).collect();
+        format!("{truncated}…")
+    }
+}
+
+/// Watches the event bus and posts until the server shuts down. Spawned
+/// from [`crate::serve`]; returns immediately when no notifier is
+/// configured.
+pub(crate) async fn run_loop(state: AppState) {
+    let Some(notifier) = state.notifier.clone() else {
+        return;
+    };
+    let mut rx = state.events.subscribe();
+    while let Some(event) = rx.next().await {
+        if let Some(message) = notifier.render(&event) {
+            notifier.post(&message).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn notifier(base_url: Option<&str>) -> Notifier {
+        Notifier::from_toml(&HttpNotifyToml {
+            slack_webhook_url: Some("https://hooks.slack.invalid/T000/B000".to_string()),
+            discord_webhook_url: None,
+            base_url: base_url.map(str::to_string),
+        })
+        .expect("build notifier")
+    }
+
+    #[test]
+    fn job_completion_links_back_to_the_job() {
+        let message = notifier(Some("https://codex.example.com/"))
+            .render(&ServerEvent {
+                kind: "job.done".to_string(),
+                payload: json!({ "id": 7, "prompt": "fix the flaky test\nand more" }),
+            })
+            .expect("rendered");
+        assert_eq!(
+            message,
+            "Job 7 completed: fix the flaky test\nhttps://codex.example.com/jobs/7"
+        );
+    }
+
+    #[test]
+    fn sandbox_denials_are_reported_as_needing_approval() {
+        let message = notifier(None)
+            .render(&ServerEvent {
+                kind: "sandbox.denied".to_string(),
+                payload: json!({ "conversation_id": "abc" }),
+            })
+            .expect("rendered");
+        assert!(message.contains("Conversation abc"));
+        assert!(message.contains("approve"));
+    }
+
+    #[test]
+    fn unrelated_events_are_not_notified() {
+        let event = ServerEvent {
+            kind: "turn.started".to_string(),
+            payload: json!({ "priority": "batch" }),
+        };
+        assert_eq!(notifier(None).render(&event), None);
+    }
+
+    #[test]
+    fn a_webhook_url_is_required() {
+        let err = Notifier::from_toml(&HttpNotifyToml::default()).err();
+        assert!(err.is_some());
+    }
+}
@@ -0,0 +1,342 @@
+//! Hand a conversation off to a remote Infinity agent: the
+//! `/conversations/{id}/offload` and `/offloads` routes.
+//!
+//! Offloading packages the conversation state — the repository and optional
+//! git ref, the rendered transcript, and any pending plan — into a handoff
+//! prompt and launches an agent through the Infinity control-plane client
+//! (`codex-infinity`). A background task then polls the agent; when it
+//! finishes, the remote outcome is folded back into a local conversation by
+//! running the hand-back prompt through the conversation runner, and the
+//! offload is marked accordingly. Credentials come from the usual Infinity
+//! sources (`CODEX_INFINITY_API_KEY` or the keychain).
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use axum::Json;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use chrono::DateTime;
+use chrono::Utc;
+use codex_core::export::ConversationExport;
+use codex_core::export::ExportFormat;
+use codex_infinity::Agent;
+use codex_infinity::InfinityClient;
+use codex_infinity::LaunchAgentRequest;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::AppState;
+use crate::error::ApiError;
+use crate::events::ServerEvent;
+use crate::storage::audit;
+
+/// How often the hand-back task polls the remote agent.
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Consecutive status-poll failures before an offload is given up on.
+const MAX_POLL_FAILURES: u32 = 10;
+
+/// Log lines from the remote agent carried into the hand-back prompt.
+const HAND_BACK_LOG_LINES: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum OffloadStatus {
+    /// The remote agent is still working.
+    Offloaded,
+    /// The agent finished and its outcome was resumed locally.
+    HandedBack,
+    /// The agent failed, was cancelled, or became unreachable.
+    Failed,
+}
+
+/// One conversation handed off to a remote agent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Offload {
+    pub agent_id: String,
+    pub conversation_id: String,
+    pub status: OffloadStatus,
+    pub created_at: DateTime<Utc>,
+    /// Outcome of the local hand-back run, or the failure reason.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct OffloadRequest {
+    /// Git ref the agent should work from; the handoff otherwise names only
+    /// the recorded repository path.
+    #[serde(default)]
+    git_ref: Option<String>,
+    /// Pending plan text forwarded verbatim to the remote agent.
+    #[serde(default)]
+    plan: Option<String>,
+    #[serde(default)]
+    model: Option<String>,
+}
+
+/// `POST /conversations/{id}/offload`
+pub(crate) async fn offload_conversation(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<OffloadRequest>,
+) -> Response {
+    let export = match codex_core::export::load_conversation_export(&state.codex_home, &id).await {
+        Ok(Some(export)) => export,
+        Ok(None) => {
+            return ApiError::not_found(format!("no conversation with id {id}")).into_response();
+        }
+        Err(err) => {
+            return ApiError::internal(format!("failed to load conversation: {err}"))
+                .into_response();
+        }
+    };
+    let client = match InfinityClient::from_env() {
+        Ok(client) => client,
+        Err(err) => {
+            return ApiError::missing_credentials(format!("{err:#}")).into_response();
+        }
+    };
+    let prompt = handoff_prompt(&export, request.git_ref.as_deref(), request.plan.as_deref());
+    let name = format!("handoff-{id}");
+    let agent = match client
+        .launch_agent(&LaunchAgentRequest {
+            name: &name,
+            prompt: &prompt,
+            model: request.model.as_deref(),
+            server_id: None,
+        })
+        .await
+    {
+        Ok(agent) => agent,
+        Err(err) => {
+            return ApiError::upstream(format!("failed to launch Infinity agent: {err:#}"))
+                .into_response();
+        }
+    };
+    let offload = Offload {
+        agent_id: agent.id.clone(),
+        conversation_id: id.clone(),
+        status: OffloadStatus::Offloaded,
+        created_at: Utc::now(),
+        detail: None,
+    };
+    if let Err(err) = state.storage.save_offload(&offload).await {
+        warn!("failed to persist offload {}: {err}", agent.id);
+    }
+    audit(
+        &*state.storage,
+        "offload.start",
+        &format!("conversation {id} -> agent {}", agent.id),
+    )
+    .await;
+    state
+        .events
+        .publish(ServerEvent {
+            kind: "offload.started".to_string(),
+            payload: serde_json::json!({
+                "conversation_id": id,
+                "agent_id": agent.id,
+            }),
+        })
+        .await;
+    let cwd = export.cwd.clone().map(PathBuf::from);
+    tokio::spawn(hand_back_loop(state, client, offload.clone(), cwd));
+    (StatusCode::ACCEPTED, Json(offload)).into_response()
+}
+
+/// `GET /offloads`
+pub(crate) async fn list_offloads(State(state): State<AppState>) -> Response {
+    match state.storage.load_offloads().await {
+        Ok(offloads) => Json(offloads).into_response(),
+        Err(err) => ApiError::internal(format!("failed to load offloads: {err}")).into_response(),
+    }
+}
+
+/// Polls the remote agent and resumes the conversation locally once it
+/// finishes.
+async fn hand_back_loop(
+    state: AppState,
+    client: InfinityClient,
+    mut offload: Offload,
+    cwd: Option<PathBuf>,
+) {
+    let mut failures = 0u32;
+    let agent = loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        match client.agent_status(&offload.agent_id).await {
+            Ok(agent) if is_terminal(&agent.status) => break agent,
+            Ok(_) => failures = 0,
+            Err(err) => {
+                failures += 1;
+                warn!("failed to poll agent {}: {err:#}", offload.agent_id);
+                if failures >= MAX_POLL_FAILURES {
+                    finish_offload(
+                        &state,
+                        &mut offload,
+                        OffloadStatus::Failed,
+                        format!("agent became unreachable: {err:#}"),
+                    )
+                    .await;
+                    return;
+                }
+            }
+        }
+    };
+    if !is_success(&agent.status) {
+        finish_offload(
+            &state,
+            &mut offload,
+            OffloadStatus::Failed,
+            format!("agent finished with status {}", agent.status),
+        )
+        .await;
+        return;
+    }
+    let log_tail = match client.agent_logs(&agent.id, None).await {
+        Ok(logs) => {
+            let skip = logs.lines.len().saturating_sub(HAND_BACK_LOG_LINES);
+            logs.lines[skip..].join("\n")
+        }
+        Err(err) => {
+            warn!("failed to fetch logs for agent {}: {err:#}", agent.id);
+            String::new()
+        }
+    };
+    let outcome = state
+        .runner
+        .run(&hand_back_prompt(&agent, &log_tail), cwd.as_deref(), &[])
+        .await;
+    let status = if outcome.success {
+        OffloadStatus::HandedBack
+    } else {
+        OffloadStatus::Failed
+    };
+    finish_offload(&state, &mut offload, status, outcome.detail).await;
+}
+
+async fn finish_offload(
+    state: &AppState,
+    offload: &mut Offload,
+    status: OffloadStatus,
+    detail: String,
+) {
+    offload.status = status;
+    offload.detail = Some(detail);
+    if let Err(err) = state.storage.save_offload(offload).await {
+        warn!("failed to persist offload {}: {err}", offload.agent_id);
+    }
+    let kind = match status {
+        OffloadStatus::HandedBack => "offload.handed_back",
+        _ => "offload.failed",
+    };
+    audit(
+        &*state.storage,
+        kind,
+        &format!("conversation {}", offload.conversation_id),
+    )
+    .await;
+    state
+        .events
+        .publish(ServerEvent {
+            kind: kind.to_string(),
+            payload: serde_json::json!({
+                "conversation_id": offload.conversation_id,
+                "agent_id": offload.agent_id,
+            }),
+        })
+        .await;
+}
+
+/// Everything the remote agent needs to continue the conversation.
+fn handoff_prompt(
+    export: &ConversationExport,
+    git_ref: Option<&str>,
+    plan: Option<&str>,
+) -> String {
+    let mut out =
+        String::from("You are taking over an in-progress codex conversation from another agent.\n");
+    if let Some(cwd) = &export.cwd {
+        match git_ref {
+            Some(git_ref) => out.push_str(&format!("Repository: {cwd} at ref {git_ref}\n")),
+            None => out.push_str(&format!("Repository: {cwd}\n")),
+        }
+    }
+    if let Some(plan) = plan {
+        out.push_str("\n## Pending plan\n\n");
+        out.push_str(plan);
+        out.push('\n');
+    }
+    out.push_str("\n## Transcript so far\n\n");
+    out.push_str(&export.render(ExportFormat::Markdown));
+    out.push_str("\nContinue the task from where the transcript leaves off.\n");
+    out
+}
+
+/// Prompt that folds the remote agent's outcome back into a local
+/// conversation.
+fn hand_back_prompt(agent: &Agent, log_tail: &str) -> String {
+    format!(
+        "A remote agent ({name}) finished working on this repository on your behalf.\n\
+         Review its log below, verify the work in the working tree, and finish anything left over.\n\n\
+         ## Agent log\n\n{log_tail}\n",
+        name = agent.name
+    )
+}
+
+fn is_terminal(status: &str) -> bool {
+    !matches!(status, "pending" | "queued" | "starting" | "running")
+}
+
+fn is_success(status: &str) -> bool {
+    matches!(status, "succeeded" | "completed" | "finished" | "done")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_state;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn handoff_prompt_packages_repo_plan_and_transcript() {
+        let export = ConversationExport {
+            id: "t-1".to_string(),
+            timestamp: None,
+            cwd: Some("/srv/repos/api".to_string()),
+            entries: Vec::new(),
+        };
+        let prompt = handoff_prompt(&export, Some("feature/retry"), Some("1. add retries"));
+        assert!(prompt.contains("Repository: /srv/repos/api at ref feature/retry"));
+        assert!(prompt.contains("## Pending plan"));
+        assert!(prompt.contains("1. add retries"));
+        assert!(prompt.contains("## Transcript so far"));
+    }
+
+    #[test]
+    fn agent_statuses_classify_terminal_and_success() {
+        assert!(!is_terminal("running"));
+        assert!(!is_terminal("pending"));
+        assert!(is_terminal("succeeded"));
+        assert!(is_terminal("cancelled"));
+        assert!(is_success("succeeded"));
+        assert!(!is_success("cancelled"));
+    }
+
+    #[tokio::test]
+    async fn offloading_unknown_conversation_is_not_found() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let response = offload_conversation(
+            State(test_state(codex_home.path()).await),
+            Path("0199a213-81ba-7142-ba53-6b2ebc1b3a5a".to_string()),
+            Json(OffloadRequest::default()),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
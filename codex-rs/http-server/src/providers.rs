@@ -0,0 +1,156 @@
+//! Runtime provider management and the `/admin/providers` routes.
+//!
+//! Surfaces the merged provider view (built-ins plus `model_providers` from
+//! config.toml) and lets operators hot-add or replace a definition while the
+//! server is running, e.g. to point at a fallback endpoint during a provider
+//! outage. Definitions added here are not written back to config.toml.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use axum::Json;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use codex_model_provider_info::ModelProviderInfo;
+
+use crate::AppState;
+use crate::error::ApiError;
+use crate::storage::audit;
+
+/// Shared registry of provider definitions, keyed by provider id.
+#[derive(Clone)]
+pub(crate) struct ProviderRegistry {
+    providers: Arc<Mutex<BTreeMap<String, ModelProviderInfo>>>,
+}
+
+impl ProviderRegistry {
+    /// Starts from the merged view built at startup.
+    pub(crate) fn new(merged: HashMap<String, ModelProviderInfo>) -> Self {
+        Self {
+            providers: Arc::new(Mutex::new(merged.into_iter().collect())),
+        }
+    }
+
+    pub(crate) fn list(&self) -> BTreeMap<String, ModelProviderInfo> {
+        self.lock().clone()
+    }
+
+    /// Adds or replaces a provider; `true` if an existing one was replaced.
+    pub(crate) fn upsert(&self, id: String, provider: ModelProviderInfo) -> bool {
+        self.lock().insert(id, provider).is_some()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, ModelProviderInfo>> {
+        match self.providers.lock() {
+            Ok(providers) => providers,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+/// `GET /admin/providers`
+pub(crate) async fn list_providers(
+    State(state): State<AppState>,
+) -> Json<BTreeMap<String, ModelProviderInfo>> {
+    Json(state.providers.list())
+}
+
+/// `PUT /admin/providers/{id}`
+///
+/// The body is a full [`ModelProviderInfo`]; unknown or malformed fields are
+/// rejected by deserialization before the handler runs.
+pub(crate) async fn put_provider(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(provider): Json<ModelProviderInfo>,
+) -> Response {
+    if id.trim().is_empty() {
+        return ApiError::invalid_request("provider id must not be empty").into_response();
+    }
+    if provider.base_url.as_deref().is_none_or(str::is_empty) {
+        return ApiError::invalid_request("provider base_url must not be empty").into_response();
+    }
+    let replaced = state.providers.upsert(id.clone(), provider.clone());
+    audit(
+        &*state.storage,
+        "provider.update",
+        &format!("provider {id}"),
+    )
+    .await;
+    let status = if replaced {
+        StatusCode::OK
+    } else {
+        StatusCode::CREATED
+    };
+    (status, Json(provider)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_state;
+    use pretty_assertions::assert_eq;
+
+    fn fallback_provider() -> ModelProviderInfo {
+        ModelProviderInfo {
+            name: "Fallback".to_string(),
+            base_url: Some("https://llm.fallback.example/v1".to_string()),
+            env_key: Some("FALLBACK_API_KEY".to_string()),
+            ..ModelProviderInfo::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn merged_view_includes_built_ins() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let providers = list_providers(State(test_state(codex_home.path()).await))
+            .await
+            .0;
+        assert!(providers.contains_key("openai"));
+        assert!(providers.contains_key("ollama"));
+    }
+
+    #[tokio::test]
+    async fn hot_added_provider_shows_up_in_the_list() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let state = test_state(codex_home.path()).await;
+        let response = put_provider(
+            State(state.clone()),
+            Path("fallback".to_string()),
+            Json(fallback_provider()),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let providers = list_providers(State(state.clone())).await.0;
+        assert_eq!(providers["fallback"].name, "Fallback");
+
+        let replaced = put_provider(
+            State(state),
+            Path("fallback".to_string()),
+            Json(fallback_provider()),
+        )
+        .await;
+        assert_eq!(replaced.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn provider_without_base_url_is_rejected() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let response = put_provider(
+            State(test_state(codex_home.path()).await),
+            Path("fallback".to_string()),
+            Json(ModelProviderInfo {
+                name: "Fallback".to_string(),
+                ..ModelProviderInfo::default()
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
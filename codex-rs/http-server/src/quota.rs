@@ -0,0 +1,356 @@
+//! Disk quotas for conversation workspaces and the
+//! `/conversations/{id}/cleanup` route.
+//!
+//! A conversation without a recorded working directory gets a scratch
+//! workspace under `CODEX_HOME/workspaces/{id}`, and jobs already run in
+//! worktrees under `CODEX_HOME` — on a multi-tenant server those
+//! directories grow until the disk fills, silently. When
+//! `[http_server.quota]` sets a cap, a turn in a workspace already over it
+//! is refused with `413` and a `quota.exceeded` event, so the writes stop
+//! with a clear signal instead of an ENOSPC deep inside the run. The
+//! cleanup route deletes a conversation's scratch workspace on demand, and
+//! a graceful shutdown deletes all of them.
+
+use std::path::Component;
+use std::path::Path as FsPath;
+use std::path::PathBuf;
+
+use axum::Json;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use codex_config::types::HttpQuotaToml;
+use codex_core::export::load_conversation_export;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::AppState;
+use crate::error::ApiError;
+use crate::events::ServerEvent;
+use crate::storage::audit;
+
+/// Scratch workspaces under `CODEX_HOME`, one subdirectory per
+/// conversation.
+pub(crate) const WORKSPACES_SUBDIR: &str = "workspaces";
+
+/// Disk bounds enforced on conversation workspaces.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Quota {
+    /// Largest workspace size, in bytes, a turn may start over; `None`
+    /// disables enforcement.
+    pub(crate) max_workspace_bytes: Option<u64>,
+}
+
+impl Quota {
+    pub(crate) fn from_toml(toml: Option<&HttpQuotaToml>) -> Self {
+        Self {
+            max_workspace_bytes: toml.and_then(|toml| toml.max_workspace_bytes),
+        }
+    }
+}
+
+/// The conversation's scratch workspace; used as the turn's working
+/// directory when the conversation has no recorded one.
+pub(crate) fn workspace_dir(codex_home: &FsPath, id: &str) -> PathBuf {
+    codex_home.join(WORKSPACES_SUBDIR).join(id)
+}
+
+/// Total size of every file under `dir`, without following symlinks; `0`
+/// when the directory does not exist.
+pub(crate) async fn dir_size(dir: &FsPath) -> u64 {
+    let dir = dir.to_path_buf();
+    tokio::task::spawn_blocking(move || dir_size_blocking(&dir))
+        .await
+        .unwrap_or(0)
+}
+
+fn dir_size_blocking(dir: &FsPath) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    let mut total = 0;
+    for entry in entries.flatten() {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            total += dir_size_blocking(&entry.path());
+        } else if metadata.is_file() {
+            total += metadata.len();
+        }
+    }
+    total
+}
+
+/// Refuses the turn when `cwd` is a workspace under `CODEX_HOME` already
+/// over the configured quota, publishing `quota.exceeded` so the operator
+/// hears about it.
+pub(crate) async fn check_workspace(
+    state: &AppState,
+    id: &str,
+    cwd: &FsPath,
+) -> Result<(), ApiError> {
+    let Some(max_workspace_bytes) = state.quota.max_workspace_bytes else {
+        return Ok(());
+    };
+    // Checkouts the operator pointed a conversation at are theirs to
+    // manage; the quota covers only what this server provisions.
+    if !cwd.starts_with(&state.codex_home) {
+        return Ok(());
+    }
+    let used_bytes = dir_size(cwd).await;
+    if used_bytes <= max_workspace_bytes {
+        return Ok(());
+    }
+    state
+        .events
+        .publish(ServerEvent {
+            kind: "quota.exceeded".to_string(),
+            payload: serde_json::json!({
+                "conversation_id": id,
+                "used_bytes": used_bytes,
+                "max_workspace_bytes": max_workspace_bytes,
+            }),
+        })
+        .await;
+    Err(ApiError::payload_too_large(format!(
+        "conversation {id}'s workspace uses {used_bytes} bytes, over the \
+         {max_workspace_bytes}-byte quota; clean it up with \
+         POST /conversations/{id}/cleanup"
+    ))
+    .with_details(serde_json::json!({
+        "used_bytes": used_bytes,
+        "max_workspace_bytes": max_workspace_bytes,
+    })))
+}
+
+/// Resolves the directory a turn in conversation `id` runs in — its
+/// recorded working directory, or its scratch workspace created on demand
+/// — and enforces the quota on it.
+pub(crate) async fn turn_cwd(
+    state: &AppState,
+    id: &str,
+    recorded: Option<PathBuf>,
+) -> Result<PathBuf, ApiError> {
+    let cwd = match recorded {
+        Some(cwd) => cwd,
+        None => {
+            let workspace = workspace_dir(&state.codex_home, id);
+            tokio::fs::create_dir_all(&workspace).await.map_err(|err| {
+                ApiError::internal(format!("failed to create the workspace: {err}"))
+            })?;
+            workspace
+        }
+    };
+    check_workspace(state, id, &cwd).await?;
+    Ok(cwd)
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct CleanupResponse {
+    /// Bytes the workspace held before it was deleted.
+    pub freed_bytes: u64,
+}
+
+/// Conversation ids name one directory under `workspaces/`; anything with
+/// other components — `..`, separators (axum percent-decodes `%2F`), an
+/// absolute prefix — could address a directory outside it, and this route
+/// deletes what the id addresses.
+fn is_safe_workspace_id(id: &str) -> bool {
+    !id.is_empty()
+        && FsPath::new(id)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+/// `POST /conversations/{id}/cleanup`
+///
+/// Deletes the conversation's scratch workspace and reports how much disk
+/// that freed; `404` for ids no conversation has and `409` while the
+/// conversation has a turn running in it.
+pub(crate) async fn cleanup_workspace(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Response {
+    if !is_safe_workspace_id(&id) {
+        return ApiError::invalid_request(format!("invalid conversation id {id}")).into_response();
+    }
+    match load_conversation_export(&state.codex_home, &id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return ApiError::not_found(format!("no conversation with id {id}")).into_response();
+        }
+        Err(err) => {
+            return ApiError::internal(format!("failed to load conversation: {err}"))
+                .into_response();
+        }
+    }
+    if let Err(active) = state.active_turns.try_begin(&id, "workspace cleanup") {
+        return ApiError::invalid_state(format!("conversation {id} has a turn running"))
+            .with_details(serde_json::to_value(&active).unwrap_or_default())
+            .into_response();
+    }
+    let dir = workspace_dir(&state.codex_home, &id);
+    let freed_bytes = dir_size(&dir).await;
+    let removed = match tokio::fs::remove_dir_all(&dir).await {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err),
+    };
+    state.active_turns.finish(&id);
+    if let Err(err) = removed {
+        return ApiError::internal(format!("failed to delete the workspace: {err}"))
+            .into_response();
+    }
+    audit(
+        &*state.storage,
+        "workspace.cleanup",
+        &format!("conversation {id}"),
+    )
+    .await;
+    state
+        .events
+        .publish(ServerEvent {
+            kind: "workspace.cleaned".to_string(),
+            payload: serde_json::json!({
+                "conversation_id": id,
+                "freed_bytes": freed_bytes,
+            }),
+        })
+        .await;
+    Json(CleanupResponse { freed_bytes }).into_response()
+}
+
+/// Deletes every scratch workspace; run when the server shuts down
+/// gracefully so abandoned workspaces do not outlive their conversations.
+pub(crate) async fn cleanup_all(codex_home: &FsPath) {
+    let dir = codex_home.join(WORKSPACES_SUBDIR);
+    match tokio::fs::remove_dir_all(&dir).await {
+        Ok(()) => {}
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => warn!("failed to clean up {}: {err}", dir.display()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+    use crate::test_support::test_state;
+    use axum::http::StatusCode;
+    use pretty_assertions::assert_eq;
+
+    const CONVERSATION_ID: &str = "0199a213-81ba-7142-ba53-6b2ebc1b3a5a";
+
+    /// Writes a one-line rollout so the cleanup route finds the
+    /// conversation.
+    fn seed_conversation(codex_home: &FsPath, id: &str) {
+        let dir = codex_home.join("sessions/2026/08/27");
+        std::fs::create_dir_all(&dir).expect("create sessions dir");
+        let line = serde_json::json!({
+            "timestamp": "2026-08-27T10:00:00Z",
+            "type": "response_item",
+            "payload": {
+                "type": "message",
+                "role": "user",
+                "content": [{"type": "input_text", "text": "seed"}],
+            },
+        });
+        std::fs::write(
+            dir.join(format!("rollout-2026-08-27T10-00-00-{id}.jsonl")),
+            format!("{line}\n"),
+        )
+        .expect("write rollout");
+    }
+
+    #[tokio::test]
+    async fn dir_size_sums_nested_files() {
+        let dir = tempfile::tempdir().expect("create tempdir");
+        std::fs::create_dir_all(dir.path().join("nested")).expect("create nested");
+        std::fs::write(dir.path().join("a.txt"), b"12345").expect("write a");
+        std::fs::write(dir.path().join("nested/b.txt"), b"123").expect("write b");
+        assert_eq!(dir_size(dir.path()).await, 8);
+        assert_eq!(dir_size(&dir.path().join("missing")).await, 0);
+    }
+
+    #[tokio::test]
+    async fn turns_over_quota_are_refused_with_an_event() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let mut state = test_state(codex_home.path()).await;
+        state.quota = Quota {
+            max_workspace_bytes: Some(4),
+        };
+        let workspace = workspace_dir(codex_home.path(), "abc");
+        std::fs::create_dir_all(&workspace).expect("create workspace");
+        std::fs::write(workspace.join("big.bin"), b"12345678").expect("write file");
+
+        let mut rx = state.events.subscribe();
+        let err = check_workspace(&state, "abc", &workspace)
+            .await
+            .expect_err("over quota");
+        assert_eq!(err.code(), ErrorCode::PayloadTooLarge);
+        let event = rx.next().await.expect("receive event");
+        assert_eq!(event.kind, "quota.exceeded");
+        assert_eq!(event.payload["used_bytes"], serde_json::json!(8));
+
+        // Directories the server does not manage are not measured.
+        let outside = tempfile::tempdir().expect("create tempdir");
+        check_workspace(&state, "abc", outside.path())
+            .await
+            .expect("outside CODEX_HOME");
+    }
+
+    #[tokio::test]
+    async fn cleanup_deletes_the_workspace_and_reports_freed_bytes() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let state = test_state(codex_home.path()).await;
+        seed_conversation(codex_home.path(), CONVERSATION_ID);
+        let workspace = workspace_dir(codex_home.path(), CONVERSATION_ID);
+        std::fs::create_dir_all(&workspace).expect("create workspace");
+        std::fs::write(workspace.join("scratch.txt"), b"1234").expect("write file");
+
+        let response =
+            cleanup_workspace(State(state.clone()), Path(CONVERSATION_ID.to_string())).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!workspace.exists());
+
+        // Cleaning an absent workspace is fine and frees nothing.
+        let response = cleanup_workspace(State(state), Path(CONVERSATION_ID.to_string())).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn cleanup_refuses_while_a_turn_runs() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let state = test_state(codex_home.path()).await;
+        seed_conversation(codex_home.path(), CONVERSATION_ID);
+        state
+            .active_turns
+            .try_begin(CONVERSATION_ID, "long running")
+            .expect("claim");
+        let response =
+            cleanup_workspace(State(state.clone()), Path(CONVERSATION_ID.to_string())).await;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn cleanup_for_unknown_conversation_is_not_found() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let state = test_state(codex_home.path()).await;
+        let response = cleanup_workspace(State(state), Path(CONVERSATION_ID.to_string())).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn cleanup_rejects_ids_that_escape_the_workspaces_dir() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let state = test_state(codex_home.path()).await;
+        std::fs::write(codex_home.path().join("config.toml"), b"# keep").expect("write sentinel");
+        for id in ["..", "../..", "a/../../b", "/etc", "."] {
+            let response = cleanup_workspace(State(state.clone()), Path(id.to_string())).await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "id {id}");
+        }
+        assert!(codex_home.path().join("config.toml").exists());
+    }
+}
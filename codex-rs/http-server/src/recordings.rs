@@ -0,0 +1,451 @@
+//! Session recordings and deterministic replays: the
+//! `/conversations/{id}/recordings`, `/recordings`, and `/replays` routes.
+//!
+//! A recording captures the slice of a conversation's transcript — user
+//! inputs, model responses, and tool calls — between an explicit start and
+//! stop, as a self-contained record that survives the rollout file. A replay
+//! re-executes the recorded user inputs through the conversation runner
+//! while a throwaway mock provider serves the recorded model responses, so
+//! the model side is pinned and any divergence in the resulting output can
+//! be attributed to the codex version under test. In-progress start markers
+//! are process-local and do not survive a restart.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use axum::Json;
+use axum::Router;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::http::header::CONTENT_TYPE;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use axum::routing::post;
+use chrono::DateTime;
+use chrono::Utc;
+use codex_core::export::ConversationExport;
+use codex_core::export::TranscriptEntry;
+use codex_core::export::TranscriptEntryKind;
+use codex_core::export::load_conversation_export;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::AppState;
+use crate::error::ApiError;
+use crate::storage::audit;
+
+/// A self-contained transcript slice captured between start and stop.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Recording {
+    pub id: u64,
+    pub conversation_id: String,
+    pub created_at: DateTime<Utc>,
+    pub entries: Vec<TranscriptEntry>,
+}
+
+/// Start markers for recordings in progress, keyed by conversation id.
+#[derive(Clone, Default)]
+pub(crate) struct RecordingSessions {
+    marks: Arc<Mutex<HashMap<String, usize>>>,
+}
+
+impl RecordingSessions {
+    /// Marks the entry count a later stop slices from; restarts an
+    /// in-progress recording for the same conversation.
+    fn start(&self, conversation_id: &str, entries: usize) {
+        self.lock().insert(conversation_id.to_string(), entries);
+    }
+
+    fn stop(&self, conversation_id: &str) -> Option<usize> {
+        self.lock().remove(conversation_id)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, usize>> {
+        match self.marks.lock() {
+            Ok(marks) => marks,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum RecordingAction {
+    Start,
+    Stop,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct RecordingRequest {
+    action: RecordingAction,
+}
+
+/// `POST /conversations/{id}/recordings`
+///
+/// `{"action": "start"}` marks the current end of the transcript;
+/// `{"action": "stop"}` captures everything since the mark as a recording.
+pub(crate) async fn record_conversation(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<RecordingRequest>,
+) -> Response {
+    let export = match load_conversation_export(&state.codex_home, &id).await {
+        Ok(Some(export)) => export,
+        Ok(None) => {
+            return ApiError::not_found(format!("no conversation with id {id}")).into_response();
+        }
+        Err(err) => {
+            return ApiError::internal(format!("failed to load conversation: {err}"))
+                .into_response();
+        }
+    };
+    match request.action {
+        RecordingAction::Start => {
+            state.recording_sessions.start(&id, export.entries.len());
+            Json(serde_json::json!({
+                "conversation_id": id,
+                "status": "recording",
+            }))
+            .into_response()
+        }
+        RecordingAction::Stop => {
+            let Some(mark) = state.recording_sessions.stop(&id) else {
+                return ApiError::invalid_state(format!(
+                    "no recording in progress for conversation {id}"
+                ))
+                .into_response();
+            };
+            match save_recording(&state, id, &export, mark).await {
+                Ok(recording) => {
+                    audit(
+                        &*state.storage,
+                        "recording.create",
+                        &format!("recording {}", recording.id),
+                    )
+                    .await;
+                    (StatusCode::CREATED, Json(recording)).into_response()
+                }
+                Err(err) => {
+                    ApiError::internal(format!("failed to save recording: {err}")).into_response()
+                }
+            }
+        }
+    }
+}
+
+async fn save_recording(
+    state: &AppState,
+    conversation_id: String,
+    export: &ConversationExport,
+    mark: usize,
+) -> anyhow::Result<Recording> {
+    let next_id = state
+        .storage
+        .load_recordings()
+        .await?
+        .iter()
+        .map(|recording| recording.id)
+        .max()
+        .unwrap_or(0)
+        + 1;
+    let recording = Recording {
+        id: next_id,
+        conversation_id,
+        created_at: Utc::now(),
+        entries: export.entries.get(mark..).unwrap_or_default().to_vec(),
+    };
+    state.storage.save_recording(&recording).await?;
+    Ok(recording)
+}
+
+/// `GET /recordings`
+pub(crate) async fn list_recordings(State(state): State<AppState>) -> Response {
+    match state.storage.load_recordings().await {
+        Ok(recordings) => Json(recordings).into_response(),
+        Err(err) => ApiError::internal(format!("failed to load recordings: {err}")).into_response(),
+    }
+}
+
+/// `GET /recordings/{id}`
+pub(crate) async fn get_recording(State(state): State<AppState>, Path(id): Path<u64>) -> Response {
+    match state.storage.load_recordings().await {
+        Ok(recordings) => match recordings.into_iter().find(|recording| recording.id == id) {
+            Some(recording) => Json(recording).into_response(),
+            None => ApiError::not_found(format!("no recording with id {id}")).into_response(),
+        },
+        Err(err) => ApiError::internal(format!("failed to load recordings: {err}")).into_response(),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CreateReplayRequest {
+    recording_id: u64,
+}
+
+/// One recorded user input re-executed during a replay.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ReplayStep {
+    pub prompt: String,
+    /// Assistant response recorded for this input.
+    pub expected: String,
+    /// What the re-executed conversation produced.
+    pub output: String,
+    pub success: bool,
+    /// The new output does not contain the recorded response.
+    pub diverged: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ReplayReport {
+    pub recording_id: u64,
+    pub steps: Vec<ReplayStep>,
+    pub diverged: bool,
+}
+
+/// `POST /replays`
+///
+/// Re-executes a recording's user inputs through the conversation runner
+/// with the model pinned to the recorded responses.
+pub(crate) async fn create_replay(
+    State(state): State<AppState>,
+    Json(request): Json<CreateReplayRequest>,
+) -> Response {
+    let recording = match state.storage.load_recordings().await {
+        Ok(recordings) => match recordings
+            .into_iter()
+            .find(|recording| recording.id == request.recording_id)
+        {
+            Some(recording) => recording,
+            None => {
+                return ApiError::not_found(format!(
+                    "no recording with id {}",
+                    request.recording_id
+                ))
+                .into_response();
+            }
+        },
+        Err(err) => {
+            return ApiError::internal(format!("failed to load recordings: {err}")).into_response();
+        }
+    };
+    let steps = replay_steps(&recording.entries);
+    let responses: Vec<String> = steps.iter().map(|(_, expected)| expected.clone()).collect();
+    let (base_url, provider) = match start_mock_provider(responses).await {
+        Ok(started) => started,
+        Err(err) => {
+            return ApiError::internal(format!("failed to start mock provider: {err}"))
+                .into_response();
+        }
+    };
+    let overrides = provider_overrides(&base_url);
+    let mut report = ReplayReport {
+        recording_id: recording.id,
+        steps: Vec::new(),
+        diverged: false,
+    };
+    for (prompt, expected) in steps {
+        let outcome = state.runner.run(&prompt, None, &overrides).await;
+        let diverged = !outcome.success || !outcome.detail.contains(&expected);
+        report.diverged |= diverged;
+        report.steps.push(ReplayStep {
+            prompt,
+            expected,
+            output: outcome.detail,
+            success: outcome.success,
+            diverged,
+        });
+    }
+    provider.abort();
+    audit(
+        &*state.storage,
+        "replay.run",
+        &format!("recording {}", recording.id),
+    )
+    .await;
+    Json(report).into_response()
+}
+
+/// Pairs each recorded user input with the assistant response that followed
+/// it (empty when the recording ends first).
+fn replay_steps(entries: &[TranscriptEntry]) -> Vec<(String, String)> {
+    let mut steps = Vec::new();
+    for (index, entry) in entries.iter().enumerate() {
+        if entry.kind != TranscriptEntryKind::Message || entry.heading != "User" {
+            continue;
+        }
+        let expected = entries[index + 1..]
+            .iter()
+            .find(|next| next.kind == TranscriptEntryKind::Message && next.heading == "Assistant")
+            .map(|next| next.body.clone())
+            .unwrap_or_default();
+        steps.push((entry.body.clone(), expected));
+    }
+    steps
+}
+
+/// `-c` overrides pointing a `codex exec` run at the mock provider.
+fn provider_overrides(base_url: &str) -> Vec<String> {
+    vec![
+        "model_provider=\"replay\"".to_string(),
+        "model_providers.replay.name=\"replay\"".to_string(),
+        format!("model_providers.replay.base_url={base_url:?}"),
+        "model_providers.replay.wire_api=\"chat\"".to_string(),
+    ]
+}
+
+/// Serves the recorded assistant responses, in order, as OpenAI-style chat
+/// completions on an ephemeral port for the duration of one replay.
+async fn start_mock_provider(
+    responses: Vec<String>,
+) -> std::io::Result<(String, tokio::task::JoinHandle<()>)> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let base_url = format!("http://{}/v1", listener.local_addr()?);
+    let queue = Arc::new(Mutex::new(VecDeque::from(responses)));
+    let app = Router::new().route(
+        "/v1/chat/completions",
+        post(move || {
+            let queue = queue.clone();
+            async move {
+                let next = {
+                    let mut queue = match queue.lock() {
+                        Ok(queue) => queue,
+                        Err(poisoned) => poisoned.into_inner(),
+                    };
+                    queue.pop_front()
+                };
+                let Some(content) = next else {
+                    warn!("mock provider ran out of recorded responses");
+                    return ApiError::invalid_state("no recorded response left".to_string())
+                        .into_response();
+                };
+                (
+                    [(CONTENT_TYPE, "text/event-stream")],
+                    chat_completion_sse(&content),
+                )
+                    .into_response()
+            }
+        }),
+    );
+    let provider = tokio::spawn(async move {
+        if let Err(err) = axum::serve(listener, app).await {
+            warn!("mock provider exited: {err}");
+        }
+    });
+    Ok((base_url, provider))
+}
+
+/// One recorded response as a streamed chat completion.
+fn chat_completion_sse(content: &str) -> String {
+    let delta = serde_json::json!({
+        "id": "chatcmpl-replay",
+        "object": "chat.completion.chunk",
+        "choices": [{
+            "index": 0,
+            "delta": {"role": "assistant", "content": content},
+            "finish_reason": null,
+        }],
+    });
+    let stop = serde_json::json!({
+        "id": "chatcmpl-replay",
+        "object": "chat.completion.chunk",
+        "choices": [{"index": 0, "delta": {}, "finish_reason": "stop"}],
+    });
+    format!("data: {delta}\n\ndata: {stop}\n\ndata: [DONE]\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_state;
+    use pretty_assertions::assert_eq;
+
+    fn message(heading: &str, body: &str) -> TranscriptEntry {
+        TranscriptEntry {
+            kind: TranscriptEntryKind::Message,
+            heading: heading.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn steps_pair_inputs_with_following_responses() {
+        let entries = vec![
+            message("User", "fix the bug"),
+            TranscriptEntry {
+                kind: TranscriptEntryKind::Command,
+                heading: "Command".to_string(),
+                body: "cargo test".to_string(),
+            },
+            message("Assistant", "done, tests pass"),
+            message("User", "now update the docs"),
+        ];
+        assert_eq!(
+            replay_steps(&entries),
+            vec![
+                ("fix the bug".to_string(), "done, tests pass".to_string()),
+                ("now update the docs".to_string(), String::new()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn recording_unknown_conversation_is_not_found() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let response = record_conversation(
+            State(test_state(codex_home.path()).await),
+            Path("0199a213-81ba-7142-ba53-6b2ebc1b3a5a".to_string()),
+            Json(RecordingRequest {
+                action: RecordingAction::Start,
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn replaying_unknown_recording_is_not_found() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let response = create_replay(
+            State(test_state(codex_home.path()).await),
+            Json(CreateReplayRequest { recording_id: 42 }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn replay_reports_divergence_per_step() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let state = test_state(codex_home.path()).await;
+        state
+            .storage
+            .save_recording(&Recording {
+                id: 1,
+                conversation_id: "t-1".to_string(),
+                created_at: Utc::now(),
+                entries: vec![
+                    message("User", "fix the bug"),
+                    message("Assistant", "done, tests pass"),
+                ],
+            })
+            .await
+            .expect("save recording");
+
+        // The test runner succeeds with empty output, which cannot contain
+        // the recorded response, so the step diverges.
+        let response =
+            create_replay(State(state), Json(CreateReplayRequest { recording_id: 1 })).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn mock_provider_streams_recorded_content() {
+        let body = chat_completion_sse("done, tests pass");
+        assert!(body.contains("done, tests pass"));
+        assert!(body.ends_with("data: [DONE]\n\n"));
+    }
+}
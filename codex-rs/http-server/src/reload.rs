@@ -0,0 +1,167 @@
+//! Live reload of the HTTP server's non-disruptive settings.
+//!
+//! A background task watches `CODEX_HOME/config.toml` and re-applies the
+//! `[http_server]` settings that can change without a restart — the GitHub
+//! token, sandbox limits, and config-defined schedules and templates —
+//! since restarting the process would drop every active SSE stream.
+//! `POST /admin/reload` triggers the same reload explicitly. The listen
+//! address, worker count, and event bus still require a restart.
+
+use std::sync::Arc;
+use std::sync::RwLock;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use axum::Json;
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use codex_config::CONFIG_TOML_FILE;
+use codex_config::config_toml::ConfigToml;
+use codex_config::types::HttpSandboxLimitsToml;
+use codex_config::types::HttpServerConfig;
+use serde::Serialize;
+use tracing::info;
+use tracing::warn;
+
+use crate::AppState;
+use crate::error::ApiError;
+use crate::storage::audit;
+
+const WATCH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Settings handlers read per request, so a reload takes effect
+/// immediately.
+#[derive(Debug, Default)]
+pub(crate) struct ReloadableSettings {
+    pub github_token: Option<String>,
+    pub sandbox_limits: HttpSandboxLimitsToml,
+}
+
+pub(crate) type SharedSettings = Arc<RwLock<ReloadableSettings>>;
+
+/// What a reload applied, echoed by `POST /admin/reload`.
+#[derive(Debug, Serialize)]
+pub(crate) struct ReloadOutcome {
+    /// Schedules registered after the reload.
+    schedules: usize,
+    /// Templates registered after the reload.
+    templates: usize,
+}
+
+/// Re-reads config.toml and applies the non-disruptive `[http_server]`
+/// settings. A missing file applies the defaults.
+pub(crate) async fn reload_from_disk(state: &AppState) -> Result<ReloadOutcome, String> {
+    let path = state.codex_home.join(CONFIG_TOML_FILE);
+    let config = match tokio::fs::read_to_string(&path).await {
+        Ok(contents) => toml::from_str::<ConfigToml>(&contents)
+            .map_err(|err| format!("failed to parse {}: {err}", path.display()))?,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => ConfigToml::default(),
+        Err(err) => return Err(format!("failed to read {}: {err}", path.display())),
+    };
+    let http_server = HttpServerConfig::from_toml(config.http_server);
+    {
+        let mut settings = match state.settings.write() {
+            Ok(settings) => settings,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        settings.github_token = http_server.github_token;
+        settings.sandbox_limits = http_server.sandbox_limits;
+    }
+    state
+        .scheduler
+        .seed_from_config(&http_server.schedules)
+        .await;
+    state.templates.seed_from_config(&http_server.templates);
+    Ok(ReloadOutcome {
+        schedules: state.scheduler.list().len(),
+        templates: state.templates.list().len(),
+    })
+}
+
+/// `POST /admin/reload`
+pub(crate) async fn reload_config(State(state): State<AppState>) -> Response {
+    match reload_from_disk(&state).await {
+        Ok(outcome) => {
+            audit(&*state.storage, "config.reload", "explicit reload").await;
+            Json(outcome).into_response()
+        }
+        Err(message) => ApiError::internal(message).into_response(),
+    }
+}
+
+/// Polls config.toml and reloads when its mtime changes.
+pub(crate) async fn watch_loop(state: AppState) {
+    let path = state.codex_home.join(CONFIG_TOML_FILE);
+    let mut last_modified = modified_at(&path).await;
+    loop {
+        tokio::time::sleep(WATCH_INTERVAL).await;
+        let modified = modified_at(&path).await;
+        if modified == last_modified {
+            continue;
+        }
+        last_modified = modified;
+        match reload_from_disk(&state).await {
+            Ok(_) => info!("reloaded {}", path.display()),
+            Err(err) => warn!("config reload failed: {err}"),
+        }
+    }
+}
+
+async fn modified_at(path: &std::path::Path) -> Option<SystemTime> {
+    tokio::fs::metadata(path).await.ok()?.modified().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_state;
+    use axum::http::StatusCode;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn reload_applies_new_settings() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let state = test_state(codex_home.path()).await;
+        std::fs::write(
+            codex_home.path().join(CONFIG_TOML_FILE),
+            r#"
+[http_server]
+github_token = "rotated"
+
+[[http_server.templates]]
+name = "triage-bug"
+prompt = "triage {issue}"
+"#,
+        )
+        .expect("write config");
+
+        let outcome = reload_from_disk(&state).await.expect("reload");
+        assert_eq!(outcome.templates, 1);
+        assert_eq!(
+            state.settings.read().expect("read settings").github_token,
+            Some("rotated".to_string())
+        );
+        assert_eq!(
+            state.templates.get("triage-bug"),
+            Some("triage {issue}".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn malformed_config_is_reported() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let state = test_state(codex_home.path()).await;
+        std::fs::write(codex_home.path().join(CONFIG_TOML_FILE), "http_server = 3")
+            .expect("write config");
+        assert!(reload_from_disk(&state).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn missing_config_reloads_defaults() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let state = test_state(codex_home.path()).await;
+        let response = reload_config(State(state)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,115 @@
+//! Retrying a failed turn: the `/conversations/{id}/retry-last` route.
+//!
+//! A turn that dies on a provider error leaves the client with nothing to
+//! do but re-send the message, which duplicates it in the conversation's
+//! history. Every completion turn's input and outcome are recorded, so a
+//! retry replays the stored input — optionally on a different model —
+//! without the client holding onto it.
+
+use std::path::PathBuf;
+
+use axum::Json;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::http::HeaderMap;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use chrono::DateTime;
+use chrono::Utc;
+use codex_core::export::load_conversation_export;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::AppState;
+use crate::complete::TurnInput;
+use crate::complete::stream_turn;
+use crate::error::ApiError;
+use crate::storage::audit;
+
+/// The last completion turn run in one conversation, kept so a failure can
+/// be retried verbatim.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct TurnAttempt {
+    pub conversation_id: String,
+    pub input: TurnInput,
+    pub success: bool,
+    pub at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct RetryRequest {
+    /// Model to retry on instead of the one the turn failed with.
+    model: Option<String>,
+}
+
+/// `POST /conversations/{id}/retry-last`
+pub(crate) async fn retry_last(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    body: Option<Json<RetryRequest>>,
+) -> Response {
+    let export = match load_conversation_export(&state.codex_home, &id).await {
+        Ok(Some(export)) => export,
+        Ok(None) => {
+            return ApiError::not_found(format!("no conversation with id {id}")).into_response();
+        }
+        Err(err) => {
+            return ApiError::internal(format!("failed to load conversation: {err}"))
+                .into_response();
+        }
+    };
+    let attempt = match state.storage.load_turn_attempt(&id).await {
+        Ok(Some(attempt)) => attempt,
+        Ok(None) => {
+            return ApiError::not_found(format!("no recorded turn for conversation {id}"))
+                .into_response();
+        }
+        Err(err) => {
+            return ApiError::internal(format!("failed to load last turn: {err}")).into_response();
+        }
+    };
+    if attempt.success {
+        return ApiError::invalid_state(format!(
+            "the last turn for conversation {id} succeeded; there is nothing to retry"
+        ))
+        .into_response();
+    }
+    let mut input = attempt.input;
+    if let Some(model) = body.and_then(|Json(request)| request.model) {
+        input.config_overrides.push(format!("model={model}"));
+    }
+    let cwd = match crate::quota::turn_cwd(&state, &id, export.cwd.map(PathBuf::from)).await {
+        Ok(cwd) => cwd,
+        Err(err) => return err.into_response(),
+    };
+    if let Err(active) = state.active_turns.try_begin(&id, &input.prompt) {
+        return ApiError::invalid_state(format!("conversation {id} already has a turn running"))
+            .with_details(serde_json::to_value(&active).unwrap_or_default())
+            .into_response();
+    }
+    audit(&*state.storage, "retry.run", &format!("conversation {id}")).await;
+    let span = crate::trace::turn_span(&headers, &id);
+    stream_turn(state, id, input, Some(cwd), span).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_state;
+    use axum::http::StatusCode;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn retrying_unknown_conversation_is_not_found() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let response = retry_last(
+            State(test_state(codex_home.path()).await),
+            Path("0199a213-81ba-7142-ba53-6b2ebc1b3a5a".to_string()),
+            HeaderMap::new(),
+            None,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,387 @@
+//! Runs one non-interactive conversation for the scheduler and job queue.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+
+use async_trait::async_trait;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncReadExt;
+use tokio::io::BufReader;
+use tokio::process::Child;
+use tokio::process::Command;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Captured output kept per run.
+const MAX_RUN_OUTPUT_CHARS: usize = 4_000;
+
+/// Outcome reported by a [`ConversationRunner`].
+pub(crate) struct RunOutcome {
+    pub success: bool,
+    pub detail: String,
+}
+
+/// Outcome of starting a brand-new conversation: the run plus the id the
+/// conversation was recorded under, when the runner could observe it.
+pub(crate) struct NewConversationOutcome {
+    pub conversation_id: Option<String>,
+    pub outcome: RunOutcome,
+}
+
+/// Executes one non-interactive conversation. Abstracted so tests can fake
+/// runs.
+#[async_trait]
+pub(crate) trait ConversationRunner: Send + Sync {
+    async fn run(
+        &self,
+        prompt: &str,
+        cwd: Option<&Path>,
+        config_overrides: &[String],
+    ) -> RunOutcome;
+
+    /// Like [`ConversationRunner::run`], but attaches `images` to the
+    /// prompt and forwards assistant text to `text` as the conversation
+    /// produces it. The default buffers the whole run through
+    /// [`ConversationRunner::run`] — which takes no attachments, so images
+    /// are ignored — and sends the output as one chunk; runners that can
+    /// observe the conversation while it runs override it.
+    async fn run_streaming(
+        &self,
+        prompt: &str,
+        cwd: Option<&Path>,
+        config_overrides: &[String],
+        _images: &[PathBuf],
+        text: mpsc::Sender<String>,
+    ) -> RunOutcome {
+        let outcome = self.run(prompt, cwd, config_overrides).await;
+        if !outcome.detail.is_empty() {
+            let _ = text.send(outcome.detail.clone()).await;
+        }
+        outcome
+    }
+
+    /// Starts a brand-new conversation and reports the id it was recorded
+    /// under, so the caller can address follow-up turns at it. The default
+    /// runs buffered and reports no id; runners that can observe the
+    /// conversation's event stream override it.
+    async fn run_new_conversation(
+        &self,
+        prompt: &str,
+        cwd: Option<&Path>,
+        config_overrides: &[String],
+    ) -> NewConversationOutcome {
+        NewConversationOutcome {
+            conversation_id: None,
+            outcome: self.run(prompt, cwd, config_overrides).await,
+        }
+    }
+}
+
+/// Default runner: spawns `codex exec` so the conversation gets the full CLI
+/// stack (config, auth, sandboxing) without this server linking it.
+pub(crate) struct CodexExecRunner {
+    pub codex_bin: PathBuf,
+}
+
+impl CodexExecRunner {
+    /// `codex exec` with the shared flags; the caller appends `--json` when
+    /// it wants the event stream, then the prompt.
+    fn exec_command(&self, cwd: Option<&Path>, config_overrides: &[String]) -> Command {
+        let mut command = Command::new(&self.codex_bin);
+        command.arg("exec");
+        if let Some(cwd) = cwd {
+            command.arg("--cd").arg(cwd);
+        }
+        for config_override in config_overrides {
+            command.arg("-c").arg(config_override);
+        }
+        // `codex exec` parents its root span on these, continuing the
+        // caller's trace into the conversation (see `crate::trace`).
+        for (name, value) in crate::trace::child_trace_env(&tracing::Span::current()) {
+            command.env(name, value);
+        }
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            // A run cancelled by the job queue's turn timeout — or by a
+            // streaming client disconnecting — must take the spawned
+            // conversation down with it.
+            .kill_on_drop(true);
+        command
+    }
+}
+
+/// Drains the child's stderr on its own task, concurrently with the stdout
+/// reader. Reading the pipes sequentially would deadlock a child that fills
+/// the stderr pipe buffer while the server is still waiting on stdout.
+fn drain_stderr(child: &mut Child) -> JoinHandle<String> {
+    let stderr = child.stderr.take();
+    tokio::spawn(async move {
+        let mut buffer = String::new();
+        if let Some(mut stderr) = stderr {
+            let _ = stderr.read_to_string(&mut buffer).await;
+        }
+        buffer
+    })
+}
+
+#[async_trait]
+impl ConversationRunner for CodexExecRunner {
+    async fn run(
+        &self,
+        prompt: &str,
+        cwd: Option<&Path>,
+        config_overrides: &[String],
+    ) -> RunOutcome {
+        let mut command = self.exec_command(cwd, config_overrides);
+        command.arg(prompt);
+        match command.output().await {
+            Ok(output) => {
+                let mut detail = String::from_utf8_lossy(&output.stdout).into_owned();
+                if !output.status.success() {
+                    detail.push_str(&String::from_utf8_lossy(&output.stderr));
+                }
+                RunOutcome {
+                    success: output.status.success(),
+                    detail: tail_chars(&detail),
+                }
+            }
+            Err(err) => RunOutcome {
+                success: false,
+                detail: format!("failed to launch {}: {err}", self.codex_bin.display()),
+            },
+        }
+    }
+
+    async fn run_streaming(
+        &self,
+        prompt: &str,
+        cwd: Option<&Path>,
+        config_overrides: &[String],
+        images: &[PathBuf],
+        text: mpsc::Sender<String>,
+    ) -> RunOutcome {
+        let mut command = self.exec_command(cwd, config_overrides);
+        for image in images {
+            command.arg("--image").arg(image);
+        }
+        command.arg("--json").arg(prompt);
+        let mut child = match command.spawn() {
+            Ok(child) => child,
+            Err(err) => {
+                return RunOutcome {
+                    success: false,
+                    detail: format!("failed to launch {}: {err}", self.codex_bin.display()),
+                };
+            }
+        };
+        let stderr_task = drain_stderr(&mut child);
+        let mut detail = String::new();
+        if let Some(stdout) = child.stdout.take() {
+            let mut lines = BufReader::new(stdout).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if let Some(message) = agent_message_text(&line) {
+                    detail.push_str(&message);
+                    let _ = text.send(message).await;
+                }
+            }
+        }
+        let status = child.wait().await;
+        let stderr = stderr_task.await.unwrap_or_default();
+        match status {
+            Ok(status) => {
+                if !status.success() {
+                    let _ = text.send(stderr.clone()).await;
+                    detail.push_str(&stderr);
+                }
+                RunOutcome {
+                    success: status.success(),
+                    detail: tail_chars(&detail),
+                }
+            }
+            Err(err) => RunOutcome {
+                success: false,
+                detail: format!("failed to run {}: {err}", self.codex_bin.display()),
+            },
+        }
+    }
+
+    async fn run_new_conversation(
+        &self,
+        prompt: &str,
+        cwd: Option<&Path>,
+        config_overrides: &[String],
+    ) -> NewConversationOutcome {
+        let mut command = self.exec_command(cwd, config_overrides);
+        command.arg("--json").arg(prompt);
+        let mut child = match command.spawn() {
+            Ok(child) => child,
+            Err(err) => {
+                return NewConversationOutcome {
+                    conversation_id: None,
+                    outcome: RunOutcome {
+                        success: false,
+                        detail: format!("failed to launch {}: {err}", self.codex_bin.display()),
+                    },
+                };
+            }
+        };
+        let stderr_task = drain_stderr(&mut child);
+        let mut conversation_id = None;
+        let mut detail = String::new();
+        if let Some(stdout) = child.stdout.take() {
+            let mut lines = BufReader::new(stdout).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if conversation_id.is_none() {
+                    conversation_id = thread_started_id(&line);
+                }
+                if let Some(message) = agent_message_text(&line) {
+                    detail.push_str(&message);
+                }
+            }
+        }
+        let status = child.wait().await;
+        let stderr = stderr_task.await.unwrap_or_default();
+        let outcome = match status {
+            Ok(status) => {
+                if !status.success() {
+                    detail.push_str(&stderr);
+                }
+                RunOutcome {
+                    success: status.success(),
+                    detail: tail_chars(&detail),
+                }
+            }
+            Err(err) => RunOutcome {
+                success: false,
+                detail: format!("failed to run {}: {err}", self.codex_bin.display()),
+            },
+        };
+        NewConversationOutcome {
+            conversation_id,
+            outcome,
+        }
+    }
+}
+
+/// The assistant text carried by one `codex exec --json` line: the `text` of
+/// an `item.completed` event whose item is an `agent_message`. Every other
+/// event — and any unparseable line — carries none.
+fn agent_message_text(line: &str) -> Option<String> {
+    let event: serde_json::Value = serde_json::from_str(line).ok()?;
+    if event.get("type")?.as_str()? != "item.completed" {
+        return None;
+    }
+    let item = event.get("item")?;
+    if item.get("type")?.as_str()? != "agent_message" {
+        return None;
+    }
+    Some(item.get("text")?.as_str()?.to_string())
+}
+
+/// The conversation id carried by a `thread.started` line of `codex exec
+/// --json`, emitted once when the conversation is created.
+fn thread_started_id(line: &str) -> Option<String> {
+    let event: serde_json::Value = serde_json::from_str(line).ok()?;
+    if event.get("type")?.as_str()? != "thread.started" {
+        return None;
+    }
+    Some(event.get("thread_id")?.as_str()?.to_string())
+}
+
+fn tail_chars(text: &str) -> String {
+    let count = text.chars().count();
+    if count <= MAX_RUN_OUTPUT_CHARS {
+        return text.to_string();
+    }
+    text.chars().skip(count - MAX_RUN_OUTPUT_CHARS).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn agent_messages_are_extracted_from_jsonl() {
+        let message = r#"{"type":"item.completed","item":{"id":"item_0","type":"agent_message","text":"done, tests pass"}}"#;
+        assert_eq!(
+            agent_message_text(message),
+            Some("done, tests pass".to_string())
+        );
+
+        let reasoning = r#"{"type":"item.completed","item":{"id":"item_1","type":"reasoning","text":"checking the suite"}}"#;
+        assert_eq!(agent_message_text(reasoning), None);
+        assert_eq!(agent_message_text(r#"{"type":"turn.started"}"#), None);
+        assert_eq!(agent_message_text("not json"), None);
+    }
+
+    #[test]
+    fn thread_ids_are_extracted_from_jsonl() {
+        let started =
+            r#"{"type":"thread.started","thread_id":"0199a213-81ba-7142-ba53-6b2ebc1b3a5a"}"#;
+        assert_eq!(
+            thread_started_id(started),
+            Some("0199a213-81ba-7142-ba53-6b2ebc1b3a5a".to_string())
+        );
+        assert_eq!(thread_started_id(r#"{"type":"turn.started"}"#), None);
+        assert_eq!(thread_started_id("not json"), None);
+    }
+
+    #[tokio::test]
+    async fn chatty_stderr_does_not_deadlock_a_streaming_run() {
+        use std::os::unix::fs::PermissionsExt;
+
+        // Writes well past the pipe buffer to stderr before touching stdout;
+        // with sequential pipe reads this deadlocked the run.
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let script = dir.path().join("codex");
+        std::fs::write(
+            &script,
+            "#!/bin/sh\nhead -c 200000 /dev/zero | tr '\\0' 'e' >&2\n\
+             echo '{\"type\":\"item.completed\",\"item\":{\"type\":\"agent_message\",\"text\":\"hi\"}}'\n\
+             exit 1\n",
+        )
+        .expect("write stub codex");
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755))
+            .expect("mark stub executable");
+
+        let runner = CodexExecRunner { codex_bin: script };
+        let (tx, mut rx) = mpsc::channel(16);
+        let outcome = tokio::time::timeout(
+            std::time::Duration::from_secs(30),
+            runner.run_streaming("prompt", None, &[], &[], tx),
+        )
+        .await
+        .expect("run must not deadlock on stderr");
+        assert!(!outcome.success);
+        assert_eq!(rx.recv().await, Some("hi".to_string()));
+    }
+
+    #[tokio::test]
+    async fn streaming_default_forwards_the_final_output() {
+        struct Buffered;
+
+        #[async_trait]
+        impl ConversationRunner for Buffered {
+            async fn run(
+                &self,
+                _prompt: &str,
+                _cwd: Option<&Path>,
+                _config_overrides: &[String],
+            ) -> RunOutcome {
+                RunOutcome {
+                    success: true,
+                    detail: "done, tests pass".to_string(),
+                }
+            }
+        }
+
+        let (tx, mut rx) = mpsc::channel(1);
+        let outcome = Buffered.run_streaming("prompt", None, &[], &[], tx).await;
+        assert!(outcome.success);
+        assert_eq!(rx.recv().await, Some("done, tests pass".to_string()));
+    }
+}
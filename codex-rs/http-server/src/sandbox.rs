@@ -0,0 +1,291 @@
+//! Per-conversation sandbox overrides and the `/conversations/{id}/sandbox`
+//! routes.
+//!
+//! Operators can widen a conversation's sandbox mid-task — extra writable
+//! roots or network access — without restarting it. Grants are validated
+//! against the server-side maximums in `[http_server.sandbox_limits]` and
+//! persisted, so a resumed conversation picks them up as config overrides.
+
+use std::path::Component;
+use std::path::PathBuf;
+
+use axum::Json;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use codex_config::types::HttpSandboxLimitsToml;
+use codex_core::export::load_conversation_export;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::AppState;
+use crate::error::ApiError;
+use crate::events::ServerEvent;
+use crate::storage::audit;
+
+/// Sandbox permissions granted to one conversation on top of its defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct SandboxOverride {
+    /// Extra directories the conversation may write to.
+    #[serde(default)]
+    pub writable_roots: Vec<PathBuf>,
+    /// Whether the conversation may reach the network.
+    #[serde(default)]
+    pub network_access: bool,
+}
+
+impl SandboxOverride {
+    /// `-c key=value` overrides that apply this grant to a `codex exec`
+    /// resume of the conversation.
+    pub(crate) fn config_overrides(&self) -> Vec<String> {
+        let mut overrides = Vec::new();
+        if !self.writable_roots.is_empty() {
+            let roots: Vec<String> = self
+                .writable_roots
+                .iter()
+                .map(|root| format!("{:?}", root.display().to_string()))
+                .collect();
+            overrides.push(format!(
+                "sandbox_workspace_write.writable_roots=[{}]",
+                roots.join(",")
+            ));
+        }
+        if self.network_access {
+            overrides.push("sandbox_workspace_write.network_access=true".to_string());
+        }
+        overrides
+    }
+}
+
+/// Partial update: absent fields keep their current value.
+#[derive(Debug, Deserialize)]
+pub(crate) struct UpdateSandboxRequest {
+    writable_roots: Option<Vec<PathBuf>>,
+    network_access: Option<bool>,
+}
+
+/// Rejects grants that exceed the server-side maximum permissions.
+fn validate_against_limits(
+    requested: &SandboxOverride,
+    limits: &HttpSandboxLimitsToml,
+) -> Result<(), ApiError> {
+    for root in &requested.writable_roots {
+        if !root.is_absolute() {
+            return Err(ApiError::invalid_request(format!(
+                "writable root {} is not absolute",
+                root.display()
+            )));
+        }
+        // The allowlist check below compares literal components, so a root
+        // like `/srv/repos/../../etc` would pass it while addressing a
+        // directory outside the limits.
+        if root
+            .components()
+            .any(|component| matches!(component, Component::ParentDir | Component::CurDir))
+        {
+            return Err(ApiError::invalid_request(format!(
+                "writable root {} must not contain `.` or `..` components",
+                root.display()
+            )));
+        }
+        if !limits
+            .writable_roots
+            .iter()
+            .any(|max| root.starts_with(max))
+        {
+            return Err(ApiError::sandbox_denied(format!(
+                "writable root {} is outside the server's sandbox_limits",
+                root.display()
+            )));
+        }
+    }
+    if requested.network_access && !limits.network_access {
+        return Err(ApiError::sandbox_denied(
+            "network access is disabled by the server's sandbox_limits",
+        ));
+    }
+    Ok(())
+}
+
+/// 404 unless the conversation is on disk.
+async fn require_conversation(state: &AppState, id: &str) -> Result<(), Response> {
+    match load_conversation_export(&state.codex_home, id).await {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => {
+            Err(ApiError::not_found(format!("no conversation with id {id}")).into_response())
+        }
+        Err(err) => {
+            Err(ApiError::internal(format!("failed to load conversation: {err}")).into_response())
+        }
+    }
+}
+
+/// `GET /conversations/{id}/sandbox`
+pub(crate) async fn get_sandbox(State(state): State<AppState>, Path(id): Path<String>) -> Response {
+    if let Err(response) = require_conversation(&state, &id).await {
+        return response;
+    }
+    match state.storage.load_sandbox_override(&id).await {
+        Ok(current) => Json(current.unwrap_or_default()).into_response(),
+        Err(err) => {
+            ApiError::internal(format!("failed to load sandbox override: {err}")).into_response()
+        }
+    }
+}
+
+/// `PATCH /conversations/{id}/sandbox`
+pub(crate) async fn update_sandbox(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<UpdateSandboxRequest>,
+) -> Response {
+    if let Err(response) = require_conversation(&state, &id).await {
+        return response;
+    }
+    let mut current = match state.storage.load_sandbox_override(&id).await {
+        Ok(current) => current.unwrap_or_default(),
+        Err(err) => {
+            return ApiError::internal(format!("failed to load sandbox override: {err}"))
+                .into_response();
+        }
+    };
+    if let Some(writable_roots) = request.writable_roots {
+        current.writable_roots = writable_roots;
+    }
+    if let Some(network_access) = request.network_access {
+        current.network_access = network_access;
+    }
+    let limits = state.settings().sandbox_limits.clone();
+    if let Err(err) = validate_against_limits(&current, &limits) {
+        // Surface the denial on the event bus so the operator who can raise
+        // the limits hears about it without the requester relaying it.
+        state
+            .events
+            .publish(ServerEvent {
+                kind: "sandbox.denied".to_string(),
+                payload: serde_json::json!({
+                    "conversation_id": id,
+                    "requested": current,
+                }),
+            })
+            .await;
+        return err.into_response();
+    }
+    if let Err(err) = state.storage.save_sandbox_override(&id, &current).await {
+        return ApiError::internal(format!("failed to save sandbox override: {err}"))
+            .into_response();
+    }
+    audit(
+        &*state.storage,
+        "sandbox.update",
+        &format!("conversation {id}"),
+    )
+    .await;
+    Json(current).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+    use crate::test_support::test_state;
+    use axum::http::StatusCode;
+    use pretty_assertions::assert_eq;
+
+    fn limits(writable_roots: &[&str], network_access: bool) -> HttpSandboxLimitsToml {
+        HttpSandboxLimitsToml {
+            writable_roots: writable_roots.iter().map(PathBuf::from).collect(),
+            network_access,
+        }
+    }
+
+    #[test]
+    fn grants_within_limits_are_accepted() {
+        let requested = SandboxOverride {
+            writable_roots: vec![PathBuf::from("/srv/repos/api/target")],
+            network_access: true,
+        };
+        assert!(validate_against_limits(&requested, &limits(&["/srv/repos"], true)).is_ok());
+    }
+
+    #[test]
+    fn roots_outside_the_allowlist_are_forbidden() {
+        let requested = SandboxOverride {
+            writable_roots: vec![PathBuf::from("/etc")],
+            network_access: false,
+        };
+        let err = validate_against_limits(&requested, &limits(&["/srv/repos"], true))
+            .expect_err("should be rejected");
+        assert_eq!(err.code(), ErrorCode::SandboxDenied);
+    }
+
+    #[test]
+    fn network_access_requires_the_server_limit() {
+        let requested = SandboxOverride {
+            writable_roots: Vec::new(),
+            network_access: true,
+        };
+        let err = validate_against_limits(&requested, &limits(&["/srv/repos"], false))
+            .expect_err("should be rejected");
+        assert_eq!(err.code(), ErrorCode::SandboxDenied);
+    }
+
+    #[test]
+    fn roots_with_parent_components_are_a_bad_request() {
+        // `/srv/repos/../../etc` starts with the allowed `/srv/repos`
+        // component-wise but addresses `/etc`.
+        let requested = SandboxOverride {
+            writable_roots: vec![PathBuf::from("/srv/repos/../../etc")],
+            network_access: false,
+        };
+        let err = validate_against_limits(&requested, &limits(&["/srv/repos"], true))
+            .expect_err("should be rejected");
+        assert_eq!(err.code(), ErrorCode::InvalidRequest);
+
+        let requested = SandboxOverride {
+            writable_roots: vec![PathBuf::from("/srv/repos/./api")],
+            network_access: false,
+        };
+        let err = validate_against_limits(&requested, &limits(&["/srv/repos"], true))
+            .expect_err("should be rejected");
+        assert_eq!(err.code(), ErrorCode::InvalidRequest);
+    }
+
+    #[test]
+    fn relative_roots_are_a_bad_request() {
+        let requested = SandboxOverride {
+            writable_roots: vec![PathBuf::from("target")],
+            network_access: false,
+        };
+        let err = validate_against_limits(&requested, &limits(&["/srv/repos"], true))
+            .expect_err("should be rejected");
+        assert_eq!(err.code(), ErrorCode::InvalidRequest);
+    }
+
+    #[test]
+    fn grant_renders_as_config_overrides() {
+        let grant = SandboxOverride {
+            writable_roots: vec![PathBuf::from("/srv/repos/api")],
+            network_access: true,
+        };
+        assert_eq!(
+            grant.config_overrides(),
+            vec![
+                "sandbox_workspace_write.writable_roots=[\"/srv/repos/api\"]".to_string(),
+                "sandbox_workspace_write.network_access=true".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown_conversation_is_not_found() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let response = get_sandbox(
+            State(test_state(codex_home.path()).await),
+            Path("0199a213-81ba-7142-ba53-6b2ebc1b3a5a".to_string()),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
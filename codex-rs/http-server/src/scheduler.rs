@@ -0,0 +1,452 @@
+//! Scheduled conversations: cron-triggered `codex exec` runs.
+//!
+//! Schedules come from `[[http_server.schedules]]` in config.toml or from
+//! `POST /schedules`. A background loop wakes once a minute, starts every
+//! schedule that is due, and keeps a bounded per-schedule run history that
+//! `GET /schedules/{id}/runs` exposes.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::DateTime;
+use chrono::Utc;
+use codex_config::types::HttpScheduleToml;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::info;
+use tracing::warn;
+
+use crate::cron::CronExpr;
+use crate::runner::ConversationRunner;
+use crate::runner::RunOutcome;
+use crate::storage::Storage;
+
+/// Runs kept per schedule; older outcomes fall off the back.
+const MAX_RUNS_PER_SCHEDULE: usize = 50;
+
+/// How often the scheduler loop checks for due schedules.
+const TICK_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Schedule {
+    pub id: u64,
+    pub name: String,
+    pub cron: String,
+    pub prompt: String,
+    pub cwd: Option<PathBuf>,
+    pub created_at: DateTime<Utc>,
+    pub next_run_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+/// One recorded execution of a schedule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleRun {
+    pub id: u64,
+    pub schedule_id: u64,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub status: RunStatus,
+    /// Tail of the conversation output, or the launch error.
+    pub detail: String,
+}
+
+struct ScheduleEntry {
+    schedule: Schedule,
+    cron: CronExpr,
+    runs: Vec<ScheduleRun>,
+}
+
+#[derive(Default)]
+struct SchedulerState {
+    next_schedule_id: u64,
+    next_run_id: u64,
+    entries: HashMap<u64, ScheduleEntry>,
+}
+
+/// Shared schedule registry plus the runner used to execute due schedules.
+#[derive(Clone)]
+pub(crate) struct Scheduler {
+    state: Arc<Mutex<SchedulerState>>,
+    runner: Arc<dyn ConversationRunner>,
+    storage: Arc<dyn Storage>,
+}
+
+impl Scheduler {
+    pub(crate) fn new(runner: Arc<dyn ConversationRunner>, storage: Arc<dyn Storage>) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(SchedulerState::default())),
+            runner,
+            storage,
+        }
+    }
+
+    /// Restores API-created schedules from storage, recomputing each next
+    /// run and skipping (and logging) rows whose cron no longer parses.
+    pub(crate) async fn load_persisted(&self) {
+        let schedules = match self.storage.load_schedules().await {
+            Ok(schedules) => schedules,
+            Err(err) => {
+                warn!("failed to load persisted schedules: {err}");
+                return;
+            }
+        };
+        let mut state = self.lock_state();
+        for mut schedule in schedules {
+            let cron = match CronExpr::parse(&schedule.cron) {
+                Ok(cron) => cron,
+                Err(err) => {
+                    warn!("skipping persisted schedule `{}`: {err}", schedule.name);
+                    continue;
+                }
+            };
+            schedule.next_run_at = cron.next_after(Utc::now());
+            state.next_schedule_id = state.next_schedule_id.max(schedule.id);
+            state.entries.insert(
+                schedule.id,
+                ScheduleEntry {
+                    schedule,
+                    cron,
+                    runs: Vec::new(),
+                },
+            );
+        }
+    }
+
+    /// Registers the `[[http_server.schedules]]` entries from config.toml,
+    /// skipping entries whose name is already registered (e.g. restored from
+    /// storage) and entries whose cron expression does not parse.
+    pub(crate) async fn seed_from_config(&self, schedules: &[HttpScheduleToml]) {
+        for schedule in schedules {
+            let name = schedule
+                .name
+                .clone()
+                .unwrap_or_else(|| schedule.cron.clone());
+            if self.list().iter().any(|existing| existing.name == name) {
+                continue;
+            }
+            match self
+                .add(
+                    schedule.name.clone(),
+                    &schedule.cron,
+                    schedule.prompt.clone(),
+                    schedule.cwd.clone(),
+                )
+                .await
+            {
+                Ok(added) => info!("registered configured schedule `{}`", added.name),
+                Err(err) => warn!("skipping configured schedule: {err}"),
+            }
+        }
+    }
+
+    pub(crate) async fn add(
+        &self,
+        name: Option<String>,
+        cron: &str,
+        prompt: String,
+        cwd: Option<PathBuf>,
+    ) -> Result<Schedule, String> {
+        let cron_expr = CronExpr::parse(cron)?;
+        let schedule = {
+            let mut state = self.lock_state();
+            state.next_schedule_id += 1;
+            let id = state.next_schedule_id;
+            let schedule = Schedule {
+                id,
+                name: name.unwrap_or_else(|| cron.to_string()),
+                cron: cron.to_string(),
+                prompt,
+                cwd,
+                created_at: Utc::now(),
+                next_run_at: cron_expr.next_after(Utc::now()),
+            };
+            state.entries.insert(
+                id,
+                ScheduleEntry {
+                    schedule: schedule.clone(),
+                    cron: cron_expr,
+                    runs: Vec::new(),
+                },
+            );
+            schedule
+        };
+        if let Err(err) = self.storage.save_schedule(&schedule).await {
+            warn!("failed to persist schedule `{}`: {err}", schedule.name);
+        }
+        Ok(schedule)
+    }
+
+    pub(crate) async fn remove(&self, id: u64) -> bool {
+        if self.lock_state().entries.remove(&id).is_none() {
+            return false;
+        }
+        if let Err(err) = self.storage.delete_schedule(id).await {
+            warn!("failed to delete persisted schedule {id}: {err}");
+        }
+        true
+    }
+
+    pub(crate) fn list(&self) -> Vec<Schedule> {
+        let mut schedules: Vec<Schedule> = self
+            .lock_state()
+            .entries
+            .values()
+            .map(|entry| entry.schedule.clone())
+            .collect();
+        schedules.sort_by_key(|schedule| schedule.id);
+        schedules
+    }
+
+    /// Run history for a schedule, newest first; `None` for unknown ids.
+    pub(crate) fn runs(&self, id: u64) -> Option<Vec<ScheduleRun>> {
+        let state = self.lock_state();
+        let entry = state.entries.get(&id)?;
+        let mut runs = entry.runs.clone();
+        runs.reverse();
+        Some(runs)
+    }
+
+    /// Wakes once a minute and executes every schedule that has come due.
+    pub(crate) async fn run_loop(self) {
+        let mut interval = tokio::time::interval(TICK_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            self.run_due_schedules(Utc::now()).await;
+        }
+    }
+
+    /// Executes every schedule whose next run is at or before `now`.
+    pub(crate) async fn run_due_schedules(&self, now: DateTime<Utc>) {
+        let due: Vec<(u64, String, Option<PathBuf>)> = {
+            let mut state = self.lock_state();
+            let mut due = Vec::new();
+            for entry in state.entries.values_mut() {
+                let Some(next_run_at) = entry.schedule.next_run_at else {
+                    continue;
+                };
+                if next_run_at > now {
+                    continue;
+                }
+                entry.schedule.next_run_at = entry.cron.next_after(now);
+                due.push((
+                    entry.schedule.id,
+                    render_prompt(&entry.schedule.prompt, entry.schedule.cwd.as_deref(), now),
+                    entry.schedule.cwd.clone(),
+                ));
+            }
+            due
+        };
+        for (schedule_id, prompt, cwd) in due {
+            self.execute(schedule_id, prompt, cwd).await;
+        }
+    }
+
+    async fn execute(&self, schedule_id: u64, prompt: String, cwd: Option<PathBuf>) {
+        let run_id = {
+            let mut state = self.lock_state();
+            state.next_run_id += 1;
+            let run_id = state.next_run_id;
+            let Some(entry) = state.entries.get_mut(&schedule_id) else {
+                return;
+            };
+            info!(schedule = %entry.schedule.name, "starting scheduled conversation");
+            entry.runs.push(ScheduleRun {
+                id: run_id,
+                schedule_id,
+                started_at: Utc::now(),
+                finished_at: None,
+                status: RunStatus::Running,
+                detail: String::new(),
+            });
+            if entry.runs.len() > MAX_RUNS_PER_SCHEDULE {
+                entry.runs.remove(0);
+            }
+            run_id
+        };
+        let outcome = self.runner.run(&prompt, cwd.as_deref(), &[]).await;
+        let mut state = self.lock_state();
+        let Some(entry) = state.entries.get_mut(&schedule_id) else {
+            return;
+        };
+        if let Some(run) = entry.runs.iter_mut().find(|run| run.id == run_id) {
+            run.finished_at = Some(Utc::now());
+            run.status = if outcome.success {
+                RunStatus::Succeeded
+            } else {
+                RunStatus::Failed
+            };
+            run.detail = outcome.detail;
+        }
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, SchedulerState> {
+        match self.state.lock() {
+            Ok(state) => state,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+/// Substitutes `{date}`, `{time}`, and `{cwd}` placeholders in a prompt.
+fn render_prompt(prompt: &str, cwd: Option<&Path>, now: DateTime<Utc>) -> String {
+    prompt
+        .replace("{date}", &now.format("%Y-%m-%d").to_string())
+        .replace("{time}", &now.format("%H:%M").to_string())
+        .replace(
+            "{cwd}",
+            &cwd.map(|cwd| cwd.display().to_string()).unwrap_or_default(),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use pretty_assertions::assert_eq;
+
+    struct RecordingRunner {
+        prompts: Mutex<Vec<String>>,
+        success: bool,
+    }
+
+    #[async_trait]
+    impl ConversationRunner for RecordingRunner {
+        async fn run(
+            &self,
+            prompt: &str,
+            _cwd: Option<&Path>,
+            _config_overrides: &[String],
+        ) -> RunOutcome {
+            self.prompts
+                .lock()
+                .expect("lock prompts")
+                .push(prompt.to_string());
+            RunOutcome {
+                success: self.success,
+                detail: "done".to_string(),
+            }
+        }
+    }
+
+    async fn scheduler(success: bool) -> (tempfile::TempDir, Scheduler, Arc<RecordingRunner>) {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let storage = Arc::new(
+            crate::storage::SqliteStorage::open(codex_home.path())
+                .await
+                .expect("open storage"),
+        );
+        let runner = Arc::new(RecordingRunner {
+            prompts: Mutex::new(Vec::new()),
+            success,
+        });
+        let scheduler = Scheduler::new(runner.clone(), storage);
+        (codex_home, scheduler, runner)
+    }
+
+    #[tokio::test]
+    async fn due_schedule_runs_and_records_outcome() {
+        let (_codex_home, scheduler, runner) = scheduler(true).await;
+        let schedule = scheduler
+            .add(
+                Some("nightly".to_string()),
+                "0 3 * * *",
+                "fix flaky tests as of {date}".to_string(),
+                None,
+            )
+            .await
+            .expect("add schedule");
+        let due_at = Utc
+            .with_ymd_and_hms(2026, 8, 28, 3, 0, 0)
+            .single()
+            .expect("valid timestamp");
+        scheduler.run_due_schedules(due_at).await;
+
+        let prompts = runner.prompts.lock().expect("lock prompts");
+        assert_eq!(prompts.as_slice(), ["fix flaky tests as of 2026-08-28"]);
+        let runs = scheduler.runs(schedule.id).expect("schedule exists");
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].status, RunStatus::Succeeded);
+        assert_eq!(runs[0].detail, "done");
+    }
+
+    #[tokio::test]
+    async fn schedule_not_due_does_not_run() {
+        let (_codex_home, scheduler, runner) = scheduler(true).await;
+        scheduler
+            .add(None, "0 3 * * *", "prompt".to_string(), None)
+            .await
+            .expect("add schedule");
+        let before_due = Utc
+            .with_ymd_and_hms(2026, 8, 27, 23, 0, 0)
+            .single()
+            .expect("valid timestamp");
+        scheduler.run_due_schedules(before_due).await;
+        assert!(runner.prompts.lock().expect("lock prompts").is_empty());
+    }
+
+    #[tokio::test]
+    async fn failed_run_is_recorded_as_failed() {
+        let (_codex_home, scheduler, _runner) = scheduler(false).await;
+        let schedule = scheduler
+            .add(None, "* * * * *", "prompt".to_string(), None)
+            .await
+            .expect("add schedule");
+        scheduler
+            .run_due_schedules(Utc::now() + chrono::Duration::minutes(2))
+            .await;
+        let runs = scheduler.runs(schedule.id).expect("schedule exists");
+        assert_eq!(runs[0].status, RunStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn add_rejects_invalid_cron() {
+        let (_codex_home, scheduler, _runner) = scheduler(true).await;
+        assert!(
+            scheduler
+                .add(None, "not cron", "p".to_string(), None)
+                .await
+                .is_err()
+        );
+        assert!(scheduler.list().is_empty());
+    }
+
+    #[tokio::test]
+    async fn schedules_survive_restart() {
+        let (codex_home, scheduler, runner) = scheduler(true).await;
+        let added = scheduler
+            .add(
+                Some("nightly".to_string()),
+                "0 3 * * *",
+                "p".to_string(),
+                None,
+            )
+            .await
+            .expect("add schedule");
+
+        let storage = Arc::new(
+            crate::storage::SqliteStorage::open(codex_home.path())
+                .await
+                .expect("open storage"),
+        );
+        let restored = Scheduler::new(runner, storage);
+        restored.load_persisted().await;
+        let schedules = restored.list();
+        assert_eq!(schedules.len(), 1);
+        assert_eq!(schedules[0].id, added.id);
+        assert_eq!(schedules[0].name, "nightly");
+    }
+}
@@ -0,0 +1,142 @@
+//! Handlers for the `/schedules` routes.
+
+use std::path::PathBuf;
+
+use axum::Json;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::AppState;
+use crate::error::ApiError;
+use crate::scheduler::Schedule;
+use crate::storage::audit;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateScheduleRequest {
+    pub name: Option<String>,
+    /// Five-field cron expression (minute hour day-of-month month
+    /// day-of-week); as in standard cron, restricting both day fields
+    /// fires on either.
+    pub cron: String,
+    pub prompt: String,
+    pub cwd: Option<PathBuf>,
+}
+
+/// `POST /schedules`
+pub(crate) async fn create_schedule(
+    State(state): State<AppState>,
+    Json(request): Json<CreateScheduleRequest>,
+) -> Response {
+    match state
+        .scheduler
+        .add(request.name, &request.cron, request.prompt, request.cwd)
+        .await
+    {
+        Ok(schedule) => {
+            audit(
+                &*state.storage,
+                "schedule.create",
+                &format!("schedule {} ({})", schedule.id, schedule.name),
+            )
+            .await;
+            (StatusCode::CREATED, Json(schedule)).into_response()
+        }
+        Err(message) => ApiError::invalid_request(message).into_response(),
+    }
+}
+
+/// `GET /schedules`
+pub(crate) async fn list_schedules(State(state): State<AppState>) -> Json<Vec<Schedule>> {
+    Json(state.scheduler.list())
+}
+
+/// `DELETE /schedules/{id}`
+pub(crate) async fn delete_schedule(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+) -> Response {
+    if state.scheduler.remove(id).await {
+        audit(
+            &*state.storage,
+            "schedule.delete",
+            &format!("schedule {id}"),
+        )
+        .await;
+        StatusCode::NO_CONTENT.into_response()
+    } else {
+        ApiError::not_found(format!("no schedule with id {id}")).into_response()
+    }
+}
+
+/// `GET /schedules/{id}/runs`
+pub(crate) async fn schedule_runs(State(state): State<AppState>, Path(id): Path<u64>) -> Response {
+    match state.scheduler.runs(id) {
+        Some(runs) => Json(runs).into_response(),
+        None => ApiError::not_found(format!("no schedule with id {id}")).into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_state;
+    use pretty_assertions::assert_eq;
+
+    async fn state() -> (tempfile::TempDir, AppState) {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let state = test_state(codex_home.path()).await;
+        (codex_home, state)
+    }
+
+    #[tokio::test]
+    async fn create_then_list_and_delete() {
+        let (_codex_home, state) = state().await;
+        let response = create_schedule(
+            State(state.clone()),
+            Json(CreateScheduleRequest {
+                name: Some("nightly".to_string()),
+                cron: "0 3 * * *".to_string(),
+                prompt: "fix flaky tests".to_string(),
+                cwd: None,
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let schedules = list_schedules(State(state.clone())).await.0;
+        assert_eq!(schedules.len(), 1);
+        assert_eq!(schedules[0].name, "nightly");
+
+        let response = delete_schedule(State(state.clone()), Path(schedules[0].id)).await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert!(list_schedules(State(state)).await.0.is_empty());
+    }
+
+    #[tokio::test]
+    async fn invalid_cron_is_rejected() {
+        let (_codex_home, state) = state().await;
+        let response = create_schedule(
+            State(state),
+            Json(CreateScheduleRequest {
+                name: None,
+                cron: "whenever".to_string(),
+                prompt: "p".to_string(),
+                cwd: None,
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn runs_for_unknown_schedule_is_not_found() {
+        let (_codex_home, state) = state().await;
+        let response = schedule_runs(State(state), Path(42)).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
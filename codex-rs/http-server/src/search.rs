@@ -0,0 +1,82 @@
+//! Handler for the `/search` route.
+
+use axum::Json;
+use axum::extract::Query;
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use codex_core::search::search_conversations;
+use serde::Deserialize;
+
+use crate::AppState;
+use crate::error::ApiError;
+
+/// Results beyond this are dropped regardless of the requested limit.
+const MAX_RESULTS: usize = 100;
+const DEFAULT_RESULTS: usize = 20;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct SearchQuery {
+    q: String,
+    #[serde(default)]
+    archived: bool,
+    limit: Option<usize>,
+}
+
+/// `GET /search?q=...&archived=false&limit=20`
+///
+/// Searches rollout transcripts under `CODEX_HOME` and returns matching
+/// conversations with a snippet around the first hit, newest first.
+pub(crate) async fn search(
+    State(state): State<AppState>,
+    Query(query): Query<SearchQuery>,
+) -> Response {
+    if query.q.trim().is_empty() {
+        return ApiError::invalid_request("q must not be empty").into_response();
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_RESULTS).clamp(1, MAX_RESULTS);
+    match search_conversations(&state.codex_home, &query.q, query.archived, limit).await {
+        Ok(results) => Json(results).into_response(),
+        Err(err) => {
+            ApiError::internal(format!("failed to search conversations: {err}")).into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_state;
+    use axum::http::StatusCode;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn empty_query_is_rejected() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let response = search(
+            State(test_state(codex_home.path()).await),
+            Query(SearchQuery {
+                q: "  ".to_string(),
+                archived: false,
+                limit: None,
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn empty_home_returns_no_results() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let response = search(
+            State(test_state(codex_home.path()).await),
+            Query(SearchQuery {
+                q: "flaky auth test".to_string(),
+                archived: false,
+                limit: None,
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
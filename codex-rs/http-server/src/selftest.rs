@@ -0,0 +1,212 @@
+//! `codex-http-server selftest`: an end-to-end smoke test of the running
+//! binary.
+//!
+//! Boots the real server on an ephemeral port under a throwaway
+//! `CODEX_HOME`, with a stub `codex` standing in for the model, and drives
+//! one scripted conversation through the public API: queue a job, send a
+//! completion, watch the event stream, interrupt a running turn, and shut
+//! down. Each step prints `ok` or fails the run, so packaging checks and
+//! deployments can catch integration regressions without a model or a
+//! network.
+
+use std::os::unix::fs::PermissionsExt;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::bail;
+use codex_config::types::HttpSandboxLimitsToml;
+use futures::StreamExt;
+
+use crate::ServerConfig;
+
+/// Stub standing in for the model: prompts mentioning `slow` hang long
+/// enough to be interrupted, everything else completes immediately.
+const STUB_SCRIPT: &str = "#!/bin/sh
+for arg in \"$@\"; do last=\"$arg\"; done
+case \"$last\" in
+  *slow*) sleep 30 ;;
+esac
+echo 'selftest-ok'
+";
+
+/// How long any one step may take before the run fails.
+const STEP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Runs the scripted conversation and reports pass/fail through the exit
+/// status.
+pub async fn run() -> anyhow::Result<()> {
+    let codex_home =
+        std::env::temp_dir().join(format!("codex-http-selftest-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&codex_home).context("create temporary CODEX_HOME")?;
+    let codex_bin = codex_home.join("codex");
+    std::fs::write(&codex_bin, STUB_SCRIPT).context("write stub codex")?;
+    std::fs::set_permissions(&codex_bin, std::fs::Permissions::from_mode(0o755))
+        .context("mark stub codex executable")?;
+    let conversation_id = seed_conversation(&codex_home).context("seed conversation")?;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .context("bind ephemeral port")?;
+    let base = format!("http://{}", listener.local_addr()?);
+    let config = ServerConfig {
+        codex_home: codex_home.clone(),
+        schedules: Vec::new(),
+        job_workers: 1,
+        codex_bin,
+        github_token: None,
+        templates: Vec::new(),
+        event_bus: None,
+        sandbox_limits: HttpSandboxLimitsToml::default(),
+        model_providers: std::collections::HashMap::new(),
+        max_turn_seconds: None,
+        max_concurrent_turns: None,
+        archive: None,
+        notify: None,
+        limits: None,
+        images: None,
+        chatops: None,
+        quota: None,
+        redaction: None,
+        instructions: None,
+    };
+    let server = tokio::spawn(async move { crate::serve(listener, config).await });
+
+    let result = tokio::time::timeout(STEP_TIMEOUT * 5, script(&base, &conversation_id)).await;
+    // Shutdown: the server must still be serving when the script ends,
+    // and must stop when told to.
+    if server.is_finished() {
+        bail!("server exited before the script finished");
+    }
+    server.abort();
+    println!("ok shutdown");
+    let _ = std::fs::remove_dir_all(&codex_home);
+    match result {
+        Ok(result) => result,
+        Err(_) => bail!("selftest timed out"),
+    }?;
+    println!("selftest passed");
+    Ok(())
+}
+
+/// The scripted conversation, one step per public surface.
+async fn script(base: &str, conversation_id: &str) -> anyhow::Result<()> {
+    let http = reqwest::Client::new();
+
+    // Readiness: the server answers before anything else is tried.
+    step(STEP_TIMEOUT, async {
+        let response = http.get(format!("{base}/health/ready")).send().await?;
+        check(response.status().is_success(), "readiness probe failed")
+    })
+    .await
+    .context("ready")?;
+    println!("ok ready");
+
+    // Create: queue a job and watch the bus report it done.
+    step(STEP_TIMEOUT, async {
+        let events = http.get(format!("{base}/events")).send().await?;
+        let job: serde_json::Value = http
+            .post(format!("{base}/jobs"))
+            .json(&serde_json::json!({"prompt": "selftest job"}))
+            .send()
+            .await?
+            .json()
+            .await?;
+        check(job.get("id").is_some(), "job creation returned no id")?;
+        println!("ok create");
+
+        // Stream: `job.done` for it arrives over SSE.
+        let mut body = events.bytes_stream();
+        let mut seen = String::new();
+        while let Some(chunk) = body.next().await {
+            seen.push_str(&String::from_utf8_lossy(&chunk?));
+            if seen.contains("event: job.done") {
+                return Ok(());
+            }
+        }
+        bail!("event stream ended without job.done")
+    })
+    .await
+    .context("create/stream")?;
+    println!("ok stream");
+
+    // Send: a completion turn streams the stub's answer back.
+    step(STEP_TIMEOUT, async {
+        let body = http
+            .post(format!("{base}/conversations/{conversation_id}/complete"))
+            .json(&serde_json::json!({"prompt": "say hello"}))
+            .send()
+            .await?
+            .text()
+            .await?;
+        check(
+            body.contains("selftest-ok"),
+            "completion body is missing the stub's output",
+        )
+    })
+    .await
+    .context("send")?;
+    println!("ok send");
+
+    // Interrupt: a hanging turn is taken down on request.
+    step(STEP_TIMEOUT, async {
+        let slow = http
+            .post(format!("{base}/conversations/{conversation_id}/complete"))
+            .json(&serde_json::json!({"prompt": "slow"}))
+            .send()
+            .await?;
+        check(slow.status().is_success(), "slow turn did not start")?;
+        let interrupt = http
+            .post(format!("{base}/conversations/{conversation_id}/interrupt"))
+            .send()
+            .await?;
+        check(
+            interrupt.status().is_success(),
+            "interrupt did not find the running turn",
+        )
+    })
+    .await
+    .context("interrupt")?;
+    println!("ok interrupt");
+    Ok(())
+}
+
+/// One scripted step under its own deadline.
+async fn step<F>(deadline: Duration, step: F) -> anyhow::Result<()>
+where
+    F: Future<Output = anyhow::Result<()>>,
+{
+    match tokio::time::timeout(deadline, step).await {
+        Ok(result) => result,
+        Err(_) => bail!("step timed out"),
+    }
+}
+
+fn check(ok: bool, message: &str) -> anyhow::Result<()> {
+    if ok { Ok(()) } else { bail!("{message}") }
+}
+
+/// Writes a one-line rollout so the conversation routes have something to
+/// load, the same way a deployment has recorded sessions.
+fn seed_conversation(codex_home: &std::path::Path) -> anyhow::Result<String> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let now = chrono::Utc::now();
+    let dir = codex_home
+        .join("sessions")
+        .join(now.format("%Y/%m/%d").to_string());
+    std::fs::create_dir_all(&dir)?;
+    let file = dir.join(format!(
+        "rollout-{}-{id}.jsonl",
+        now.format("%Y-%m-%dT%H-%M-%S")
+    ));
+    let line = serde_json::json!({
+        "timestamp": now.to_rfc3339(),
+        "type": "response_item",
+        "payload": {
+            "type": "message",
+            "role": "user",
+            "content": [{"type": "input_text", "text": "selftest seed"}],
+        },
+    });
+    std::fs::write(&file, format!("{line}\n"))?;
+    Ok(id)
+}
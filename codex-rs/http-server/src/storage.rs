@@ -0,0 +1,599 @@
+//! SQLite-backed persistence for the HTTP server's mutable state.
+//!
+//! Jobs, schedules, templates, and the audit log all go through [`Storage`]
+//! instead of process-local maps, so a restart loses nothing and a future
+//! deployment can point several replicas at one shared database. The trait
+//! keeps the rest of the server independent of the backing store.
+
+use std::path::Path;
+
+use async_trait::async_trait;
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+use sqlx::Row;
+use sqlx::SqlitePool;
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::sqlite::SqliteJournalMode;
+use sqlx::sqlite::SqlitePoolOptions;
+use tracing::warn;
+
+use crate::archive::ConversationArchive;
+use crate::job_queue::Job;
+use crate::offload::Offload;
+use crate::recordings::Recording;
+use crate::retry::TurnAttempt;
+use crate::sandbox::SandboxOverride;
+use crate::scheduler::Schedule;
+use crate::templates::PromptTemplate;
+
+/// Database file under `CODEX_HOME`.
+pub(crate) const STORAGE_DB_FILE_NAME: &str = "http-server.sqlite";
+
+pub(crate) type StorageResult<T> = anyhow::Result<T>;
+
+/// One row of the audit log: a mutating API action and when it happened.
+/// Shared with `codex-http-server-client`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub id: u64,
+    pub at: DateTime<Utc>,
+    pub action: String,
+    pub detail: String,
+}
+
+/// Persistence used by the job queue, scheduler, and template store.
+#[async_trait]
+pub(crate) trait Storage: Send + Sync {
+    async fn load_jobs(&self) -> StorageResult<Vec<Job>>;
+    async fn save_job(&self, job: &Job) -> StorageResult<()>;
+
+    async fn load_schedules(&self) -> StorageResult<Vec<Schedule>>;
+    async fn save_schedule(&self, schedule: &Schedule) -> StorageResult<()>;
+    async fn delete_schedule(&self, id: u64) -> StorageResult<()>;
+
+    async fn load_templates(&self) -> StorageResult<Vec<PromptTemplate>>;
+    async fn save_template(&self, template: &PromptTemplate) -> StorageResult<()>;
+    async fn delete_template(&self, name: &str) -> StorageResult<()>;
+
+    async fn load_sandbox_override(
+        &self,
+        conversation_id: &str,
+    ) -> StorageResult<Option<SandboxOverride>>;
+    async fn save_sandbox_override(
+        &self,
+        conversation_id: &str,
+        sandbox: &SandboxOverride,
+    ) -> StorageResult<()>;
+
+    async fn load_recordings(&self) -> StorageResult<Vec<Recording>>;
+    async fn save_recording(&self, recording: &Recording) -> StorageResult<()>;
+
+    async fn load_offloads(&self) -> StorageResult<Vec<Offload>>;
+    async fn save_offload(&self, offload: &Offload) -> StorageResult<()>;
+
+    async fn load_archives(&self) -> StorageResult<Vec<ConversationArchive>>;
+    async fn save_archive(&self, archive: &ConversationArchive) -> StorageResult<()>;
+
+    async fn load_turn_attempt(&self, conversation_id: &str) -> StorageResult<Option<TurnAttempt>>;
+    async fn save_turn_attempt(&self, attempt: &TurnAttempt) -> StorageResult<()>;
+
+    async fn append_audit(&self, action: &str, detail: &str) -> StorageResult<()>;
+    async fn recent_audit(&self, limit: usize) -> StorageResult<Vec<AuditEntry>>;
+}
+
+/// Best-effort audit write; the API stays usable if the disk write fails.
+pub(crate) async fn audit(storage: &dyn Storage, action: &str, detail: &str) {
+    if let Err(err) = storage.append_audit(action, detail).await {
+        warn!("failed to record audit entry {action}: {err}");
+    }
+}
+
+const SCHEMA: &[&str] = &[
+    "CREATE TABLE IF NOT EXISTS jobs (id INTEGER PRIMARY KEY, data TEXT NOT NULL)",
+    "CREATE TABLE IF NOT EXISTS schedules (id INTEGER PRIMARY KEY, data TEXT NOT NULL)",
+    "CREATE TABLE IF NOT EXISTS templates (name TEXT PRIMARY KEY, prompt TEXT NOT NULL)",
+    "CREATE TABLE IF NOT EXISTS sandbox_overrides (conversation_id TEXT PRIMARY KEY, data TEXT NOT NULL)",
+    "CREATE TABLE IF NOT EXISTS recordings (id INTEGER PRIMARY KEY, data TEXT NOT NULL)",
+    "CREATE TABLE IF NOT EXISTS offloads (agent_id TEXT PRIMARY KEY, data TEXT NOT NULL)",
+    "CREATE TABLE IF NOT EXISTS archives (conversation_id TEXT PRIMARY KEY, data TEXT NOT NULL)",
+    "CREATE TABLE IF NOT EXISTS turn_attempts (conversation_id TEXT PRIMARY KEY, data TEXT NOT NULL)",
+    "CREATE TABLE IF NOT EXISTS audit_log (id INTEGER PRIMARY KEY AUTOINCREMENT, at TEXT NOT NULL, action TEXT NOT NULL, detail TEXT NOT NULL)",
+];
+
+/// [`Storage`] over a SQLite database under `CODEX_HOME`. Jobs and schedules
+/// are stored as JSON rows so their shapes can evolve without migrations.
+pub(crate) struct SqliteStorage {
+    pool: SqlitePool,
+}
+
+impl SqliteStorage {
+    pub(crate) async fn open(codex_home: &Path) -> StorageResult<Self> {
+        let options = SqliteConnectOptions::new()
+            .filename(codex_home.join(STORAGE_DB_FILE_NAME))
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(5)
+            .connect_with(options)
+            .await?;
+        for statement in SCHEMA {
+            sqlx::query(statement).execute(&pool).await?;
+        }
+        Ok(Self { pool })
+    }
+}
+
+#[async_trait]
+impl Storage for SqliteStorage {
+    async fn load_jobs(&self) -> StorageResult<Vec<Job>> {
+        let rows = sqlx::query("SELECT data FROM jobs ORDER BY id")
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows
+            .iter()
+            .filter_map(|row| {
+                let data: String = row.get("data");
+                match serde_json::from_str(&data) {
+                    Ok(job) => Some(job),
+                    Err(err) => {
+                        warn!("ignoring malformed job row: {err}");
+                        None
+                    }
+                }
+            })
+            .collect())
+    }
+
+    async fn save_job(&self, job: &Job) -> StorageResult<()> {
+        let data = serde_json::to_string(job)?;
+        sqlx::query(
+            "INSERT INTO jobs (id, data) VALUES (?1, ?2) \
+             ON CONFLICT(id) DO UPDATE SET data = excluded.data",
+        )
+        .bind(job.id as i64)
+        .bind(data)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn load_schedules(&self) -> StorageResult<Vec<Schedule>> {
+        let rows = sqlx::query("SELECT data FROM schedules ORDER BY id")
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows
+            .iter()
+            .filter_map(|row| {
+                let data: String = row.get("data");
+                match serde_json::from_str(&data) {
+                    Ok(schedule) => Some(schedule),
+                    Err(err) => {
+                        warn!("ignoring malformed schedule row: {err}");
+                        None
+                    }
+                }
+            })
+            .collect())
+    }
+
+    async fn save_schedule(&self, schedule: &Schedule) -> StorageResult<()> {
+        let data = serde_json::to_string(schedule)?;
+        sqlx::query(
+            "INSERT INTO schedules (id, data) VALUES (?1, ?2) \
+             ON CONFLICT(id) DO UPDATE SET data = excluded.data",
+        )
+        .bind(schedule.id as i64)
+        .bind(data)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn delete_schedule(&self, id: u64) -> StorageResult<()> {
+        sqlx::query("DELETE FROM schedules WHERE id = ?1")
+            .bind(id as i64)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn load_templates(&self) -> StorageResult<Vec<PromptTemplate>> {
+        let rows = sqlx::query("SELECT name, prompt FROM templates ORDER BY name")
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows
+            .iter()
+            .map(|row| PromptTemplate {
+                name: row.get("name"),
+                prompt: row.get("prompt"),
+            })
+            .collect())
+    }
+
+    async fn save_template(&self, template: &PromptTemplate) -> StorageResult<()> {
+        sqlx::query(
+            "INSERT INTO templates (name, prompt) VALUES (?1, ?2) \
+             ON CONFLICT(name) DO UPDATE SET prompt = excluded.prompt",
+        )
+        .bind(&template.name)
+        .bind(&template.prompt)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn delete_template(&self, name: &str) -> StorageResult<()> {
+        sqlx::query("DELETE FROM templates WHERE name = ?1")
+            .bind(name)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn load_sandbox_override(
+        &self,
+        conversation_id: &str,
+    ) -> StorageResult<Option<SandboxOverride>> {
+        let row = sqlx::query("SELECT data FROM sandbox_overrides WHERE conversation_id = ?1")
+            .bind(conversation_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        match row {
+            Some(row) => {
+                let data: String = row.get("data");
+                Ok(Some(serde_json::from_str(&data)?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn save_sandbox_override(
+        &self,
+        conversation_id: &str,
+        sandbox: &SandboxOverride,
+    ) -> StorageResult<()> {
+        let data = serde_json::to_string(sandbox)?;
+        sqlx::query(
+            "INSERT INTO sandbox_overrides (conversation_id, data) VALUES (?1, ?2) \
+             ON CONFLICT(conversation_id) DO UPDATE SET data = excluded.data",
+        )
+        .bind(conversation_id)
+        .bind(data)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn load_recordings(&self) -> StorageResult<Vec<Recording>> {
+        let rows = sqlx::query("SELECT data FROM recordings ORDER BY id")
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows
+            .iter()
+            .filter_map(|row| {
+                let data: String = row.get("data");
+                match serde_json::from_str(&data) {
+                    Ok(recording) => Some(recording),
+                    Err(err) => {
+                        warn!("ignoring malformed recording row: {err}");
+                        None
+                    }
+                }
+            })
+            .collect())
+    }
+
+    async fn save_recording(&self, recording: &Recording) -> StorageResult<()> {
+        let data = serde_json::to_string(recording)?;
+        sqlx::query(
+            "INSERT INTO recordings (id, data) VALUES (?1, ?2) \
+             ON CONFLICT(id) DO UPDATE SET data = excluded.data",
+        )
+        .bind(recording.id as i64)
+        .bind(data)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn load_offloads(&self) -> StorageResult<Vec<Offload>> {
+        let rows = sqlx::query("SELECT data FROM offloads ORDER BY agent_id")
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows
+            .iter()
+            .filter_map(|row| {
+                let data: String = row.get("data");
+                match serde_json::from_str(&data) {
+                    Ok(offload) => Some(offload),
+                    Err(err) => {
+                        warn!("ignoring malformed offload row: {err}");
+                        None
+                    }
+                }
+            })
+            .collect())
+    }
+
+    async fn save_offload(&self, offload: &Offload) -> StorageResult<()> {
+        let data = serde_json::to_string(offload)?;
+        sqlx::query(
+            "INSERT INTO offloads (agent_id, data) VALUES (?1, ?2) \
+             ON CONFLICT(agent_id) DO UPDATE SET data = excluded.data",
+        )
+        .bind(&offload.agent_id)
+        .bind(data)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn load_archives(&self) -> StorageResult<Vec<ConversationArchive>> {
+        let rows = sqlx::query("SELECT data FROM archives ORDER BY conversation_id")
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows
+            .iter()
+            .filter_map(|row| {
+                let data: String = row.get("data");
+                match serde_json::from_str(&data) {
+                    Ok(archive) => Some(archive),
+                    Err(err) => {
+                        warn!("ignoring malformed archive row: {err}");
+                        None
+                    }
+                }
+            })
+            .collect())
+    }
+
+    async fn save_archive(&self, archive: &ConversationArchive) -> StorageResult<()> {
+        let data = serde_json::to_string(archive)?;
+        sqlx::query(
+            "INSERT INTO archives (conversation_id, data) VALUES (?1, ?2) \
+             ON CONFLICT(conversation_id) DO UPDATE SET data = excluded.data",
+        )
+        .bind(&archive.conversation_id)
+        .bind(data)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn load_turn_attempt(&self, conversation_id: &str) -> StorageResult<Option<TurnAttempt>> {
+        let row = sqlx::query("SELECT data FROM turn_attempts WHERE conversation_id = ?1")
+            .bind(conversation_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        match row {
+            Some(row) => {
+                let data: String = row.get("data");
+                Ok(Some(serde_json::from_str(&data)?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn save_turn_attempt(&self, attempt: &TurnAttempt) -> StorageResult<()> {
+        let data = serde_json::to_string(attempt)?;
+        sqlx::query(
+            "INSERT INTO turn_attempts (conversation_id, data) VALUES (?1, ?2) \
+             ON CONFLICT(conversation_id) DO UPDATE SET data = excluded.data",
+        )
+        .bind(&attempt.conversation_id)
+        .bind(data)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn append_audit(&self, action: &str, detail: &str) -> StorageResult<()> {
+        sqlx::query("INSERT INTO audit_log (at, action, detail) VALUES (?1, ?2, ?3)")
+            .bind(Utc::now().to_rfc3339())
+            .bind(action)
+            .bind(detail)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn recent_audit(&self, limit: usize) -> StorageResult<Vec<AuditEntry>> {
+        let rows =
+            sqlx::query("SELECT id, at, action, detail FROM audit_log ORDER BY id DESC LIMIT ?1")
+                .bind(limit as i64)
+                .fetch_all(&self.pool)
+                .await?;
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let at: String = row.get("at");
+                AuditEntry {
+                    id: row.get::<i64, _>("id") as u64,
+                    at: DateTime::parse_from_rfc3339(&at)
+                        .map(|at| at.with_timezone(&Utc))
+                        .unwrap_or_default(),
+                    action: row.get("action"),
+                    detail: row.get("detail"),
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::job_queue::JobStatus;
+    use pretty_assertions::assert_eq;
+
+    async fn storage(codex_home: &Path) -> SqliteStorage {
+        SqliteStorage::open(codex_home).await.expect("open storage")
+    }
+
+    #[tokio::test]
+    async fn jobs_round_trip_and_update_in_place() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let storage = storage(codex_home.path()).await;
+        let mut job = Job::queued(7, crate::job_queue::JobSpec::default());
+        storage.save_job(&job).await.expect("save job");
+        job.status = JobStatus::Done;
+        storage.save_job(&job).await.expect("update job");
+
+        let jobs = storage.load_jobs().await.expect("load jobs");
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].id, 7);
+        assert_eq!(jobs[0].status, JobStatus::Done);
+    }
+
+    #[tokio::test]
+    async fn templates_round_trip_and_delete() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let storage = storage(codex_home.path()).await;
+        storage
+            .save_template(&PromptTemplate {
+                name: "triage-bug".to_string(),
+                prompt: "triage {issue}".to_string(),
+            })
+            .await
+            .expect("save template");
+        assert_eq!(
+            storage.load_templates().await.expect("load templates")[0].name,
+            "triage-bug"
+        );
+        storage
+            .delete_template("triage-bug")
+            .await
+            .expect("delete template");
+        assert!(
+            storage
+                .load_templates()
+                .await
+                .expect("load templates")
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn recordings_round_trip() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let storage = storage(codex_home.path()).await;
+        storage
+            .save_recording(&crate::recordings::Recording {
+                id: 3,
+                conversation_id: "t-1".to_string(),
+                created_at: Utc::now(),
+                entries: Vec::new(),
+            })
+            .await
+            .expect("save recording");
+        let recordings = storage.load_recordings().await.expect("load recordings");
+        assert_eq!(recordings.len(), 1);
+        assert_eq!(recordings[0].conversation_id, "t-1");
+    }
+
+    #[tokio::test]
+    async fn offloads_are_upserted_by_agent_id() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let storage = storage(codex_home.path()).await;
+        let mut offload = crate::offload::Offload {
+            agent_id: "agent-1".to_string(),
+            conversation_id: "t-1".to_string(),
+            status: crate::offload::OffloadStatus::Offloaded,
+            created_at: Utc::now(),
+            detail: None,
+        };
+        storage.save_offload(&offload).await.expect("save offload");
+        offload.status = crate::offload::OffloadStatus::HandedBack;
+        storage
+            .save_offload(&offload)
+            .await
+            .expect("update offload");
+        let offloads = storage.load_offloads().await.expect("load offloads");
+        assert_eq!(offloads.len(), 1);
+        assert_eq!(
+            offloads[0].status,
+            crate::offload::OffloadStatus::HandedBack
+        );
+    }
+
+    #[tokio::test]
+    async fn archives_are_upserted_by_conversation_id() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let storage = storage(codex_home.path()).await;
+        let mut archive = ConversationArchive {
+            conversation_id: "t-1".to_string(),
+            keys: vec!["codex/t-1/rollout.jsonl".to_string()],
+            archived_at: Utc::now(),
+        };
+        storage.save_archive(&archive).await.expect("save archive");
+        archive
+            .keys
+            .push("codex/t-1/artifacts/report.md".to_string());
+        storage
+            .save_archive(&archive)
+            .await
+            .expect("update archive");
+        let archives = storage.load_archives().await.expect("load archives");
+        assert_eq!(archives.len(), 1);
+        assert_eq!(archives[0].keys.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn the_last_turn_attempt_wins() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let storage = storage(codex_home.path()).await;
+        let mut attempt = TurnAttempt {
+            conversation_id: "t-1".to_string(),
+            input: crate::complete::TurnInput {
+                prompt: "first".to_string(),
+                config_overrides: Vec::new(),
+                images: Vec::new(),
+            },
+            success: false,
+            at: Utc::now(),
+        };
+        storage
+            .save_turn_attempt(&attempt)
+            .await
+            .expect("save attempt");
+        attempt.success = true;
+        storage
+            .save_turn_attempt(&attempt)
+            .await
+            .expect("update attempt");
+        let loaded = storage
+            .load_turn_attempt("t-1")
+            .await
+            .expect("load attempt")
+            .expect("attempt present");
+        assert!(loaded.success);
+        assert_eq!(loaded.input.prompt, "first");
+        assert!(
+            storage
+                .load_turn_attempt("t-2")
+                .await
+                .expect("load")
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn audit_log_is_returned_newest_first() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let storage = storage(codex_home.path()).await;
+        storage
+            .append_audit("job.create", "first")
+            .await
+            .expect("append audit");
+        storage
+            .append_audit("schedule.delete", "second")
+            .await
+            .expect("append audit");
+        let entries = storage.recent_audit(10).await.expect("recent audit");
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].action, "schedule.delete");
+        assert_eq!(entries[1].action, "job.create");
+    }
+}
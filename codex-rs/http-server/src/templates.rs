@@ -0,0 +1,337 @@
+//! Named prompt templates and their `/templates` CRUD routes.
+//!
+//! Templates standardize recurring prompts: teams define them once (in
+//! `[[http_server.templates]]` or over the API) and clients reference them
+//! by name with a set of variables instead of duplicating prompt text.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use axum::Json;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use codex_config::types::HttpTemplateToml;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::AppState;
+use crate::error::ApiError;
+use crate::storage::Storage;
+use crate::storage::audit;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptTemplate {
+    pub name: String,
+    /// Prompt text with `{variable}` placeholders.
+    pub prompt: String,
+}
+
+/// Shared template registry: an in-memory cache over [`Storage`], seeded
+/// from config and editable over the API. Config-defined templates are not
+/// persisted; they are re-seeded from config.toml on every boot.
+#[derive(Clone)]
+pub(crate) struct TemplateStore {
+    templates: Arc<Mutex<BTreeMap<String, String>>>,
+    storage: Arc<dyn Storage>,
+}
+
+impl TemplateStore {
+    /// Loads API-created templates from storage into the cache.
+    pub(crate) async fn load(storage: Arc<dyn Storage>) -> Self {
+        let mut map = BTreeMap::new();
+        match storage.load_templates().await {
+            Ok(templates) => {
+                for template in templates {
+                    map.insert(template.name, template.prompt);
+                }
+            }
+            Err(err) => warn!("failed to load persisted templates: {err}"),
+        }
+        Self {
+            templates: Arc::new(Mutex::new(map)),
+            storage,
+        }
+    }
+
+    pub(crate) fn seed_from_config(&self, templates: &[HttpTemplateToml]) {
+        let mut map = self.lock();
+        for template in templates {
+            map.entry(template.name.clone())
+                .or_insert_with(|| template.prompt.clone());
+        }
+    }
+
+    pub(crate) fn list(&self) -> Vec<PromptTemplate> {
+        self.lock()
+            .iter()
+            .map(|(name, prompt)| PromptTemplate {
+                name: name.clone(),
+                prompt: prompt.clone(),
+            })
+            .collect()
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<String> {
+        self.lock().get(name).cloned()
+    }
+
+    /// Inserts a new template; `false` if the name is already taken.
+    pub(crate) async fn insert(&self, name: String, prompt: String) -> bool {
+        {
+            let mut map = self.lock();
+            if map.contains_key(&name) {
+                return false;
+            }
+            map.insert(name.clone(), prompt.clone());
+        }
+        self.persist(&name, &prompt).await;
+        true
+    }
+
+    /// Replaces an existing template; `false` if it does not exist.
+    pub(crate) async fn update(&self, name: &str, prompt: String) -> bool {
+        {
+            let mut map = self.lock();
+            match map.get_mut(name) {
+                Some(existing) => *existing = prompt.clone(),
+                None => return false,
+            }
+        }
+        self.persist(name, &prompt).await;
+        true
+    }
+
+    pub(crate) async fn remove(&self, name: &str) -> bool {
+        if self.lock().remove(name).is_none() {
+            return false;
+        }
+        if let Err(err) = self.storage.delete_template(name).await {
+            warn!("failed to delete persisted template {name}: {err}");
+        }
+        true
+    }
+
+    /// Best-effort write-through; the cache stays authoritative if it fails.
+    async fn persist(&self, name: &str, prompt: &str) {
+        let template = PromptTemplate {
+            name: name.to_string(),
+            prompt: prompt.to_string(),
+        };
+        if let Err(err) = self.storage.save_template(&template).await {
+            warn!("failed to persist template {name}: {err}");
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, String>> {
+        match self.templates.lock() {
+            Ok(map) => map,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+/// Fills `{variable}` placeholders in `prompt` from `vars`; errors when a
+/// placeholder has no value so typos fail loudly instead of reaching a model.
+pub(crate) fn render_template(
+    prompt: &str,
+    vars: &HashMap<String, String>,
+) -> Result<String, String> {
+    let mut rendered = prompt.to_string();
+    for (name, value) in vars {
+        rendered = rendered.replace(&format!("{{{name}}}"), value);
+    }
+    let missing = placeholder_names(&rendered);
+    if missing.is_empty() {
+        Ok(rendered)
+    } else {
+        Err(format!(
+            "missing template variables: {}",
+            missing.join(", ")
+        ))
+    }
+}
+
+/// `{word}` placeholders still present in `prompt`, in order of appearance.
+fn placeholder_names(prompt: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let mut rest = prompt;
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+        let name = &rest[..end];
+        if !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            && !names.iter().any(|seen| seen == name)
+        {
+            names.push(name.to_string());
+        }
+        rest = &rest[end + 1..];
+    }
+    names
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct UpdateTemplateRequest {
+    prompt: String,
+}
+
+/// `GET /templates`
+pub(crate) async fn list_templates(State(state): State<AppState>) -> Json<Vec<PromptTemplate>> {
+    Json(state.templates.list())
+}
+
+/// `POST /templates`
+pub(crate) async fn create_template(
+    State(state): State<AppState>,
+    Json(template): Json<PromptTemplate>,
+) -> Response {
+    if template.name.trim().is_empty() {
+        return ApiError::invalid_request("name must not be empty").into_response();
+    }
+    if state
+        .templates
+        .insert(template.name.clone(), template.prompt.clone())
+        .await
+    {
+        audit(
+            &*state.storage,
+            "template.create",
+            &format!("template {}", template.name),
+        )
+        .await;
+        (StatusCode::CREATED, Json(template)).into_response()
+    } else {
+        ApiError::invalid_state(format!("template {} already exists", template.name))
+            .into_response()
+    }
+}
+
+/// `GET /templates/{name}`
+pub(crate) async fn get_template(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Response {
+    match state.templates.get(&name) {
+        Some(prompt) => Json(PromptTemplate { name, prompt }).into_response(),
+        None => ApiError::not_found(format!("no template named {name}")).into_response(),
+    }
+}
+
+/// `PUT /templates/{name}`
+pub(crate) async fn update_template(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Json(request): Json<UpdateTemplateRequest>,
+) -> Response {
+    if state.templates.update(&name, request.prompt.clone()).await {
+        audit(
+            &*state.storage,
+            "template.update",
+            &format!("template {name}"),
+        )
+        .await;
+        Json(PromptTemplate {
+            name,
+            prompt: request.prompt,
+        })
+        .into_response()
+    } else {
+        ApiError::not_found(format!("no template named {name}")).into_response()
+    }
+}
+
+/// `DELETE /templates/{name}`
+pub(crate) async fn delete_template(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Response {
+    if state.templates.remove(&name).await {
+        audit(
+            &*state.storage,
+            "template.delete",
+            &format!("template {name}"),
+        )
+        .await;
+        StatusCode::NO_CONTENT.into_response()
+    } else {
+        ApiError::not_found(format!("no template named {name}")).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_state;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn render_substitutes_variables() {
+        let vars = HashMap::from([("component".to_string(), "parser".to_string())]);
+        assert_eq!(
+            render_template("triage the {component} bug", &vars),
+            Ok("triage the parser bug".to_string())
+        );
+    }
+
+    #[test]
+    fn render_reports_missing_variables() {
+        let vars = HashMap::new();
+        assert_eq!(
+            render_template("fix {component} in {repo}", &vars),
+            Err("missing template variables: component, repo".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn crud_round_trip() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let state = test_state(codex_home.path()).await;
+        let response = create_template(
+            State(state.clone()),
+            Json(PromptTemplate {
+                name: "triage-bug".to_string(),
+                prompt: "triage {issue}".to_string(),
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let duplicate = create_template(
+            State(state.clone()),
+            Json(PromptTemplate {
+                name: "triage-bug".to_string(),
+                prompt: "other".to_string(),
+            }),
+        )
+        .await;
+        assert_eq!(duplicate.status(), StatusCode::CONFLICT);
+
+        let updated = update_template(
+            State(state.clone()),
+            Path("triage-bug".to_string()),
+            Json(UpdateTemplateRequest {
+                prompt: "triage {issue} carefully".to_string(),
+            }),
+        )
+        .await;
+        assert_eq!(updated.status(), StatusCode::OK);
+        assert_eq!(
+            state.templates.get("triage-bug"),
+            Some("triage {issue} carefully".to_string())
+        );
+
+        let deleted = delete_template(State(state.clone()), Path("triage-bug".to_string())).await;
+        assert_eq!(deleted.status(), StatusCode::NO_CONTENT);
+        assert!(state.templates.list().is_empty());
+    }
+}
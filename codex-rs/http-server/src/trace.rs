@@ -0,0 +1,71 @@
+//! W3C trace propagation from HTTP requests into conversation turns.
+//!
+//! A client that is itself traced sends `traceparent`/`tracestate` headers;
+//! the turn routes parent their span on them, and the runner hands the
+//! span's context to the spawned `codex exec` through the `TRACEPARENT`/
+//! `TRACESTATE` environment variables the core's otel manager already
+//! reads. With `[otel]` in config.toml pointing at an OTLP endpoint (see
+//! `main.rs`), one trace then covers API latency, model latency, and tool
+//! latency end to end.
+
+use axum::http::HeaderMap;
+use codex_otel::set_parent_from_w3c_trace_context;
+use codex_otel::span_w3c_trace_context;
+use codex_protocol::protocol::W3cTraceContext;
+use tracing::Span;
+use tracing::info_span;
+
+/// Span for one conversation turn, parented on the request's trace
+/// context when the client sent one.
+pub(crate) fn turn_span(headers: &HeaderMap, id: &str) -> Span {
+    let span = info_span!(
+        "codex.http_turn",
+        otel.kind = "server",
+        conversation.id = %id,
+    );
+    let trace = W3cTraceContext {
+        traceparent: header(headers, "traceparent"),
+        tracestate: header(headers, "tracestate"),
+    };
+    if trace.traceparent.is_some() {
+        let _ = set_parent_from_w3c_trace_context(&span, &trace);
+    }
+    span
+}
+
+/// The `TRACEPARENT`/`TRACESTATE` environment carrying `span`'s context
+/// into a spawned `codex exec`; empty when the span is not sampled.
+pub(crate) fn child_trace_env(span: &Span) -> Vec<(&'static str, String)> {
+    let Some(trace) = span_w3c_trace_context(span) else {
+        return Vec::new();
+    };
+    let mut env = Vec::new();
+    if let Some(traceparent) = trace.traceparent {
+        env.push(("TRACEPARENT", traceparent));
+    }
+    if let Some(tracestate) = trace.tracestate {
+        env.push(("TRACESTATE", tracestate));
+    }
+    env
+}
+
+fn header(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn unsampled_spans_export_no_child_environment() {
+        // Without an otel subscriber installed the span has no recorded
+        // trace context, so the child process environment stays clean.
+        let span = turn_span(&HeaderMap::new(), "abc");
+        assert_eq!(child_trace_env(&span), Vec::new());
+    }
+}
@@ -0,0 +1,297 @@
+//! Process-wide cap on concurrent model turns.
+//!
+//! Every conversation turn the server starts — queued jobs, scheduled runs,
+//! replays, offload hand-backs — ultimately burns shared API quota, so the
+//! gate limits how many run at once regardless of which subsystem asked.
+//! Waiters are split into two priority classes: interactive turns (started
+//! by a request handler on a user's behalf) always overtake batch turns
+//! (jobs and schedules), so a burst of batch work cannot starve interactive
+//! users. Each waiter's queue position is published on the event bus as a
+//! `turn.queued` event, followed by `turn.started` once it gets a slot.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use serde::Serialize;
+use tokio::sync::mpsc;
+use tokio::sync::oneshot;
+
+use crate::events::EventBus;
+use crate::events::ServerEvent;
+use crate::runner::ConversationRunner;
+use crate::runner::NewConversationOutcome;
+use crate::runner::RunOutcome;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum TurnPriority {
+    /// A turn a user is waiting on; overtakes every queued batch turn.
+    Interactive,
+    /// A job-queue or scheduled turn.
+    Batch,
+}
+
+struct GateState {
+    active: usize,
+    interactive: VecDeque<oneshot::Sender<TurnPermit>>,
+    batch: VecDeque<oneshot::Sender<TurnPermit>>,
+}
+
+struct GateInner {
+    limit: usize,
+    state: Mutex<GateState>,
+    events: Arc<dyn EventBus>,
+}
+
+impl GateInner {
+    fn lock(&self) -> std::sync::MutexGuard<'_, GateState> {
+        match self.state.lock() {
+            Ok(state) => state,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+/// Shared turn-concurrency limiter; clones gate the same set of slots.
+#[derive(Clone)]
+pub(crate) struct TurnGate {
+    inner: Arc<GateInner>,
+}
+
+impl TurnGate {
+    /// `limit` of `None` leaves turns uncapped; a cap of zero would deadlock
+    /// every caller, so it is bumped to one.
+    pub(crate) fn new(limit: Option<usize>, events: Arc<dyn EventBus>) -> Self {
+        Self {
+            inner: Arc::new(GateInner {
+                limit: limit.map_or(usize::MAX, |limit| limit.max(1)),
+                state: Mutex::new(GateState {
+                    active: 0,
+                    interactive: VecDeque::new(),
+                    batch: VecDeque::new(),
+                }),
+                events,
+            }),
+        }
+    }
+
+    /// Waits for a free turn slot; the slot is held until the returned
+    /// permit is dropped.
+    pub(crate) async fn acquire(&self, priority: TurnPriority) -> TurnPermit {
+        let waiter = {
+            let mut state = self.inner.lock();
+            if state.active < self.inner.limit {
+                state.active += 1;
+                None
+            } else {
+                let (tx, rx) = oneshot::channel();
+                let position = match priority {
+                    TurnPriority::Interactive => {
+                        state.interactive.push_back(tx);
+                        state.interactive.len()
+                    }
+                    TurnPriority::Batch => {
+                        state.batch.push_back(tx);
+                        state.interactive.len() + state.batch.len()
+                    }
+                };
+                Some((rx, position))
+            }
+        };
+        let Some((rx, position)) = waiter else {
+            return TurnPermit {
+                inner: self.inner.clone(),
+            };
+        };
+        self.inner
+            .events
+            .publish(ServerEvent {
+                kind: "turn.queued".to_string(),
+                payload: serde_json::json!({
+                    "priority": priority,
+                    "position": position,
+                }),
+            })
+            .await;
+        let permit = match rx.await {
+            Ok(permit) => permit,
+            // The queue entry lives in state we keep alive through the gate,
+            // so the sender cannot be dropped while we wait; treat the
+            // impossible case as a fresh slot rather than hanging.
+            Err(_) => TurnPermit {
+                inner: self.inner.clone(),
+            },
+        };
+        self.inner
+            .events
+            .publish(ServerEvent {
+                kind: "turn.started".to_string(),
+                payload: serde_json::json!({ "priority": priority }),
+            })
+            .await;
+        permit
+    }
+
+    /// Wraps `runner` so every turn it runs first takes a slot at `priority`.
+    pub(crate) fn wrap(
+        &self,
+        priority: TurnPriority,
+        runner: Arc<dyn ConversationRunner>,
+    ) -> Arc<dyn ConversationRunner> {
+        Arc::new(GatedRunner {
+            gate: self.clone(),
+            priority,
+            runner,
+        })
+    }
+}
+
+/// One held turn slot; dropping it hands the slot to the next waiter,
+/// interactive queue first.
+pub(crate) struct TurnPermit {
+    inner: Arc<GateInner>,
+}
+
+impl Drop for TurnPermit {
+    fn drop(&mut self) {
+        let waiter = {
+            let mut state = self.inner.lock();
+            let waiter = state
+                .interactive
+                .pop_front()
+                .or_else(|| state.batch.pop_front());
+            if waiter.is_none() {
+                state.active -= 1;
+            }
+            waiter
+        };
+        if let Some(tx) = waiter {
+            // Hand the slot over without touching `active`. If the waiter
+            // gave up, the unsent permit drops right here and the hand-off
+            // repeats with the next one.
+            let _ = tx.send(TurnPermit {
+                inner: self.inner.clone(),
+            });
+        }
+    }
+}
+
+/// [`ConversationRunner`] that holds a [`TurnGate`] slot for the duration of
+/// each turn.
+struct GatedRunner {
+    gate: TurnGate,
+    priority: TurnPriority,
+    runner: Arc<dyn ConversationRunner>,
+}
+
+#[async_trait]
+impl ConversationRunner for GatedRunner {
+    async fn run(
+        &self,
+        prompt: &str,
+        cwd: Option<&std::path::Path>,
+        config_overrides: &[String],
+    ) -> RunOutcome {
+        let _permit = self.gate.acquire(self.priority).await;
+        self.runner.run(prompt, cwd, config_overrides).await
+    }
+
+    async fn run_streaming(
+        &self,
+        prompt: &str,
+        cwd: Option<&std::path::Path>,
+        config_overrides: &[String],
+        images: &[std::path::PathBuf],
+        text: mpsc::Sender<String>,
+    ) -> RunOutcome {
+        let _permit = self.gate.acquire(self.priority).await;
+        self.runner
+            .run_streaming(prompt, cwd, config_overrides, images, text)
+            .await
+    }
+
+    async fn run_new_conversation(
+        &self,
+        prompt: &str,
+        cwd: Option<&std::path::Path>,
+        config_overrides: &[String],
+    ) -> NewConversationOutcome {
+        let _permit = self.gate.acquire(self.priority).await;
+        self.runner
+            .run_new_conversation(prompt, cwd, config_overrides)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::LocalEventBus;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    fn gate(limit: usize) -> TurnGate {
+        TurnGate::new(Some(limit), Arc::new(LocalEventBus::new()))
+    }
+
+    #[tokio::test]
+    async fn turns_beyond_the_cap_wait_for_a_free_slot() {
+        let gate = gate(1);
+        let held = gate.acquire(TurnPriority::Batch).await;
+        let waiter = tokio::spawn({
+            let gate = gate.clone();
+            async move { gate.acquire(TurnPriority::Batch).await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiter.is_finished());
+        drop(held);
+        waiter.await.expect("waiter finishes");
+    }
+
+    #[tokio::test]
+    async fn interactive_waiters_overtake_batch_waiters() {
+        let gate = gate(1);
+        let held = gate.acquire(TurnPriority::Batch).await;
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let mut waiters = Vec::new();
+        for priority in [TurnPriority::Batch, TurnPriority::Interactive] {
+            let gate = gate.clone();
+            let order = order.clone();
+            waiters.push(tokio::spawn(async move {
+                let permit = gate.acquire(priority).await;
+                order.lock().expect("lock order").push(priority);
+                drop(permit);
+            }));
+            // Let this waiter enqueue before starting the next one.
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        drop(held);
+        for waiter in waiters {
+            waiter.await.expect("waiter finishes");
+        }
+        let order = order.lock().expect("lock order").clone();
+        assert_eq!(order, vec![TurnPriority::Interactive, TurnPriority::Batch]);
+    }
+
+    #[tokio::test]
+    async fn queue_position_is_published() {
+        let events: Arc<dyn EventBus> = Arc::new(LocalEventBus::new());
+        let mut rx = events.subscribe();
+        let gate = TurnGate::new(Some(1), events);
+        let held = gate.acquire(TurnPriority::Interactive).await;
+        let waiter = tokio::spawn({
+            let gate = gate.clone();
+            async move { gate.acquire(TurnPriority::Batch).await }
+        });
+        let queued = rx.next().await.expect("queued event");
+        assert_eq!(queued.kind, "turn.queued");
+        assert_eq!(queued.payload["priority"], "batch");
+        assert_eq!(queued.payload["position"], 1);
+        drop(held);
+        waiter.await.expect("waiter finishes");
+        let started = rx.next().await.expect("started event");
+        assert_eq!(started.kind, "turn.started");
+    }
+}
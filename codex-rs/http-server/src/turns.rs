@@ -0,0 +1,204 @@
+//! Per-conversation turn locks and the `/conversations/{id}/interrupt`
+//! route.
+//!
+//! Two clients posting to `/conversations/{id}/complete` at once would
+//! interleave their turns through the same rollout unpredictably. The
+//! server tracks at most one active turn per conversation: a second
+//! request is told the conversation is busy — `409` with the running
+//! turn's details — unless it asks to queue behind the running turn or to
+//! force an interrupt and take over. A running turn can also be stopped
+//! outright through the interrupt route, without starting a replacement.
+//! This is orthogonal to the [`crate::turn_gate`], which caps concurrency
+//! across conversations.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use axum::Json;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Serialize;
+use tokio::sync::Notify;
+use tokio::task::AbortHandle;
+
+use crate::AppState;
+use crate::error::ApiError;
+use crate::storage::audit;
+
+/// The turn currently running in one conversation; serialized into the
+/// `409` details so the caller sees what it collided with.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ActiveTurn {
+    /// Prompt the running turn was started with.
+    pub prompt: String,
+    pub started_at: DateTime<Utc>,
+    /// Aborting drops the runner future, which kills the spawned process.
+    #[serde(skip)]
+    abort: Option<AbortHandle>,
+}
+
+/// Which conversations have a turn running right now.
+#[derive(Clone, Default)]
+pub(crate) struct ActiveTurns {
+    inner: Arc<Mutex<HashMap<String, ActiveTurn>>>,
+    /// Woken whenever a turn ends, so queued requests retry the lock.
+    freed: Arc<Notify>,
+}
+
+impl ActiveTurns {
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, ActiveTurn>> {
+        match self.inner.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Claims the conversation for a new turn, or reports the turn it is
+    /// already running.
+    pub(crate) fn try_begin(&self, id: &str, prompt: &str) -> Result<(), ActiveTurn> {
+        let mut active = self.lock();
+        if let Some(current) = active.get(id) {
+            return Err(current.clone());
+        }
+        active.insert(
+            id.to_string(),
+            ActiveTurn {
+                prompt: prompt.to_string(),
+                started_at: Utc::now(),
+                abort: None,
+            },
+        );
+        Ok(())
+    }
+
+    /// Claims the conversation, waiting behind however many turns are
+    /// queued ahead; there is no fairness guarantee between waiters.
+    pub(crate) async fn begin_queued(&self, id: &str, prompt: &str) {
+        loop {
+            // Register for the wakeup before checking, so a turn that ends
+            // between the check and the await is not missed.
+            let freed = self.freed.notified();
+            if self.try_begin(id, prompt).is_ok() {
+                return;
+            }
+            freed.await;
+        }
+    }
+
+    /// Attaches the spawned turn's abort handle, so a later force can
+    /// actually stop it. No-op if the turn was already interrupted.
+    pub(crate) fn set_abort(&self, id: &str, abort: AbortHandle) {
+        if let Some(current) = self.lock().get_mut(id) {
+            current.abort = Some(abort);
+        }
+    }
+
+    /// Releases the conversation after its turn ends.
+    pub(crate) fn finish(&self, id: &str) {
+        self.lock().remove(id);
+        self.freed.notify_waiters();
+    }
+
+    /// Interrupts the running turn, if any, and releases the conversation.
+    pub(crate) fn interrupt(&self, id: &str) -> Option<ActiveTurn> {
+        let interrupted = self.lock().remove(id);
+        if let Some(turn) = &interrupted {
+            if let Some(abort) = &turn.abort {
+                abort.abort();
+            }
+            self.freed.notify_waiters();
+        }
+        interrupted
+    }
+}
+
+/// `POST /conversations/{id}/interrupt`
+///
+/// Stops the conversation's running turn, killing the spawned process, and
+/// returns the turn that was interrupted; `409` when no turn is running.
+pub(crate) async fn interrupt_turn(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Response {
+    match state.active_turns.interrupt(&id) {
+        Some(turn) => {
+            audit(
+                &*state.storage,
+                "turn.interrupt",
+                &format!("conversation {id}"),
+            )
+            .await;
+            Json(turn).into_response()
+        }
+        None => ApiError::invalid_state(format!("conversation {id} has no turn running"))
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_state;
+    use axum::http::StatusCode;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn a_second_turn_sees_the_running_one() {
+        let turns = ActiveTurns::default();
+        turns.try_begin("abc", "first prompt").expect("claim");
+        let active = turns
+            .try_begin("abc", "second prompt")
+            .expect_err("should be busy");
+        assert_eq!(active.prompt, "first prompt");
+        // Other conversations are unaffected.
+        turns.try_begin("def", "elsewhere").expect("claim other");
+    }
+
+    #[tokio::test]
+    async fn finishing_admits_the_queued_turn() {
+        let turns = ActiveTurns::default();
+        turns.try_begin("abc", "first").expect("claim");
+        let waiter = {
+            let turns = turns.clone();
+            tokio::spawn(async move { turns.begin_queued("abc", "second").await })
+        };
+        turns.finish("abc");
+        waiter.await.expect("queued turn admitted");
+        let active = turns.try_begin("abc", "third").expect_err("busy again");
+        assert_eq!(active.prompt, "second");
+    }
+
+    #[test]
+    fn interrupting_frees_the_conversation() {
+        let turns = ActiveTurns::default();
+        turns.try_begin("abc", "long running").expect("claim");
+        let interrupted = turns.interrupt("abc").expect("was running");
+        assert_eq!(interrupted.prompt, "long running");
+        assert!(turns.interrupt("abc").is_none());
+        turns.try_begin("abc", "next").expect("free again");
+    }
+
+    #[tokio::test]
+    async fn interrupt_route_requires_a_running_turn() {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let state = test_state(codex_home.path()).await;
+        let response = interrupt_turn(State(state.clone()), Path("abc".to_string())).await;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        state
+            .active_turns
+            .try_begin("abc", "long running")
+            .expect("claim");
+        let response = interrupt_turn(State(state.clone()), Path("abc".to_string())).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        state
+            .active_turns
+            .try_begin("abc", "next")
+            .expect("free again");
+    }
+}
@@ -0,0 +1,156 @@
+//! Git worktree isolation for queued conversations.
+//!
+//! A job created with `use_worktree` runs in a dedicated worktree and branch
+//! carved off the base repository, so concurrent jobs on one repo do not
+//! trample each other's working tree. The worktree survives the job so its
+//! work can be diffed, committed, pushed, or discarded afterwards.
+
+use std::path::Path;
+use std::process::Stdio;
+
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::process::Command;
+
+/// Worktree attached to a job, recorded so later requests can operate on it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorktreeState {
+    pub path: std::path::PathBuf,
+    pub branch: String,
+    pub base_repo: std::path::PathBuf,
+}
+
+/// Creates `worktree_path` on a new `branch` starting at the base repo's HEAD.
+pub(crate) async fn create(
+    base_repo: &Path,
+    worktree_path: &Path,
+    branch: &str,
+) -> Result<(), String> {
+    if let Some(parent) = worktree_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|err| format!("failed to create {}: {err}", parent.display()))?;
+    }
+    run_git(
+        base_repo,
+        &[
+            "worktree",
+            "add",
+            "-b",
+            branch,
+            &worktree_path.display().to_string(),
+        ],
+    )
+    .await
+    .map(|_| ())
+}
+
+/// Working-tree diff of everything the conversation changed since the
+/// worktree was created (staged and unstaged, relative to the last commit).
+pub(crate) async fn diff(worktree: &Path) -> Result<String, String> {
+    run_git(worktree, &["diff", "HEAD"]).await
+}
+
+/// Stages and commits all changes in the worktree; returns git's summary.
+pub(crate) async fn commit(worktree: &Path, message: &str) -> Result<String, String> {
+    run_git(worktree, &["add", "-A"]).await?;
+    run_git(worktree, &["commit", "-m", message]).await
+}
+
+/// Pushes the worktree's branch to `origin`, setting the upstream.
+pub(crate) async fn push(worktree: &Path, branch: &str) -> Result<String, String> {
+    run_git(worktree, &["push", "-u", "origin", branch]).await
+}
+
+/// Removes the worktree and deletes its branch, dropping all of its work.
+pub(crate) async fn discard(base_repo: &Path, worktree: &Path, branch: &str) -> Result<(), String> {
+    run_git(
+        base_repo,
+        &[
+            "worktree",
+            "remove",
+            "--force",
+            &worktree.display().to_string(),
+        ],
+    )
+    .await?;
+    run_git(base_repo, &["branch", "-D", branch]).await?;
+    Ok(())
+}
+
+/// Runs `git -C dir args...`, returning stdout or the failure output.
+pub(crate) async fn run_git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .await
+        .map_err(|err| format!("failed to run git: {err}"))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        Err(format!(
+            "git {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    async fn init_repo(dir: &Path) {
+        for args in [
+            vec!["init", "--initial-branch=main"],
+            vec!["config", "user.email", "test@example.com"],
+            vec!["config", "user.name", "Test"],
+        ] {
+            run_git(dir, &args).await.expect("git setup");
+        }
+        std::fs::write(dir.join("README.md"), "hello\n").expect("write file");
+        run_git(dir, &["add", "-A"]).await.expect("git add");
+        run_git(dir, &["commit", "-m", "init"])
+            .await
+            .expect("git commit");
+    }
+
+    #[tokio::test]
+    async fn create_diff_commit_and_discard() {
+        let base = tempfile::tempdir().expect("create tempdir");
+        init_repo(base.path()).await;
+        let worktree_path = base.path().join("wt").join("job-1");
+        create(base.path(), &worktree_path, "codex/job-1")
+            .await
+            .expect("create worktree");
+
+        // Work in the worktree is invisible to the base repo.
+        std::fs::write(worktree_path.join("new.rs"), "fn main() {}\n").expect("write file");
+        run_git(&worktree_path, &["add", "-N", "new.rs"])
+            .await
+            .expect("track new file");
+        let wt_diff = diff(&worktree_path).await.expect("diff");
+        assert!(wt_diff.contains("new.rs"));
+        assert!(!base.path().join("new.rs").exists());
+
+        commit(&worktree_path, "add new.rs").await.expect("commit");
+        assert_eq!(diff(&worktree_path).await.expect("diff"), "");
+
+        discard(base.path(), &worktree_path, "codex/job-1")
+            .await
+            .expect("discard");
+        assert!(!worktree_path.exists());
+    }
+
+    #[tokio::test]
+    async fn create_fails_outside_a_repository() {
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let result = create(dir.path(), &dir.path().join("wt"), "codex/job-1").await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,5 @@
+#![allow(clippy::expect_used)]
+
+// Single integration test binary that aggregates all test modules.
+// The submodules live in `tests/suite/`.
+mod suite;
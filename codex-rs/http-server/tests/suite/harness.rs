@@ -0,0 +1,94 @@
+//! Boots the real server on an ephemeral port for end-to-end tests.
+//!
+//! The server's model boundary is the `codex exec` subprocess it spawns, so
+//! the harness stands in a stub `codex` binary (a shell script under the
+//! test's `CODEX_HOME`) rather than mocking a provider endpoint; each test
+//! picks what the stub prints and how it exits. Requests go through
+//! `codex-http-server-client`, so the suite also exercises the published
+//! client surface.
+
+use std::collections::HashMap;
+use std::os::unix::fs::PermissionsExt;
+use std::time::Duration;
+
+use codex_config::types::HttpSandboxLimitsToml;
+use codex_http_server::ServerConfig;
+use codex_http_server_client::HttpServerClient;
+use codex_http_server_client::Job;
+use codex_http_server_client::JobSpec;
+use codex_http_server_client::JobStatus;
+
+/// Stub that completes every conversation successfully.
+pub const SUCCESS_SCRIPT: &str = "#!/bin/sh\necho 'stub turn complete'\n";
+
+/// Stub that fails every conversation.
+pub const FAILURE_SCRIPT: &str = "#!/bin/sh\necho 'model exploded' >&2\nexit 1\n";
+
+pub struct TestServer {
+    pub client: HttpServerClient,
+    _codex_home: tempfile::TempDir,
+}
+
+impl TestServer {
+    /// Boots `serve` on an ephemeral port with one job worker and a stub
+    /// `codex` binary running `script`.
+    pub async fn start(script: &str) -> Self {
+        let codex_home = tempfile::tempdir().expect("create tempdir");
+        let codex_bin = codex_home.path().join("codex");
+        std::fs::write(&codex_bin, script).expect("write stub codex");
+        std::fs::set_permissions(&codex_bin, std::fs::Permissions::from_mode(0o755))
+            .expect("mark stub executable");
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind");
+        let addr = listener.local_addr().expect("local addr");
+        let config = ServerConfig {
+            codex_home: codex_home.path().to_path_buf(),
+            schedules: Vec::new(),
+            job_workers: 1,
+            codex_bin,
+            github_token: None,
+            templates: Vec::new(),
+            event_bus: None,
+            sandbox_limits: HttpSandboxLimitsToml::default(),
+            model_providers: HashMap::new(),
+            max_turn_seconds: None,
+            max_concurrent_turns: None,
+            archive: None,
+            notify: None,
+            limits: None,
+            images: None,
+            chatops: None,
+            quota: None,
+            redaction: None,
+            instructions: None,
+        };
+        tokio::spawn(async move {
+            let _ = codex_http_server::serve(listener, config).await;
+        });
+        Self {
+            client: HttpServerClient::new(format!("http://{addr}")),
+            _codex_home: codex_home,
+        }
+    }
+
+    /// Queues `prompt` and polls until the job finishes either way.
+    pub async fn run_job(&self, prompt: &str) -> Job {
+        let job = self
+            .client
+            .create_job(&JobSpec {
+                prompt: prompt.to_string(),
+                ..JobSpec::default()
+            })
+            .await
+            .expect("create job");
+        for _ in 0..500 {
+            let job = self.client.get_job(job.id).await.expect("get job");
+            if matches!(job.status, JobStatus::Done | JobStatus::Failed) {
+                return job;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("job {} did not finish", job.id);
+    }
+}
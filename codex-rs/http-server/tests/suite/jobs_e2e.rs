@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use codex_http_server_client::JobStatus;
+use futures::StreamExt;
+use pretty_assertions::assert_eq;
+
+use super::harness::FAILURE_SCRIPT;
+use super::harness::SUCCESS_SCRIPT;
+use super::harness::TestServer;
+
+#[tokio::test]
+async fn job_runs_to_completion_end_to_end() {
+    let server = TestServer::start(SUCCESS_SCRIPT).await;
+    let mut events = Box::pin(server.client.events().await.expect("open event stream"));
+
+    let job = server.run_job("fix the flaky test").await;
+    assert_eq!(job.status, JobStatus::Done);
+    assert!(
+        job.result
+            .as_deref()
+            .unwrap_or_default()
+            .contains("stub turn complete"),
+        "unexpected result: {:?}",
+        job.result
+    );
+
+    let mut kinds = Vec::new();
+    while kinds.len() < 3 {
+        let event = tokio::time::timeout(Duration::from_secs(5), events.next())
+            .await
+            .expect("event within timeout")
+            .expect("stream open")
+            .expect("well-formed event");
+        kinds.push(event.kind);
+    }
+    assert_eq!(kinds, ["job.queued", "job.running", "job.done"]);
+}
+
+#[tokio::test]
+async fn failing_conversation_marks_the_job_failed() {
+    let server = TestServer::start(FAILURE_SCRIPT).await;
+    let job = server.run_job("break everything").await;
+    assert_eq!(job.status, JobStatus::Failed);
+    assert!(
+        job.result
+            .as_deref()
+            .unwrap_or_default()
+            .contains("model exploded"),
+        "unexpected result: {:?}",
+        job.result
+    );
+}
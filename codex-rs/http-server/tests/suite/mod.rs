@@ -0,0 +1,6 @@
+// End-to-end suite: boots the real server against a stub `codex` binary,
+// which needs a unix shell.
+#[cfg(unix)]
+mod harness;
+#[cfg(unix)]
+mod jobs_e2e;
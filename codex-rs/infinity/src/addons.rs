@@ -0,0 +1,312 @@
+use anyhow::Result;
+use anyhow::bail;
+use chrono::DateTime;
+use chrono::NaiveDate;
+use chrono::NaiveTime;
+use chrono::Utc;
+
+use crate::cli::AddonsListCommand;
+use crate::cli::BackupsCommand;
+use crate::cli::EventsCommand;
+use crate::cli::MetricsCommand;
+use crate::cli::RestoreCommand;
+use crate::client::Addon;
+use crate::client::AddonBackup;
+use crate::client::AddonMetrics;
+use crate::client::InfinityClient;
+
+const BACKUP_STATUS_COMPLETED: &str = "completed";
+const METRICS_WATCH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Resolve the addon a command targets, either by `--addon-id` or by the
+/// project's single addon of `--type`.
+pub(crate) async fn resolve_addon(
+    client: &InfinityClient,
+    project: &str,
+    addon_type: Option<&str>,
+    addon_id: Option<&str>,
+) -> Result<Addon> {
+    match (addon_id, addon_type) {
+        (Some(id), _) => client.find_addon_by_id(project, id).await,
+        (None, Some(addon_type)) => client.find_addon_by_type(project, addon_type).await,
+        // clap requires one of --type/--addon-id.
+        (None, None) => unreachable!(),
+    }
+}
+
+pub async fn run_backups(cmd: BackupsCommand) -> Result<()> {
+    let client = InfinityClient::from_env()?;
+    let addon = resolve_addon(
+        &client,
+        &cmd.project,
+        cmd.addon_type.as_deref(),
+        cmd.addon_id.as_deref(),
+    )
+    .await?;
+    let backups = client.list_backups(&addon.id).await?;
+    if backups.is_empty() {
+        println!(
+            "no backups for {} addon {} of {}",
+            addon.addon_type, addon.id, cmd.project
+        );
+        return Ok(());
+    }
+    println!("{:<48}  {:<25}  {:>10}  STATUS", "KEY", "CREATED", "SIZE");
+    for backup in &backups {
+        println!(
+            "{:<48}  {:<25}  {:>10}  {}",
+            backup.key,
+            backup.created_at.to_rfc3339(),
+            format_size(backup.size_bytes),
+            backup.status,
+        );
+    }
+    Ok(())
+}
+
+pub async fn run_restore(cmd: RestoreCommand) -> Result<()> {
+    let client = InfinityClient::from_env()?;
+    let addon = resolve_addon(
+        &client,
+        &cmd.project,
+        cmd.addon_type.as_deref(),
+        cmd.addon_id.as_deref(),
+    )
+    .await?;
+    let key = match cmd.key {
+        Some(key) => key,
+        None => {
+            let selector = BackupSelector::from_flags(cmd.latest, cmd.before.as_deref())?;
+            let backups = client.list_backups(&addon.id).await?;
+            select_backup(&backups, &selector)?.key.clone()
+        }
+    };
+    let restore = client.restore_backup(&addon.id, &key).await?;
+    println!(
+        "restore {} started for {} addon {} from {key} (status: {})",
+        restore.restore_id, addon.addon_type, addon.id, restore.status
+    );
+    Ok(())
+}
+
+pub async fn run_list(cmd: AddonsListCommand) -> Result<()> {
+    let client = InfinityClient::from_env()?;
+    let listing = client.list_addons(&cmd.project).await?;
+    if listing.addons.is_empty() {
+        println!("project {} has no addons", cmd.project);
+        return Ok(());
+    }
+    println!(
+        "{:<24}  {:<10}  {:<10}  {:<12}  {:<10}  BACKUPS",
+        "ID", "TYPE", "PLAN", "REGION", "STATUS"
+    );
+    for addon in &listing.addons {
+        println!(
+            "{:<24}  {:<10}  {:<10}  {:<12}  {:<10}  {}",
+            addon.id,
+            addon.addon_type,
+            addon.plan,
+            addon.region,
+            addon.status,
+            if addon.backup_enabled {
+                "enabled"
+            } else {
+                "disabled"
+            },
+        );
+    }
+    Ok(())
+}
+
+pub async fn run_events(cmd: EventsCommand) -> Result<()> {
+    let client = InfinityClient::from_env()?;
+    let addon = resolve_addon(
+        &client,
+        &cmd.project,
+        cmd.addon_type.as_deref(),
+        cmd.addon_id.as_deref(),
+    )
+    .await?;
+    let events = client.list_events(&addon.id).await?;
+    if events.is_empty() {
+        println!(
+            "no events for {} addon {} of {}",
+            addon.addon_type, addon.id, cmd.project
+        );
+        return Ok(());
+    }
+    for event in events.iter().take(cmd.limit) {
+        println!(
+            "{}  {:<12}  {}",
+            event.created_at.to_rfc3339(),
+            event.kind,
+            event.message
+        );
+    }
+    Ok(())
+}
+
+pub async fn run_metrics(cmd: MetricsCommand) -> Result<()> {
+    let client = InfinityClient::from_env()?;
+    let addon = client
+        .find_addon_by_type(&cmd.project, &cmd.addon_type)
+        .await?;
+    loop {
+        let metrics = client.addon_metrics(&addon.id).await?;
+        if cmd.json {
+            println!("{}", serde_json::to_string_pretty(&metrics)?);
+        } else {
+            if cmd.watch {
+                // Clear the screen between refreshes so the table stays put.
+                print!("\x1b[2J\x1b[H");
+            }
+            print_metrics_table(&addon.id, &metrics);
+        }
+        if !cmd.watch {
+            return Ok(());
+        }
+        tokio::time::sleep(METRICS_WATCH_INTERVAL).await;
+    }
+}
+
+fn print_metrics_table(addon_id: &str, metrics: &AddonMetrics) {
+    println!(
+        "addon {addon_id} (collected {})",
+        metrics.collected_at.to_rfc3339()
+    );
+    println!(
+        "{:<14} {} / {}",
+        "connections", metrics.connections, metrics.max_connections
+    );
+    println!(
+        "{:<14} {} / {}",
+        "storage",
+        format_size(metrics.storage_used_bytes),
+        format_size(metrics.storage_limit_bytes),
+    );
+    println!("{:<14} {:.1}", "qps", metrics.queries_per_second);
+}
+
+/// How to pick a backup when the user did not paste an object key.
+#[derive(Debug, PartialEq)]
+enum BackupSelector {
+    Latest,
+    Before(DateTime<Utc>),
+}
+
+impl BackupSelector {
+    fn from_flags(latest: bool, before: Option<&str>) -> Result<Self> {
+        match (latest, before) {
+            (true, None) => Ok(Self::Latest),
+            (false, Some(raw)) => Ok(Self::Before(parse_before_timestamp(raw)?)),
+            (false, None) => bail!("pass a backup KEY, --latest, or --before <timestamp>"),
+            // clap rejects --latest together with --before.
+            (true, Some(_)) => unreachable!(),
+        }
+    }
+}
+
+fn parse_before_timestamp(raw: &str) -> Result<DateTime<Utc>> {
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(raw) {
+        return Ok(timestamp.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
+        return Ok(date.and_time(NaiveTime::MIN).and_utc());
+    }
+    bail!("could not parse `{raw}` as an RFC 3339 timestamp or a YYYY-MM-DD date")
+}
+
+fn select_backup<'a>(
+    backups: &'a [AddonBackup],
+    selector: &BackupSelector,
+) -> Result<&'a AddonBackup> {
+    let selected = backups
+        .iter()
+        .filter(|backup| backup.status == BACKUP_STATUS_COMPLETED)
+        .filter(|backup| match selector {
+            BackupSelector::Latest => true,
+            BackupSelector::Before(cutoff) => backup.created_at <= *cutoff,
+        })
+        .max_by_key(|backup| backup.created_at);
+    match selected {
+        Some(backup) => Ok(backup),
+        None => match selector {
+            BackupSelector::Latest => bail!("no completed backups found"),
+            BackupSelector::Before(cutoff) => {
+                bail!(
+                    "no completed backups found at or before {}",
+                    cutoff.to_rfc3339()
+                )
+            }
+        },
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn backup(key: &str, created_at: &str, status: &str) -> AddonBackup {
+        AddonBackup {
+            key: key.to_string(),
+            created_at: DateTime::parse_from_rfc3339(created_at)
+                .expect("valid timestamp")
+                .with_timezone(&Utc),
+            size_bytes: 1024,
+            status: status.to_string(),
+        }
+    }
+
+    #[test]
+    fn latest_picks_newest_completed_backup() {
+        let backups = vec![
+            backup("a", "2026-01-01T00:00:00Z", "completed"),
+            backup("b", "2026-01-03T00:00:00Z", "pending"),
+            backup("c", "2026-01-02T00:00:00Z", "completed"),
+        ];
+        let selected = select_backup(&backups, &BackupSelector::Latest).expect("backup selected");
+        assert_eq!(selected.key, "c");
+    }
+
+    #[test]
+    fn before_picks_newest_backup_at_or_before_cutoff() {
+        let backups = vec![
+            backup("a", "2026-01-01T00:00:00Z", "completed"),
+            backup("b", "2026-01-02T00:00:00Z", "completed"),
+            backup("c", "2026-01-03T00:00:00Z", "completed"),
+        ];
+        let cutoff = parse_before_timestamp("2026-01-02T00:00:00Z").expect("valid cutoff");
+        let selected =
+            select_backup(&backups, &BackupSelector::Before(cutoff)).expect("backup selected");
+        assert_eq!(selected.key, "b");
+    }
+
+    #[test]
+    fn before_with_no_match_is_an_error() {
+        let backups = vec![backup("a", "2026-01-02T00:00:00Z", "completed")];
+        let cutoff = parse_before_timestamp("2026-01-01").expect("valid cutoff");
+        assert!(select_backup(&backups, &BackupSelector::Before(cutoff)).is_err());
+    }
+
+    #[test]
+    fn date_only_cutoff_parses_as_midnight_utc() {
+        let cutoff = parse_before_timestamp("2026-01-02").expect("valid cutoff");
+        assert_eq!(cutoff.to_rfc3339(), "2026-01-02T00:00:00+00:00");
+    }
+}
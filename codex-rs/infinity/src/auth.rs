@@ -0,0 +1,70 @@
+//! API key storage for the Infinity CLI.
+//!
+//! The key is looked up from `CODEX_INFINITY_API_KEY` first so scripts and CI
+//! keep working, then from the OS keychain where `codex infinity login`
+//! stores it.
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use codex_keyring_store::DefaultKeyringStore;
+use codex_keyring_store::KeyringStore;
+use std::io::IsTerminal;
+use std::io::Write;
+
+use crate::client::INFINITY_API_KEY_ENV;
+
+const KEYRING_SERVICE: &str = "codex-infinity";
+const KEYRING_ACCOUNT: &str = "api-key";
+
+/// Resolve the API key from the environment or the keychain.
+pub(crate) fn load_api_key() -> Result<String> {
+    if let Ok(key) = std::env::var(INFINITY_API_KEY_ENV)
+        && !key.is_empty()
+    {
+        return Ok(key);
+    }
+    if let Some(key) = DefaultKeyringStore
+        .load(KEYRING_SERVICE, KEYRING_ACCOUNT)
+        .ok()
+        .flatten()
+    {
+        return Ok(key);
+    }
+    bail!("no Infinity API key found; run `codex infinity login` or export {INFINITY_API_KEY_ENV}")
+}
+
+pub async fn run_login() -> Result<()> {
+    let key = if std::io::stdin().is_terminal() {
+        print!("Paste your Infinity API key: ");
+        std::io::stdout().flush()?;
+        let mut key = String::new();
+        std::io::stdin().read_line(&mut key)?;
+        key.trim().to_string()
+    } else {
+        // Piped input, e.g. `echo $KEY | codex infinity login`.
+        let mut key = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut key)?;
+        key.trim().to_string()
+    };
+    if key.is_empty() {
+        bail!("no API key provided");
+    }
+    DefaultKeyringStore
+        .save(KEYRING_SERVICE, KEYRING_ACCOUNT, &key)
+        .context("failed to store the API key in the system keychain")?;
+    println!("Stored Infinity API key in the system keychain.");
+    Ok(())
+}
+
+pub async fn run_logout() -> Result<()> {
+    let removed = DefaultKeyringStore
+        .delete(KEYRING_SERVICE, KEYRING_ACCOUNT)
+        .context("failed to remove the API key from the system keychain")?;
+    if removed {
+        println!("Removed the stored Infinity API key.");
+    } else {
+        println!("No Infinity API key was stored.");
+    }
+    Ok(())
+}
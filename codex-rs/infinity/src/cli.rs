@@ -0,0 +1,403 @@
+use clap::Args;
+use clap::Parser;
+use codex_utils_cli::CliConfigOverrides;
+use std::path::PathBuf;
+
+/// Manage Codex Infinity projects and addons.
+#[derive(Debug, Parser)]
+pub struct Cli {
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Command {
+    /// Manage database addons attached to a project.
+    Addons(AddonsCli),
+    /// Inspect and roll back project deploys.
+    Deploys(DeploysCli),
+    /// Manage environment variables for a project.
+    Env(EnvCli),
+    /// Manage custom domains for a project.
+    Domains(DomainsCli),
+    /// Store the Infinity API key in the system keychain.
+    Login,
+    /// Remove the stored Infinity API key.
+    Logout,
+    /// List cloud servers and agents.
+    List(ListCommand),
+    /// Open an SSH session on a cloud server.
+    Attach(AttachCommand),
+    /// Create a new cloud server.
+    Create(CreateServerCommand),
+    /// Reboot a cloud server.
+    Reboot(ServerActionCommand),
+    /// Power a cloud server off.
+    Poweroff(ServerActionCommand),
+    /// Permanently delete a cloud server.
+    Delete(ServerActionCommand),
+}
+
+#[derive(Debug, Args)]
+pub struct CreateServerCommand {
+    /// Name for the new server.
+    #[arg(value_name = "NAME")]
+    pub name: String,
+
+    /// Region to create the server in.
+    #[arg(long, value_name = "REGION")]
+    pub region: Option<String>,
+
+    /// Plan (size) for the server.
+    #[arg(long, value_name = "PLAN")]
+    pub plan: Option<String>,
+
+    /// Print the created server as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ServerActionCommand {
+    /// Server name or id, as printed by `codex infinity list`.
+    #[arg(value_name = "SERVER")]
+    pub server: String,
+
+    /// Skip the confirmation prompt.
+    #[arg(long)]
+    pub yes: bool,
+
+    /// Print the resulting server state as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ListCommand {
+    /// Print servers and agents as JSON.
+    #[arg(long)]
+    pub json: bool,
+
+    /// Include extra columns (ip, ssh destination, created_at).
+    #[arg(long, conflicts_with = "json")]
+    pub wide: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct AttachCommand {
+    /// Server name or id, as printed by `codex infinity list`.
+    #[arg(value_name = "SERVER")]
+    pub server: String,
+
+    /// SSH port to connect to (defaults to the server's reported port).
+    #[arg(long, value_name = "PORT")]
+    pub port: Option<u16>,
+
+    /// Identity file passed to ssh as `-i`.
+    #[arg(long = "identity-file", value_name = "FILE")]
+    pub identity_file: Option<PathBuf>,
+
+    /// Jump host passed to ssh as `-J`, for bastion-only setups.
+    #[arg(long = "jump", value_name = "HOST")]
+    pub jump: Option<String>,
+
+    /// Extra ssh options forwarded verbatim as `-o`. Repeat for multiple.
+    #[arg(short = 'o', value_name = "OPTION", action = clap::ArgAction::Append)]
+    pub ssh_options: Vec<String>,
+
+    /// Run this command instead of an interactive shell.
+    #[arg(long = "command", value_name = "CMD")]
+    pub command: Option<String>,
+}
+
+#[derive(Debug, Parser)]
+pub struct DomainsCli {
+    #[command(subcommand)]
+    pub command: DomainsCommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum DomainsCommand {
+    /// List custom domains and their DNS verification status.
+    List(DomainsListCommand),
+    /// Attach a custom domain to a project.
+    Add(DomainAddCommand),
+    /// Detach a custom domain from a project.
+    Remove(DomainRemoveCommand),
+}
+
+#[derive(Debug, Args)]
+pub struct DomainsListCommand {
+    /// Project in `owner/repo` form.
+    #[arg(value_name = "OWNER/REPO")]
+    pub project: String,
+}
+
+#[derive(Debug, Args)]
+pub struct DomainAddCommand {
+    /// Project in `owner/repo` form.
+    #[arg(value_name = "OWNER/REPO")]
+    pub project: String,
+
+    /// Domain to attach, e.g. example.com.
+    #[arg(value_name = "DOMAIN")]
+    pub domain: String,
+}
+
+#[derive(Debug, Args)]
+pub struct DomainRemoveCommand {
+    /// Project in `owner/repo` form.
+    #[arg(value_name = "OWNER/REPO")]
+    pub project: String,
+
+    /// Domain to detach.
+    #[arg(value_name = "DOMAIN")]
+    pub domain: String,
+}
+
+#[derive(Debug, Parser)]
+pub struct EnvCli {
+    #[command(subcommand)]
+    pub command: EnvCommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum EnvCommand {
+    /// List environment variables, masking secret values by default.
+    List(EnvListCommand),
+    /// Set an environment variable.
+    Set(EnvSetCommand),
+    /// Remove an environment variable.
+    Unset(EnvUnsetCommand),
+}
+
+#[derive(Debug, Args)]
+pub struct EnvListCommand {
+    /// Project in `owner/repo` form.
+    #[arg(value_name = "OWNER/REPO")]
+    pub project: String,
+
+    /// Print the variables as JSON.
+    #[arg(long)]
+    pub json: bool,
+
+    /// Print secret values in clear text instead of masking them.
+    #[arg(long = "show-values")]
+    pub show_values: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct EnvSetCommand {
+    /// Project in `owner/repo` form.
+    #[arg(value_name = "OWNER/REPO")]
+    pub project: String,
+
+    /// Variable to set, in `KEY=VALUE` form.
+    #[arg(value_name = "KEY=VALUE")]
+    pub pair: String,
+
+    /// Mark the variable as secret so listings mask its value.
+    #[arg(long)]
+    pub secret: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct EnvUnsetCommand {
+    /// Project in `owner/repo` form.
+    #[arg(value_name = "OWNER/REPO")]
+    pub project: String,
+
+    /// Variable name to remove.
+    #[arg(value_name = "KEY")]
+    pub key: String,
+}
+
+#[derive(Debug, Parser)]
+pub struct DeploysCli {
+    #[command(subcommand)]
+    pub command: DeploysCommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum DeploysCommand {
+    /// List deploys for a project, newest first.
+    List(DeploysListCommand),
+    /// Print the build/runtime logs of a deploy.
+    Logs(DeployLogsCommand),
+    /// Roll a project back to an earlier deploy.
+    Rollback(DeployRollbackCommand),
+}
+
+#[derive(Debug, Args)]
+pub struct DeploysListCommand {
+    /// Project in `owner/repo` form.
+    #[arg(value_name = "OWNER/REPO")]
+    pub project: String,
+}
+
+#[derive(Debug, Args)]
+pub struct DeployLogsCommand {
+    /// Deploy id, as printed by `deploys list`.
+    #[arg(value_name = "DEPLOY_ID")]
+    pub deploy_id: String,
+
+    /// Keep polling for new log lines until the deploy finishes.
+    #[arg(long)]
+    pub follow: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct DeployRollbackCommand {
+    /// Deploy id to roll back to, as printed by `deploys list`.
+    #[arg(value_name = "DEPLOY_ID")]
+    pub deploy_id: String,
+
+    /// Skip the confirmation prompt.
+    #[arg(long)]
+    pub yes: bool,
+}
+
+#[derive(Debug, Parser)]
+pub struct AddonsCli {
+    #[command(subcommand)]
+    pub command: AddonsCommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum AddonsCommand {
+    /// List every addon attached to a project.
+    List(AddonsListCommand),
+    /// List backups for an addon.
+    Backups(BackupsCommand),
+    /// Restore an addon from a backup.
+    Restore(RestoreCommand),
+    /// Open an interactive database shell (psql/mongosh) against an addon.
+    Shell(ShellCommand),
+    /// Show current usage metrics for an addon.
+    Metrics(MetricsCommand),
+    /// Show recent lifecycle events for an addon.
+    Events(EventsCommand),
+}
+
+#[derive(Debug, Args)]
+pub struct AddonsListCommand {
+    /// Project in `owner/repo` form.
+    #[arg(value_name = "OWNER/REPO")]
+    pub project: String,
+}
+
+#[derive(Debug, Args)]
+pub struct BackupsCommand {
+    /// Project in `owner/repo` form.
+    #[arg(value_name = "OWNER/REPO")]
+    pub project: String,
+
+    /// Addon type to operate on, as reported by the platform (e.g. postgres,
+    /// mongo, redis). Validated against the project's addon listing.
+    #[arg(
+        long = "type",
+        value_name = "TYPE",
+        required_unless_present = "addon_id"
+    )]
+    pub addon_type: Option<String>,
+
+    /// Target a specific addon by id, e.g. when a project has two addons of
+    /// the same type.
+    #[arg(long = "addon-id", value_name = "ID", conflicts_with = "addon_type")]
+    pub addon_id: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct EventsCommand {
+    /// Project in `owner/repo` form.
+    #[arg(value_name = "OWNER/REPO")]
+    pub project: String,
+
+    /// Addon type to operate on.
+    #[arg(
+        long = "type",
+        value_name = "TYPE",
+        required_unless_present = "addon_id"
+    )]
+    pub addon_type: Option<String>,
+
+    /// Target a specific addon by id, e.g. when a project has two addons of
+    /// the same type.
+    #[arg(long = "addon-id", value_name = "ID", conflicts_with = "addon_type")]
+    pub addon_id: Option<String>,
+
+    /// Show at most this many recent events.
+    #[arg(long, value_name = "N", default_value_t = 50)]
+    pub limit: usize,
+}
+
+#[derive(Debug, Args)]
+pub struct MetricsCommand {
+    /// Project in `owner/repo` form.
+    #[arg(value_name = "OWNER/REPO")]
+    pub project: String,
+
+    /// Addon type to operate on.
+    #[arg(long = "type", value_name = "TYPE")]
+    pub addon_type: String,
+
+    /// Refresh the metrics every few seconds until interrupted.
+    #[arg(long)]
+    pub watch: bool,
+
+    /// Print metrics as JSON instead of a table.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ShellCommand {
+    /// Project in `owner/repo` form.
+    #[arg(value_name = "OWNER/REPO")]
+    pub project: String,
+
+    /// Addon type to operate on.
+    #[arg(long = "type", value_name = "TYPE")]
+    pub addon_type: String,
+
+    /// Always tunnel through the project server, even if the addon looks
+    /// publicly reachable.
+    #[arg(long)]
+    pub tunnel: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct RestoreCommand {
+    /// Project in `owner/repo` form.
+    #[arg(value_name = "OWNER/REPO")]
+    pub project: String,
+
+    /// Addon type to operate on.
+    #[arg(
+        long = "type",
+        value_name = "TYPE",
+        required_unless_present = "addon_id"
+    )]
+    pub addon_type: Option<String>,
+
+    /// Target a specific addon by id, e.g. when a project has two addons of
+    /// the same type.
+    #[arg(long = "addon-id", value_name = "ID", conflicts_with = "addon_type")]
+    pub addon_id: Option<String>,
+
+    /// Object key of the backup to restore, as printed by `addons backups`.
+    #[arg(value_name = "KEY", conflicts_with_all = ["latest", "before"])]
+    pub key: Option<String>,
+
+    /// Restore the most recent completed backup.
+    #[arg(long, conflicts_with = "before")]
+    pub latest: bool,
+
+    /// Restore the newest completed backup taken at or before this timestamp
+    /// (RFC 3339, or `YYYY-MM-DD` for midnight UTC).
+    #[arg(long, value_name = "TIMESTAMP")]
+    pub before: Option<String>,
+}
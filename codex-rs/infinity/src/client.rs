@@ -0,0 +1,658 @@
+//! Thin HTTP client for the Infinity control-plane API.
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use chrono::DateTime;
+use chrono::Utc;
+use reqwest::Method;
+use reqwest::StatusCode;
+use serde::Deserialize;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+
+pub const INFINITY_API_URL_ENV: &str = "CODEX_INFINITY_API_URL";
+pub const INFINITY_API_KEY_ENV: &str = "CODEX_INFINITY_API_KEY";
+const DEFAULT_API_URL: &str = "https://api.codex-infinity.com/v1";
+
+pub struct InfinityClient {
+    http: reqwest::Client,
+    base_url: String,
+    api_key: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Addon {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub addon_type: String,
+    pub plan: String,
+    pub region: String,
+    pub status: String,
+    pub backup_enabled: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddonListResponse {
+    pub addons: Vec<Addon>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AddonBackup {
+    /// Object key identifying the backup in the platform's backup store.
+    pub key: String,
+    pub created_at: DateTime<Utc>,
+    pub size_bytes: u64,
+    pub status: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddonBackupListResponse {
+    pub backups: Vec<AddonBackup>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AddonCredentials {
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub password: String,
+    pub database: String,
+    /// Whether the addon accepts connections from outside the platform
+    /// network. When false, clients must tunnel through the project server.
+    pub publicly_reachable: bool,
+    /// SSH destination of the project server (`user@host`), usable as a
+    /// tunnel endpoint for addons that are not publicly reachable.
+    pub server_ssh: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AddonEvent {
+    pub created_at: DateTime<Utc>,
+    pub kind: String,
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddonEventListResponse {
+    pub events: Vec<AddonEvent>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddonMetrics {
+    pub connections: u64,
+    pub max_connections: u64,
+    pub storage_used_bytes: u64,
+    pub storage_limit_bytes: u64,
+    pub queries_per_second: f64,
+    pub collected_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Deploy {
+    pub id: String,
+    pub status: String,
+    pub commit_sha: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeployListResponse {
+    pub deploys: Vec<Deploy>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeployLogsResponse {
+    pub lines: Vec<String>,
+    /// Opaque cursor to resume from; `None` once the deploy has finished.
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RollbackResponse {
+    pub deploy_id: String,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvVar {
+    pub key: String,
+    pub value: String,
+    /// Secret variables have their values masked in listings.
+    #[serde(default)]
+    pub secret: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EnvListResponse {
+    pub vars: Vec<EnvVar>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Server {
+    pub id: String,
+    pub name: String,
+    pub status: String,
+    pub region: String,
+    pub ip_address: Option<String>,
+    /// SSH destination in `user@host` form, when the server is reachable.
+    pub ssh_destination: Option<String>,
+    pub ssh_port: Option<u16>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ServerListResponse {
+    pub servers: Vec<Server>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Agent {
+    pub id: String,
+    pub name: String,
+    pub status: String,
+    /// Server the agent runs on, if it has been placed.
+    pub server_id: Option<String>,
+    pub model: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AgentListResponse {
+    pub agents: Vec<Agent>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LaunchAgentRequest<'a> {
+    pub name: &'a str,
+    /// Task prompt the agent starts working on.
+    pub prompt: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<&'a str>,
+    /// Server to place the agent on; the platform picks one when omitted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_id: Option<&'a str>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AgentLogsResponse {
+    pub lines: Vec<String>,
+    /// Opaque cursor to resume from; `None` once the agent has finished.
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Domain {
+    pub hostname: String,
+    /// DNS verification status: `pending`, `verified`, or `failed`.
+    pub dns_status: String,
+    /// CNAME target the domain must point at before verification can pass.
+    pub expected_cname: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DomainListResponse {
+    pub domains: Vec<Domain>,
+}
+
+#[derive(Debug, Serialize)]
+struct AddDomainRequest<'a> {
+    hostname: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateServerRequest<'a> {
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    plan: Option<&'a str>,
+}
+
+#[derive(Debug, Serialize)]
+struct RestoreRequest<'a> {
+    key: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RestoreResponse {
+    pub restore_id: String,
+    pub status: String,
+}
+
+impl InfinityClient {
+    /// Whether an API key is resolvable without prompting, so embedders can
+    /// decide up front whether to offer Infinity operations at all.
+    pub fn is_configured() -> bool {
+        crate::auth::load_api_key().is_ok()
+    }
+
+    pub fn from_env() -> Result<Self> {
+        let base_url = std::env::var(INFINITY_API_URL_ENV)
+            .unwrap_or_else(|_| DEFAULT_API_URL.to_string())
+            .trim_end_matches('/')
+            .to_string();
+        let api_key = crate::auth::load_api_key()?;
+        Ok(Self {
+            http: reqwest::Client::new(),
+            base_url,
+            api_key,
+        })
+    }
+
+    pub async fn list_addons(&self, project: &str) -> Result<AddonListResponse> {
+        self.get_json(&format!("/projects/{project}/addons")).await
+    }
+
+    /// Resolve the single addon of `addon_type` attached to `project`. The
+    /// requested type is validated against whatever the listing reports, so
+    /// new addon types work without a CLI update.
+    pub async fn find_addon_by_type(&self, project: &str, addon_type: &str) -> Result<Addon> {
+        let listing = self.list_addons(project).await?;
+        let available: Vec<&str> = listing
+            .addons
+            .iter()
+            .map(|addon| addon.addon_type.as_str())
+            .collect();
+        let mut matching = listing
+            .addons
+            .iter()
+            .filter(|addon| addon.addon_type == addon_type);
+        let Some(addon) = matching.next() else {
+            if available.is_empty() {
+                bail!("project {project} has no addons");
+            }
+            bail!(
+                "project {project} has no {addon_type} addon (available: {})",
+                available.join(", ")
+            );
+        };
+        if matching.next().is_some() {
+            bail!(
+                "project {project} has more than one {addon_type} addon; pass --addon-id to pick one"
+            );
+        }
+        Ok(addon.clone())
+    }
+
+    pub async fn find_addon_by_id(&self, project: &str, addon_id: &str) -> Result<Addon> {
+        let listing = self.list_addons(project).await?;
+        listing
+            .addons
+            .into_iter()
+            .find(|addon| addon.id == addon_id)
+            .with_context(|| format!("project {project} has no addon with id {addon_id}"))
+    }
+
+    pub async fn list_events(&self, addon_id: &str) -> Result<Vec<AddonEvent>> {
+        let listing: AddonEventListResponse =
+            self.get_json(&format!("/addons/{addon_id}/events")).await?;
+        Ok(listing.events)
+    }
+
+    pub async fn list_servers(&self) -> Result<Vec<Server>> {
+        let listing: ServerListResponse = self.get_json("/servers").await?;
+        Ok(listing.servers)
+    }
+
+    /// Resolve a server by name or id.
+    pub async fn find_server(&self, name_or_id: &str) -> Result<Server> {
+        let servers = self.list_servers().await?;
+        servers
+            .into_iter()
+            .find(|server| server.id == name_or_id || server.name == name_or_id)
+            .with_context(|| format!("no server named {name_or_id}"))
+    }
+
+    pub async fn list_agents(&self) -> Result<Vec<Agent>> {
+        let listing: AgentListResponse = self.get_json("/agents").await?;
+        Ok(listing.agents)
+    }
+
+    pub async fn launch_agent(&self, request: &LaunchAgentRequest<'_>) -> Result<Agent> {
+        self.post_json("/agents", request).await
+    }
+
+    /// Resolve an agent by name or id.
+    pub async fn find_agent(&self, name_or_id: &str) -> Result<Agent> {
+        let agents = self.list_agents().await?;
+        agents
+            .into_iter()
+            .find(|agent| agent.id == name_or_id || agent.name == name_or_id)
+            .with_context(|| format!("no agent named {name_or_id}"))
+    }
+
+    pub async fn agent_status(&self, agent_id: &str) -> Result<Agent> {
+        self.get_json(&format!("/agents/{agent_id}")).await
+    }
+
+    pub async fn agent_logs(
+        &self,
+        agent_id: &str,
+        cursor: Option<&str>,
+    ) -> Result<AgentLogsResponse> {
+        let path = match cursor {
+            Some(cursor) => format!("/agents/{agent_id}/logs?cursor={cursor}"),
+            None => format!("/agents/{agent_id}/logs"),
+        };
+        self.get_json(&path).await
+    }
+
+    /// Cancel a running agent and return its new state. Cancelling an
+    /// already-cancelled agent is a no-op, so this opts into POST retries.
+    pub async fn cancel_agent(&self, agent_id: &str) -> Result<Agent> {
+        self.post_json_retryable(
+            &format!("/agents/{agent_id}/cancel"),
+            &serde_json::json!({}),
+        )
+        .await
+    }
+
+    pub async fn create_server(
+        &self,
+        name: &str,
+        region: Option<&str>,
+        plan: Option<&str>,
+    ) -> Result<Server> {
+        self.post_json("/servers", &CreateServerRequest { name, region, plan })
+            .await
+    }
+
+    /// Run a lifecycle action (`reboot` or `poweroff`) on a server and
+    /// return its new state.
+    pub async fn server_action(&self, server_id: &str, action: &str) -> Result<Server> {
+        self.post_json(
+            &format!("/servers/{server_id}/{action}"),
+            &serde_json::json!({}),
+        )
+        .await
+    }
+
+    pub async fn delete_server(&self, server_id: &str) -> Result<()> {
+        self.delete_empty(&format!("/servers/{server_id}")).await
+    }
+
+    pub async fn list_domains(&self, project: &str) -> Result<Vec<Domain>> {
+        let listing: DomainListResponse = self
+            .get_json(&format!("/projects/{project}/domains"))
+            .await?;
+        Ok(listing.domains)
+    }
+
+    pub async fn add_domain(&self, project: &str, hostname: &str) -> Result<Domain> {
+        self.post_json(
+            &format!("/projects/{project}/domains"),
+            &AddDomainRequest { hostname },
+        )
+        .await
+    }
+
+    pub async fn remove_domain(&self, project: &str, hostname: &str) -> Result<()> {
+        self.delete_empty(&format!("/projects/{project}/domains/{hostname}"))
+            .await
+    }
+
+    pub async fn list_env(&self, project: &str) -> Result<Vec<EnvVar>> {
+        let listing: EnvListResponse = self.get_json(&format!("/projects/{project}/env")).await?;
+        Ok(listing.vars)
+    }
+
+    pub async fn set_env(&self, project: &str, var: &EnvVar) -> Result<()> {
+        self.put_empty(&format!("/projects/{project}/env"), var)
+            .await
+    }
+
+    pub async fn unset_env(&self, project: &str, key: &str) -> Result<()> {
+        self.delete_empty(&format!("/projects/{project}/env/{key}"))
+            .await
+    }
+
+    pub async fn list_deploys(&self, project: &str) -> Result<Vec<Deploy>> {
+        let listing: DeployListResponse = self
+            .get_json(&format!("/projects/{project}/deploys"))
+            .await?;
+        Ok(listing.deploys)
+    }
+
+    pub async fn deploy_logs(
+        &self,
+        deploy_id: &str,
+        cursor: Option<&str>,
+    ) -> Result<DeployLogsResponse> {
+        let path = match cursor {
+            Some(cursor) => format!("/deploys/{deploy_id}/logs?cursor={cursor}"),
+            None => format!("/deploys/{deploy_id}/logs"),
+        };
+        self.get_json(&path).await
+    }
+
+    pub async fn rollback_deploy(&self, deploy_id: &str) -> Result<RollbackResponse> {
+        self.post_json(
+            &format!("/deploys/{deploy_id}/rollback"),
+            &serde_json::json!({}),
+        )
+        .await
+    }
+
+    pub async fn addon_metrics(&self, addon_id: &str) -> Result<AddonMetrics> {
+        self.get_json(&format!("/addons/{addon_id}/metrics")).await
+    }
+
+    pub async fn addon_credentials(&self, addon_id: &str) -> Result<AddonCredentials> {
+        self.get_json(&format!("/addons/{addon_id}/credentials"))
+            .await
+    }
+
+    pub async fn list_backups(&self, addon_id: &str) -> Result<Vec<AddonBackup>> {
+        let listing: AddonBackupListResponse = self
+            .get_json(&format!("/addons/{addon_id}/backups"))
+            .await?;
+        Ok(listing.backups)
+    }
+
+    pub async fn restore_backup(&self, addon_id: &str, key: &str) -> Result<RestoreResponse> {
+        self.post_json(
+            &format!("/addons/{addon_id}/restore"),
+            &RestoreRequest { key },
+        )
+        .await
+    }
+
+    async fn put_empty<B: Serialize>(&self, path: &str, body: &B) -> Result<()> {
+        self.execute(
+            Method::PUT,
+            path,
+            Some(serde_json::to_value(body)?),
+            Idempotency::Retryable,
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn delete_empty(&self, path: &str) -> Result<()> {
+        self.execute(Method::DELETE, path, None, Idempotency::Retryable)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let response = self
+            .execute(Method::GET, path, None, Idempotency::Retryable)
+            .await?;
+        Ok(Self::decode_json(path, response).await?)
+    }
+
+    async fn post_json<B: Serialize, T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T> {
+        let response = self
+            .execute(
+                Method::POST,
+                path,
+                Some(serde_json::to_value(body)?),
+                Idempotency::NonIdempotent,
+            )
+            .await?;
+        Ok(Self::decode_json(path, response).await?)
+    }
+
+    /// Like [`Self::post_json`], but with retries: for POSTs whose effect is
+    /// safe to repeat (e.g. cancelling an agent that may already be
+    /// cancelled). Creates and other one-shot actions must stay on
+    /// [`Self::post_json`].
+    async fn post_json_retryable<B: Serialize, T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T> {
+        let response = self
+            .execute(
+                Method::POST,
+                path,
+                Some(serde_json::to_value(body)?),
+                Idempotency::Retryable,
+            )
+            .await?;
+        Ok(Self::decode_json(path, response).await?)
+    }
+
+    /// Send one request. Idempotent requests retry transport failures and
+    /// retryable statuses (429 and 5xx) with exponential backoff, honouring
+    /// `Retry-After` when the server provides one. Non-idempotent requests
+    /// are never resent: a timed-out `POST /servers` may have succeeded
+    /// upstream, and retrying it would provision a second server.
+    async fn execute(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<serde_json::Value>,
+        idempotency: Idempotency,
+    ) -> Result<reqwest::Response, InfinityError> {
+        let url = format!("{}{path}", self.base_url);
+        let mut attempt = 0;
+        loop {
+            let mut request = self
+                .http
+                .request(method.clone(), &url)
+                .bearer_auth(&self.api_key);
+            if let Some(body) = &body {
+                request = request.json(body);
+            }
+            let error = match request.send().await {
+                Ok(response) if response.status().is_success() => return Ok(response),
+                Ok(response) => {
+                    let status = response.status();
+                    let retry_after = parse_retry_after(response.headers());
+                    let body = response.text().await.unwrap_or_default();
+                    InfinityError::Status {
+                        path: path.to_string(),
+                        status,
+                        body,
+                        retry_after,
+                    }
+                }
+                Err(source) => InfinityError::Network {
+                    method: method.clone(),
+                    url: url.clone(),
+                    source,
+                },
+            };
+            if attempt >= MAX_ATTEMPTS
+                || idempotency == Idempotency::NonIdempotent
+                || !error.is_retryable()
+            {
+                return Err(error);
+            }
+            attempt += 1;
+            let delay = error
+                .retry_after()
+                .unwrap_or_else(|| codex_client::backoff(RETRY_BASE_DELAY, attempt));
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    async fn decode_json<T: DeserializeOwned>(
+        path: &str,
+        response: reqwest::Response,
+    ) -> Result<T, InfinityError> {
+        response
+            .json::<T>()
+            .await
+            .map_err(|source| InfinityError::Decode {
+                path: path.to_string(),
+                source,
+            })
+    }
+}
+
+const MAX_ATTEMPTS: u64 = 3;
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Whether [`InfinityClient::execute`] may resend a failed request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Idempotency {
+    /// Repeating the request cannot change the outcome; retryable errors
+    /// retry.
+    Retryable,
+    /// Repeating the request could apply its effect twice; the first error
+    /// is returned as-is.
+    NonIdempotent,
+}
+
+/// Errors from the Infinity control-plane API, split by what callers (and the
+/// retry loop) can do about them.
+#[derive(Debug, thiserror::Error)]
+pub enum InfinityError {
+    #[error("{path} returned {status}: {body}")]
+    Status {
+        path: String,
+        status: StatusCode,
+        body: String,
+        retry_after: Option<Duration>,
+    },
+    #[error("{method} {url} failed: {source}")]
+    Network {
+        method: Method,
+        url: String,
+        source: reqwest::Error,
+    },
+    #[error("failed to decode response from {path}: {source}")]
+    Decode {
+        path: String,
+        source: reqwest::Error,
+    },
+}
+
+impl InfinityError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::Status { status, .. } => {
+                *status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+            }
+            Self::Network { source, .. } => source.is_timeout() || source.is_connect(),
+            Self::Decode { .. } => false,
+        }
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::Status { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
+fn parse_retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    let seconds: u64 = headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(Duration::from_secs(seconds))
+}
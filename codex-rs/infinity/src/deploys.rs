@@ -0,0 +1,79 @@
+use anyhow::Result;
+use anyhow::bail;
+use std::io::IsTerminal;
+use std::io::Write;
+use std::time::Duration;
+
+use crate::cli::DeployLogsCommand;
+use crate::cli::DeployRollbackCommand;
+use crate::cli::DeploysListCommand;
+use crate::client::InfinityClient;
+
+const LOGS_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+pub async fn run_list(cmd: DeploysListCommand) -> Result<()> {
+    let client = InfinityClient::from_env()?;
+    let deploys = client.list_deploys(&cmd.project).await?;
+    if deploys.is_empty() {
+        println!("project {} has no deploys", cmd.project);
+        return Ok(());
+    }
+    println!("{:<24}  {:<10}  {:<10}  CREATED", "ID", "STATUS", "COMMIT");
+    for deploy in &deploys {
+        let short_sha = deploy.commit_sha.get(..8).unwrap_or(&deploy.commit_sha);
+        println!(
+            "{:<24}  {:<10}  {:<10}  {}",
+            deploy.id,
+            deploy.status,
+            short_sha,
+            deploy.created_at.to_rfc3339(),
+        );
+    }
+    Ok(())
+}
+
+pub async fn run_logs(cmd: DeployLogsCommand) -> Result<()> {
+    let client = InfinityClient::from_env()?;
+    let mut cursor: Option<String> = None;
+    loop {
+        let logs = client
+            .deploy_logs(&cmd.deploy_id, cursor.as_deref())
+            .await?;
+        for line in &logs.lines {
+            println!("{line}");
+        }
+        match logs.next_cursor {
+            Some(next) if cmd.follow => {
+                cursor = Some(next);
+                tokio::time::sleep(LOGS_POLL_INTERVAL).await;
+            }
+            _ => return Ok(()),
+        }
+    }
+}
+
+pub async fn run_rollback(cmd: DeployRollbackCommand) -> Result<()> {
+    if !cmd.yes && !confirm(&format!("Roll back to deploy {}?", cmd.deploy_id))? {
+        println!("aborted");
+        return Ok(());
+    }
+    let client = InfinityClient::from_env()?;
+    let rollback = client.rollback_deploy(&cmd.deploy_id).await?;
+    println!(
+        "rollback to {} started (status: {})",
+        rollback.deploy_id, rollback.status
+    );
+    Ok(())
+}
+
+/// Ask a yes/no question on stdin; defaults to "no".
+pub(crate) fn confirm(question: &str) -> Result<bool> {
+    if !std::io::stdin().is_terminal() {
+        bail!("refusing to prompt without a terminal; pass --yes to proceed");
+    }
+    print!("{question} [y/N] ");
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
@@ -0,0 +1,48 @@
+use anyhow::Result;
+
+use crate::cli::DomainAddCommand;
+use crate::cli::DomainRemoveCommand;
+use crate::cli::DomainsListCommand;
+use crate::client::InfinityClient;
+
+pub async fn run_list(cmd: DomainsListCommand) -> Result<()> {
+    let client = InfinityClient::from_env()?;
+    let domains = client.list_domains(&cmd.project).await?;
+    if domains.is_empty() {
+        println!("project {} has no custom domains", cmd.project);
+        return Ok(());
+    }
+    println!("{:<40}  {:<10}  CNAME TARGET", "DOMAIN", "DNS");
+    for domain in &domains {
+        println!(
+            "{:<40}  {:<10}  {}",
+            domain.hostname,
+            domain.dns_status,
+            domain.expected_cname.as_deref().unwrap_or("-"),
+        );
+    }
+    Ok(())
+}
+
+pub async fn run_add(cmd: DomainAddCommand) -> Result<()> {
+    let client = InfinityClient::from_env()?;
+    let domain = client.add_domain(&cmd.project, &cmd.domain).await?;
+    println!(
+        "added {} to {} (dns: {})",
+        domain.hostname, cmd.project, domain.dns_status
+    );
+    if let Some(cname) = &domain.expected_cname {
+        println!(
+            "point a CNAME record for {} at {cname} to finish verification",
+            domain.hostname
+        );
+    }
+    Ok(())
+}
+
+pub async fn run_remove(cmd: DomainRemoveCommand) -> Result<()> {
+    let client = InfinityClient::from_env()?;
+    client.remove_domain(&cmd.project, &cmd.domain).await?;
+    println!("removed {} from {}", cmd.domain, cmd.project);
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use anyhow::Result;
+use anyhow::bail;
+
+use crate::cli::EnvListCommand;
+use crate::cli::EnvSetCommand;
+use crate::cli::EnvUnsetCommand;
+use crate::client::EnvVar;
+use crate::client::InfinityClient;
+
+const MASKED_VALUE: &str = "********";
+
+pub async fn run_list(cmd: EnvListCommand) -> Result<()> {
+    let client = InfinityClient::from_env()?;
+    let mut vars = client.list_env(&cmd.project).await?;
+    vars.sort_by(|a, b| a.key.cmp(&b.key));
+    if !cmd.show_values {
+        for var in &mut vars {
+            if var.secret {
+                var.value = MASKED_VALUE.to_string();
+            }
+        }
+    }
+    if cmd.json {
+        println!("{}", serde_json::to_string_pretty(&vars)?);
+        return Ok(());
+    }
+    if vars.is_empty() {
+        println!("project {} has no environment variables", cmd.project);
+        return Ok(());
+    }
+    for var in &vars {
+        println!("{}={}", var.key, var.value);
+    }
+    Ok(())
+}
+
+pub async fn run_set(cmd: EnvSetCommand) -> Result<()> {
+    let (key, value) = parse_pair(&cmd.pair)?;
+    let client = InfinityClient::from_env()?;
+    let var = EnvVar {
+        key: key.to_string(),
+        value: value.to_string(),
+        secret: cmd.secret,
+    };
+    client.set_env(&cmd.project, &var).await?;
+    println!("set {key} on {}", cmd.project);
+    Ok(())
+}
+
+pub async fn run_unset(cmd: EnvUnsetCommand) -> Result<()> {
+    let client = InfinityClient::from_env()?;
+    client.unset_env(&cmd.project, &cmd.key).await?;
+    println!("unset {} on {}", cmd.key, cmd.project);
+    Ok(())
+}
+
+fn parse_pair(pair: &str) -> Result<(&str, &str)> {
+    match pair.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key, value)),
+        _ => bail!("expected KEY=VALUE, got `{pair}`"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_pair_splits_on_first_equals() {
+        let (key, value) = parse_pair("DATABASE_URL=postgres://x?a=b").expect("valid pair");
+        assert_eq!(key, "DATABASE_URL");
+        assert_eq!(value, "postgres://x?a=b");
+    }
+
+    #[test]
+    fn parse_pair_rejects_missing_equals_or_key() {
+        assert!(parse_pair("JUST_A_KEY").is_err());
+        assert!(parse_pair("=value").is_err());
+    }
+}
@@ -0,0 +1,56 @@
+//! `codex infinity` - CLI for the Codex Infinity hosting platform.
+//!
+//! Talks to the Infinity control-plane API to manage projects and the
+//! database addons attached to them.
+
+mod addons;
+mod auth;
+mod cli;
+mod client;
+mod deploys;
+mod domains;
+mod env;
+mod servers;
+mod shell;
+
+pub use cli::Cli;
+pub use client::Agent;
+pub use client::AgentLogsResponse;
+pub use client::InfinityClient;
+pub use client::LaunchAgentRequest;
+
+pub async fn run_main(cli: Cli) -> anyhow::Result<()> {
+    match cli.command {
+        cli::Command::Addons(addons_cli) => match addons_cli.command {
+            cli::AddonsCommand::List(cmd) => addons::run_list(cmd).await,
+            cli::AddonsCommand::Backups(cmd) => addons::run_backups(cmd).await,
+            cli::AddonsCommand::Restore(cmd) => addons::run_restore(cmd).await,
+            cli::AddonsCommand::Shell(cmd) => shell::run_shell(cmd).await,
+            cli::AddonsCommand::Metrics(cmd) => addons::run_metrics(cmd).await,
+            cli::AddonsCommand::Events(cmd) => addons::run_events(cmd).await,
+        },
+        cli::Command::Deploys(deploys_cli) => match deploys_cli.command {
+            cli::DeploysCommand::List(cmd) => deploys::run_list(cmd).await,
+            cli::DeploysCommand::Logs(cmd) => deploys::run_logs(cmd).await,
+            cli::DeploysCommand::Rollback(cmd) => deploys::run_rollback(cmd).await,
+        },
+        cli::Command::Env(env_cli) => match env_cli.command {
+            cli::EnvCommand::List(cmd) => env::run_list(cmd).await,
+            cli::EnvCommand::Set(cmd) => env::run_set(cmd).await,
+            cli::EnvCommand::Unset(cmd) => env::run_unset(cmd).await,
+        },
+        cli::Command::Domains(domains_cli) => match domains_cli.command {
+            cli::DomainsCommand::List(cmd) => domains::run_list(cmd).await,
+            cli::DomainsCommand::Add(cmd) => domains::run_add(cmd).await,
+            cli::DomainsCommand::Remove(cmd) => domains::run_remove(cmd).await,
+        },
+        cli::Command::Login => auth::run_login().await,
+        cli::Command::Logout => auth::run_logout().await,
+        cli::Command::List(cmd) => servers::run_list(cmd).await,
+        cli::Command::Attach(cmd) => servers::run_attach(cmd).await,
+        cli::Command::Create(cmd) => servers::run_create(cmd).await,
+        cli::Command::Reboot(cmd) => servers::run_action(cmd, "reboot").await,
+        cli::Command::Poweroff(cmd) => servers::run_action(cmd, "poweroff").await,
+        cli::Command::Delete(cmd) => servers::run_delete(cmd).await,
+    }
+}
@@ -0,0 +1,190 @@
+//! Cloud server commands: attach over SSH plus lifecycle management.
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use std::process::Command;
+
+use crate::cli::AttachCommand;
+use crate::cli::CreateServerCommand;
+use crate::cli::ListCommand;
+use crate::cli::ServerActionCommand;
+use crate::client::InfinityClient;
+use crate::client::Server;
+use crate::deploys::confirm;
+
+pub async fn run_attach(cmd: AttachCommand) -> Result<()> {
+    let client = InfinityClient::from_env()?;
+    let server = client.find_server(&cmd.server).await?;
+    let destination = ssh_destination(&server)?;
+
+    let mut ssh = Command::new("ssh");
+    if let Some(port) = cmd.port.or(server.ssh_port) {
+        ssh.arg("-p").arg(port.to_string());
+    }
+    if let Some(identity_file) = &cmd.identity_file {
+        ssh.arg("-i").arg(identity_file);
+    }
+    if let Some(jump) = &cmd.jump {
+        ssh.arg("-J").arg(jump);
+    }
+    for option in &cmd.ssh_options {
+        ssh.arg("-o").arg(option);
+    }
+    ssh.arg(&destination);
+    if let Some(command) = &cmd.command {
+        ssh.arg(command);
+    }
+
+    let status = ssh
+        .status()
+        .with_context(|| format!("failed to launch ssh to {destination}"))?;
+    if !status.success() {
+        bail!("ssh to {destination} exited with {status}");
+    }
+    Ok(())
+}
+
+pub async fn run_list(cmd: ListCommand) -> Result<()> {
+    let client = InfinityClient::from_env()?;
+    let servers = client.list_servers().await?;
+    let agents = client.list_agents().await?;
+
+    if cmd.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "servers": servers,
+                "agents": agents,
+            }))?
+        );
+        return Ok(());
+    }
+
+    if servers.is_empty() {
+        println!("no servers");
+    } else if cmd.wide {
+        println!(
+            "{:<24}  {:<20}  {:<10}  {:<12}  {:<16}  {:<28}  CREATED",
+            "ID", "NAME", "STATUS", "REGION", "IP", "SSH"
+        );
+        for server in &servers {
+            println!(
+                "{:<24}  {:<20}  {:<10}  {:<12}  {:<16}  {:<28}  {}",
+                server.id,
+                server.name,
+                server.status,
+                server.region,
+                server.ip_address.as_deref().unwrap_or("-"),
+                server.ssh_destination.as_deref().unwrap_or("-"),
+                server.created_at.to_rfc3339(),
+            );
+        }
+    } else {
+        println!("{:<24}  {:<20}  {:<10}  REGION", "ID", "NAME", "STATUS");
+        for server in &servers {
+            println!(
+                "{:<24}  {:<20}  {:<10}  {}",
+                server.id, server.name, server.status, server.region
+            );
+        }
+    }
+
+    println!();
+    if agents.is_empty() {
+        println!("no agents");
+    } else if cmd.wide {
+        println!(
+            "{:<24}  {:<20}  {:<10}  {:<24}  {:<20}  CREATED",
+            "AGENT", "NAME", "STATUS", "SERVER", "MODEL"
+        );
+        for agent in &agents {
+            println!(
+                "{:<24}  {:<20}  {:<10}  {:<24}  {:<20}  {}",
+                agent.id,
+                agent.name,
+                agent.status,
+                agent.server_id.as_deref().unwrap_or("-"),
+                agent.model.as_deref().unwrap_or("-"),
+                agent.created_at.to_rfc3339(),
+            );
+        }
+    } else {
+        println!("{:<24}  {:<20}  STATUS", "AGENT", "NAME");
+        for agent in &agents {
+            println!("{:<24}  {:<20}  {}", agent.id, agent.name, agent.status);
+        }
+    }
+    Ok(())
+}
+
+pub async fn run_create(cmd: CreateServerCommand) -> Result<()> {
+    let client = InfinityClient::from_env()?;
+    let server = client
+        .create_server(&cmd.name, cmd.region.as_deref(), cmd.plan.as_deref())
+        .await?;
+    if cmd.json {
+        println!("{}", serde_json::to_string_pretty(&server)?);
+    } else {
+        println!(
+            "created server {} ({}) in {} (status: {})",
+            server.name, server.id, server.region, server.status
+        );
+    }
+    Ok(())
+}
+
+pub async fn run_action(cmd: ServerActionCommand, action: &str) -> Result<()> {
+    let client = InfinityClient::from_env()?;
+    let server = client.find_server(&cmd.server).await?;
+    if !cmd.yes && !confirm(&format!("{action} server {} ({})?", server.name, server.id))? {
+        println!("aborted");
+        return Ok(());
+    }
+    let updated = client.server_action(&server.id, action).await?;
+    if cmd.json {
+        println!("{}", serde_json::to_string_pretty(&updated)?);
+    } else {
+        println!(
+            "{action} requested for {} (status: {})",
+            updated.name, updated.status
+        );
+    }
+    Ok(())
+}
+
+pub async fn run_delete(cmd: ServerActionCommand) -> Result<()> {
+    let client = InfinityClient::from_env()?;
+    let server = client.find_server(&cmd.server).await?;
+    if !cmd.yes
+        && !confirm(&format!(
+            "Permanently delete server {} ({})? This cannot be undone.",
+            server.name, server.id
+        ))?
+    {
+        println!("aborted");
+        return Ok(());
+    }
+    client.delete_server(&server.id).await?;
+    if cmd.json {
+        println!("{}", serde_json::json!({ "deleted": server.id }));
+    } else {
+        println!("deleted server {} ({})", server.name, server.id);
+    }
+    Ok(())
+}
+
+fn ssh_destination(server: &Server) -> Result<String> {
+    if let Some(destination) = &server.ssh_destination {
+        return Ok(destination.clone());
+    }
+    if let Some(ip) = &server.ip_address {
+        return Ok(format!("root@{ip}"));
+    }
+    bail!(
+        "server {} ({}) has no SSH destination; is it still {}?",
+        server.name,
+        server.id,
+        server.status
+    )
+}
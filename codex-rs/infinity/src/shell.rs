@@ -0,0 +1,144 @@
+//! `addons shell`: open an interactive psql/mongosh session against an addon.
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::process::Child;
+use std::process::Command;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::cli::ShellCommand;
+use crate::client::AddonCredentials;
+use crate::client::InfinityClient;
+
+const TUNNEL_READY_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub async fn run_shell(cmd: ShellCommand) -> Result<()> {
+    let client = InfinityClient::from_env()?;
+    let addon = client
+        .find_addon_by_type(&cmd.project, &cmd.addon_type)
+        .await?;
+    let credentials = client.addon_credentials(&addon.id).await?;
+
+    let mut tunnel = None;
+    let (host, port) = if credentials.publicly_reachable && !cmd.tunnel {
+        (credentials.host.clone(), credentials.port)
+    } else {
+        let Some(server_ssh) = credentials.server_ssh.as_deref() else {
+            bail!(
+                "{} addon {} is not publicly reachable and the project has no server to tunnel through",
+                addon.addon_type,
+                addon.id
+            );
+        };
+        let local_port = pick_local_port()?;
+        tunnel = Some(spawn_tunnel(server_ssh, &credentials, local_port)?);
+        ("127.0.0.1".to_string(), local_port)
+    };
+
+    let status = shell_command(&cmd.addon_type, &credentials, &host, port)?
+        .status()
+        .with_context(|| format!("failed to launch {} shell", shell_binary(&cmd.addon_type)))?;
+
+    if let Some(mut tunnel) = tunnel {
+        let _ = tunnel.kill();
+        let _ = tunnel.wait();
+    }
+
+    if !status.success() {
+        bail!("{} exited with {status}", shell_binary(&cmd.addon_type));
+    }
+    Ok(())
+}
+
+fn shell_binary(addon_type: &str) -> &'static str {
+    match addon_type {
+        "mongo" => "mongosh",
+        "redis" | "valkey" => "redis-cli",
+        _ => "psql",
+    }
+}
+
+fn shell_command(
+    addon_type: &str,
+    credentials: &AddonCredentials,
+    host: &str,
+    port: u16,
+) -> Result<Command> {
+    let mut command = Command::new(shell_binary(addon_type));
+    match addon_type {
+        "mongo" => {
+            command.arg(format!(
+                "mongodb://{}:{}@{host}:{port}/{}",
+                credentials.username, credentials.password, credentials.database
+            ));
+        }
+        "postgres" => {
+            command
+                .arg("-h")
+                .arg(host)
+                .arg("-p")
+                .arg(port.to_string())
+                .arg("-U")
+                .arg(&credentials.username)
+                .arg("-d")
+                .arg(&credentials.database)
+                .env("PGPASSWORD", &credentials.password);
+        }
+        "redis" | "valkey" => {
+            command
+                .arg("-h")
+                .arg(host)
+                .arg("-p")
+                .arg(port.to_string())
+                .env("REDISCLI_AUTH", &credentials.password);
+        }
+        other => bail!("no interactive shell available for {other} addons"),
+    }
+    Ok(command)
+}
+
+fn pick_local_port() -> Result<u16> {
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .context("failed to reserve a local port for the tunnel")?;
+    let port = listener.local_addr()?.port();
+    drop(listener);
+    Ok(port)
+}
+
+/// Forward `local_port` to the addon through the project server and wait for
+/// the forwarded port to accept connections.
+fn spawn_tunnel(
+    server_ssh: &str,
+    credentials: &AddonCredentials,
+    local_port: u16,
+) -> Result<Child> {
+    let forward = format!("{local_port}:{}:{}", credentials.host, credentials.port);
+    let mut child = Command::new("ssh")
+        .arg("-N")
+        .arg("-o")
+        .arg("ExitOnForwardFailure=yes")
+        .arg("-L")
+        .arg(&forward)
+        .arg(server_ssh)
+        .spawn()
+        .with_context(|| format!("failed to start ssh tunnel via {server_ssh}"))?;
+
+    let deadline = Instant::now() + TUNNEL_READY_TIMEOUT;
+    loop {
+        if TcpStream::connect(("127.0.0.1", local_port)).is_ok() {
+            return Ok(child);
+        }
+        if let Some(status) = child.try_wait()? {
+            bail!("ssh tunnel via {server_ssh} exited with {status}");
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            bail!("timed out waiting for ssh tunnel via {server_ssh}");
+        }
+        std::thread::sleep(Duration::from_millis(200));
+    }
+}
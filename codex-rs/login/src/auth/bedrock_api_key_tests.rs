@@ -20,6 +20,7 @@ fn api_key_auth() -> AuthDotJson {
         agent_identity: None,
         personal_access_token: None,
         bedrock_api_key: None,
+        provider_tokens: None,
     }
 }
 
@@ -31,6 +32,7 @@ fn bedrock_only_auth() -> AuthDotJson {
         last_refresh: None,
         agent_identity: None,
         personal_access_token: None,
+        provider_tokens: None,
         bedrock_api_key: Some(bedrock_auth()),
     }
 }
@@ -75,6 +77,7 @@ async fn login_with_bedrock_api_key_replaces_openai_auth() -> anyhow::Result<()>
         last_refresh: None,
         agent_identity: None,
         personal_access_token: None,
+        provider_tokens: None,
         bedrock_api_key: Some(bedrock_auth()),
     };
     assert_eq!(loaded, expected);
@@ -25,6 +25,7 @@ use codex_agent_identity::ChatGptEnvironment;
 use codex_protocol::auth::AuthMode;
 use codex_protocol::config_types::ForcedLoginMethod;
 use codex_protocol::config_types::ModelProviderAuthInfo;
+use codex_protocol::config_types::ModelProviderOAuthInfo;
 
 use super::access_token::CodexAccessToken;
 use super::access_token::classify_codex_access_token;
@@ -37,6 +38,8 @@ use super::agent_identity::register_managed_chatgpt_agent_identity;
 use super::agent_identity::require_agent_identity_authapi_base_url;
 use super::agent_identity::verified_record_from_jwt;
 use super::external_bearer::BearerTokenRefresher;
+use super::provider_key::ProviderKeyRefresher;
+use super::provider_key::ProviderKeySource;
 use super::revoke::revoke_auth_tokens;
 use crate::auth::AuthHeaders;
 pub use crate::auth::agent_identity::AgentIdentityAuth;
@@ -53,6 +56,7 @@ use crate::auth::util::try_parse_error_message;
 use crate::default_client::create_client;
 use crate::default_client::create_default_auth_client;
 use crate::outbound_proxy::AuthRouteConfig;
+use crate::provider_oauth::ProviderOAuthRefresher;
 use crate::token_data::TokenData;
 use crate::token_data::parse_chatgpt_jwt_claims;
 use crate::token_data::parse_jwt_expiration;
@@ -712,6 +716,7 @@ impl CodexAuth {
             agent_identity: None,
             personal_access_token: None,
             bedrock_api_key: None,
+            provider_tokens: None,
         };
 
         let state = ChatgptAuthState {
@@ -916,6 +921,7 @@ pub fn login_with_api_key(
         agent_identity: None,
         personal_access_token: None,
         bedrock_api_key: None,
+        provider_tokens: None,
     };
     save_auth(
         codex_home,
@@ -949,6 +955,7 @@ pub async fn login_with_access_token(
                 agent_identity: None,
                 personal_access_token: Some(access_token.to_string()),
                 bedrock_api_key: None,
+                provider_tokens: None,
             }
         }
         CodexAccessToken::AgentIdentityJwt(jwt) => {
@@ -965,6 +972,7 @@ pub async fn login_with_access_token(
                 agent_identity: Some(AgentIdentityStorage::Jwt(jwt.to_string())),
                 personal_access_token: None,
                 bedrock_api_key: None,
+                provider_tokens: None,
             }
         }
     };
@@ -1485,6 +1493,7 @@ impl AuthDotJson {
             agent_identity: None,
             personal_access_token: None,
             bedrock_api_key: None,
+            provider_tokens: None,
         })
     }
 
@@ -1993,6 +2002,66 @@ impl AuthManager {
         })
     }
 
+    /// Builds a provider-scoped manager serving an API key fetched from the
+    /// provider's `key_command` or `key_file`. The key is cached and
+    /// re-fetched on the 401 retry path so rotated secrets are picked up.
+    pub fn provider_key_only(source: ProviderKeySource) -> Arc<Self> {
+        let (auth_change_tx, _auth_change_rx) = watch::channel(0);
+        Arc::new(Self {
+            codex_home: PathBuf::from("non-existent"),
+            inner: RwLock::new(CachedAuth {
+                auth: None,
+                permanent_refresh_failure: None,
+            }),
+            auth_change_tx,
+            enable_codex_api_key_env: false,
+            auth_credentials_store_mode: AuthCredentialsStoreMode::File,
+            keyring_backend_kind: AuthKeyringBackendKind::default(),
+            forced_chatgpt_workspace_id: RwLock::new(None),
+            chatgpt_base_url: None,
+            agent_identity_authapi_base_url: default_agent_identity_authapi_base_url(),
+            refresh_lock: Semaphore::new(/*permits*/ 1),
+            agent_identity_lock: Semaphore::new(/*permits*/ 1),
+            agent_identity_bootstrap_cooldown: Mutex::default(),
+            external_auth: RwLock::new(Some(
+                Arc::new(ProviderKeyRefresher::new(source)) as Arc<dyn ExternalAuth>
+            )),
+            auth_route_config: None,
+        })
+    }
+
+    /// Builds a provider-scoped manager serving OAuth tokens stored by
+    /// `codex login --provider <id>`, reusing this manager's auth storage
+    /// location and backend.
+    pub fn provider_oauth_only(&self, config: ModelProviderOAuthInfo) -> Arc<Self> {
+        let refresher = ProviderOAuthRefresher::new(
+            self.codex_home.clone(),
+            self.auth_credentials_store_mode,
+            self.keyring_backend_kind,
+            config,
+        );
+        let (auth_change_tx, _auth_change_rx) = watch::channel(0);
+        Arc::new(Self {
+            codex_home: self.codex_home.clone(),
+            inner: RwLock::new(CachedAuth {
+                auth: None,
+                permanent_refresh_failure: None,
+            }),
+            auth_change_tx,
+            enable_codex_api_key_env: false,
+            auth_credentials_store_mode: self.auth_credentials_store_mode,
+            keyring_backend_kind: self.keyring_backend_kind,
+            forced_chatgpt_workspace_id: RwLock::new(None),
+            chatgpt_base_url: None,
+            agent_identity_authapi_base_url: default_agent_identity_authapi_base_url(),
+            refresh_lock: Semaphore::new(/*permits*/ 1),
+            agent_identity_lock: Semaphore::new(/*permits*/ 1),
+            agent_identity_bootstrap_cooldown: Mutex::default(),
+            external_auth: RwLock::new(Some(Arc::new(refresher) as Arc<dyn ExternalAuth>)),
+            auth_route_config: None,
+        })
+    }
+
     /// Current cached auth (clone) without attempting a refresh.
     pub fn auth_cached(&self) -> Option<CodexAuth> {
         self.inner
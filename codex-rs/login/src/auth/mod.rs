@@ -10,6 +10,7 @@ mod util;
 
 mod external_bearer;
 mod manager;
+mod provider_key;
 mod revoke;
 
 pub use auth_headers::AuthHeaders;
@@ -18,3 +19,4 @@ pub use bedrock_api_key::login_with_bedrock_api_key;
 pub use error::RefreshTokenFailedError;
 pub use error::RefreshTokenFailedReason;
 pub use manager::*;
+pub use provider_key::ProviderKeySource;
@@ -0,0 +1,173 @@
+use super::manager::CodexAuth;
+use super::manager::ExternalAuth;
+use super::manager::ExternalAuthFuture;
+use super::manager::ExternalAuthRefreshContext;
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::process::Command;
+use tokio::sync::Mutex;
+
+/// Maximum time to wait for a `key_command` invocation to exit successfully.
+const PROVIDER_KEY_COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Where a provider's API key is fetched from when it is not stored in the
+/// environment (the `key_command` / `key_file` provider config options).
+#[derive(Clone, Debug)]
+pub enum ProviderKeySource {
+    /// Command (argv) that prints the key on stdout, e.g. a vault or
+    /// secrets-manager CLI.
+    Command(Vec<String>),
+    /// File whose contents, with surrounding whitespace trimmed, are the key.
+    File(PathBuf),
+}
+
+/// Serves a provider API key fetched from a [`ProviderKeySource`].
+///
+/// The key is cached for the lifetime of the session and re-fetched on the
+/// 401 retry path, so rotated secrets are picked up without restarting.
+#[derive(Clone)]
+pub(crate) struct ProviderKeyRefresher {
+    state: Arc<ProviderKeyState>,
+}
+
+impl ProviderKeyRefresher {
+    pub(crate) fn new(source: ProviderKeySource) -> Self {
+        Self {
+            state: Arc::new(ProviderKeyState {
+                source,
+                cached_key: Mutex::new(None),
+            }),
+        }
+    }
+
+    #[expect(
+        clippy::await_holding_invalid_type,
+        reason = "provider key cache misses intentionally hold cached_key across the fetch to avoid duplicate fetches"
+    )]
+    async fn resolve(&self) -> io::Result<CodexAuth> {
+        let api_key = {
+            let mut cached = self.state.cached_key.lock().await;
+            if let Some(api_key) = cached.as_ref() {
+                return Ok(CodexAuth::from_api_key(api_key));
+            }
+
+            let api_key = fetch_provider_key(&self.state.source).await?;
+            *cached = Some(api_key.clone());
+            api_key
+        };
+        Ok(CodexAuth::from_api_key(api_key.as_str()))
+    }
+
+    async fn refresh(&self, _context: ExternalAuthRefreshContext) -> io::Result<CodexAuth> {
+        let api_key = fetch_provider_key(&self.state.source).await?;
+        let mut cached = self.state.cached_key.lock().await;
+        *cached = Some(api_key.clone());
+        Ok(CodexAuth::from_api_key(api_key.as_str()))
+    }
+}
+
+impl ExternalAuth for ProviderKeyRefresher {
+    fn resolve(&self) -> ExternalAuthFuture<'_, CodexAuth> {
+        Box::pin(ProviderKeyRefresher::resolve(self))
+    }
+
+    fn refresh(&self, context: ExternalAuthRefreshContext) -> ExternalAuthFuture<'_, CodexAuth> {
+        Box::pin(ProviderKeyRefresher::refresh(self, context))
+    }
+}
+
+impl fmt::Debug for ProviderKeyRefresher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProviderKeyRefresher")
+            .finish_non_exhaustive()
+    }
+}
+
+struct ProviderKeyState {
+    source: ProviderKeySource,
+    cached_key: Mutex<Option<String>>,
+}
+
+async fn fetch_provider_key(source: &ProviderKeySource) -> io::Result<String> {
+    match source {
+        ProviderKeySource::Command(argv) => run_provider_key_command(argv).await,
+        ProviderKeySource::File(path) => read_provider_key_file(path).await,
+    }
+}
+
+async fn run_provider_key_command(argv: &[String]) -> io::Result<String> {
+    let Some((program, args)) = argv.split_first() else {
+        return Err(io::Error::other("provider key_command must not be empty"));
+    };
+    let mut command = Command::new(program);
+    command
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    let output = tokio::time::timeout(PROVIDER_KEY_COMMAND_TIMEOUT, command.output())
+        .await
+        .map_err(|_| {
+            io::Error::other(format!(
+                "provider key command `{program}` timed out after {} ms",
+                PROVIDER_KEY_COMMAND_TIMEOUT.as_millis()
+            ))
+        })?
+        .map_err(|err| {
+            io::Error::other(format!(
+                "provider key command `{program}` failed to start: {err}"
+            ))
+        })?;
+
+    if !output.status.success() {
+        let status = output.status;
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        let stderr_suffix = if stderr.is_empty() {
+            String::new()
+        } else {
+            format!(": {stderr}")
+        };
+        return Err(io::Error::other(format!(
+            "provider key command `{program}` exited with status {status}{stderr_suffix}"
+        )));
+    }
+
+    let stdout = String::from_utf8(output.stdout).map_err(|_| {
+        io::Error::other(format!(
+            "provider key command `{program}` wrote non-UTF-8 data to stdout"
+        ))
+    })?;
+    let api_key = stdout.trim().to_string();
+    if api_key.is_empty() {
+        return Err(io::Error::other(format!(
+            "provider key command `{program}` produced an empty key"
+        )));
+    }
+
+    Ok(api_key)
+}
+
+async fn read_provider_key_file(path: &Path) -> io::Result<String> {
+    let contents = tokio::fs::read_to_string(path).await.map_err(|err| {
+        io::Error::other(format!(
+            "provider key file `{}` could not be read: {err}",
+            path.display()
+        ))
+    })?;
+    let api_key = contents.trim().to_string();
+    if api_key.is_empty() {
+        return Err(io::Error::other(format!(
+            "provider key file `{}` is empty",
+            path.display()
+        )));
+    }
+
+    Ok(api_key)
+}
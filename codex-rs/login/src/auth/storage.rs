@@ -19,6 +19,7 @@ use std::sync::Mutex;
 use tracing::warn;
 
 use super::BedrockApiKeyAuth;
+use crate::provider_oauth::ProviderOAuthTokens;
 use crate::token_data::TokenData;
 use codex_agent_identity::AgentIdentityJwtClaims;
 use codex_agent_identity::decode_agent_identity_jwt;
@@ -58,6 +59,10 @@ pub struct AuthDotJson {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub bedrock_api_key: Option<BedrockApiKeyAuth>,
+
+    /// OAuth tokens for third-party providers, keyed by OAuth `client_id`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider_tokens: Option<HashMap<String, ProviderOAuthTokens>>,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
@@ -5,6 +5,7 @@ pub mod token_data;
 mod device_code_auth;
 mod outbound_proxy;
 mod pkce;
+mod provider_oauth;
 mod server;
 mod success_page;
 
@@ -14,6 +15,8 @@ pub use device_code_auth::DeviceCode;
 pub use device_code_auth::complete_device_code_login;
 pub use device_code_auth::request_device_code;
 pub use device_code_auth::run_device_code_login;
+pub use provider_oauth::ProviderOAuthTokens;
+pub use provider_oauth::run_provider_device_login;
 pub use server::LoginServer;
 pub use server::ServerOptions;
 pub use server::ShutdownHandle;
@@ -39,6 +42,7 @@ pub use auth::ExternalAuthFuture;
 pub use auth::ExternalAuthRefreshContext;
 pub use auth::ExternalAuthRefreshReason;
 pub use auth::OPENAI_API_KEY_ENV_VAR;
+pub use auth::ProviderKeySource;
 pub use auth::REFRESH_TOKEN_URL_OVERRIDE_ENV_VAR;
 pub use auth::REVOKE_TOKEN_URL_OVERRIDE_ENV_VAR;
 pub use auth::RefreshTokenError;
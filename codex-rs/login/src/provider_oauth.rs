@@ -0,0 +1,398 @@
+//! Generic OAuth2 device-authorization login for third-party providers.
+//!
+//! Providers configured with `[model_providers.<id>.oauth]` (auth_url,
+//! token_url, client_id, scopes) can be logged into via
+//! `codex login --provider <id>`. This implements the standard RFC 8628
+//! device flow rather than the Codex-specific flow in `device_code_auth`:
+//! tokens land in `auth.json` keyed by `client_id` and are refreshed with
+//! the `refresh_token` grant when they expire or a request comes back 401.
+
+use chrono::DateTime;
+use chrono::Utc;
+use codex_config::types::AuthCredentialsStoreMode;
+use codex_protocol::config_types::ModelProviderOAuthInfo;
+use http::StatusCode;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::auth::AuthKeyringBackendKind;
+use crate::auth::CodexAuth;
+use crate::auth::ExternalAuth;
+use crate::auth::ExternalAuthFuture;
+use crate::auth::ExternalAuthRefreshContext;
+use crate::auth::load_auth_dot_json;
+use crate::auth::save_auth;
+use crate::default_client::create_raw_auth_client;
+
+const DEVICE_CODE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+const REFRESH_TOKEN_GRANT_TYPE: &str = "refresh_token";
+/// Give up polling after the RFC's customary upper bound.
+const DEVICE_FLOW_MAX_WAIT: Duration = Duration::from_secs(15 * 60);
+const DEFAULT_POLL_INTERVAL_SECS: u64 = 5;
+/// Refresh this long before the recorded expiry so in-flight requests do not
+/// race the deadline.
+const EXPIRY_SKEW_SECS: i64 = 60;
+
+/// Tokens stored in `auth.json` for one provider OAuth client.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct ProviderOAuthTokens {
+    pub access_token: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl ProviderOAuthTokens {
+    fn needs_refresh(&self) -> bool {
+        self.expires_at.is_some_and(|expires_at| {
+            Utc::now() + chrono::Duration::seconds(EXPIRY_SKEW_SECS) >= expires_at
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct DeviceAuthorizationResponse {
+    device_code: String,
+    user_code: String,
+    verification_uri: String,
+    #[serde(default)]
+    verification_uri_complete: Option<String>,
+    #[serde(default)]
+    interval: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct TokenErrorResponse {
+    error: String,
+}
+
+/// Runs the device-authorization flow for `provider_id` and stores the
+/// resulting tokens in `auth.json`. Prints the verification URL and user
+/// code for the user to complete in a browser.
+pub async fn run_provider_device_login(
+    codex_home: &Path,
+    provider_id: &str,
+    oauth: &ModelProviderOAuthInfo,
+    auth_credentials_store_mode: AuthCredentialsStoreMode,
+    keyring_backend_kind: AuthKeyringBackendKind,
+) -> io::Result<()> {
+    let client = create_raw_auth_client(&oauth.auth_url, None).map_err(io::Error::other)?;
+
+    let mut body = format!("client_id={}", urlencoding::encode(&oauth.client_id));
+    if !oauth.scopes.is_empty() {
+        let scope = oauth.scopes.join(" ");
+        body.push_str(&format!("&scope={}", urlencoding::encode(&scope)));
+    }
+    let resp = client
+        .post(&oauth.auth_url)
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .header("Accept", "application/json")
+        .body(body)
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    if !resp.status().is_success() {
+        return Err(io::Error::other(format!(
+            "device authorization request to {url} failed with status {status}",
+            url = oauth.auth_url,
+            status = resp.status(),
+        )));
+    }
+    let authorization: DeviceAuthorizationResponse = resp.json().await.map_err(io::Error::other)?;
+
+    let verification_url = authorization
+        .verification_uri_complete
+        .as_deref()
+        .unwrap_or(&authorization.verification_uri);
+    eprintln!("\nTo log in to the `{provider_id}` provider, open this link in your browser:");
+    eprintln!("  {verification_url}");
+    eprintln!(
+        "\nand enter this one-time code: {code}\n",
+        code = authorization.user_code
+    );
+
+    let tokens = poll_for_tokens(oauth, &authorization).await?;
+    store_provider_tokens(
+        codex_home,
+        &oauth.client_id,
+        tokens,
+        auth_credentials_store_mode,
+        keyring_backend_kind,
+    )
+}
+
+/// Polls the token endpoint until the user approves, the flow fails, or the
+/// RFC's 15-minute budget runs out.
+async fn poll_for_tokens(
+    oauth: &ModelProviderOAuthInfo,
+    authorization: &DeviceAuthorizationResponse,
+) -> io::Result<ProviderOAuthTokens> {
+    let client = create_raw_auth_client(&oauth.token_url, None).map_err(io::Error::other)?;
+    let mut interval = authorization
+        .interval
+        .unwrap_or(DEFAULT_POLL_INTERVAL_SECS)
+        .max(1);
+    let start = Instant::now();
+
+    loop {
+        if start.elapsed() >= DEVICE_FLOW_MAX_WAIT {
+            return Err(io::Error::other(
+                "device authorization timed out after 15 minutes",
+            ));
+        }
+        tokio::time::sleep(Duration::from_secs(interval)).await;
+
+        let body = format!(
+            "grant_type={grant}&device_code={code}&client_id={client_id}",
+            grant = urlencoding::encode(DEVICE_CODE_GRANT_TYPE),
+            code = urlencoding::encode(&authorization.device_code),
+            client_id = urlencoding::encode(&oauth.client_id),
+        );
+        let resp = client
+            .post(&oauth.token_url)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .header("Accept", "application/json")
+            .body(body)
+            .send()
+            .await
+            .map_err(io::Error::other)?;
+
+        let status = resp.status();
+        let text = resp.text().await.map_err(io::Error::other)?;
+        if status.is_success() {
+            let token: TokenResponse = serde_json::from_str(&text).map_err(io::Error::other)?;
+            return Ok(tokens_from_response(token));
+        }
+
+        // RFC 8628 reports pending/slow-down as OAuth errors with a 4xx
+        // status; anything else is terminal.
+        let error = serde_json::from_str::<TokenErrorResponse>(&text)
+            .map(|resp| resp.error)
+            .unwrap_or_default();
+        match error.as_str() {
+            "authorization_pending" => continue,
+            "slow_down" => {
+                interval += 5;
+                continue;
+            }
+            "expired_token" => {
+                return Err(io::Error::other(
+                    "the device code expired before the login was approved",
+                ));
+            }
+            "access_denied" => {
+                return Err(io::Error::other("the login request was denied"));
+            }
+            _ if status == StatusCode::BAD_REQUEST && error.is_empty() => {
+                return Err(io::Error::other(format!(
+                    "token request failed with status {status}: {text}"
+                )));
+            }
+            other => {
+                return Err(io::Error::other(format!(
+                    "token request failed with status {status}: {other}"
+                )));
+            }
+        }
+    }
+}
+
+/// Exchanges a refresh token for a new access token.
+async fn refresh_tokens(
+    oauth: &ModelProviderOAuthInfo,
+    refresh_token: &str,
+) -> io::Result<ProviderOAuthTokens> {
+    let client = create_raw_auth_client(&oauth.token_url, None).map_err(io::Error::other)?;
+    let body = format!(
+        "grant_type={grant}&refresh_token={token}&client_id={client_id}",
+        grant = urlencoding::encode(REFRESH_TOKEN_GRANT_TYPE),
+        token = urlencoding::encode(refresh_token),
+        client_id = urlencoding::encode(&oauth.client_id),
+    );
+    let resp = client
+        .post(&oauth.token_url)
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .header("Accept", "application/json")
+        .body(body)
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    if !resp.status().is_success() {
+        return Err(io::Error::other(format!(
+            "provider token refresh failed with status {status}",
+            status = resp.status(),
+        )));
+    }
+    let token: TokenResponse = resp.json().await.map_err(io::Error::other)?;
+    let mut tokens = tokens_from_response(token);
+    // Providers that do not rotate refresh tokens omit the field; keep the
+    // old one so the next refresh still works.
+    if tokens.refresh_token.is_none() {
+        tokens.refresh_token = Some(refresh_token.to_string());
+    }
+    Ok(tokens)
+}
+
+fn tokens_from_response(token: TokenResponse) -> ProviderOAuthTokens {
+    ProviderOAuthTokens {
+        access_token: token.access_token,
+        refresh_token: token.refresh_token,
+        expires_at: token
+            .expires_in
+            .and_then(|secs| i64::try_from(secs).ok())
+            .map(|secs| Utc::now() + chrono::Duration::seconds(secs)),
+    }
+}
+
+fn store_provider_tokens(
+    codex_home: &Path,
+    client_id: &str,
+    tokens: ProviderOAuthTokens,
+    auth_credentials_store_mode: AuthCredentialsStoreMode,
+    keyring_backend_kind: AuthKeyringBackendKind,
+) -> io::Result<()> {
+    let mut auth_dot_json = load_auth_dot_json(
+        codex_home,
+        auth_credentials_store_mode,
+        keyring_backend_kind,
+    )?
+    .unwrap_or_else(empty_auth_dot_json);
+    auth_dot_json
+        .provider_tokens
+        .get_or_insert_with(HashMap::new)
+        .insert(client_id.to_string(), tokens);
+    save_auth(
+        codex_home,
+        &auth_dot_json,
+        auth_credentials_store_mode,
+        keyring_backend_kind,
+    )
+}
+
+fn load_provider_tokens(
+    codex_home: &Path,
+    client_id: &str,
+    auth_credentials_store_mode: AuthCredentialsStoreMode,
+    keyring_backend_kind: AuthKeyringBackendKind,
+) -> io::Result<Option<ProviderOAuthTokens>> {
+    Ok(load_auth_dot_json(
+        codex_home,
+        auth_credentials_store_mode,
+        keyring_backend_kind,
+    )?
+    .and_then(|auth| auth.provider_tokens?.get(client_id).cloned()))
+}
+
+fn empty_auth_dot_json() -> crate::auth::AuthDotJson {
+    crate::auth::AuthDotJson {
+        auth_mode: None,
+        openai_api_key: None,
+        tokens: None,
+        last_refresh: None,
+        agent_identity: None,
+        personal_access_token: None,
+        bedrock_api_key: None,
+        provider_tokens: None,
+    }
+}
+
+/// `ExternalAuth` implementation serving stored provider OAuth tokens.
+///
+/// `resolve` hands out the stored access token, refreshing proactively when
+/// it is close to expiry; `refresh` is the post-401 path and always goes back
+/// to the token endpoint.
+pub(crate) struct ProviderOAuthRefresher {
+    codex_home: PathBuf,
+    auth_credentials_store_mode: AuthCredentialsStoreMode,
+    keyring_backend_kind: AuthKeyringBackendKind,
+    config: ModelProviderOAuthInfo,
+}
+
+impl ProviderOAuthRefresher {
+    pub(crate) fn new(
+        codex_home: PathBuf,
+        auth_credentials_store_mode: AuthCredentialsStoreMode,
+        keyring_backend_kind: AuthKeyringBackendKind,
+        config: ModelProviderOAuthInfo,
+    ) -> Self {
+        Self {
+            codex_home,
+            auth_credentials_store_mode,
+            keyring_backend_kind,
+            config,
+        }
+    }
+
+    fn stored_tokens(&self) -> io::Result<ProviderOAuthTokens> {
+        load_provider_tokens(
+            &self.codex_home,
+            &self.config.client_id,
+            self.auth_credentials_store_mode,
+            self.keyring_backend_kind,
+        )?
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "not logged in to this provider; run `codex login --provider <id>`",
+            )
+        })
+    }
+
+    async fn refresh_and_store(&self, refresh_token: &str) -> io::Result<ProviderOAuthTokens> {
+        let tokens = refresh_tokens(&self.config, refresh_token).await?;
+        store_provider_tokens(
+            &self.codex_home,
+            &self.config.client_id,
+            tokens.clone(),
+            self.auth_credentials_store_mode,
+            self.keyring_backend_kind,
+        )?;
+        Ok(tokens)
+    }
+
+    async fn resolve_auth(&self) -> io::Result<CodexAuth> {
+        let tokens = self.stored_tokens()?;
+        let tokens = match (tokens.needs_refresh(), tokens.refresh_token.as_deref()) {
+            (true, Some(refresh_token)) => self.refresh_and_store(refresh_token).await?,
+            _ => tokens,
+        };
+        Ok(CodexAuth::from_api_key(&tokens.access_token))
+    }
+
+    async fn refresh_auth(&self) -> io::Result<CodexAuth> {
+        let tokens = self.stored_tokens()?;
+        let Some(refresh_token) = tokens.refresh_token.as_deref() else {
+            return Err(io::Error::other(
+                "provider access token was rejected and no refresh token is stored; run `codex login --provider <id>` again",
+            ));
+        };
+        let tokens = self.refresh_and_store(refresh_token).await?;
+        Ok(CodexAuth::from_api_key(&tokens.access_token))
+    }
+}
+
+impl ExternalAuth for ProviderOAuthRefresher {
+    fn resolve(&self) -> ExternalAuthFuture<'_, CodexAuth> {
+        Box::pin(self.resolve_auth())
+    }
+
+    fn refresh(&self, _context: ExternalAuthRefreshContext) -> ExternalAuthFuture<'_, CodexAuth> {
+        Box::pin(self.refresh_auth())
+    }
+}
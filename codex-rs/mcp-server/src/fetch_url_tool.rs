@@ -0,0 +1,513 @@
+//! Fetch URL tool - downloads a web page and reduces it to markdown so agents
+//! can read documentation referenced in a task without shelling out to curl.
+//!
+//! The response body is size-limited while streaming and the content type is
+//! checked against a whitelist before anything is read, so the tool cannot be
+//! used to pull arbitrary binaries into the conversation.
+
+use crate::ra1_tool::tool_schema_for;
+use rmcp::model::CallToolResult;
+use rmcp::model::Tool;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use std::time::Duration;
+
+/// Bytes of response body read unless overridden via `max_bytes`.
+const DEFAULT_MAX_BYTES: usize = 256 * 1024;
+/// Hard ceiling on `max_bytes` so one page cannot blow out the context.
+const MAX_BYTES_LIMIT: usize = 1024 * 1024;
+/// Time allowed for the whole request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Content types fetched when `allowed_content_types` is not provided.
+const DEFAULT_ALLOWED_CONTENT_TYPES: &[&str] = &[
+    "text/html",
+    "application/xhtml+xml",
+    "text/plain",
+    "text/markdown",
+    "application/json",
+];
+
+/// Elements whose contents are boilerplate rather than page text.
+const SKIPPED_ELEMENTS: &[&str] = &[
+    "script", "style", "head", "nav", "header", "footer", "aside", "noscript", "template",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FetchUrlParams {
+    /// The http(s) URL to fetch.
+    pub url: String,
+
+    /// Maximum bytes of the response body to read. Defaults to 262144 (256
+    /// KiB), capped at 1 MiB; longer pages are truncated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_bytes: Option<usize>,
+
+    /// Content types to accept (e.g. ["text/html", "application/json"]).
+    /// Defaults to common text formats; anything else is refused unread.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_content_types: Option<Vec<String>>,
+}
+
+/// Structured output returned in `CallToolResult.structured_content`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct FetchUrlOutput {
+    /// The URL that was fetched.
+    pub url: String,
+    /// Content type reported by the server.
+    pub content_type: String,
+    /// Page content converted to markdown (or passed through for non-HTML).
+    pub content: String,
+    /// Whether the body was cut off at the size limit.
+    pub truncated: bool,
+}
+
+pub fn create_tool_for_fetch_url() -> Tool {
+    Tool {
+        name: "fetch-url".into(),
+        title: Some("Fetch URL".to_string()),
+        input_schema: tool_schema_for::<FetchUrlParams>(),
+        output_schema: Some(tool_schema_for::<FetchUrlOutput>()),
+        description: Some(
+            "Download a web page and return its content as markdown, with a size limit and \
+             content-type whitelist."
+                .into(),
+        ),
+        annotations: None,
+        execution: None,
+        icons: None,
+        meta: None,
+    }
+}
+
+fn error_result(msg: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![rmcp::model::Content::text(msg)],
+        is_error: Some(true),
+        structured_content: None,
+        meta: None,
+    }
+}
+
+/// Compare the media type of a `Content-Type` header (parameters stripped)
+/// against the whitelist, case-insensitively.
+fn is_allowed_content_type(content_type: &str, allowed: &[String]) -> bool {
+    let media_type = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    allowed
+        .iter()
+        .any(|entry| entry.trim().eq_ignore_ascii_case(&media_type))
+}
+
+/// Decode the handful of HTML entities that show up in ordinary prose.
+fn decode_entities(text: &str) -> String {
+    text.replace("&amp;", "&")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+}
+
+/// Pull the value of `attr` out of a raw tag body like `a href="..." class=x`.
+fn attr_value(tag: &str, attr: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let needle = format!("{attr}=");
+    let mut search_from = 0;
+    loop {
+        let idx = lower[search_from..].find(&needle)? + search_from;
+        // Require a word boundary so `href=` does not match `data-href=`.
+        let boundary = idx == 0
+            || lower[..idx]
+                .chars()
+                .next_back()
+                .is_some_and(|c| c.is_whitespace());
+        if !boundary {
+            search_from = idx + needle.len();
+            continue;
+        }
+        let value = &tag[idx + needle.len()..];
+        return Some(match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let value = &value[1..];
+                value[..value.find(quote).unwrap_or(value.len())].to_string()
+            }
+            _ => value
+                .split_whitespace()
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+        });
+    }
+}
+
+/// Strip an HTML page down to markdown: boilerplate elements are dropped,
+/// headings/lists/links are converted, and everything else becomes plain text.
+fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+    let mut skip_depth: usize = 0;
+    let mut in_pre = false;
+    let mut link_href: Option<String> = None;
+    let mut link_text = String::new();
+
+    while let Some(lt) = rest.find('<') {
+        let text = &rest[..lt];
+        if skip_depth == 0 {
+            push_text(&mut out, &mut link_href, &mut link_text, text, in_pre);
+        }
+        rest = &rest[lt..];
+
+        // Comments can legally contain `>` so they need their own terminator.
+        if let Some(after) = rest.strip_prefix("<!--") {
+            rest = match after.find("-->") {
+                Some(end) => &after[end + 3..],
+                None => "",
+            };
+            continue;
+        }
+        let Some(gt) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[1..gt];
+        rest = &rest[gt + 1..];
+
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        if SKIPPED_ELEMENTS.contains(&name.as_str()) {
+            if closing {
+                skip_depth = skip_depth.saturating_sub(1);
+            } else if !tag.ends_with('/') {
+                skip_depth += 1;
+            }
+            continue;
+        }
+        if skip_depth > 0 {
+            continue;
+        }
+
+        match name.as_str() {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                if closing {
+                    out.push_str("\n\n");
+                } else {
+                    let level = name[1..].parse::<usize>().unwrap_or(1);
+                    out.push_str("\n\n");
+                    out.push_str(&"#".repeat(level));
+                    out.push(' ');
+                }
+            }
+            "p" | "div" | "section" | "article" | "main" | "table" | "tr" | "ul" | "ol"
+            | "blockquote" => out.push_str("\n\n"),
+            "br" => out.push('\n'),
+            "li" => {
+                if !closing {
+                    out.push_str("\n- ");
+                }
+            }
+            "pre" => {
+                in_pre = !closing;
+                out.push_str("\n```\n");
+            }
+            "a" => {
+                if closing {
+                    let text = link_text.trim().to_string();
+                    match link_href.take() {
+                        Some(href) if !text.is_empty() && !href.is_empty() => {
+                            out.push_str(&format!("[{text}]({href})"));
+                        }
+                        _ => out.push_str(&text),
+                    }
+                    link_text.clear();
+                } else {
+                    link_href = attr_value(tag, "href");
+                    link_text.clear();
+                }
+            }
+            _ => {}
+        }
+    }
+    if skip_depth == 0 {
+        push_text(&mut out, &mut link_href, &mut link_text, rest, in_pre);
+    }
+
+    collapse_blank_lines(&out)
+}
+
+/// Append a text node, collapsing runs of whitespace outside `<pre>` blocks
+/// and routing text into the pending link when one is open.
+fn push_text(
+    out: &mut String,
+    link_href: &mut Option<String>,
+    link_text: &mut String,
+    text: &str,
+    in_pre: bool,
+) {
+    if text.is_empty() {
+        return;
+    }
+    let decoded = decode_entities(text);
+    let normalized = if in_pre {
+        decoded
+    } else {
+        let mut normalized = String::with_capacity(decoded.len());
+        let mut last_was_space = false;
+        for c in decoded.chars() {
+            if c.is_whitespace() {
+                if !last_was_space {
+                    normalized.push(' ');
+                }
+                last_was_space = true;
+            } else {
+                normalized.push(c);
+                last_was_space = false;
+            }
+        }
+        normalized
+    };
+    if link_href.is_some() {
+        link_text.push_str(&normalized);
+    } else {
+        out.push_str(&normalized);
+    }
+}
+
+/// Trim trailing spaces and squeeze runs of blank lines down to one.
+fn collapse_blank_lines(text: &str) -> String {
+    let mut lines: Vec<&str> = Vec::new();
+    let mut last_was_blank = true;
+    for line in text.lines() {
+        let trimmed = line.trim_end();
+        let blank = trimmed.trim_start().is_empty();
+        if blank && last_was_blank {
+            continue;
+        }
+        lines.push(if blank { "" } else { trimmed });
+        last_was_blank = blank;
+    }
+    while lines.last() == Some(&"") {
+        lines.pop();
+    }
+    lines.join("\n").trim_start().to_string()
+}
+
+/// Fetch the URL, refusing disallowed content types before the body is read
+/// and cutting the download off at `max_bytes`.
+async fn fetch(
+    url: &str,
+    max_bytes: usize,
+    allowed: &[String],
+) -> Result<(String, String, bool), String> {
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {e}"))?;
+    let mut response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {e}"))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("Server returned {status} for {url}"));
+    }
+
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("application/octet-stream")
+        .to_string();
+    if !is_allowed_content_type(&content_type, allowed) {
+        return Err(format!(
+            "Refusing to fetch content type {content_type}; allowed types are {}.",
+            allowed.join(", ")
+        ));
+    }
+
+    let mut body: Vec<u8> = Vec::new();
+    let mut truncated = false;
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("Failed to read response body: {e}"))?
+    {
+        let remaining = max_bytes - body.len();
+        if chunk.len() > remaining {
+            body.extend_from_slice(&chunk[..remaining]);
+            truncated = true;
+            break;
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    let body = String::from_utf8_lossy(&body).into_owned();
+    Ok((content_type, body, truncated))
+}
+
+pub(crate) async fn handle_fetch_url(
+    arguments: Option<serde_json::Map<String, serde_json::Value>>,
+) -> CallToolResult {
+    let arguments = arguments.map(serde_json::Value::Object);
+    let params: FetchUrlParams = match arguments {
+        Some(json_val) => match serde_json::from_value(json_val) {
+            Ok(p) => p,
+            Err(e) => {
+                return error_result(format!("Failed to parse parameters: {e}"));
+            }
+        },
+        None => {
+            return error_result("Missing arguments; the `url` field is required.".to_string());
+        }
+    };
+
+    if !params.url.starts_with("http://") && !params.url.starts_with("https://") {
+        return error_result(format!(
+            "Only http(s) URLs are supported; got {}",
+            params.url
+        ));
+    }
+    let max_bytes = params
+        .max_bytes
+        .unwrap_or(DEFAULT_MAX_BYTES)
+        .clamp(1, MAX_BYTES_LIMIT);
+    let allowed = params.allowed_content_types.unwrap_or_else(|| {
+        DEFAULT_ALLOWED_CONTENT_TYPES
+            .iter()
+            .map(|entry| (*entry).to_string())
+            .collect()
+    });
+
+    let (content_type, body, truncated) = match fetch(&params.url, max_bytes, &allowed).await {
+        Ok(result) => result,
+        Err(msg) => {
+            return error_result(msg);
+        }
+    };
+
+    let media_type = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    let content = if media_type == "text/html" || media_type == "application/xhtml+xml" {
+        html_to_markdown(&body)
+    } else {
+        body
+    };
+
+    let output = FetchUrlOutput {
+        url: params.url.clone(),
+        content_type,
+        content,
+        truncated,
+    };
+    let mut text = format!("Fetched {} ({}):", output.url, output.content_type);
+    if truncated {
+        text.push_str(&format!(" [truncated at {max_bytes} bytes]"));
+    }
+    text.push_str("\n\n");
+    text.push_str(&output.content);
+
+    CallToolResult {
+        content: vec![rmcp::model::Content::text(text)],
+        is_error: Some(false),
+        structured_content: serde_json::to_value(&output).ok(),
+        meta: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_fetch_url_tool_json_schema() {
+        let tool = create_tool_for_fetch_url();
+        assert_eq!(tool.name.as_ref(), "fetch-url");
+        let schema = serde_json::to_value(&tool.input_schema).unwrap();
+        let props = schema.get("properties").unwrap();
+        assert!(props.get("url").is_some());
+        assert!(props.get("max_bytes").is_some());
+        assert!(props.get("allowed_content_types").is_some());
+        let required = schema.get("required").unwrap().as_array().unwrap();
+        assert!(required.iter().any(|v| v.as_str() == Some("url")));
+    }
+
+    #[test]
+    fn content_type_whitelist_ignores_parameters_and_case() {
+        let allowed = vec!["text/html".to_string(), "application/json".to_string()];
+        assert!(is_allowed_content_type(
+            "text/html; charset=utf-8",
+            &allowed
+        ));
+        assert!(is_allowed_content_type("Application/JSON", &allowed));
+        assert!(!is_allowed_content_type("image/png", &allowed));
+    }
+
+    #[test]
+    fn html_to_markdown_converts_headings_lists_and_links() {
+        let html = r#"
+            <html><head><title>ignored</title></head><body>
+            <h1>Guide</h1>
+            <p>See the <a href="https://example.com/docs">docs</a> for more.</p>
+            <ul><li>first</li><li>second</li></ul>
+            </body></html>
+        "#;
+        let markdown = html_to_markdown(html);
+        assert!(markdown.starts_with("# Guide"));
+        assert!(markdown.contains("[docs](https://example.com/docs)"));
+        assert!(markdown.contains("- first"));
+        assert!(markdown.contains("- second"));
+    }
+
+    #[test]
+    fn html_to_markdown_strips_boilerplate_elements() {
+        let html = concat!(
+            "<nav>Home | About</nav>",
+            "<script>alert(1)</script>",
+            "<style>body { color: red; }</style>",
+            "<p>Real &amp; useful content</p>",
+            "<footer>Copyright</footer>",
+        );
+        let markdown = html_to_markdown(html);
+        assert_eq!(markdown, "Real & useful content");
+    }
+
+    #[test]
+    fn html_to_markdown_preserves_pre_blocks() {
+        let html = "<p>Run:</p><pre>cargo build\ncargo test</pre>";
+        let markdown = html_to_markdown(html);
+        assert!(markdown.contains("```\ncargo build\ncargo test\n```"));
+    }
+
+    #[test]
+    fn attr_value_handles_quoting_and_ignores_prefixed_attributes() {
+        assert_eq!(
+            attr_value(r#"a data-href="/nope" href="/docs""#, "href").as_deref(),
+            Some("/docs")
+        );
+        assert_eq!(
+            attr_value("a href='/single'", "href").as_deref(),
+            Some("/single")
+        );
+        assert_eq!(
+            attr_value("a href=/bare class=x", "href").as_deref(),
+            Some("/bare")
+        );
+        assert_eq!(attr_value("a class=x", "href"), None);
+    }
+}
@@ -0,0 +1,161 @@
+//! Session-wide cost tracking for the paid RA1 generation tools.
+//!
+//! Each successful generation reports a `cost`; this module aggregates the
+//! spend for the lifetime of the MCP session, rejects calls once the
+//! configured `[tools.ra1] session_cost_cap_usd` budget is exhausted, and
+//! exposes the running total via the `generation-usage` tool.
+
+use crate::ra1_tool::tool_schema_for;
+use rmcp::model::CallToolResult;
+use rmcp::model::Tool;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::sync::Mutex;
+
+/// Cumulative generation spend for one MCP session.
+pub(crate) struct GenerationUsage {
+    spent_usd: Mutex<f64>,
+    session_cost_cap_usd: Option<f64>,
+}
+
+impl GenerationUsage {
+    pub(crate) fn new(session_cost_cap_usd: Option<f64>) -> Self {
+        Self {
+            spent_usd: Mutex::new(0.0),
+            session_cost_cap_usd,
+        }
+    }
+
+    /// Errors when the session budget is already spent; paid handlers call
+    /// this before issuing a generation request.
+    pub(crate) fn ensure_within_budget(&self) -> Result<(), String> {
+        let Some(cap) = self.session_cost_cap_usd else {
+            return Ok(());
+        };
+        let spent = self.spent();
+        if spent >= cap {
+            return Err(format!(
+                "Session generation budget exhausted: spent ${spent:.2} of the ${cap:.2} cap \
+                 configured via `[tools.ra1] session_cost_cap_usd`."
+            ));
+        }
+        Ok(())
+    }
+
+    /// Records the cost of a completed generation. `cost` is the API's cost
+    /// string (e.g. `"0.04"` or `"$0.04"`); unparseable values are ignored
+    /// rather than blocking the result.
+    pub(crate) fn record_cost(&self, cost: &str) {
+        if let Ok(cost_usd) = cost.trim().trim_start_matches('$').parse::<f64>() {
+            #[expect(clippy::unwrap_used)]
+            let mut spent = self.spent_usd.lock().unwrap();
+            *spent += cost_usd;
+        }
+    }
+
+    pub(crate) fn spent(&self) -> f64 {
+        #[expect(clippy::unwrap_used)]
+        let spent = self.spent_usd.lock().unwrap();
+        *spent
+    }
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+struct GenerationUsageParams {}
+
+/// Structured output returned in `CallToolResult.structured_content`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct GenerationUsageOutput {
+    /// Cumulative generation spend this session, in USD.
+    pub spent_usd: f64,
+    /// Configured session budget, in USD. Unset means unlimited.
+    pub session_cost_cap_usd: Option<f64>,
+    /// Budget remaining, when a cap is configured.
+    pub remaining_usd: Option<f64>,
+}
+
+pub fn create_tool_for_generation_usage() -> Tool {
+    Tool {
+        name: "generation-usage".into(),
+        title: Some("Generation Usage".to_string()),
+        input_schema: tool_schema_for::<GenerationUsageParams>(),
+        output_schema: Some(tool_schema_for::<GenerationUsageOutput>()),
+        description: Some(
+            "Report the cumulative cost of paid generation tool calls this session, along with \
+             the configured budget."
+                .into(),
+        ),
+        annotations: None,
+        execution: None,
+        icons: None,
+        meta: None,
+    }
+}
+
+pub(crate) fn handle_generation_usage(usage: &GenerationUsage) -> CallToolResult {
+    let spent_usd = usage.spent();
+    let output = GenerationUsageOutput {
+        spent_usd,
+        session_cost_cap_usd: usage.session_cost_cap_usd,
+        remaining_usd: usage
+            .session_cost_cap_usd
+            .map(|cap| (cap - spent_usd).max(0.0)),
+    };
+    let text = match usage.session_cost_cap_usd {
+        Some(cap) => format!("Generation spend this session: ${spent_usd:.2} of ${cap:.2} cap."),
+        None => format!("Generation spend this session: ${spent_usd:.2} (no cap configured)."),
+    };
+    CallToolResult {
+        content: vec![rmcp::model::Content::text(text)],
+        is_error: Some(false),
+        structured_content: serde_json::to_value(&output).ok(),
+        meta: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_and_reports_spend() {
+        let usage = GenerationUsage::new(Some(1.0));
+        usage.record_cost("0.40");
+        usage.record_cost("$0.25");
+        usage.record_cost("not-a-number");
+        assert!((usage.spent() - 0.65).abs() < f64::EPSILON);
+        assert!(usage.ensure_within_budget().is_ok());
+    }
+
+    #[test]
+    fn rejects_calls_once_budget_is_spent() {
+        let usage = GenerationUsage::new(Some(0.5));
+        usage.record_cost("0.50");
+        let err = usage.ensure_within_budget().unwrap_err();
+        assert!(
+            err.contains("budget exhausted"),
+            "unexpected message: {err}"
+        );
+    }
+
+    #[test]
+    fn unlimited_without_a_cap() {
+        let usage = GenerationUsage::new(None);
+        usage.record_cost("100.0");
+        assert!(usage.ensure_within_budget().is_ok());
+    }
+
+    #[test]
+    fn verify_generation_usage_tool_json_schema() {
+        let tool = create_tool_for_generation_usage();
+        assert_eq!(tool.name.as_ref(), "generation-usage");
+        let output_schema = tool
+            .output_schema
+            .expect("output schema should be declared");
+        let schema = serde_json::to_value(&output_schema).unwrap();
+        let props = schema.get("properties").unwrap();
+        assert!(props.get("spent_usd").is_some());
+        assert!(props.get("session_cost_cap_usd").is_some());
+        assert!(props.get("remaining_usd").is_some());
+    }
+}
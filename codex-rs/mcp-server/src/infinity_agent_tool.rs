@@ -0,0 +1,412 @@
+//! Infinity agent tools - expose the Infinity control-plane agent operations
+//! (launch, list, status, logs, cancel) as MCP tools so an orchestrating
+//! agent can spin up and monitor cloud agents as part of its plan instead of
+//! a human driving the `codex infinity` CLI.
+//!
+//! The tools are only listed when an Infinity API key is resolvable, the same
+//! credential lookup the CLI uses.
+
+use crate::ra1_tool::tool_schema_for;
+use codex_infinity::Agent;
+use codex_infinity::InfinityClient;
+use codex_infinity::LaunchAgentRequest;
+use rmcp::model::CallToolResult;
+use rmcp::model::Tool;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InfinityAgentLaunchParams {
+    /// Name for the new agent.
+    pub name: String,
+
+    /// Task prompt the agent starts working on.
+    pub prompt: String,
+
+    /// Model the agent should run. Defaults to the platform default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+
+    /// Server (name or id) to place the agent on. The platform picks one
+    /// when omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InfinityAgentListParams {
+    /// Only return agents whose status matches (e.g. "running").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InfinityAgentParams {
+    /// Agent name or id.
+    pub agent: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InfinityAgentLogsParams {
+    /// Agent name or id.
+    pub agent: String,
+
+    /// Cursor from a previous call to resume where the last page ended.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+/// One agent in the structured output; mirrors the control-plane record with
+/// the timestamp rendered as RFC 3339.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct InfinityAgentOutput {
+    pub id: String,
+    pub name: String,
+    pub status: String,
+    /// Server the agent runs on, if it has been placed.
+    pub server_id: Option<String>,
+    pub model: Option<String>,
+    pub created_at: String,
+}
+
+/// Structured output of `infinity-agent-list`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct InfinityAgentListOutput {
+    pub agents: Vec<InfinityAgentOutput>,
+}
+
+/// Structured output of `infinity-agent-logs`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct InfinityAgentLogsOutput {
+    pub lines: Vec<String>,
+    /// Cursor to pass back for the next page; absent once the agent has
+    /// finished.
+    pub next_cursor: Option<String>,
+}
+
+impl From<Agent> for InfinityAgentOutput {
+    fn from(agent: Agent) -> Self {
+        Self {
+            id: agent.id,
+            name: agent.name,
+            status: agent.status,
+            server_id: agent.server_id,
+            model: agent.model,
+            created_at: agent.created_at.to_rfc3339(),
+        }
+    }
+}
+
+pub(crate) fn is_infinity_available() -> bool {
+    InfinityClient::is_configured()
+}
+
+pub fn create_tool_for_infinity_agent_launch() -> Tool {
+    Tool {
+        name: "infinity-agent-launch".into(),
+        title: Some("Launch Infinity Agent".to_string()),
+        input_schema: tool_schema_for::<InfinityAgentLaunchParams>(),
+        output_schema: Some(tool_schema_for::<InfinityAgentOutput>()),
+        description: Some(
+            "Launch a cloud agent on the Infinity platform with a name and task prompt.".into(),
+        ),
+        annotations: None,
+        execution: None,
+        icons: None,
+        meta: None,
+    }
+}
+
+pub fn create_tool_for_infinity_agent_list() -> Tool {
+    Tool {
+        name: "infinity-agent-list".into(),
+        title: Some("List Infinity Agents".to_string()),
+        input_schema: tool_schema_for::<InfinityAgentListParams>(),
+        output_schema: Some(tool_schema_for::<InfinityAgentListOutput>()),
+        description: Some(
+            "List cloud agents on the Infinity platform, optionally filtered by status.".into(),
+        ),
+        annotations: None,
+        execution: None,
+        icons: None,
+        meta: None,
+    }
+}
+
+pub fn create_tool_for_infinity_agent_status() -> Tool {
+    Tool {
+        name: "infinity-agent-status".into(),
+        title: Some("Infinity Agent Status".to_string()),
+        input_schema: tool_schema_for::<InfinityAgentParams>(),
+        output_schema: Some(tool_schema_for::<InfinityAgentOutput>()),
+        description: Some("Fetch the current status of one Infinity cloud agent.".into()),
+        annotations: None,
+        execution: None,
+        icons: None,
+        meta: None,
+    }
+}
+
+pub fn create_tool_for_infinity_agent_logs() -> Tool {
+    Tool {
+        name: "infinity-agent-logs".into(),
+        title: Some("Infinity Agent Logs".to_string()),
+        input_schema: tool_schema_for::<InfinityAgentLogsParams>(),
+        output_schema: Some(tool_schema_for::<InfinityAgentLogsOutput>()),
+        description: Some(
+            "Fetch logs from an Infinity cloud agent, with a cursor for incremental reads.".into(),
+        ),
+        annotations: None,
+        execution: None,
+        icons: None,
+        meta: None,
+    }
+}
+
+pub fn create_tool_for_infinity_agent_cancel() -> Tool {
+    Tool {
+        name: "infinity-agent-cancel".into(),
+        title: Some("Cancel Infinity Agent".to_string()),
+        input_schema: tool_schema_for::<InfinityAgentParams>(),
+        output_schema: Some(tool_schema_for::<InfinityAgentOutput>()),
+        description: Some("Cancel a running Infinity cloud agent.".into()),
+        annotations: None,
+        execution: None,
+        icons: None,
+        meta: None,
+    }
+}
+
+fn error_result(msg: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![rmcp::model::Content::text(msg)],
+        is_error: Some(true),
+        structured_content: None,
+        meta: None,
+    }
+}
+
+fn parse_params<T: serde::de::DeserializeOwned>(
+    arguments: Option<serde_json::Map<String, serde_json::Value>>,
+) -> Result<T, CallToolResult> {
+    let value = serde_json::Value::Object(arguments.unwrap_or_default());
+    serde_json::from_value(value)
+        .map_err(|e| error_result(format!("Failed to parse parameters: {e}")))
+}
+
+fn client() -> Result<InfinityClient, CallToolResult> {
+    InfinityClient::from_env().map_err(|e| error_result(format!("{e:#}")))
+}
+
+fn agent_result(summary: String, agent: Agent) -> CallToolResult {
+    let output = InfinityAgentOutput::from(agent);
+    CallToolResult {
+        content: vec![rmcp::model::Content::text(summary)],
+        is_error: Some(false),
+        structured_content: serde_json::to_value(&output).ok(),
+        meta: None,
+    }
+}
+
+pub(crate) async fn handle_infinity_agent_launch(
+    arguments: Option<serde_json::Map<String, serde_json::Value>>,
+) -> CallToolResult {
+    let params: InfinityAgentLaunchParams = match parse_params(arguments) {
+        Ok(params) => params,
+        Err(result) => return result,
+    };
+    let client = match client() {
+        Ok(client) => client,
+        Err(result) => return result,
+    };
+
+    // Accept a server name as well as an id, like the CLI does.
+    let server_id = match &params.server {
+        Some(server) => match client.find_server(server).await {
+            Ok(server) => Some(server.id),
+            Err(e) => return error_result(format!("{e:#}")),
+        },
+        None => None,
+    };
+
+    let request = LaunchAgentRequest {
+        name: &params.name,
+        prompt: &params.prompt,
+        model: params.model.as_deref(),
+        server_id: server_id.as_deref(),
+    };
+    match client.launch_agent(&request).await {
+        Ok(agent) => agent_result(
+            format!(
+                "Launched agent {} ({}) with status {}.",
+                agent.name, agent.id, agent.status
+            ),
+            agent,
+        ),
+        Err(e) => error_result(format!("{e:#}")),
+    }
+}
+
+pub(crate) async fn handle_infinity_agent_list(
+    arguments: Option<serde_json::Map<String, serde_json::Value>>,
+) -> CallToolResult {
+    let params: InfinityAgentListParams = match parse_params(arguments) {
+        Ok(params) => params,
+        Err(result) => return result,
+    };
+    let client = match client() {
+        Ok(client) => client,
+        Err(result) => return result,
+    };
+    let agents = match client.list_agents().await {
+        Ok(agents) => agents,
+        Err(e) => return error_result(format!("{e:#}")),
+    };
+
+    let agents: Vec<InfinityAgentOutput> = agents
+        .into_iter()
+        .filter(|agent| {
+            params
+                .status
+                .as_ref()
+                .is_none_or(|status| agent.status.eq_ignore_ascii_case(status))
+        })
+        .map(InfinityAgentOutput::from)
+        .collect();
+
+    let mut lines = vec![format!("{} agent(s):", agents.len())];
+    for agent in &agents {
+        lines.push(format!("- {} ({}): {}", agent.name, agent.id, agent.status));
+    }
+    let output = InfinityAgentListOutput { agents };
+    CallToolResult {
+        content: vec![rmcp::model::Content::text(lines.join("\n"))],
+        is_error: Some(false),
+        structured_content: serde_json::to_value(&output).ok(),
+        meta: None,
+    }
+}
+
+pub(crate) async fn handle_infinity_agent_status(
+    arguments: Option<serde_json::Map<String, serde_json::Value>>,
+) -> CallToolResult {
+    let params: InfinityAgentParams = match parse_params(arguments) {
+        Ok(params) => params,
+        Err(result) => return result,
+    };
+    let client = match client() {
+        Ok(client) => client,
+        Err(result) => return result,
+    };
+    let agent = match client.find_agent(&params.agent).await {
+        Ok(agent) => agent,
+        Err(e) => return error_result(format!("{e:#}")),
+    };
+    match client.agent_status(&agent.id).await {
+        Ok(agent) => agent_result(
+            format!("Agent {} ({}) is {}.", agent.name, agent.id, agent.status),
+            agent,
+        ),
+        Err(e) => error_result(format!("{e:#}")),
+    }
+}
+
+pub(crate) async fn handle_infinity_agent_logs(
+    arguments: Option<serde_json::Map<String, serde_json::Value>>,
+) -> CallToolResult {
+    let params: InfinityAgentLogsParams = match parse_params(arguments) {
+        Ok(params) => params,
+        Err(result) => return result,
+    };
+    let client = match client() {
+        Ok(client) => client,
+        Err(result) => return result,
+    };
+    let agent = match client.find_agent(&params.agent).await {
+        Ok(agent) => agent,
+        Err(e) => return error_result(format!("{e:#}")),
+    };
+    let logs = match client.agent_logs(&agent.id, params.cursor.as_deref()).await {
+        Ok(logs) => logs,
+        Err(e) => return error_result(format!("{e:#}")),
+    };
+
+    let output = InfinityAgentLogsOutput {
+        lines: logs.lines,
+        next_cursor: logs.next_cursor,
+    };
+    CallToolResult {
+        content: vec![rmcp::model::Content::text(output.lines.join("\n"))],
+        is_error: Some(false),
+        structured_content: serde_json::to_value(&output).ok(),
+        meta: None,
+    }
+}
+
+pub(crate) async fn handle_infinity_agent_cancel(
+    arguments: Option<serde_json::Map<String, serde_json::Value>>,
+) -> CallToolResult {
+    let params: InfinityAgentParams = match parse_params(arguments) {
+        Ok(params) => params,
+        Err(result) => return result,
+    };
+    let client = match client() {
+        Ok(client) => client,
+        Err(result) => return result,
+    };
+    let agent = match client.find_agent(&params.agent).await {
+        Ok(agent) => agent,
+        Err(e) => return error_result(format!("{e:#}")),
+    };
+    match client.cancel_agent(&agent.id).await {
+        Ok(agent) => agent_result(
+            format!(
+                "Cancel requested for agent {} ({}); status is now {}.",
+                agent.name, agent.id, agent.status
+            ),
+            agent,
+        ),
+        Err(e) => error_result(format!("{e:#}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_infinity_agent_launch_tool_json_schema() {
+        let tool = create_tool_for_infinity_agent_launch();
+        assert_eq!(tool.name.as_ref(), "infinity-agent-launch");
+        let schema = serde_json::to_value(&tool.input_schema).unwrap();
+        let props = schema.get("properties").unwrap();
+        assert!(props.get("name").is_some());
+        assert!(props.get("prompt").is_some());
+        assert!(props.get("model").is_some());
+        assert!(props.get("server").is_some());
+        let required = schema.get("required").unwrap().as_array().unwrap();
+        assert!(required.iter().any(|v| v.as_str() == Some("name")));
+        assert!(required.iter().any(|v| v.as_str() == Some("prompt")));
+    }
+
+    #[test]
+    fn verify_infinity_agent_list_tool_has_no_required_fields() {
+        let tool = create_tool_for_infinity_agent_list();
+        assert_eq!(tool.name.as_ref(), "infinity-agent-list");
+        let schema = serde_json::to_value(&tool.input_schema).unwrap();
+        assert!(schema.get("required").is_none());
+    }
+
+    #[test]
+    fn verify_infinity_agent_logs_tool_json_schema() {
+        let tool = create_tool_for_infinity_agent_logs();
+        assert_eq!(tool.name.as_ref(), "infinity-agent-logs");
+        let schema = serde_json::to_value(&tool.input_schema).unwrap();
+        let props = schema.get("properties").unwrap();
+        assert!(props.get("agent").is_some());
+        assert!(props.get("cursor").is_some());
+        let required = schema.get("required").unwrap().as_array().unwrap();
+        assert!(required.iter().any(|v| v.as_str() == Some("agent")));
+    }
+}
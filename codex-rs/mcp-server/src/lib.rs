@@ -31,9 +31,19 @@ use tracing_subscriber::prelude::*;
 mod codex_tool_config;
 mod codex_tool_runner;
 mod exec_approval;
+mod fetch_url_tool;
+mod generation_usage;
+mod infinity_agent_tool;
 pub(crate) mod message_processor;
 mod outgoing_message;
 mod patch_approval;
+mod ra1_batch_tool;
+mod ra1_image_edit_tool;
+mod ra1_tool;
+mod ra1_video_tool;
+mod tool_audit;
+mod tool_availability;
+mod web_search_tool;
 
 use crate::message_processor::MessageProcessor;
 use crate::outgoing_message::OutgoingJsonRpcMessage;
@@ -5,6 +5,7 @@ use codex_arg0::Arg0DispatchPaths;
 use codex_core::StateDbHandle;
 use codex_core::ThreadManager;
 use codex_core::config::Config;
+use codex_core::config::Ra1ToolConfig;
 use codex_exec_server::EnvironmentManager;
 use codex_extension_api::ExtensionRegistryBuilder;
 use codex_home::CodexHomeUserInstructionsProvider;
@@ -36,7 +37,23 @@ use crate::codex_tool_config::CodexToolCallParam;
 use crate::codex_tool_config::CodexToolCallReplyParam;
 use crate::codex_tool_config::create_tool_for_codex_tool_call_param;
 use crate::codex_tool_config::create_tool_for_codex_tool_call_reply_param;
+use crate::fetch_url_tool::create_tool_for_fetch_url;
+use crate::generation_usage::GenerationUsage;
+use crate::generation_usage::create_tool_for_generation_usage;
+use crate::infinity_agent_tool::create_tool_for_infinity_agent_cancel;
+use crate::infinity_agent_tool::create_tool_for_infinity_agent_launch;
+use crate::infinity_agent_tool::create_tool_for_infinity_agent_list;
+use crate::infinity_agent_tool::create_tool_for_infinity_agent_logs;
+use crate::infinity_agent_tool::create_tool_for_infinity_agent_status;
 use crate::outgoing_message::OutgoingMessageSender;
+use crate::ra1_batch_tool::create_tool_for_ra1_art_batch;
+use crate::ra1_image_edit_tool::create_tool_for_ra1_image_edit;
+use crate::ra1_tool::create_tool_for_ra1_art_generator;
+use crate::ra1_video_tool::create_tool_for_ra1_video_generator;
+use crate::tool_audit::ToolAuditLog;
+use crate::tool_audit::create_tool_for_list_recent_tool_calls;
+use crate::tool_availability::ToolAvailability;
+use crate::web_search_tool::create_tool_for_web_search;
 
 pub(crate) struct MessageProcessor {
     outgoing: Arc<OutgoingMessageSender>,
@@ -44,6 +61,10 @@ pub(crate) struct MessageProcessor {
     arg0_paths: Arg0DispatchPaths,
     thread_manager: Arc<ThreadManager>,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ThreadId>>>,
+    ra1_config: Ra1ToolConfig,
+    generation_usage: Arc<GenerationUsage>,
+    tool_audit: Arc<ToolAuditLog>,
+    tool_availability: Arc<std::sync::Mutex<ToolAvailability>>,
 }
 
 impl MessageProcessor {
@@ -86,12 +107,28 @@ impl MessageProcessor {
             /*attestation_provider*/ None,
             /*external_time_provider*/ None,
         ));
+        // Re-check the environment-gated tools in the background so that
+        // e.g. exporting an API key after startup does not require a
+        // restart; the watcher announces changes via
+        // `notifications/tools/list_changed`.
+        let tool_availability = Arc::new(std::sync::Mutex::new(crate::tool_availability::current(
+            &config.ra1_tool,
+        )));
+        crate::tool_availability::spawn_watcher(
+            config.ra1_tool.clone(),
+            outgoing.clone(),
+            tool_availability.clone(),
+        );
         Self {
             outgoing,
             initialized: false,
             arg0_paths,
             thread_manager,
             running_requests_id_to_codex_uuid: Arc::new(Mutex::new(HashMap::new())),
+            generation_usage: Arc::new(GenerationUsage::new(config.ra1_tool.session_cost_cap_usd)),
+            tool_audit: Arc::new(ToolAuditLog::new(config.codex_home.clone())),
+            tool_availability,
+            ra1_config: config.ra1_tool.clone(),
         }
     }
 
@@ -322,12 +359,43 @@ impl MessageProcessor {
         params: Option<rmcp::model::PaginatedRequestParams>,
     ) {
         tracing::trace!("tools/list -> {params:?}");
+        let mut tools = vec![
+            create_tool_for_codex_tool_call_param(),
+            create_tool_for_codex_tool_call_reply_param(),
+            create_tool_for_fetch_url(),
+            create_tool_for_list_recent_tool_calls(),
+        ];
+        // Re-run the availability checks on every listing and sync the
+        // watcher's snapshot so it only notifies about changes the client
+        // has not seen yet.
+        let availability = crate::tool_availability::current(&self.ra1_config);
+        crate::tool_availability::refresh(&self.tool_availability, availability.clone());
+        // The netwrck generation tools are only usable with an API key, so
+        // keep them out of the listing when none is configured.
+        if availability.ra1 {
+            tools.push(create_tool_for_ra1_art_generator());
+            tools.push(create_tool_for_ra1_art_batch());
+            tools.push(create_tool_for_ra1_image_edit());
+            tools.push(create_tool_for_ra1_video_generator());
+            tools.push(create_tool_for_generation_usage());
+        }
+        // Web search is only offered when a backend is configured in the
+        // environment.
+        if availability.web_search {
+            tools.push(create_tool_for_web_search());
+        }
+        // Infinity agent management needs the platform API key, the same
+        // credential lookup the `codex infinity` CLI uses.
+        if availability.infinity {
+            tools.push(create_tool_for_infinity_agent_launch());
+            tools.push(create_tool_for_infinity_agent_list());
+            tools.push(create_tool_for_infinity_agent_status());
+            tools.push(create_tool_for_infinity_agent_logs());
+            tools.push(create_tool_for_infinity_agent_cancel());
+        }
         let result = rmcp::model::ListToolsResult {
             meta: None,
-            tools: vec![
-                create_tool_for_codex_tool_call_param(),
-                create_tool_for_codex_tool_call_reply_param(),
-            ],
+            tools,
             next_cursor: None,
         };
 
@@ -340,12 +408,169 @@ impl MessageProcessor {
             name, arguments, ..
         } = params;
 
+        // The built-in tools below record themselves in the audit log; the
+        // codex session tools stream their results through the tool runner
+        // and are persisted as rollouts instead.
+        let audit = self.tool_audit.clone();
+        let audit_args = arguments.clone();
+        let started = std::time::Instant::now();
+
         match name.as_ref() {
             "codex" => self.handle_tool_call_codex(id, arguments).await,
             "codex-reply" => {
                 self.handle_tool_call_codex_session_reply(id, arguments)
                     .await
             }
+            "ra1-art-generator" => {
+                let outgoing = self.outgoing.clone();
+                let config = self.ra1_config.clone();
+                let usage = self.generation_usage.clone();
+                task::spawn(async move {
+                    let result =
+                        crate::ra1_tool::handle_ra1_art_generator(arguments, &config, &usage).await;
+                    audit
+                        .record("ra1-art-generator", audit_args, started, &result)
+                        .await;
+                    outgoing.send_response(id, result).await;
+                });
+            }
+            "ra1-art-batch" => {
+                let outgoing = self.outgoing.clone();
+                let config = self.ra1_config.clone();
+                let usage = self.generation_usage.clone();
+                task::spawn(async move {
+                    let result =
+                        crate::ra1_batch_tool::handle_ra1_art_batch(arguments, config, usage).await;
+                    audit
+                        .record("ra1-art-batch", audit_args, started, &result)
+                        .await;
+                    outgoing.send_response(id, result).await;
+                });
+            }
+            "ra1-image-edit" => {
+                let outgoing = self.outgoing.clone();
+                let config = self.ra1_config.clone();
+                let usage = self.generation_usage.clone();
+                task::spawn(async move {
+                    let result = crate::ra1_image_edit_tool::handle_ra1_image_edit(
+                        arguments, &config, &usage,
+                    )
+                    .await;
+                    audit
+                        .record("ra1-image-edit", audit_args, started, &result)
+                        .await;
+                    outgoing.send_response(id, result).await;
+                });
+            }
+            "ra1-video-generator" => {
+                let outgoing = self.outgoing.clone();
+                let config = self.ra1_config.clone();
+                let usage = self.generation_usage.clone();
+                task::spawn(async move {
+                    let result = crate::ra1_video_tool::handle_ra1_video_generator(
+                        arguments,
+                        outgoing.clone(),
+                        &config,
+                        &usage,
+                    )
+                    .await;
+                    audit
+                        .record("ra1-video-generator", audit_args, started, &result)
+                        .await;
+                    outgoing.send_response(id, result).await;
+                });
+            }
+            "fetch-url" => {
+                let outgoing = self.outgoing.clone();
+                task::spawn(async move {
+                    let result = crate::fetch_url_tool::handle_fetch_url(arguments).await;
+                    audit
+                        .record("fetch-url", audit_args, started, &result)
+                        .await;
+                    outgoing.send_response(id, result).await;
+                });
+            }
+            "web-search" => {
+                let outgoing = self.outgoing.clone();
+                task::spawn(async move {
+                    let result = crate::web_search_tool::handle_web_search(arguments).await;
+                    audit
+                        .record("web-search", audit_args, started, &result)
+                        .await;
+                    outgoing.send_response(id, result).await;
+                });
+            }
+            "infinity-agent-launch" => {
+                let outgoing = self.outgoing.clone();
+                task::spawn(async move {
+                    let result =
+                        crate::infinity_agent_tool::handle_infinity_agent_launch(arguments).await;
+                    audit
+                        .record("infinity-agent-launch", audit_args, started, &result)
+                        .await;
+                    outgoing.send_response(id, result).await;
+                });
+            }
+            "infinity-agent-list" => {
+                let outgoing = self.outgoing.clone();
+                task::spawn(async move {
+                    let result =
+                        crate::infinity_agent_tool::handle_infinity_agent_list(arguments).await;
+                    audit
+                        .record("infinity-agent-list", audit_args, started, &result)
+                        .await;
+                    outgoing.send_response(id, result).await;
+                });
+            }
+            "infinity-agent-status" => {
+                let outgoing = self.outgoing.clone();
+                task::spawn(async move {
+                    let result =
+                        crate::infinity_agent_tool::handle_infinity_agent_status(arguments).await;
+                    audit
+                        .record("infinity-agent-status", audit_args, started, &result)
+                        .await;
+                    outgoing.send_response(id, result).await;
+                });
+            }
+            "infinity-agent-logs" => {
+                let outgoing = self.outgoing.clone();
+                task::spawn(async move {
+                    let result =
+                        crate::infinity_agent_tool::handle_infinity_agent_logs(arguments).await;
+                    audit
+                        .record("infinity-agent-logs", audit_args, started, &result)
+                        .await;
+                    outgoing.send_response(id, result).await;
+                });
+            }
+            "infinity-agent-cancel" => {
+                let outgoing = self.outgoing.clone();
+                task::spawn(async move {
+                    let result =
+                        crate::infinity_agent_tool::handle_infinity_agent_cancel(arguments).await;
+                    audit
+                        .record("infinity-agent-cancel", audit_args, started, &result)
+                        .await;
+                    outgoing.send_response(id, result).await;
+                });
+            }
+            "generation-usage" => {
+                let result =
+                    crate::generation_usage::handle_generation_usage(&self.generation_usage);
+                audit
+                    .record("generation-usage", audit_args, started, &result)
+                    .await;
+                self.outgoing.send_response(id, result).await;
+            }
+            "list-recent-tool-calls" => {
+                let outgoing = self.outgoing.clone();
+                task::spawn(async move {
+                    let result =
+                        crate::tool_audit::handle_list_recent_tool_calls(arguments, &audit).await;
+                    outgoing.send_response(id, result).await;
+                });
+            }
             _ => {
                 let result = CallToolResult::error(vec![rmcp::model::Content::text(format!(
                     "Unknown tool '{name}'"
@@ -0,0 +1,285 @@
+//! RA1 Art Batch tool - generates several images in one call with bounded
+//! concurrency, so agents producing sprite sheets or asset sets do not have
+//! to serialize dozens of individual generator calls.
+
+use crate::generation_usage::GenerationUsage;
+use crate::ra1_tool::Ra1ArtGeneratorOutput;
+use crate::ra1_tool::handle_ra1_art_generator;
+use crate::ra1_tool::tool_schema_for;
+use codex_core::config::Ra1ToolConfig;
+use rmcp::model::CallToolResult;
+use rmcp::model::Tool;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// Concurrent generations unless overridden via `max_concurrency`.
+const DEFAULT_MAX_CONCURRENCY: usize = 4;
+/// Hard ceiling so one call cannot saturate the API.
+const MAX_CONCURRENCY_LIMIT: usize = 8;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Ra1ArtBatchParams {
+    /// Prompts to generate, one image each.
+    pub prompts: Vec<String>,
+
+    /// Shared image size for the whole batch (e.g. "1024x1024"). Defaults to
+    /// the configured `[tools.ra1] default_size`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<String>,
+
+    /// Directory to save the generated images into. Defaults to "./assets/".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub save_to: Option<String>,
+
+    /// How many generations to run concurrently. Defaults to 4, capped at 8.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrency: Option<usize>,
+}
+
+/// Outcome of one prompt in the batch; failures are reported per item so a
+/// single bad prompt does not discard the rest of the sheet.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Ra1ArtBatchItem {
+    /// The prompt this item was generated from.
+    pub prompt: String,
+    /// Remote URL of the generated image, when generation succeeded.
+    pub image_url: Option<String>,
+    /// Path of the downloaded copy in the workspace, when available.
+    pub local_path: Option<String>,
+    /// Generation cost in USD, when generation succeeded.
+    pub cost: Option<String>,
+    /// Error message, when this item failed.
+    pub error: Option<String>,
+}
+
+/// Structured output returned in `CallToolResult.structured_content`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Ra1ArtBatchOutput {
+    /// Per-prompt results in the same order as the request.
+    pub items: Vec<Ra1ArtBatchItem>,
+    /// Number of prompts that generated successfully.
+    pub succeeded: usize,
+    /// Number of prompts that failed.
+    pub failed: usize,
+}
+
+pub fn create_tool_for_ra1_art_batch() -> Tool {
+    Tool {
+        name: "ra1-art-batch".into(),
+        title: Some("RA1 Art Batch".to_string()),
+        input_schema: tool_schema_for::<Ra1ArtBatchParams>(),
+        output_schema: Some(tool_schema_for::<Ra1ArtBatchOutput>()),
+        description: Some(
+            "Generate several AI images in one call with bounded concurrency. Returns a \
+             structured list of URLs with per-prompt failure reporting."
+                .into(),
+        ),
+        annotations: None,
+        execution: None,
+        icons: None,
+        meta: None,
+    }
+}
+
+fn error_result(msg: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![rmcp::model::Content::text(msg)],
+        is_error: Some(true),
+        structured_content: None,
+        meta: None,
+    }
+}
+
+/// Collapse one generator `CallToolResult` into a batch item, pulling the
+/// URL/path/cost out of its structured content and the error text out of the
+/// first text block on failure.
+fn batch_item_from_result(prompt: String, result: CallToolResult) -> Ra1ArtBatchItem {
+    if result.is_error == Some(true) {
+        let error = result
+            .content
+            .into_iter()
+            .find_map(|content| match content.raw {
+                rmcp::model::RawContent::Text(text) => Some(text.text),
+                _ => None,
+            })
+            .unwrap_or_else(|| "generation failed".to_string());
+        return Ra1ArtBatchItem {
+            prompt,
+            image_url: None,
+            local_path: None,
+            cost: None,
+            error: Some(error),
+        };
+    }
+    let output = result
+        .structured_content
+        .and_then(|value| serde_json::from_value::<Ra1ArtGeneratorOutput>(value).ok());
+    match output {
+        Some(output) => Ra1ArtBatchItem {
+            prompt,
+            image_url: Some(output.image_url),
+            local_path: output.local_path,
+            cost: Some(output.cost),
+            error: None,
+        },
+        None => Ra1ArtBatchItem {
+            prompt,
+            image_url: None,
+            local_path: None,
+            cost: None,
+            error: Some("generator returned no structured content".to_string()),
+        },
+    }
+}
+
+pub(crate) async fn handle_ra1_art_batch(
+    arguments: Option<serde_json::Map<String, serde_json::Value>>,
+    config: Ra1ToolConfig,
+    usage: Arc<GenerationUsage>,
+) -> CallToolResult {
+    let arguments = arguments.map(serde_json::Value::Object);
+    let params: Ra1ArtBatchParams = match arguments {
+        Some(json_val) => match serde_json::from_value(json_val) {
+            Ok(p) => p,
+            Err(e) => {
+                return error_result(format!("Failed to parse parameters: {e}"));
+            }
+        },
+        None => {
+            return error_result("Missing arguments; the `prompts` field is required.".to_string());
+        }
+    };
+
+    if params.prompts.is_empty() {
+        return error_result("`prompts` must contain at least one prompt.".to_string());
+    }
+
+    let max_concurrency = params
+        .max_concurrency
+        .unwrap_or(DEFAULT_MAX_CONCURRENCY)
+        .clamp(1, MAX_CONCURRENCY_LIMIT);
+    let semaphore = Arc::new(Semaphore::new(max_concurrency));
+
+    let mut handles = Vec::with_capacity(params.prompts.len());
+    for prompt in &params.prompts {
+        let prompt = prompt.clone();
+        let semaphore = semaphore.clone();
+        let config = config.clone();
+        let usage = usage.clone();
+        let mut item_args = serde_json::Map::new();
+        item_args.insert(
+            "prompt".to_string(),
+            serde_json::Value::String(prompt.clone()),
+        );
+        if let Some(size) = &params.size {
+            item_args.insert("size".to_string(), serde_json::Value::String(size.clone()));
+        }
+        if let Some(save_to) = &params.save_to {
+            item_args.insert(
+                "save_to".to_string(),
+                serde_json::Value::String(save_to.clone()),
+            );
+        }
+        handles.push(tokio::spawn(async move {
+            let _permit = semaphore.acquire().await;
+            let result = handle_ra1_art_generator(Some(item_args), &config, &usage).await;
+            batch_item_from_result(prompt, result)
+        }));
+    }
+
+    let mut items = Vec::with_capacity(handles.len());
+    for (handle, prompt) in handles.into_iter().zip(&params.prompts) {
+        match handle.await {
+            Ok(item) => items.push(item),
+            Err(e) => items.push(Ra1ArtBatchItem {
+                prompt: prompt.clone(),
+                image_url: None,
+                local_path: None,
+                cost: None,
+                error: Some(format!("generation task panicked: {e}")),
+            }),
+        }
+    }
+
+    let succeeded = items.iter().filter(|item| item.error.is_none()).count();
+    let failed = items.len() - succeeded;
+    let output = Ra1ArtBatchOutput {
+        items,
+        succeeded,
+        failed,
+    };
+
+    let mut lines = vec![format!(
+        "Batch generated {succeeded}/{} images ({failed} failed).",
+        output.items.len()
+    )];
+    for item in &output.items {
+        match (&item.image_url, &item.error) {
+            (Some(url), _) => lines.push(format!("- {}: {url}", item.prompt)),
+            (None, Some(error)) => lines.push(format!("- {}: FAILED ({error})", item.prompt)),
+            (None, None) => {}
+        }
+    }
+
+    CallToolResult {
+        content: vec![rmcp::model::Content::text(lines.join("\n"))],
+        is_error: Some(failed == output.items.len()),
+        structured_content: serde_json::to_value(&output).ok(),
+        meta: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_ra1_art_batch_tool_json_schema() {
+        let tool = create_tool_for_ra1_art_batch();
+        assert_eq!(tool.name.as_ref(), "ra1-art-batch");
+        let schema = serde_json::to_value(&tool.input_schema).unwrap();
+        let props = schema.get("properties").unwrap();
+        assert!(props.get("prompts").is_some());
+        assert!(props.get("size").is_some());
+        assert!(props.get("max_concurrency").is_some());
+        let required = schema.get("required").unwrap().as_array().unwrap();
+        assert!(required.iter().any(|v| v.as_str() == Some("prompts")));
+    }
+
+    #[test]
+    fn batch_item_from_error_result_captures_message() {
+        let result = error_result("API error: out of credits".to_string());
+        let item = batch_item_from_result("a cat".to_string(), result);
+        assert_eq!(item.prompt, "a cat");
+        assert!(item.image_url.is_none());
+        assert_eq!(item.error.as_deref(), Some("API error: out of credits"));
+    }
+
+    #[test]
+    fn batch_item_from_success_result_reads_structured_content() {
+        let output = Ra1ArtGeneratorOutput {
+            image_url: "https://cdn.netwrck.com/art/a.png".to_string(),
+            local_path: Some("assets/a.png".to_string()),
+            prompt_used: "a cat".to_string(),
+            size_used: "1024x1024".to_string(),
+            cost: "0.04".to_string(),
+        };
+        let result = CallToolResult {
+            content: vec![],
+            is_error: Some(false),
+            structured_content: serde_json::to_value(&output).ok(),
+            meta: None,
+        };
+        let item = batch_item_from_result("a cat".to_string(), result);
+        assert_eq!(
+            item.image_url.as_deref(),
+            Some("https://cdn.netwrck.com/art/a.png")
+        );
+        assert_eq!(item.local_path.as_deref(), Some("assets/a.png"));
+        assert_eq!(item.cost.as_deref(), Some("0.04"));
+        assert!(item.error.is_none());
+    }
+}
@@ -0,0 +1,267 @@
+//! RA1 Image Edit tool - edits/inpaints an existing image via netwrck.com API.
+//!
+//! Accepts a source image (local path or URL) and an optional mask, so agents
+//! can iterate on an existing asset instead of regenerating it from scratch.
+
+use crate::generation_usage::GenerationUsage;
+use base64::Engine;
+use codex_core::config::Ra1ToolConfig;
+use rmcp::model::CallToolResult;
+use rmcp::model::Tool;
+use schemars::JsonSchema;
+use schemars::r#gen::SchemaSettings;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Map as JsonObject;
+use std::env;
+use std::path::Path;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Ra1ImageEditParams {
+    /// The prompt describing the edit to apply.
+    pub prompt: String,
+
+    /// Source image to edit: a local file path or an http(s) URL.
+    pub image: String,
+
+    /// Optional mask image (local file path or http(s) URL). White areas of
+    /// the mask are regions to be repainted; when omitted the whole image may
+    /// be modified.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mask: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Ra1ImageEditResponse {
+    pub image_url: String,
+    pub prompt_used: String,
+    pub cost: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Ra1ImageEditError {
+    pub error: String,
+}
+
+pub fn create_tool_for_ra1_image_edit() -> Tool {
+    let schema = SchemaSettings::draft2019_09()
+        .with(|s| {
+            s.inline_subschemas = true;
+            s.option_add_null_type = false;
+        })
+        .into_generator()
+        .into_root_schema_for::<Ra1ImageEditParams>();
+
+    #[expect(clippy::expect_used)]
+    let schema_value =
+        serde_json::to_value(&schema).expect("RA1 image edit tool schema should serialise to JSON");
+
+    let mut schema_object = match schema_value {
+        serde_json::Value::Object(object) => object,
+        _ => panic!("tool schema should serialize to a JSON object"),
+    };
+    let mut input_schema = JsonObject::new();
+    for key in ["properties", "required", "type", "$defs", "definitions"] {
+        if let Some(val) = schema_object.remove(key) {
+            input_schema.insert(key.to_string(), val);
+        }
+    }
+    let tool_input_schema = Arc::new(input_schema);
+
+    Tool {
+        name: "ra1-image-edit".into(),
+        title: Some("RA1 Image Edit".to_string()),
+        input_schema: tool_input_schema,
+        output_schema: None,
+        description: Some(
+            "Edit or inpaint an existing image using the RA1 image editor. Accepts a source \
+             image (path or URL), an optional mask, and a prompt. Returns an image URL."
+                .into(),
+        ),
+        annotations: None,
+        execution: None,
+        icons: None,
+        meta: None,
+    }
+}
+
+fn error_result(msg: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![rmcp::model::Content::text(msg)],
+        is_error: Some(true),
+        structured_content: None,
+        meta: None,
+    }
+}
+
+/// Resolve an image reference into the request body fields the API expects:
+/// URLs are passed through under `{field}_url`, local paths are read and sent
+/// base64-encoded under `{field}_b64`.
+async fn insert_image_field(
+    body: &mut serde_json::Map<String, serde_json::Value>,
+    field: &str,
+    reference: &str,
+) -> Result<(), String> {
+    if reference.starts_with("http://") || reference.starts_with("https://") {
+        body.insert(
+            format!("{field}_url"),
+            serde_json::Value::String(reference.to_string()),
+        );
+        return Ok(());
+    }
+    let path = Path::new(reference);
+    let bytes = tokio::fs::read(path)
+        .await
+        .map_err(|e| format!("Failed to read {field} file `{reference}`: {e}"))?;
+    let encoded = base64::engine::general_purpose::STANDARD.encode(bytes);
+    body.insert(format!("{field}_b64"), serde_json::Value::String(encoded));
+    Ok(())
+}
+
+pub(crate) async fn handle_ra1_image_edit(
+    arguments: Option<serde_json::Map<String, serde_json::Value>>,
+    config: &Ra1ToolConfig,
+    usage: &GenerationUsage,
+) -> CallToolResult {
+    if let Err(msg) = usage.ensure_within_budget() {
+        return error_result(msg);
+    }
+    let arguments = arguments.map(serde_json::Value::Object);
+    let api_key = match env::var(&config.api_key_env) {
+        Ok(key) => key,
+        Err(_) => {
+            return error_result(format!(
+                "{} environment variable not set",
+                config.api_key_env
+            ));
+        }
+    };
+
+    let params: Ra1ImageEditParams = match arguments {
+        Some(json_val) => match serde_json::from_value(json_val) {
+            Ok(p) => p,
+            Err(e) => {
+                return error_result(format!("Failed to parse parameters: {e}"));
+            }
+        },
+        None => {
+            return error_result(
+                "Missing arguments; the `prompt` and `image` fields are required.".to_string(),
+            );
+        }
+    };
+
+    let mut request_body = serde_json::Map::new();
+    request_body.insert(
+        "api_key".to_string(),
+        serde_json::Value::String(api_key.clone()),
+    );
+    request_body.insert(
+        "prompt".to_string(),
+        serde_json::Value::String(params.prompt.clone()),
+    );
+    if let Err(msg) = insert_image_field(&mut request_body, "image", &params.image).await {
+        return error_result(msg);
+    }
+    if let Some(mask) = &params.mask
+        && let Err(msg) = insert_image_field(&mut request_body, "mask", mask).await
+    {
+        return error_result(msg);
+    }
+
+    let client = reqwest::Client::new();
+    let url = format!("{}/ra1-image-edit", config.base_url);
+    let response = match client
+        .post(&url)
+        .header("Content-Type", "application/json")
+        .json(&serde_json::Value::Object(request_body))
+        .send()
+        .await
+    {
+        Ok(r) => r,
+        Err(e) => {
+            return error_result(format!("HTTP request failed: {e}"));
+        }
+    };
+
+    let status = response.status();
+    let body = match response.text().await {
+        Ok(b) => b,
+        Err(e) => {
+            return error_result(format!("Failed to read response body: {e}"));
+        }
+    };
+
+    if !status.is_success() {
+        if let Ok(err) = serde_json::from_str::<Ra1ImageEditError>(&body) {
+            return error_result(format!("API error: {}", err.error));
+        }
+        return error_result(format!("API error ({status}): {body}"));
+    }
+
+    match serde_json::from_str::<Ra1ImageEditResponse>(&body) {
+        Ok(resp) => {
+            usage.record_cost(&resp.cost);
+            CallToolResult {
+                content: vec![rmcp::model::Content::text(format!(
+                    "Image edited successfully!\nURL: {}\nPrompt: {}\nCost: ${}",
+                    resp.image_url, resp.prompt_used, resp.cost
+                ))],
+                is_error: Some(false),
+                structured_content: None,
+                meta: None,
+            }
+        }
+        Err(e) => error_result(format!("Failed to parse API response: {e}\nRaw: {body}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_ra1_image_edit_tool_json_schema() {
+        let tool = create_tool_for_ra1_image_edit();
+        assert_eq!(tool.name.as_ref(), "ra1-image-edit");
+        assert!(tool.description.is_some());
+        let schema = serde_json::to_value(&tool.input_schema).unwrap();
+        let props = schema.get("properties").unwrap();
+        assert!(props.get("prompt").is_some());
+        assert!(props.get("image").is_some());
+        assert!(props.get("mask").is_some());
+        let required = schema.get("required").unwrap().as_array().unwrap();
+        assert!(required.iter().any(|v| v.as_str() == Some("prompt")));
+        assert!(required.iter().any(|v| v.as_str() == Some("image")));
+    }
+
+    #[tokio::test]
+    async fn insert_image_field_passes_urls_through() {
+        let mut body = serde_json::Map::new();
+        insert_image_field(&mut body, "image", "https://example.com/cat.png")
+            .await
+            .unwrap();
+        assert_eq!(
+            body.get("image_url").and_then(|v| v.as_str()),
+            Some("https://example.com/cat.png")
+        );
+        assert!(!body.contains_key("image_b64"));
+    }
+
+    #[tokio::test]
+    async fn insert_image_field_encodes_local_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mask.png");
+        tokio::fs::write(&path, b"not-a-real-png").await.unwrap();
+        let mut body = serde_json::Map::new();
+        insert_image_field(&mut body, "mask", path.to_str().unwrap())
+            .await
+            .unwrap();
+        let encoded = body.get("mask_b64").and_then(|v| v.as_str()).unwrap();
+        let decoded = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .unwrap();
+        assert_eq!(decoded, b"not-a-real-png");
+    }
+}
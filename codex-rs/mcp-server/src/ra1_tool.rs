@@ -1,5 +1,9 @@
 //! RA1 Art Generator tool - generates AI images via netwrck.com API.
 
+use crate::generation_usage::GenerationUsage;
+use base64::Engine;
+use codex_client::backoff;
+use codex_core::config::Ra1ToolConfig;
 use rmcp::model::CallToolResult;
 use rmcp::model::Tool;
 use schemars::JsonSchema;
@@ -9,18 +13,33 @@ use serde::Serialize;
 use serde_json::Map as JsonObject;
 use std::env;
 use std::sync::Arc;
+use std::time::Duration;
 
-const NETWRCK_API_KEY_ENV: &str = "NETWRCK_API_KEY";
-const RA1_API_URL: &str = "https://netwrck.com/api/ra1-art-generator";
+/// Overall per-request timeout unless overridden via `timeout_seconds`.
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+/// Retries on 429/5xx/transport errors before giving up.
+const MAX_RETRY_ATTEMPTS: u64 = 3;
+/// Base delay fed into the shared exponential backoff.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Ra1ArtGeneratorParams {
     /// The prompt describing the image to generate.
     pub prompt: String,
 
-    /// Image size (e.g. "1024x1024", "1360x768"). Defaults to "1024x1024".
+    /// Image size (e.g. "1024x1024", "1360x768"). Defaults to the configured
+    /// `[tools.ra1] default_size` ("1024x1024" unless overridden).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub size: Option<String>,
+
+    /// Directory to save the generated image into. Defaults to "./assets/".
+    /// The remote URL expires, so the image is always downloaded locally.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub save_to: Option<String>,
+
+    /// Overall request timeout in seconds. Defaults to 120.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_seconds: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -36,18 +55,37 @@ pub struct Ra1ArtGeneratorError {
     pub error: String,
 }
 
-pub fn is_ra1_available() -> bool {
-    env::var(NETWRCK_API_KEY_ENV).is_ok()
+/// Structured output returned in `CallToolResult.structured_content`,
+/// matching the tool's declared `output_schema`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Ra1ArtGeneratorOutput {
+    /// Remote URL of the generated image. Expires after a while.
+    pub image_url: String,
+    /// Path of the downloaded copy in the workspace, when the download
+    /// succeeded.
+    pub local_path: Option<String>,
+    /// Prompt the generator actually used.
+    pub prompt_used: String,
+    /// Size the generator actually used.
+    pub size_used: String,
+    /// Generation cost in USD.
+    pub cost: String,
 }
 
-pub fn create_tool_for_ra1_art_generator() -> Tool {
+pub fn is_ra1_available(config: &Ra1ToolConfig) -> bool {
+    config.enabled && env::var(&config.api_key_env).is_ok()
+}
+
+/// Generate a trimmed JSON schema object for `T` in the shape rmcp expects
+/// for tool input/output schemas.
+pub(crate) fn tool_schema_for<T: JsonSchema>() -> Arc<JsonObject<String, serde_json::Value>> {
     let schema = SchemaSettings::draft2019_09()
         .with(|s| {
             s.inline_subschemas = true;
             s.option_add_null_type = false;
         })
         .into_generator()
-        .into_root_schema_for::<Ra1ArtGeneratorParams>();
+        .into_root_schema_for::<T>();
 
     #[expect(clippy::expect_used)]
     let schema_value =
@@ -57,19 +95,21 @@ pub fn create_tool_for_ra1_art_generator() -> Tool {
         serde_json::Value::Object(object) => object,
         _ => panic!("tool schema should serialize to a JSON object"),
     };
-    let mut input_schema = JsonObject::new();
+    let mut trimmed = JsonObject::new();
     for key in ["properties", "required", "type", "$defs", "definitions"] {
         if let Some(val) = schema_object.remove(key) {
-            input_schema.insert(key.to_string(), val);
+            trimmed.insert(key.to_string(), val);
         }
     }
-    let tool_input_schema = Arc::new(input_schema);
+    Arc::new(trimmed)
+}
 
+pub fn create_tool_for_ra1_art_generator() -> Tool {
     Tool {
         name: "ra1-art-generator".into(),
         title: Some("RA1 Art Generator".to_string()),
-        input_schema: tool_input_schema,
-        output_schema: None,
+        input_schema: tool_schema_for::<Ra1ArtGeneratorParams>(),
+        output_schema: Some(tool_schema_for::<Ra1ArtGeneratorOutput>()),
         description: Some(
             "Generate AI images using the RA1 art generator. Returns an image URL.".into(),
         ),
@@ -80,6 +120,127 @@ pub fn create_tool_for_ra1_art_generator() -> Tool {
     }
 }
 
+/// Pick a filename for a downloaded image from the last path segment of its
+/// URL, falling back to a timestamped name when the URL has no usable segment.
+fn filename_from_url(url: &str) -> String {
+    let trimmed = url.split(['?', '#']).next().unwrap_or(url);
+    match trimmed.rsplit('/').next() {
+        Some(segment) if !segment.is_empty() && segment.contains('.') => segment.to_string(),
+        _ => {
+            let secs = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default();
+            format!("ra1-{secs}.png")
+        }
+    }
+}
+
+/// MIME type for a downloaded image, inferred from its filename extension.
+fn mime_type_for(path: &std::path::Path) -> &'static str {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        _ => "image/png",
+    }
+}
+
+/// Download the generated image into `save_to` (created if necessary) and
+/// return the path of the written file along with the raw bytes.
+async fn download_image(
+    client: &reqwest::Client,
+    image_url: &str,
+    save_to: &str,
+) -> Result<(std::path::PathBuf, Vec<u8>), String> {
+    let response = client
+        .get(image_url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download image: {e}"))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "Failed to download image: server returned {}",
+            response.status()
+        ));
+    }
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to read image bytes: {e}"))?;
+
+    let dir = std::path::Path::new(save_to);
+    tokio::fs::create_dir_all(dir)
+        .await
+        .map_err(|e| format!("Failed to create directory `{save_to}`: {e}"))?;
+    let path = dir.join(filename_from_url(image_url));
+    tokio::fs::write(&path, &bytes)
+        .await
+        .map_err(|e| format!("Failed to write image to {}: {e}", path.display()))?;
+    Ok((path, bytes.to_vec()))
+}
+
+/// POST `body` to `url`, retrying 429/5xx responses and transient transport
+/// failures with the same exponential backoff `codex-client` applies to
+/// provider requests. Returns the final status and response body.
+pub(crate) async fn post_with_retry(
+    client: &reqwest::Client,
+    url: &str,
+    body: &serde_json::Value,
+) -> Result<(reqwest::StatusCode, String), String> {
+    let mut attempt = 0u64;
+    loop {
+        let response = client
+            .post(url)
+            .header("Content-Type", "application/json")
+            .json(body)
+            .send()
+            .await;
+        match response {
+            Ok(resp) => {
+                let status = resp.status();
+                let retry_after = resp
+                    .headers()
+                    .get(reqwest::header::RETRY_AFTER)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.parse::<u64>().ok());
+                let text = resp
+                    .text()
+                    .await
+                    .map_err(|e| format!("Failed to read response body: {e}"))?;
+                let retryable = status.as_u16() == 429 || status.is_server_error();
+                if !retryable {
+                    return Ok((status, text));
+                }
+                if attempt >= MAX_RETRY_ATTEMPTS {
+                    if status.as_u16() == 429 {
+                        let after = retry_after.unwrap_or(30);
+                        return Err(format!(
+                            "Rate limited by the netwrck API; retry after {after}s."
+                        ));
+                    }
+                    return Ok((status, text));
+                }
+                attempt += 1;
+                // Honour Retry-After when the server provides one; otherwise
+                // fall back to jittered exponential backoff.
+                let delay = retry_after
+                    .map(Duration::from_secs)
+                    .unwrap_or_else(|| backoff(RETRY_BASE_DELAY, attempt));
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) if e.is_timeout() || e.is_connect() || e.is_request() => {
+                if attempt >= MAX_RETRY_ATTEMPTS {
+                    return Err(format!("HTTP request failed after {attempt} retries: {e}"));
+                }
+                attempt += 1;
+                tokio::time::sleep(backoff(RETRY_BASE_DELAY, attempt)).await;
+            }
+            Err(e) => return Err(format!("HTTP request failed: {e}")),
+        }
+    }
+}
+
 fn error_result(msg: String) -> CallToolResult {
     CallToolResult {
         content: vec![rmcp::model::Content::text(msg)],
@@ -89,15 +250,21 @@ fn error_result(msg: String) -> CallToolResult {
     }
 }
 
-pub async fn handle_ra1_art_generator(
+pub(crate) async fn handle_ra1_art_generator(
     arguments: Option<serde_json::Map<String, serde_json::Value>>,
+    config: &Ra1ToolConfig,
+    usage: &GenerationUsage,
 ) -> CallToolResult {
+    if let Err(msg) = usage.ensure_within_budget() {
+        return error_result(msg);
+    }
     let arguments = arguments.map(serde_json::Value::Object);
-    let api_key = match env::var(NETWRCK_API_KEY_ENV) {
+    let api_key = match env::var(&config.api_key_env) {
         Ok(key) => key,
         Err(_) => {
             return error_result(format!(
-                "{NETWRCK_API_KEY_ENV} environment variable not set"
+                "{} environment variable not set",
+                config.api_key_env
             ));
         }
     };
@@ -114,33 +281,29 @@ pub async fn handle_ra1_art_generator(
         }
     };
 
-    let size = params.size.unwrap_or_else(|| "1024x1024".to_string());
+    let size = params.size.unwrap_or_else(|| config.default_size.clone());
+    let timeout = params
+        .timeout_seconds
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_REQUEST_TIMEOUT);
 
-    let client = reqwest::Client::new();
+    let client = match reqwest::Client::builder().timeout(timeout).build() {
+        Ok(client) => client,
+        Err(e) => {
+            return error_result(format!("Failed to build HTTP client: {e}"));
+        }
+    };
     let request_body = serde_json::json!({
         "api_key": api_key,
         "prompt": params.prompt,
         "size": size
     });
 
-    let response = match client
-        .post(RA1_API_URL)
-        .header("Content-Type", "application/json")
-        .json(&request_body)
-        .send()
-        .await
-    {
-        Ok(r) => r,
-        Err(e) => {
-            return error_result(format!("HTTP request failed: {e}"));
-        }
-    };
-
-    let status = response.status();
-    let body = match response.text().await {
-        Ok(b) => b,
-        Err(e) => {
-            return error_result(format!("Failed to read response body: {e}"));
+    let url = format!("{}/ra1-art-generator", config.base_url);
+    let (status, body) = match post_with_retry(&client, &url, &request_body).await {
+        Ok(result) => result,
+        Err(msg) => {
+            return error_result(msg);
         }
     };
 
@@ -148,20 +311,57 @@ pub async fn handle_ra1_art_generator(
         if let Ok(err) = serde_json::from_str::<Ra1ArtGeneratorError>(&body) {
             return error_result(format!("API error: {}", err.error));
         }
-        return error_result(format!("API error ({}): {body}", status));
-    }
-
-    match serde_json::from_str::<Ra1ArtGeneratorResponse>(&body) {
-        Ok(resp) => CallToolResult {
-            content: vec![rmcp::model::Content::text(format!(
-                "Image generated successfully!\nURL: {}\nPrompt: {}\nSize: {}\nCost: ${}",
-                resp.image_url, resp.prompt_used, resp.size_used, resp.cost
-            ))],
-            is_error: Some(false),
-            structured_content: None,
-            meta: None,
-        },
-        Err(e) => error_result(format!("Failed to parse API response: {e}\nRaw: {body}")),
+        return error_result(format!("API error ({status}): {body}"));
+    }
+
+    let resp = match serde_json::from_str::<Ra1ArtGeneratorResponse>(&body) {
+        Ok(resp) => resp,
+        Err(e) => {
+            return error_result(format!("Failed to parse API response: {e}\nRaw: {body}"));
+        }
+    };
+    usage.record_cost(&resp.cost);
+
+    // The remote URL expires, so download the image into the workspace and
+    // hand back a stable local path alongside it.
+    let save_to = params.save_to.as_deref().unwrap_or("./assets/");
+    let (local_path_line, local_path, image_block) =
+        match download_image(&client, &resp.image_url, save_to).await {
+            Ok((path, bytes)) => {
+                let encoded = base64::engine::general_purpose::STANDARD.encode(&bytes);
+                let block = rmcp::model::Content::image(encoded, mime_type_for(&path));
+                (
+                    format!("Local path: {}\n", path.display()),
+                    Some(path.display().to_string()),
+                    Some(block),
+                )
+            }
+            Err(e) => (format!("Warning: {e}\n"), None, None),
+        };
+
+    let output = Ra1ArtGeneratorOutput {
+        image_url: resp.image_url,
+        local_path,
+        prompt_used: resp.prompt_used,
+        size_used: resp.size_used,
+        cost: resp.cost,
+    };
+
+    let mut content = vec![rmcp::model::Content::text(format!(
+        "Image generated successfully!\nURL: {}\n{local_path_line}Prompt: {}\nSize: {}\nCost: ${}",
+        output.image_url, output.prompt_used, output.size_used, output.cost
+    ))];
+    // Include an inline image block so MCP clients can render the result
+    // without fetching the URL.
+    if let Some(block) = image_block {
+        content.push(block);
+    }
+
+    CallToolResult {
+        content,
+        is_error: Some(false),
+        structured_content: serde_json::to_value(&output).ok(),
+        meta: None,
     }
 }
 
@@ -183,9 +383,60 @@ mod tests {
     }
 
     #[test]
-    fn is_ra1_available_respects_env() {
+    fn verify_ra1_tool_output_schema() {
+        let tool = create_tool_for_ra1_art_generator();
+        let output_schema = tool
+            .output_schema
+            .expect("output schema should be declared");
+        let schema = serde_json::to_value(&output_schema).unwrap();
+        let props = schema.get("properties").unwrap();
+        for field in [
+            "image_url",
+            "local_path",
+            "prompt_used",
+            "size_used",
+            "cost",
+        ] {
+            assert!(
+                props.get(field).is_some(),
+                "missing {field} in output schema"
+            );
+        }
+    }
+
+    #[test]
+    fn mime_type_for_maps_known_extensions() {
+        assert_eq!(mime_type_for(std::path::Path::new("a.jpg")), "image/jpeg");
+        assert_eq!(mime_type_for(std::path::Path::new("a.webp")), "image/webp");
+        assert_eq!(mime_type_for(std::path::Path::new("a")), "image/png");
+    }
+
+    #[test]
+    fn filename_from_url_uses_last_segment() {
+        assert_eq!(
+            filename_from_url("https://cdn.netwrck.com/art/abc123.png?expires=99"),
+            "abc123.png"
+        );
+    }
+
+    #[test]
+    fn filename_from_url_falls_back_without_extension() {
+        let name = filename_from_url("https://cdn.netwrck.com/art/");
+        assert!(name.starts_with("ra1-"));
+        assert!(name.ends_with(".png"));
+    }
+
+    #[test]
+    fn is_ra1_available_respects_env_and_enabled_flag() {
+        let config = Ra1ToolConfig::default();
         // SAFETY: This is a test and we're only removing a test env var
-        unsafe { std::env::remove_var(NETWRCK_API_KEY_ENV) };
-        assert!(!is_ra1_available());
+        unsafe { std::env::remove_var(&config.api_key_env) };
+        assert!(!is_ra1_available(&config));
+
+        let disabled = Ra1ToolConfig {
+            enabled: false,
+            ..Ra1ToolConfig::default()
+        };
+        assert!(!is_ra1_available(&disabled));
     }
 }
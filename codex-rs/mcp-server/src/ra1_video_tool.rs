@@ -0,0 +1,306 @@
+//! RA1 Video Generator tool - generates short AI video clips via netwrck.com API.
+//!
+//! Unlike the art generator, video generation is asynchronous on the server
+//! side: the initial request submits a job, and the tool polls the job status
+//! until the clip is ready, emitting progress notifications along the way.
+
+use crate::generation_usage::GenerationUsage;
+use crate::outgoing_message::OutgoingMessageSender;
+use crate::outgoing_message::OutgoingNotification;
+use codex_core::config::Ra1ToolConfig;
+use rmcp::model::CallToolResult;
+use rmcp::model::Tool;
+use schemars::JsonSchema;
+use schemars::r#gen::SchemaSettings;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Map as JsonObject;
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How long to wait between job status polls.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Give up after this many polls (10 minutes at the current interval).
+const MAX_POLL_ATTEMPTS: u32 = 300;
+/// Notification method used for job progress updates.
+const PROGRESS_NOTIFICATION_METHOD: &str = "netwrck/video_progress";
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Ra1VideoGeneratorParams {
+    /// The prompt describing the video to generate.
+    pub prompt: String,
+
+    /// Clip duration in seconds. Defaults to 5.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_seconds: Option<u32>,
+
+    /// Video size (e.g. "1280x720", "768x768"). Defaults to "1280x720".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Ra1VideoSubmitResponse {
+    pub job_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Ra1VideoJobStatus {
+    pub status: String,
+    #[serde(default)]
+    pub progress: Option<f64>,
+    #[serde(default)]
+    pub video_url: Option<String>,
+    #[serde(default)]
+    pub cost: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Ra1VideoGeneratorError {
+    pub error: String,
+}
+
+pub fn create_tool_for_ra1_video_generator() -> Tool {
+    let schema = SchemaSettings::draft2019_09()
+        .with(|s| {
+            s.inline_subschemas = true;
+            s.option_add_null_type = false;
+        })
+        .into_generator()
+        .into_root_schema_for::<Ra1VideoGeneratorParams>();
+
+    #[expect(clippy::expect_used)]
+    let schema_value =
+        serde_json::to_value(&schema).expect("RA1 video tool schema should serialise to JSON");
+
+    let mut schema_object = match schema_value {
+        serde_json::Value::Object(object) => object,
+        _ => panic!("tool schema should serialize to a JSON object"),
+    };
+    let mut input_schema = JsonObject::new();
+    for key in ["properties", "required", "type", "$defs", "definitions"] {
+        if let Some(val) = schema_object.remove(key) {
+            input_schema.insert(key.to_string(), val);
+        }
+    }
+    let tool_input_schema = Arc::new(input_schema);
+
+    Tool {
+        name: "ra1-video-generator".into(),
+        title: Some("RA1 Video Generator".to_string()),
+        input_schema: tool_input_schema,
+        output_schema: None,
+        description: Some(
+            "Generate short AI video clips using the RA1 video generator. Returns a video URL."
+                .into(),
+        ),
+        annotations: None,
+        execution: None,
+        icons: None,
+        meta: None,
+    }
+}
+
+fn error_result(msg: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![rmcp::model::Content::text(msg)],
+        is_error: Some(true),
+        structured_content: None,
+        meta: None,
+    }
+}
+
+async fn notify_progress(
+    outgoing: &OutgoingMessageSender,
+    job_id: &str,
+    status: &Ra1VideoJobStatus,
+) {
+    outgoing
+        .send_notification(OutgoingNotification {
+            method: PROGRESS_NOTIFICATION_METHOD.to_string(),
+            params: Some(serde_json::json!({
+                "job_id": job_id,
+                "status": status.status,
+                "progress": status.progress,
+            })),
+        })
+        .await;
+}
+
+pub(crate) async fn handle_ra1_video_generator(
+    arguments: Option<serde_json::Map<String, serde_json::Value>>,
+    outgoing: Arc<OutgoingMessageSender>,
+    config: &Ra1ToolConfig,
+    usage: &GenerationUsage,
+) -> CallToolResult {
+    if let Err(msg) = usage.ensure_within_budget() {
+        return error_result(msg);
+    }
+    let arguments = arguments.map(serde_json::Value::Object);
+    let api_key = match env::var(&config.api_key_env) {
+        Ok(key) => key,
+        Err(_) => {
+            return error_result(format!(
+                "{} environment variable not set",
+                config.api_key_env
+            ));
+        }
+    };
+
+    let params: Ra1VideoGeneratorParams = match arguments {
+        Some(json_val) => match serde_json::from_value(json_val) {
+            Ok(p) => p,
+            Err(e) => {
+                return error_result(format!("Failed to parse parameters: {e}"));
+            }
+        },
+        None => {
+            return error_result("Missing arguments; the `prompt` field is required.".to_string());
+        }
+    };
+
+    let duration_seconds = params.duration_seconds.unwrap_or(5);
+    let size = params.size.unwrap_or_else(|| "1280x720".to_string());
+
+    let client = reqwest::Client::new();
+    let request_body = serde_json::json!({
+        "api_key": api_key,
+        "prompt": params.prompt,
+        "duration_seconds": duration_seconds,
+        "size": size
+    });
+
+    let submit_url = format!("{}/ra1-video-generator", config.base_url);
+    let response = match client
+        .post(&submit_url)
+        .header("Content-Type", "application/json")
+        .json(&request_body)
+        .send()
+        .await
+    {
+        Ok(r) => r,
+        Err(e) => {
+            return error_result(format!("HTTP request failed: {e}"));
+        }
+    };
+
+    let status = response.status();
+    let body = match response.text().await {
+        Ok(b) => b,
+        Err(e) => {
+            return error_result(format!("Failed to read response body: {e}"));
+        }
+    };
+
+    if !status.is_success() {
+        if let Ok(err) = serde_json::from_str::<Ra1VideoGeneratorError>(&body) {
+            return error_result(format!("API error: {}", err.error));
+        }
+        return error_result(format!("API error ({status}): {body}"));
+    }
+
+    let job_id = match serde_json::from_str::<Ra1VideoSubmitResponse>(&body) {
+        Ok(resp) => resp.job_id,
+        Err(e) => {
+            return error_result(format!("Failed to parse submit response: {e}\nRaw: {body}"));
+        }
+    };
+
+    // Poll the job until it completes, fails, or we give up.
+    for _ in 0..MAX_POLL_ATTEMPTS {
+        tokio::time::sleep(POLL_INTERVAL).await;
+
+        let poll_url = format!("{submit_url}/jobs/{job_id}");
+        let response = match client
+            .get(&poll_url)
+            .query(&[("api_key", api_key.as_str())])
+            .send()
+            .await
+        {
+            Ok(r) => r,
+            Err(e) => {
+                return error_result(format!("Job status request failed: {e}"));
+            }
+        };
+
+        let status_code = response.status();
+        let body = match response.text().await {
+            Ok(b) => b,
+            Err(e) => {
+                return error_result(format!("Failed to read job status body: {e}"));
+            }
+        };
+
+        if !status_code.is_success() {
+            if let Ok(err) = serde_json::from_str::<Ra1VideoGeneratorError>(&body) {
+                return error_result(format!("API error: {}", err.error));
+            }
+            return error_result(format!("API error ({status_code}): {body}"));
+        }
+
+        let job_status = match serde_json::from_str::<Ra1VideoJobStatus>(&body) {
+            Ok(s) => s,
+            Err(e) => {
+                return error_result(format!("Failed to parse job status: {e}\nRaw: {body}"));
+            }
+        };
+
+        notify_progress(&outgoing, &job_id, &job_status).await;
+
+        match job_status.status.as_str() {
+            "completed" => {
+                let Some(video_url) = job_status.video_url else {
+                    return error_result(
+                        "Job reported completed but did not include a video URL.".to_string(),
+                    );
+                };
+                let cost = job_status.cost.unwrap_or_else(|| "unknown".to_string());
+                usage.record_cost(&cost);
+                return CallToolResult {
+                    content: vec![rmcp::model::Content::text(format!(
+                        "Video generated successfully!\nURL: {video_url}\nPrompt: {}\nDuration: {duration_seconds}s\nSize: {size}\nCost: ${cost}",
+                        params.prompt
+                    ))],
+                    is_error: Some(false),
+                    structured_content: None,
+                    meta: None,
+                };
+            }
+            "failed" => {
+                let reason = job_status
+                    .error
+                    .unwrap_or_else(|| "no error details provided".to_string());
+                return error_result(format!("Video generation failed: {reason}"));
+            }
+            // "queued" and "processing" (and any future intermediate states)
+            // fall through to the next poll.
+            _ => {}
+        }
+    }
+
+    error_result(format!(
+        "Timed out waiting for video job {job_id} to complete."
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_ra1_video_tool_json_schema() {
+        let tool = create_tool_for_ra1_video_generator();
+        assert_eq!(tool.name.as_ref(), "ra1-video-generator");
+        assert!(tool.description.is_some());
+        let schema = serde_json::to_value(&tool.input_schema).unwrap();
+        let props = schema.get("properties").unwrap();
+        assert!(props.get("prompt").is_some());
+        assert!(props.get("duration_seconds").is_some());
+        assert!(props.get("size").is_some());
+        let required = schema.get("required").unwrap().as_array().unwrap();
+        assert!(required.iter().any(|v| v.as_str() == Some("prompt")));
+    }
+}
@@ -0,0 +1,374 @@
+//! Tool invocation audit log - records every built-in tool call (name,
+//! redacted arguments, duration, outcome, cost) to a JSONL file under
+//! `codex_home` so operators can audit what generation tools agents invoked
+//! and at what cost. The `codex`/`codex-reply` session tools are excluded;
+//! their transcripts are already persisted as rollouts.
+//!
+//! The log rotates once it exceeds a size cap, keeping one previous
+//! generation, and is surfaced back through a `list-recent-tool-calls` tool.
+
+use crate::ra1_tool::tool_schema_for;
+use rmcp::model::CallToolResult;
+use rmcp::model::Tool;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Instant;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+use tokio::io::AsyncWriteExt;
+
+const LOG_FILE_NAME: &str = "tool_calls.jsonl";
+/// Rotate once the log grows past this size; one previous generation is kept.
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+/// Argument strings longer than this are truncated in the log.
+const MAX_ARGUMENT_STRING_LEN: usize = 256;
+/// Argument keys containing any of these fragments have their values
+/// replaced, so credentials passed as tool parameters never reach the log.
+const SENSITIVE_KEY_FRAGMENTS: &[&str] = &[
+    "key",
+    "token",
+    "secret",
+    "password",
+    "authorization",
+    "credential",
+];
+const REDACTED_PLACEHOLDER: &str = "[redacted]";
+
+/// Entries returned unless overridden via `limit`.
+const DEFAULT_LIST_LIMIT: usize = 20;
+/// Upper bound on `limit` to keep responses compact.
+const MAX_LIST_LIMIT: usize = 100;
+
+/// One line in the audit log.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ToolCallRecord {
+    /// Unix timestamp of the call in milliseconds.
+    pub timestamp_ms: u64,
+    /// Tool name as listed in `tools/list`.
+    pub tool: String,
+    /// Call arguments with sensitive values redacted and long strings
+    /// truncated.
+    pub arguments: serde_json::Value,
+    pub duration_ms: u64,
+    pub success: bool,
+    /// Error message, when the call failed.
+    pub error: Option<String>,
+    /// Generation cost in USD, when the tool reported one.
+    pub cost: Option<String>,
+}
+
+/// Appends one record per tool call to `codex_home/tool_calls.jsonl`.
+pub(crate) struct ToolAuditLog {
+    path: PathBuf,
+}
+
+impl ToolAuditLog {
+    pub(crate) fn new(codex_home: PathBuf) -> Self {
+        Self {
+            path: codex_home.join(LOG_FILE_NAME),
+        }
+    }
+
+    /// Record one finished tool call. Logging failures are reported via
+    /// tracing and never surfaced to the caller.
+    pub(crate) async fn record(
+        &self,
+        tool: &str,
+        arguments: Option<serde_json::Map<String, serde_json::Value>>,
+        started: Instant,
+        result: &CallToolResult,
+    ) {
+        let mut arguments = serde_json::Value::Object(arguments.unwrap_or_default());
+        redact_arguments(&mut arguments);
+
+        let success = result.is_error != Some(true);
+        let error = if success {
+            None
+        } else {
+            result
+                .content
+                .iter()
+                .find_map(|content| match &content.raw {
+                    rmcp::model::RawContent::Text(text) => Some(text.text.clone()),
+                    _ => None,
+                })
+        };
+        let cost = result
+            .structured_content
+            .as_ref()
+            .and_then(|value| value.get("cost"))
+            .and_then(|cost| cost.as_str())
+            .map(str::to_string);
+
+        let record = ToolCallRecord {
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+            tool: tool.to_string(),
+            arguments,
+            duration_ms: started.elapsed().as_millis() as u64,
+            success,
+            error,
+            cost,
+        };
+        let Ok(mut line) = serde_json::to_string(&record) else {
+            return;
+        };
+        line.push('\n');
+
+        rotate_if_needed(&self.path, MAX_LOG_BYTES);
+        let open = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await;
+        let result = match open {
+            Ok(mut file) => file.write_all(line.as_bytes()).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            tracing::warn!("failed to append to tool audit log {:?}: {e}", self.path);
+        }
+    }
+
+    /// Load the most recent records, oldest first, optionally filtered by
+    /// tool name.
+    async fn recent(&self, tool: Option<&str>, limit: usize) -> Vec<ToolCallRecord> {
+        let contents = tokio::fs::read_to_string(&self.path)
+            .await
+            .unwrap_or_default();
+        let mut records: Vec<ToolCallRecord> = contents
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .filter(|record: &ToolCallRecord| tool.is_none_or(|tool| record.tool == tool))
+            .collect();
+        if records.len() > limit {
+            records.drain(..records.len() - limit);
+        }
+        records
+    }
+}
+
+/// Rename the log aside once it exceeds `max_bytes`, replacing any previous
+/// rotated generation.
+fn rotate_if_needed(path: &Path, max_bytes: u64) {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return;
+    };
+    if metadata.len() < max_bytes {
+        return;
+    }
+    let rotated = path.with_extension("jsonl.1");
+    if let Err(e) = std::fs::rename(path, &rotated) {
+        tracing::warn!("failed to rotate tool audit log {path:?}: {e}");
+    }
+}
+
+/// Redact sensitive values and truncate long strings, recursively.
+fn redact_arguments(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                let key = key.to_ascii_lowercase();
+                if SENSITIVE_KEY_FRAGMENTS
+                    .iter()
+                    .any(|fragment| key.contains(fragment))
+                {
+                    *value = serde_json::Value::String(REDACTED_PLACEHOLDER.to_string());
+                } else {
+                    redact_arguments(value);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                redact_arguments(item);
+            }
+        }
+        serde_json::Value::String(text) => {
+            if text.chars().count() > MAX_ARGUMENT_STRING_LEN {
+                let truncated: String = text.chars().take(MAX_ARGUMENT_STRING_LEN).collect();
+                *text = format!("{truncated}…");
+            }
+        }
+        _ => {}
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ListRecentToolCallsParams {
+    /// Number of calls to return (1-100). Defaults to 20.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+
+    /// Only return calls to this tool.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+}
+
+/// Structured output returned in `CallToolResult.structured_content`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ListRecentToolCallsOutput {
+    /// Matching calls, oldest first.
+    pub calls: Vec<ToolCallRecord>,
+}
+
+pub fn create_tool_for_list_recent_tool_calls() -> Tool {
+    Tool {
+        name: "list-recent-tool-calls".into(),
+        title: Some("List Recent Tool Calls".to_string()),
+        input_schema: tool_schema_for::<ListRecentToolCallsParams>(),
+        output_schema: Some(tool_schema_for::<ListRecentToolCallsOutput>()),
+        description: Some(
+            "List recent tool invocations from the audit log, with durations, outcomes, and \
+             generation costs."
+                .into(),
+        ),
+        annotations: None,
+        execution: None,
+        icons: None,
+        meta: None,
+    }
+}
+
+fn error_result(msg: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![rmcp::model::Content::text(msg)],
+        is_error: Some(true),
+        structured_content: None,
+        meta: None,
+    }
+}
+
+pub(crate) async fn handle_list_recent_tool_calls(
+    arguments: Option<serde_json::Map<String, serde_json::Value>>,
+    log: &ToolAuditLog,
+) -> CallToolResult {
+    let arguments = serde_json::Value::Object(arguments.unwrap_or_default());
+    let params: ListRecentToolCallsParams = match serde_json::from_value(arguments) {
+        Ok(params) => params,
+        Err(e) => {
+            return error_result(format!("Failed to parse parameters: {e}"));
+        }
+    };
+
+    let limit = params
+        .limit
+        .unwrap_or(DEFAULT_LIST_LIMIT)
+        .clamp(1, MAX_LIST_LIMIT);
+    let calls = log.recent(params.tool.as_deref(), limit).await;
+
+    let mut lines = vec![format!("{} recent tool call(s):", calls.len())];
+    for call in &calls {
+        let outcome = if call.success { "ok" } else { "FAILED" };
+        let cost = call
+            .cost
+            .as_deref()
+            .map(|cost| format!(", cost {cost}"))
+            .unwrap_or_default();
+        lines.push(format!(
+            "- {} ({outcome}, {}ms{cost})",
+            call.tool, call.duration_ms
+        ));
+    }
+    let output = ListRecentToolCallsOutput { calls };
+
+    CallToolResult {
+        content: vec![rmcp::model::Content::text(lines.join("\n"))],
+        is_error: Some(false),
+        structured_content: serde_json::to_value(&output).ok(),
+        meta: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn verify_list_recent_tool_calls_tool_json_schema() {
+        let tool = create_tool_for_list_recent_tool_calls();
+        assert_eq!(tool.name.as_ref(), "list-recent-tool-calls");
+        let schema = serde_json::to_value(&tool.input_schema).unwrap();
+        let props = schema.get("properties").unwrap();
+        assert!(props.get("limit").is_some());
+        assert!(props.get("tool").is_some());
+    }
+
+    #[test]
+    fn redacts_sensitive_keys_and_truncates_long_strings() {
+        let mut value = json!({
+            "prompt": "a".repeat(300),
+            "api_key": "sk-live-1234",
+            "nested": { "auth_token": "abc", "size": "1024x1024" },
+        });
+        redact_arguments(&mut value);
+        let prompt = value["prompt"].as_str().unwrap();
+        assert_eq!(prompt.chars().count(), MAX_ARGUMENT_STRING_LEN + 1);
+        assert!(prompt.ends_with('…'));
+        assert_eq!(value["api_key"], REDACTED_PLACEHOLDER);
+        assert_eq!(value["nested"]["auth_token"], REDACTED_PLACEHOLDER);
+        assert_eq!(value["nested"]["size"], "1024x1024");
+    }
+
+    #[test]
+    fn rotates_log_once_it_exceeds_the_cap() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(LOG_FILE_NAME);
+        std::fs::write(&path, "x".repeat(64)).unwrap();
+
+        rotate_if_needed(&path, 1024);
+        assert!(path.exists());
+
+        rotate_if_needed(&path, 16);
+        assert!(!path.exists());
+        assert!(path.with_extension("jsonl.1").exists());
+    }
+
+    #[tokio::test]
+    async fn records_calls_and_lists_them_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = ToolAuditLog::new(dir.path().to_path_buf());
+
+        let mut args = serde_json::Map::new();
+        args.insert("prompt".to_string(), json!("a cat"));
+        let ok = CallToolResult {
+            content: vec![],
+            is_error: Some(false),
+            structured_content: Some(json!({ "cost": "0.04" })),
+            meta: None,
+        };
+        log.record("ra1-art-generator", Some(args), Instant::now(), &ok)
+            .await;
+        let failed = error_result("boom".to_string());
+        log.record("web-search", None, Instant::now(), &failed)
+            .await;
+
+        let result = handle_list_recent_tool_calls(None, &log).await;
+        assert_eq!(result.is_error, Some(false));
+        let calls = result.structured_content.unwrap()["calls"]
+            .as_array()
+            .unwrap()
+            .clone();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0]["tool"], "ra1-art-generator");
+        assert_eq!(calls[0]["cost"], "0.04");
+        assert_eq!(calls[1]["success"], false);
+        assert_eq!(calls[1]["error"], "boom");
+
+        let mut filter = serde_json::Map::new();
+        filter.insert("tool".to_string(), json!("web-search"));
+        let result = handle_list_recent_tool_calls(Some(filter), &log).await;
+        let calls = result.structured_content.unwrap()["calls"]
+            .as_array()
+            .unwrap()
+            .clone();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0]["tool"], "web-search");
+    }
+}
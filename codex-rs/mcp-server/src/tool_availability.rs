@@ -0,0 +1,105 @@
+//! Tracks which gated built-in tools are currently available and tells
+//! clients when that changes.
+//!
+//! Availability depends on environment state (API keys, keychain entries)
+//! that can change while the server is running - exporting `NETWRCK_API_KEY`
+//! or running `codex infinity login` should not require a restart. A
+//! background watcher re-evaluates the checks periodically and emits
+//! `notifications/tools/list_changed` so clients re-list and pick up newly
+//! enabled (or newly unavailable) tools live.
+
+use crate::infinity_agent_tool::is_infinity_available;
+use crate::outgoing_message::OutgoingMessageSender;
+use crate::outgoing_message::OutgoingNotification;
+use crate::ra1_tool::is_ra1_available;
+use crate::web_search_tool::is_web_search_available;
+use codex_core::config::Ra1ToolConfig;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::task;
+
+const TOOLS_LIST_CHANGED_METHOD: &str = "notifications/tools/list_changed";
+/// How often the watcher re-runs the availability checks.
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Snapshot of which gated tool groups are currently offered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ToolAvailability {
+    pub ra1: bool,
+    pub web_search: bool,
+    pub infinity: bool,
+}
+
+/// Run the availability checks right now.
+pub(crate) fn current(ra1_config: &Ra1ToolConfig) -> ToolAvailability {
+    ToolAvailability {
+        ra1: is_ra1_available(ra1_config),
+        web_search: is_web_search_available(),
+        infinity: is_infinity_available(),
+    }
+}
+
+/// Store `current` in the shared snapshot, returning whether it differed
+/// from the previous value. `tools/list` also refreshes the snapshot so the
+/// watcher does not re-announce availability a client has already seen.
+pub(crate) fn refresh(snapshot: &Mutex<ToolAvailability>, current: ToolAvailability) -> bool {
+    #[expect(clippy::unwrap_used)]
+    let mut snapshot = snapshot.lock().unwrap();
+    let changed = *snapshot != current;
+    *snapshot = current;
+    changed
+}
+
+/// Re-evaluate availability on an interval and notify the client when it
+/// changes.
+pub(crate) fn spawn_watcher(
+    ra1_config: Ra1ToolConfig,
+    outgoing: Arc<OutgoingMessageSender>,
+    snapshot: Arc<Mutex<ToolAvailability>>,
+) {
+    task::spawn(async move {
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            if refresh(&snapshot, current(&ra1_config)) {
+                tracing::info!("tool availability changed; sending {TOOLS_LIST_CHANGED_METHOD}");
+                outgoing
+                    .send_notification(OutgoingNotification {
+                        method: TOOLS_LIST_CHANGED_METHOD.to_string(),
+                        params: None,
+                    })
+                    .await;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refresh_reports_a_change_only_once() {
+        let snapshot = Mutex::new(ToolAvailability {
+            ra1: false,
+            web_search: false,
+            infinity: false,
+        });
+        let enabled = ToolAvailability {
+            ra1: true,
+            web_search: false,
+            infinity: false,
+        };
+        assert!(refresh(&snapshot, enabled.clone()));
+        assert!(!refresh(&snapshot, enabled));
+    }
+
+    #[test]
+    fn current_respects_the_ra1_enabled_flag() {
+        let config = Ra1ToolConfig {
+            enabled: false,
+            ..Default::default()
+        };
+        assert!(!current(&config).ra1);
+    }
+}
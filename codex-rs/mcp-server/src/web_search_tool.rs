@@ -0,0 +1,322 @@
+//! Web search tool - queries a configurable search backend so agents running
+//! through the codex MCP server can research without a separate third-party
+//! MCP server.
+//!
+//! The backend is selected from the environment: Brave (`BRAVE_API_KEY`),
+//! Bing (`BING_SEARCH_API_KEY`), or a self-hosted SearXNG instance
+//! (`SEARXNG_BASE_URL`, no key required).
+
+use crate::ra1_tool::tool_schema_for;
+use rmcp::model::CallToolResult;
+use rmcp::model::Tool;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use std::env;
+
+pub(crate) const BRAVE_API_KEY_ENV: &str = "BRAVE_API_KEY";
+pub(crate) const BING_API_KEY_ENV: &str = "BING_SEARCH_API_KEY";
+pub(crate) const SEARXNG_BASE_URL_ENV: &str = "SEARXNG_BASE_URL";
+
+/// Results returned unless overridden via `count`.
+const DEFAULT_RESULT_COUNT: u32 = 5;
+/// Upper bound on `count` to keep responses compact.
+const MAX_RESULT_COUNT: u32 = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WebSearchParams {
+    /// The search query.
+    pub query: String,
+
+    /// Number of results to return (1-10). Defaults to 5.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+}
+
+/// One search hit in the structured output.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct WebSearchResultItem {
+    pub title: String,
+    pub url: String,
+    pub snippet: String,
+}
+
+/// Structured output returned in `CallToolResult.structured_content`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct WebSearchOutput {
+    /// Which backend served the query ("brave", "bing", or "searxng").
+    pub backend: String,
+    pub results: Vec<WebSearchResultItem>,
+}
+
+/// Search backend resolved from the environment, in priority order:
+/// Brave, then Bing, then SearXNG.
+#[derive(Debug, Clone)]
+pub(crate) enum WebSearchBackend {
+    Brave { api_key: String },
+    Bing { api_key: String },
+    Searxng { base_url: String },
+}
+
+impl WebSearchBackend {
+    pub(crate) fn from_env() -> Option<Self> {
+        if let Ok(api_key) = env::var(BRAVE_API_KEY_ENV) {
+            return Some(Self::Brave { api_key });
+        }
+        if let Ok(api_key) = env::var(BING_API_KEY_ENV) {
+            return Some(Self::Bing { api_key });
+        }
+        if let Ok(base_url) = env::var(SEARXNG_BASE_URL_ENV) {
+            return Some(Self::Searxng {
+                base_url: base_url.trim_end_matches('/').to_string(),
+            });
+        }
+        None
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Brave { .. } => "brave",
+            Self::Bing { .. } => "bing",
+            Self::Searxng { .. } => "searxng",
+        }
+    }
+}
+
+pub(crate) fn is_web_search_available() -> bool {
+    WebSearchBackend::from_env().is_some()
+}
+
+pub fn create_tool_for_web_search() -> Tool {
+    Tool {
+        name: "web-search".into(),
+        title: Some("Web Search".to_string()),
+        input_schema: tool_schema_for::<WebSearchParams>(),
+        output_schema: Some(tool_schema_for::<WebSearchOutput>()),
+        description: Some(
+            "Search the web and return titles, URLs, and snippets for the top results.".into(),
+        ),
+        annotations: None,
+        execution: None,
+        icons: None,
+        meta: None,
+    }
+}
+
+fn error_result(msg: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![rmcp::model::Content::text(msg)],
+        is_error: Some(true),
+        structured_content: None,
+        meta: None,
+    }
+}
+
+/// Pull `(title, url, snippet)` triples out of a backend response. Each
+/// backend nests results differently but the per-item fields map directly.
+fn parse_results(backend: &WebSearchBackend, body: &serde_json::Value) -> Vec<WebSearchResultItem> {
+    let (items, title_key, snippet_key) = match backend {
+        WebSearchBackend::Brave { .. } => (
+            body.get("web").and_then(|web| web.get("results")),
+            "title",
+            "description",
+        ),
+        WebSearchBackend::Bing { .. } => (
+            body.get("webPages").and_then(|pages| pages.get("value")),
+            "name",
+            "snippet",
+        ),
+        WebSearchBackend::Searxng { .. } => (body.get("results"), "title", "content"),
+    };
+    let Some(items) = items.and_then(|items| items.as_array()) else {
+        return Vec::new();
+    };
+    items
+        .iter()
+        .filter_map(|item| {
+            let url = item.get("url")?.as_str()?.to_string();
+            let title = item
+                .get(title_key)
+                .and_then(|title| title.as_str())
+                .unwrap_or_default()
+                .to_string();
+            let snippet = item
+                .get(snippet_key)
+                .and_then(|snippet| snippet.as_str())
+                .unwrap_or_default()
+                .to_string();
+            Some(WebSearchResultItem {
+                title,
+                url,
+                snippet,
+            })
+        })
+        .collect()
+}
+
+async fn execute_search(
+    backend: &WebSearchBackend,
+    query: &str,
+    count: u32,
+) -> Result<Vec<WebSearchResultItem>, String> {
+    let client = reqwest::Client::new();
+    let request = match backend {
+        WebSearchBackend::Brave { api_key } => client
+            .get("https://api.search.brave.com/res/v1/web/search")
+            .query(&[("q", query), ("count", &count.to_string())])
+            .header("X-Subscription-Token", api_key)
+            .header("Accept", "application/json"),
+        WebSearchBackend::Bing { api_key } => client
+            .get("https://api.bing.microsoft.com/v7.0/search")
+            .query(&[("q", query), ("count", &count.to_string())])
+            .header("Ocp-Apim-Subscription-Key", api_key),
+        WebSearchBackend::Searxng { base_url } => client
+            .get(format!("{base_url}/search"))
+            .query(&[("q", query), ("format", "json")]),
+    };
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Search request failed: {e}"))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read search response: {e}"))?;
+    if !status.is_success() {
+        return Err(format!("Search backend returned {status}: {body}"));
+    }
+    let body: serde_json::Value =
+        serde_json::from_str(&body).map_err(|e| format!("Failed to parse search response: {e}"))?;
+
+    let mut results = parse_results(backend, &body);
+    results.truncate(count as usize);
+    Ok(results)
+}
+
+pub(crate) async fn handle_web_search(
+    arguments: Option<serde_json::Map<String, serde_json::Value>>,
+) -> CallToolResult {
+    let Some(backend) = WebSearchBackend::from_env() else {
+        return error_result(format!(
+            "No search backend configured; set {BRAVE_API_KEY_ENV}, {BING_API_KEY_ENV}, or \
+             {SEARXNG_BASE_URL_ENV}."
+        ));
+    };
+
+    let arguments = arguments.map(serde_json::Value::Object);
+    let params: WebSearchParams = match arguments {
+        Some(json_val) => match serde_json::from_value(json_val) {
+            Ok(p) => p,
+            Err(e) => {
+                return error_result(format!("Failed to parse parameters: {e}"));
+            }
+        },
+        None => {
+            return error_result("Missing arguments; the `query` field is required.".to_string());
+        }
+    };
+
+    let count = params
+        .count
+        .unwrap_or(DEFAULT_RESULT_COUNT)
+        .clamp(1, MAX_RESULT_COUNT);
+
+    let results = match execute_search(&backend, &params.query, count).await {
+        Ok(results) => results,
+        Err(msg) => {
+            return error_result(msg);
+        }
+    };
+
+    let output = WebSearchOutput {
+        backend: backend.name().to_string(),
+        results,
+    };
+    let mut lines = vec![format!(
+        "{} result(s) for \"{}\" via {}:",
+        output.results.len(),
+        params.query,
+        output.backend
+    )];
+    for result in &output.results {
+        lines.push(format!("- {} — {}", result.title, result.url));
+        if !result.snippet.is_empty() {
+            lines.push(format!("  {}", result.snippet));
+        }
+    }
+
+    CallToolResult {
+        content: vec![rmcp::model::Content::text(lines.join("\n"))],
+        is_error: Some(false),
+        structured_content: serde_json::to_value(&output).ok(),
+        meta: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn verify_web_search_tool_json_schema() {
+        let tool = create_tool_for_web_search();
+        assert_eq!(tool.name.as_ref(), "web-search");
+        let schema = serde_json::to_value(&tool.input_schema).unwrap();
+        let props = schema.get("properties").unwrap();
+        assert!(props.get("query").is_some());
+        assert!(props.get("count").is_some());
+        let required = schema.get("required").unwrap().as_array().unwrap();
+        assert!(required.iter().any(|v| v.as_str() == Some("query")));
+    }
+
+    #[test]
+    fn parses_brave_results() {
+        let backend = WebSearchBackend::Brave {
+            api_key: "k".to_string(),
+        };
+        let body = json!({
+            "web": { "results": [
+                { "title": "Rust", "url": "https://rust-lang.org", "description": "A language" }
+            ]}
+        });
+        let results = parse_results(&backend, &body);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "Rust");
+        assert_eq!(results[0].url, "https://rust-lang.org");
+        assert_eq!(results[0].snippet, "A language");
+    }
+
+    #[test]
+    fn parses_bing_results() {
+        let backend = WebSearchBackend::Bing {
+            api_key: "k".to_string(),
+        };
+        let body = json!({
+            "webPages": { "value": [
+                { "name": "Rust", "url": "https://rust-lang.org", "snippet": "A language" }
+            ]}
+        });
+        let results = parse_results(&backend, &body);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "Rust");
+    }
+
+    #[test]
+    fn parses_searxng_results_and_skips_items_without_urls() {
+        let backend = WebSearchBackend::Searxng {
+            base_url: "http://localhost:8888".to_string(),
+        };
+        let body = json!({
+            "results": [
+                { "title": "Rust", "url": "https://rust-lang.org", "content": "A language" },
+                { "title": "No URL" }
+            ]
+        });
+        let results = parse_results(&backend, &body);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].snippet, "A language");
+    }
+}
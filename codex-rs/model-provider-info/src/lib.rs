@@ -10,6 +10,7 @@ use codex_api::RetryConfig as ApiRetryConfig;
 use codex_api::is_azure_responses_provider;
 use codex_protocol::auth::AuthMode;
 use codex_protocol::config_types::ModelProviderAuthInfo;
+use codex_protocol::config_types::ModelProviderOAuthInfo;
 use codex_protocol::error::CodexErr;
 use codex_protocol::error::EnvVarError;
 use codex_protocol::error::Result as CodexResult;
@@ -21,9 +22,13 @@ use serde::Deserialize;
 use serde::Serialize;
 use std::collections::HashMap;
 use std::fmt;
+use std::path::PathBuf;
 use std::time::Duration;
+use std::time::Instant;
 
 const DEFAULT_STREAM_IDLE_TIMEOUT_MS: u64 = 300_000;
+/// Upper bound on how long a preflight health-check probe may take.
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
 const DEFAULT_STREAM_MAX_RETRIES: u64 = 5;
 const DEFAULT_REQUEST_MAX_RETRIES: u64 = 4;
 pub const DEFAULT_WEBSOCKET_CONNECT_TIMEOUT_MS: u64 = 15_000;
@@ -47,6 +52,20 @@ pub const AMAZON_BEDROCK_DEFAULT_BASE_URL: &str =
     "https://bedrock-mantle.us-east-1.api.aws/openai/v1";
 const AMAZON_BEDROCK_MANTLE_CLIENT_AGENT_HEADER: &str = "x-amzn-mantle-client-agent";
 const AMAZON_BEDROCK_MANTLE_CLIENT_AGENT_VALUE: &str = "codex";
+const GOOGLE_VERTEX_PROVIDER_NAME: &str = "Google Vertex AI";
+pub const GOOGLE_VERTEX_PROVIDER_ID: &str = "google-vertex";
+const GOOGLE_CLOUD_PROJECT_ENV: &str = "GOOGLE_CLOUD_PROJECT";
+const GOOGLE_CLOUD_LOCATION_ENV: &str = "GOOGLE_CLOUD_LOCATION";
+const GOOGLE_VERTEX_DEFAULT_LOCATION: &str = "us-central1";
+/// Vertex bearer tokens come from gcloud Application Default Credentials
+/// rather than a static API key.
+const GOOGLE_VERTEX_ADC_TOKEN_COMMAND: &str = "gcloud";
+const GOOGLE_VERTEX_ADC_TOKEN_ARGS: &[&str] =
+    &["auth", "application-default", "print-access-token"];
+const GROQ_PROVIDER_ID: &str = "groq";
+const MISTRAL_PROVIDER_ID: &str = "mistral";
+const DEEPSEEK_PROVIDER_ID: &str = "deepseek";
+const TOGETHER_PROVIDER_ID: &str = "together";
 const CHAT_WIRE_API_REMOVED_ERROR: &str = "`wire_api = \"chat\"` is no longer supported.\nHow to fix: set `wire_api = \"responses\"` in your provider config.\nMore info: https://github.com/openai/codex/discussions/7782";
 pub const LEGACY_OLLAMA_CHAT_PROVIDER_ID: &str = "ollama-chat";
 pub const OLLAMA_CHAT_PROVIDER_REMOVED_ERROR: &str = "`ollama-chat` is no longer supported.\nHow to fix: replace `ollama-chat` with `ollama` in `model_provider`, `oss_provider`, or `--local-provider`.\nMore info: https://github.com/openai/codex/discussions/7782";
@@ -58,12 +77,24 @@ pub enum WireApi {
     /// The Responses API exposed by OpenAI at `/v1/responses`.
     #[default]
     Responses,
+    /// Anthropic's native Messages API at `/v1/messages`. Unlike the
+    /// OpenAI-compat shim, this keeps prompt caching and native tool use.
+    #[serde(rename = "anthropic_messages")]
+    AnthropicMessages,
+    /// Google's native `generateContent` API at
+    /// `/models/{model}:streamGenerateContent`. Unlike the OpenAI-compat
+    /// endpoint, this keeps system instructions, native function calling,
+    /// and inline images intact.
+    #[serde(rename = "gemini_generate_content")]
+    GeminiGenerateContent,
 }
 
 impl fmt::Display for WireApi {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let value = match self {
             Self::Responses => "responses",
+            Self::AnthropicMessages => "anthropic_messages",
+            Self::GeminiGenerateContent => "gemini_generate_content",
         };
         f.write_str(value)
     }
@@ -77,8 +108,13 @@ impl<'de> Deserialize<'de> for WireApi {
         let value = String::deserialize(deserializer)?;
         match value.as_str() {
             "responses" => Ok(Self::Responses),
+            "anthropic_messages" => Ok(Self::AnthropicMessages),
+            "gemini_generate_content" => Ok(Self::GeminiGenerateContent),
             "chat" => Err(serde::de::Error::custom(CHAT_WIRE_API_REMOVED_ERROR)),
-            _ => Err(serde::de::Error::unknown_variant(&value, &["responses"])),
+            _ => Err(serde::de::Error::unknown_variant(
+                &value,
+                &["responses", "anthropic_messages", "gemini_generate_content"],
+            )),
         }
     }
 }
@@ -106,6 +142,31 @@ pub struct ModelProviderInfo {
     pub auth: Option<ModelProviderAuthInfo>,
     /// AWS SigV4 auth configuration for this provider.
     pub aws: Option<ModelProviderAwsAuthInfo>,
+    /// OAuth2 device-flow configuration enabling `codex login --provider <id>`.
+    pub oauth: Option<ModelProviderOAuthInfo>,
+    /// Command (argv) that prints the API key for this provider on stdout,
+    /// e.g. a vault or `aws secretsmanager` invocation. The key is cached for
+    /// the session and re-fetched after an authorization failure.
+    pub key_command: Option<Vec<String>>,
+    /// File containing the API key for this provider; surrounding whitespace
+    /// is trimmed. The key is cached for the session and re-read after an
+    /// authorization failure.
+    pub key_file: Option<PathBuf>,
+    /// HTTPS proxy URL to route requests to this provider through, e.g.
+    /// `http://proxy.corp.example:3128`. Takes precedence over the
+    /// process-wide proxy environment variables for this provider only.
+    pub https_proxy: Option<String>,
+    /// Comma-separated list of hosts that bypass `https_proxy`, e.g.
+    /// `localhost,.internal.example`.
+    pub no_proxy: Option<String>,
+    /// Path to a PEM bundle of additional root certificates to trust when
+    /// connecting to this provider, e.g. a corporate CA.
+    pub ca_bundle_path: Option<PathBuf>,
+    /// Disable TLS certificate verification for requests to this provider.
+    /// Only intended for local debugging against self-signed endpoints; never
+    /// enable this for endpoints that receive real credentials.
+    #[serde(default)]
+    pub insecure_skip_verify: bool,
     /// Which wire protocol this provider expects.
     #[serde(default)]
     pub wire_api: WireApi,
@@ -138,6 +199,59 @@ pub struct ModelProviderInfo {
     /// Whether this provider supports the Responses API WebSocket transport.
     #[serde(default)]
     pub supports_websockets: bool,
+    /// Prompt-caching controls for this provider. `None` keeps the default
+    /// behaviour (per-thread cache key, system prompt marked as the only
+    /// Anthropic cache breakpoint).
+    pub prompt_cache: Option<ModelProviderPromptCacheInfo>,
+    /// Model to use for `POST /embeddings` requests against this provider,
+    /// e.g. `text-embedding-3-small`. When unset, embeddings are unavailable
+    /// for this provider.
+    pub embedding_model: Option<String>,
+}
+
+/// Prompt-caching controls for a model provider.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ModelProviderPromptCacheInfo {
+    /// Fixed `prompt_cache_key` sent with Responses API requests so sessions
+    /// sharing the same static prefix land on the same cache shard. When
+    /// unset, a per-thread key is used.
+    pub prompt_cache_key: Option<String>,
+    /// For `anthropic_messages` providers, place a `cache_control` breakpoint
+    /// on the system prompt. Defaults to `true`; the breakpoint also covers
+    /// the tool definitions that precede it in the cached prefix.
+    pub cache_system_prompt: Option<bool>,
+    /// For `anthropic_messages` providers, place an additional `cache_control`
+    /// breakpoint on the last tool definition so the tool schemas stay cached
+    /// even when the system prompt changes. Defaults to `false` since each
+    /// breakpoint counts against Anthropic's per-request limit.
+    pub cache_tool_definitions: Option<bool>,
+}
+
+/// Result of a [`ModelProviderInfo::health_check`] preflight probe.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderHealthCheck {
+    /// Whether the endpoint answered at all.
+    pub reachable: bool,
+    /// Whether credentials were accepted; `None` when unknown (e.g. the
+    /// endpoint was unreachable so no auth decision was observed).
+    pub auth_ok: Option<bool>,
+    /// Round-trip latency of the probe in milliseconds.
+    pub latency_ms: u64,
+    /// Status line or error description for display.
+    pub detail: String,
+}
+
+/// OpenAI-compatible `GET /models` response body.
+#[derive(Debug, Deserialize)]
+struct ModelsListResponse {
+    #[serde(default)]
+    data: Vec<ModelsListEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModelsListEntry {
+    id: String,
 }
 
 /// AWS SigV4 auth configuration for a model provider.
@@ -182,6 +296,45 @@ impl ModelProviderInfo {
             }
         }
 
+        if self.key_command.is_some() || self.key_file.is_some() {
+            if self.key_command.is_some() && self.key_file.is_some() {
+                return Err("provider key_command cannot be combined with key_file".to_string());
+            }
+            if self.key_command.as_ref().is_some_and(Vec::is_empty) {
+                return Err("provider key_command must not be empty".to_string());
+            }
+
+            let mut conflicts = Vec::new();
+            if self.env_key.is_some() {
+                conflicts.push("env_key");
+            }
+            if self.experimental_bearer_token.is_some() {
+                conflicts.push("experimental_bearer_token");
+            }
+            if self.auth.is_some() {
+                conflicts.push("auth");
+            }
+            if self.requires_openai_auth {
+                conflicts.push("requires_openai_auth");
+            }
+
+            if !conflicts.is_empty() {
+                let field = if self.key_command.is_some() {
+                    "key_command"
+                } else {
+                    "key_file"
+                };
+                return Err(format!(
+                    "provider {field} cannot be combined with {}",
+                    conflicts.join(", ")
+                ));
+            }
+        }
+
+        if self.no_proxy.is_some() && self.https_proxy.is_none() {
+            return Err("provider no_proxy requires https_proxy".to_string());
+        }
+
         let Some(auth) = self.auth.as_ref() else {
             return Ok(());
         };
@@ -211,6 +364,60 @@ impl ModelProviderInfo {
         }
     }
 
+    /// Whether this provider declares proxy or TLS transport overrides that
+    /// require a dedicated reqwest client.
+    pub fn has_transport_overrides(&self) -> bool {
+        self.https_proxy.is_some() || self.ca_bundle_path.is_some() || self.insecure_skip_verify
+    }
+
+    /// Applies this provider's `https_proxy`/`no_proxy`, `ca_bundle_path`, and
+    /// `insecure_skip_verify` settings to a reqwest client builder.
+    pub fn apply_transport_overrides(
+        &self,
+        mut builder: reqwest::ClientBuilder,
+    ) -> std::result::Result<reqwest::ClientBuilder, String> {
+        if let Some(https_proxy) = &self.https_proxy {
+            let mut proxy = reqwest::Proxy::https(https_proxy)
+                .map_err(|err| format!("provider https_proxy `{https_proxy}` is invalid: {err}"))?;
+            if let Some(no_proxy) = &self.no_proxy {
+                proxy = proxy.no_proxy(reqwest::NoProxy::from_string(no_proxy));
+            }
+            builder = builder.proxy(proxy);
+        }
+
+        if let Some(ca_bundle_path) = &self.ca_bundle_path {
+            let pem = std::fs::read(ca_bundle_path).map_err(|err| {
+                format!(
+                    "failed to read provider ca_bundle_path `{}`: {err}",
+                    ca_bundle_path.display()
+                )
+            })?;
+            let certificates = reqwest::Certificate::from_pem_bundle(&pem).map_err(|err| {
+                format!(
+                    "failed to parse provider ca_bundle_path `{}`: {err}",
+                    ca_bundle_path.display()
+                )
+            })?;
+            for certificate in certificates {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+
+        if self.insecure_skip_verify {
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+
+        Ok(builder)
+    }
+
+    /// Builds a reqwest client with this provider's transport overrides
+    /// applied, for callers that otherwise share one client across providers.
+    pub fn build_http_client(&self) -> std::result::Result<reqwest::Client, String> {
+        self.apply_transport_overrides(reqwest::Client::builder())?
+            .build()
+            .map_err(|err| format!("failed to build provider HTTP client: {err}"))
+    }
+
     fn build_header_map(&self) -> CodexResult<HeaderMap> {
         let capacity = self.http_headers.as_ref().map_or(0, HashMap::len)
             + self.env_http_headers.as_ref().map_or(0, HashMap::len);
@@ -265,6 +472,8 @@ impl ModelProviderInfo {
             retry_429: false,
             retry_5xx: true,
             retry_transport: true,
+            budget: None,
+            observer: None,
         };
 
         Ok(ApiProvider {
@@ -326,6 +535,98 @@ impl ModelProviderInfo {
             .unwrap_or(Duration::from_millis(DEFAULT_WEBSOCKET_CONNECT_TIMEOUT_MS))
     }
 
+    /// Performs a cheap preflight request (`GET {base_url}/models`) against
+    /// this provider and reports reachability, auth validity, and measured
+    /// latency. Never mutates state; backs `codex providers check` so
+    /// misconfigured env keys are caught before a long task dies mid-run.
+    pub async fn health_check(&self, client: &reqwest::Client) -> ProviderHealthCheck {
+        let base_url = self
+            .base_url
+            .clone()
+            .unwrap_or_else(|| "https://api.openai.com/v1".to_string());
+        let url = format!("{}/models", base_url.trim_end_matches('/'));
+
+        // A required env key that is missing or empty is an auth failure we
+        // can report without issuing a request.
+        let api_key = match self.api_key() {
+            Ok(api_key) => api_key,
+            Err(err) => {
+                return ProviderHealthCheck {
+                    reachable: false,
+                    auth_ok: Some(false),
+                    latency_ms: 0,
+                    detail: err.to_string(),
+                };
+            }
+        };
+
+        let headers = self.build_header_map().unwrap_or_default();
+        let mut request = client
+            .get(&url)
+            .headers(headers)
+            .timeout(HEALTH_CHECK_TIMEOUT);
+        if let Some(api_key) = api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let start = Instant::now();
+        match request.send().await {
+            Ok(response) => {
+                let status = response.status();
+                ProviderHealthCheck {
+                    reachable: true,
+                    auth_ok: Some(!matches!(status.as_u16(), 401 | 403)),
+                    latency_ms: start.elapsed().as_millis() as u64,
+                    detail: format!("HTTP {status}"),
+                }
+            }
+            Err(err) => ProviderHealthCheck {
+                reachable: false,
+                auth_ok: None,
+                latency_ms: start.elapsed().as_millis() as u64,
+                detail: err.to_string(),
+            },
+        }
+    }
+
+    /// Queries this provider's OpenAI-compatible `GET {base_url}/models`
+    /// endpoint and returns the advertised model ids, sorted and deduped.
+    /// OpenRouter, Ollama, and LM Studio all serve the same
+    /// `{"data": [{"id": ...}]}` shape on their compat endpoints; backs
+    /// `codex models list` so users see valid ids instead of guessing.
+    pub async fn list_model_ids(&self, client: &reqwest::Client) -> Result<Vec<String>, String> {
+        let base_url = self
+            .base_url
+            .clone()
+            .unwrap_or_else(|| "https://api.openai.com/v1".to_string());
+        let url = format!("{}/models", base_url.trim_end_matches('/'));
+
+        let api_key = self.api_key().map_err(|err| err.to_string())?;
+        let headers = self.build_header_map().unwrap_or_default();
+        let mut request = client
+            .get(&url)
+            .headers(headers)
+            .timeout(HEALTH_CHECK_TIMEOUT);
+        if let Some(api_key) = api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request.send().await.map_err(|err| err.to_string())?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("HTTP {status}"));
+        }
+        let body: ModelsListResponse = response
+            .json()
+            .await
+            .map_err(|err| format!("invalid models response: {err}"))?;
+
+        let mut model_ids: Vec<String> = body.data.into_iter().map(|entry| entry.id).collect();
+        model_ids.sort_unstable();
+        model_ids.dedup();
+        Ok(model_ids)
+    }
+
     pub fn create_openai_provider(base_url: Option<String>) -> ModelProviderInfo {
         ModelProviderInfo {
             name: OPENAI_PROVIDER_NAME.into(),
@@ -335,6 +636,13 @@ impl ModelProviderInfo {
             experimental_bearer_token: None,
             auth: None,
             aws: None,
+            oauth: None,
+            key_command: None,
+            key_file: None,
+            https_proxy: None,
+            no_proxy: None,
+            ca_bundle_path: None,
+            insecure_skip_verify: false,
             wire_api: WireApi::Responses,
             query_params: None,
             http_headers: Some(
@@ -360,6 +668,8 @@ impl ModelProviderInfo {
             websocket_connect_timeout_ms: None,
             requires_openai_auth: true,
             supports_websockets: true,
+            prompt_cache: None,
+            embedding_model: None,
         }
     }
 
@@ -373,6 +683,13 @@ impl ModelProviderInfo {
             env_key_instructions: None,
             experimental_bearer_token: None,
             auth: None,
+            oauth: None,
+            key_command: None,
+            key_file: None,
+            https_proxy: None,
+            no_proxy: None,
+            ca_bundle_path: None,
+            insecure_skip_verify: false,
             aws: Some(aws.unwrap_or(ModelProviderAwsAuthInfo {
                 profile: None,
                 region: None,
@@ -390,6 +707,65 @@ impl ModelProviderInfo {
             websocket_connect_timeout_ms: None,
             requires_openai_auth: false,
             supports_websockets: false,
+            prompt_cache: None,
+            embedding_model: None,
+        }
+    }
+
+    /// Vertex AI's OpenAI-compatible endpoint is project- and
+    /// location-scoped, so the base URL is derived from
+    /// `GOOGLE_CLOUD_PROJECT` / `GOOGLE_CLOUD_LOCATION`. Auth uses a bearer
+    /// token minted by gcloud Application Default Credentials.
+    pub fn create_google_vertex_provider() -> ModelProviderInfo {
+        let location = std::env::var(GOOGLE_CLOUD_LOCATION_ENV)
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .unwrap_or_else(|| GOOGLE_VERTEX_DEFAULT_LOCATION.to_string());
+        let base_url = std::env::var(GOOGLE_CLOUD_PROJECT_ENV)
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .map(|project| {
+                format!(
+                    "https://{location}-aiplatform.googleapis.com/v1/projects/{project}/locations/{location}/endpoints/openapi"
+                )
+            });
+        ModelProviderInfo {
+            name: GOOGLE_VERTEX_PROVIDER_NAME.into(),
+            base_url,
+            env_key: None,
+            env_key_instructions: Some(
+                "Set GOOGLE_CLOUD_PROJECT (and optionally GOOGLE_CLOUD_LOCATION), then run \
+`gcloud auth application-default login` so Codex can mint access tokens."
+                    .into(),
+            ),
+            experimental_bearer_token: None,
+            auth: Some(ModelProviderAuthInfo::for_command(
+                GOOGLE_VERTEX_ADC_TOKEN_COMMAND,
+                GOOGLE_VERTEX_ADC_TOKEN_ARGS
+                    .iter()
+                    .map(|arg| (*arg).to_string())
+                    .collect(),
+            )),
+            aws: None,
+            oauth: None,
+            key_command: None,
+            key_file: None,
+            https_proxy: None,
+            no_proxy: None,
+            ca_bundle_path: None,
+            insecure_skip_verify: false,
+            wire_api: WireApi::Responses,
+            query_params: None,
+            http_headers: None,
+            env_http_headers: None,
+            request_max_retries: None,
+            stream_max_retries: None,
+            stream_idle_timeout_ms: None,
+            websocket_connect_timeout_ms: None,
+            requires_openai_auth: false,
+            supports_websockets: false,
+            prompt_cache: None,
+            embedding_model: None,
         }
     }
 
@@ -411,6 +787,31 @@ impl ModelProviderInfo {
         self.name == AMAZON_BEDROCK_PROVIDER_NAME
     }
 
+    /// True when the provider is served from a loopback endpoint (ollama,
+    /// lmstudio, or a custom `base_url` on localhost), meaning it can be
+    /// reached without network access.
+    pub fn is_local(&self) -> bool {
+        let Some(base_url) = self.base_url.as_deref() else {
+            return false;
+        };
+        let rest = base_url
+            .split_once("://")
+            .map_or(base_url, |(_, rest)| rest);
+        let authority = rest.split(['/', '?', '#']).next().unwrap_or(rest);
+        let authority = authority
+            .rsplit_once('@')
+            .map_or(authority, |(_, host)| host);
+        let host = if let Some(bracketed) = authority.strip_prefix('[') {
+            bracketed.split(']').next().unwrap_or(bracketed)
+        } else {
+            authority.split(':').next().unwrap_or(authority)
+        };
+        matches!(
+            host.to_ascii_lowercase().as_str(),
+            "localhost" | "127.0.0.1" | "::1"
+        )
+    }
+
     pub fn supports_remote_compaction(&self) -> bool {
         self.is_openai() || is_azure_responses_provider(&self.name, self.base_url.as_deref())
     }
@@ -433,14 +834,16 @@ pub fn built_in_model_providers(
     use ModelProviderInfo as P;
     let openai_provider = P::create_openai_provider(openai_base_url);
     let amazon_bedrock_provider = P::create_amazon_bedrock_provider(/*aws*/ None);
+    let google_vertex_provider = P::create_google_vertex_provider();
 
-    // We do not want to be in the business of adjucating which third-party
-    // providers are bundled with Codex CLI, so we only include the OpenAI and
-    // open source ("oss") providers by default. Users are encouraged to add to
-    // `model_providers` in config.toml to add their own providers.
+    // Besides the OpenAI and open source ("oss") providers, we bundle entries
+    // for popular hosted OpenAI-compatible endpoints so users don't each
+    // reinvent the TOML stanzas (and get the headers subtly wrong). Anything
+    // else can still be added via `model_providers` in config.toml.
     [
         (OPENAI_PROVIDER_ID, openai_provider),
         (AMAZON_BEDROCK_PROVIDER_ID, amazon_bedrock_provider),
+        (GOOGLE_VERTEX_PROVIDER_ID, google_vertex_provider),
         (
             OLLAMA_OSS_PROVIDER_ID,
             create_oss_provider(DEFAULT_OLLAMA_PORT, WireApi::Responses),
@@ -449,6 +852,42 @@ pub fn built_in_model_providers(
             LMSTUDIO_OSS_PROVIDER_ID,
             create_oss_provider(DEFAULT_LMSTUDIO_PORT, WireApi::Responses),
         ),
+        (
+            GROQ_PROVIDER_ID,
+            create_api_key_provider(
+                "Groq",
+                "https://api.groq.com/openai/v1",
+                "GROQ_API_KEY",
+                "Create an API key at https://console.groq.com/keys and export it as GROQ_API_KEY.",
+            ),
+        ),
+        (
+            MISTRAL_PROVIDER_ID,
+            create_api_key_provider(
+                "Mistral",
+                "https://api.mistral.ai/v1",
+                "MISTRAL_API_KEY",
+                "Create an API key at https://console.mistral.ai/api-keys and export it as MISTRAL_API_KEY.",
+            ),
+        ),
+        (
+            DEEPSEEK_PROVIDER_ID,
+            create_api_key_provider(
+                "DeepSeek",
+                "https://api.deepseek.com/v1",
+                "DEEPSEEK_API_KEY",
+                "Create an API key at https://platform.deepseek.com/api_keys and export it as DEEPSEEK_API_KEY.",
+            ),
+        ),
+        (
+            TOGETHER_PROVIDER_ID,
+            create_api_key_provider(
+                "Together AI",
+                "https://api.together.xyz/v1",
+                "TOGETHER_API_KEY",
+                "Create an API key at https://api.together.ai/settings/api-keys and export it as TOGETHER_API_KEY.",
+            ),
+        ),
     ]
     .into_iter()
     .map(|(k, v)| (k.to_string(), v))
@@ -493,6 +932,45 @@ pub fn merge_configured_model_providers(
     Ok(model_providers)
 }
 
+/// Shared shape for hosted OpenAI-compatible providers that authenticate
+/// with a single API-key environment variable.
+fn create_api_key_provider(
+    name: &str,
+    base_url: &str,
+    env_key: &str,
+    env_key_instructions: &str,
+) -> ModelProviderInfo {
+    ModelProviderInfo {
+        name: name.into(),
+        base_url: Some(base_url.into()),
+        env_key: Some(env_key.into()),
+        env_key_instructions: Some(env_key_instructions.into()),
+        experimental_bearer_token: None,
+        auth: None,
+        aws: None,
+        oauth: None,
+        key_command: None,
+        key_file: None,
+        https_proxy: None,
+        no_proxy: None,
+        ca_bundle_path: None,
+        insecure_skip_verify: false,
+        wire_api: WireApi::Responses,
+        query_params: None,
+        http_headers: None,
+        env_http_headers: None,
+        // Use global defaults for retry/timeout unless overridden in config.toml.
+        request_max_retries: None,
+        stream_max_retries: None,
+        stream_idle_timeout_ms: None,
+        websocket_connect_timeout_ms: None,
+        requires_openai_auth: false,
+        supports_websockets: false,
+        prompt_cache: None,
+        embedding_model: None,
+    }
+}
+
 pub fn create_oss_provider(default_provider_port: u16, wire_api: WireApi) -> ModelProviderInfo {
     // These CODEX_OSS_ environment variables are experimental: we may
     // switch to reading values from config.toml instead.
@@ -521,6 +999,13 @@ pub fn create_oss_provider_with_base_url(base_url: &str, wire_api: WireApi) -> M
         experimental_bearer_token: None,
         auth: None,
         aws: None,
+        oauth: None,
+        key_command: None,
+        key_file: None,
+        https_proxy: None,
+        no_proxy: None,
+        ca_bundle_path: None,
+        insecure_skip_verify: false,
         wire_api,
         query_params: None,
         http_headers: None,
@@ -531,6 +1016,8 @@ pub fn create_oss_provider_with_base_url(base_url: &str, wire_api: WireApi) -> M
         websocket_connect_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        prompt_cache: None,
+        embedding_model: None,
     }
 }
 
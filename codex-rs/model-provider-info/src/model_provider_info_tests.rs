@@ -19,6 +19,13 @@ base_url = "http://localhost:11434/v1"
         experimental_bearer_token: None,
         auth: None,
         aws: None,
+        oauth: None,
+        key_command: None,
+        key_file: None,
+        https_proxy: None,
+        no_proxy: None,
+        ca_bundle_path: None,
+        insecure_skip_verify: false,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
@@ -29,12 +36,37 @@ base_url = "http://localhost:11434/v1"
         websocket_connect_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        prompt_cache: None,
+        embedding_model: None,
     };
 
     let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
     assert_eq!(expected_provider, provider);
 }
 
+#[test]
+fn test_deserialize_prompt_cache_options_toml() {
+    let provider_toml = r#"
+name = "Anthropic"
+base_url = "https://api.anthropic.com/v1"
+wire_api = "anthropic_messages"
+
+[prompt_cache]
+prompt_cache_key = "shared-agent-prefix"
+cache_tool_definitions = true
+        "#;
+    let provider: ModelProviderInfo = toml::from_str(provider_toml).unwrap();
+
+    assert_eq!(
+        Some(ModelProviderPromptCacheInfo {
+            prompt_cache_key: Some("shared-agent-prefix".into()),
+            cache_system_prompt: None,
+            cache_tool_definitions: Some(true),
+        }),
+        provider.prompt_cache
+    );
+}
+
 #[test]
 fn test_deserialize_azure_model_provider_toml() {
     let azure_provider_toml = r#"
@@ -51,6 +83,13 @@ query_params = { api-version = "2025-04-01-preview" }
         experimental_bearer_token: None,
         auth: None,
         aws: None,
+        oauth: None,
+        key_command: None,
+        key_file: None,
+        https_proxy: None,
+        no_proxy: None,
+        ca_bundle_path: None,
+        insecure_skip_verify: false,
         wire_api: WireApi::Responses,
         query_params: Some(maplit::hashmap! {
             "api-version".to_string() => "2025-04-01-preview".to_string(),
@@ -63,6 +102,8 @@ query_params = { api-version = "2025-04-01-preview" }
         websocket_connect_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        prompt_cache: None,
+        embedding_model: None,
     };
 
     let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -86,6 +127,13 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
         experimental_bearer_token: None,
         auth: None,
         aws: None,
+        oauth: None,
+        key_command: None,
+        key_file: None,
+        https_proxy: None,
+        no_proxy: None,
+        ca_bundle_path: None,
+        insecure_skip_verify: false,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: Some(maplit::hashmap! {
@@ -100,6 +148,8 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
         websocket_connect_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        prompt_cache: None,
+        embedding_model: None,
     };
 
     let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -119,6 +169,32 @@ wire_api = "chat"
     assert!(err.to_string().contains(CHAT_WIRE_API_REMOVED_ERROR));
 }
 
+#[test]
+fn test_deserialize_anthropic_messages_wire_api() {
+    let provider_toml = r#"
+name = "Anthropic"
+base_url = "https://api.anthropic.com/v1"
+env_key = "ANTHROPIC_API_KEY"
+wire_api = "anthropic_messages"
+        "#;
+
+    let provider: ModelProviderInfo = toml::from_str(provider_toml).unwrap();
+    assert_eq!(provider.wire_api, WireApi::AnthropicMessages);
+}
+
+#[test]
+fn test_deserialize_gemini_generate_content_wire_api() {
+    let provider_toml = r#"
+name = "Gemini"
+base_url = "https://generativelanguage.googleapis.com/v1beta"
+env_key = "GEMINI_API_KEY"
+wire_api = "gemini_generate_content"
+        "#;
+
+    let provider: ModelProviderInfo = toml::from_str(provider_toml).unwrap();
+    assert_eq!(provider.wire_api, WireApi::GeminiGenerateContent);
+}
+
 #[test]
 fn test_deserialize_websocket_connect_timeout() {
     let provider_toml = r#"
@@ -139,6 +215,27 @@ fn test_supports_remote_compaction_for_openai() {
     assert!(provider.supports_remote_compaction());
 }
 
+#[test]
+fn test_is_local_for_loopback_and_cloud_base_urls() {
+    let ollama = create_oss_provider(DEFAULT_OLLAMA_PORT, WireApi::Responses);
+    assert!(ollama.is_local());
+
+    let ipv6 = ModelProviderInfo {
+        base_url: Some("http://[::1]:8080/v1".into()),
+        ..ModelProviderInfo::default()
+    };
+    assert!(ipv6.is_local());
+
+    let cloud = ModelProviderInfo {
+        base_url: Some("https://api.groq.com/openai/v1".into()),
+        ..ModelProviderInfo::default()
+    };
+    assert!(!cloud.is_local());
+
+    // No base_url means the provider resolves to a hosted default.
+    assert!(!ModelProviderInfo::create_openai_provider(/*base_url*/ None).is_local());
+}
+
 #[test]
 fn test_personal_access_token_uses_chatgpt_codex_base_url() {
     let api_provider = ModelProviderInfo::create_openai_provider(/*base_url*/ None)
@@ -167,6 +264,13 @@ fn test_supports_remote_compaction_for_azure_name() {
         experimental_bearer_token: None,
         auth: None,
         aws: None,
+        oauth: None,
+        key_command: None,
+        key_file: None,
+        https_proxy: None,
+        no_proxy: None,
+        ca_bundle_path: None,
+        insecure_skip_verify: false,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
@@ -177,6 +281,8 @@ fn test_supports_remote_compaction_for_azure_name() {
         websocket_connect_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        prompt_cache: None,
+        embedding_model: None,
     };
 
     assert!(provider.supports_remote_compaction());
@@ -192,6 +298,13 @@ fn test_supports_remote_compaction_for_non_openai_non_azure_provider() {
         experimental_bearer_token: None,
         auth: None,
         aws: None,
+        oauth: None,
+        key_command: None,
+        key_file: None,
+        https_proxy: None,
+        no_proxy: None,
+        ca_bundle_path: None,
+        insecure_skip_verify: false,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
@@ -202,6 +315,8 @@ fn test_supports_remote_compaction_for_non_openai_non_azure_provider() {
         websocket_connect_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        prompt_cache: None,
+        embedding_model: None,
     };
 
     assert!(!provider.supports_remote_compaction());
@@ -282,6 +397,114 @@ region = "us-west-2"
     );
 }
 
+#[test]
+fn test_deserialize_provider_key_source_config() {
+    let provider_toml = r#"
+name = "Corp"
+base_url = "https://llm.corp.example.com/v1"
+key_command = ["vault", "kv", "get", "-field=key", "secret/llm"]
+        "#;
+
+    let provider: ModelProviderInfo = toml::from_str(provider_toml).unwrap();
+
+    assert_eq!(
+        provider.key_command,
+        Some(vec![
+            "vault".to_string(),
+            "kv".to_string(),
+            "get".to_string(),
+            "-field=key".to_string(),
+            "secret/llm".to_string(),
+        ])
+    );
+    assert_eq!(provider.key_file, None);
+}
+
+#[test]
+fn test_validate_provider_key_command_rejects_key_file() {
+    let provider = ModelProviderInfo {
+        key_command: Some(vec!["vault".to_string()]),
+        key_file: Some(PathBuf::from("/run/secrets/llm-key")),
+        ..ModelProviderInfo::default()
+    };
+
+    assert_eq!(
+        provider.validate(),
+        Err("provider key_command cannot be combined with key_file".to_string())
+    );
+}
+
+#[test]
+fn test_validate_provider_key_file_rejects_env_key() {
+    let provider = ModelProviderInfo {
+        key_file: Some(PathBuf::from("/run/secrets/llm-key")),
+        env_key: Some("CORP_API_KEY".to_string()),
+        ..ModelProviderInfo::default()
+    };
+
+    assert_eq!(
+        provider.validate(),
+        Err("provider key_file cannot be combined with env_key".to_string())
+    );
+}
+
+#[test]
+fn test_deserialize_provider_transport_overrides() {
+    let provider_toml = r#"
+name = "Corp"
+base_url = "https://llm.corp.example.com/v1"
+env_key = "CORP_API_KEY"
+https_proxy = "http://proxy.corp.example.com:3128"
+no_proxy = "localhost,.corp.example.com"
+ca_bundle_path = "/etc/ssl/corp-ca.pem"
+        "#;
+
+    let provider: ModelProviderInfo = toml::from_str(provider_toml).unwrap();
+
+    assert_eq!(
+        provider.https_proxy,
+        Some("http://proxy.corp.example.com:3128".to_string())
+    );
+    assert_eq!(
+        provider.no_proxy,
+        Some("localhost,.corp.example.com".to_string())
+    );
+    assert_eq!(
+        provider.ca_bundle_path,
+        Some(PathBuf::from("/etc/ssl/corp-ca.pem"))
+    );
+    assert!(!provider.insecure_skip_verify);
+    assert!(provider.has_transport_overrides());
+    assert!(provider.validate().is_ok());
+}
+
+#[test]
+fn test_validate_provider_no_proxy_requires_https_proxy() {
+    let provider = ModelProviderInfo {
+        no_proxy: Some("localhost".to_string()),
+        ..ModelProviderInfo::default()
+    };
+
+    assert_eq!(
+        provider.validate(),
+        Err("provider no_proxy requires https_proxy".to_string())
+    );
+}
+
+#[test]
+fn test_apply_transport_overrides_rejects_invalid_proxy_url() {
+    let provider = ModelProviderInfo {
+        https_proxy: Some("not a proxy url".to_string()),
+        ..ModelProviderInfo::default()
+    };
+
+    let error = provider
+        .apply_transport_overrides(reqwest::Client::builder())
+        .err()
+        .unwrap();
+    assert!(error.starts_with("provider https_proxy `not a proxy url` is invalid"));
+}
+
 #[test]
 fn test_create_amazon_bedrock_provider() {
     assert_eq!(
@@ -293,6 +516,13 @@ fn test_create_amazon_bedrock_provider() {
             env_key_instructions: None,
             experimental_bearer_token: None,
             auth: None,
+            oauth: None,
+            key_command: None,
+            key_file: None,
+            https_proxy: None,
+            no_proxy: None,
+            ca_bundle_path: None,
+            insecure_skip_verify: false,
             aws: Some(ModelProviderAwsAuthInfo {
                 profile: None,
                 region: None,
@@ -310,6 +540,8 @@ fn test_create_amazon_bedrock_provider() {
             websocket_connect_timeout_ms: None,
             requires_openai_auth: false,
             supports_websockets: false,
+            prompt_cache: None,
+            embedding_model: None,
         }
     );
 }
@@ -341,6 +573,36 @@ fn test_built_in_model_providers_include_amazon_bedrock() {
     );
 }
 
+#[test]
+fn test_built_in_model_providers_include_google_vertex() {
+    let providers = built_in_model_providers(/*openai_base_url*/ None);
+
+    let vertex = providers
+        .get(GOOGLE_VERTEX_PROVIDER_ID)
+        .expect("google-vertex provider should be built in");
+    assert_eq!(vertex.name, "Google Vertex AI");
+    assert!(vertex.auth.is_some(), "vertex should use command auth");
+}
+
+#[test]
+fn test_built_in_model_providers_include_hosted_api_key_providers() {
+    let providers = built_in_model_providers(/*openai_base_url*/ None);
+
+    for (id, env_key) in [
+        ("groq", "GROQ_API_KEY"),
+        ("mistral", "MISTRAL_API_KEY"),
+        ("deepseek", "DEEPSEEK_API_KEY"),
+        ("together", "TOGETHER_API_KEY"),
+    ] {
+        let provider = providers
+            .get(id)
+            .unwrap_or_else(|| panic!("{id} provider should be built in"));
+        assert_eq!(provider.env_key.as_deref(), Some(env_key));
+        assert!(provider.base_url.is_some());
+        assert!(provider.env_key_instructions.is_some());
+    }
+}
+
 #[test]
 fn test_merge_configured_model_providers_adds_custom_provider() {
     let custom_provider = ModelProviderInfo {
@@ -368,6 +630,13 @@ fn test_merge_configured_model_providers_applies_amazon_bedrock_profile_override
     let configured_model_providers = std::collections::HashMap::from([(
         AMAZON_BEDROCK_PROVIDER_ID.to_string(),
         ModelProviderInfo {
+            oauth: None,
+            key_command: None,
+            key_file: None,
+            https_proxy: None,
+            no_proxy: None,
+            ca_bundle_path: None,
+            insecure_skip_verify: false,
             aws: Some(ModelProviderAwsAuthInfo {
                 profile: Some("codex-bedrock".to_string()),
                 region: Some("us-west-2".to_string()),
@@ -400,6 +669,13 @@ fn test_merge_configured_model_providers_rejects_amazon_bedrock_non_default_fiel
         AMAZON_BEDROCK_PROVIDER_ID.to_string(),
         ModelProviderInfo {
             name: "Custom Bedrock".to_string(),
+            oauth: None,
+            key_command: None,
+            key_file: None,
+            https_proxy: None,
+            no_proxy: None,
+            ca_bundle_path: None,
+            insecure_skip_verify: false,
             aws: Some(ModelProviderAwsAuthInfo {
                 profile: Some("codex-bedrock".to_string()),
                 region: None,
@@ -425,6 +701,13 @@ fn test_merge_configured_model_providers_allows_amazon_bedrock_default_fields()
     let configured_model_providers = std::collections::HashMap::from([(
         AMAZON_BEDROCK_PROVIDER_ID.to_string(),
         ModelProviderInfo {
+            oauth: None,
+            key_command: None,
+            key_file: None,
+            https_proxy: None,
+            no_proxy: None,
+            ca_bundle_path: None,
+            insecure_skip_verify: false,
             aws: Some(ModelProviderAwsAuthInfo {
                 profile: None,
                 region: None,
@@ -446,6 +729,13 @@ fn test_merge_configured_model_providers_allows_amazon_bedrock_default_fields()
 #[test]
 fn test_validate_provider_aws_rejects_conflicting_auth() {
     let provider = ModelProviderInfo {
+        oauth: None,
+        key_command: None,
+        key_file: None,
+        https_proxy: None,
+        no_proxy: None,
+        ca_bundle_path: None,
+        insecure_skip_verify: false,
         aws: Some(ModelProviderAwsAuthInfo {
             profile: None,
             region: None,
@@ -464,6 +754,13 @@ fn test_validate_provider_aws_rejects_conflicting_auth() {
 #[test]
 fn test_validate_provider_aws_rejects_websockets() {
     let provider = ModelProviderInfo {
+        oauth: None,
+        key_command: None,
+        key_file: None,
+        https_proxy: None,
+        no_proxy: None,
+        ca_bundle_path: None,
+        insecure_skip_verify: false,
         aws: Some(ModelProviderAwsAuthInfo {
             profile: None,
             region: None,
@@ -10,6 +10,7 @@ use codex_api::SharedAuthProvider;
 use codex_login::AuthHeaders;
 use codex_login::AuthManager;
 use codex_login::CodexAuth;
+use codex_login::ProviderKeySource;
 use codex_login::auth::AgentIdentityAuth;
 use codex_login::auth::AgentIdentityAuthError;
 use codex_login::auth::AgentIdentityAuthPolicy;
@@ -163,13 +164,29 @@ pub fn unauthenticated_auth_provider() -> SharedAuthProvider {
     Arc::new(UnauthenticatedAuthProvider)
 }
 
-/// Returns the provider-scoped auth manager when this provider uses command-backed auth.
+/// Returns the provider-scoped auth manager when this provider uses
+/// device-flow OAuth, an out-of-band key source, or command-backed auth.
 ///
 /// Providers without custom auth continue using the caller-supplied base manager, when present.
 pub(crate) fn auth_manager_for_provider(
     auth_manager: Option<Arc<AuthManager>>,
     provider: &ModelProviderInfo,
 ) -> Option<Arc<AuthManager>> {
+    if let Some(oauth) = provider.oauth.clone()
+        && let Some(base) = auth_manager.as_deref()
+    {
+        return Some(base.provider_oauth_only(oauth));
+    }
+    if let Some(argv) = provider.key_command.clone() {
+        return Some(AuthManager::provider_key_only(ProviderKeySource::Command(
+            argv,
+        )));
+    }
+    if let Some(path) = provider.key_file.clone() {
+        return Some(AuthManager::provider_key_only(ProviderKeySource::File(
+            path,
+        )));
+    }
     match provider.auth.clone() {
         Some(config) => Some(AuthManager::external_bearer_only(config)),
         None => auth_manager,
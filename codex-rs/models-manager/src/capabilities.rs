@@ -0,0 +1,178 @@
+//! Capability table for model families reachable through custom providers.
+//!
+//! The remote models feed only describes first-party models; anything else
+//! falls back to one-size-fits-all metadata. This table corrects the fallback
+//! for known families so unsupported options are rejected or downgraded
+//! before the provider returns an opaque 400: images sent to a text-only
+//! model are stripped from history and `view_image` refuses with an
+//! actionable error, reasoning effort is dropped for models that do not
+//! accept it, and models without tool calling are rejected at config load.
+
+use codex_protocol::openai_models::InputModality;
+use codex_protocol::openai_models::ModelInfo;
+
+/// Capabilities of a known model family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelCapabilities {
+    /// Upper bound on the context window, in tokens.
+    pub max_context_window: i64,
+    /// Whether the model accepts image input.
+    pub supports_vision: bool,
+    /// Whether the model supports tool/function calling at all. Codex cannot
+    /// drive a model without it.
+    pub supports_tools: bool,
+    /// Whether the model accepts a reasoning-effort parameter.
+    pub supports_reasoning_effort: bool,
+    /// Whether the model may emit several tool calls in a single response.
+    pub supports_parallel_tool_calls: bool,
+}
+
+/// Longest-prefix table of model families commonly pointed at Codex through
+/// `model_providers` entries. First-party models come from the models feed
+/// and never consult this table.
+const KNOWN_MODEL_CAPABILITIES: &[(&str, ModelCapabilities)] = &[
+    (
+        "claude-",
+        ModelCapabilities {
+            max_context_window: 200_000,
+            supports_vision: true,
+            supports_tools: true,
+            supports_reasoning_effort: false,
+            supports_parallel_tool_calls: true,
+        },
+    ),
+    (
+        "gemini-",
+        ModelCapabilities {
+            max_context_window: 1_048_576,
+            supports_vision: true,
+            supports_tools: true,
+            supports_reasoning_effort: false,
+            supports_parallel_tool_calls: true,
+        },
+    ),
+    (
+        "gemini-embedding",
+        ModelCapabilities {
+            max_context_window: 8_192,
+            supports_vision: false,
+            supports_tools: false,
+            supports_reasoning_effort: false,
+            supports_parallel_tool_calls: false,
+        },
+    ),
+    (
+        "text-embedding-",
+        ModelCapabilities {
+            max_context_window: 8_192,
+            supports_vision: false,
+            supports_tools: false,
+            supports_reasoning_effort: false,
+            supports_parallel_tool_calls: false,
+        },
+    ),
+    (
+        "deepseek-",
+        ModelCapabilities {
+            max_context_window: 131_072,
+            supports_vision: false,
+            supports_tools: true,
+            supports_reasoning_effort: false,
+            supports_parallel_tool_calls: false,
+        },
+    ),
+    (
+        "llama-",
+        ModelCapabilities {
+            max_context_window: 131_072,
+            supports_vision: false,
+            supports_tools: true,
+            supports_reasoning_effort: false,
+            supports_parallel_tool_calls: true,
+        },
+    ),
+    (
+        "qwen",
+        ModelCapabilities {
+            max_context_window: 131_072,
+            supports_vision: false,
+            supports_tools: true,
+            supports_reasoning_effort: false,
+            supports_parallel_tool_calls: true,
+        },
+    ),
+    (
+        "mistral-",
+        ModelCapabilities {
+            max_context_window: 131_072,
+            supports_vision: false,
+            supports_tools: true,
+            supports_reasoning_effort: false,
+            supports_parallel_tool_calls: true,
+        },
+    ),
+];
+
+/// Looks up capabilities for `model` by longest matching prefix. Accepts a
+/// single namespaced slug like `my-proxy/claude-opus-4` the same way fallback
+/// metadata resolution does.
+pub fn known_model_capabilities(model: &str) -> Option<&'static ModelCapabilities> {
+    lookup_by_longest_prefix(model).or_else(|| {
+        let (namespace, suffix) = model.split_once('/')?;
+        if namespace.is_empty() || suffix.contains('/') {
+            return None;
+        }
+        lookup_by_longest_prefix(suffix)
+    })
+}
+
+fn lookup_by_longest_prefix(model: &str) -> Option<&'static ModelCapabilities> {
+    let mut best: Option<(&str, &'static ModelCapabilities)> = None;
+    for (prefix, capabilities) in KNOWN_MODEL_CAPABILITIES {
+        if !model.starts_with(prefix) {
+            continue;
+        }
+        let is_better_match = best.is_none_or(|(current, _)| prefix.len() > current.len());
+        if is_better_match {
+            best = Some((prefix, capabilities));
+        }
+    }
+    best.map(|(_, capabilities)| capabilities)
+}
+
+/// Returns an actionable error when `model` is known to be unable to drive
+/// Codex at all (no tool/function calling support, e.g. embedding models).
+pub fn validate_model_supported(model: &str) -> Result<(), String> {
+    match known_model_capabilities(model) {
+        Some(capabilities) if !capabilities.supports_tools => Err(format!(
+            "model `{model}` does not support tool calling, which Codex requires; \
+             choose a tool-capable model"
+        )),
+        _ => Ok(()),
+    }
+}
+
+impl ModelCapabilities {
+    /// Folds these capabilities into fallback metadata so the existing
+    /// downgrade paths (image stripping, reasoning-effort selection,
+    /// parallel tool calls) see accurate limits for this family.
+    pub(crate) fn apply_to(&self, model: &mut ModelInfo) {
+        model.context_window = Some(self.max_context_window);
+        model.max_context_window = Some(self.max_context_window);
+        model.input_modalities = if self.supports_vision {
+            vec![InputModality::Text, InputModality::Image]
+        } else {
+            vec![InputModality::Text]
+        };
+        model.supports_parallel_tool_calls = self.supports_parallel_tool_calls;
+        if !self.supports_reasoning_effort {
+            model.default_reasoning_level = None;
+            model.supported_reasoning_levels.clear();
+            model.supports_reasoning_summary_parameter = false;
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "capabilities_tests.rs"]
+mod tests;
@@ -0,0 +1,60 @@
+use super::*;
+use crate::model_info::model_info_from_slug;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn longest_prefix_wins_over_shorter_family_entry() {
+    let capabilities =
+        known_model_capabilities("gemini-embedding-001").expect("embedding family should be known");
+
+    assert!(!capabilities.supports_tools);
+    assert_eq!(capabilities.max_context_window, 8_192);
+}
+
+#[test]
+fn namespaced_slug_resolves_to_family_capabilities() {
+    let capabilities =
+        known_model_capabilities("my-proxy/claude-opus-4").expect("claude family should be known");
+
+    assert!(capabilities.supports_vision);
+    assert_eq!(capabilities.max_context_window, 200_000);
+}
+
+#[test]
+fn unknown_model_has_no_capabilities() {
+    assert_eq!(known_model_capabilities("totally-made-up-model"), None);
+    assert_eq!(known_model_capabilities("a/b/c"), None);
+}
+
+#[test]
+fn validate_rejects_tool_free_models_with_actionable_error() {
+    let err = validate_model_supported("text-embedding-3-large")
+        .expect_err("embedding models cannot drive Codex");
+
+    assert!(err.contains("does not support tool calling"));
+    assert_eq!(validate_model_supported("claude-sonnet-4-5"), Ok(()));
+    assert_eq!(validate_model_supported("some-unknown-model"), Ok(()));
+}
+
+#[test]
+fn fallback_metadata_applies_text_only_capabilities() {
+    let model = model_info_from_slug("deepseek-chat");
+
+    assert!(model.used_fallback_model_metadata);
+    assert_eq!(model.input_modalities, vec![InputModality::Text]);
+    assert_eq!(model.context_window, Some(131_072));
+    assert!(!model.supports_parallel_tool_calls);
+    assert!(!model.supports_reasoning_summary_parameter);
+}
+
+#[test]
+fn fallback_metadata_applies_vision_capabilities() {
+    let model = model_info_from_slug("claude-sonnet-4-5");
+
+    assert_eq!(
+        model.input_modalities,
+        vec![InputModality::Text, InputModality::Image]
+    );
+    assert_eq!(model.context_window, Some(200_000));
+    assert!(model.supports_parallel_tool_calls);
+}
@@ -1,4 +1,5 @@
 pub(crate) mod cache;
+pub mod capabilities;
 pub mod collaboration_mode_presets;
 pub(crate) mod config;
 pub mod manager;
@@ -121,7 +121,7 @@ fn clear_instruction_messages(model: &mut ModelInfo) {
 /// Build a minimal fallback model descriptor for missing/unknown slugs.
 pub fn model_info_from_slug(slug: &str) -> ModelInfo {
     warn!("Unknown model {slug} is used. This will use fallback model metadata.");
-    ModelInfo {
+    let mut model_info = ModelInfo {
         slug: slug.to_string(),
         display_name: slug.to_string(),
         description: None,
@@ -161,7 +161,11 @@ pub fn model_info_from_slug(slug: &str) -> ModelInfo {
         auto_review_model_override: None,
         tool_mode: None,
         multi_agent_version: None,
+    };
+    if let Some(capabilities) = crate::capabilities::known_model_capabilities(slug) {
+        capabilities.apply_to(&mut model_info);
     }
+    model_info
 }
 
 fn local_personality_messages_for_slug(slug: &str) -> Option<ModelMessages> {
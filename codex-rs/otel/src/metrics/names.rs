@@ -28,6 +28,9 @@ pub const TURN_NETWORK_PROXY_METRIC: &str = "codex.turn.network_proxy";
 pub const TURN_MEMORY_METRIC: &str = "codex.turn.memory";
 pub const TURN_TOOL_CALL_METRIC: &str = "codex.turn.tool.call";
 pub const TURN_TOKEN_USAGE_METRIC: &str = "codex.turn.token_usage";
+pub const MODEL_STREAM_TTFT_DURATION_METRIC: &str = "codex.model_stream.ttft.duration_ms";
+pub const MODEL_STREAM_TOKENS_PER_SEC_METRIC: &str = "codex.model_stream.tokens_per_sec";
+pub const MODEL_STREAM_STALL_COUNT_METRIC: &str = "codex.model_stream.stall";
 pub const GUARDIAN_REVIEW_COUNT_METRIC: &str = "codex.guardian.review";
 pub const GUARDIAN_REVIEW_DURATION_METRIC: &str = "codex.guardian.review.duration_ms";
 pub const GUARDIAN_REVIEW_TTFT_DURATION_METRIC: &str = "codex.guardian.review.ttft.duration_ms";
@@ -500,6 +500,28 @@ pub enum ForcedLoginMethod {
 const DEFAULT_PROVIDER_AUTH_TIMEOUT_MS: u64 = 5_000;
 const DEFAULT_PROVIDER_AUTH_REFRESH_INTERVAL_MS: u64 = 300_000;
 
+/// Configuration for logging into a provider via the OAuth2 device
+/// authorization grant (RFC 8628).
+///
+/// Tokens obtained through `codex login --provider <id>` are stored in
+/// `auth.json` keyed by `client_id` and refreshed automatically.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ModelProviderOAuthInfo {
+    /// Device authorization endpoint, e.g. `https://example.com/oauth/device/code`.
+    pub auth_url: String,
+
+    /// Token endpoint used for the device-code grant and refreshes.
+    pub token_url: String,
+
+    /// OAuth client identifier registered for Codex with this provider.
+    pub client_id: String,
+
+    /// Scopes requested during the device authorization.
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
 /// Configuration for obtaining a provider bearer token from a command.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema)]
 #[schemars(deny_unknown_fields)]
@@ -527,6 +549,18 @@ pub struct ModelProviderAuthInfo {
 }
 
 impl ModelProviderAuthInfo {
+    /// Token command with default timeout, refresh interval, and cwd. Used by
+    /// built-in providers whose bearer token comes from an external CLI.
+    pub fn for_command(command: impl Into<String>, args: Vec<String>) -> Self {
+        Self {
+            command: command.into(),
+            args,
+            timeout_ms: default_provider_auth_timeout_ms(),
+            refresh_interval_ms: default_provider_auth_refresh_interval_ms(),
+            cwd: default_provider_auth_cwd(),
+        }
+    }
+
     pub fn timeout(&self) -> Duration {
         Duration::from_millis(self.timeout_ms.get())
     }
@@ -855,6 +855,11 @@ pub enum ContentItem {
     OutputText {
         text: String,
     },
+    /// Refusal output item from the Responses API
+    /// (`{"type": "refusal", "refusal": "..."}`).
+    Refusal {
+        refusal: String,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema, TS)]
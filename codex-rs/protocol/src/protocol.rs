@@ -484,6 +484,12 @@ pub struct ThreadSettingsOverrides {
     /// Updated model slug. When set, the model info is derived automatically.
     pub model: Option<String>,
 
+    /// Updated model provider id. When set, subsequent turns are sent to the
+    /// new provider's endpoint while reusing the existing conversation
+    /// history. The id must name an entry in the configured `model_providers`
+    /// map.
+    pub model_provider: Option<String>,
+
     /// Updated reasoning effort (honored only for reasoning-capable models).
     ///
     /// Use `Some(Some(_))` to set a specific effort, `Some(None)` to clear the
@@ -1303,6 +1309,10 @@ pub enum EventMsg {
     /// Model routing changed from the requested model to a different model.
     ModelReroute(ModelRerouteEvent),
 
+    /// The response was classified as a refusal and the turn was rerouted to
+    /// a fallback model.
+    FallbackTriggered(FallbackTriggeredEvent),
+
     /// Backend recommends additional account verification for this turn.
     ModelVerification(ModelVerificationEvent),
 
@@ -1923,6 +1933,15 @@ pub struct ModelRerouteEvent {
     pub reason: ModelRerouteReason,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct FallbackTriggeredEvent {
+    pub from_model: String,
+    pub to_model: String,
+    /// Human-readable explanation of why the fallback fired, e.g. the
+    /// refusal phrase that matched.
+    pub reason: String,
+}
+
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
 #[serde(rename_all = "snake_case")]
 #[ts(rename_all = "snake_case")]
@@ -2107,6 +2126,23 @@ impl TokenUsageInfo {
 pub struct TokenCountEvent {
     pub info: Option<TokenUsageInfo>,
     pub rate_limits: Option<RateLimitSnapshot>,
+    /// Attribution and estimated spend for the session so far; `None` when
+    /// the producer predates cost accounting or no usage was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<TokenCostInfo>,
+}
+
+/// Per-session provider/model attribution and estimated cost, priced from
+/// the bundled rate table (overridable via `[model_rates]` in config.toml).
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct TokenCostInfo {
+    /// Provider the session's turns were billed against.
+    pub provider_id: String,
+    /// Model slug used for pricing.
+    pub model: String,
+    /// Estimated dollars for the session so far; `None` when no rate is
+    /// known for the model.
+    pub estimated_cost_usd: Option<f64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
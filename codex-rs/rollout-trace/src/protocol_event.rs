@@ -350,6 +350,7 @@ pub(crate) fn tool_runtime_trace_event(event: &EventMsg) -> Option<ToolRuntimeTr
         | EventMsg::RealtimeConversationClosed(_)
         | EventMsg::RealtimeConversationSdp(_)
         | EventMsg::ModelReroute(_)
+        | EventMsg::FallbackTriggered(_)
         | EventMsg::ModelVerification(_)
         | EventMsg::TurnModerationMetadata(_)
         | EventMsg::ContextCompacted(_)
@@ -425,6 +426,7 @@ pub(crate) fn wrapped_protocol_event_type(event: &EventMsg) -> Option<&'static s
         | EventMsg::RealtimeConversationClosed(_)
         | EventMsg::RealtimeConversationSdp(_)
         | EventMsg::ModelReroute(_)
+        | EventMsg::FallbackTriggered(_)
         | EventMsg::ModelVerification(_)
         | EventMsg::TurnModerationMetadata(_)
         | EventMsg::ContextCompacted(_)
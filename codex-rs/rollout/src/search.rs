@@ -292,7 +292,9 @@ fn conversation_text_from_item(item: &RolloutItem) -> Option<String> {
 
 fn content_item_text(item: &ContentItem) -> Option<&str> {
     match item {
-        ContentItem::InputText { text } | ContentItem::OutputText { text } => Some(text.as_str()),
+        ContentItem::InputText { text }
+        | ContentItem::OutputText { text }
+        | ContentItem::Refusal { refusal: text } => Some(text.as_str()),
         ContentItem::InputImage { .. } => None,
     }
 }
@@ -2900,6 +2900,7 @@ mod tests {
                     model_context_window: None,
                 }),
                 rate_limits: None,
+                cost: None,
             },
         ))];
         let override_updated_at =
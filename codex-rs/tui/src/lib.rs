@@ -888,6 +888,11 @@ pub async fn run_main(
             .raw_overrides
             .push("trust_all_projects=true".to_string());
     }
+    if cli.offline {
+        cli.config_overrides
+            .raw_overrides
+            .push("offline=true".to_string());
+    }
 
     // When using `--oss`, let the bootstrapper pick the model (defaulting to
     // gpt-oss:20b) and ensure it is present locally. Also, force the built‑in
@@ -4,8 +4,6 @@ use std::sync::LazyLock;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
 use crate::key_hint::KeyBindingListExt;
-use codex_model_provider_info::DEFAULT_LMSTUDIO_PORT;
-use codex_model_provider_info::DEFAULT_OLLAMA_PORT;
 use codex_model_provider_info::LMSTUDIO_OSS_PROVIDER_ID;
 use codex_model_provider_info::OLLAMA_OSS_PROVIDER_ID;
 use crossterm::event::Event;
@@ -38,7 +36,6 @@ use ratatui::widgets::Paragraph;
 use ratatui::widgets::Widget;
 use ratatui::widgets::WidgetRef;
 use ratatui::widgets::Wrap;
-use std::time::Duration;
 
 #[derive(Clone)]
 struct ProviderOption {
@@ -370,32 +367,18 @@ pub async fn select_oss_provider() -> io::Result<OssProviderSelection> {
 }
 
 async fn check_lmstudio_status() -> ProviderStatus {
-    match check_port_status(DEFAULT_LMSTUDIO_PORT).await {
-        Ok(true) => ProviderStatus::Running,
-        Ok(false) => ProviderStatus::NotRunning,
-        Err(_) => ProviderStatus::Unknown,
-    }
+    provider_status(codex_utils_oss::check_lmstudio_status().await)
 }
 
 async fn check_ollama_status() -> ProviderStatus {
-    match check_port_status(DEFAULT_OLLAMA_PORT).await {
-        Ok(true) => ProviderStatus::Running,
-        Ok(false) => ProviderStatus::NotRunning,
-        Err(_) => ProviderStatus::Unknown,
-    }
+    provider_status(codex_utils_oss::check_ollama_status().await)
 }
 
-async fn check_port_status(port: u16) -> io::Result<bool> {
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(2))
-        .build()
-        .map_err(io::Error::other)?;
-
-    let url = format!("http://localhost:{port}");
-
-    match client.get(&url).send().await {
-        Ok(response) => Ok(response.status().is_success()),
-        Err(_) => Ok(false), // Connection failed = not running
+fn provider_status(status: codex_utils_oss::LocalServerStatus) -> ProviderStatus {
+    match status {
+        codex_utils_oss::LocalServerStatus::Running => ProviderStatus::Running,
+        codex_utils_oss::LocalServerStatus::NotRunning => ProviderStatus::NotRunning,
+        codex_utils_oss::LocalServerStatus::Unknown => ProviderStatus::Unknown,
     }
 }
 
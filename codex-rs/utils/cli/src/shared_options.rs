@@ -72,6 +72,12 @@ pub struct SharedCliOptions {
     #[arg(long = "local-provider")]
     pub oss_provider: Option<String>,
 
+    /// Restrict model providers to local endpoints (ollama, lmstudio, or a
+    /// custom localhost base_url) and fail fast on anything that needs the
+    /// network.
+    #[arg(long = "offline", default_value_t = false)]
+    pub offline: bool,
+
     /// Layer $CODEX_HOME/<name>.config.toml on top of the base user config.
     #[arg(long = "profile", short = 'p')]
     pub config_profile_v2: Option<ProfileV2Name>,
@@ -139,6 +145,7 @@ impl SharedCliOptions {
             model,
             oss,
             oss_provider,
+            offline,
             config_profile_v2,
             sandbox_mode,
             dangerously_bypass_approvals_and_sandbox,
@@ -154,6 +161,7 @@ impl SharedCliOptions {
             model: root_model,
             oss: root_oss,
             oss_provider: root_oss_provider,
+            offline: root_offline,
             config_profile_v2: root_config_profile_v2,
             sandbox_mode: root_sandbox_mode,
             dangerously_bypass_approvals_and_sandbox: root_dangerously_bypass_approvals_and_sandbox,
@@ -174,6 +182,9 @@ impl SharedCliOptions {
         if oss_provider.is_none() {
             oss_provider.clone_from(root_oss_provider);
         }
+        if *root_offline {
+            *offline = true;
+        }
         if config_profile_v2.is_none() {
             config_profile_v2.clone_from(root_config_profile_v2);
         }
@@ -213,6 +224,7 @@ impl SharedCliOptions {
             model,
             oss,
             oss_provider,
+            offline,
             config_profile_v2,
             sandbox_mode,
             dangerously_bypass_approvals_and_sandbox,
@@ -233,6 +245,9 @@ impl SharedCliOptions {
         if let Some(oss_provider) = oss_provider {
             self.oss_provider = Some(oss_provider);
         }
+        if offline {
+            self.offline = true;
+        }
         if let Some(config_profile_v2) = config_profile_v2 {
             self.config_profile_v2 = Some(config_profile_v2);
         }
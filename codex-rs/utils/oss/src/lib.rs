@@ -1,8 +1,19 @@
 //! OSS provider utilities shared between TUI and exec.
 
 use codex_core::config::Config;
+use codex_model_provider_info::DEFAULT_LMSTUDIO_PORT;
+use codex_model_provider_info::DEFAULT_OLLAMA_PORT;
 use codex_model_provider_info::LMSTUDIO_OSS_PROVIDER_ID;
 use codex_model_provider_info::OLLAMA_OSS_PROVIDER_ID;
+use std::time::Duration;
+
+/// Result of probing a local OSS server's default port.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LocalServerStatus {
+    Running,
+    NotRunning,
+    Unknown,
+}
 
 /// Returns the default model for a given OSS provider.
 pub fn get_default_model_for_oss_provider(provider_id: &str) -> Option<&'static str> {
@@ -13,6 +24,48 @@ pub fn get_default_model_for_oss_provider(provider_id: &str) -> Option<&'static
     }
 }
 
+/// Probe whether an LM Studio server is listening on its default port.
+pub async fn check_lmstudio_status() -> LocalServerStatus {
+    check_port_status(DEFAULT_LMSTUDIO_PORT).await
+}
+
+/// Probe whether an Ollama server is listening on its default port.
+pub async fn check_ollama_status() -> LocalServerStatus {
+    check_port_status(DEFAULT_OLLAMA_PORT).await
+}
+
+/// Probe the default Ollama and LM Studio ports and return the provider id of
+/// the one server that is running, or `None` when neither (or both) respond.
+/// Callers that can prompt should fall back to an interactive selection; the
+/// ambiguous both-running case is left to the user on purpose.
+pub async fn discover_running_oss_provider() -> Option<&'static str> {
+    let (lmstudio, ollama) = tokio::join!(check_lmstudio_status(), check_ollama_status());
+    match (lmstudio, ollama) {
+        (LocalServerStatus::Running, LocalServerStatus::Running) => None,
+        (LocalServerStatus::Running, _) => Some(LMSTUDIO_OSS_PROVIDER_ID),
+        (_, LocalServerStatus::Running) => Some(OLLAMA_OSS_PROVIDER_ID),
+        _ => None,
+    }
+}
+
+async fn check_port_status(port: u16) -> LocalServerStatus {
+    let client = match reqwest::Client::builder()
+        .timeout(Duration::from_secs(2))
+        .build()
+    {
+        Ok(client) => client,
+        Err(_) => return LocalServerStatus::Unknown,
+    };
+
+    let url = format!("http://localhost:{port}");
+    match client.get(&url).send().await {
+        Ok(response) if response.status().is_success() => LocalServerStatus::Running,
+        Ok(_) => LocalServerStatus::NotRunning,
+        // Connection refused means nothing is listening on the port.
+        Err(_) => LocalServerStatus::NotRunning,
+    }
+}
+
 /// Ensures the specified OSS provider is ready (models downloaded, service reachable).
 pub async fn ensure_oss_provider_ready(
     provider_id: &str,